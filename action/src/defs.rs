@@ -1,5 +1,6 @@
 pub mod agent;
 pub mod app;
+pub mod buffer_group;
 pub mod commits;
 pub mod conflict;
 pub mod dump;
@@ -17,5 +18,7 @@ pub mod review;
 pub mod run;
 pub mod set_theme;
 pub mod tab;
+pub mod task;
 pub mod terminal;
+pub mod user_command;
 pub mod workspace;
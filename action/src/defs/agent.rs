@@ -10,3 +10,36 @@ define_action!(
     ActionPriority::Common,
     command_name = "claude"
 );
+
+define_action!(
+    AgentPermissionAllowDef,
+    AgentPermissionAllow,
+    "AgentPermissionAllow",
+    ActionKind::AgentPermissionAllow,
+    "allow the agent's tool call once",
+    "Allow the tool call the owned agent is waiting on, asking again next time it requests the same tool.",
+    ActionPriority::Common,
+    palette_visible = false
+);
+
+define_action!(
+    AgentPermissionAlwaysDef,
+    AgentPermissionAlways,
+    "AgentPermissionAlways",
+    ActionKind::AgentPermissionAlways,
+    "always allow the agent's tool",
+    "Allow the tool call the owned agent is waiting on and remember the tool for this workspace, so later calls to it run without a prompt.",
+    ActionPriority::Common,
+    palette_visible = false
+);
+
+define_action!(
+    AgentPermissionDenyDef,
+    AgentPermissionDeny,
+    "AgentPermissionDeny",
+    ActionKind::AgentPermissionDeny,
+    "deny the agent's tool call",
+    "Deny the tool call the owned agent is waiting on. The agent is told the call was blocked.",
+    ActionPriority::Common,
+    palette_visible = false
+);
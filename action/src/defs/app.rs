@@ -1,4 +1,8 @@
-use crate::{action::define_action, ActionKind, ActionPriority};
+use crate::{
+    action::define_action, Action, ActionDef, ActionKind, ActionPriority, ParamDef, ParamKind,
+    ValueSource,
+};
+use std::any::Any;
 
 define_action!(
     QuitDef,
@@ -66,10 +70,171 @@ define_action!(
     command_name = "logs"
 );
 
+const SET_LOG_LEVEL_PARAMS: &[ParamDef] = &[ParamDef {
+    name: "level",
+    kind: ParamKind::String,
+    value_source: ValueSource::Values(&["error", "warn", "info", "debug", "trace", "reset"]),
+    required: true,
+    description: "A level for the stoat crates, `target=level` pairs separated by commas, or reset",
+}];
+
+#[derive(Debug)]
+pub struct SetLogLevelDef;
+
+impl ActionDef for SetLogLevelDef {
+    fn name(&self) -> &'static str {
+        "SetLogLevel"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("log-level")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::SetLogLevel
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        SET_LOG_LEVEL_PARAMS
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "change the log level without restarting"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Change the running log filter. A bare level such as debug applies to the stoat crates, `stoat::lsp=trace` overrides one target and keeps earlier overrides, and reset restores the filter the session started with. Shows the resulting filter in the status line."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Normal
+    }
+}
+
+#[derive(Debug)]
+pub struct SetLogLevel {
+    pub level: String,
+}
+
+impl SetLogLevel {
+    pub const DEF: &SetLogLevelDef = &SetLogLevelDef;
+}
+
+impl Action for SetLogLevel {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+define_action!(
+    RunToastActionDef,
+    RunToastAction,
+    "RunToastAction",
+    ActionKind::RunToastAction,
+    "run the notification's action",
+    "Run the action offered by the newest visible notification that has one, such as opening the log after a language server fails, and dismiss that notification.",
+    ActionPriority::Normal
+);
+
+define_action!(
+    DismissToastsDef,
+    DismissToasts,
+    "DismissToasts",
+    ActionKind::DismissToasts,
+    "dismiss notifications",
+    "Dismiss every visible notification before its timer runs out. They stay in the notification history.",
+    ActionPriority::Normal
+);
+
+define_action!(
+    TogglePerfOverlayDef,
+    TogglePerfOverlay,
+    "TogglePerfOverlay",
+    ActionKind::TogglePerfOverlay,
+    "toggle the perf overlay",
+    "Show or hide a box in the bottom-right corner listing the last, median, 95th percentile, and worst frame time, its layout and paint steps, event handling time, and editor lines painted. Needs a build with the perf feature; otherwise reports that in the status line.",
+    ActionPriority::Rare,
+    command_name = "perf"
+);
+
+define_action!(
+    ToggleProfileCaptureDef,
+    ToggleProfileCapture,
+    "ToggleProfileCapture",
+    ActionKind::ToggleProfileCapture,
+    "start or stop a profile capture",
+    "Start recording every update, layout, paint, and frame as a trace span. Running it again stops the capture and writes the interval as a Chrome trace JSON file in the log directory, loadable in chrome://tracing or Perfetto. Needs a build with the perf feature.",
+    ActionPriority::Rare,
+    command_name = "profile"
+);
+
+const NOTIFICATION_HISTORY_PARAMS: &[ParamDef] = &[ParamDef {
+    name: "notification",
+    kind: ParamKind::String,
+    value_source: ValueSource::Notifications,
+    required: true,
+    description: "A notification from this session, as the history lists it.",
+}];
+
+#[derive(Debug)]
+pub struct NotificationHistoryDef;
+
+impl ActionDef for NotificationHistoryDef {
+    fn name(&self) -> &'static str {
+        "NotificationHistory"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("notifications")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::NotificationHistory
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        NOTIFICATION_HISTORY_PARAMS
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "browse past notifications"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "List the notifications raised this session, newest first, and show the picked one again along with its action."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Normal
+    }
+}
+
+#[derive(Debug)]
+pub struct NotificationHistory {
+    pub notification: String,
+}
+
+impl NotificationHistory {
+    pub const DEF: &NotificationHistoryDef = &NotificationHistoryDef;
+}
+
+impl Action for NotificationHistory {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Action;
 
     #[test]
     fn quit() {
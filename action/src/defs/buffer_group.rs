@@ -0,0 +1,138 @@
+use crate::{
+    action::define_action, Action, ActionDef, ActionKind, ActionPriority, ParamDef, ParamKind,
+    ValueSource,
+};
+use std::any::Any;
+
+define_action!(
+    TogglePinBufferDef,
+    TogglePinBuffer,
+    "TogglePinBuffer",
+    ActionKind::TogglePinBuffer,
+    "pin or unpin the focused buffer",
+    "Toggle the pin on the focused buffer. Pinned buffers sort first in the buffer finder and stay open through CloseOtherBuffers and buffer group switches.",
+    ActionPriority::Normal
+);
+
+define_action!(
+    CloseOtherBuffersDef,
+    CloseOtherBuffers,
+    "CloseOtherBuffers",
+    ActionKind::CloseOtherBuffers,
+    "close every unpinned buffer but the focused one",
+    "Close each file buffer other than the focused one that is not pinned, with CloseBuffer semantics. Dirty buffers are left open and counted in the status message.",
+    ActionPriority::Normal
+);
+
+const GROUP_NAME_PARAMS: &[ParamDef] = &[ParamDef {
+    name: "name",
+    kind: ParamKind::String,
+    value_source: ValueSource::BufferGroups,
+    required: true,
+    description:
+        "Name of the buffer group. Adding to a name that does not exist yet creates the group.",
+}];
+
+#[derive(Debug)]
+pub struct AddBufferToGroupDef;
+
+impl ActionDef for AddBufferToGroupDef {
+    fn name(&self) -> &'static str {
+        "AddBufferToGroup"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("group-add")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::AddBufferToGroup
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        GROUP_NAME_PARAMS
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "add the focused buffer to a named group"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Record the focused buffer's file in the named buffer group, creating the group. Groups persist with the workspace and are switched as a unit with SwitchBufferGroup. Scratch buffers have no file and cannot be grouped."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Normal
+    }
+}
+
+#[derive(Debug)]
+pub struct AddBufferToGroup {
+    pub name: String,
+}
+
+impl AddBufferToGroup {
+    pub const DEF: &AddBufferToGroupDef = &AddBufferToGroupDef;
+}
+
+impl Action for AddBufferToGroup {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct SwitchBufferGroupDef;
+
+impl ActionDef for SwitchBufferGroupDef {
+    fn name(&self) -> &'static str {
+        "SwitchBufferGroup"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("group")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::SwitchBufferGroup
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        GROUP_NAME_PARAMS
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "switch to a named buffer group"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Open every file of the named buffer group, focusing its first member, then close the other file buffers that are neither pinned nor dirty. Shows a message when the group does not exist."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Normal
+    }
+}
+
+#[derive(Debug)]
+pub struct SwitchBufferGroup {
+    pub name: String,
+}
+
+impl SwitchBufferGroup {
+    pub const DEF: &SwitchBufferGroupDef = &SwitchBufferGroupDef;
+}
+
+impl Action for SwitchBufferGroup {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
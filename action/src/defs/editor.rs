@@ -96,7 +96,7 @@ define_action!(
     "Undo",
     ActionKind::Undo,
     "undo last edit",
-    "Reverse the most recent edit on the focused buffer. Repeat to walk further back through edit history; no-ops once history is empty. Anchor-based selections re-validate against the post-undo snapshot.",
+    "Reverse the most recent edit on the focused buffer, stepping to the parent state in its undo tree. Repeat to walk further back through edit history; no-ops once history is empty. Anchor-based selections re-validate against the post-undo snapshot.",
     ActionPriority::Common
 );
 
@@ -106,7 +106,7 @@ define_action!(
     "Redo",
     ActionKind::Redo,
     "redo last undone edit",
-    "Re-apply the most recently undone edit on the focused buffer. Repeat to walk forward through the redo stack; no-ops once it is empty. Any new edit clears the redo stack per standard undo/redo semantics.",
+    "Re-apply the most recently undone edit on the focused buffer. Repeat to walk forward along the branch last undone; no-ops at the branch tip. A new edit after an undo starts a new branch, and the undone one stays reachable through OpenUndoTree.",
     ActionPriority::Common
 );
 
@@ -150,6 +150,46 @@ define_action!(
     ActionPriority::Rare
 );
 
+define_action!(
+    DuplicateAndCommentOriginalDef,
+    DuplicateAndCommentOriginal,
+    "DuplicateAndCommentOriginal",
+    ActionKind::DuplicateAndCommentOriginal,
+    "duplicate lines, comment out the original",
+    "Copy every line touched by any selection and keep the copy live below a commented-out original, as one undo step. Selections stay on the live copy, so editing continues where it was while the old version remains visible above. Blank lines in the original block are copied uncommented. Selections on adjacent or overlapping lines share one block. Buffers whose language has no `line_comment` are a no-op.",
+    ActionPriority::Rare
+);
+
+define_action!(
+    DuplicateSelectionDef,
+    DuplicateSelection,
+    "DuplicateSelection",
+    ActionKind::DuplicateSelection,
+    "duplicate selection",
+    "Duplicate each selection, as one undo step. A bare cursor or a selection covering whole lines duplicates every line it touches; any other selection duplicates just its text. The copy is placed before the original and selections stay on the second copy, so repeating the action keeps stacking copies. A count repeats it.",
+    ActionPriority::Rare
+);
+
+define_action!(
+    MoveLinesUpDef,
+    MoveLinesUp,
+    "MoveLinesUp",
+    ActionKind::MoveLinesUp,
+    "move lines up",
+    "Move every line touched by any selection up by the count (default one), as one undo step. Selections stay on the moved text. Selections on adjacent or overlapping lines move as one block, and a block at the top of the buffer stays put. When the move carries lines into or out of a syntax block, they are re-indented by the difference in depth while keeping their relative indentation.",
+    ActionPriority::Rare
+);
+
+define_action!(
+    MoveLinesDownDef,
+    MoveLinesDown,
+    "MoveLinesDown",
+    ActionKind::MoveLinesDown,
+    "move lines down",
+    "Move every line touched by any selection down by the count (default one), as one undo step. Selections stay on the moved text. Selections on adjacent or overlapping lines move as one block, and a block at the bottom of the buffer stays put. When the move carries lines into or out of a syntax block, they are re-indented by the difference in depth while keeping their relative indentation.",
+    ActionPriority::Rare
+);
+
 define_action!(
     ToggleSyntaxHighlightDef,
     ToggleSyntaxHighlight,
@@ -183,6 +223,28 @@ define_action!(
     command_name = "inlay-hints"
 );
 
+define_action!(
+    SetLineEndingLfDef,
+    SetLineEndingLf,
+    "SetLineEndingLf",
+    ActionKind::SetLineEndingLf,
+    "convert line endings to LF",
+    "Save the focused buffer with Unix LF line endings from now on. The text itself is unchanged, so undo history is kept, but the buffer reads as modified until saved. A buffer on mixed line endings also has the carriage return of each CRLF break removed, as one undo step. No-op when the buffer already uses LF or is read-only.",
+    ActionPriority::Rare,
+    command_name = "line-ending-lf"
+);
+
+define_action!(
+    SetLineEndingCrlfDef,
+    SetLineEndingCrlf,
+    "SetLineEndingCrlf",
+    ActionKind::SetLineEndingCrlf,
+    "convert line endings to CRLF",
+    "Save the focused buffer with Windows CRLF line endings from now on. The text itself is unchanged, so undo history is kept, but the buffer reads as modified until saved. A buffer on mixed line endings is first brought to LF breaks, as one undo step. The status bar shows CRLF while it applies. No-op when the buffer already uses CRLF or is read-only.",
+    ActionPriority::Rare,
+    command_name = "line-ending-crlf"
+);
+
 define_action!(
     MoveLeftDef,
     MoveLeft,
@@ -564,6 +626,17 @@ define_action!(
     command_name = "jumplist"
 );
 
+define_action!(
+    OpenUndoTreeDef,
+    OpenUndoTree,
+    "OpenUndoTree",
+    ActionKind::OpenUndoTree,
+    "open undo tree",
+    "Open a modal drawing the focused buffer's branching undo history, one row per undo step with its line and inserted/deleted byte counts. Older branches indent under the step they split from. Enter restores the buffer to the selected state. Esc cancels.",
+    ActionPriority::Rare,
+    command_name = "undotree"
+);
+
 define_action!(
     OpenLastPickerDef,
     OpenLastPicker,
@@ -585,6 +658,16 @@ define_action!(
     command_name = "search"
 );
 
+define_action!(
+    OpenRegexPlaygroundDef,
+    OpenRegexPlayground,
+    "OpenRegexPlayground",
+    ActionKind::OpenRegexPlayground,
+    "open regex playground",
+    "Open a regex tester in a split pane beside the current one, with a pattern input over a sample text pane. Matches are highlighted in the sample as the pattern is typed, using the same regex engine as search, and a side list breaks each match into its capture groups. The pattern starts from the last search and the sample from the current selection. Tab switches between the pattern and the sample. Enter on the pattern runs it as a workspace code search. Esc closes the pane.",
+    ActionPriority::Rare
+);
+
 define_action!(
     SplitSelectionDef,
     SplitSelection,
@@ -695,6 +778,46 @@ define_action!(
     ActionPriority::Common
 );
 
+define_action!(
+    RunCommandDef,
+    RunCommand,
+    "RunCommand",
+    ActionKind::RunCommand,
+    "run a shell command into a scratch buffer",
+    "Open an input modal for a shell command. On submit, open a read-only scratch buffer in a split and run the command in the background with empty stdin, streaming its stdout and stderr into the buffer as it runs.",
+    ActionPriority::Common
+);
+
+define_action!(
+    RunCommandWithSelectionDef,
+    RunCommandWithSelection,
+    "RunCommandWithSelection",
+    ActionKind::RunCommandWithSelection,
+    "run a shell command on the selection into a scratch buffer",
+    "Like `RunCommand`, but the primary selection's text is piped to the command as stdin.",
+    ActionPriority::Normal
+);
+
+define_action!(
+    RerunCommandDef,
+    RerunCommand,
+    "RerunCommand",
+    ActionKind::RerunCommand,
+    "re-run the command of the focused output buffer",
+    "Run the command that produced the focused command-output buffer again, with the same stdin, replacing the buffer's content with the new output.",
+    ActionPriority::Normal
+);
+
+define_action!(
+    InsertCommandOutputDef,
+    InsertCommandOutput,
+    "InsertCommandOutput",
+    ActionKind::InsertCommandOutput,
+    "insert command output into the source buffer",
+    "Insert the focused command-output buffer's last stdout at every cursor of the editor the command was run from.",
+    ActionPriority::Normal
+);
+
 define_action!(
     SaveBufferDef,
     SaveBuffer,
@@ -884,7 +1007,7 @@ define_action!(
     "SurroundAdd",
     ActionKind::SurroundAdd,
     "surround selection with pair",
-    "Wait for the next char keypress, then wrap every non-empty selection with the matching pair: bracket-like opens/closes (`(`/`)`, `[`/`]`, `{`/`}`, `<`/`>`) wrap with the canonical open and close; quote-like chars (`\"`, `'`, `` ` ``) wrap with the same char on both sides; any other printable char wraps with that char on both sides. Empty (collapsed) selections are skipped.",
+    "Wait for the next char keypress, then wrap every non-empty selection with the matching pair: bracket-like opens/closes (`(`/`)`, `[`/`]`, `{`/`}`, `<`/`>`) wrap with the canonical open and close; quote-like chars (`\"`, `'`, `` ` ``) wrap with the same char on both sides; `t` prompts for a tag and wraps with `<tag attrs>` and `</tag>`; any other printable char wraps with that char on both sides. Empty (collapsed) selections are skipped.",
     ActionPriority::Rare
 );
 
@@ -894,7 +1017,7 @@ define_action!(
     "SurroundReplace",
     ActionKind::SurroundReplace,
     "replace surrounding pair",
-    "Wait for two char keypresses (`from` then `to`), then for every selection's cursor find the nearest enclosing `from` pair and replace its open/close with the canonical pair for `to`. Bracket-like chars use canonical opens/closes; symmetric chars (quotes, etc.) use the same char on both sides. No-op when the cursor is not enclosed by a `from` pair.",
+    "Wait for two char keypresses (`from` then `to`), then for every selection's cursor find the nearest enclosing `from` pair and replace its open/close with the canonical pair for `to`. Bracket-like chars use canonical opens/closes; symmetric chars (quotes, etc.) use the same char on both sides. A `from` of `t` matches the nearest enclosing HTML/XML tag and `m` the nearest pair of any type. A `to` of `t` prompts for a tag; replacing a tag with a bare tag name renames it and keeps its attributes. Brackets, quotes and tags inside strings or comments are ignored when the buffer is parsed. No-op when the cursor is not enclosed by a `from` pair.",
     ActionPriority::Rare
 );
 
@@ -904,7 +1027,7 @@ define_action!(
     "SurroundDelete",
     ActionKind::SurroundDelete,
     "delete surrounding pair",
-    "Wait for the next char keypress, then for every selection's cursor find the nearest enclosing pair for that char and delete the open/close chars, leaving the inner content. No-op when the cursor is not enclosed by such a pair.",
+    "Wait for the next char keypress, then for every selection's cursor find the nearest enclosing pair for that char and delete the open/close chars, leaving the inner content. `t` deletes the nearest enclosing HTML/XML tag's opening and closing tags and `m` the nearest pair of any type. No-op when the cursor is not enclosed by such a pair.",
     ActionPriority::Rare
 );
 
@@ -1008,6 +1131,28 @@ define_action!(
     ActionPriority::Common
 );
 
+define_action!(
+    SearchHistoryPrevDef,
+    SearchHistoryPrev,
+    "SearchHistoryPrev",
+    ActionKind::SearchHistoryPrev,
+    "recall older search",
+    "Recall the previous query from this workspace's search history into the open search input, fish-style: the already-typed text is a substring needle that filters matches. Bound by default to Up while the search input is open.",
+    ActionPriority::Normal,
+    palette_visible = false
+);
+
+define_action!(
+    SearchHistoryNextDef,
+    SearchHistoryNext,
+    "SearchHistoryNext",
+    ActionKind::SearchHistoryNext,
+    "recall newer search",
+    "Recall the next query toward the newest in search history, under the same substring needle. Stepping past the newest restores the originally-typed text. Bound by default to Down while the search input is open.",
+    ActionPriority::Normal,
+    palette_visible = false
+);
+
 define_action!(
     YankDef,
     Yank,
@@ -1068,6 +1213,16 @@ define_action!(
     ActionPriority::Common
 );
 
+define_action!(
+    CutToClipboardDef,
+    CutToClipboard,
+    "CutToClipboard",
+    ActionKind::CutToClipboard,
+    "cut selections to system clipboard",
+    "Write every non-collapsed selection's content (joined by newlines in start-offset order) to the system clipboard via the active `ClipboardHost`, then delete the selections. The register store is left untouched, so the unnamed register keeps its previous content.",
+    ActionPriority::Common
+);
+
 define_action!(
     PasteClipboardAfterDef,
     PasteClipboardAfter,
@@ -1847,6 +2002,80 @@ define_action!(
     ActionPriority::Rare
 );
 
+define_action!(
+    EditColorAtCursorDef,
+    EditColorAtCursor,
+    "EditColorAtCursor",
+    ActionKind::EditColorAtCursor,
+    "edit the color under the cursor",
+    "Open a color picker seeded with the hex or rgb() color literal under \
+     the cursor, previewing the typed value. Submitting replaces the literal; \
+     no-op when the cursor is not on a color.",
+    ActionPriority::Rare
+);
+
+define_action!(
+    RenameLocalDef,
+    RenameLocal,
+    "RenameLocal",
+    ActionKind::RenameLocal,
+    "rename the local binding under the cursor",
+    "Open a rename prompt seeded with the name under the cursor, resolved \
+     to its binding through the language's tree-sitter locals query. \
+     Submitting rewrites every reference of that binding in the buffer as \
+     one undo step, leaving same-named bindings in other scopes alone. \
+     No-op when the name does not resolve to a local binding.",
+    ActionPriority::Rare
+);
+
+define_action!(
+    FoldFunctionBodiesDef,
+    FoldFunctionBodies,
+    "FoldFunctionBodies",
+    ActionKind::FoldFunctionBodies,
+    "fold every function body",
+    "Fold the body of every multi-line function and method in the focused \
+     buffer, found through the `function.inside` captures of the language's \
+     `textobjects.scm`. Each fold shows how many lines it hides, as in \
+     `{ … 42 lines }`. No-op for languages without a textobjects query.",
+    ActionPriority::Rare
+);
+
+define_action!(
+    FoldClassBodiesDef,
+    FoldClassBodies,
+    "FoldClassBodies",
+    ActionKind::FoldClassBodies,
+    "fold every type and impl body",
+    "Fold the body of every multi-line struct, enum, trait and impl in the \
+     focused buffer, found through the `class.inside` captures of the \
+     language's `textobjects.scm`. Each fold shows how many lines it hides. \
+     No-op for languages without a textobjects query.",
+    ActionPriority::Rare
+);
+
+define_action!(
+    FoldImportsDef,
+    FoldImports,
+    "FoldImports",
+    ActionKind::FoldImports,
+    "fold import blocks",
+    "Fold each run of two or more adjacent top-level import statements in \
+     the focused buffer behind a placeholder counting its lines. No-op \
+     before the buffer is parsed.",
+    ActionPriority::Rare
+);
+
+define_action!(
+    UnfoldAllDef,
+    UnfoldAll,
+    "UnfoldAll",
+    ActionKind::UnfoldAll,
+    "unfold everything",
+    "Remove every fold in the focused editor.",
+    ActionPriority::Rare
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,9 +68,9 @@ impl Action for OpenFile {
 const OPEN_CONFIG_PARAMS: &[ParamDef] = &[ParamDef {
     name: "target",
     kind: ParamKind::String,
-    value_source: ValueSource::Values(&["stoat", "stoatty"]),
+    value_source: ValueSource::Values(&["stoat", "stoatty", "project"]),
     required: false,
-    description: "stoat (default) or stoatty",
+    description: "stoat (default), stoatty, or project",
 }];
 
 #[derive(Debug)]
@@ -98,7 +98,7 @@ impl ActionDef for OpenConfigDef {
     }
 
     fn long_desc(&self) -> &'static str {
-        "Open a user config in the focused pane. Omitted or `stoat` opens ~/.config/stoat/config.stcfg; `stoatty` opens the terminal's ~/.config/stoatty/config.toml. A config that does not yet exist is created from the matching built-in default."
+        "Open a user config in the focused pane. Omitted or `stoat` opens ~/.config/stoat/config.stcfg; `stoatty` opens the terminal's ~/.config/stoatty/config.toml; `project` opens the workspace root's .stoat/config, which layers over the user config. A config that does not yet exist is created from the matching built-in default."
     }
 
     fn priority(&self) -> ActionPriority {
@@ -126,6 +126,52 @@ impl Action for OpenConfig {
     }
 }
 
+#[derive(Debug)]
+pub struct ShowEffectiveConfigDef;
+
+impl ActionDef for ShowEffectiveConfigDef {
+    fn name(&self) -> &'static str {
+        "ShowEffectiveConfig"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("effective-config")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::ShowEffectiveConfig
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        &[]
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "show the merged user and project config"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Open a read-only split listing every setting the user config and the workspace root's .stoat/config assign, with the final value and the layer it came from, followed by the project's binding blocks."
+    }
+}
+
+#[derive(Debug)]
+pub struct ShowEffectiveConfig;
+
+impl ShowEffectiveConfig {
+    pub const DEF: &ShowEffectiveConfigDef = &ShowEffectiveConfigDef;
+}
+
+impl Action for ShowEffectiveConfig {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct ToggleMinimapDef;
 
@@ -226,6 +272,156 @@ impl Action for ToggleWrap {
     }
 }
 
+#[derive(Debug)]
+pub struct ToggleCursorLineDef;
+
+impl ActionDef for ToggleCursorLineDef {
+    fn name(&self) -> &'static str {
+        "ToggleCursorLine"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("cursorline")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::ToggleCursorLine
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        &[]
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "highlight the cursor line"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Toggle the background highlight on the focused editor's cursor line, overriding the editor.cursor_line setting for this session."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Common
+    }
+}
+
+#[derive(Debug)]
+pub struct ToggleCursorLine;
+
+impl ToggleCursorLine {
+    pub const DEF: &ToggleCursorLineDef = &ToggleCursorLineDef;
+}
+
+impl Action for ToggleCursorLine {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct ToggleRelativeNumbersDef;
+
+impl ActionDef for ToggleRelativeNumbersDef {
+    fn name(&self) -> &'static str {
+        "ToggleRelativeNumbers"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("relativenumber")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::ToggleRelativeNumbers
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        &[]
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "toggle relative line numbers"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Switch the editor gutter between relative and absolute line numbers, overriding the editor.line_numbers setting for this session. The cursor's row always shows its absolute number. A hidden gutter comes back with relative numbers."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Common
+    }
+}
+
+#[derive(Debug)]
+pub struct ToggleRelativeNumbers;
+
+impl ToggleRelativeNumbers {
+    pub const DEF: &ToggleRelativeNumbersDef = &ToggleRelativeNumbersDef;
+}
+
+impl Action for ToggleRelativeNumbers {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct OpenAsTextDef;
+
+impl ActionDef for OpenAsTextDef {
+    fn name(&self) -> &'static str {
+        "OpenAsText"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("open-as-text")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::OpenAsText
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        &[]
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "open a binary file as text"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Replace the focused hex view with its file opened as an ordinary text buffer, decoding bytes that are not valid UTF-8 as replacement characters. Saving the buffer writes those replacements back, so the original bytes are only kept while the file is not saved. No-op outside a hex view."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Rare
+    }
+}
+
+#[derive(Debug)]
+pub struct OpenAsText;
+
+impl OpenAsText {
+    pub const DEF: &OpenAsTextDef = &OpenAsTextDef;
+}
+
+impl Action for OpenAsText {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 const OPEN_BUFFER_PARAMS: &[ParamDef] = &[ParamDef {
     name: "path",
     kind: ParamKind::String,
@@ -151,6 +151,56 @@ impl Action for DismissKeyHints {
     }
 }
 
+#[derive(Debug)]
+pub struct CheckKeymapDef;
+
+impl ActionDef for CheckKeymapDef {
+    fn name(&self) -> &'static str {
+        "CheckKeymap"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("check-keymap")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::CheckKeymap
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        &[]
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "report keybinding conflicts"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Analyze the loaded keymap and open a report in a read-only split. It \
+         lists duplicate bindings, where two bindings share chords and scope so \
+         only one ever runs, shadowed bindings, where a binding on the same \
+         chords holds in every state another does and outranks it, and modes a \
+         binding block selects on that no chain of SetMode actions reaches."
+    }
+}
+
+#[derive(Debug)]
+pub struct CheckKeymap;
+
+impl CheckKeymap {
+    pub const DEF: &CheckKeymapDef = &CheckKeymapDef;
+}
+
+impl Action for CheckKeymap {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 macro_rules! plumbing_action {
     ($def:ident, $action:ident, $name:expr_2021, $kind:expr_2021, $short:expr_2021, $long:expr_2021) => {
         #[derive(Debug)]
@@ -61,8 +61,9 @@ define_action!(
     "Move the primary cursor to the definition of the symbol under the cursor \
      by issuing an LSP `textDocument/definition` request. Multi-file targets \
      open the destination file in the focused pane before jumping. Multiple \
-     candidates jump to the first; no-op when the server returns nothing or \
-     does not advertise the capability.",
+     candidates jump to the first; no-op when the server returns nothing. \
+     With no server advertising the capability, a name bound in the same \
+     file resolves through the language's tree-sitter locals query.",
     ActionPriority::Common
 );
 
@@ -1,4 +1,7 @@
-use crate::{Action, ActionDef, ActionKind, ParamDef};
+use crate::{
+    action::define_action, Action, ActionDef, ActionKind, ActionPriority, ParamDef, ParamKind,
+    ValueSource,
+};
 use std::any::Any;
 
 #[derive(Debug)]
@@ -48,6 +51,76 @@ impl Action for OpenCommandPalette {
     }
 }
 
+define_action!(
+    RepeatLastCommandDef,
+    RepeatLastCommand,
+    "RepeatLastCommand",
+    ActionKind::RepeatLastCommand,
+    "repeat the last palette command",
+    "Run the newest entry of the workspace's command log again, with the same arguments.",
+    ActionPriority::Normal
+);
+
+const RUN_FROM_HISTORY_PARAMS: &[ParamDef] = &[ParamDef {
+    name: "line",
+    kind: ParamKind::String,
+    value_source: ValueSource::CommandHistory,
+    required: true,
+    description: "A command line from the workspace's command log, as the palette recorded it.",
+}];
+
+#[derive(Debug)]
+pub struct RunFromHistoryDef;
+
+impl ActionDef for RunFromHistoryDef {
+    fn name(&self) -> &'static str {
+        "RunFromHistory"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("history")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::RunFromHistory
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        RUN_FROM_HISTORY_PARAMS
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "pick a previous palette command to run again"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "List the commands run from the palette in this workspace, newest first, and run the picked one again with its original arguments."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Normal
+    }
+}
+
+#[derive(Debug)]
+pub struct RunFromHistory {
+    pub line: String,
+}
+
+impl RunFromHistory {
+    pub const DEF: &RunFromHistoryDef = &RunFromHistoryDef;
+}
+
+impl Action for RunFromHistory {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,6 +44,50 @@ define_action!(
     palette_visible = false
 );
 
+define_action!(
+    UndoTreeNextDef,
+    UndoTreeNext,
+    "UndoTreeNext",
+    ActionKind::UndoTreeNext,
+    "next undo tree row",
+    "Move the undo tree's selection to the next row.",
+    ActionPriority::Common,
+    palette_visible = false
+);
+
+define_action!(
+    UndoTreePrevDef,
+    UndoTreePrev,
+    "UndoTreePrev",
+    ActionKind::UndoTreePrev,
+    "previous undo tree row",
+    "Move the undo tree's selection to the previous row.",
+    ActionPriority::Common,
+    palette_visible = false
+);
+
+define_action!(
+    UndoTreeSelectDef,
+    UndoTreeSelect,
+    "UndoTreeSelect",
+    ActionKind::UndoTreeSelect,
+    "restore selected undo state",
+    "Restore the focused buffer to the undo tree state under the selection.",
+    ActionPriority::Common,
+    palette_visible = false
+);
+
+define_action!(
+    UndoTreeCloseDef,
+    UndoTreeClose,
+    "UndoTreeClose",
+    ActionKind::UndoTreeClose,
+    "close undo tree",
+    "Dismiss the undo tree without restoring.",
+    ActionPriority::Common,
+    palette_visible = false
+);
+
 define_action!(
     DiagnosticsPickerNextDef,
     DiagnosticsPickerNext,
@@ -210,3 +254,14 @@ define_action!(
     ActionPriority::Common,
     palette_visible = false
 );
+
+define_action!(
+    RegexPlaygroundFocusToggleDef,
+    RegexPlaygroundFocusToggle,
+    "RegexPlaygroundFocusToggle",
+    ActionKind::RegexPlaygroundFocusToggle,
+    "switch regex playground input",
+    "Move typing in the regex playground between the pattern input and the sample text pane.",
+    ActionPriority::Common,
+    palette_visible = false
+);
@@ -100,6 +100,20 @@ define_action!(
     ActionPriority::Common
 );
 
+define_action!(
+    RevertHunkDef,
+    RevertHunk,
+    "RevertHunk",
+    ActionKind::RevertHunk,
+    "revert the hunk under the cursor to HEAD",
+    "Restore the HEAD version of the diff hunk under the cursor into the \
+     buffer, discarding just that change as a single undoable edit. The \
+     git index is left alone. Works in any editor view on a git-tracked \
+     file, and is a no-op with a status message when the cursor is not on \
+     a hunk.",
+    ActionPriority::Common
+);
+
 define_action!(
     JumpToMoveSourceDef,
     JumpToMoveSource,
@@ -534,6 +548,66 @@ impl Action for OpenReviewAgentEdits {
     }
 }
 
+const DIFF_WITH_BUFFER_PARAMS: &[ParamDef] = &[ParamDef {
+    name: "path",
+    kind: ParamKind::String,
+    value_source: ValueSource::Buffers,
+    required: true,
+    description: "Path of the buffer to diff the focused buffer against. \
+                  Relative paths resolve against the workspace root.",
+}];
+
+#[derive(Debug)]
+pub struct DiffWithBufferDef;
+
+impl ActionDef for DiffWithBufferDef {
+    fn name(&self) -> &'static str {
+        "DiffWithBuffer"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("diff-with")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::DiffWithBuffer
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        DIFF_WITH_BUFFER_PARAMS
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "diff the focused buffer against another buffer"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Open the side-by-side diff view with the named buffer on the left and \
+         the focused buffer on the right, hunks aligned row for row. The diff \
+         follows edits to either buffer, and GotoNextChange / GotoPrevChange \
+         walk its hunks. Diff toggles the view off again."
+    }
+}
+
+#[derive(Debug)]
+pub struct DiffWithBuffer {
+    pub path: PathBuf,
+}
+
+impl DiffWithBuffer {
+    pub const DEF: &DiffWithBufferDef = &DiffWithBufferDef;
+}
+
+impl Action for DiffWithBuffer {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -552,4 +626,16 @@ mod tests {
         let action: Box<dyn Action> = Box::new(Diff);
         assert!(action.as_any().downcast_ref::<Diff>().is_some());
     }
+
+    #[test]
+    fn diff_with_buffer_takes_a_buffer_path() {
+        let action = DiffWithBuffer {
+            path: PathBuf::from("/tmp/a.rs"),
+        };
+        assert_eq!(action.kind(), ActionKind::DiffWithBuffer);
+        assert_eq!(action.def().name(), "DiffWithBuffer");
+        assert_eq!(action.def().command_name(), Some("diff-with"));
+        assert_eq!(action.def().params().len(), 1);
+        assert_eq!(action.def().params()[0].value_source, ValueSource::Buffers);
+    }
 }
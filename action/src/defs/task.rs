@@ -0,0 +1,122 @@
+use crate::{
+    action::define_action, Action, ActionDef, ActionKind, ActionPriority, ParamDef, ParamKind,
+    ValueSource,
+};
+use std::any::Any;
+
+const RUN_TASK_PARAMS: &[ParamDef] = &[ParamDef {
+    name: "name",
+    kind: ParamKind::String,
+    value_source: ValueSource::Tasks,
+    required: true,
+    description:
+        "Name of the task to run, resolved against the config's `task.command.<name>` settings.",
+}];
+
+#[derive(Debug)]
+pub struct RunTaskDef;
+
+impl ActionDef for RunTaskDef {
+    fn name(&self) -> &'static str {
+        "RunTask"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("task")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::RunTask
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        RUN_TASK_PARAMS
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "run a configured task"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Run the named task's shell command from the workspace root in the background. When it exits, its output is parsed by the task's problem matcher and every problem found replaces the previous task run's diagnostics, ready for GotoNextTaskProblem. A task already running is left alone."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Common
+    }
+}
+
+#[derive(Debug)]
+pub struct RunTask {
+    pub name: String,
+}
+
+impl RunTask {
+    pub const DEF: &RunTaskDef = &RunTaskDef;
+}
+
+impl Action for RunTask {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+define_action!(
+    RerunTaskDef,
+    RerunTask,
+    "RerunTask",
+    ActionKind::RerunTask,
+    "re-run the last task",
+    "Run the most recently started task again, as `RunTask` with its name.",
+    ActionPriority::Normal
+);
+
+define_action!(
+    GotoNextTaskProblemDef,
+    GotoNextTaskProblem,
+    "GotoNextTaskProblem",
+    ActionKind::GotoNextTaskProblem,
+    "jump to the next task problem",
+    "Open the file of the next problem the last task run reported and move the cursor to it, in output order, wrapping past the last one.",
+    ActionPriority::Normal
+);
+
+define_action!(
+    GotoPrevTaskProblemDef,
+    GotoPrevTaskProblem,
+    "GotoPrevTaskProblem",
+    ActionKind::GotoPrevTaskProblem,
+    "jump to the previous task problem",
+    "Open the file of the previous problem the last task run reported and move the cursor to it, wrapping before the first one.",
+    ActionPriority::Normal
+);
+
+define_action!(
+    RunTestAtCursorDef,
+    RunTestAtCursor,
+    "RunTestAtCursor",
+    ActionKind::RunTestAtCursor,
+    "run the test under the cursor",
+    "Run the Rust test function containing the cursor with `cargo test` from the workspace root. The lens above the test shows its result; a failure opens its output in a read-only buffer.",
+    ActionPriority::Normal
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_and_name() {
+        let action = RunTask {
+            name: "build".to_string(),
+        };
+        assert_eq!(action.kind(), ActionKind::RunTask);
+        assert_eq!(action.def().name(), "RunTask");
+        assert_eq!(action.def().params()[0].name, "name");
+        assert_eq!(action.def().command_name(), Some("task"));
+    }
+}
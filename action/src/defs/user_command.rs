@@ -0,0 +1,62 @@
+use crate::{Action, ActionDef, ActionKind, ActionPriority, ParamDef, ParamKind, ValueSource};
+use std::any::Any;
+
+const PARAMS: &[ParamDef] = &[ParamDef {
+    name: "line",
+    kind: ParamKind::String,
+    value_source: ValueSource::Commands,
+    required: true,
+    description: "Name of a config `command`, followed by its arguments separated by spaces. The last argument takes the rest of the line.",
+}];
+
+#[derive(Debug)]
+pub struct RunUserCommandDef;
+
+impl ActionDef for RunUserCommandDef {
+    fn name(&self) -> &'static str {
+        "RunUserCommand"
+    }
+
+    fn command_name(&self) -> Option<&'static str> {
+        Some("command")
+    }
+
+    fn kind(&self) -> ActionKind {
+        ActionKind::RunUserCommand
+    }
+
+    fn params(&self) -> &'static [ParamDef] {
+        PARAMS
+    }
+
+    fn short_desc(&self) -> &'static str {
+        "run a command defined in the config"
+    }
+
+    fn long_desc(&self) -> &'static str {
+        "Run the actions of a `command NAME(params) -> ...` statement from the config, with each `$param` in them replaced by the matching argument. Shows a message when the name is unknown or an argument is missing."
+    }
+
+    fn priority(&self) -> ActionPriority {
+        ActionPriority::Normal
+    }
+}
+
+#[derive(Debug)]
+pub struct RunUserCommand {
+    pub line: String,
+}
+
+impl RunUserCommand {
+    pub const DEF: &RunUserCommandDef = &RunUserCommandDef;
+}
+
+impl Action for RunUserCommand {
+    fn def(&self) -> &'static dyn ActionDef {
+        Self::DEF
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
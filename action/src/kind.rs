@@ -6,6 +6,12 @@ pub enum ActionKind {
     QuitAllCancel,
     ShowVersion,
     OpenLogs,
+    SetLogLevel,
+    RunToastAction,
+    DismissToasts,
+    NotificationHistory,
+    TogglePerfOverlay,
+    ToggleProfileCapture,
     SplitRight,
     SplitDown,
     SplitNewRight,
@@ -38,8 +44,12 @@ pub enum ActionKind {
     ReattachPane,
     OpenFile,
     OpenConfig,
+    ShowEffectiveConfig,
     ToggleMinimap,
     ToggleWrap,
+    ToggleCursorLine,
+    ToggleRelativeNumbers,
+    OpenAsText,
     ToggleKeyHints,
     DismissKeyHints,
     OpenBuffer,
@@ -56,8 +66,12 @@ pub enum ActionKind {
     FileFinderScopeToggle,
     FileFinderComplete,
     OpenCommandPalette,
+    RepeatLastCommand,
+    RunFromHistory,
     OpenHelp,
+    CheckKeymap,
     Diff,
+    DiffWithBuffer,
     ToggleDiff,
     Conflict,
     CloseConflict,
@@ -78,6 +92,7 @@ pub enum ActionKind {
     StageLine,
     UnstageLine,
     ToggleStageLine,
+    RevertHunk,
     AddSelectionBelow,
     AddSelectionAbove,
     MoveLeft,
@@ -118,14 +133,20 @@ pub enum ActionKind {
     JumpBackward,
     JumpForward,
     OpenJumplistPicker,
+    OpenUndoTree,
     OpenDiagnosticsPicker,
     OpenWorkspaceDiagnosticsPicker,
     OpenLastPicker,
     OpenCodeSearch,
+    OpenRegexPlayground,
     JumplistPickerNext,
     JumplistPickerPrev,
     JumplistPickerSelect,
     JumplistPickerClose,
+    UndoTreeNext,
+    UndoTreePrev,
+    UndoTreeSelect,
+    UndoTreeClose,
     DiagnosticsPickerNext,
     DiagnosticsPickerPrev,
     DiagnosticsPickerSelect,
@@ -141,6 +162,7 @@ pub enum ActionKind {
     CodeSearchSelect,
     CodeSearchClose,
     CodeSearchModeToggle,
+    RegexPlaygroundFocusToggle,
     SplitSelection,
     SelectRegex,
     KeepSelections,
@@ -152,10 +174,24 @@ pub enum ActionKind {
     ShellInsertOutput,
     ShellAppendOutput,
     ShellKeepPipe,
+    RunCommand,
+    RunCommandWithSelection,
+    RerunCommand,
+    InsertCommandOutput,
+    RunTask,
+    RerunTask,
+    GotoNextTaskProblem,
+    GotoPrevTaskProblem,
+    RunTestAtCursor,
+    RunUserCommand,
     SaveBuffer,
     ForceSaveBuffer,
     WriteQuit,
     CloseBuffer,
+    CloseOtherBuffers,
+    TogglePinBuffer,
+    AddBufferToGroup,
+    SwitchBufferGroup,
     AutoReload,
     AutoReloadConfig,
     AcceptCompletion,
@@ -186,12 +222,15 @@ pub enum ActionKind {
     OpenReverseSearchInput,
     SearchNext,
     SearchPrev,
+    SearchHistoryPrev,
+    SearchHistoryNext,
     Yank,
     PasteAfter,
     PasteBefore,
     ReplaceWithYanked,
     YankToClipboard,
     YankMainToClipboard,
+    CutToClipboard,
     PasteClipboardAfter,
     PasteClipboardBefore,
     SelectRegister,
@@ -248,9 +287,15 @@ pub enum ActionKind {
     IndentSelection,
     UnindentSelection,
     ToggleComments,
+    DuplicateAndCommentOriginal,
+    DuplicateSelection,
+    MoveLinesUp,
+    MoveLinesDown,
     ToggleSyntaxHighlight,
     ToggleLspStatus,
     ToggleInlayHints,
+    SetLineEndingLf,
+    SetLineEndingCrlf,
     ExtendToLineStart,
     ExtendToLineEnd,
     ExtendToFileStart,
@@ -279,6 +324,9 @@ pub enum ActionKind {
     RunModalDismiss,
     Run,
     SpawnClaude,
+    AgentPermissionAllow,
+    AgentPermissionAlways,
+    AgentPermissionDeny,
     Terminal,
     ToggleDockRight,
     ToggleDockLeft,
@@ -303,6 +351,12 @@ pub enum ActionKind {
     MarkTrailEnd,
     TrailNext,
     TrailPrev,
+    EditColorAtCursor,
+    RenameLocal,
+    FoldFunctionBodies,
+    FoldClassBodies,
+    FoldImports,
+    UnfoldAll,
     Hover,
     CodeAction,
     RenameSymbol,
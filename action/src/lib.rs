@@ -6,7 +6,11 @@ pub mod registry;
 
 pub use action::{Action, ActionDef, ActionPriority};
 pub use defs::{
-    app::{OpenLogs, Quit, QuitAll, ShowVersion},
+    app::{
+        DismissToasts, NotificationHistory, OpenLogs, Quit, QuitAll, RunToastAction, SetLogLevel,
+        ShowVersion, TogglePerfOverlay, ToggleProfileCapture,
+    },
+    buffer_group::{AddBufferToGroup, CloseOtherBuffers, SwitchBufferGroup, TogglePinBuffer},
     commits::{
         CloseCommits, CommitsFirst, CommitsLast, CommitsNext, CommitsOpenReview, CommitsPageDown,
         CommitsPageUp, CommitsPrev, CommitsRefresh, OpenCommits,
@@ -19,8 +23,9 @@ pub use defs::{
     dump::Dump,
     editor::{
         AcceptCompletion, AddSelectionBelow, AlignSelections, AlignViewBottom, AlignViewCenter,
-        AlignViewTop, ChangeSelection, CloseBuffer, CollapseSelection, Decrement, DeleteSelection,
-        DeleteSelectionNoYank, EnsureSelectionsForward, ExpandSelection, ExtendDown,
+        AlignViewTop, ChangeSelection, CloseBuffer, CollapseSelection, CutToClipboard, Decrement,
+        DeleteSelection, DeleteSelectionNoYank, DuplicateAndCommentOriginal, DuplicateSelection,
+        EditColorAtCursor, EnsureSelectionsForward, ExpandSelection, ExtendDown,
         ExtendFindNextChar, ExtendFindPrevChar, ExtendGotoColumn, ExtendGotoFileStart,
         ExtendGotoFirstNonwhitespace, ExtendGotoLastLine, ExtendGotoWindowBottom,
         ExtendGotoWindowCenter, ExtendGotoWindowTop, ExtendLeft, ExtendMoveParentNodeEnd,
@@ -28,33 +33,37 @@ pub use defs::{
         ExtendPrevWordStart, ExtendRight, ExtendSelectNextSibling, ExtendSelectPrevSibling,
         ExtendTillNextChar, ExtendTillPrevChar, ExtendToFileStart, ExtendToLastLine,
         ExtendToLineBounds, ExtendToLineEnd, ExtendToLineStart, ExtendUp, FindNextChar,
-        FindPrevChar, FlipSelections, GotoColumn, GotoFileStart, GotoFirstNonwhitespace,
-        GotoLastLine, GotoLineEnd, GotoLineNumber, GotoLineStart, GotoMark, GotoMarkExact,
-        GotoNextChange, GotoNextClass, GotoNextFunction, GotoNextParagraph, GotoPrevChange,
-        GotoPrevClass, GotoPrevFunction, GotoPrevParagraph, GotoWindowBottom, GotoWindowCenter,
-        GotoWindowTop, GotoWord, HalfPageDown, HalfPageUp, Increment, IndentSelection,
-        InsertRegister, JoinSelections, JoinSelectionsSpace, JumpBackward, JumpForward,
-        KeepPrimarySelection, KeepSelections, MatchBrackets, MoveDown, MoveLeft, MoveNextWordEnd,
+        FindPrevChar, FlipSelections, FoldClassBodies, FoldFunctionBodies, FoldImports, GotoColumn,
+        GotoFileStart, GotoFirstNonwhitespace, GotoLastLine, GotoLineEnd, GotoLineNumber,
+        GotoLineStart, GotoMark, GotoMarkExact, GotoNextChange, GotoNextClass, GotoNextFunction,
+        GotoNextParagraph, GotoPrevChange, GotoPrevClass, GotoPrevFunction, GotoPrevParagraph,
+        GotoWindowBottom, GotoWindowCenter, GotoWindowTop, GotoWord, HalfPageDown, HalfPageUp,
+        Increment, IndentSelection, InsertCommandOutput, InsertRegister, JoinSelections,
+        JoinSelectionsSpace, JumpBackward, JumpForward, KeepPrimarySelection, KeepSelections,
+        MatchBrackets, MoveDown, MoveLeft, MoveLinesDown, MoveLinesUp, MoveNextWordEnd,
         MoveNextWordStart, MoveParentNodeEnd, MoveParentNodeStart, MovePrevWordEnd,
         MovePrevWordStart, MoveRight, MoveUp, OpenAbove, OpenBelow, OpenCodeSearch,
-        OpenJumplistPicker, OpenLastPicker, OpenReverseSearchInput, OpenSearchInput, PageDown,
-        PageUp, PasteAfter, PasteBefore, PasteClipboardAfter, PasteClipboardBefore, RecordMacro,
-        Redo, RemovePrimarySelection, RemoveSelections, RepeatLastMotion, ReplaceChar,
-        ReplaceWithYanked, ReplayMacro, RotateSelectionContentsBackward,
-        RotateSelectionContentsForward, RotateSelectionsBackward, RotateSelectionsForward,
-        SaveBuffer, SaveSelection, ScrollDown, ScrollUp, SearchNext, SearchPrev, SelectAll,
-        SelectAllChildren, SelectAllSiblings, SelectLineBelow, SelectNextSibling,
-        SelectPrevSibling, SelectRegex, SelectRegister, SelectTextobjectAround,
-        SelectTextobjectInner, SetMark, ShellAppendOutput, ShellInsertOutput, ShellKeepPipe,
-        ShellPipe, ShellPipeTo, ShrinkSelection, ShrinkToLineBounds, SmartTab, SplitSelection,
-        SurroundAdd, SurroundDelete, SurroundReplace, SwitchCase, SwitchToLowercase,
-        SwitchToUppercase, TillNextChar, TillPrevChar, ToggleComments, ToggleInlayHints,
-        ToggleLspStatus, ToggleSyntaxHighlight, TriggerCompletion, TrimSelections, Undo,
-        UnindentSelection, WriteQuit, Yank, YankMainToClipboard, YankToClipboard,
+        OpenJumplistPicker, OpenLastPicker, OpenRegexPlayground, OpenReverseSearchInput,
+        OpenSearchInput, OpenUndoTree, PageDown, PageUp, PasteAfter, PasteBefore,
+        PasteClipboardAfter, PasteClipboardBefore, RecordMacro, Redo, RemovePrimarySelection,
+        RemoveSelections, RenameLocal, RepeatLastMotion, ReplaceChar, ReplaceWithYanked,
+        ReplayMacro, RerunCommand, RotateSelectionContentsBackward, RotateSelectionContentsForward,
+        RotateSelectionsBackward, RotateSelectionsForward, RunCommand, RunCommandWithSelection,
+        SaveBuffer, SaveSelection, ScrollDown, ScrollUp, SearchHistoryNext, SearchHistoryPrev,
+        SearchNext, SearchPrev, SelectAll, SelectAllChildren, SelectAllSiblings, SelectLineBelow,
+        SelectNextSibling, SelectPrevSibling, SelectRegex, SelectRegister, SelectTextobjectAround,
+        SelectTextobjectInner, SetLineEndingCrlf, SetLineEndingLf, SetMark, ShellAppendOutput,
+        ShellInsertOutput, ShellKeepPipe, ShellPipe, ShellPipeTo, ShrinkSelection,
+        ShrinkToLineBounds, SmartTab, SplitSelection, SurroundAdd, SurroundDelete, SurroundReplace,
+        SwitchCase, SwitchToLowercase, SwitchToUppercase, TillNextChar, TillPrevChar,
+        ToggleComments, ToggleInlayHints, ToggleLspStatus, ToggleSyntaxHighlight,
+        TriggerCompletion, TrimSelections, Undo, UnfoldAll, UnindentSelection, WriteQuit, Yank,
+        YankMainToClipboard, YankToClipboard,
     },
     file::{
-        AutoReload, AutoReloadConfig, ForceSaveBuffer, OpenBuffer, OpenConfig, OpenFile,
-        ToggleMinimap, ToggleWrap,
+        AutoReload, AutoReloadConfig, ForceSaveBuffer, OpenAsText, OpenBuffer, OpenConfig,
+        OpenFile, ShowEffectiveConfig, ToggleCursorLine, ToggleMinimap, ToggleRelativeNumbers,
+        ToggleWrap,
     },
     file_finder::{
         FileFinderComplete, FileFinderPageDown, FileFinderPageUp, FileFinderScopeToggle,
@@ -62,9 +71,9 @@ pub use defs::{
         OpenFileFinder, OpenFileFinderHSplit, OpenFileFinderVSplit, OpenWorkspaceFileFinder,
     },
     help::{
-        CloseHelp, DismissKeyHints, HelpComplete, HelpJumpFirst, HelpJumpLast, HelpScopeToggle,
-        HelpScrollDetailDown, HelpScrollDetailUp, HelpSelectNext, HelpSelectPrev, OpenHelp,
-        ToggleKeyHints,
+        CheckKeymap, CloseHelp, DismissKeyHints, HelpComplete, HelpJumpFirst, HelpJumpLast,
+        HelpScopeToggle, HelpScrollDetailDown, HelpScrollDetailUp, HelpSelectNext, HelpSelectPrev,
+        OpenHelp, ToggleKeyHints,
     },
     lsp::{
         CodeAction, Format, FormatSelections, GotoDeclaration, GotoDefinition, GotoImplementation,
@@ -73,7 +82,7 @@ pub use defs::{
         SymbolFinderComplete, SymbolFinderPageDown, SymbolFinderPageUp, SymbolFinderSelectNext,
         SymbolFinderSelectPrev,
     },
-    palette::OpenCommandPalette,
+    palette::{OpenCommandPalette, RepeatLastCommand, RunFromHistory},
     pane::{
         ClosePane, FocusDown, FocusLeft, FocusNext, FocusPane, FocusPrev, FocusRight, FocusUp,
         MovePaneDown, MovePaneLeft, MovePaneNext, MovePanePrev, MovePaneRight, MovePaneUp,
@@ -92,17 +101,19 @@ pub use defs::{
         SetRebaseOpEdit, SetRebaseOpFixup, SetRebaseOpPick, SetRebaseOpReword, SetRebaseOpSquash,
     },
     review::{
-        AgentEdit, CloseReview, Diff, JumpToMoveSource, JumpToMoveTarget, JumpToNextMoveSource,
-        JumpToPrevMoveSource, OpenReviewAgentEdits, OpenReviewCommit, OpenReviewCommitRange,
-        QueryMoveRelationships, ReviewApplyStaged, ReviewExternalEdit, ReviewNextChunk,
-        ReviewPrevChunk, ReviewRefresh, ReviewRemoveSelected, ReviewSkipChunk, ReviewStageChunk,
-        ReviewToggleStage, ReviewUnstageChunk, StageHunk, StageLine, ToggleDiff, ToggleStageHunk,
-        ToggleStageLine, UnstageHunk, UnstageLine,
+        AgentEdit, CloseReview, Diff, DiffWithBuffer, JumpToMoveSource, JumpToMoveTarget,
+        JumpToNextMoveSource, JumpToPrevMoveSource, OpenReviewAgentEdits, OpenReviewCommit,
+        OpenReviewCommitRange, QueryMoveRelationships, RevertHunk, ReviewApplyStaged,
+        ReviewExternalEdit, ReviewNextChunk, ReviewPrevChunk, ReviewRefresh, ReviewRemoveSelected,
+        ReviewSkipChunk, ReviewStageChunk, ReviewToggleStage, ReviewUnstageChunk, StageHunk,
+        StageLine, ToggleDiff, ToggleStageHunk, ToggleStageLine, UnstageHunk, UnstageLine,
     },
     run::{OpenRun, Run, RunHistoryNext, RunHistoryPrev, RunInterrupt, RunSubmit},
     set_theme::SetTheme,
     tab::{CloseTab, GotoTab, NewTab, NextTab, PrevTab, RenameTab, ToggleTab, ToggleTabBar},
+    task::{GotoNextTaskProblem, GotoPrevTaskProblem, RerunTask, RunTask, RunTestAtCursor},
     terminal::Terminal,
+    user_command::RunUserCommand,
     workspace::{
         CloseWorkspace, CopyWorkspace, NewWorkspace, ReloadEnv, RenameWorkspace, SetCwd, ShowCwd,
         SwitchWorkspace,
@@ -37,6 +37,16 @@ pub enum ValueSource {
     Directories,
     /// Names of the configured theme blocks, for the theme switcher.
     Themes,
+    /// Names of the configured `task.command.<name>` settings.
+    Tasks,
+    /// Names of the config's `command` statements.
+    Commands,
+    /// Command lines from the workspace's command log, newest first.
+    CommandHistory,
+    /// Names of the workspace's buffer groups.
+    BufferGroups,
+    /// Notifications raised this session, newest first.
+    Notifications,
     /// A closed set of accepted values, listed verbatim. Arguments that read
     /// as an enum (`on`, `off`, `follow`) carry their spellings here so the
     /// picker can offer them instead of leaving the user to recall them.
@@ -108,6 +118,18 @@ impl ParamValue {
     }
 }
 
+/// The value as [`ParamValue::parse`] reads it back, so a logged argument
+/// round-trips to the same value.
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::String(s) => f.write_str(s),
+            ParamValue::Number(n) => write!(f, "{n}"),
+            ParamValue::Bool(b) => write!(f, "{b}"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Snafu)]
 #[snafu(visibility(pub))]
 pub enum ParamError {
@@ -137,6 +159,17 @@ pub enum ParamError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn display_round_trips_through_parse() {
+        for (kind, value) in [
+            (ParamKind::String, ParamValue::String("a b".into())),
+            (ParamKind::Number, ParamValue::Number(2.5)),
+            (ParamKind::Bool, ParamValue::Bool(false)),
+        ] {
+            assert_eq!(ParamValue::parse(kind, &value.to_string()), Ok(value));
+        }
+    }
+
     #[test]
     fn parse_string_passthrough() {
         assert_eq!(
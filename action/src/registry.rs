@@ -1,7 +1,12 @@
 use crate::{
     defs::{
-        agent::SpawnClaude,
-        app::{OpenLogs, Quit, QuitAll, QuitAllCancel, QuitAllConfirm, ShowVersion},
+        agent::{AgentPermissionAllow, AgentPermissionAlways, AgentPermissionDeny, SpawnClaude},
+        app::{
+            DismissToasts, NotificationHistory, OpenLogs, Quit, QuitAll, QuitAllCancel,
+            QuitAllConfirm, RunToastAction, SetLogLevel, ShowVersion, TogglePerfOverlay,
+            ToggleProfileCapture,
+        },
+        buffer_group::{AddBufferToGroup, CloseOtherBuffers, SwitchBufferGroup, TogglePinBuffer},
         commits::{
             CloseCommits, CommitsFirst, CommitsLast, CommitsNext, CommitsOpenReview,
             CommitsPageDown, CommitsPageUp, CommitsPrev, CommitsRefresh, OpenCommits,
@@ -15,9 +20,10 @@ use crate::{
         editor::{
             AcceptCompletion, AddSelectionAbove, AddSelectionBelow, AlignSelections,
             AlignViewBottom, AlignViewCenter, AlignViewTop, AppendMode, ChangeSelection,
-            CloseBuffer, CollapseSelection, CommitUndoCheckpoint, Decrement, DeleteSelection,
-            DeleteSelectionNoYank, EnsureSelectionsForward, EnterInsertMode, ExpandSelection,
-            ExtendDown, ExtendFindNextChar, ExtendFindPrevChar, ExtendGotoColumn,
+            CloseBuffer, CollapseSelection, CommitUndoCheckpoint, CutToClipboard, Decrement,
+            DeleteSelection, DeleteSelectionNoYank, DuplicateAndCommentOriginal,
+            DuplicateSelection, EditColorAtCursor, EnsureSelectionsForward, EnterInsertMode,
+            ExpandSelection, ExtendDown, ExtendFindNextChar, ExtendFindPrevChar, ExtendGotoColumn,
             ExtendGotoFileStart, ExtendGotoFirstNonwhitespace, ExtendGotoLastLine,
             ExtendGotoWindowBottom, ExtendGotoWindowCenter, ExtendGotoWindowTop, ExtendLeft,
             ExtendMoveParentNodeEnd, ExtendMoveParentNodeStart, ExtendNextWordEnd,
@@ -25,38 +31,42 @@ use crate::{
             ExtendSelectNextSibling, ExtendSelectPrevSibling, ExtendTillNextChar,
             ExtendTillPrevChar, ExtendToFileStart, ExtendToLastLine, ExtendToLineBounds,
             ExtendToLineEnd, ExtendToLineStart, ExtendUp, FindNextChar, FindPrevChar,
-            FlipSelections, GotoCallee, GotoCaller, GotoColumn, GotoDiffCalleeDown,
-            GotoDiffCallerUp, GotoFileStart, GotoFirstNonwhitespace, GotoImplementors,
-            GotoLastLine, GotoLineEnd, GotoLineNumber, GotoLineStart, GotoMark, GotoMarkExact,
-            GotoNextChange, GotoNextClass, GotoNextFunction, GotoNextParagraph, GotoPrevChange,
-            GotoPrevClass, GotoPrevFunction, GotoPrevParagraph, GotoReferences, GotoWindowBottom,
-            GotoWindowCenter, GotoWindowTop, GotoWord, HalfPageDown, HalfPageUp, Increment,
-            IndentSelection, InsertAtLineEnd, InsertAtLineStart, InsertRegister, InsertTab,
-            JoinSelections, JoinSelectionsSpace, JumpBackward, JumpForward, KeepPrimarySelection,
-            KeepSelections, MarkTrailEnd, MarkTrailStart, MatchBrackets, MoveDown, MoveLeft,
-            MoveNextLongWordEnd, MoveNextLongWordStart, MoveNextWordEnd, MoveNextWordStart,
-            MoveParentNodeEnd, MoveParentNodeStart, MovePrevLongWordEnd, MovePrevLongWordStart,
-            MovePrevWordEnd, MovePrevWordStart, MoveRight, MoveUp, OpenAbove, OpenBelow,
-            OpenCodeSearch, OpenJumplistPicker, OpenLastPicker, OpenReverseSearchInput,
-            OpenSearchInput, PageDown, PageUp, PasteAfter, PasteBefore, PasteClipboardAfter,
-            PasteClipboardBefore, RecordMacro, Redo, RemovePrimarySelection, RemoveSelections,
-            RepeatLastMotion, ReplaceChar, ReplaceWithYanked, ReplayMacro,
+            FlipSelections, FoldClassBodies, FoldFunctionBodies, FoldImports, GotoCallee,
+            GotoCaller, GotoColumn, GotoDiffCalleeDown, GotoDiffCallerUp, GotoFileStart,
+            GotoFirstNonwhitespace, GotoImplementors, GotoLastLine, GotoLineEnd, GotoLineNumber,
+            GotoLineStart, GotoMark, GotoMarkExact, GotoNextChange, GotoNextClass,
+            GotoNextFunction, GotoNextParagraph, GotoPrevChange, GotoPrevClass, GotoPrevFunction,
+            GotoPrevParagraph, GotoReferences, GotoWindowBottom, GotoWindowCenter, GotoWindowTop,
+            GotoWord, HalfPageDown, HalfPageUp, Increment, IndentSelection, InsertAtLineEnd,
+            InsertAtLineStart, InsertCommandOutput, InsertRegister, InsertTab, JoinSelections,
+            JoinSelectionsSpace, JumpBackward, JumpForward, KeepPrimarySelection, KeepSelections,
+            MarkTrailEnd, MarkTrailStart, MatchBrackets, MoveDown, MoveLeft, MoveLinesDown,
+            MoveLinesUp, MoveNextLongWordEnd, MoveNextLongWordStart, MoveNextWordEnd,
+            MoveNextWordStart, MoveParentNodeEnd, MoveParentNodeStart, MovePrevLongWordEnd,
+            MovePrevLongWordStart, MovePrevWordEnd, MovePrevWordStart, MoveRight, MoveUp,
+            OpenAbove, OpenBelow, OpenCodeSearch, OpenJumplistPicker, OpenLastPicker,
+            OpenRegexPlayground, OpenReverseSearchInput, OpenSearchInput, OpenUndoTree, PageDown,
+            PageUp, PasteAfter, PasteBefore, PasteClipboardAfter, PasteClipboardBefore,
+            RecordMacro, Redo, RemovePrimarySelection, RemoveSelections, RenameLocal,
+            RepeatLastMotion, ReplaceChar, ReplaceWithYanked, ReplayMacro, RerunCommand,
             RotateSelectionContentsBackward, RotateSelectionContentsForward,
-            RotateSelectionsBackward, RotateSelectionsForward, SaveBuffer, SaveSelection,
-            ScrollDown, ScrollUp, SearchNext, SearchPrev, SelectAll, SelectAllChildren,
-            SelectAllSiblings, SelectLineBelow, SelectNextSibling, SelectPrevSibling, SelectRegex,
-            SelectRegister, SelectTextobjectAround, SelectTextobjectInner, SetMark,
-            ShellAppendOutput, ShellInsertOutput, ShellKeepPipe, ShellPipe, ShellPipeTo,
+            RotateSelectionsBackward, RotateSelectionsForward, RunCommand, RunCommandWithSelection,
+            SaveBuffer, SaveSelection, ScrollDown, ScrollUp, SearchHistoryNext, SearchHistoryPrev,
+            SearchNext, SearchPrev, SelectAll, SelectAllChildren, SelectAllSiblings,
+            SelectLineBelow, SelectNextSibling, SelectPrevSibling, SelectRegex, SelectRegister,
+            SelectTextobjectAround, SelectTextobjectInner, SetLineEndingCrlf, SetLineEndingLf,
+            SetMark, ShellAppendOutput, ShellInsertOutput, ShellKeepPipe, ShellPipe, ShellPipeTo,
             ShrinkSelection, ShrinkToLineBounds, SmartTab, SplitSelection, SplitSelectionOnNewline,
             SurroundAdd, SurroundDelete, SurroundReplace, SwitchCase, SwitchToLowercase,
             SwitchToUppercase, TillNextChar, TillPrevChar, ToggleComments, ToggleInlayHints,
             ToggleLspStatus, ToggleSyntaxHighlight, TrailNext, TrailPrev, TriggerCompletion,
-            TrimSelections, Undo, UnindentSelection, WriteQuit, Yank, YankMainToClipboard,
-            YankToClipboard,
+            TrimSelections, Undo, UnfoldAll, UnindentSelection, WriteQuit, Yank,
+            YankMainToClipboard, YankToClipboard,
         },
         file::{
-            AutoReload, AutoReloadConfig, ForceSaveBuffer, OpenBuffer, OpenConfig, OpenFile,
-            ToggleMinimap, ToggleWrap,
+            AutoReload, AutoReloadConfig, ForceSaveBuffer, OpenAsText, OpenBuffer, OpenConfig,
+            OpenFile, ShowEffectiveConfig, ToggleCursorLine, ToggleMinimap, ToggleRelativeNumbers,
+            ToggleWrap,
         },
         file_finder::{
             FileFinderComplete, FileFinderPageDown, FileFinderPageUp, FileFinderScopeToggle,
@@ -64,9 +74,9 @@ use crate::{
             OpenFileFinder, OpenFileFinderHSplit, OpenFileFinderVSplit, OpenWorkspaceFileFinder,
         },
         help::{
-            CloseHelp, DismissKeyHints, HelpComplete, HelpJumpFirst, HelpJumpLast, HelpScopeToggle,
-            HelpScrollDetailDown, HelpScrollDetailUp, HelpSelectNext, HelpSelectPrev, OpenHelp,
-            ToggleKeyHints,
+            CheckKeymap, CloseHelp, DismissKeyHints, HelpComplete, HelpJumpFirst, HelpJumpLast,
+            HelpScopeToggle, HelpScrollDetailDown, HelpScrollDetailUp, HelpSelectNext,
+            HelpSelectPrev, OpenHelp, ToggleKeyHints,
         },
         lsp::{
             CodeAction, Format, FormatSelections, GotoDeclaration, GotoDefinition,
@@ -75,7 +85,7 @@ use crate::{
             OpenWorkspaceSymbolPicker, RenameSymbol, SymbolFinderComplete, SymbolFinderPageDown,
             SymbolFinderPageUp, SymbolFinderSelectNext, SymbolFinderSelectPrev,
         },
-        palette::OpenCommandPalette,
+        palette::{OpenCommandPalette, RepeatLastCommand, RunFromHistory},
         pane::{
             CloseOtherPanes, ClosePane, DetachPane, FocusDown, FocusLeft, FocusNext, FocusPane,
             FocusPrev, FocusRight, FocusUp, MovePaneDown, MovePaneLeft, MovePaneNext, MovePanePrev,
@@ -88,6 +98,7 @@ use crate::{
             DiagnosticsPickerNext, DiagnosticsPickerPrev, DiagnosticsPickerSelect,
             JumplistPickerClose, JumplistPickerNext, JumplistPickerPrev, JumplistPickerSelect,
             LocationPickerClose, LocationPickerNext, LocationPickerPrev, LocationPickerSelect,
+            RegexPlaygroundFocusToggle, UndoTreeClose, UndoTreeNext, UndoTreePrev, UndoTreeSelect,
         },
         prompt::{
             CancelPromptInput, PaletteComplete, PaletteHistoryNext, PaletteHistoryPrev,
@@ -103,19 +114,21 @@ use crate::{
             SetRebaseOpSquash,
         },
         review::{
-            CloseReview, Diff, JumpToMoveSource, JumpToMoveTarget, JumpToNextMoveSource,
-            JumpToPrevMoveSource, OpenReviewCommit, OpenReviewCommitRange, QueryMoveRelationships,
-            ReviewApplyStaged, ReviewNextChunk, ReviewPrevChunk, ReviewRefresh,
-            ReviewRemoveSelected, ReviewSkipChunk, ReviewStageChunk, ReviewToggleStage,
-            ReviewUnstageChunk, StageHunk, StageLine, ToggleDiff, ToggleStageHunk, ToggleStageLine,
-            UnstageHunk, UnstageLine,
+            CloseReview, Diff, DiffWithBuffer, JumpToMoveSource, JumpToMoveTarget,
+            JumpToNextMoveSource, JumpToPrevMoveSource, OpenReviewCommit, OpenReviewCommitRange,
+            QueryMoveRelationships, RevertHunk, ReviewApplyStaged, ReviewNextChunk,
+            ReviewPrevChunk, ReviewRefresh, ReviewRemoveSelected, ReviewSkipChunk,
+            ReviewStageChunk, ReviewToggleStage, ReviewUnstageChunk, StageHunk, StageLine,
+            ToggleDiff, ToggleStageHunk, ToggleStageLine, UnstageHunk, UnstageLine,
         },
         run::{
             OpenRun, Run, RunHistoryNext, RunHistoryPrev, RunInterrupt, RunModalDismiss, RunSubmit,
         },
         set_theme::SetTheme,
         tab::{CloseTab, GotoTab, NewTab, NextTab, PrevTab, RenameTab, ToggleTab, ToggleTabBar},
+        task::{GotoNextTaskProblem, GotoPrevTaskProblem, RerunTask, RunTask, RunTestAtCursor},
         terminal::Terminal,
+        user_command::RunUserCommand,
         workspace::{
             CloseWorkspace, CopyWorkspace, NewWorkspace, ReloadEnv, RenameWorkspace, SetCwd,
             ShowCwd, SwitchWorkspace, WorkspacePickerClose, WorkspacePickerComplete,
@@ -180,6 +193,40 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(QuitAllCancel::DEF, |_| Ok(Box::new(QuitAllCancel)));
     add(ShowVersion::DEF, |_| Ok(Box::new(ShowVersion)));
     add(OpenLogs::DEF, |_| Ok(Box::new(OpenLogs)));
+    add(SetLogLevel::DEF, |params| {
+        let raw = params
+            .first()
+            .context(MissingSnafu { name: "level" })?
+            .as_string()
+            .context(WrongKindSnafu {
+                name: "level",
+                expected: ParamKind::String,
+            })?;
+        Ok(Box::new(SetLogLevel {
+            level: raw.to_owned(),
+        }))
+    });
+    add(RunToastAction::DEF, |_| Ok(Box::new(RunToastAction)));
+    add(DismissToasts::DEF, |_| Ok(Box::new(DismissToasts)));
+    add(TogglePerfOverlay::DEF, |_| Ok(Box::new(TogglePerfOverlay)));
+    add(ToggleProfileCapture::DEF, |_| {
+        Ok(Box::new(ToggleProfileCapture))
+    });
+    add(NotificationHistory::DEF, |params| {
+        let raw = params
+            .first()
+            .context(MissingSnafu {
+                name: "notification",
+            })?
+            .as_string()
+            .context(WrongKindSnafu {
+                name: "notification",
+                expected: ParamKind::String,
+            })?;
+        Ok(Box::new(NotificationHistory {
+            notification: raw.to_owned(),
+        }))
+    });
     add(SplitRight::DEF, |_| Ok(Box::new(SplitRight)));
     add(SplitDown::DEF, |_| Ok(Box::new(SplitDown)));
     add(SplitNewRight::DEF, |_| Ok(Box::new(SplitNewRight)));
@@ -267,6 +314,20 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(OpenCommandPalette::DEF, |_| {
         Ok(Box::new(OpenCommandPalette))
     });
+    add(RepeatLastCommand::DEF, |_| Ok(Box::new(RepeatLastCommand)));
+    add(RunFromHistory::DEF, |params| {
+        let raw = params
+            .first()
+            .context(MissingSnafu { name: "line" })?
+            .as_string()
+            .context(WrongKindSnafu {
+                name: "line",
+                expected: ParamKind::String,
+            })?;
+        Ok(Box::new(RunFromHistory {
+            line: raw.to_owned(),
+        }))
+    });
     add(OpenFileFinder::DEF, |_| Ok(Box::new(OpenFileFinder)));
     add(OpenFileFinderHSplit::DEF, |_| {
         Ok(Box::new(OpenFileFinderHSplit))
@@ -298,8 +359,22 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
         Ok(Box::new(FileFinderComplete))
     });
     add(OpenHelp::DEF, |_| Ok(Box::new(OpenHelp)));
+    add(CheckKeymap::DEF, |_| Ok(Box::new(CheckKeymap)));
     add(Diff::DEF, |_| Ok(Box::new(Diff)));
     add(ToggleDiff::DEF, |_| Ok(Box::new(ToggleDiff)));
+    add(DiffWithBuffer::DEF, |params| {
+        let raw = params
+            .first()
+            .context(MissingSnafu { name: "path" })?
+            .as_string()
+            .context(WrongKindSnafu {
+                name: "path",
+                expected: ParamKind::String,
+            })?;
+        Ok(Box::new(DiffWithBuffer {
+            path: PathBuf::from(raw),
+        }))
+    });
     add(Conflict::DEF, |_| Ok(Box::new(Conflict)));
     add(CloseConflict::DEF, |_| Ok(Box::new(CloseConflict)));
     add(ConflictPickOurs::DEF, |_| Ok(Box::new(ConflictPickOurs)));
@@ -327,6 +402,7 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(StageLine::DEF, |_| Ok(Box::new(StageLine)));
     add(UnstageLine::DEF, |_| Ok(Box::new(UnstageLine)));
     add(ToggleStageLine::DEF, |_| Ok(Box::new(ToggleStageLine)));
+    add(RevertHunk::DEF, |_| Ok(Box::new(RevertHunk)));
     add(JumpToMoveSource::DEF, |_| Ok(Box::new(JumpToMoveSource)));
     add(JumpToMoveTarget::DEF, |_| Ok(Box::new(JumpToMoveTarget)));
     add(JumpToNextMoveSource::DEF, |_| {
@@ -505,6 +581,7 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(OpenJumplistPicker::DEF, |_| {
         Ok(Box::new(OpenJumplistPicker))
     });
+    add(OpenUndoTree::DEF, |_| Ok(Box::new(OpenUndoTree)));
     add(OpenLastPicker::DEF, |_| Ok(Box::new(OpenLastPicker)));
     add(OpenDiagnosticsPicker::DEF, |_| {
         Ok(Box::new(OpenDiagnosticsPicker))
@@ -524,6 +601,12 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(CodeSearchModeToggle::DEF, |_| {
         Ok(Box::new(CodeSearchModeToggle))
     });
+    add(OpenRegexPlayground::DEF, |_| {
+        Ok(Box::new(OpenRegexPlayground))
+    });
+    add(RegexPlaygroundFocusToggle::DEF, |_| {
+        Ok(Box::new(RegexPlaygroundFocusToggle))
+    });
     add(JumplistPickerNext::DEF, |_| {
         Ok(Box::new(JumplistPickerNext))
     });
@@ -536,6 +619,10 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(JumplistPickerClose::DEF, |_| {
         Ok(Box::new(JumplistPickerClose))
     });
+    add(UndoTreeNext::DEF, |_| Ok(Box::new(UndoTreeNext)));
+    add(UndoTreePrev::DEF, |_| Ok(Box::new(UndoTreePrev)));
+    add(UndoTreeSelect::DEF, |_| Ok(Box::new(UndoTreeSelect)));
+    add(UndoTreeClose::DEF, |_| Ok(Box::new(UndoTreeClose)));
     add(DiagnosticsPickerNext::DEF, |_| {
         Ok(Box::new(DiagnosticsPickerNext))
     });
@@ -571,6 +658,14 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(ShellInsertOutput::DEF, |_| Ok(Box::new(ShellInsertOutput)));
     add(ShellAppendOutput::DEF, |_| Ok(Box::new(ShellAppendOutput)));
     add(ShellKeepPipe::DEF, |_| Ok(Box::new(ShellKeepPipe)));
+    add(RunCommand::DEF, |_| Ok(Box::new(RunCommand)));
+    add(RunCommandWithSelection::DEF, |_| {
+        Ok(Box::new(RunCommandWithSelection))
+    });
+    add(RerunCommand::DEF, |_| Ok(Box::new(RerunCommand)));
+    add(InsertCommandOutput::DEF, |_| {
+        Ok(Box::new(InsertCommandOutput))
+    });
     add(SaveBuffer::DEF, |_| Ok(Box::new(SaveBuffer)));
     add(ForceSaveBuffer::DEF, |_| Ok(Box::new(ForceSaveBuffer)));
     add(OpenConfig::DEF, |params| {
@@ -590,12 +685,48 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
             .map(str::to_owned);
         Ok(Box::new(OpenConfig { target }))
     });
+    add(ShowEffectiveConfig::DEF, |_| {
+        Ok(Box::new(ShowEffectiveConfig))
+    });
     add(ToggleMinimap::DEF, |_| Ok(Box::new(ToggleMinimap)));
     add(ToggleWrap::DEF, |_| Ok(Box::new(ToggleWrap)));
+    add(ToggleCursorLine::DEF, |_| Ok(Box::new(ToggleCursorLine)));
+    add(ToggleRelativeNumbers::DEF, |_| {
+        Ok(Box::new(ToggleRelativeNumbers))
+    });
+    add(OpenAsText::DEF, |_| Ok(Box::new(OpenAsText)));
     add(ToggleKeyHints::DEF, |_| Ok(Box::new(ToggleKeyHints)));
     add(DismissKeyHints::DEF, |_| Ok(Box::new(DismissKeyHints)));
     add(WriteQuit::DEF, |_| Ok(Box::new(WriteQuit)));
     add(CloseBuffer::DEF, |_| Ok(Box::new(CloseBuffer)));
+    add(CloseOtherBuffers::DEF, |_| Ok(Box::new(CloseOtherBuffers)));
+    add(TogglePinBuffer::DEF, |_| Ok(Box::new(TogglePinBuffer)));
+    add(AddBufferToGroup::DEF, |params| {
+        let raw = params
+            .first()
+            .context(MissingSnafu { name: "name" })?
+            .as_string()
+            .context(WrongKindSnafu {
+                name: "name",
+                expected: ParamKind::String,
+            })?;
+        Ok(Box::new(AddBufferToGroup {
+            name: raw.to_owned(),
+        }))
+    });
+    add(SwitchBufferGroup::DEF, |params| {
+        let raw = params
+            .first()
+            .context(MissingSnafu { name: "name" })?
+            .as_string()
+            .context(WrongKindSnafu {
+                name: "name",
+                expected: ParamKind::String,
+            })?;
+        Ok(Box::new(SwitchBufferGroup {
+            name: raw.to_owned(),
+        }))
+    });
     add(AcceptCompletion::DEF, |_| Ok(Box::new(AcceptCompletion)));
     add(SmartTab::DEF, |_| Ok(Box::new(SmartTab)));
     add(InsertTab::DEF, |_| Ok(Box::new(InsertTab)));
@@ -634,6 +765,8 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     });
     add(SearchNext::DEF, |_| Ok(Box::new(SearchNext)));
     add(SearchPrev::DEF, |_| Ok(Box::new(SearchPrev)));
+    add(SearchHistoryPrev::DEF, |_| Ok(Box::new(SearchHistoryPrev)));
+    add(SearchHistoryNext::DEF, |_| Ok(Box::new(SearchHistoryNext)));
     add(Yank::DEF, |_| Ok(Box::new(Yank)));
     add(PasteAfter::DEF, |_| Ok(Box::new(PasteAfter)));
     add(PasteBefore::DEF, |_| Ok(Box::new(PasteBefore)));
@@ -642,6 +775,7 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(YankMainToClipboard::DEF, |_| {
         Ok(Box::new(YankMainToClipboard))
     });
+    add(CutToClipboard::DEF, |_| Ok(Box::new(CutToClipboard)));
     add(PasteClipboardAfter::DEF, |_| {
         Ok(Box::new(PasteClipboardAfter))
     });
@@ -683,6 +817,14 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(MarkTrailEnd::DEF, |_| Ok(Box::new(MarkTrailEnd)));
     add(TrailNext::DEF, |_| Ok(Box::new(TrailNext)));
     add(TrailPrev::DEF, |_| Ok(Box::new(TrailPrev)));
+    add(EditColorAtCursor::DEF, |_| Ok(Box::new(EditColorAtCursor)));
+    add(RenameLocal::DEF, |_| Ok(Box::new(RenameLocal)));
+    add(FoldFunctionBodies::DEF, |_| {
+        Ok(Box::new(FoldFunctionBodies))
+    });
+    add(FoldClassBodies::DEF, |_| Ok(Box::new(FoldClassBodies)));
+    add(FoldImports::DEF, |_| Ok(Box::new(FoldImports)));
+    add(UnfoldAll::DEF, |_| Ok(Box::new(UnfoldAll)));
     add(ExtendGotoColumn::DEF, |_| Ok(Box::new(ExtendGotoColumn)));
     add(GotoNextChange::DEF, |_| Ok(Box::new(GotoNextChange)));
     add(GotoPrevChange::DEF, |_| Ok(Box::new(GotoPrevChange)));
@@ -738,11 +880,21 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     add(IndentSelection::DEF, |_| Ok(Box::new(IndentSelection)));
     add(UnindentSelection::DEF, |_| Ok(Box::new(UnindentSelection)));
     add(ToggleComments::DEF, |_| Ok(Box::new(ToggleComments)));
+    add(DuplicateAndCommentOriginal::DEF, |_| {
+        Ok(Box::new(DuplicateAndCommentOriginal))
+    });
+    add(DuplicateSelection::DEF, |_| {
+        Ok(Box::new(DuplicateSelection))
+    });
+    add(MoveLinesUp::DEF, |_| Ok(Box::new(MoveLinesUp)));
+    add(MoveLinesDown::DEF, |_| Ok(Box::new(MoveLinesDown)));
     add(ToggleSyntaxHighlight::DEF, |_| {
         Ok(Box::new(ToggleSyntaxHighlight))
     });
     add(ToggleLspStatus::DEF, |_| Ok(Box::new(ToggleLspStatus)));
     add(ToggleInlayHints::DEF, |_| Ok(Box::new(ToggleInlayHints)));
+    add(SetLineEndingLf::DEF, |_| Ok(Box::new(SetLineEndingLf)));
+    add(SetLineEndingCrlf::DEF, |_| Ok(Box::new(SetLineEndingCrlf)));
     add(ExtendToLineStart::DEF, |_| Ok(Box::new(ExtendToLineStart)));
     add(ExtendToLineEnd::DEF, |_| Ok(Box::new(ExtendToLineEnd)));
     add(ExtendToFileStart::DEF, |_| Ok(Box::new(ExtendToFileStart)));
@@ -841,6 +993,15 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
     });
     add(OpenRun::DEF, |_| Ok(Box::new(OpenRun)));
     add(SpawnClaude::DEF, |_| Ok(Box::new(SpawnClaude)));
+    add(AgentPermissionAllow::DEF, |_| {
+        Ok(Box::new(AgentPermissionAllow))
+    });
+    add(AgentPermissionAlways::DEF, |_| {
+        Ok(Box::new(AgentPermissionAlways))
+    });
+    add(AgentPermissionDeny::DEF, |_| {
+        Ok(Box::new(AgentPermissionDeny))
+    });
     add(Terminal::DEF, |_| Ok(Box::new(Terminal)));
     add(RunSubmit::DEF, |_| Ok(Box::new(RunSubmit)));
     add(RunInterrupt::DEF, |_| Ok(Box::new(RunInterrupt)));
@@ -948,6 +1109,40 @@ fn init() -> HashMap<&'static str, RegistryEntry> {
             name: raw.to_owned(),
         }))
     });
+    add(RunTask::DEF, |params| {
+        let raw = params
+            .first()
+            .context(MissingSnafu { name: "name" })?
+            .as_string()
+            .context(WrongKindSnafu {
+                name: "name",
+                expected: ParamKind::String,
+            })?;
+        Ok(Box::new(RunTask {
+            name: raw.to_owned(),
+        }))
+    });
+    add(RerunTask::DEF, |_| Ok(Box::new(RerunTask)));
+    add(RunUserCommand::DEF, |params| {
+        let raw = params
+            .first()
+            .context(MissingSnafu { name: "line" })?
+            .as_string()
+            .context(WrongKindSnafu {
+                name: "line",
+                expected: ParamKind::String,
+            })?;
+        Ok(Box::new(RunUserCommand {
+            line: raw.to_owned(),
+        }))
+    });
+    add(GotoNextTaskProblem::DEF, |_| {
+        Ok(Box::new(GotoNextTaskProblem))
+    });
+    add(GotoPrevTaskProblem::DEF, |_| {
+        Ok(Box::new(GotoPrevTaskProblem))
+    });
+    add(RunTestAtCursor::DEF, |_| Ok(Box::new(RunTestAtCursor)));
     add(NewWorkspace::DEF, |_| Ok(Box::new(NewWorkspace)));
     add(CopyWorkspace::DEF, |_| Ok(Box::new(CopyWorkspace)));
     add(SwitchWorkspace::DEF, |_| Ok(Box::new(SwitchWorkspace)));
@@ -1055,11 +1250,20 @@ mod tests {
     const ZERO_ARG_NAMES: &[&str] = &[
         "Quit",
         "WriteQuit",
+        "TogglePinBuffer",
+        "CloseOtherBuffers",
         "QuitAll",
         "QuitAllConfirm",
         "QuitAllCancel",
         "ShowVersion",
         "OpenLogs",
+        "RunToastAction",
+        "DismissToasts",
+        "TogglePerfOverlay",
+        "ToggleProfileCapture",
+        "ToggleCursorLine",
+        "ShowEffectiveConfig",
+        "ToggleRelativeNumbers",
         "SplitRight",
         "SplitDown",
         "SplitNewRight",
@@ -1074,6 +1278,7 @@ mod tests {
         "CloseOtherPanes",
         "TogglePaneWiden",
         "OpenCommandPalette",
+        "RepeatLastCommand",
         "OpenFileFinder",
         "OpenFileFinderHSplit",
         "OpenFileFinderVSplit",
@@ -1087,7 +1292,9 @@ mod tests {
         "FileFinderScopeToggle",
         "FileFinderComplete",
         "OpenHelp",
+        "CheckKeymap",
         "Diff",
+        "DiffWithBuffer",
         "ToggleDiff",
         "Conflict",
         "CloseConflict",
@@ -1112,6 +1319,12 @@ mod tests {
         "MarkTrailEnd",
         "TrailNext",
         "TrailPrev",
+        "EditColorAtCursor",
+        "RenameLocal",
+        "FoldFunctionBodies",
+        "FoldClassBodies",
+        "FoldImports",
+        "UnfoldAll",
         "Hover",
         "CodeAction",
         "RenameSymbol",
@@ -1129,6 +1342,8 @@ mod tests {
         "OpenReverseSearchInput",
         "SearchNext",
         "SearchPrev",
+        "SearchHistoryPrev",
+        "SearchHistoryNext",
         "Yank",
         "DeleteSelectionNoYank",
         "ChangeSelection",
@@ -1136,6 +1351,7 @@ mod tests {
         "PasteBefore",
         "YankToClipboard",
         "YankMainToClipboard",
+        "CutToClipboard",
         "PasteClipboardAfter",
         "PasteClipboardBefore",
         "SelectRegister",
@@ -1167,9 +1383,16 @@ mod tests {
         "RotateSelectionsForward",
         "RotateSelectionsBackward",
         "TrimSelections",
+        "DuplicateAndCommentOriginal",
+        "DuplicateSelection",
+        "MoveLinesUp",
+        "MoveLinesDown",
         "ToggleSyntaxHighlight",
         "ToggleLspStatus",
         "ToggleInlayHints",
+        "SetLineEndingLf",
+        "SetLineEndingCrlf",
+        "OpenAsText",
         "ReviewNextChunk",
         "ReviewPrevChunk",
         "ReviewStageChunk",
@@ -1215,6 +1438,9 @@ mod tests {
         "RebaseConflictAbort",
         "OpenRun",
         "SpawnClaude",
+        "AgentPermissionAllow",
+        "AgentPermissionAlways",
+        "AgentPermissionDeny",
         "terminal",
         "RunSubmit",
         "RunInterrupt",
@@ -1235,6 +1461,11 @@ mod tests {
         "JumplistPickerPrev",
         "JumplistPickerSelect",
         "JumplistPickerClose",
+        "OpenUndoTree",
+        "UndoTreeNext",
+        "UndoTreePrev",
+        "UndoTreeSelect",
+        "UndoTreeClose",
         "DiagnosticsPickerNext",
         "DiagnosticsPickerPrev",
         "DiagnosticsPickerSelect",
@@ -1251,6 +1482,8 @@ mod tests {
         "CodeSearchSelect",
         "CodeSearchClose",
         "CodeSearchModeToggle",
+        "OpenRegexPlayground",
+        "RegexPlaygroundFocusToggle",
         "CloseWorkspace",
         "ShowCwd",
         "ReloadEnv",
@@ -1271,6 +1504,10 @@ mod tests {
         "PalettePageDown",
         "PaletteScopeToggle",
         "PaletteComplete",
+        "RerunTask",
+        "GotoNextTaskProblem",
+        "GotoPrevTaskProblem",
+        "RunTestAtCursor",
     ];
 
     #[test]
@@ -1692,7 +1929,33 @@ mod tests {
         // + 2 ConflictNextFile, ConflictPrevFile.
         // + 1 ConflictApply.
         // + 1 OpenWorkspaceFileFinder.
-        assert_eq!(all().count(), 397);
+        // + 1 DuplicateAndCommentOriginal.
+        // + 2 OpenRegexPlayground, RegexPlaygroundFocusToggle.
+        // + 3 DuplicateSelection, MoveLinesUp, MoveLinesDown.
+        // + 1 DiffWithBuffer.
+        // + 1 RevertHunk.
+        // + 1 CutToClipboard.
+        // + 4 RunCommand, RunCommandWithSelection, RerunCommand, InsertCommandOutput.
+        // + 4 RunTask, RerunTask, GotoNextTaskProblem, GotoPrevTaskProblem.
+        // + 1 RunTestAtCursor.
+        // + 1 EditColorAtCursor.
+        // + 5 OpenUndoTree, UndoTree Next/Prev/Select/Close.
+        // + 2 SetLineEndingLf, SetLineEndingCrlf.
+        // + 1 OpenAsText.
+        // + 1 RenameLocal.
+        // + 4 FoldFunctionBodies, FoldClassBodies, FoldImports, UnfoldAll.
+        // + 1 RunUserCommand.
+        // + 2 RepeatLastCommand, RunFromHistory.
+        // + 4 TogglePinBuffer, CloseOtherBuffers, AddBufferToGroup, SwitchBufferGroup.
+        // + 1 SetLogLevel.
+        // + 3 AgentPermissionAllow, AgentPermissionAlways, AgentPermissionDeny.
+        // + 3 RunToastAction, DismissToasts, NotificationHistory.
+        // + 2 TogglePerfOverlay, ToggleProfileCapture.
+        // + 2 ToggleCursorLine, ToggleRelativeNumbers.
+        // + 2 SearchHistoryPrev/Next.
+        // + 1 ShowEffectiveConfig.
+        // + 1 CheckKeymap.
+        assert_eq!(all().count(), 451);
     }
 
     #[test]
@@ -88,7 +88,8 @@ fn log_file_stem(stoatty_id: Option<&str>, id: &LogId) -> String {
 /// Point fd 2 at the log file so crates that write raw bytes to stderr
 /// (arboard's debug-build Drop warning is the known case) land in the log
 /// rather than painting over the raw-mode TUI. Best-effort: an open or `dup2`
-/// failure is warn-logged and the original stderr left in place.
+/// failure is warn-logged and the original stderr left in place. Once the log
+/// rotates, raw stderr keeps landing in the renamed `.log.1` backup.
 #[cfg(unix)]
 fn redirect_stderr_to(path: &std::path::Path) {
     use std::os::fd::AsRawFd;
@@ -1,7 +1,9 @@
 pub mod agent_api;
+pub mod config;
 pub mod default;
 pub mod diff;
 pub mod dump;
 pub mod editor;
 pub mod fixture;
+pub mod keymap;
 pub mod query;
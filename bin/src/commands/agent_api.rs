@@ -1,7 +1,7 @@
 use clap::Subcommand;
 use snafu::{whatever, ResultExt, Whatever};
 use std::{
-    io::Write,
+    io::{BufRead, BufReader, Read, Write},
     os::unix::net::UnixStream,
     path::{Path, PathBuf},
 };
@@ -21,6 +21,23 @@ pub enum AgentApiCommand {
         /// first value. Other hooks take none.
         values: Vec<String>,
 
+        /// Target session uid (16-hex, as in STOAT_SESSION). Defaults to the
+        /// socket path in the STOAT_AGENT_SOCK env var injected at spawn.
+        #[arg(long)]
+        session: Option<String>,
+    },
+    /// Ask the owning session whether a tool call may run, for a Claude
+    /// `PreToolUse` hook. Exits 0 when the user allows it and 2, which blocks
+    /// the call, when they deny it or the session goes away.
+    Permission {
+        /// Tool name. Without it the hook payload is read from stdin and the
+        /// tool name and input are taken from it.
+        tool: Option<String>,
+
+        /// What the call would do, shown under the tool name in the prompt.
+        #[arg(long)]
+        detail: Option<String>,
+
         /// Target session uid (16-hex, as in STOAT_SESSION). Defaults to the
         /// socket path in the STOAT_AGENT_SOCK env var injected at spawn.
         #[arg(long)]
@@ -35,9 +52,80 @@ pub fn run(sub: AgentApiCommand) -> Result<(), Whatever> {
             values,
             session,
         } => hook(&name, &values, session.as_deref()),
+        AgentApiCommand::Permission {
+            tool,
+            detail,
+            session,
+        } => permission(tool, detail, session.as_deref()),
     }
 }
 
+fn permission(
+    tool: Option<String>,
+    detail: Option<String>,
+    session: Option<&str>,
+) -> Result<(), Whatever> {
+    let (tool, detail) = match tool {
+        Some(tool) => (tool, detail),
+        None => {
+            let mut payload = String::new();
+            std::io::stdin()
+                .read_to_string(&mut payload)
+                .whatever_context("read hook payload")?;
+            let (tool, from_payload) = parse_hook_payload(&payload)?;
+            (tool, detail.or(from_payload))
+        },
+    };
+    let env_sock = std::env::var("STOAT_AGENT_SOCK").ok();
+    let socket_path = resolve_socket_path(session, env_sock)?;
+    let request = serde_json::json!({ "req": "permission", "tool": tool, "detail": detail });
+    let line = serde_json::to_string(&request).whatever_context("serialize permission request")?;
+    let mut stream =
+        UnixStream::connect(&socket_path).whatever_context("connect to agent socket")?;
+    stream
+        .write_all(line.as_bytes())
+        .whatever_context("write permission request")?;
+    stream
+        .write_all(b"\n")
+        .whatever_context("write permission request terminator")?;
+
+    let mut reply = String::new();
+    let _ = BufReader::new(stream).read_line(&mut reply);
+    if reply_allows(&reply) {
+        return Ok(());
+    }
+    eprintln!("{tool} was denied in stoat");
+    std::process::exit(2);
+}
+
+/// Pull the tool name and a one-line summary of its input out of a Claude hook
+/// payload. The summary is the `command` or `file_path` input when present,
+/// else the whole input as compact JSON.
+fn parse_hook_payload(payload: &str) -> Result<(String, Option<String>), Whatever> {
+    let value: serde_json::Value =
+        serde_json::from_str(payload).whatever_context("parse hook payload")?;
+    let Some(tool) = value.get("tool_name").and_then(|tool| tool.as_str()) else {
+        whatever!("hook payload has no tool_name");
+    };
+    let detail = value.get("tool_input").map(|input| {
+        ["command", "file_path"]
+            .iter()
+            .find_map(|key| input.get(key).and_then(|v| v.as_str()))
+            .map(str::to_string)
+            .unwrap_or_else(|| input.to_string())
+    });
+    Ok((tool.to_string(), detail))
+}
+
+/// True when `line` is the session's `{"decision":"allow"}` reply. Anything
+/// else, including no reply at all, denies.
+fn reply_allows(line: &str) -> bool {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+        return false;
+    };
+    value.get("decision").and_then(|d| d.as_str()) == Some("allow")
+}
+
 fn hook(name: &str, values: &[String], session: Option<&str>) -> Result<(), Whatever> {
     let event = build_event(name, values)?;
     let env_sock = std::env::var("STOAT_AGENT_SOCK").ok();
@@ -147,6 +235,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hook_payload_yields_tool_and_command() {
+        let payload = r#"{"tool_name":"Bash","tool_input":{"command":"rm -rf target"}}"#;
+        assert_eq!(
+            parse_hook_payload(payload).unwrap(),
+            ("Bash".to_string(), Some("rm -rf target".to_string()))
+        );
+        assert!(parse_hook_payload("{}").is_err());
+    }
+
+    #[test]
+    fn only_an_allow_decision_allows() {
+        assert!(reply_allows(r#"{"decision":"allow"}"#));
+        assert!(!reply_allows(r#"{"decision":"deny"}"#));
+        assert!(!reply_allows(""));
+    }
+
     #[test]
     fn missing_target_is_an_error() {
         assert!(resolve_socket_path(None, None).is_err());
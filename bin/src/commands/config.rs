@@ -0,0 +1,158 @@
+use clap::Subcommand;
+use snafu::{whatever, ResultExt, Whatever};
+use std::{collections::HashMap, fs, path::PathBuf};
+use stoat::keymap::{Keymap, KeymapState, StateValue};
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Rewrite a config file in canonical layout: four-space indentation,
+    /// one statement per line, statements grouped by kind.
+    Fmt {
+        /// Config file to format. Defaults to the user config.
+        file: Option<PathBuf>,
+        /// Leave the file alone and fail if formatting would change it.
+        #[arg(long)]
+        check: bool,
+    },
+    /// List every binding on a key sequence, whether its conditions hold in
+    /// the given state, and which one a press runs.
+    Explain {
+        /// Keys as an input string, such as `gg`, `<C-s>` or `<Space>ff`.
+        keys: String,
+        /// Mode to resolve the keys in.
+        #[arg(long, default_value = "normal")]
+        mode: String,
+        /// Further keymap state as `field=value`, such as `pane=run`.
+        #[arg(long = "set", value_name = "FIELD=VALUE")]
+        set: Vec<String>,
+        /// Config file to read. Defaults to the user config, then the
+        /// built-in one.
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+pub fn run(sub: ConfigCommand) -> Result<(), Whatever> {
+    match sub {
+        ConfigCommand::Fmt { file, check } => fmt(file, check),
+        ConfigCommand::Explain {
+            keys,
+            mode,
+            set,
+            file,
+        } => explain(&keys, mode, &set, file),
+    }
+}
+
+fn fmt(file: Option<PathBuf>, check: bool) -> Result<(), Whatever> {
+    let Some(path) = file.or_else(stoat::user_config_path) else {
+        whatever!("no config file given and no user config directory");
+    };
+    let source =
+        fs::read_to_string(&path).whatever_context(format!("read config {}", path.display()))?;
+    let formatted = match stoat_config::format(&source) {
+        Ok(formatted) => formatted,
+        Err(errors) => {
+            eprint!("{}", stoat_config::format_errors(&source, &errors));
+            whatever!("{} does not parse", path.display());
+        },
+    };
+    if formatted == source {
+        return Ok(());
+    }
+    if check {
+        whatever!("{} is not formatted", path.display());
+    }
+    fs::write(&path, formatted).whatever_context(format!("write config {}", path.display()))
+}
+
+/// Keymap fields given on the command line.
+struct Fields(HashMap<String, StateValue>);
+
+impl KeymapState for Fields {
+    fn get(&self, field: &str) -> Option<&StateValue> {
+        self.0.get(field)
+    }
+}
+
+fn explain(
+    keys: &str,
+    mode: String,
+    set: &[String],
+    file: Option<PathBuf>,
+) -> Result<(), Whatever> {
+    let presses = stoat::input_parse::parse_input_sequence(keys)
+        .whatever_context(format!("parse keys {keys:?}"))?;
+    let config = load_config(file)?;
+
+    let mut fields = HashMap::from([("mode".to_string(), StateValue::String(mode.into()))]);
+    for assignment in set {
+        let Some((field, value)) = assignment.split_once('=') else {
+            whatever!("--set {assignment:?} is not FIELD=VALUE");
+        };
+        fields.insert(field.to_string(), state_value(value));
+    }
+
+    let keymap = Keymap::compile(&config);
+    let candidates = keymap.explain(&Fields(fields), &presses);
+    if candidates.is_empty() {
+        println!("nothing is bound to {keys}");
+        return Ok(());
+    }
+    for candidate in candidates {
+        let verdict = match (candidate.won, candidate.active) {
+            (true, _) => "wins",
+            (false, true) => "outranked",
+            (false, false) => "inactive",
+        };
+        let conditions = if candidate.predicates.is_empty() {
+            "always".to_string()
+        } else {
+            candidate
+                .predicates
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" && ")
+        };
+        let actions: Vec<&str> = candidate.actions.iter().map(|a| a.name.as_str()).collect();
+        println!(
+            "{verdict:<9} {}  [{conditions}]  -> {}  (specificity {}, block {})",
+            candidate.label,
+            actions.join(", "),
+            candidate.specificity,
+            candidate.block,
+        );
+    }
+    Ok(())
+}
+
+/// Parse `file`, or the user config when none is given, falling back to the
+/// built-in config when there is no user config. Parse errors go to stderr.
+pub(crate) fn load_config(file: Option<PathBuf>) -> Result<stoat_config::Config, Whatever> {
+    let source = match file.or_else(|| stoat::user_config_path().filter(|path| path.exists())) {
+        Some(path) => {
+            fs::read_to_string(&path).whatever_context(format!("read config {}", path.display()))?
+        },
+        None => stoat::app::DEFAULT_KEYMAP.to_string(),
+    };
+    let (config, errors) = stoat_config::parse(&source);
+    let Some(config) = config.filter(|_| errors.is_empty()) else {
+        eprint!("{}", stoat_config::format_errors(&source, &errors));
+        whatever!("config does not parse");
+    };
+    Ok(config)
+}
+
+/// A `--set` value read the way the config reads a literal: `true` and
+/// `false` are bools, numbers are numbers, and anything else is a string.
+fn state_value(text: &str) -> StateValue {
+    match text {
+        "true" => StateValue::Bool(true),
+        "false" => StateValue::Bool(false),
+        _ => match text.parse::<f64>() {
+            Ok(number) => StateValue::Number(number),
+            Err(_) => StateValue::String(text.into()),
+        },
+    }
+}
@@ -1,3 +1,4 @@
+use crate::commands::diff::DiffOpen;
 use clap::{ArgAction, CommandFactory, Parser, Subcommand, ValueHint};
 use crossterm::event::{Event, KeyEvent};
 use snafu::{whatever, ResultExt, Whatever};
@@ -77,8 +78,9 @@ enum Command {
     /// current repo for changes against HEAD and renders a diff
     /// for each changed path. With `--git`, acts as the
     /// `GIT_EXTERNAL_DIFF` adapter using the seven path arguments
-    /// git supplies; positional args are not accepted in the
-    /// default mode.
+    /// git supplies. `stoat diff <a> <b>` and `stoat diff --git
+    /// <file>` instead open the editor in the side-by-side diff
+    /// view of the two files, or of the file against HEAD.
     Diff(crate::commands::diff::DiffArgs),
     /// Thin client the owned Claude subshell's hooks invoke to push status
     /// into the owning session.
@@ -102,6 +104,16 @@ enum Command {
     /// lists the catalog.
     Fixture(FixtureArgs),
 
+    /// Work with the config file.
+    Config {
+        #[command(subcommand)]
+        sub: crate::commands::config::ConfigCommand,
+    },
+    /// Work with the keymap.
+    Keymap {
+        #[command(subcommand)]
+        sub: crate::commands::keymap::KeymapCommand,
+    },
     /// Print a shell completion script to stdout, e.g. `stoat completions fish >
     /// ~/.config/fish/completions/stoat.fish` (zsh and bash install the same
     /// way).
@@ -122,11 +134,16 @@ pub fn run(args: Args) -> Result<(), Whatever> {
 
     match command {
         Some(Command::Dump { sub }) => crate::commands::dump::run(sub),
-        Some(Command::Diff(args)) => crate::commands::diff::run(args),
+        Some(Command::Diff(args)) => match args.open_target() {
+            Some(open) => run_tui(text_proto_log, common, working_dir, TuiStart::Diff(open)),
+            None => crate::commands::diff::run(args),
+        },
         Some(Command::AgentApi { sub }) => crate::commands::agent_api::run(sub),
         Some(Command::Editor { file }) => crate::commands::editor::run(file),
         Some(Command::Query { sub }) => crate::commands::query::run(sub),
         Some(Command::Fixture(fixture)) => run_fixture(fixture, text_proto_log, common),
+        Some(Command::Config { sub }) => crate::commands::config::run(sub),
+        Some(Command::Keymap { sub }) => crate::commands::keymap::run(sub),
         Some(Command::Completions { shell }) => {
             clap_complete::generate(shell, &mut Args::command(), "stoat", &mut std::io::stdout());
             Ok(())
@@ -167,6 +184,7 @@ enum TuiStart {
     Review,
     Conflict,
     Files,
+    Diff(crate::commands::diff::DiffOpen),
 }
 
 fn run_tui(
@@ -230,21 +248,36 @@ fn run_tui(
         mouse_capture: None,
         scrolloff: None,
         editor_line_numbers: None,
+        editor_cursor_line: None,
         editor_minimap: None,
         editor_wrap: None,
         editor_wrap_column: None,
+        editor_wrap_break: None,
+        editor_wrap_indent: None,
         ui_tab_bar: None,
         ui_inactive_dim: None,
+        ui_which_key_delay: None,
+        editor_cursor_blink: None,
+        editor_cursor_animation: None,
         highlight_retention: None,
         terminal_shell: None,
         terminal_args: None,
         direnv_load: None,
         direnv_reload_on_cd: None,
         direnv_unset_on_exit: None,
+        spell_enabled: None,
+        spell_dictionary: None,
+        clipboard_mirror: None,
+        state_format: None,
+        test_lenses: None,
+        color_swatches: None,
+        editor_breadcrumbs: None,
+        editor_highlight_occurrences: None,
         review_follow: None,
         review_rebase_head: None,
         review_precompute: None,
         mode_badges: std::collections::BTreeMap::new(),
+        cursor_shapes: std::collections::BTreeMap::new(),
         lsp_servers: std::collections::BTreeMap::new(),
         lsp_server_lists: std::collections::BTreeMap::new(),
         lsp_commands: std::collections::BTreeMap::new(),
@@ -252,6 +285,10 @@ fn run_tui(
         lsp_except: std::collections::BTreeMap::new(),
         finder_scopes: std::collections::BTreeMap::new(),
         finder_default_scope: None,
+        task_commands: std::collections::BTreeMap::new(),
+        task_matchers: std::collections::BTreeMap::new(),
+        palette_aliases: std::collections::BTreeMap::new(),
+        commands: std::collections::BTreeMap::new(),
     };
 
     // Materialize a requested fixture and switch into it before resolving the
@@ -304,7 +341,7 @@ fn run_tui(
             .flatten()
             .unwrap_or_else(|| cwd.clone())
     } else {
-        cwd
+        cwd.clone()
     };
 
     rt.block_on(async {
@@ -357,12 +394,27 @@ fn run_tui(
         match start {
             TuiStart::Review => stoat.open_working_tree_diff(),
             TuiStart::Conflict => stoat.open_conflict_view(),
+            TuiStart::Diff(DiffOpen::Files { base, path }) => {
+                stoat.open_file_diff(&cwd.join(base), &cwd.join(path))
+            },
+            TuiStart::Diff(DiffOpen::Head(path)) => {
+                stoat.open_file(&cwd.join(path));
+                stoat.toggle_diff_view();
+            },
             TuiStart::Files => {
-                for (i, path) in files.iter().enumerate() {
+                // Resolved here, after --fixture and --working-dir moved the cwd,
+                // so a literal `name:12` file is found where it opens.
+                let targets = stoat_cli::file_targets(&files, |path| path.exists());
+                for (i, target) in targets.iter().enumerate() {
                     if i > 0 {
                         stoat.active_workspace_mut().panes.split(Axis::Vertical);
                     }
-                    stoat.open_file(path);
+                    match target.line {
+                        Some(line) => {
+                            stoat.open_file_at(&target.path, line, target.column.unwrap_or(1))
+                        },
+                        None => stoat.open_file(&target.path),
+                    }
                 }
             },
         }
@@ -466,6 +518,7 @@ fn read_user_themes(dir: PathBuf) -> Vec<(String, String)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::config::ConfigCommand;
     use stoat_scheduler::TestScheduler;
 
     #[test]
@@ -514,6 +567,87 @@ mod tests {
         assert_eq!(args.name.as_deref(), Some("rust-lsp"));
     }
 
+    #[test]
+    fn config_fmt_parses_a_file_and_check() {
+        let args = Args::try_parse_from(["stoat", "config", "fmt", "--check", "a.stcfg"])
+            .expect("parse config fmt");
+        let Some(Command::Config {
+            sub: ConfigCommand::Fmt { file, check },
+        }) = args.command
+        else {
+            panic!("expected config fmt subcommand");
+        };
+        assert_eq!(file.as_deref(), Some(std::path::Path::new("a.stcfg")));
+        assert!(check);
+    }
+
+    #[test]
+    fn config_explain_parses_keys_and_state() {
+        let args = Args::try_parse_from([
+            "stoat", "config", "explain", "<Space>f", "--mode", "space", "--set", "pane=run",
+        ])
+        .expect("parse config explain");
+        let Some(Command::Config {
+            sub: ConfigCommand::Explain {
+                keys, mode, set, ..
+            },
+        }) = args.command
+        else {
+            panic!("expected config explain subcommand");
+        };
+        assert_eq!(keys, "<Space>f");
+        assert_eq!(mode, "space");
+        assert_eq!(set, ["pane=run"]);
+    }
+
+    #[test]
+    fn diff_positionals_pick_the_editor_or_stdout() {
+        let open = |argv: &[&str]| {
+            let Some(Command::Diff(args)) = Args::try_parse_from(argv).expect("parse").command
+            else {
+                panic!("expected diff subcommand");
+            };
+            args.open_target()
+        };
+        assert_eq!(
+            open(&["stoat", "diff", "a.rs", "b.rs"]),
+            Some(DiffOpen::Files {
+                base: PathBuf::from("a.rs"),
+                path: PathBuf::from("b.rs"),
+            }),
+        );
+        assert_eq!(
+            open(&["stoat", "diff", "--git", "a.rs"]),
+            Some(DiffOpen::Head(PathBuf::from("a.rs"))),
+        );
+        assert_eq!(open(&["stoat", "diff"]), None);
+        assert_eq!(
+            open(&["stoat", "diff", "--git", "p", "o", "1", "m", "n", "2", "m"]),
+            None,
+            "seven args stay the GIT_EXTERNAL_DIFF adapter",
+        );
+    }
+
+    #[test]
+    fn keymap_dump_parses_the_format() {
+        use crate::commands::keymap::{DumpFormat, KeymapCommand};
+        let format = |argv: &[&str]| {
+            let Some(Command::Keymap {
+                sub: KeymapCommand::Dump { format, .. },
+            }) = Args::try_parse_from(argv).expect("parse").command
+            else {
+                panic!("expected keymap dump subcommand");
+            };
+            format
+        };
+        assert_eq!(format(&["stoat", "keymap", "dump"]), DumpFormat::Markdown);
+        assert_eq!(
+            format(&["stoat", "keymap", "dump", "--format", "json"]),
+            DumpFormat::Json
+        );
+        assert!(Args::try_parse_from(["stoat", "keymap", "dump", "--format", "yaml"]).is_err());
+    }
+
     #[test]
     fn conflict_subcommand_parses() {
         let args = Args::try_parse_from(["stoat", "conflict"]).expect("parse conflict");
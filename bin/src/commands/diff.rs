@@ -65,12 +65,38 @@ pub struct DiffArgs {
     #[arg(long)]
     pub language: Option<String>,
 
-    /// Positional args. Required (exactly seven) with `--git`,
-    /// where they supply the `GIT_EXTERNAL_DIFF` parameters in
-    /// order. Rejected in the default (workspace-scan) mode.
+    /// Positional args. Seven with `--git` supply the
+    /// `GIT_EXTERNAL_DIFF` parameters in order. One with `--git`
+    /// opens that file in the editor diffed against HEAD, and two
+    /// without it open the second file diffed against the first.
+    /// No others are accepted.
     pub git_args: Vec<String>,
 }
 
+/// The editor session `stoat diff` opens instead of printing to stdout.
+#[derive(Debug, PartialEq)]
+pub enum DiffOpen {
+    /// `stoat diff <a> <b>`: `path` in the right column against `base`.
+    Files { base: PathBuf, path: PathBuf },
+    /// `stoat diff --git <file>`: the file against its HEAD version.
+    Head(PathBuf),
+}
+
+impl DiffArgs {
+    /// The editor session these args ask for, or `None` when they describe
+    /// stdout rendering (a workspace scan or the `GIT_EXTERNAL_DIFF` adapter).
+    pub fn open_target(&self) -> Option<DiffOpen> {
+        match (self.git, self.git_args.as_slice()) {
+            (true, [file]) => Some(DiffOpen::Head(PathBuf::from(file))),
+            (false, [base, path]) => Some(DiffOpen::Files {
+                base: PathBuf::from(base),
+                path: PathBuf::from(path),
+            }),
+            _ => None,
+        }
+    }
+}
+
 pub fn run(args: DiffArgs) -> Result<(), Whatever> {
     // FIXME: --language is parsed but not threaded; per-file detection
     // via `LanguageRegistry::for_path` covers default and --git modes.
@@ -146,7 +172,7 @@ pub fn run_with_io<W: Write>(
     } else {
         if !args.git_args.is_empty() {
             return Err(WriteError::Other(FromString::without_source(format!(
-                "positional args are only valid with --git; got {} unexpected arg(s)",
+                "expected two files to compare or none; got {} positional arg(s)",
                 args.git_args.len()
             ))));
         }
@@ -214,8 +240,8 @@ fn read_git_external_inputs(
 ) -> Result<ReviewFileInput, Whatever> {
     if git_args.len() != 7 {
         whatever!(
-            "--git mode requires exactly 7 positional args (path old-file old-hex old-mode \
-             new-file new-hex new-mode); got {}",
+            "--git mode requires one file or exactly 7 positional args (path old-file \
+             old-hex old-mode new-file new-hex new-mode); got {}",
             git_args.len()
         );
     }
@@ -0,0 +1,50 @@
+use clap::{Subcommand, ValueEnum};
+use snafu::{ResultExt, Whatever};
+use std::path::PathBuf;
+use stoat::{
+    help::{keymap_reference, reference_markdown},
+    keymap::Keymap,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum KeymapCommand {
+    /// Print every keybinding of the loaded config, grouped by mode.
+    Dump {
+        /// Output format.
+        #[arg(long, value_enum, default_value_t = DumpFormat::Markdown)]
+        format: DumpFormat,
+        /// Config file to read. Defaults to the user config, then the
+        /// built-in one.
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+/// How `keymap dump` prints the reference.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// A `##` section and table per mode.
+    Markdown,
+    /// An array of `{mode, bindings}` groups.
+    Json,
+}
+
+pub fn run(sub: KeymapCommand) -> Result<(), Whatever> {
+    match sub {
+        KeymapCommand::Dump { format, file } => dump(format, file),
+    }
+}
+
+fn dump(format: DumpFormat, file: Option<PathBuf>) -> Result<(), Whatever> {
+    let config = crate::commands::config::load_config(file)?;
+    let groups = keymap_reference(&Keymap::compile(&config));
+    match format {
+        DumpFormat::Markdown => print!("{}", reference_markdown(&groups)),
+        DumpFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&groups).whatever_context("serialize keymap")?;
+            println!("{json}");
+        },
+    }
+    Ok(())
+}
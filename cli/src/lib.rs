@@ -5,7 +5,7 @@
 //! [`CommonArgs::to_argv`] to forward to the stoat child it launches.
 
 use clap::{builder::PossibleValuesParser, Args, Subcommand, ValueHint};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// The deterministic fixtures both binaries expose, as `(name, one-line
 /// description)`, in the order `stoat fixture ls` prints them.
@@ -46,6 +46,14 @@ pub const FIXTURES: &[(&str, &str)] = &[
         "rust-diff",
         "the rust-lsp crate plus a live staged and unstaged rust diff",
     ),
+    (
+        "nested-repos",
+        "a committed submodule and an unregistered nested repo",
+    ),
+    (
+        "upstream",
+        "a branch diverged from its local bare remote, plus tags",
+    ),
 ];
 
 /// A clap value parser accepting only the [`FIXTURES`] names, so an unknown
@@ -75,8 +83,9 @@ pub fn ls_text() -> String {
 /// selectors, and the `--inputs`/`--timeout` scripted-run controls.
 #[derive(Args, Debug, PartialEq)]
 pub struct CommonArgs {
-    /// Files to open.
-    #[arg(help = "Files to open", value_hint = ValueHint::FilePath)]
+    /// Files to open. A `path:line[:col]` suffix or a preceding `+line`
+    /// argument opens the file with the cursor there; see [`file_targets`].
+    #[arg(help = "Files to open, as PATH[:LINE[:COL]] or +LINE PATH", value_hint = ValueHint::FilePath)]
     pub files: Vec<PathBuf>,
 
     /// Restore the most-recently-used workspace for this repository instead of
@@ -129,6 +138,89 @@ pub enum FixtureSub {
     Ls,
 }
 
+/// A file positional resolved into the path to open and the 1-based position
+/// to place the cursor at, if one was given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileTarget {
+    pub path: PathBuf,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+/// Resolve the [`CommonArgs::files`] positionals into the files to open and
+/// where to put the cursor in each.
+///
+/// `path:line` and `path:line:col` split the position off the path unless
+/// `exists` reports the whole argument as a real file, so a file whose name
+/// ends in `:12` still opens as itself. A `+line` argument applies to the file
+/// after it, or to the one before it when it comes last.
+pub fn file_targets(files: &[PathBuf], exists: impl Fn(&Path) -> bool) -> Vec<FileTarget> {
+    let mut targets: Vec<FileTarget> = Vec::new();
+    let mut pending_line = None;
+    for arg in files {
+        if let Some(line) = plus_line(arg) {
+            pending_line = Some(line);
+            continue;
+        }
+        let mut target = split_position(arg, &exists);
+        if let Some(line) = pending_line.take() {
+            target.line = Some(line);
+            target.column = None;
+        }
+        targets.push(target);
+    }
+    if let (Some(line), Some(last)) = (pending_line, targets.last_mut()) {
+        last.line = Some(line);
+        last.column = None;
+    }
+    targets
+}
+
+/// The line of a `+N` argument, or `None` for anything else.
+fn plus_line(arg: &Path) -> Option<u32> {
+    arg.to_str()?.strip_prefix('+')?.parse().ok()
+}
+
+/// Split a trailing `:line` or `:line:col` off `arg`.
+fn split_position(arg: &Path, exists: &impl Fn(&Path) -> bool) -> FileTarget {
+    let whole = FileTarget {
+        path: arg.to_path_buf(),
+        line: None,
+        column: None,
+    };
+    let Some(text) = arg.to_str() else {
+        return whole;
+    };
+    if exists(arg) {
+        return whole;
+    }
+    let mut parts = text.rsplitn(3, ':');
+    let last = parts.next().and_then(|s| s.parse::<u32>().ok());
+    let middle = parts.next();
+    let rest = parts.next();
+    match (last, middle, rest) {
+        (Some(col), Some(line), Some(path)) if !path.is_empty() => match line.parse() {
+            Ok(line) => FileTarget {
+                path: PathBuf::from(path),
+                line: Some(line),
+                column: Some(col),
+            },
+            // Only the last segment is numeric, so the middle belongs to the path.
+            Err(_) => FileTarget {
+                path: PathBuf::from(format!("{path}:{line}")),
+                line: Some(col),
+                column: None,
+            },
+        },
+        (Some(line), Some(path), None) if !path.is_empty() => FileTarget {
+            path: PathBuf::from(path),
+            line: Some(line),
+            column: None,
+        },
+        _ => whole,
+    }
+}
+
 /// Parse and validate a `--timeout` value, rejecting non-finite or negative
 /// seconds so a bad duration fails at parse time.
 fn parse_timeout(value: &str) -> Result<f64, String> {
@@ -246,6 +338,44 @@ mod tests {
         assert!(Harness::try_parse_from(["prog", "--fixture", "nonesuch"]).is_err());
     }
 
+    #[test]
+    fn file_targets_split_positions_and_plus_lines() {
+        let args = Harness::try_parse_from([
+            "prog",
+            "src/main.rs:120:5",
+            "+7",
+            "lib.rs",
+            "weird:name.rs:3",
+            "notes.txt",
+            "+9",
+        ])
+        .expect("parse");
+        let target = |path: &str, line, column| super::FileTarget {
+            path: PathBuf::from(path),
+            line,
+            column,
+        };
+        assert_eq!(
+            super::file_targets(&args.common.files, |_| false),
+            vec![
+                target("src/main.rs", Some(120), Some(5)),
+                target("lib.rs", Some(7), None),
+                target("weird:name.rs", Some(3), None),
+                target("notes.txt", Some(9), None),
+            ],
+        );
+    }
+
+    #[test]
+    fn file_targets_keep_an_existing_path_whole() {
+        let args = Harness::try_parse_from(["prog", "log:12"]).expect("parse");
+        let targets = super::file_targets(&args.common.files, |path| {
+            path == std::path::Path::new("log:12")
+        });
+        assert_eq!(targets[0].path, PathBuf::from("log:12"));
+        assert_eq!(targets[0].line, None);
+    }
+
     #[test]
     fn ls_text_lists_every_fixture() {
         let text = super::ls_text();
@@ -49,14 +49,38 @@ pub enum Statement {
     Binding(Binding),
     Let(LetBinding),
     FnDecl(FnDecl),
-    FnCall(Spanned<String>),
+    FnCall(FnCall),
     PredicateBlock(PredicateBlock),
+    Alias(Alias),
+    Command(CommandDecl),
+    Unbind(Unbind),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Setting {
     pub path: Vec<Spanned<String>>,
-    pub value: Spanned<Value>,
+    pub value: Spanned<Expr>,
+}
+
+/// A palette alias, `alias "format document" -> Format;`. The quoted name
+/// matches in the command palette alongside the action's own name, and picking
+/// it runs the named action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alias {
+    pub name: Spanned<String>,
+    pub action: Spanned<String>,
+}
+
+/// A user command, `command grep_notes(pattern) "Search the notes" ->
+/// Search($pattern);`. The palette lists it by name and runs its actions in
+/// order, with each parameter's value standing in for `$param` in their
+/// arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandDecl {
+    pub name: Spanned<String>,
+    pub params: Vec<Spanned<String>>,
+    pub description: Option<Spanned<String>>,
+    pub action: Spanned<ActionExpr>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -68,9 +92,26 @@ pub struct LetBinding {
 #[derive(Debug, Clone, PartialEq)]
 pub struct FnDecl {
     pub name: Spanned<String>,
+    /// Parameter names. In the body, a named key spelled like a parameter
+    /// stands for the chord the call passes in its place.
+    pub params: Vec<Spanned<String>>,
     pub body: Vec<Spanned<Statement>>,
 }
 
+/// A call of a declared `fn`, `motions(Ctrl);`, with one chord per parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FnCall {
+    pub name: Spanned<String>,
+    pub args: Vec<Spanned<KeyPart>>,
+}
+
+/// `unbind g g;`, dropping the bindings on these chords made before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unbind {
+    pub key: Spanned<KeyPart>,
+    pub then: Vec<Spanned<KeyPart>>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct PredicateBlock {
     pub predicate: Spanned<Predicate>,
@@ -80,6 +121,9 @@ pub struct PredicateBlock {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Binding {
     pub key: Spanned<KeyPart>,
+    /// Chords pressed after `key` to complete a sequence such as `g g`.
+    /// Empty for a single-chord binding.
+    pub then: Vec<Spanned<KeyPart>>,
     pub action: Spanned<ActionExpr>,
 }
 
@@ -226,6 +270,61 @@ pub enum Expr {
         else_expr: Box<Spanned<Expr>>,
     },
     Variable(String),
+    /// `left op right`: arithmetic on numbers, or `+` joining strings.
+    Binary {
+        op: BinOp,
+        left: Box<Spanned<Expr>>,
+        right: Box<Spanned<Expr>>,
+    },
+    /// `match scrutinee { pattern => value, ... }`: the value of the first arm
+    /// whose pattern equals the scrutinee.
+    Match {
+        scrutinee: Box<Spanned<Expr>>,
+        arms: Vec<MatchArm>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Spanned<Pattern>,
+    pub value: Spanned<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// Matches a scrutinee equal to the value.
+    Value(Value),
+    /// `_`, matching anything.
+    Wildcard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl BinOp {
+    /// How tightly the operator binds: `*` and `/` before `+` and `-`.
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinOp::Add | BinOp::Sub => 1,
+            BinOp::Mul | BinOp::Div => 2,
+        }
+    }
+}
+
+impl fmt::Display for BinOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BinOp::Add => "+",
+            BinOp::Sub => "-",
+            BinOp::Mul => "*",
+            BinOp::Div => "/",
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
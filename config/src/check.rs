@@ -0,0 +1,435 @@
+//! Semantic checks over a parsed [`Config`].
+//!
+//! The parser accepts any well-formed config, so a misspelled action, a
+//! setting no code reads, or a number where an action wants a string only
+//! shows up when the binding silently does nothing. [`check`] walks the tree
+//! after parsing and reports each of these against its span:
+//!
+//! - binding, alias and command actions are looked up in [`stoat_action::registry`], and their
+//!   arguments are matched against the action's parameters by count, name and kind;
+//! - `$name` arguments must name a value the keymap supplies, or in a `command` one of its
+//!   parameters;
+//! - `fn` calls must name a declared `fn` and pass one chord per parameter;
+//! - `on init` settings are matched against [`settings_schema`] by path and value shape, after
+//!   evaluating their expressions against the block's `let` bindings.
+//!
+//! The reports are [`ParseError`]s so [`crate::format_errors`] renders them
+//! like syntax errors, but a config that fails them still loads.
+
+use crate::{
+    ast::{Action, ActionExpr, Arg, Config, EventType, Setting, Span, Spanned, Statement, Value},
+    error::ParseError,
+    eval::{builtins, eval},
+    schema::{settings_schema, PathSeg, SettingDef, ValueShape},
+};
+use std::collections::HashMap;
+use stoat_action::ParamKind;
+
+/// Actions the keymap runs itself rather than through the registry, with the
+/// number of arguments each takes.
+const KEYMAP_ACTIONS: &[(&str, usize)] = &[("SetMode", 1), ("SetVar", 2)];
+
+/// `$name` values an action argument may refer to, with the kind each
+/// resolves to. `$num` is the digit a `num` placeholder key matched.
+const STATE_REFS: &[(&str, ParamKind)] = &[("num", ParamKind::Number)];
+
+/// Every semantic problem in `config`, in source order within each block.
+pub fn check(config: &Config) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    // The keymap gathers every key block's `fn`s before expanding calls, so
+    // a call may name one declared anywhere.
+    let fns: HashMap<&str, usize> = config
+        .blocks
+        .iter()
+        .filter(|block| block.node.event == EventType::Key)
+        .flat_map(|block| &block.node.statements)
+        .filter_map(|statement| match &statement.node {
+            Statement::FnDecl(decl) => Some((decl.name.node.as_str(), decl.params.len())),
+            _ => None,
+        })
+        .collect();
+    for block in &config.blocks {
+        let init = block.node.event == EventType::Init;
+        let mut env = builtins();
+        check_statements(&block.node.statements, init, &mut env, &fns, &mut errors);
+    }
+    errors
+}
+
+fn check_statements(
+    statements: &[Spanned<Statement>],
+    init: bool,
+    env: &mut HashMap<String, Value>,
+    fns: &HashMap<&str, usize>,
+    errors: &mut Vec<ParseError>,
+) {
+    for statement in statements {
+        match &statement.node {
+            Statement::Binding(binding) => check_action_expr(&binding.action, &[], errors),
+            Statement::PredicateBlock(block) => {
+                check_statements(&block.body, init, env, fns, errors)
+            },
+            Statement::FnDecl(decl) => check_statements(&decl.body, init, env, fns, errors),
+            Statement::FnCall(call) => match fns.get(call.name.node.as_str()) {
+                None => errors.push(
+                    ParseError::new(
+                        call.name.span.clone(),
+                        format!("unknown function `{}`", call.name.node),
+                    )
+                    .with_label("no `fn` declares this name"),
+                ),
+                Some(&count) if count != call.args.len() => errors.push(ParseError::new(
+                    statement.span.clone(),
+                    format!(
+                        "`{}` takes {count} argument{}, got {}",
+                        call.name.node,
+                        if count == 1 { "" } else { "s" },
+                        call.args.len()
+                    ),
+                )),
+                Some(_) => {},
+            },
+            Statement::Let(binding) if init => match eval(&binding.value, env) {
+                Ok(value) => {
+                    env.insert(binding.name.node.clone(), value);
+                },
+                Err(error) => errors.push(error),
+            },
+            Statement::Setting(setting) if init => check_setting(setting, env, errors),
+            Statement::Alias(alias) if init => {
+                if stoat_action::registry::lookup_alias(&alias.action.node).is_none() {
+                    errors.push(
+                        ParseError::new(
+                            alias.action.span.clone(),
+                            format!("unknown action `{}`", alias.action.node),
+                        )
+                        .with_label("not a registered action"),
+                    );
+                }
+            },
+            Statement::Command(command) if init => {
+                let params: Vec<&str> = command.params.iter().map(|p| p.node.as_str()).collect();
+                check_action_expr(&command.action, &params, errors);
+            },
+            Statement::Setting(_)
+            | Statement::Alias(_)
+            | Statement::Command(_)
+            | Statement::Let(_)
+            | Statement::Unbind(_) => {},
+        }
+    }
+}
+
+/// Check each action of `expr`, where `params` names the `$param` strings a
+/// `command` body may refer to.
+fn check_action_expr(expr: &Spanned<ActionExpr>, params: &[&str], errors: &mut Vec<ParseError>) {
+    match &expr.node {
+        ActionExpr::Single(action) => check_action(action, &expr.span, params, errors),
+        ActionExpr::Sequence(actions) => {
+            for action in actions {
+                check_action(&action.node, &action.span, params, errors);
+            }
+        },
+    }
+}
+
+fn check_action(action: &Action, span: &Span, locals: &[&str], errors: &mut Vec<ParseError>) {
+    if let Some(&(_, count)) = KEYMAP_ACTIONS.iter().find(|(name, _)| *name == action.name) {
+        if action.args.len() != count {
+            errors.push(ParseError::new(
+                span.clone(),
+                format!(
+                    "`{}` takes {count} argument{}, got {}",
+                    action.name,
+                    if count == 1 { "" } else { "s" },
+                    action.args.len()
+                ),
+            ));
+        }
+        for arg in &action.args {
+            check_arg_value(arg_value(&arg.node), None, locals, errors);
+        }
+        return;
+    }
+
+    let Some(entry) = stoat_action::registry::lookup(&action.name) else {
+        errors.push(
+            ParseError::new(span.clone(), format!("unknown action `{}`", action.name))
+                .with_label("not a registered action"),
+        );
+        return;
+    };
+    let params = entry.def.params();
+
+    // The keymap passes arguments to the action in order, so a named argument
+    // still fills the parameter at its position.
+    for (ix, arg) in action.args.iter().enumerate() {
+        let Some(param) = params.get(ix) else {
+            errors.push(
+                ParseError::new(
+                    arg.span.clone(),
+                    format!(
+                        "`{}` takes {} argument{}, got {}",
+                        action.name,
+                        params.len(),
+                        if params.len() == 1 { "" } else { "s" },
+                        action.args.len()
+                    ),
+                )
+                .with_label("extra argument"),
+            );
+            break;
+        };
+        if let Arg::Named { name, .. } = &arg.node
+            && !params.iter().any(|p| p.name == name.node)
+        {
+            errors.push(ParseError::new(
+                name.span.clone(),
+                format!("`{}` has no parameter `{}`", action.name, name.node),
+            ));
+        }
+        check_arg_value(
+            arg_value(&arg.node),
+            Some((param.name, param.kind)),
+            locals,
+            errors,
+        );
+    }
+    if let Some(missing) = params
+        .iter()
+        .skip(action.args.len())
+        .find(|param| param.required)
+    {
+        errors.push(ParseError::new(
+            span.clone(),
+            format!(
+                "`{}` is missing required argument `{}`",
+                action.name, missing.name
+            ),
+        ));
+    }
+}
+
+fn arg_value(arg: &Arg) -> &Spanned<Value> {
+    match arg {
+        Arg::Positional(value) | Arg::Named { value, .. } => value,
+    }
+}
+
+/// Check an argument value resolves and, when `expected` names the parameter
+/// it fills, that it has the parameter's kind. A `$name` in `locals` is a
+/// command parameter, which is always a string.
+fn check_arg_value(
+    value: &Spanned<Value>,
+    expected: Option<(&str, ParamKind)>,
+    locals: &[&str],
+    errors: &mut Vec<ParseError>,
+) {
+    let kind = match &value.node {
+        Value::String(_) | Value::Ident(_) => ParamKind::String,
+        Value::Number(_) => ParamKind::Number,
+        Value::Bool(_) => ParamKind::Bool,
+        Value::StateRef(name) if locals.contains(&name.as_str()) => ParamKind::String,
+        Value::StateRef(name) => match STATE_REFS.iter().find(|(known, _)| known == name) {
+            Some(&(_, kind)) => kind,
+            None => {
+                errors.push(
+                    ParseError::new(value.span.clone(), format!("unknown state `${name}`"))
+                        .with_label("the keymap supplies no value by this name"),
+                );
+                return;
+            },
+        },
+        Value::Enum { .. } | Value::Array(_) | Value::Map(_) => {
+            errors.push(ParseError::new(
+                value.span.clone(),
+                "action arguments must be a string, number or bool",
+            ));
+            return;
+        },
+    };
+    if let Some((name, expected)) = expected
+        && kind != expected
+    {
+        errors.push(
+            ParseError::new(
+                value.span.clone(),
+                format!("argument `{name}` expects a {expected}"),
+            )
+            .with_label(format!("this is a {kind}")),
+        );
+    }
+}
+
+fn check_setting(setting: &Setting, env: &HashMap<String, Value>, errors: &mut Vec<ParseError>) {
+    let segments: Vec<&str> = setting.path.iter().map(|seg| seg.node.as_str()).collect();
+    let path = segments.join(".");
+    let span = match (setting.path.first(), setting.path.last()) {
+        (Some(first), Some(last)) => first.span.start..last.span.end,
+        _ => setting.value.span.clone(),
+    };
+    let Some(def) = settings_schema()
+        .iter()
+        .find(|def| path_matches(def, &segments))
+    else {
+        errors.push(
+            ParseError::new(span, format!("unknown setting `{path}`"))
+                .with_label("not a recognized setting"),
+        );
+        return;
+    };
+    let value = match eval(&setting.value, env) {
+        Ok(value) => value,
+        Err(error) => {
+            errors.push(error);
+            return;
+        },
+    };
+    if let Some(expected) = shape_mismatch(def.shape, &value) {
+        errors.push(
+            ParseError::new(
+                setting.value.span.clone(),
+                format!("`{path}` expects {expected}"),
+            )
+            .with_label(format!("default is `{}`", def.default)),
+        );
+    }
+}
+
+fn path_matches(def: &SettingDef, segments: &[&str]) -> bool {
+    def.path.len() == segments.len()
+        && def
+            .path
+            .iter()
+            .zip(segments)
+            .all(|(seg, actual)| match seg {
+                PathSeg::Lit(lit) => lit == actual,
+                PathSeg::Wildcard(_) => true,
+            })
+}
+
+/// What `shape` expects, when `value` does not fit it.
+///
+/// Enum settings also take a bool, since several read `true` and `false` as
+/// shorthands for their variants.
+fn shape_mismatch(shape: ValueShape, value: &Value) -> Option<String> {
+    let fits = match shape {
+        ValueShape::Bool => matches!(value, Value::Bool(_)),
+        ValueShape::Number => matches!(value, Value::Number(_)),
+        ValueShape::String => matches!(value, Value::String(_) | Value::Ident(_)),
+        ValueShape::Enum(variants) => match value {
+            Value::Ident(text) | Value::String(text) => variants.contains(&text.as_str()),
+            Value::Bool(_) => true,
+            _ => false,
+        },
+        ValueShape::StringArray => match value {
+            Value::Array(items) => items
+                .iter()
+                .all(|item| matches!(item.node, Value::String(_) | Value::Ident(_))),
+            _ => false,
+        },
+    };
+    if fits {
+        return None;
+    }
+    Some(match shape {
+        ValueShape::Bool => "`true` or `false`".to_string(),
+        ValueShape::Number => "a number".to_string(),
+        ValueShape::String => "a string".to_string(),
+        ValueShape::Enum(variants) => format!("one of {}", variants.join(", ")),
+        ValueShape::StringArray => "an array of strings".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use crate::parse;
+
+    fn messages(source: &str) -> Vec<String> {
+        let (config, errors) = parse(source);
+        assert!(errors.is_empty(), "`{source}` failed to parse: {errors:?}");
+        check(&config.expect("parsed config"))
+            .into_iter()
+            .map(|error| error.message)
+            .collect()
+    }
+
+    #[test]
+    fn default_config_is_clean() {
+        assert_eq!(
+            messages(include_str!("../../config.stcfg")),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn reports_unknown_actions_and_bad_arguments() {
+        let source = r#"on key {
+            a -> Quitt();
+            b -> GotoTab("one");
+            c -> GotoTab(1, 2);
+            d -> GotoTab();
+            mode == normal { e -> [SetMode(insert), GotoTab($count)]; }
+            fn motions(mod) { mod-h -> MoveLeft(); }
+            motions(Ctrl, Alt);
+            movement();
+        }"#;
+        assert_eq!(
+            messages(source),
+            [
+                "unknown action `Quitt`",
+                "argument `index` expects a number",
+                "`GotoTab` takes 1 argument, got 2",
+                "`GotoTab` is missing required argument `index`",
+                "unknown state `$count`",
+                "`motions` takes 1 argument, got 2",
+                "unknown function `movement`",
+            ]
+        );
+    }
+
+    #[test]
+    fn command_params_resolve_in_their_own_body() {
+        let source = r#"on init {
+            command retitle(title) -> RenameTab($title);
+            command stray -> [RenameTab($title), Quitt()];
+            command jump(n) -> GotoTab($n);
+        }"#;
+        assert_eq!(
+            messages(source),
+            [
+                "unknown state `$title`",
+                "unknown action `Quitt`",
+                "argument `index` expects a number",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_settings_and_mismatched_values() {
+        let source = r#"on init {
+            editor.scrollofff = 3;
+            editor.scrolloff = "three";
+            editor.wrap = sideways;
+            editor.wrap = true;
+            lsp.server.rust = ["rust-analyzer"];
+            let rows = 2;
+            editor.scrolloff = rows * "x";
+            editor.scrolloff = rows + 1;
+            editor.scrolloff = $rows + $cols;
+            alias "quit" -> Quitt;
+        }"#;
+        assert_eq!(
+            messages(source),
+            [
+                "unknown setting `editor.scrollofff`",
+                "`editor.scrolloff` expects a number",
+                "`editor.wrap` expects one of none, editor_width, bounded",
+                "`*` expects two numbers",
+                "`cols` is not defined",
+                "unknown action `Quitt`",
+            ]
+        );
+    }
+}
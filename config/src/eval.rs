@@ -0,0 +1,311 @@
+//! Reducing an [`Expr`] to the [`Value`] it stands for.
+//!
+//! `let` bindings and setting values are expressions: a plain value, a
+//! reference to an earlier binding, or arithmetic over them. [`eval`] folds
+//! one against the bindings in scope. A bare name that is bound resolves to
+//! its binding and otherwise stays an identifier, since most identifiers are
+//! enum-like setting values such as `relative`. A `$name` must be bound.
+//!
+//! Conditionals test the same bindings: `if platform == "macos" then ...`
+//! and `match platform { "macos" => ..., _ => ... }` read `platform` from
+//! [`builtins`], which every evaluation starts from. Environment variables
+//! read as `$env.NAME`, so one config can branch per machine on
+//! `$hostname` or `$env.USER`.
+
+use crate::{
+    ast::{BinOp, Expr, Pattern, Predicate, Spanned, Value},
+    error::ParseError,
+};
+use std::collections::HashMap;
+
+/// Names bound before any `let`: `platform` is the operating system the
+/// editor runs on, such as `"linux"` or `"macos"`, and `hostname` is the
+/// machine's name.
+pub fn builtins() -> HashMap<String, Value> {
+    HashMap::from([
+        (
+            "platform".to_string(),
+            Value::String(std::env::consts::OS.to_string()),
+        ),
+        ("hostname".to_string(), Value::String(hostname())),
+    ])
+}
+
+/// This machine's hostname, or `"unknown"` when it cannot be read, so a
+/// config never fails to load over it.
+#[cfg(unix)]
+fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    let ret = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) };
+    if ret != 0 {
+        return "unknown".to_string();
+    }
+
+    let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..end]).into_owned()
+}
+
+#[cfg(not(unix))]
+fn hostname() -> String {
+    "unknown".to_string()
+}
+
+/// The value of `expr` with the names in `env` bound.
+pub fn eval(expr: &Spanned<Expr>, env: &HashMap<String, Value>) -> Result<Value, ParseError> {
+    match &expr.node {
+        Expr::Value(Value::Ident(name)) => Ok(env
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| Value::Ident(name.clone()))),
+        Expr::Value(Value::StateRef(name)) | Expr::Variable(name) => {
+            if let Some(value) = env.get(name) {
+                return Ok(value.clone());
+            }
+            // An unset variable reads as empty, so a `match` can branch on
+            // whether it is set.
+            if let Some(var) = name.strip_prefix("env.") {
+                return Ok(Value::String(std::env::var(var).unwrap_or_default()));
+            }
+            Err(
+                ParseError::new(expr.span.clone(), format!("`{name}` is not defined"))
+                    .with_label("no earlier `let` binds this name"),
+            )
+        },
+        Expr::Value(value) => Ok(value.clone()),
+        Expr::If {
+            condition,
+            then_expr,
+            else_expr,
+        } => {
+            if test(condition, env)? {
+                eval(then_expr, env)
+            } else {
+                eval(else_expr, env)
+            }
+        },
+        Expr::Match { scrutinee, arms } => {
+            let value = eval(scrutinee, env)?;
+            let arm = arms.iter().find(|arm| match &arm.pattern.node {
+                Pattern::Wildcard => true,
+                Pattern::Value(pattern) => same(pattern, &value),
+            });
+            match arm {
+                Some(arm) => eval(&arm.value, env),
+                None => Err(ParseError::new(
+                    scrutinee.span.clone(),
+                    format!("no arm matches {}", describe(&value)),
+                )
+                .with_label("add a `_ => ...` arm to cover the rest")),
+            }
+        },
+        Expr::Binary { op, left, right } => {
+            let lhs = eval(left, env)?;
+            let rhs = eval(right, env)?;
+            binary(*op, lhs, rhs, expr)
+        },
+    }
+}
+
+fn binary(op: BinOp, lhs: Value, rhs: Value, expr: &Spanned<Expr>) -> Result<Value, ParseError> {
+    match (op, lhs, rhs) {
+        (BinOp::Div, Value::Number(_), Value::Number(rhs)) if rhs == 0.0 => {
+            Err(ParseError::new(expr.span.clone(), "division by zero"))
+        },
+        (op, Value::Number(lhs), Value::Number(rhs)) => Ok(Value::Number(match op {
+            BinOp::Add => lhs + rhs,
+            BinOp::Sub => lhs - rhs,
+            BinOp::Mul => lhs * rhs,
+            BinOp::Div => lhs / rhs,
+        })),
+        (BinOp::Add, lhs, rhs) => match (text(&lhs), text(&rhs)) {
+            (Some(lhs), Some(rhs)) => Ok(Value::String(lhs + &rhs)),
+            _ => Err(mismatch(op, expr)),
+        },
+        (op, _, _) => Err(mismatch(op, expr)),
+    }
+}
+
+/// Whether `predicate` holds with the names in `env` bound.
+fn test(predicate: &Spanned<Predicate>, env: &HashMap<String, Value>) -> Result<bool, ParseError> {
+    let lookup = |field: &Spanned<String>| {
+        env.get(&field.node).ok_or_else(|| {
+            ParseError::new(
+                field.span.clone(),
+                format!("`{}` is not defined", field.node),
+            )
+        })
+    };
+    let numbers =
+        |field: &Spanned<String>, value: &Spanned<Value>| -> Result<(f64, f64), ParseError> {
+            match (lookup(field)?, &value.node) {
+                (Value::Number(lhs), Value::Number(rhs)) => Ok((*lhs, *rhs)),
+                _ => Err(ParseError::new(
+                    predicate.span.clone(),
+                    "ordering compares two numbers",
+                )),
+            }
+        };
+    match &predicate.node {
+        Predicate::Eq(field, value) => Ok(same(lookup(field)?, &value.node)),
+        Predicate::NotEq(field, value) => Ok(!same(lookup(field)?, &value.node)),
+        Predicate::Gt(field, value) => numbers(field, value).map(|(a, b)| a > b),
+        Predicate::Lt(field, value) => numbers(field, value).map(|(a, b)| a < b),
+        Predicate::Gte(field, value) => numbers(field, value).map(|(a, b)| a >= b),
+        Predicate::Lte(field, value) => numbers(field, value).map(|(a, b)| a <= b),
+        Predicate::Bool(field) => match lookup(field)? {
+            Value::Bool(flag) => Ok(*flag),
+            _ => Err(ParseError::new(
+                field.span.clone(),
+                format!("`{}` is not a bool", field.node),
+            )),
+        },
+        Predicate::Not(inner) => Ok(!test(inner, env)?),
+        Predicate::And(left, right) => Ok(test(left, env)? && test(right, env)?),
+        Predicate::Or(left, right) => Ok(test(left, env)? || test(right, env)?),
+        Predicate::Matches(..) => Err(ParseError::new(
+            predicate.span.clone(),
+            "`~` cannot be evaluated in an expression",
+        )),
+    }
+}
+
+/// Equality for conditionals, where a bare word and a string of the same
+/// text are the same value: `platform == macos` reads like `== "macos"`.
+fn same(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::String(lhs) | Value::Ident(lhs), Value::String(rhs) | Value::Ident(rhs)) => {
+            lhs == rhs
+        },
+        _ => lhs == rhs,
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::String(text) => format!("{text:?}"),
+        Value::Ident(text) => format!("`{text}`"),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// `value` as the text it contributes to a concatenation. Numbers join by
+/// their written form, so `"size-" + 2` is `"size-2"`.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) | Value::Ident(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn mismatch(op: BinOp, expr: &Spanned<Expr>) -> ParseError {
+    let expected = match op {
+        BinOp::Add => "two numbers or two strings",
+        BinOp::Sub | BinOp::Mul | BinOp::Div => "two numbers",
+    };
+    ParseError::new(expr.span.clone(), format!("`{op}` expects {expected}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::eval;
+    use crate::{parse, Statement, Value};
+    use std::collections::HashMap;
+
+    /// The value of `let x = <source>;` with `env` bound.
+    fn value_of(source: &str, env: &[(&str, Value)]) -> Result<Value, String> {
+        let (config, errors) = parse(&format!("on init {{ let x = {source}; }}"));
+        assert!(errors.is_empty(), "`{source}` failed to parse: {errors:?}");
+        let config = config.expect("parsed config");
+        let Statement::Let(binding) = &config.blocks[0].node.statements[0].node else {
+            panic!("expected a let binding");
+        };
+        let env = env
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect::<HashMap<_, _>>();
+        eval(&binding.value, &env).map_err(|error| error.message)
+    }
+
+    #[test]
+    fn arithmetic_follows_precedence_and_parentheses() {
+        assert_eq!(value_of("1 + 2 * 3", &[]), Ok(Value::Number(7.0)));
+        assert_eq!(value_of("(1 + 2) * 3", &[]), Ok(Value::Number(9.0)));
+        assert_eq!(value_of("10 - 4 - 3", &[]), Ok(Value::Number(3.0)));
+        assert_eq!(value_of("9 / 2", &[]), Ok(Value::Number(4.5)));
+    }
+
+    #[test]
+    fn names_resolve_against_bindings() {
+        let env = [("base_size", Value::Number(12.0))];
+        assert_eq!(value_of("$base_size + 2", &env), Ok(Value::Number(14.0)));
+        assert_eq!(value_of("base_size * 2", &env), Ok(Value::Number(24.0)));
+        assert_eq!(
+            value_of("relative", &env),
+            Ok(Value::Ident("relative".into()))
+        );
+        assert_eq!(
+            value_of("$missing + 1", &env),
+            Err("`missing` is not defined".to_string())
+        );
+    }
+
+    #[test]
+    fn plus_joins_strings() {
+        assert_eq!(
+            value_of(r#""~/notes" + "/" + "today.md""#, &[]),
+            Ok(Value::String("~/notes/today.md".into()))
+        );
+        assert_eq!(
+            value_of(r#""size-" + 2"#, &[]),
+            Ok(Value::String("size-2".into()))
+        );
+        assert_eq!(
+            value_of(r#""a" - 1"#, &[]),
+            Err("`-` expects two numbers".to_string())
+        );
+        assert_eq!(value_of("1 / 0", &[]), Err("division by zero".to_string()));
+    }
+
+    #[test]
+    fn else_if_chains_take_the_first_true_branch() {
+        let env = [("width", Value::Number(120.0)), ("dark", Value::Bool(true))];
+        let chain = "if width < 80 then 0 else if width < 160 && dark then 1 else 2";
+        assert_eq!(value_of(chain, &env), Ok(Value::Number(1.0)));
+        let negated = "if !dark then light else dark_mode";
+        assert_eq!(
+            value_of(negated, &env),
+            Ok(Value::Ident("dark_mode".into()))
+        );
+    }
+
+    #[test]
+    fn env_refs_read_the_environment() {
+        let path = std::env::var("PATH").unwrap_or_default();
+        assert_eq!(value_of("$env.PATH", &[]), Ok(Value::String(path)));
+        assert_eq!(
+            value_of("$env.STOAT_TEST_SURELY_UNSET", &[]),
+            Ok(Value::String(String::new()))
+        );
+        let env = [("hostname", Value::String("work".into()))];
+        assert_eq!(
+            value_of(r#"match $hostname { "work" => 1, _ => 2 }"#, &env),
+            Ok(Value::Number(1.0))
+        );
+    }
+
+    #[test]
+    fn match_picks_the_first_equal_arm() {
+        let env = [("platform", Value::String("linux".into()))];
+        let source = r#"match platform { "macos" => cmd, linux => super, _ => ctrl }"#;
+        assert_eq!(value_of(source, &env), Ok(Value::Ident("super".into())));
+        let fallback = r#"match platform { "macos" => 1, _ => 2 }"#;
+        assert_eq!(value_of(fallback, &env), Ok(Value::Number(2.0)));
+        assert_eq!(
+            value_of(r#"match platform { "macos" => 1 }"#, &env),
+            Err(r#"no arm matches "linux""#.to_string())
+        );
+    }
+}
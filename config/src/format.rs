@@ -0,0 +1,773 @@
+//! Canonical layout for config source.
+//!
+//! [`format`] parses a config and prints it back with four-space indentation,
+//! one statement per line and single spaces around operators. Within a block,
+//! statements are grouped by kind: `let` bindings, `fn` declarations,
+//! settings, aliases, then key bindings and predicate blocks. Each group keeps
+//! its source order, so a later setting still overrides an earlier one. Key
+//! bindings only move ahead of predicate blocks when the block calls no `fn`,
+//! since a call expands in place and its bindings could tie on specificity
+//! with a predicate block's, where source order decides.
+//!
+//! The AST carries no comments, so they are recovered from the source: a
+//! comment on its own line travels with the statement after it, one after a
+//! statement on the same line stays there, and the rest of a block's comments
+//! close it. Blank lines between statements are kept, collapsed to one, and
+//! consecutive `let` bindings or settings align their `=`.
+
+use crate::{
+    ast::{
+        Action, ActionExpr, Arg, Config, EventBlock, EventType, Expr, Key, KeyPart, Pattern, Span,
+        Spanned, Statement, ThemeBlock, Value,
+    },
+    error::ParseError,
+    parser,
+};
+use std::{fmt::Write, ops::Range};
+
+const INDENT: &str = "    ";
+
+/// `source` in canonical layout, or the errors that stopped it parsing.
+pub fn format(source: &str) -> Result<String, Vec<ParseError>> {
+    let (config, errors) = parser::parse(source);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let Some(config) = config else {
+        return Err(vec![ParseError::new(
+            0..source.len(),
+            "failed to parse config",
+        )]);
+    };
+    let mut keys = Vec::new();
+    for block in &config.blocks {
+        key_spans(&block.node.statements, &mut keys);
+    }
+    for block in &config.themes {
+        key_spans(&block.node.statements, &mut keys);
+    }
+    let mut printer = Printer {
+        source,
+        comments: scan_comments(source, &keys),
+        out: String::new(),
+    };
+    printer.top_level(&config);
+    Ok(printer.out)
+}
+
+/// Byte ranges of every `#` comment in `source`, without the newline that
+/// ends it. `keys` are the spans of binding keys, skipped so a `"` key does
+/// not read as the start of a string.
+fn scan_comments(source: &str, keys: &[Span]) -> Vec<Span> {
+    let mut comments = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    let mut in_string = false;
+    while let Some((ix, ch)) = chars.next() {
+        if let Some(key) = keys.iter().find(|key| key.start == ix) {
+            while chars.next_if(|&(next, _)| next < key.end).is_some() {}
+            continue;
+        }
+        match ch {
+            '\\' if in_string => {
+                chars.next();
+            },
+            '"' => in_string = !in_string,
+            '#' if !in_string => {
+                let end = source[ix..].find('\n').map_or(source.len(), |n| ix + n);
+                comments.push(ix..end);
+                while chars.next_if(|&(next, _)| next < end).is_some() {}
+            },
+            _ => {},
+        }
+    }
+    comments
+}
+
+/// Spans of the keys bound anywhere in `statements`.
+fn key_spans(statements: &[Spanned<Statement>], keys: &mut Vec<Span>) {
+    for statement in statements {
+        match &statement.node {
+            Statement::Binding(binding) => {
+                keys.push(binding.key.span.clone());
+                keys.extend(binding.then.iter().map(|chord| chord.span.clone()));
+            },
+            Statement::Unbind(unbind) => {
+                keys.push(unbind.key.span.clone());
+                keys.extend(unbind.then.iter().map(|chord| chord.span.clone()));
+            },
+            Statement::PredicateBlock(block) => key_spans(&block.body, keys),
+            Statement::FnDecl(decl) => key_spans(&decl.body, keys),
+            _ => {},
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Event(&'a Spanned<EventBlock>),
+    Theme(&'a Spanned<ThemeBlock>),
+    Statement(&'a Spanned<Statement>),
+}
+
+impl Node<'_> {
+    fn span(&self) -> &Span {
+        match self {
+            Node::Event(block) => &block.span,
+            Node::Theme(block) => &block.span,
+            Node::Statement(statement) => &statement.span,
+        }
+    }
+}
+
+/// A comment, and whether a blank line separated it from what came before.
+struct Comment {
+    blank_before: bool,
+    span: Span,
+}
+
+/// The comments between two items of a block.
+struct Gap {
+    /// On the line the earlier item ends.
+    trailing: Option<Span>,
+    /// On lines of their own.
+    comments: Vec<Comment>,
+    /// Whether a blank line separates the last comment, or the earlier item
+    /// when there is none, from the later item.
+    blank_after: bool,
+}
+
+impl Gap {
+    /// Whether the later item, with the comments above it, opens a new
+    /// paragraph.
+    fn blank_before(&self) -> bool {
+        self.comments
+            .first()
+            .map_or(self.blank_after, |comment| comment.blank_before)
+    }
+}
+
+struct Child<'a> {
+    node: Node<'a>,
+    group: u8,
+    blank_before: bool,
+    leading: Vec<Comment>,
+    /// Whether a blank line separates the leading comments from the node.
+    blank_after_leading: bool,
+    trailing: Option<Span>,
+}
+
+struct Printer<'a> {
+    source: &'a str,
+    comments: Vec<Span>,
+    out: String,
+}
+
+impl<'a> Printer<'a> {
+    fn top_level(&mut self, config: &'a Config) {
+        let nodes: Vec<Node<'a>> = config
+            .blocks
+            .iter()
+            .map(Node::Event)
+            .chain(config.themes.iter().map(Node::Theme))
+            .collect();
+        self.body(nodes, 0..self.source.len(), 0);
+    }
+
+    /// Print `nodes`, the contents of a block whose body spans `body`, at
+    /// `depth` levels of indentation. Top-level items, at depth zero, keep
+    /// their order and are always separated by a blank line.
+    fn body(&mut self, mut nodes: Vec<Node<'a>>, body: Range<usize>, depth: usize) {
+        nodes.sort_by_key(|node| node.span().start);
+        // Fn calls and unbinds act on what precedes them, so a body holding
+        // one keeps its blocks in source order among the bindings.
+        let ordered = nodes.iter().any(|node| {
+            matches!(node, Node::Statement(s)
+                if matches!(s.node, Statement::FnCall(_) | Statement::Unbind(_)))
+        });
+
+        let mut children: Vec<Child<'a>> = Vec::with_capacity(nodes.len());
+        let mut prev_end = body.start;
+        for node in nodes {
+            let span = node.span().clone();
+            let gap = self.gap(prev_end, span.start, !children.is_empty());
+            if let Some(last) = children.last_mut() {
+                last.trailing = gap.trailing.clone();
+            }
+            let blank_before = gap.blank_before();
+            let blank_after_leading = !gap.comments.is_empty() && gap.blank_after;
+            let mut leading = gap.comments;
+            // Comments inside a statement, or between a block's header and
+            // its `{`, have nowhere to go but above it.
+            let inner_end = self.inner_start(node).unwrap_or(span.end);
+            leading.extend(
+                self.comments_in(span.start..inner_end)
+                    .into_iter()
+                    .map(|span| Comment {
+                        blank_before: false,
+                        span,
+                    }),
+            );
+            children.push(Child {
+                node,
+                group: if depth == 0 { 0 } else { group(node, ordered) },
+                blank_before,
+                leading,
+                blank_after_leading,
+                trailing: None,
+            });
+            prev_end = span.end;
+        }
+        let closing = self.gap(prev_end, body.end, !children.is_empty());
+        if let Some(last) = children.last_mut() {
+            last.trailing = closing.trailing.clone();
+        }
+
+        children.sort_by_key(|child| child.group);
+        let widths = align_widths(&children);
+
+        for (ix, child) in children.iter().enumerate() {
+            if ix > 0 && (depth == 0 || child.blank_before || child.group != children[ix - 1].group)
+            {
+                self.out.push('\n');
+            }
+            for (n, comment) in child.leading.iter().enumerate() {
+                if n > 0 && comment.blank_before {
+                    self.out.push('\n');
+                }
+                self.comment_line(&comment.span, depth);
+            }
+            if child.blank_after_leading {
+                self.out.push('\n');
+            }
+            self.out.push_str(&INDENT.repeat(depth));
+            self.node(child.node, widths[ix], depth);
+            if let Some(span) = &child.trailing {
+                self.out.push(' ');
+                self.out.push_str(&self.source[span.clone()]);
+            }
+            self.out.push('\n');
+        }
+        for (n, comment) in closing.comments.iter().enumerate() {
+            if comment.blank_before && (n > 0 || !children.is_empty()) {
+                self.out.push('\n');
+            }
+            self.comment_line(&comment.span, depth);
+        }
+    }
+
+    /// The comments between `start` and `end`. One on the line ending at
+    /// `start` trails the item there, when `after_item` says there is one.
+    fn gap(&self, start: usize, end: usize, after_item: bool) -> Gap {
+        let mut trailing = None;
+        let mut comments = Vec::new();
+        let mut cursor = start;
+        for span in self.comments_in(start..end) {
+            let between = &self.source[cursor..span.start];
+            if after_item && cursor == start && !between.contains('\n') {
+                trailing = Some(span.clone());
+            } else {
+                comments.push(Comment {
+                    blank_before: is_blank_gap(between),
+                    span: span.clone(),
+                });
+            }
+            cursor = span.end;
+        }
+        Gap {
+            trailing,
+            comments,
+            blank_after: is_blank_gap(&self.source[cursor..end]),
+        }
+    }
+
+    fn comments_in(&self, range: Range<usize>) -> Vec<Span> {
+        self.comments
+            .iter()
+            .filter(|span| span.start >= range.start && span.end <= range.end)
+            .cloned()
+            .collect()
+    }
+
+    /// Where the body of a block-shaped node begins, just past its `{`.
+    fn inner_start(&self, node: Node<'a>) -> Option<usize> {
+        let search_from = match node {
+            Node::Event(block) => block.span.start,
+            Node::Theme(block) => block
+                .node
+                .parent
+                .as_ref()
+                .map_or(block.node.name.span.end, |parent| parent.span.end),
+            Node::Statement(statement) => match &statement.node {
+                Statement::PredicateBlock(block) => block.predicate.span.end,
+                Statement::FnDecl(decl) => decl
+                    .params
+                    .last()
+                    .map_or(decl.name.span.end, |param| param.span.end),
+                _ => return None,
+            },
+        };
+        self.source[search_from..]
+            .find('{')
+            .map(|ix| search_from + ix + 1)
+    }
+
+    fn comment_line(&mut self, span: &Span, depth: usize) {
+        self.out.push_str(&INDENT.repeat(depth));
+        self.out.push_str(self.source[span.clone()].trim_end());
+        self.out.push('\n');
+    }
+
+    fn node(&mut self, node: Node<'a>, width: usize, depth: usize) {
+        match node {
+            Node::Event(block) => {
+                let event = match block.node.event {
+                    EventType::Init => "init",
+                    EventType::Buffer => "buffer",
+                    EventType::Key => "key",
+                };
+                let _ = write!(self.out, "on {event} ");
+                self.block(node, &block.node.statements, block.span.end, depth);
+            },
+            Node::Theme(block) => {
+                let _ = write!(self.out, "theme {} ", block.node.name.node);
+                if let Some(parent) = &block.node.parent {
+                    let _ = write!(self.out, "inherits {} ", parent.node);
+                }
+                self.block(node, &block.node.statements, block.span.end, depth);
+            },
+            Node::Statement(statement) => self.statement(node, statement, width, depth),
+        }
+    }
+
+    /// Print `{`, the statements of a block ending at `end`, and `}`.
+    fn block(
+        &mut self,
+        node: Node<'a>,
+        statements: &'a [Spanned<Statement>],
+        end: usize,
+        depth: usize,
+    ) {
+        let start = self.inner_start(node).unwrap_or(end);
+        // The body ends before the closing brace.
+        let body = start..end.saturating_sub(1).max(start);
+        if statements.is_empty() && self.comments_in(body.clone()).is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+        self.out.push_str("{\n");
+        self.body(
+            statements.iter().map(Node::Statement).collect(),
+            body,
+            depth + 1,
+        );
+        self.out.push_str(&INDENT.repeat(depth));
+        self.out.push('}');
+    }
+
+    fn statement(
+        &mut self,
+        node: Node<'a>,
+        statement: &'a Spanned<Statement>,
+        width: usize,
+        depth: usize,
+    ) {
+        match &statement.node {
+            Statement::Setting(setting) => {
+                let path = setting_path(&setting.path);
+                let _ = write!(self.out, "{path:<width$} = ");
+                write_expr(&mut self.out, &setting.value.node);
+                self.out.push(';');
+            },
+            Statement::Binding(binding) => {
+                write_key(&mut self.out, &binding.key.node);
+                for chord in &binding.then {
+                    self.out.push(' ');
+                    write_key(&mut self.out, &chord.node);
+                }
+                self.out.push_str(" -> ");
+                write_action_expr(&mut self.out, &binding.action.node);
+                self.out.push(';');
+            },
+            Statement::Let(binding) => {
+                let name = format!("let {}", binding.name.node);
+                let _ = write!(self.out, "{name:<width$} = ");
+                write_expr(&mut self.out, &binding.value.node);
+                self.out.push(';');
+            },
+            Statement::FnDecl(decl) => {
+                let params: Vec<&str> = decl.params.iter().map(|p| p.node.as_str()).collect();
+                let _ = write!(self.out, "fn {}({}) ", decl.name.node, params.join(", "));
+                self.block(node, &decl.body, statement.span.end, depth);
+            },
+            Statement::FnCall(call) => {
+                let _ = write!(self.out, "{}(", call.name.node);
+                for (ix, arg) in call.args.iter().enumerate() {
+                    if ix > 0 {
+                        self.out.push_str(", ");
+                    }
+                    write_key(&mut self.out, &arg.node);
+                }
+                self.out.push_str(");");
+            },
+            Statement::PredicateBlock(block) => {
+                let _ = write!(self.out, "{} ", block.predicate.node);
+                self.block(node, &block.body, statement.span.end, depth);
+            },
+            Statement::Alias(alias) => {
+                self.out.push_str("alias ");
+                write_string(&mut self.out, &alias.name.node);
+                let _ = write!(self.out, " -> {};", alias.action.node);
+            },
+            Statement::Command(command) => {
+                let _ = write!(self.out, "command {}", command.name.node);
+                if !command.params.is_empty() {
+                    let params: Vec<&str> =
+                        command.params.iter().map(|p| p.node.as_str()).collect();
+                    let _ = write!(self.out, "({})", params.join(", "));
+                }
+                if let Some(description) = &command.description {
+                    self.out.push(' ');
+                    write_string(&mut self.out, &description.node);
+                }
+                self.out.push_str(" -> ");
+                write_action_expr(&mut self.out, &command.action.node);
+                self.out.push(';');
+            },
+            Statement::Unbind(unbind) => {
+                self.out.push_str("unbind ");
+                write_key(&mut self.out, &unbind.key.node);
+                for chord in &unbind.then {
+                    self.out.push(' ');
+                    write_key(&mut self.out, &chord.node);
+                }
+                self.out.push(';');
+            },
+        }
+    }
+}
+
+/// Whether the whitespace `between` two items holds an empty line.
+fn is_blank_gap(between: &str) -> bool {
+    between.matches('\n').count() >= 2
+}
+
+/// Sort key placing each kind of statement in its group.
+fn group(node: Node<'_>, ordered: bool) -> u8 {
+    let Node::Statement(statement) = node else {
+        return 0;
+    };
+    match statement.node {
+        Statement::Let(_) => 0,
+        Statement::FnDecl(_) => 1,
+        Statement::Setting(_) => 2,
+        Statement::Alias(_) | Statement::Command(_) => 3,
+        Statement::Binding(_) | Statement::FnCall(_) | Statement::Unbind(_) => 4,
+        Statement::PredicateBlock(_) => {
+            if ordered {
+                4
+            } else {
+                5
+            }
+        },
+    }
+}
+
+/// Width each child's left-hand side pads to. A run of `let` bindings or of
+/// settings, unbroken by a blank line or comment, pads to its widest name.
+fn align_widths(children: &[Child<'_>]) -> Vec<usize> {
+    let lhs_width = |child: &Child<'_>| match child.node {
+        Node::Statement(statement) => match &statement.node {
+            Statement::Let(binding) => Some("let ".len() + binding.name.node.len()),
+            Statement::Setting(setting) => Some(setting_path(&setting.path).len()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let mut widths = vec![0; children.len()];
+    let mut ix = 0;
+    while ix < children.len() {
+        if lhs_width(&children[ix]).is_none() {
+            ix += 1;
+            continue;
+        }
+        let start = ix;
+        ix += 1;
+        while ix < children.len()
+            && lhs_width(&children[ix]).is_some()
+            && children[ix].group == children[start].group
+            && !children[ix].blank_before
+            && children[ix].leading.is_empty()
+        {
+            ix += 1;
+        }
+        let width = children[start..ix]
+            .iter()
+            .filter_map(lhs_width)
+            .max()
+            .unwrap_or(0);
+        for slot in &mut widths[start..ix] {
+            *slot = width;
+        }
+    }
+    widths
+}
+
+fn setting_path(path: &[Spanned<String>]) -> String {
+    path.iter()
+        .map(|segment| segment.node.as_str())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn write_key(out: &mut String, key: &KeyPart) {
+    for (ix, key) in key.keys.iter().enumerate() {
+        if ix > 0 {
+            out.push('-');
+        }
+        match key {
+            Key::Char(ch) => out.push(*ch),
+            Key::Named(name) => out.push_str(name),
+        }
+    }
+}
+
+fn write_action_expr(out: &mut String, expr: &ActionExpr) {
+    match expr {
+        ActionExpr::Single(action) => write_action(out, action),
+        ActionExpr::Sequence(actions) => {
+            out.push('[');
+            for (ix, action) in actions.iter().enumerate() {
+                if ix > 0 {
+                    out.push_str(", ");
+                }
+                write_action(out, &action.node);
+            }
+            out.push(']');
+        },
+    }
+}
+
+fn write_action(out: &mut String, action: &Action) {
+    out.push_str(&action.name);
+    out.push('(');
+    for (ix, arg) in action.args.iter().enumerate() {
+        if ix > 0 {
+            out.push_str(", ");
+        }
+        match &arg.node {
+            Arg::Positional(value) => write_value(out, &value.node),
+            Arg::Named { name, value } => {
+                out.push_str(&name.node);
+                out.push_str(": ");
+                write_value(out, &value.node);
+            },
+        }
+    }
+    out.push(')');
+}
+
+fn write_expr(out: &mut String, expr: &Expr) {
+    match expr {
+        Expr::Value(value) => write_value(out, value),
+        Expr::Variable(name) => out.push_str(name),
+        Expr::If {
+            condition,
+            then_expr,
+            else_expr,
+        } => {
+            let _ = write!(out, "if {} then ", condition.node);
+            write_expr(out, &then_expr.node);
+            out.push_str(" else ");
+            write_expr(out, &else_expr.node);
+        },
+        Expr::Binary { op, left, right } => {
+            write_operand(out, &left.node, |child| child < op.precedence());
+            let _ = write!(out, " {op} ");
+            write_operand(out, &right.node, |child| child <= op.precedence());
+        },
+        Expr::Match { scrutinee, arms } => {
+            out.push_str("match ");
+            write_expr(out, &scrutinee.node);
+            out.push_str(" { ");
+            for (ix, arm) in arms.iter().enumerate() {
+                if ix > 0 {
+                    out.push_str(", ");
+                }
+                match &arm.pattern.node {
+                    Pattern::Value(value) => write_value(out, value),
+                    Pattern::Wildcard => out.push('_'),
+                }
+                out.push_str(" => ");
+                write_expr(out, &arm.value.node);
+            }
+            out.push_str(" }");
+        },
+    }
+}
+
+/// Write an operand of a binary expression, parenthesized when `needs_parens`
+/// says its operator binds too loosely to stand bare on that side.
+fn write_operand(out: &mut String, expr: &Expr, needs_parens: impl Fn(u8) -> bool) {
+    let parens = match expr {
+        Expr::Binary { op, .. } => needs_parens(op.precedence()),
+        Expr::If { .. } | Expr::Match { .. } => true,
+        Expr::Value(_) | Expr::Variable(_) => false,
+    };
+    if parens {
+        out.push('(');
+        write_expr(out, expr);
+        out.push(')');
+    } else {
+        write_expr(out, expr);
+    }
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::String(text) => write_string(out, text),
+        Value::Number(number) => {
+            let _ = write!(out, "{number}");
+        },
+        Value::Bool(flag) => {
+            let _ = write!(out, "{flag}");
+        },
+        Value::Ident(name) => out.push_str(name),
+        Value::Enum { ty, variant } => {
+            let _ = write!(out, "{ty}::{variant}");
+        },
+        Value::Array(items) => {
+            out.push('[');
+            for (ix, item) in items.iter().enumerate() {
+                if ix > 0 {
+                    out.push_str(", ");
+                }
+                write_value(out, &item.node);
+            }
+            out.push(']');
+        },
+        Value::Map(entries) if entries.is_empty() => out.push_str("{}"),
+        Value::Map(entries) => {
+            out.push_str("{ ");
+            for (ix, (key, value)) in entries.iter().enumerate() {
+                if ix > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&key.node);
+                out.push_str(": ");
+                write_value(out, &value.node);
+            }
+            out.push_str(" }");
+        },
+        Value::StateRef(name) => {
+            out.push('$');
+            out.push_str(name);
+        },
+    }
+}
+
+/// A string literal the parser reads back as `text`. A backslash takes the
+/// next character literally, so only quotes and backslashes need one.
+fn write_string(out: &mut String, text: &str) {
+    out.push('"');
+    for ch in text.chars() {
+        if matches!(ch, '"' | '\\') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format;
+
+    fn formatted(source: &str) -> String {
+        format(source).unwrap_or_else(|errors| panic!("format failed: {errors:?}"))
+    }
+
+    #[test]
+    fn normalizes_spacing_indentation_and_order() {
+        let source = "on  key{\nmode==normal{x->Quit( );}\n  a ->[ SetMode( insert ),Undo()] ;\n}\n\
+                      on init {\n  alias \"fmt\" -> Format;\n  editor.scrolloff=3;\n  let n = 1;\n}";
+        assert_eq!(
+            formatted(source),
+            "on key {\n    a -> [SetMode(insert), Undo()];\n\n    mode == normal {\n        x -> Quit();\n    }\n}\n\n\
+             on init {\n    let n = 1;\n\n    editor.scrolloff = 3;\n\n    alias \"fmt\" -> Format;\n}\n"
+        );
+    }
+
+    #[test]
+    fn comments_travel_with_their_statements() {
+        let source = "# header\non init {\n    # scroll\n    editor.scrolloff = 3; # rows\n\n    let x = 1;\n    # closing\n}\n";
+        assert_eq!(
+            formatted(source),
+            "# header\non init {\n    let x = 1;\n\n    # scroll\n    editor.scrolloff = 3; # rows\n    # closing\n}\n"
+        );
+    }
+
+    #[test]
+    fn aligns_runs_of_assignments() {
+        let source = "theme t {\n    let a = \"#000\";\n    let longer = a;\n\n    ui.x.fg = a;\n    ui.longer.bg = { fg: a, modifiers: [bold] };\n}\n";
+        assert_eq!(
+            formatted(source),
+            "theme t {\n    let a      = \"#000\";\n    let longer = a;\n\n    ui.x.fg      = a;\n    ui.longer.bg = { fg: a, modifiers: [bold] };\n}\n"
+        );
+    }
+
+    #[test]
+    fn arithmetic_keeps_only_needed_parentheses() {
+        assert_eq!(
+            formatted("on init { editor.scrolloff = (1+2)*(3) - (4 - 5) + (6*7); }"),
+            "on init {\n    editor.scrolloff = (1 + 2) * 3 - (4 - 5) + 6 * 7;\n}\n"
+        );
+    }
+
+    #[test]
+    fn conditionals_print_on_one_line() {
+        assert_eq!(
+            formatted(
+                "on init {\nlet m = match platform {\n  \"macos\" => cmd,\n  _ => if a then b else if c then d else e,\n};\n}"
+            ),
+            "on init {\n    let m = match platform { \"macos\" => cmd, _ => if a then b else if c then d else e };\n}\n"
+        );
+    }
+
+    #[test]
+    fn key_sequences_join_with_one_space() {
+        assert_eq!(
+            formatted("on key {\n    Space   f\n  \" -> Quit();\n}"),
+            "on key {\n    Space f \" -> Quit();\n}\n"
+        );
+    }
+
+    #[test]
+    fn unbind_keeps_blocks_in_source_order() {
+        assert_eq!(
+            formatted("on key {\nmode == normal { h -> Quit(); }\nunbind   h;\nh -> Undo();\n}"),
+            "on key {\n    mode == normal {\n        h -> Quit();\n    }\n    unbind h;\n    h -> Undo();\n}\n"
+        );
+    }
+
+    #[test]
+    fn commands_print_params_and_description() {
+        assert_eq!(
+            formatted(
+                "on init {\ncommand grep( pattern )\"Search\"->[Search($pattern),SetMode(normal)];\ncommand w->SaveBuffer();\n}"
+            ),
+            "on init {\n    command grep(pattern) \"Search\" -> [Search($pattern), SetMode(normal)];\n    command w -> SaveBuffer();\n}\n"
+        );
+    }
+
+    #[test]
+    fn default_config_formats_stably() {
+        let source = include_str!("../../config.stcfg");
+        let once = formatted(source);
+        assert_eq!(formatted(&once), once);
+        assert_eq!(
+            once.matches('#').count(),
+            source.matches('#').count(),
+            "no comment is dropped"
+        );
+    }
+}
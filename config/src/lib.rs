@@ -1,16 +1,26 @@
 mod ast;
+mod check;
 mod error;
+mod eval;
+mod format;
 mod parser;
 mod schema;
 mod settings;
 
 pub use ast::{
-    Action, ActionExpr, Arg, Binding, Config, EventBlock, EventType, Expr, FnDecl, Key, KeyPart,
-    LetBinding, Predicate, PredicateBlock, Setting, Span, Spanned, Statement, ThemeBlock, Value,
+    Action, ActionExpr, Alias, Arg, BinOp, Binding, CommandDecl, Config, EventBlock, EventType,
+    Expr, FnCall, FnDecl, Key, KeyPart, LetBinding, MatchArm, Pattern, Predicate, PredicateBlock,
+    Setting, Span, Spanned, Statement, ThemeBlock, Unbind, Value,
 };
+pub use check::check;
 pub use error::{format_errors, ParseError};
+pub use eval::{builtins, eval};
+pub use format::format;
 pub use schema::{settings_schema, PathSeg, SettingDef, ValueShape};
-pub use settings::{LineNumbers, MinimapMode, MouseCapturePolicy, Settings, TabBarMode, WrapMode};
+pub use settings::{
+    CursorAnimation, CursorShape, LineNumbers, MinimapMode, MouseCapturePolicy, Settings,
+    StateFormat, TabBarMode, UserCommand, WrapBreak, WrapMode,
+};
 
 pub fn parse(source: &str) -> (Option<Config>, Vec<ParseError>) {
     parser::parse(source)
@@ -210,20 +220,35 @@ mod tests {
         assert_eq!(setting1.path.len(), 2);
         assert_eq!(setting1.path[0].node, "font");
         assert_eq!(setting1.path[1].node, "size");
-        assert_eq!(setting1.value.node, Value::Number(14.0));
+        assert_eq!(setting1.value.node, Expr::Value(Value::Number(14.0)));
 
         let setting2 = assert_setting(&block.statements[1]);
         assert_eq!(setting2.path.len(), 2);
         assert_eq!(setting2.path[0].node, "editor");
         assert_eq!(setting2.path[1].node, "tab_size");
-        assert_eq!(setting2.value.node, Value::Number(4.0));
+        assert_eq!(setting2.value.node, Expr::Value(Value::Number(4.0)));
     }
 
     #[test]
     fn float_values() {
         let config = parse_ok("on init { font.size = 14.5; }");
         let setting = assert_setting(&config.blocks[0].node.statements[0]);
-        assert_eq!(setting.value.node, Value::Number(14.5));
+        assert_eq!(setting.value.node, Expr::Value(Value::Number(14.5)));
+    }
+
+    #[test]
+    fn arithmetic_setting_values() {
+        let config = parse_ok("on init { font.size = $base_size + 2 * 3; }");
+        let setting = assert_setting(&config.blocks[0].node.statements[0]);
+        let Expr::Binary { op, left, right } = &setting.value.node else {
+            panic!("expected a binary expression");
+        };
+        assert_eq!(*op, BinOp::Add);
+        assert_eq!(
+            left.node,
+            Expr::Value(Value::StateRef("base_size".to_string()))
+        );
+        assert!(matches!(right.node, Expr::Binary { op: BinOp::Mul, .. }));
     }
 
     #[test]
@@ -276,7 +301,7 @@ mod tests {
         let config = parse_ok("on init { editor.rulers = [79, 100]; }");
         let setting = assert_setting(&config.blocks[0].node.statements[0]);
         match &setting.value.node {
-            Value::Array(items) => {
+            Expr::Value(Value::Array(items)) => {
                 assert_eq!(items.len(), 2);
                 assert_eq!(items[0].node, Value::Number(79.0));
                 assert_eq!(items[1].node, Value::Number(100.0));
@@ -815,7 +840,7 @@ mod tests {
         let setting = assert_setting(&config.blocks[0].node.statements[0]);
         assert_eq!(
             setting.value.node,
-            Value::StateRef("register_count".to_string())
+            Expr::Value(Value::StateRef("register_count".to_string()))
         );
     }
 
@@ -823,11 +848,98 @@ mod tests {
     fn fn_call_statement() {
         let config = parse_ok("on key { vim_motions(); }");
         match &config.blocks[0].node.statements[0].node {
-            Statement::FnCall(name) => assert_eq!(name.node, "vim_motions"),
+            Statement::FnCall(call) => {
+                assert_eq!(call.name.node, "vim_motions");
+                assert!(call.args.is_empty());
+            },
+            _ => panic!("expected fn call"),
+        }
+    }
+
+    #[test]
+    fn fn_parameters_and_call_arguments() {
+        let config = parse_ok(
+            "on key { fn motions(mod, extra) { mod-h -> MoveLeft(); } motions(Ctrl, Alt-Shift); }",
+        );
+        let decl = assert_fn_decl(&config.blocks[0].node.statements[0]);
+        let params: Vec<&str> = decl.params.iter().map(|p| p.node.as_str()).collect();
+        assert_eq!(params, ["mod", "extra"]);
+        let binding = assert_binding(&decl.body[0]);
+        assert_eq!(binding.key.node.keys, vec![key_named("mod"), key_char('h')]);
+
+        match &config.blocks[0].node.statements[1].node {
+            Statement::FnCall(call) => {
+                assert_eq!(call.args[0].node.keys, vec![key_named("Ctrl")]);
+                assert_eq!(
+                    call.args[1].node.keys,
+                    vec![key_named("Alt"), key_named("Shift")]
+                );
+            },
             _ => panic!("expected fn call"),
         }
     }
 
+    #[test]
+    fn alias_statement() {
+        let config = parse_ok(r#"on init { alias "format document" -> Format; }"#);
+        match &config.blocks[0].node.statements[0].node {
+            Statement::Alias(alias) => {
+                assert_eq!(alias.name.node, "format document");
+                assert_eq!(alias.action.node, "Format");
+            },
+            other => panic!("expected alias, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn command_statement() {
+        let config = parse_ok(
+            r#"on init {
+                command save_all -> SaveAll();
+                command grep_notes(pattern) "Search the notes" -> [Search($pattern), SetMode(normal)];
+            }"#,
+        );
+        let statements = &config.blocks[0].node.statements;
+        let Statement::Command(plain) = &statements[0].node else {
+            panic!("expected command, got {:?}", statements[0].node);
+        };
+        assert_eq!(plain.name.node, "save_all");
+        assert!(plain.params.is_empty() && plain.description.is_none());
+        let Statement::Command(grep) = &statements[1].node else {
+            panic!("expected command, got {:?}", statements[1].node);
+        };
+        assert_eq!(grep.params[0].node, "pattern");
+        assert_eq!(
+            grep.description.as_ref().map(|d| d.node.as_str()),
+            Some("Search the notes")
+        );
+        assert!(matches!(&grep.action.node, ActionExpr::Sequence(actions) if actions.len() == 2));
+    }
+
+    #[test]
+    fn unbind_statement() {
+        let config = parse_ok("on key { unbind g g; mode == normal { unbind C-s; } }");
+        let statements = &config.blocks[0].node.statements;
+        match &statements[0].node {
+            Statement::Unbind(unbind) => {
+                assert_eq!(unbind.key.node.keys, vec![key_char('g')]);
+                assert_eq!(unbind.then[0].node.keys, vec![key_char('g')]);
+            },
+            other => panic!("expected unbind, got {other:?}"),
+        }
+        let Statement::PredicateBlock(block) = &statements[1].node else {
+            panic!("expected predicate block");
+        };
+        assert!(matches!(&block.body[0].node, Statement::Unbind(unbind) if unbind.then.is_empty()));
+    }
+
+    #[test]
+    fn alias_prefixed_setting_is_not_an_alias() {
+        let config = parse_ok("on init { aliasing = true; }");
+        let setting = assert_setting(&config.blocks[0].node.statements[0]);
+        assert_eq!(setting.path[0].node, "aliasing");
+    }
+
     #[test]
     fn chord_keys() {
         let config = parse_ok("on key { h-j -> Diagonal(); }");
@@ -835,6 +947,25 @@ mod tests {
         assert_eq!(binding.key.node.keys, vec![key_char('h'), key_char('j')]);
     }
 
+    #[test]
+    fn key_sequences() {
+        let config =
+            parse_ok("on key { g g -> MoveToFileStart(); Space f C-f -> OpenFileFinder(); }");
+        let statements = &config.blocks[0].node.statements;
+        let binding = assert_binding(&statements[0]);
+        assert_eq!(binding.key.node.keys, vec![key_char('g')]);
+        let then: Vec<_> = binding.then.iter().map(|chord| &chord.node.keys).collect();
+        assert_eq!(then, [&vec![key_char('g')]]);
+
+        let binding = assert_binding(&statements[1]);
+        assert_eq!(binding.key.node.keys, vec![key_named("Space")]);
+        let then: Vec<_> = binding.then.iter().map(|chord| &chord.node.keys).collect();
+        assert_eq!(
+            then,
+            [&vec![key_char('f')], &vec![key_char('C'), key_char('f')]]
+        );
+    }
+
     #[test]
     fn f_keys() {
         let config = parse_ok("on key { F1 -> Help(); }");
@@ -901,7 +1032,10 @@ mod tests {
                 .collect::<Vec<_>>(),
             vec!["ui", "border", "focused", "fg"]
         );
-        assert_eq!(setting.value.node, Value::Ident("accent".to_string()));
+        assert_eq!(
+            setting.value.node,
+            Expr::Value(Value::Ident("accent".to_string()))
+        );
     }
 
     #[test]
@@ -914,7 +1048,7 @@ mod tests {
         let theme = &config.themes[0].node;
         let setting = assert_setting(&theme.statements[0]);
         match &setting.value.node {
-            Value::Map(entries) => {
+            Expr::Value(Value::Map(entries)) => {
                 assert_eq!(entries.len(), 2);
                 assert_eq!(entries[0].0.node, "fg");
                 assert_eq!(entries[0].1.node, Value::Ident("bg".to_string()));
@@ -935,7 +1069,7 @@ mod tests {
         let theme = &config.themes[0].node;
         let setting = assert_setting(&theme.statements[0]);
         match &setting.value.node {
-            Value::Map(entries) => {
+            Expr::Value(Value::Map(entries)) => {
                 assert_eq!(entries.len(), 2);
                 assert_eq!(entries[1].0.node, "modifiers");
                 match &entries[1].1.node {
@@ -961,7 +1095,7 @@ mod tests {
         let theme = &config.themes[0].node;
         let setting = assert_setting(&theme.statements[0]);
         match &setting.value.node {
-            Value::Map(entries) => {
+            Expr::Value(Value::Map(entries)) => {
                 assert_eq!(entries[0].0.node, "bg");
                 assert_eq!(entries[0].1.node, Value::String("#45475a".to_string()));
             },
@@ -973,7 +1107,7 @@ mod tests {
     fn map_value_empty() {
         let config = parse_ok("theme t { ui.x = { }; }");
         let setting = assert_setting(&config.themes[0].node.statements[0]);
-        assert_eq!(setting.value.node, Value::Map(vec![]));
+        assert_eq!(setting.value.node, Expr::Value(Value::Map(vec![])));
     }
 
     #[test]
@@ -981,7 +1115,7 @@ mod tests {
         let config = parse_ok(r#"theme t { ui.x = { fg: bg, bg: accent, }; }"#);
         let setting = assert_setting(&config.themes[0].node.statements[0]);
         match &setting.value.node {
-            Value::Map(entries) => assert_eq!(entries.len(), 2),
+            Expr::Value(Value::Map(entries)) => assert_eq!(entries.len(), 2),
             _ => panic!("expected map"),
         }
     }
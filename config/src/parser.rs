@@ -1,8 +1,8 @@
 use crate::{
     ast::{
-        Action, ActionExpr, Arg, Binding, Config, EventBlock, EventType, Expr, FnDecl, Key,
-        KeyPart, LetBinding, Predicate, PredicateBlock, Setting, Spanned, Statement, ThemeBlock,
-        Value,
+        Action, ActionExpr, Alias, Arg, BinOp, Binding, CommandDecl, Config, EventBlock, EventType,
+        Expr, FnCall, FnDecl, Key, KeyPart, LetBinding, MatchArm, Pattern, Predicate,
+        PredicateBlock, Setting, Spanned, Statement, ThemeBlock, Unbind, Value,
     },
     error::ParseError,
 };
@@ -126,7 +126,15 @@ fn enum_value<'src>() -> impl Parser<'src, &'src str, Value, Extra<'src>> + Clon
 
 fn value<'src>() -> impl Parser<'src, &'src str, Value, Extra<'src>> + Clone {
     recursive(|value| {
-        let state_ref = just('$').ignore_then(ident()).map(Value::StateRef);
+        // `$env.HOME` reads as one dotted name.
+        let state_ref = just('$')
+            .ignore_then(
+                ident()
+                    .separated_by(just('.'))
+                    .at_least(1)
+                    .collect::<Vec<_>>(),
+            )
+            .map(|parts| Value::StateRef(parts.join(".")));
 
         let spanned_value = value
             .clone()
@@ -185,19 +193,13 @@ fn spanned_value<'src>() -> impl Parser<'src, &'src str, Spanned<Value>, Extra<'
 
 fn expr<'src>() -> impl Parser<'src, &'src str, Expr, Extra<'src>> + Clone {
     recursive(|expr| {
-        let state_ref = just('$')
-            .ignore_then(ident())
-            .map(|s| Expr::Value(Value::StateRef(s)));
-
-        let string_expr = string_literal().map(|s| Expr::Value(Value::String(s)));
-        let num_expr = number().map(|n| Expr::Value(Value::Number(n)));
-        let ident_expr = ident().map(|s| match s.as_str() {
-            "true" => Expr::Value(Value::Bool(true)),
-            "false" => Expr::Value(Value::Bool(false)),
-            _ => Expr::Value(Value::Ident(s)),
-        });
+        let parens = just('(')
+            .ignore_then(ws())
+            .ignore_then(expr.clone())
+            .then_ignore(ws())
+            .then_ignore(just(')'));
 
-        let atom = choice((state_ref, string_expr, num_expr, ident_expr));
+        let atom = parens.or(value().map(Expr::Value));
 
         let with_default = atom
             .clone()
@@ -220,6 +222,36 @@ fn expr<'src>() -> impl Parser<'src, &'src str, Expr, Extra<'src>> + Clone {
                 None => left,
             });
 
+        // `*` and `/` bind tighter than `+` and `-`, and each level folds
+        // left, so `a - b - c` is `(a - b) - c`.
+        let operand = with_default.map_with(|node, e| Spanned::new(node, span_to_range(e.span())));
+        let binary = |left: Spanned<Expr>, (op, right): (BinOp, Spanned<Expr>)| {
+            let span = left.span.start..right.span.end;
+            Spanned::new(
+                Expr::Binary {
+                    op,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+                span,
+            )
+        };
+        let product = operand.clone().foldl(
+            ws().ignore_then(choice((just('*').to(BinOp::Mul), just('/').to(BinOp::Div))))
+                .then_ignore(ws())
+                .then(operand)
+                .repeated(),
+            binary,
+        );
+        let sum = product.clone().foldl(
+            ws().ignore_then(choice((just('+').to(BinOp::Add), just('-').to(BinOp::Sub))))
+                .then_ignore(ws())
+                .then(product)
+                .repeated(),
+            binary,
+        );
+        let arithmetic = sum.map(|spanned| spanned.node);
+
         let spanned_expr = expr
             .clone()
             .map_with(|node, e| Spanned::new(node, span_to_range(e.span())));
@@ -236,14 +268,44 @@ fn expr<'src>() -> impl Parser<'src, &'src str, Expr, Extra<'src>> + Clone {
             .then_ignore(ws())
             .then_ignore(just("else"))
             .then_ignore(required_ws())
-            .then(spanned_expr)
+            .then(spanned_expr.clone())
             .map(|((cond, then_branch), else_branch)| Expr::If {
                 condition: Box::new(cond),
                 then_expr: Box::new(then_branch),
                 else_expr: Box::new(else_branch),
             });
 
-        if_expr.or(with_default)
+        let pattern = value()
+            .map(|value| match value {
+                Value::Ident(name) if name == "_" => Pattern::Wildcard,
+                value => Pattern::Value(value),
+            })
+            .map_with(|node, e| Spanned::new(node, span_to_range(e.span())));
+        let arm = pattern
+            .then_ignore(ws())
+            .then_ignore(just("=>"))
+            .then_ignore(ws())
+            .then(spanned_expr.clone())
+            .map(|(pattern, value)| MatchArm { pattern, value });
+        let match_expr = just("match")
+            .ignore_then(required_ws())
+            .ignore_then(spanned_expr)
+            .then_ignore(ws())
+            .then_ignore(just('{'))
+            .then_ignore(ws())
+            .then(
+                arm.separated_by(just(',').padded_by(ws()))
+                    .allow_trailing()
+                    .collect::<Vec<_>>(),
+            )
+            .then_ignore(ws())
+            .then_ignore(just('}'))
+            .map(|(scrutinee, arms)| Expr::Match {
+                scrutinee: Box::new(scrutinee),
+                arms,
+            });
+
+        if_expr.or(match_expr).or(arithmetic)
     })
 }
 
@@ -447,7 +509,7 @@ fn setting<'src>() -> impl Parser<'src, &'src str, Setting, Extra<'src>> + Clone
         .then_ignore(ws())
         .then_ignore(just('='))
         .then_ignore(ws())
-        .then(spanned_value())
+        .then(spanned_expr())
         .map(|((first, rest), value)| {
             let mut path = vec![first];
             path.extend(rest);
@@ -455,13 +517,75 @@ fn setting<'src>() -> impl Parser<'src, &'src str, Setting, Extra<'src>> + Clone
         })
 }
 
+/// A key sequence's first chord and the chords that follow it.
+type Chords = (Spanned<KeyPart>, Vec<Spanned<KeyPart>>);
+
+/// A key sequence, its chords separated by spaces.
+fn chords<'src>() -> impl Parser<'src, &'src str, Chords, Extra<'src>> + Clone {
+    spanned_key_part().then(
+        required_ws()
+            .ignore_then(spanned_key_part())
+            .repeated()
+            .collect::<Vec<_>>(),
+    )
+}
+
 fn binding<'src>() -> impl Parser<'src, &'src str, Binding, Extra<'src>> + Clone {
-    spanned_key_part()
+    chords()
         .then_ignore(ws())
         .then_ignore(just("->"))
         .then_ignore(ws())
         .then(spanned_action_expr())
-        .map(|(key, action)| Binding { key, action })
+        .map(|((key, then), action)| Binding { key, then, action })
+}
+
+fn unbind<'src>() -> impl Parser<'src, &'src str, Unbind, Extra<'src>> + Clone {
+    just("unbind")
+        .ignore_then(required_ws())
+        .ignore_then(chords())
+        .map(|(key, then)| Unbind { key, then })
+}
+
+fn alias<'src>() -> impl Parser<'src, &'src str, Alias, Extra<'src>> + Clone {
+    just("alias")
+        .ignore_then(required_ws())
+        .ignore_then(spanned_string_literal())
+        .then_ignore(ws())
+        .then_ignore(just("->"))
+        .then_ignore(ws())
+        .then(spanned_ident())
+        .map(|(name, action)| Alias { name, action })
+}
+
+fn command<'src>() -> impl Parser<'src, &'src str, CommandDecl, Extra<'src>> + Clone {
+    let params = just('(')
+        .ignore_then(ws())
+        .ignore_then(
+            spanned_ident()
+                .separated_by(just(',').padded_by(ws()))
+                .allow_trailing()
+                .collect::<Vec<_>>(),
+        )
+        .then_ignore(ws())
+        .then_ignore(just(')'));
+
+    just("command")
+        .ignore_then(required_ws())
+        .ignore_then(spanned_ident())
+        .then_ignore(ws())
+        .then(params.or_not().map(Option::unwrap_or_default))
+        .then_ignore(ws())
+        .then(spanned_string_literal().or_not())
+        .then_ignore(ws())
+        .then_ignore(just("->"))
+        .then_ignore(ws())
+        .then(spanned_action_expr())
+        .map(|(((name, params), description), action)| CommandDecl {
+            name,
+            params,
+            description,
+            action,
+        })
 }
 
 fn let_stmt<'src>() -> impl Parser<'src, &'src str, LetBinding, Extra<'src>> + Clone {
@@ -484,6 +608,13 @@ fn fn_decl<'src>(
         .then_ignore(ws())
         .then_ignore(just('('))
         .then_ignore(ws())
+        .then(
+            spanned_ident()
+                .separated_by(just(',').padded_by(ws()))
+                .allow_trailing()
+                .collect::<Vec<_>>(),
+        )
+        .then_ignore(ws())
         .then_ignore(just(')'))
         .then_ignore(ws())
         .then_ignore(just('{'))
@@ -491,15 +622,41 @@ fn fn_decl<'src>(
         .then(stmt.repeated().collect::<Vec<_>>())
         .then_ignore(ws())
         .then_ignore(just('}'))
-        .map(|(name, body)| FnDecl { name, body })
+        .map(|((name, params), body)| FnDecl { name, params, body })
 }
 
-fn fn_call<'src>() -> impl Parser<'src, &'src str, Spanned<String>, Extra<'src>> + Clone {
+/// A chord passed to a `fn`. Only named keys are accepted, since a
+/// punctuation key like `)` or `,` would read as part of the call.
+fn spanned_key_arg<'src>() -> impl Parser<'src, &'src str, Spanned<KeyPart>, Extra<'src>> + Clone {
+    ident()
+        .map(|name| {
+            if name.chars().count() == 1 {
+                Key::Char(name.chars().next().expect("one char"))
+            } else {
+                Key::Named(name)
+            }
+        })
+        .separated_by(just('-'))
+        .at_least(1)
+        .collect::<Vec<_>>()
+        .map(|keys| KeyPart { keys })
+        .map_with(|node, e| Spanned::new(node, span_to_range(e.span())))
+}
+
+fn fn_call<'src>() -> impl Parser<'src, &'src str, FnCall, Extra<'src>> + Clone {
     spanned_ident()
         .then_ignore(ws())
         .then_ignore(just('('))
         .then_ignore(ws())
+        .then(
+            spanned_key_arg()
+                .separated_by(just(',').padded_by(ws()))
+                .allow_trailing()
+                .collect::<Vec<_>>(),
+        )
+        .then_ignore(ws())
         .then_ignore(just(')'))
+        .map(|(name, args)| FnCall { name, args })
 }
 
 fn predicate_block<'src>(
@@ -524,6 +681,9 @@ fn statement<'src>() -> impl Parser<'src, &'src str, Spanned<Statement>, Extra<'
         let fn_decl_stmt = fn_decl(stmt.clone()).map(Statement::FnDecl);
         let fn_call_stmt = fn_call().map(Statement::FnCall).then_ignore(semicolon());
         let let_binding = let_stmt().map(Statement::Let).then_ignore(semicolon());
+        let alias_stmt = alias().map(Statement::Alias).then_ignore(semicolon());
+        let command_stmt = command().map(Statement::Command).then_ignore(semicolon());
+        let unbind_stmt = unbind().map(Statement::Unbind).then_ignore(semicolon());
         let predicate_block_stmt = predicate_block(stmt).map(Statement::PredicateBlock);
         let binding_stmt = binding().map(Statement::Binding).then_ignore(semicolon());
         let setting_stmt = setting().map(Statement::Setting).then_ignore(semicolon());
@@ -532,6 +692,9 @@ fn statement<'src>() -> impl Parser<'src, &'src str, Spanned<Statement>, Extra<'
             fn_decl_stmt,
             fn_call_stmt,
             let_binding,
+            alias_stmt,
+            command_stmt,
+            unbind_stmt,
             predicate_block_stmt,
             binding_stmt,
             setting_stmt,
@@ -131,6 +131,13 @@ pub fn settings_schema() -> &'static [SettingDef] {
                   means relative).",
             default: "relative",
         },
+        SettingDef {
+            path: &[Lit("editor"), Lit("cursor_line")],
+            shape: ValueShape::Bool,
+            doc: "Whether the focused editor highlights its cursor line with the \
+                  `ui.cursorline` background.",
+            default: "false",
+        },
         SettingDef {
             path: &[Lit("editor"), Lit("minimap")],
             shape: ValueShape::Enum(&["off", "per_pane", "single"]),
@@ -154,6 +161,40 @@ pub fn settings_schema() -> &'static [SettingDef] {
                   text width.",
             default: "80",
         },
+        SettingDef {
+            path: &[Lit("editor"), Lit("wrap_break")],
+            shape: ValueShape::Enum(&["word", "anywhere"]),
+            doc: "Where soft wrap breaks long lines: word breaks between words \
+                  and around CJK characters, anywhere fills every row.",
+            default: "word",
+        },
+        SettingDef {
+            path: &[Lit("editor"), Lit("wrap_indent")],
+            shape: ValueShape::Number,
+            doc: "Extra columns wrapped continuation rows indent past the \
+                  line's own leading whitespace.",
+            default: "0",
+        },
+        SettingDef {
+            path: &[Lit("editor"), Lit("cursor_shape"), Wildcard("mode")],
+            shape: ValueShape::Enum(&["block", "bar", "underline"]),
+            doc: "Terminal cursor shape while the focused mode is `mode`.",
+            default: "terminal default",
+        },
+        SettingDef {
+            path: &[Lit("editor"), Lit("cursor_blink")],
+            shape: ValueShape::Number,
+            doc: "Milliseconds the cursor stays lit, then dark, while it \
+                  blinks. 0 keeps it steady.",
+            default: "0",
+        },
+        SettingDef {
+            path: &[Lit("editor"), Lit("cursor_animation")],
+            shape: ValueShape::Enum(&["smooth", "smear"]),
+            doc: "How stoatty animates cursor moves: smooth slides a block, \
+                  smear stretches it along the path.",
+            default: "terminal default",
+        },
         SettingDef {
             path: &[Lit("ui"), Lit("tab_bar")],
             shape: ValueShape::Enum(&["auto", "always", "never"]),
@@ -168,6 +209,13 @@ pub fn settings_schema() -> &'static [SettingDef] {
                   (0 disables).",
             default: "0.25",
         },
+        SettingDef {
+            path: &[Lit("ui"), Lit("which_key_delay")],
+            shape: ValueShape::Number,
+            doc: "Milliseconds a pending key sequence or chord sub-mode waits \
+                  before its which-key box shows.",
+            default: "0",
+        },
         SettingDef {
             path: &[Lit("terminal"), Lit("shell")],
             shape: ValueShape::String,
@@ -218,6 +266,18 @@ pub fn settings_schema() -> &'static [SettingDef] {
             doc: "Named finder scope, a list of workspace-relative globs.",
             default: "none",
         },
+        SettingDef {
+            path: &[Lit("task"), Lit("command"), Wildcard("name")],
+            shape: ValueShape::String,
+            doc: "Shell command a named task runs from the workspace root.",
+            default: "none",
+        },
+        SettingDef {
+            path: &[Lit("task"), Lit("matcher"), Wildcard("name")],
+            shape: ValueShape::String,
+            doc: "Problem matcher for a named task's output: cargo or none.",
+            default: "cargo",
+        },
         SettingDef {
             path: &[Lit("finder"), Lit("default_scope")],
             shape: ValueShape::String,
@@ -244,6 +304,57 @@ pub fn settings_schema() -> &'static [SettingDef] {
                   is applied.",
             default: "false",
         },
+        SettingDef {
+            path: &[Lit("spell"), Lit("enabled")],
+            shape: ValueShape::Bool,
+            doc: "Whether comments and strings are spell checked against \
+                  spell.dictionary and the workspace's .stoat/dictionary.txt.",
+            default: "false",
+        },
+        SettingDef {
+            path: &[Lit("spell"), Lit("dictionary")],
+            shape: ValueShape::String,
+            doc: "Word list, one word per line, that spell checking accepts.",
+            default: "/usr/share/dict/words",
+        },
+        SettingDef {
+            path: &[Lit("clipboard"), Lit("mirror")],
+            shape: ValueShape::Bool,
+            doc: "Whether yanks and deletes into the unnamed register are also \
+                  written to the system clipboard.",
+            default: "false",
+        },
+        SettingDef {
+            path: &[Lit("workspace"), Lit("state_format")],
+            shape: ValueShape::Enum(&["ron", "json", "msgpack"]),
+            doc: "Encoding for saved workspace state. Files in any format still \
+                  load.",
+            default: "ron",
+        },
+        SettingDef {
+            path: &[Lit("test"), Lit("lenses")],
+            shape: ValueShape::Bool,
+            doc: "Whether Rust test functions show a run-status lens above them.",
+            default: "false",
+        },
+        SettingDef {
+            path: &[Lit("editor"), Lit("color_swatches")],
+            shape: ValueShape::Bool,
+            doc: "Whether color literals in strings show a swatch of their color.",
+            default: "true",
+        },
+        SettingDef {
+            path: &[Lit("editor"), Lit("breadcrumbs")],
+            shape: ValueShape::Bool,
+            doc: "Whether the status bar shows the syntax items enclosing the cursor.",
+            default: "false",
+        },
+        SettingDef {
+            path: &[Lit("editor"), Lit("highlight_occurrences")],
+            shape: ValueShape::Bool,
+            doc: "Whether other occurrences of the identifier under the cursor are highlighted.",
+            default: "false",
+        },
     ]
 }
 
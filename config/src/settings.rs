@@ -6,7 +6,10 @@
 //! whether an override wins. Consumers read via
 //! `settings.field.unwrap_or(default)` at the point of use.
 
-use crate::ast::{Config, EventType, Setting, Spanned, Statement, Value};
+use crate::{
+    ast::{Action, ActionExpr, Config, EventType, Spanned, Statement, Value},
+    eval::{builtins, eval},
+};
 use std::collections::BTreeMap;
 
 /// Mouse-capture policy applied at terminal startup. `Auto` keeps the
@@ -64,6 +67,46 @@ pub enum WrapMode {
     Bounded,
 }
 
+/// Where soft wrap may break a long line. `Word` breaks between words and
+/// around CJK characters, splitting a token only when it alone overflows a
+/// row. `Anywhere` fills every row to the wrap width. `None` on the setting
+/// falls back to `Word`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapBreak {
+    Word,
+    Anywhere,
+}
+
+/// The terminal cursor's shape in a mode. `Block` covers the whole cell,
+/// `Bar` is a thin line before it, and `Underline` runs along its bottom. A
+/// mode with no shape set keeps the terminal's own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorShape {
+    Block,
+    Bar,
+    Underline,
+}
+
+/// How the terminal animates the cursor between cells under stoatty. `Smooth`
+/// slides a rigid block. `Smear` stretches it along its path and snaps back.
+/// `None` on the setting keeps the terminal's own `cursor_animation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorAnimation {
+    Smooth,
+    Smear,
+}
+
+/// How persisted workspace state is encoded on disk. `Ron` is readable and
+/// diffable, `Json` suits external tooling, and `MessagePack` is the most
+/// compact for sessions with long buffer histories. `None` on the setting falls
+/// back to `Ron`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateFormat {
+    Ron,
+    Json,
+    MessagePack,
+}
+
 /// Top-level resolved settings struct.
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Settings {
@@ -110,6 +153,11 @@ pub struct Settings {
     /// [`LineNumbers::Relative`]. Set `editor.line_numbers = relative | absolute
     /// | off;` in stcfg (`false` is accepted as `off`, `true` as `relative`).
     pub editor_line_numbers: Option<LineNumbers>,
+    /// Whether the focused editor washes its cursor line with the
+    /// `ui.cursorline` background. `None` falls back to off. Set
+    /// `editor.cursor_line = true;` in stcfg. The `:cursorline` command toggles
+    /// it at runtime.
+    pub editor_cursor_line: Option<bool>,
     /// The minimap strip mode for editor panes under stoatty, one of `off`,
     /// `per_pane`, or `single`. `None` falls back to [`MinimapMode::Single`].
     /// Set `editor.minimap = per_pane;` in stcfg to opt back (`false` means
@@ -125,6 +173,14 @@ pub struct Settings {
     /// width. `None` falls back at the consumer to 80. Set `editor.wrap_column =
     /// N;` in stcfg. Consulted only by [`WrapMode::Bounded`].
     pub editor_wrap_column: Option<u32>,
+    /// Where soft wrap breaks lines, one of `word` or `anywhere`. `None` falls
+    /// back at the consumer to [`WrapBreak::Word`]. Set `editor.wrap_break =
+    /// anywhere;` in stcfg to fill every row.
+    pub editor_wrap_break: Option<WrapBreak>,
+    /// Columns wrapped continuation rows indent past the line's own leading
+    /// whitespace, capped with it at half the wrap width. `None` falls back at
+    /// the consumer to 0. Set `editor.wrap_indent = N;` in stcfg.
+    pub editor_wrap_indent: Option<u32>,
     /// When the tab bar shows, one of `auto`, `always`, or `never`. `None`
     /// falls back to [`TabBarMode::Auto`], which reveals the bar only once a
     /// workspace holds a second tab. The `:tabs` command flips it at runtime.
@@ -134,6 +190,19 @@ pub struct Settings {
     /// disables dimming. Set `ui.inactive_dim = 0.4;` in stcfg. The raw value
     /// is stored here and clamped to 0.0..=1.0 at the consumer.
     pub ui_inactive_dim: Option<f64>,
+    /// Milliseconds a pending key sequence or chord sub-mode waits before its
+    /// which-key box shows, so a fast typist finishing `g g` or `space f`
+    /// never sees it flash. `None` or `0` shows it at once. Set
+    /// `ui.which_key_delay = 300;` in stcfg.
+    pub ui_which_key_delay: Option<u32>,
+    /// Milliseconds the terminal cursor stays lit, then dark, while it blinks.
+    /// `None` or `0` keeps it steady. Set `editor.cursor_blink = 500;` in stcfg.
+    /// Each new frame relights it, so it never blinks out while typing.
+    pub editor_cursor_blink: Option<u32>,
+    /// How stoatty animates the cursor's moves, one of `smooth` or `smear`.
+    /// `None` keeps the terminal's own setting. Set `editor.cursor_animation =
+    /// smear;` in stcfg. Other terminals ignore it.
+    pub editor_cursor_animation: Option<CursorAnimation>,
     /// How many hidden buffers keep their full highlight state (syntax tree,
     /// tokens) before the least-recently-shown are evicted. `None` falls back to
     /// 64. `0` drops a buffer's state as soon as it is hidden. Set via
@@ -161,12 +230,51 @@ pub struct Settings {
     /// in stcfg to restore the unset. A diff that loads an `.envrc` always
     /// applies regardless.
     pub direnv_unset_on_exit: Option<bool>,
+    /// Whether comment and string tokens are spell checked, publishing each
+    /// unknown word as a hint diagnostic. `None` falls back to disabled. Set
+    /// `spell.enabled = true;` in stcfg.
+    pub spell_enabled: Option<bool>,
+    /// Word list spell checking accepts, one word per line, on top of the
+    /// workspace's own `.stoat/dictionary.txt`. `None` falls back at the
+    /// consumer to `/usr/share/dict/words`. Set via `spell.dictionary =
+    /// "/path/to/words";` in stcfg.
+    pub spell_dictionary: Option<String>,
+    /// Whether writes to the unnamed register are mirrored to the system
+    /// clipboard, so a plain yank or delete also lands in other applications.
+    /// `None` falls back to disabled. Set `clipboard.mirror = true;` in stcfg.
+    pub clipboard_mirror: Option<bool>,
+    /// Encoding for saved workspace state, one of `ron`, `json`, or `msgpack`.
+    /// `None` falls back to [`StateFormat::Ron`]. Set `workspace.state_format =
+    /// json;` in stcfg. Files in any format still load, so switching never
+    /// loses a session.
+    pub state_format: Option<StateFormat>,
+    /// Whether Rust `#[test]` functions get a lens line above them showing
+    /// their last `RunTestAtCursor` result. `None` falls back to disabled. Set
+    /// `test.lenses = true;` in stcfg.
+    pub test_lenses: Option<bool>,
+    /// Whether hex and `rgb()` color literals inside strings get a swatch
+    /// inlay in their color. `None` falls back to enabled. Set
+    /// `editor.color_swatches = false;` in stcfg to turn them off.
+    pub color_swatches: Option<bool>,
+    /// Whether the focused editor's status bar shows the outline items
+    /// enclosing the cursor, e.g. `mod a > impl B > fn c`. `None` falls back
+    /// to disabled. Set `editor.breadcrumbs = true;` in stcfg.
+    pub editor_breadcrumbs: Option<bool>,
+    /// Whether the other occurrences of the identifier under a normal-mode
+    /// cursor are highlighted when no language server answers document
+    /// highlights. `None` falls back to disabled. Set
+    /// `editor.highlight_occurrences = true;` in stcfg.
+    pub editor_highlight_occurrences: Option<bool>,
     /// Per-mode status-line badge label overrides, keyed by mode name.
     /// Set via `ui.mode_badge.<name> = "ABC";` in stcfg. Renderer
     /// consults this map before falling back to its hardcoded badge
     /// table; user-defined modes can supply their own entry here so
     /// the status line shows something more meaningful than `---`.
     pub mode_badges: BTreeMap<String, String>,
+    /// Per-mode terminal cursor shapes, keyed by mode name. Set via
+    /// `editor.cursor_shape.<mode> = block | bar | underline;` in stcfg. A mode
+    /// with no entry keeps the terminal's default shape.
+    pub cursor_shapes: BTreeMap<String, CursorShape>,
     /// Per-language language-server command overrides, keyed by language
     /// name. Each value is an argv whose first element is the executable
     /// and the rest are arguments. Set via
@@ -202,21 +310,85 @@ pub struct Settings {
     /// to All. Names a builtin (`all`/`modified`) or a `finder.scope.<name>`
     /// entry. Set via `finder.default_scope = "src";` in stcfg.
     pub finder_default_scope: Option<String>,
+    /// Named tasks, each a shell command run from the workspace root by
+    /// `RunTask`. Set via `task.command.<name> = "cargo build";` in stcfg.
+    pub task_commands: BTreeMap<String, String>,
+    /// Problem matcher per task name, parsing the task's output into
+    /// diagnostics. Set via `task.matcher.<name> = cargo;` in stcfg. A task
+    /// with no entry uses the `cargo` matcher; `none` turns parsing off.
+    pub task_matchers: BTreeMap<String, String>,
+    /// Extra command-palette names for actions, keyed by the alias text with
+    /// the action name it runs as the value. Set via `alias "format document"
+    /// -> Format;` in an `on init` block. The palette matches an alias like the
+    /// action's own name, so a user can reach actions by localized or personal
+    /// names.
+    pub palette_aliases: BTreeMap<String, String>,
+    /// User commands, keyed by name. Set via `command name(param)
+    /// "description" -> [Action(), ...];` in an `on init` block, and run by
+    /// `RunCommand`. A later command of the same name replaces an earlier one.
+    pub commands: BTreeMap<String, UserCommand>,
+}
+
+/// A `command` statement, resolved for running.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserCommand {
+    /// One-line summary shown beside the name in the palette.
+    pub description: Option<String>,
+    /// Parameter names, filled in order from the words typed after the name.
+    pub params: Vec<String>,
+    /// Actions run in order. A `$param` argument names a parameter.
+    pub actions: Vec<Action>,
 }
 
 impl Settings {
     /// Extracts known settings from `on init` blocks in `config`. Unknown
     /// setting paths are silently ignored so a config file that references
     /// a future setting on an older binary does not fail to parse.
+    ///
+    /// Setting values are evaluated with [`crate::builtins`] and the block's
+    /// earlier `let` bindings in scope, so `editor.scrolloff = $margin * 2;`
+    /// reads `margin`. A value that fails to evaluate is ignored like one of
+    /// the wrong type.
     pub fn from_config(config: &Config) -> Self {
         let mut out = Settings::default();
         for block in &config.blocks {
             if block.node.event != EventType::Init {
                 continue;
             }
+            let mut env = builtins();
             for stmt in &block.node.statements {
-                if let Statement::Setting(setting) = &stmt.node {
-                    out.apply(setting);
+                match &stmt.node {
+                    Statement::Let(binding) => {
+                        if let Ok(value) = eval(&binding.value, &env) {
+                            env.insert(binding.name.node.clone(), value);
+                        }
+                    },
+                    Statement::Setting(setting) => {
+                        if let Ok(value) = eval(&setting.value, &env) {
+                            out.apply(&setting.path, &value);
+                        }
+                    },
+                    Statement::Alias(alias) => {
+                        out.palette_aliases
+                            .insert(alias.name.node.clone(), alias.action.node.clone());
+                    },
+                    Statement::Command(command) => {
+                        let actions = match &command.action.node {
+                            ActionExpr::Single(action) => vec![action.clone()],
+                            ActionExpr::Sequence(actions) => {
+                                actions.iter().map(|a| a.node.clone()).collect()
+                            },
+                        };
+                        out.commands.insert(
+                            command.name.node.clone(),
+                            UserCommand {
+                                description: command.description.as_ref().map(|d| d.node.clone()),
+                                params: command.params.iter().map(|p| p.node.clone()).collect(),
+                                actions,
+                            },
+                        );
+                    },
+                    _ => {},
                 }
             }
         }
@@ -228,6 +400,8 @@ impl Settings {
     pub fn merge(self, other: Settings) -> Settings {
         let mut mode_badges = self.mode_badges;
         mode_badges.extend(other.mode_badges);
+        let mut cursor_shapes = self.cursor_shapes;
+        cursor_shapes.extend(other.cursor_shapes);
         let mut lsp_servers = self.lsp_servers;
         lsp_servers.extend(other.lsp_servers);
         let mut lsp_server_lists = self.lsp_server_lists;
@@ -240,6 +414,14 @@ impl Settings {
         lsp_except.extend(other.lsp_except);
         let mut finder_scopes = self.finder_scopes;
         finder_scopes.extend(other.finder_scopes);
+        let mut task_commands = self.task_commands;
+        task_commands.extend(other.task_commands);
+        let mut task_matchers = self.task_matchers;
+        task_matchers.extend(other.task_matchers);
+        let mut palette_aliases = self.palette_aliases;
+        palette_aliases.extend(other.palette_aliases);
+        let mut commands = self.commands;
+        commands.extend(other.commands);
         Settings {
             text_proto_log: other.text_proto_log.or(self.text_proto_log),
             format_on_save: other.format_on_save.or(self.format_on_save),
@@ -251,18 +433,37 @@ impl Settings {
             mouse_capture: other.mouse_capture.or(self.mouse_capture),
             scrolloff: other.scrolloff.or(self.scrolloff),
             editor_line_numbers: other.editor_line_numbers.or(self.editor_line_numbers),
+            editor_cursor_line: other.editor_cursor_line.or(self.editor_cursor_line),
             editor_minimap: other.editor_minimap.or(self.editor_minimap),
             editor_wrap: other.editor_wrap.or(self.editor_wrap),
             editor_wrap_column: other.editor_wrap_column.or(self.editor_wrap_column),
+            editor_wrap_break: other.editor_wrap_break.or(self.editor_wrap_break),
+            editor_wrap_indent: other.editor_wrap_indent.or(self.editor_wrap_indent),
             ui_tab_bar: other.ui_tab_bar.or(self.ui_tab_bar),
             ui_inactive_dim: other.ui_inactive_dim.or(self.ui_inactive_dim),
+            ui_which_key_delay: other.ui_which_key_delay.or(self.ui_which_key_delay),
+            editor_cursor_blink: other.editor_cursor_blink.or(self.editor_cursor_blink),
+            editor_cursor_animation: other
+                .editor_cursor_animation
+                .or(self.editor_cursor_animation),
             highlight_retention: other.highlight_retention.or(self.highlight_retention),
             terminal_shell: other.terminal_shell.or(self.terminal_shell),
             terminal_args: other.terminal_args.or(self.terminal_args),
             direnv_load: other.direnv_load.or(self.direnv_load),
             direnv_reload_on_cd: other.direnv_reload_on_cd.or(self.direnv_reload_on_cd),
             direnv_unset_on_exit: other.direnv_unset_on_exit.or(self.direnv_unset_on_exit),
+            spell_enabled: other.spell_enabled.or(self.spell_enabled),
+            spell_dictionary: other.spell_dictionary.or(self.spell_dictionary),
+            clipboard_mirror: other.clipboard_mirror.or(self.clipboard_mirror),
+            state_format: other.state_format.or(self.state_format),
+            test_lenses: other.test_lenses.or(self.test_lenses),
+            color_swatches: other.color_swatches.or(self.color_swatches),
+            editor_breadcrumbs: other.editor_breadcrumbs.or(self.editor_breadcrumbs),
+            editor_highlight_occurrences: other
+                .editor_highlight_occurrences
+                .or(self.editor_highlight_occurrences),
             mode_badges,
+            cursor_shapes,
             lsp_servers,
             lsp_server_lists,
             lsp_commands,
@@ -270,54 +471,58 @@ impl Settings {
             lsp_except,
             finder_scopes,
             finder_default_scope: other.finder_default_scope.or(self.finder_default_scope),
+            task_commands,
+            task_matchers,
+            palette_aliases,
+            commands,
         }
     }
 
-    fn apply(&mut self, setting: &Setting) {
-        let path: Vec<&str> = setting.path.iter().map(|p| p.node.as_str()).collect();
+    fn apply(&mut self, path: &[Spanned<String>], value: &Value) {
+        let path: Vec<&str> = path.iter().map(|p| p.node.as_str()).collect();
         match path.as_slice() {
             ["text_proto_log"] => {
-                if let Value::Bool(b) = setting.value.node {
+                if let Value::Bool(b) = *value {
                     self.text_proto_log = Some(b);
                 }
             },
             ["format_on_save"] => {
-                if let Value::Bool(b) = setting.value.node {
+                if let Value::Bool(b) = *value {
                     self.format_on_save = Some(b);
                 }
             },
             ["config", "auto_reload"] => {
-                if let Value::Bool(b) = setting.value.node {
+                if let Value::Bool(b) = *value {
                     self.config_auto_reload = Some(b);
                 }
             },
             ["review", "follow"] => {
-                if let Value::Bool(b) = setting.value.node {
+                if let Value::Bool(b) = *value {
                     self.review_follow = Some(b);
                 }
             },
             ["review", "rebase_head"] => {
-                if let Value::Bool(b) = setting.value.node {
+                if let Value::Bool(b) = *value {
                     self.review_rebase_head = Some(b);
                 }
             },
             ["review", "precompute"] => {
-                if let Value::Bool(b) = setting.value.node {
+                if let Value::Bool(b) = *value {
                     self.review_precompute = Some(b);
                 }
             },
             ["theme"] => {
-                if let Value::Ident(s) | Value::String(s) = &setting.value.node {
+                if let Value::Ident(s) | Value::String(s) = value {
                     self.theme = Some(s.clone());
                 }
             },
             ["ui", "mode_badge", name] => {
-                if let Value::String(badge) | Value::Ident(badge) = &setting.value.node {
+                if let Value::String(badge) | Value::Ident(badge) = value {
                     self.mode_badges.insert((*name).to_string(), badge.clone());
                 }
             },
             ["mouse", "capture"] => {
-                let raw = match &setting.value.node {
+                let raw = match value {
                     Value::String(s) | Value::Ident(s) => Some(s.as_str()),
                     _ => None,
                 };
@@ -332,17 +537,17 @@ impl Settings {
                 }
             },
             ["editor", "scrolloff"] => {
-                if let Value::Number(n) = setting.value.node {
+                if let Value::Number(n) = *value {
                     self.scrolloff = Some(n as u32);
                 }
             },
             ["editor", "highlight_retention"] => {
-                if let Value::Number(n) = setting.value.node {
+                if let Value::Number(n) = *value {
                     self.highlight_retention = Some(n as u32);
                 }
             },
             ["editor", "line_numbers"] => {
-                let numbers = match &setting.value.node {
+                let numbers = match value {
                     Value::Bool(false) => Some(LineNumbers::Off),
                     Value::Bool(true) => Some(LineNumbers::Relative),
                     Value::String(s) | Value::Ident(s) => match s.as_str() {
@@ -357,8 +562,13 @@ impl Settings {
                     self.editor_line_numbers = Some(n);
                 }
             },
+            ["editor", "cursor_line"] => {
+                if let Value::Bool(b) = *value {
+                    self.editor_cursor_line = Some(b);
+                }
+            },
             ["editor", "minimap"] => {
-                let mode = match &setting.value.node {
+                let mode = match value {
                     Value::Bool(false) => Some(MinimapMode::Off),
                     Value::Bool(true) => Some(MinimapMode::Single),
                     Value::String(s) | Value::Ident(s) => match s.as_str() {
@@ -374,7 +584,7 @@ impl Settings {
                 }
             },
             ["editor", "wrap"] => {
-                let mode = match &setting.value.node {
+                let mode = match value {
                     Value::Bool(false) => Some(WrapMode::None),
                     Value::Bool(true) => Some(WrapMode::EditorWidth),
                     Value::String(s) | Value::Ident(s) => match s.as_str() {
@@ -390,12 +600,62 @@ impl Settings {
                 }
             },
             ["editor", "wrap_column"] => {
-                if let Value::Number(n) = setting.value.node {
+                if let Value::Number(n) = *value {
                     self.editor_wrap_column = Some(n as u32);
                 }
             },
+            ["editor", "wrap_break"] => {
+                let mode = match value {
+                    Value::String(s) | Value::Ident(s) => match s.as_str() {
+                        "word" => Some(WrapBreak::Word),
+                        "anywhere" => Some(WrapBreak::Anywhere),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(m) = mode {
+                    self.editor_wrap_break = Some(m);
+                }
+            },
+            ["editor", "wrap_indent"] => {
+                if let Value::Number(n) = *value {
+                    self.editor_wrap_indent = Some(n as u32);
+                }
+            },
+            ["editor", "cursor_shape", mode] => {
+                let shape = match value {
+                    Value::String(s) | Value::Ident(s) => match s.as_str() {
+                        "block" => Some(CursorShape::Block),
+                        "bar" => Some(CursorShape::Bar),
+                        "underline" => Some(CursorShape::Underline),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(shape) = shape {
+                    self.cursor_shapes.insert((*mode).to_string(), shape);
+                }
+            },
+            ["editor", "cursor_blink"] => {
+                if let Value::Number(n) = *value {
+                    self.editor_cursor_blink = Some(n as u32);
+                }
+            },
+            ["editor", "cursor_animation"] => {
+                let animation = match value {
+                    Value::String(s) | Value::Ident(s) => match s.as_str() {
+                        "smooth" => Some(CursorAnimation::Smooth),
+                        "smear" => Some(CursorAnimation::Smear),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(a) = animation {
+                    self.editor_cursor_animation = Some(a);
+                }
+            },
             ["ui", "tab_bar"] => {
-                let mode = match &setting.value.node {
+                let mode = match value {
                     Value::String(s) | Value::Ident(s) => match s.as_str() {
                         "auto" => Some(TabBarMode::Auto),
                         "always" => Some(TabBarMode::Always),
@@ -409,17 +669,22 @@ impl Settings {
                 }
             },
             ["ui", "inactive_dim"] => {
-                if let Value::Number(n) = setting.value.node {
+                if let Value::Number(n) = *value {
                     self.ui_inactive_dim = Some(n);
                 }
             },
+            ["ui", "which_key_delay"] => {
+                if let Value::Number(n) = *value {
+                    self.ui_which_key_delay = Some(n as u32);
+                }
+            },
             ["terminal", "shell"] => {
-                if let Value::Ident(s) | Value::String(s) = &setting.value.node {
+                if let Value::Ident(s) | Value::String(s) = value {
                     self.terminal_shell = Some(s.clone());
                 }
             },
             ["terminal", "args"] => {
-                if let Value::Array(items) = &setting.value.node {
+                if let Value::Array(items) = value {
                     self.terminal_args = Some(
                         items
                             .iter()
@@ -432,37 +697,37 @@ impl Settings {
                 }
             },
             ["lsp", "server", language] => {
-                if let Value::Array(items) = &setting.value.node {
+                if let Value::Array(items) = value {
                     self.lsp_servers
                         .insert((*language).to_string(), string_array(items));
                 }
             },
             ["lsp", "servers", language] => {
-                if let Value::Array(items) = &setting.value.node {
+                if let Value::Array(items) = value {
                     self.lsp_server_lists
                         .insert((*language).to_string(), string_array(items));
                 }
             },
             ["lsp", "command", name] => {
-                if let Value::Array(items) = &setting.value.node {
+                if let Value::Array(items) = value {
                     self.lsp_commands
                         .insert((*name).to_string(), string_array(items));
                 }
             },
             ["lsp", "only", name] => {
-                if let Value::Array(items) = &setting.value.node {
+                if let Value::Array(items) = value {
                     self.lsp_only
                         .insert((*name).to_string(), string_array(items));
                 }
             },
             ["lsp", "except", name] => {
-                if let Value::Array(items) = &setting.value.node {
+                if let Value::Array(items) = value {
                     self.lsp_except
                         .insert((*name).to_string(), string_array(items));
                 }
             },
             ["finder", "scope", name] => {
-                if let Value::Array(items) = &setting.value.node {
+                if let Value::Array(items) = value {
                     let globs: Vec<String> = items
                         .iter()
                         .filter_map(|item| match &item.node {
@@ -474,25 +739,84 @@ impl Settings {
                 }
             },
             ["finder", "default_scope"] => {
-                if let Value::Ident(s) | Value::String(s) = &setting.value.node {
+                if let Value::Ident(s) | Value::String(s) = value {
                     self.finder_default_scope = Some(s.clone());
                 }
             },
+            ["task", "command", name] => {
+                if let Value::String(s) = value {
+                    self.task_commands.insert((*name).to_string(), s.clone());
+                }
+            },
+            ["task", "matcher", name] => {
+                if let Value::Ident(s) | Value::String(s) = value {
+                    self.task_matchers.insert((*name).to_string(), s.clone());
+                }
+            },
             ["direnv", "load"] => {
-                if let Value::Bool(b) = setting.value.node {
+                if let Value::Bool(b) = *value {
                     self.direnv_load = Some(b);
                 }
             },
             ["direnv", "reload_on_cd"] => {
-                if let Value::Bool(b) = setting.value.node {
+                if let Value::Bool(b) = *value {
                     self.direnv_reload_on_cd = Some(b);
                 }
             },
             ["direnv", "unset_on_exit"] => {
-                if let Value::Bool(b) = setting.value.node {
+                if let Value::Bool(b) = *value {
                     self.direnv_unset_on_exit = Some(b);
                 }
             },
+            ["spell", "enabled"] => {
+                if let Value::Bool(b) = *value {
+                    self.spell_enabled = Some(b);
+                }
+            },
+            ["spell", "dictionary"] => {
+                if let Value::Ident(s) | Value::String(s) = value {
+                    self.spell_dictionary = Some(s.clone());
+                }
+            },
+            ["clipboard", "mirror"] => {
+                if let Value::Bool(b) = *value {
+                    self.clipboard_mirror = Some(b);
+                }
+            },
+            ["workspace", "state_format"] => {
+                let format = match value {
+                    Value::String(s) | Value::Ident(s) => match s.as_str() {
+                        "ron" => Some(StateFormat::Ron),
+                        "json" => Some(StateFormat::Json),
+                        "msgpack" => Some(StateFormat::MessagePack),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(f) = format {
+                    self.state_format = Some(f);
+                }
+            },
+            ["test", "lenses"] => {
+                if let Value::Bool(b) = *value {
+                    self.test_lenses = Some(b);
+                }
+            },
+            ["editor", "color_swatches"] => {
+                if let Value::Bool(b) = *value {
+                    self.color_swatches = Some(b);
+                }
+            },
+            ["editor", "breadcrumbs"] => {
+                if let Value::Bool(b) = *value {
+                    self.editor_breadcrumbs = Some(b);
+                }
+            },
+            ["editor", "highlight_occurrences"] => {
+                if let Value::Bool(b) = *value {
+                    self.editor_highlight_occurrences = Some(b);
+                }
+            },
             _ => {},
         }
     }
@@ -537,25 +861,44 @@ mod tests {
                 mouse_capture: None,
                 scrolloff: None,
                 editor_line_numbers: None,
+                editor_cursor_line: None,
                 editor_minimap: None,
                 editor_wrap: None,
                 editor_wrap_column: None,
+                editor_wrap_break: None,
+                editor_wrap_indent: None,
                 ui_tab_bar: None,
                 ui_inactive_dim: None,
+                ui_which_key_delay: None,
+                editor_cursor_blink: None,
+                editor_cursor_animation: None,
                 highlight_retention: None,
                 terminal_shell: None,
                 terminal_args: None,
                 direnv_load: None,
                 direnv_reload_on_cd: None,
                 direnv_unset_on_exit: None,
+                spell_enabled: None,
+                spell_dictionary: None,
+                clipboard_mirror: None,
+                state_format: None,
+                test_lenses: None,
+                color_swatches: None,
+                editor_breadcrumbs: None,
+                editor_highlight_occurrences: None,
                 mode_badges: BTreeMap::new(),
+                cursor_shapes: BTreeMap::new(),
                 lsp_servers: BTreeMap::new(),
                 lsp_server_lists: BTreeMap::new(),
                 lsp_commands: BTreeMap::new(),
                 lsp_only: BTreeMap::new(),
                 lsp_except: BTreeMap::new(),
                 finder_scopes: BTreeMap::new(),
+                task_commands: BTreeMap::new(),
+                task_matchers: BTreeMap::new(),
                 finder_default_scope: None,
+                palette_aliases: BTreeMap::new(),
+                commands: BTreeMap::new(),
             }
         );
     }
@@ -584,6 +927,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_config_collects_commands() {
+        let config = parse_ok(
+            r#"on init {
+                command grep(pattern) "Search" -> [Search($pattern), SetMode(normal)];
+                command w -> SaveBuffer();
+                command w -> WriteAll();
+            }"#,
+        );
+        let commands = Settings::from_config(&config).commands;
+        let grep = &commands["grep"];
+        assert_eq!(grep.params, ["pattern"]);
+        assert_eq!(grep.description.as_deref(), Some("Search"));
+        let names: Vec<&str> = grep.actions.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["Search", "SetMode"]);
+        assert_eq!(
+            commands["w"].actions[0].name, "WriteAll",
+            "a repeated command name keeps the last body"
+        );
+    }
+
+    #[test]
+    fn from_config_extracts_state_format() {
+        let format = |src: &str| Settings::from_config(&parse_ok(src)).state_format;
+        assert_eq!(
+            format("on init { workspace.state_format = msgpack; }"),
+            Some(StateFormat::MessagePack)
+        );
+        assert_eq!(
+            format(r#"on init { workspace.state_format = "json"; }"#),
+            Some(StateFormat::Json)
+        );
+        assert_eq!(format("on init { workspace.state_format = yaml; }"), None);
+    }
+
     #[test]
     fn from_config_extracts_config_auto_reload() {
         let config = parse_ok("on init { config.auto_reload = false; }");
@@ -618,6 +996,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_config_extracts_editor_cursor_line() {
+        let cl = |src: &str| Settings::from_config(&parse_ok(src)).editor_cursor_line;
+        assert_eq!(cl("on init { editor.cursor_line = true; }"), Some(true));
+        assert_eq!(cl("on init { editor.cursor_line = false; }"), Some(false));
+        assert_eq!(cl("on init { editor.cursor_line = 3; }"), None);
+    }
+
     #[test]
     fn from_config_extracts_editor_minimap() {
         let mode = |src: &str| Settings::from_config(&parse_ok(src)).editor_minimap;
@@ -688,6 +1074,35 @@ mod tests {
         assert_eq!(Settings::from_config(&config).editor_wrap_column, Some(60));
     }
 
+    #[test]
+    fn from_config_extracts_editor_wrap_break_and_indent() {
+        let settings = Settings::from_config(&parse_ok(
+            "on init { editor.wrap_break = anywhere; editor.wrap_indent = 2; }",
+        ));
+        assert_eq!(settings.editor_wrap_break, Some(WrapBreak::Anywhere));
+        assert_eq!(settings.editor_wrap_indent, Some(2));
+        let unknown = Settings::from_config(&parse_ok("on init { editor.wrap_break = never; }"));
+        assert_eq!(unknown.editor_wrap_break, None);
+    }
+
+    #[test]
+    fn from_config_extracts_cursor_shape_blink_and_animation() {
+        let settings = Settings::from_config(&parse_ok(
+            "on init { editor.cursor_shape.insert = bar; editor.cursor_shape.select = squiggle; \
+             editor.cursor_blink = 500; editor.cursor_animation = smear; }",
+        ));
+        assert_eq!(
+            settings.cursor_shapes,
+            BTreeMap::from([("insert".to_string(), CursorShape::Bar)]),
+            "an unknown shape is ignored",
+        );
+        assert_eq!(settings.editor_cursor_blink, Some(500));
+        assert_eq!(
+            settings.editor_cursor_animation,
+            Some(CursorAnimation::Smear)
+        );
+    }
+
     #[test]
     fn from_config_extracts_ui_inactive_dim() {
         let dim = |src: &str| Settings::from_config(&parse_ok(src)).ui_inactive_dim;
@@ -696,6 +1111,12 @@ mod tests {
         assert_eq!(dim("on init { }"), None, "absent falls back at consumer");
     }
 
+    #[test]
+    fn from_config_extracts_ui_which_key_delay() {
+        let config = parse_ok("on init { ui.which_key_delay = 300; }");
+        assert_eq!(Settings::from_config(&config).ui_which_key_delay, Some(300));
+    }
+
     #[test]
     fn from_config_extracts_highlight_retention() {
         let config = parse_ok("on init { editor.highlight_retention = 8; }");
@@ -770,6 +1191,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_config_extracts_tasks() {
+        let config = parse_ok(
+            r#"on init {
+                task.command.build = "cargo build";
+                task.command.test = "cargo test";
+                task.matcher.test = none;
+            }"#,
+        );
+        let settings = Settings::from_config(&config);
+        assert_eq!(
+            settings.task_commands,
+            BTreeMap::from([
+                ("build".to_string(), "cargo build".to_string()),
+                ("test".to_string(), "cargo test".to_string()),
+            ]),
+        );
+        assert_eq!(
+            settings.task_matchers,
+            BTreeMap::from([("test".to_string(), "none".to_string())]),
+        );
+    }
+
     #[test]
     fn merge_finder_scopes_extend_and_default_right_wins() {
         let left = Settings {
@@ -793,6 +1237,28 @@ mod tests {
         assert_eq!(merged.finder_default_scope, Some("b".to_string()));
     }
 
+    #[test]
+    fn from_config_collects_palette_aliases() {
+        let config = parse_ok(
+            r#"on init {
+                alias "format document" -> Format;
+                alias "dokument formatieren" -> Format;
+                alias "format document" -> FormatSelections;
+            }"#,
+        );
+        assert_eq!(
+            Settings::from_config(&config).palette_aliases,
+            BTreeMap::from([
+                ("dokument formatieren".to_string(), "Format".to_string()),
+                (
+                    "format document".to_string(),
+                    "FormatSelections".to_string()
+                ),
+            ]),
+            "a repeated alias name keeps the last target"
+        );
+    }
+
     #[test]
     fn from_config_false_value() {
         let config = parse_ok("on init { text_proto_log = false; }");
@@ -809,25 +1275,44 @@ mod tests {
                 mouse_capture: None,
                 scrolloff: None,
                 editor_line_numbers: None,
+                editor_cursor_line: None,
                 editor_minimap: None,
                 editor_wrap: None,
                 editor_wrap_column: None,
+                editor_wrap_break: None,
+                editor_wrap_indent: None,
                 ui_tab_bar: None,
                 ui_inactive_dim: None,
+                ui_which_key_delay: None,
+                editor_cursor_blink: None,
+                editor_cursor_animation: None,
                 highlight_retention: None,
                 terminal_shell: None,
                 terminal_args: None,
                 direnv_load: None,
                 direnv_reload_on_cd: None,
                 direnv_unset_on_exit: None,
+                spell_enabled: None,
+                spell_dictionary: None,
+                clipboard_mirror: None,
+                state_format: None,
+                test_lenses: None,
+                color_swatches: None,
+                editor_breadcrumbs: None,
+                editor_highlight_occurrences: None,
                 mode_badges: BTreeMap::new(),
+                cursor_shapes: BTreeMap::new(),
                 lsp_servers: BTreeMap::new(),
                 lsp_server_lists: BTreeMap::new(),
                 lsp_commands: BTreeMap::new(),
                 lsp_only: BTreeMap::new(),
                 lsp_except: BTreeMap::new(),
                 finder_scopes: BTreeMap::new(),
+                task_commands: BTreeMap::new(),
+                task_matchers: BTreeMap::new(),
                 finder_default_scope: None,
+                palette_aliases: BTreeMap::new(),
+                commands: BTreeMap::new(),
             }
         );
     }
@@ -848,25 +1333,44 @@ mod tests {
                 mouse_capture: None,
                 scrolloff: None,
                 editor_line_numbers: None,
+                editor_cursor_line: None,
                 editor_minimap: None,
                 editor_wrap: None,
                 editor_wrap_column: None,
+                editor_wrap_break: None,
+                editor_wrap_indent: None,
                 ui_tab_bar: None,
                 ui_inactive_dim: None,
+                ui_which_key_delay: None,
+                editor_cursor_blink: None,
+                editor_cursor_animation: None,
                 highlight_retention: None,
                 terminal_shell: None,
                 terminal_args: None,
                 direnv_load: None,
                 direnv_reload_on_cd: None,
                 direnv_unset_on_exit: None,
+                spell_enabled: None,
+                spell_dictionary: None,
+                clipboard_mirror: None,
+                state_format: None,
+                test_lenses: None,
+                color_swatches: None,
+                editor_breadcrumbs: None,
+                editor_highlight_occurrences: None,
                 mode_badges: BTreeMap::new(),
+                cursor_shapes: BTreeMap::new(),
                 lsp_servers: BTreeMap::new(),
                 lsp_server_lists: BTreeMap::new(),
                 lsp_commands: BTreeMap::new(),
                 lsp_only: BTreeMap::new(),
                 lsp_except: BTreeMap::new(),
                 finder_scopes: BTreeMap::new(),
+                task_commands: BTreeMap::new(),
+                task_matchers: BTreeMap::new(),
                 finder_default_scope: None,
+                palette_aliases: BTreeMap::new(),
+                commands: BTreeMap::new(),
             }
         );
     }
@@ -896,25 +1400,44 @@ mod tests {
             mouse_capture: None,
             scrolloff: None,
             editor_line_numbers: None,
+            editor_cursor_line: None,
             editor_minimap: None,
             editor_wrap: None,
             editor_wrap_column: None,
+            editor_wrap_break: None,
+            editor_wrap_indent: None,
             ui_tab_bar: None,
             ui_inactive_dim: None,
+            ui_which_key_delay: None,
+            editor_cursor_blink: None,
+            editor_cursor_animation: None,
             highlight_retention: None,
             terminal_shell: None,
             terminal_args: None,
             direnv_load: None,
             direnv_reload_on_cd: None,
             direnv_unset_on_exit: None,
+            spell_enabled: None,
+            spell_dictionary: None,
+            clipboard_mirror: None,
+            state_format: None,
+            test_lenses: None,
+            color_swatches: None,
+            editor_breadcrumbs: None,
+            editor_highlight_occurrences: None,
             mode_badges: BTreeMap::new(),
+            cursor_shapes: BTreeMap::new(),
             lsp_servers: BTreeMap::new(),
             lsp_server_lists: BTreeMap::new(),
             lsp_commands: BTreeMap::new(),
             lsp_only: BTreeMap::new(),
             lsp_except: BTreeMap::new(),
             finder_scopes: BTreeMap::new(),
+            task_commands: BTreeMap::new(),
+            task_matchers: BTreeMap::new(),
             finder_default_scope: None,
+            palette_aliases: BTreeMap::new(),
+            commands: BTreeMap::new(),
         };
         let right = Settings {
             text_proto_log: Some(true),
@@ -927,25 +1450,44 @@ mod tests {
             mouse_capture: None,
             scrolloff: None,
             editor_line_numbers: None,
+            editor_cursor_line: None,
             editor_minimap: None,
             editor_wrap: None,
             editor_wrap_column: None,
+            editor_wrap_break: None,
+            editor_wrap_indent: None,
             ui_tab_bar: None,
             ui_inactive_dim: None,
+            ui_which_key_delay: None,
+            editor_cursor_blink: None,
+            editor_cursor_animation: None,
             highlight_retention: None,
             terminal_shell: None,
             terminal_args: None,
             direnv_load: None,
             direnv_reload_on_cd: None,
             direnv_unset_on_exit: None,
+            spell_enabled: None,
+            spell_dictionary: None,
+            clipboard_mirror: None,
+            state_format: None,
+            test_lenses: None,
+            color_swatches: None,
+            editor_breadcrumbs: None,
+            editor_highlight_occurrences: None,
             mode_badges: BTreeMap::new(),
+            cursor_shapes: BTreeMap::new(),
             lsp_servers: BTreeMap::new(),
             lsp_server_lists: BTreeMap::new(),
             lsp_commands: BTreeMap::new(),
             lsp_only: BTreeMap::new(),
             lsp_except: BTreeMap::new(),
             finder_scopes: BTreeMap::new(),
+            task_commands: BTreeMap::new(),
+            task_matchers: BTreeMap::new(),
             finder_default_scope: None,
+            palette_aliases: BTreeMap::new(),
+            commands: BTreeMap::new(),
         };
         assert_eq!(
             left.merge(right),
@@ -960,25 +1502,44 @@ mod tests {
                 mouse_capture: None,
                 scrolloff: None,
                 editor_line_numbers: None,
+                editor_cursor_line: None,
                 editor_minimap: None,
                 editor_wrap: None,
                 editor_wrap_column: None,
+                editor_wrap_break: None,
+                editor_wrap_indent: None,
                 ui_tab_bar: None,
                 ui_inactive_dim: None,
+                ui_which_key_delay: None,
+                editor_cursor_blink: None,
+                editor_cursor_animation: None,
                 highlight_retention: None,
                 terminal_shell: None,
                 terminal_args: None,
                 direnv_load: None,
                 direnv_reload_on_cd: None,
                 direnv_unset_on_exit: None,
+                spell_enabled: None,
+                spell_dictionary: None,
+                clipboard_mirror: None,
+                state_format: None,
+                test_lenses: None,
+                color_swatches: None,
+                editor_breadcrumbs: None,
+                editor_highlight_occurrences: None,
                 mode_badges: BTreeMap::new(),
+                cursor_shapes: BTreeMap::new(),
                 lsp_servers: BTreeMap::new(),
                 lsp_server_lists: BTreeMap::new(),
                 lsp_commands: BTreeMap::new(),
                 lsp_only: BTreeMap::new(),
                 lsp_except: BTreeMap::new(),
                 finder_scopes: BTreeMap::new(),
+                task_commands: BTreeMap::new(),
+                task_matchers: BTreeMap::new(),
                 finder_default_scope: None,
+                palette_aliases: BTreeMap::new(),
+                commands: BTreeMap::new(),
             }
         );
     }
@@ -996,25 +1557,44 @@ mod tests {
             mouse_capture: None,
             scrolloff: None,
             editor_line_numbers: None,
+            editor_cursor_line: None,
             editor_minimap: None,
             editor_wrap: None,
             editor_wrap_column: None,
+            editor_wrap_break: None,
+            editor_wrap_indent: None,
             ui_tab_bar: None,
             ui_inactive_dim: None,
+            ui_which_key_delay: None,
+            editor_cursor_blink: None,
+            editor_cursor_animation: None,
             highlight_retention: None,
             terminal_shell: None,
             terminal_args: None,
             direnv_load: None,
             direnv_reload_on_cd: None,
             direnv_unset_on_exit: None,
+            spell_enabled: None,
+            spell_dictionary: None,
+            clipboard_mirror: None,
+            state_format: None,
+            test_lenses: None,
+            color_swatches: None,
+            editor_breadcrumbs: None,
+            editor_highlight_occurrences: None,
             mode_badges: BTreeMap::new(),
+            cursor_shapes: BTreeMap::new(),
             lsp_servers: BTreeMap::new(),
             lsp_server_lists: BTreeMap::new(),
             lsp_commands: BTreeMap::new(),
             lsp_only: BTreeMap::new(),
             lsp_except: BTreeMap::new(),
             finder_scopes: BTreeMap::new(),
+            task_commands: BTreeMap::new(),
+            task_matchers: BTreeMap::new(),
             finder_default_scope: None,
+            palette_aliases: BTreeMap::new(),
+            commands: BTreeMap::new(),
         };
         let right = Settings::default();
         assert_eq!(
@@ -1030,25 +1610,44 @@ mod tests {
                 mouse_capture: None,
                 scrolloff: None,
                 editor_line_numbers: None,
+                editor_cursor_line: None,
                 editor_minimap: None,
                 editor_wrap: None,
                 editor_wrap_column: None,
+                editor_wrap_break: None,
+                editor_wrap_indent: None,
                 ui_tab_bar: None,
                 ui_inactive_dim: None,
+                ui_which_key_delay: None,
+                editor_cursor_blink: None,
+                editor_cursor_animation: None,
                 highlight_retention: None,
                 terminal_shell: None,
                 terminal_args: None,
                 direnv_load: None,
                 direnv_reload_on_cd: None,
                 direnv_unset_on_exit: None,
+                spell_enabled: None,
+                spell_dictionary: None,
+                clipboard_mirror: None,
+                state_format: None,
+                test_lenses: None,
+                color_swatches: None,
+                editor_breadcrumbs: None,
+                editor_highlight_occurrences: None,
                 mode_badges: BTreeMap::new(),
+                cursor_shapes: BTreeMap::new(),
                 lsp_servers: BTreeMap::new(),
                 lsp_server_lists: BTreeMap::new(),
                 lsp_commands: BTreeMap::new(),
                 lsp_only: BTreeMap::new(),
                 lsp_except: BTreeMap::new(),
                 finder_scopes: BTreeMap::new(),
+                task_commands: BTreeMap::new(),
+                task_matchers: BTreeMap::new(),
                 finder_default_scope: None,
+                palette_aliases: BTreeMap::new(),
+                commands: BTreeMap::new(),
             }
         );
     }
@@ -1077,25 +1676,44 @@ mod tests {
                 mouse_capture: None,
                 scrolloff: None,
                 editor_line_numbers: None,
+                editor_cursor_line: None,
                 editor_minimap: None,
                 editor_wrap: None,
                 editor_wrap_column: None,
+                editor_wrap_break: None,
+                editor_wrap_indent: None,
                 ui_tab_bar: None,
                 ui_inactive_dim: None,
+                ui_which_key_delay: None,
+                editor_cursor_blink: None,
+                editor_cursor_animation: None,
                 highlight_retention: None,
                 terminal_shell: None,
                 terminal_args: None,
                 direnv_load: None,
                 direnv_reload_on_cd: None,
                 direnv_unset_on_exit: None,
+                spell_enabled: None,
+                spell_dictionary: None,
+                clipboard_mirror: None,
+                state_format: None,
+                test_lenses: None,
+                color_swatches: None,
+                editor_breadcrumbs: None,
+                editor_highlight_occurrences: None,
                 mode_badges: BTreeMap::new(),
+                cursor_shapes: BTreeMap::new(),
                 lsp_servers: BTreeMap::new(),
                 lsp_server_lists: BTreeMap::new(),
                 lsp_commands: BTreeMap::new(),
                 lsp_only: BTreeMap::new(),
                 lsp_except: BTreeMap::new(),
                 finder_scopes: BTreeMap::new(),
+                task_commands: BTreeMap::new(),
+                task_matchers: BTreeMap::new(),
                 finder_default_scope: None,
+                palette_aliases: BTreeMap::new(),
+                commands: BTreeMap::new(),
             }
         );
     }
@@ -1116,25 +1734,44 @@ mod tests {
                 mouse_capture: None,
                 scrolloff: None,
                 editor_line_numbers: None,
+                editor_cursor_line: None,
                 editor_minimap: None,
                 editor_wrap: None,
                 editor_wrap_column: None,
+                editor_wrap_break: None,
+                editor_wrap_indent: None,
                 ui_tab_bar: None,
                 ui_inactive_dim: None,
+                ui_which_key_delay: None,
+                editor_cursor_blink: None,
+                editor_cursor_animation: None,
                 highlight_retention: None,
                 terminal_shell: None,
                 terminal_args: None,
                 direnv_load: None,
                 direnv_reload_on_cd: None,
                 direnv_unset_on_exit: None,
+                spell_enabled: None,
+                spell_dictionary: None,
+                clipboard_mirror: None,
+                state_format: None,
+                test_lenses: None,
+                color_swatches: None,
+                editor_breadcrumbs: None,
+                editor_highlight_occurrences: None,
                 mode_badges: BTreeMap::new(),
+                cursor_shapes: BTreeMap::new(),
                 lsp_servers: BTreeMap::new(),
                 lsp_server_lists: BTreeMap::new(),
                 lsp_commands: BTreeMap::new(),
                 lsp_only: BTreeMap::new(),
                 lsp_except: BTreeMap::new(),
                 finder_scopes: BTreeMap::new(),
+                task_commands: BTreeMap::new(),
+                task_matchers: BTreeMap::new(),
                 finder_default_scope: None,
+                palette_aliases: BTreeMap::new(),
+                commands: BTreeMap::new(),
             }
         );
     }
@@ -1152,25 +1789,44 @@ mod tests {
             mouse_capture: None,
             scrolloff: None,
             editor_line_numbers: None,
+            editor_cursor_line: None,
             editor_minimap: None,
             editor_wrap: None,
             editor_wrap_column: None,
+            editor_wrap_break: None,
+            editor_wrap_indent: None,
             ui_tab_bar: None,
             ui_inactive_dim: None,
+            ui_which_key_delay: None,
+            editor_cursor_blink: None,
+            editor_cursor_animation: None,
             highlight_retention: None,
             terminal_shell: None,
             terminal_args: None,
             direnv_load: None,
             direnv_reload_on_cd: None,
             direnv_unset_on_exit: None,
+            spell_enabled: None,
+            spell_dictionary: None,
+            clipboard_mirror: None,
+            state_format: None,
+            test_lenses: None,
+            color_swatches: None,
+            editor_breadcrumbs: None,
+            editor_highlight_occurrences: None,
             mode_badges: BTreeMap::new(),
+            cursor_shapes: BTreeMap::new(),
             lsp_servers: BTreeMap::new(),
             lsp_server_lists: BTreeMap::new(),
             lsp_commands: BTreeMap::new(),
             lsp_only: BTreeMap::new(),
             lsp_except: BTreeMap::new(),
             finder_scopes: BTreeMap::new(),
+            task_commands: BTreeMap::new(),
+            task_matchers: BTreeMap::new(),
             finder_default_scope: None,
+            palette_aliases: BTreeMap::new(),
+            commands: BTreeMap::new(),
         };
         let right = Settings {
             text_proto_log: None,
@@ -1183,25 +1839,44 @@ mod tests {
             mouse_capture: None,
             scrolloff: None,
             editor_line_numbers: None,
+            editor_cursor_line: None,
             editor_minimap: None,
             editor_wrap: None,
             editor_wrap_column: None,
+            editor_wrap_break: None,
+            editor_wrap_indent: None,
             ui_tab_bar: None,
             ui_inactive_dim: None,
+            ui_which_key_delay: None,
+            editor_cursor_blink: None,
+            editor_cursor_animation: None,
             highlight_retention: None,
             terminal_shell: None,
             terminal_args: None,
             direnv_load: None,
             direnv_reload_on_cd: None,
             direnv_unset_on_exit: None,
+            spell_enabled: None,
+            spell_dictionary: None,
+            clipboard_mirror: None,
+            state_format: None,
+            test_lenses: None,
+            color_swatches: None,
+            editor_breadcrumbs: None,
+            editor_highlight_occurrences: None,
             mode_badges: BTreeMap::new(),
+            cursor_shapes: BTreeMap::new(),
             lsp_servers: BTreeMap::new(),
             lsp_server_lists: BTreeMap::new(),
             lsp_commands: BTreeMap::new(),
             lsp_only: BTreeMap::new(),
             lsp_except: BTreeMap::new(),
             finder_scopes: BTreeMap::new(),
+            task_commands: BTreeMap::new(),
+            task_matchers: BTreeMap::new(),
             finder_default_scope: None,
+            palette_aliases: BTreeMap::new(),
+            commands: BTreeMap::new(),
         };
         assert_eq!(left.merge(right).theme, Some("b".into()));
     }
@@ -1418,4 +2093,24 @@ mod tests {
         assert_eq!(merged.terminal_shell, Some("/bin/zsh".to_string()));
         assert_eq!(merged.terminal_args, Some(vec!["-l".to_string()]));
     }
+
+    #[test]
+    fn from_config_evaluates_expressions_against_earlier_lets() {
+        let config = parse_ok(
+            r#"on init {
+                let margin = 3;
+                let home = "/home/me";
+                editor.scrolloff = $margin * 2 + 1;
+                terminal.shell = home + "/bin/fish";
+                editor.wrap_column = $undefined + 1;
+            }"#,
+        );
+        let settings = Settings::from_config(&config);
+        assert_eq!(settings.scrolloff, Some(7));
+        assert_eq!(
+            settings.terminal_shell,
+            Some("/home/me/bin/fish".to_string())
+        );
+        assert_eq!(settings.editor_wrap_column, None);
+    }
 }
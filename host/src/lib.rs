@@ -17,7 +17,7 @@ pub mod watch;
 pub use env::{EnvHost, LocalEnv};
 pub use fake::{FakeEnv, FakeFs, FakeFsOp, FakeFsWatcher, FakeShell, FakeShellInvocation};
 pub use fs::{FsDirEntry, FsHost, FsMetadata, LocalFs};
-pub use shell::{LocalShell, ShellHost, ShellOutput};
+pub use shell::{LocalShell, ShellHost, ShellOutput, ShellStream};
 pub use watch::{
     FsEventKind, FsWatchEvent, FsWatchHost, LocalFsWatcher, NoopFsWatcher, WatchToken,
 };
@@ -1,7 +1,9 @@
 use std::{
-    io::{self, Write},
+    io::{self, Read, Write},
     path::Path,
     process::{Command, Stdio},
+    sync::mpsc,
+    thread,
 };
 
 /// Output captured from a single shell-host invocation.
@@ -12,6 +14,14 @@ pub struct ShellOutput {
     pub exit_code: i32,
 }
 
+/// Which output pipe a chunk passed to [`ShellHost::run_streaming`] came
+/// from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellStream {
+    Stdout,
+    Stderr,
+}
+
 /// Run a shell command. Production code routes through this trait so
 /// tests can install [`crate::FakeShell`] instead of spawning real
 /// subprocesses.
@@ -32,6 +42,31 @@ pub trait ShellHost: Send + Sync {
         cwd: Option<&Path>,
         env: &[(String, Option<String>)],
     ) -> io::Result<ShellOutput>;
+
+    /// Like [`Self::run`], but hands stdout and stderr to `on_chunk` as
+    /// they are produced instead of collecting them, and returns only
+    /// the exit code.
+    ///
+    /// The default runs the command to completion and then reports all
+    /// of stdout followed by all of stderr, so a host that cannot
+    /// stream still satisfies callers.
+    fn run_streaming(
+        &self,
+        cmd: &str,
+        stdin: &[u8],
+        cwd: Option<&Path>,
+        env: &[(String, Option<String>)],
+        on_chunk: &mut dyn FnMut(ShellStream, &[u8]),
+    ) -> io::Result<i32> {
+        let output = self.run(cmd, stdin, cwd, env)?;
+        if !output.stdout.is_empty() {
+            on_chunk(ShellStream::Stdout, &output.stdout);
+        }
+        if !output.stderr.is_empty() {
+            on_chunk(ShellStream::Stderr, &output.stderr);
+        }
+        Ok(output.exit_code)
+    }
 }
 
 /// Production [`ShellHost`] backed by `std::process::Command` with
@@ -39,14 +74,8 @@ pub trait ShellHost: Send + Sync {
 /// exits.
 pub struct LocalShell;
 
-impl ShellHost for LocalShell {
-    fn run(
-        &self,
-        cmd: &str,
-        stdin: &[u8],
-        cwd: Option<&Path>,
-        env: &[(String, Option<String>)],
-    ) -> io::Result<ShellOutput> {
+impl LocalShell {
+    fn command(cmd: &str, cwd: Option<&Path>, env: &[(String, Option<String>)]) -> Command {
         let mut command = Command::new("sh");
         command
             .arg("-c")
@@ -63,7 +92,19 @@ impl ShellHost for LocalShell {
                 None => command.env_remove(key),
             };
         }
-        let mut child = command.spawn()?;
+        command
+    }
+}
+
+impl ShellHost for LocalShell {
+    fn run(
+        &self,
+        cmd: &str,
+        stdin: &[u8],
+        cwd: Option<&Path>,
+        env: &[(String, Option<String>)],
+    ) -> io::Result<ShellOutput> {
+        let mut child = Self::command(cmd, cwd, env).spawn()?;
         if let Some(mut sin) = child.stdin.take() {
             sin.write_all(stdin)?;
         }
@@ -74,4 +115,73 @@ impl ShellHost for LocalShell {
             exit_code: output.status.code().unwrap_or(-1),
         })
     }
+
+    /// Reads each pipe on its own thread and forwards chunks to the
+    /// calling thread, so a command filling one pipe never stalls on the
+    /// other. Stdin is written on a thread too, for a command that
+    /// produces output before it has read all of its input.
+    fn run_streaming(
+        &self,
+        cmd: &str,
+        stdin: &[u8],
+        cwd: Option<&Path>,
+        env: &[(String, Option<String>)],
+        on_chunk: &mut dyn FnMut(ShellStream, &[u8]),
+    ) -> io::Result<i32> {
+        let mut child = Self::command(cmd, cwd, env).spawn()?;
+        let writer = child.stdin.take().map(|mut sin| {
+            let input = stdin.to_vec();
+            thread::spawn(move || {
+                // A command that exits without reading its input closes the
+                // pipe; that is not a failure of the run.
+                let _ = sin.write_all(&input);
+            })
+        });
+        let (tx, rx) = mpsc::channel();
+        let readers = [
+            child
+                .stdout
+                .take()
+                .map(|pipe| forward(pipe, ShellStream::Stdout, tx.clone())),
+            child
+                .stderr
+                .take()
+                .map(|pipe| forward(pipe, ShellStream::Stderr, tx.clone())),
+        ];
+        drop(tx);
+        for (stream, bytes) in rx {
+            on_chunk(stream, &bytes);
+        }
+        for reader in readers.into_iter().flatten() {
+            let _ = reader.join();
+        }
+        if let Some(writer) = writer {
+            let _ = writer.join();
+        }
+        Ok(child.wait()?.code().unwrap_or(-1))
+    }
+}
+
+/// Send everything read from `pipe` to `tx` as `stream` chunks until the
+/// pipe closes.
+fn forward(
+    mut pipe: impl Read + Send + 'static,
+    stream: ShellStream,
+    tx: mpsc::Sender<(ShellStream, Vec<u8>)>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut buf = [0u8; 8192];
+        loop {
+            match pipe.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    if tx.send((stream, buf[..n].to_vec())).is_err() {
+                        break;
+                    }
+                },
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {},
+                Err(_) => break,
+            }
+        }
+    })
 }
@@ -0,0 +1,85 @@
+//! The chain of outline items enclosing a byte offset, for a breadcrumbs bar.
+//!
+//! [`breadcrumbs_at`] runs each layer's
+//! [`outline_query`](crate::Language::outline_query) and keeps the `@item`
+//! matches that contain the offset, outermost first. A crumb's label is the
+//! item's `@context` and `@name` text in source order, so a Rust method reads
+//! `impl Foo` then `fn bar`.
+
+use crate::syntax_map::SyntaxSnapshot;
+use std::{cmp::Reverse, collections::BTreeMap, ops::Range};
+use stoat_text::Rope;
+
+/// One enclosing outline item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breadcrumb {
+    pub label: String,
+    /// Byte range of the item's `@item` capture.
+    pub range: Range<usize>,
+}
+
+/// The outline items of `snapshot` containing `offset`, ordered from the
+/// outermost to the innermost. Items from injected layers nest under the
+/// host items around them. Empty when no layer has an outline query or the
+/// offset sits outside every item.
+pub fn breadcrumbs_at(snapshot: &SyntaxSnapshot, rope: &Rope, offset: usize) -> Vec<Breadcrumb> {
+    let captures = snapshot.query_captures(offset..offset + 1, rope, |language| {
+        language.outline_query.as_ref()
+    });
+
+    let mut items: BTreeMap<usize, (Option<Range<usize>>, Vec<Range<usize>>)> = BTreeMap::new();
+    for capture in &captures {
+        let entry = items.entry(capture.match_index).or_default();
+        match capture.name {
+            "item" => entry.0 = Some(capture.byte_range.clone()),
+            "context" | "name" => entry.1.push(capture.byte_range.clone()),
+            _ => {},
+        }
+    }
+
+    let mut crumbs: Vec<Breadcrumb> = items
+        .into_values()
+        .filter_map(|(item, mut parts)| {
+            let range = item.filter(|r| r.contains(&offset) && r.end <= rope.len())?;
+            parts.sort_by_key(|r| r.start);
+            let label = parts
+                .iter()
+                .map(|r| rope.chunks_in_range(r.clone()).collect::<String>())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (!label.is_empty()).then_some(Breadcrumb { label, range })
+        })
+        .collect();
+    crumbs.sort_by_key(|c| (c.range.start, Reverse(c.range.end)));
+    crumbs.dedup_by(|a, b| a.range == b.range);
+    crumbs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::breadcrumbs_at;
+    use crate::{LanguageRegistry, SyntaxMap};
+    use std::path::Path;
+    use stoat_text::Rope;
+
+    #[test]
+    fn crumbs_run_from_module_to_function() {
+        let lang = LanguageRegistry::standard()
+            .for_path(Path::new("a.rs"))
+            .unwrap();
+        let source = "mod outer {\n    impl Foo {\n        fn bar() {\n            let x = 1;\n        }\n    }\n}\nfn after() {}\n";
+        let rope = Rope::from(source);
+        let mut map = SyntaxMap::new();
+        map.reparse(&rope, lang, 1).unwrap();
+
+        let offset = source.find("let x").unwrap();
+        let crumbs = breadcrumbs_at(&map.snapshot(), &rope, offset);
+        let labels: Vec<&str> = crumbs.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["mod outer", "impl Foo", "fn bar"]);
+        assert!(source[crumbs[2].range.clone()].starts_with("fn bar"));
+        assert_eq!(crumbs[0].range.start, 0);
+
+        let gap = source.find("\nfn after").unwrap();
+        assert!(breadcrumbs_at(&map.snapshot(), &rope, gap).is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+//! Language detection from file content, for files whose extension does not
+//! name a language.
+//!
+//! [`language_name_for_rope`] reads a `#!` interpreter line, then Vim and Emacs
+//! modelines. Every name it finds resolves through
+//! [`crate::LANGUAGE_EXTENSIONS`], so content detection can only ever yield a
+//! language the extension table knows.
+
+use crate::language::{language_name_for_extension, LANGUAGE_EXTENSIONS};
+use std::path::Path;
+use stoat_text::Rope;
+
+/// Lines at each end of a file searched for a Vim modeline, Vim's own
+/// `modelines` default.
+const MODELINE_LINES: u32 = 5;
+
+/// Interpreters whose name does not itself resolve to their language.
+const INTERPRETERS: &[(&str, &str)] = &[
+    ("cargo", "rust"),
+    ("rust-script", "rust"),
+    ("deno", "typescript"),
+    ("ts-node", "typescript"),
+];
+
+/// The language `path` is written in: its extension when
+/// [`LANGUAGE_EXTENSIONS`] knows it, otherwise whatever
+/// [`language_name_for_rope`] reads from `rope`.
+pub fn detect_language_name(path: &Path, rope: &Rope) -> Option<&'static str> {
+    crate::language::language_name_for_path(path).or_else(|| language_name_for_rope(rope))
+}
+
+/// The language named by `rope`'s shebang line, or failing that by a Vim
+/// modeline in its first or last [`MODELINE_LINES`] lines or an Emacs
+/// `-*- mode -*-` line in its first two. `None` when nothing resolves.
+pub fn language_name_for_rope(rope: &Rope) -> Option<&'static str> {
+    // A trailing newline ends the last line rather than starting another.
+    let max_point = rope.max_point();
+    let last_row = if max_point.column == 0 {
+        max_point.row.saturating_sub(1)
+    } else {
+        max_point.row
+    };
+    let first = rope.line_at_row(0);
+    if let Some(name) = shebang_language(&first) {
+        return Some(name);
+    }
+    let emacs_rows = 0..(last_row + 1).min(2);
+    if let Some(name) = emacs_rows
+        .map(|row| rope.line_at_row(row))
+        .find_map(|line| emacs_modeline_language(&line))
+    {
+        return Some(name);
+    }
+    let head = 0..MODELINE_LINES.min(last_row + 1);
+    let tail = last_row.saturating_sub(MODELINE_LINES - 1).max(head.end)..last_row + 1;
+    head.chain(tail)
+        .map(|row| rope.line_at_row(row))
+        .find_map(|line| vim_modeline_language(&line))
+}
+
+/// Resolve a language `token` as a name or extension in
+/// [`LANGUAGE_EXTENSIONS`], case-insensitively.
+fn language_for_token(token: &str) -> Option<&'static str> {
+    LANGUAGE_EXTENSIONS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(token))
+        .map(|&(name, _)| name)
+        .or_else(|| language_name_for_extension(token))
+}
+
+/// The language of a `#!` line's interpreter. `/usr/bin/env` is looked
+/// through, skipping its flags and `VAR=value` assignments, and a version
+/// suffix is dropped so `python3.12` reads as `python`.
+fn shebang_language(line: &str) -> Option<&'static str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = basename(words.next()?);
+    if program == "env" {
+        program = basename(words.find(|w| !w.starts_with('-') && !w.contains('='))?);
+    }
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    INTERPRETERS
+        .iter()
+        .find(|&&(interpreter, _)| interpreter == program)
+        .map(|&(_, name)| name)
+        .or_else(|| language_for_token(program))
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// The `ft=`, `filetype=`, `syntax=` or `syn=` value of a Vim modeline such
+/// as `# vim: set ft=python:` or `// vi: ts=4 ft=rust`.
+fn vim_modeline_language(line: &str) -> Option<&'static str> {
+    let start = ["vim:", "vi:", "ex:"].iter().find_map(|marker| {
+        line.match_indices(marker)
+            .find(|&(idx, _)| idx == 0 || line[..idx].ends_with(char::is_whitespace))
+            .map(|(idx, _)| idx + marker.len())
+    })?;
+    line[start..]
+        .split(|c: char| c == ':' || c.is_whitespace())
+        .filter_map(|option| option.split_once('='))
+        .find(|(key, _)| matches!(*key, "ft" | "filetype" | "syntax" | "syn"))
+        .and_then(|(_, value)| language_for_token(value))
+}
+
+/// The mode of an Emacs `-*- python -*-` or `-*- mode: python; ... -*-`
+/// line.
+fn emacs_modeline_language(line: &str) -> Option<&'static str> {
+    let (_, rest) = line.split_once("-*-")?;
+    let (vars, _) = rest.split_once("-*-")?;
+    if !vars.contains(':') {
+        return language_for_token(vars.trim());
+    }
+    vars.split(';')
+        .filter_map(|var| var.split_once(':'))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case("mode"))
+        .and_then(|(_, mode)| language_for_token(mode.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_language_name, language_name_for_rope};
+    use std::path::Path;
+    use stoat_text::Rope;
+
+    fn detect(text: &str) -> Option<&'static str> {
+        language_name_for_rope(&Rope::from(text))
+    }
+
+    #[test]
+    fn shebang_names_the_interpreter() {
+        assert_eq!(detect("#!/usr/bin/env python3\nprint()\n"), Some("python"));
+        assert_eq!(detect("#!/usr/bin/python3.12 -u\n"), Some("python"));
+        assert_eq!(
+            detect("#!/usr/bin/env -S cargo +nightly -Zscript\n"),
+            Some("rust")
+        );
+        assert_eq!(
+            detect("#!/usr/bin/env FOO=1 deno run\n"),
+            Some("typescript")
+        );
+        assert_eq!(detect("#!/bin/sh\necho\n"), None);
+    }
+
+    #[test]
+    fn modelines_name_the_language() {
+        assert_eq!(detect("x = 1\n# vim: set ft=python:\n"), Some("python"));
+        assert_eq!(
+            detect("// vi: ts=4 filetype=rust\nfn f() {}\n"),
+            Some("rust")
+        );
+        assert_eq!(
+            detect("# -*- mode: yaml; indent-tabs-mode: nil -*-\n"),
+            Some("yaml")
+        );
+        assert_eq!(detect("#!/bin/sh\n# -*- python -*-\n"), Some("python"));
+        assert_eq!(detect("devim: ft=rust\n"), None);
+        let mut middle = "# vim: ft=go\n".to_string();
+        middle.insert_str(0, &"x\n".repeat(10));
+        middle.push_str(&"x\n".repeat(10));
+        assert_eq!(
+            detect(&middle),
+            None,
+            "modelines are only read near the ends"
+        );
+        let mut fifth_from_last = "x\n".repeat(10);
+        fifth_from_last.push_str("# vim: ft=go\n");
+        fifth_from_last.push_str(&"x\n".repeat(4));
+        assert_eq!(detect(&fifth_from_last), Some("go"));
+    }
+
+    #[test]
+    fn extension_wins_over_content() {
+        let rope = Rope::from("#!/usr/bin/env python3\n");
+        assert_eq!(detect_language_name(Path::new("a.rs"), &rope), Some("rust"));
+        assert_eq!(
+            detect_language_name(Path::new("script"), &rope),
+            Some("python")
+        );
+        assert_eq!(
+            detect_language_name(Path::new("notes.txt"), &Rope::from("hi\n")),
+            None
+        );
+    }
+}
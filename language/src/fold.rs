@@ -0,0 +1,139 @@
+//! Byte ranges a syntax-driven fold hides.
+//!
+//! Function and type bodies come from the language's textobjects query,
+//! under its `function.inside` and `class.inside` captures. Imports have no
+//! capture there, so runs of adjacent top-level import nodes are found by
+//! node kind instead. Only ranges spanning more than one line are returned,
+//! since folding a single line hides nothing.
+
+use crate::{
+    language::Language,
+    query::{capture_ranges_by_match, run_query},
+};
+use std::ops::Range;
+use stoat_text::Rope;
+use tree_sitter::Node;
+
+/// What a syntax fold collapses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxFoldKind {
+    /// Function and method bodies.
+    Functions,
+    /// Struct, enum, trait and impl bodies.
+    Classes,
+    /// Runs of adjacent import statements.
+    Imports,
+}
+
+/// Node kinds that import a module across the bundled grammars.
+const IMPORT_KINDS: &[&str] = &[
+    "use_declaration",
+    "extern_crate_declaration",
+    "import_statement",
+    "import_from_statement",
+    "import_declaration",
+    "preproc_include",
+];
+
+/// Sorted byte ranges of every `kind` fold in the tree under `root`, parsed
+/// from `rope` in `language`. Empty when the language's textobjects query
+/// lacks the capture `kind` needs.
+pub fn syntax_fold_ranges(
+    language: &Language,
+    root: Node<'_>,
+    rope: &Rope,
+    kind: SyntaxFoldKind,
+) -> Vec<Range<usize>> {
+    let mut ranges = match kind {
+        SyntaxFoldKind::Functions => body_ranges(language, root, rope, "function.inside"),
+        SyntaxFoldKind::Classes => body_ranges(language, root, rope, "class.inside"),
+        SyntaxFoldKind::Imports => import_runs(root),
+    };
+    ranges.retain(|r| rope.offset_to_point(r.start).row < rope.offset_to_point(r.end).row);
+    ranges.sort_unstable_by_key(|r| (r.start, r.end));
+    ranges.dedup();
+    ranges
+}
+
+fn body_ranges(
+    language: &Language,
+    root: Node<'_>,
+    rope: &Rope,
+    capture_name: &str,
+) -> Vec<Range<usize>> {
+    let Some(query) = language.textobjects_query.as_ref() else {
+        return Vec::new();
+    };
+    let captures = run_query(query, root, rope, 0..rope.len());
+    capture_ranges_by_match(&captures, capture_name)
+}
+
+/// Ranges of each run of two or more adjacent import nodes among `root`'s
+/// children.
+fn import_runs(root: Node<'_>) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut run: Option<(Range<usize>, usize)> = None;
+    let mut cursor = root.walk();
+    for child in root.named_children(&mut cursor) {
+        if IMPORT_KINDS.contains(&child.kind()) {
+            match &mut run {
+                Some((range, count)) => {
+                    range.end = child.end_byte();
+                    *count += 1;
+                },
+                None => run = Some((child.byte_range(), 1)),
+            }
+        } else if let Some((range, count)) = run.take() {
+            if count > 1 {
+                runs.push(range);
+            }
+        }
+    }
+    if let Some((range, count)) = run {
+        if count > 1 {
+            runs.push(range);
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{syntax_fold_ranges, SyntaxFoldKind};
+    use crate::{language::LanguageRegistry, parse};
+    use stoat_text::Rope;
+
+    const SOURCE: &str = "use a::b;\nuse c::d;\n\nfn one() {\n    1;\n}\n\nfn two() {}\n\nimpl S {\n    fn three() {\n        3;\n    }\n}\n";
+
+    fn folds(kind: SyntaxFoldKind) -> Vec<&'static str> {
+        let registry = LanguageRegistry::standard();
+        let rust = registry
+            .languages()
+            .iter()
+            .find(|l| l.name == "rust")
+            .expect("rust registered");
+        let tree = parse(rust, SOURCE, None).expect("parse");
+        let rope = Rope::from(SOURCE);
+        syntax_fold_ranges(rust, tree.root_node(), &rope, kind)
+            .into_iter()
+            .map(|r| &SOURCE[r])
+            .collect()
+    }
+
+    #[test]
+    fn folds_multi_line_bodies() {
+        assert_eq!(
+            folds(SyntaxFoldKind::Functions),
+            vec!["{\n    1;\n}", "{\n        3;\n    }"]
+        );
+        assert_eq!(
+            folds(SyntaxFoldKind::Classes),
+            vec!["{\n    fn three() {\n        3;\n    }\n}"]
+        );
+    }
+
+    #[test]
+    fn folds_runs_of_imports() {
+        assert_eq!(folds(SyntaxFoldKind::Imports), vec!["use a::b;\nuse c::d;"]);
+    }
+}
@@ -28,6 +28,13 @@ pub struct Language {
     /// capture names match the host node kinds. `None` when there are no
     /// injections configured.
     pub injection_query: Option<Query>,
+    /// Injection query in the standard tree-sitter `injections.scm` form,
+    /// loaded best-effort alongside the node-kind [`Language::injections`].
+    /// Each match's `@injection.content` parses as the language named by its
+    /// `@injection.language` capture or `#set! injection.language` property,
+    /// resolved against [`Language::injection_candidates`]. Names that resolve
+    /// to no registered grammar are skipped. `None` for grammars without one.
+    pub embed_query: Option<Query>,
     /// Bracket-pair query loaded from `brackets.scm`. Captures `@open` and
     /// `@close` for matched bracket-like tokens, driving the editor's
     /// match-brackets motion through [`crate::matching_bracket`]. `None` for
@@ -48,8 +55,8 @@ pub struct Language {
     /// Outline query loaded from `outline.scm`. Captures `@item` (a
     /// definition's full range), `@name` (its identifier), `@context`
     /// (keyword and modifier tokens), and `@annotation` (attributes,
-    /// doc comments) for the symbols a file defines. Loaded but not yet
-    /// wired into a consumer. Reserved for symbol extraction. `None` for
+    /// doc comments) for the symbols a file defines. Read by
+    /// [`crate::breadcrumbs_at`] for the status-bar breadcrumbs. `None` for
     /// languages without an `outline.scm` (e.g. toml).
     pub outline_query: Option<Query>,
     /// Tags query loaded from `tags.scm`. Captures `@reference.call`
@@ -58,18 +65,24 @@ pub struct Language {
     /// wired into a consumer. Reserved for reference extraction.
     /// `None` for languages without a `tags.scm` (only rust ships one).
     pub tags_query: Option<Query>,
+    /// Locals query loaded from `locals.scm`. Captures `@local.scope`,
+    /// `@local.definition`, `@local.binding` and `@local.reference` for
+    /// [`crate::local_definition_at`], the in-file goto-definition used when
+    /// no language server answers. `None` for languages without one (only
+    /// rust ships one).
+    pub locals_query: Option<Query>,
     /// Line-comment marker for languages that have one (e.g. `"//"`
     /// for rust, `"#"` for toml). `None` for languages without line
     /// comments (e.g. JSON, markdown). Used by the `ToggleComments`
     /// action to insert / remove the prefix on each line.
     pub line_comment: Option<&'static str>,
-    /// Languages a fenced code block's info string may resolve to, for a
-    /// grammar carrying an [`InjectionInner::Fence`] injection. Late-bound: the
-    /// registry fills it after every language exists, since a fence host (e.g.
-    /// markdown) may need to resolve to a language that already holds it (e.g.
-    /// rust, which injects markdown into doc comments). Empty for grammars with
-    /// no fence injection.
-    pub fence_candidates: OnceLock<Vec<Arc<Language>>>,
+    /// Languages a fenced code block's info string or an
+    /// [`Language::embed_query`] match may resolve to, for a grammar carrying
+    /// either. Late-bound: the registry fills it after every language exists,
+    /// since a host (e.g. markdown) may need to resolve to a language that
+    /// already holds it (e.g. rust, which injects markdown into doc comments).
+    /// Empty for grammars with neither.
+    pub injection_candidates: OnceLock<Vec<Arc<Language>>>,
 }
 
 impl Language {
@@ -101,7 +114,7 @@ pub enum InjectionInner {
     /// Parse each host node's byte range as this fixed language.
     Fixed(Arc<Language>),
     /// Parse each fenced code block's content as the language its info string
-    /// names, resolved against the host language's `fence_candidates`. The
+    /// names, resolved against the host language's `injection_candidates`. The
     /// injection's `host_node_kind` is ignored, since the query matches fenced
     /// blocks directly.
     Fence,
@@ -142,28 +155,37 @@ impl LanguageRegistry {
                 markdown_inline,
             ],
         };
-        // A fence injection resolves its info-string token against every
-        // registered language. Late-bind the candidate set now that all exist.
-        // Build time is too early, since rust already holds markdown for doc
-        // comments, so markdown cannot hold rust back then.
+        // Fence and embed-query injections resolve their language name against
+        // every registered language. Late-bind the candidate set now that all
+        // exist. Build time is too early, since rust already holds markdown for
+        // doc comments, so markdown cannot hold rust back then.
         for lang in &registry.languages {
-            if lang
-                .injections
-                .iter()
-                .any(|i| matches!(i.inner, InjectionInner::Fence))
+            if lang.embed_query.is_some()
+                || lang
+                    .injections
+                    .iter()
+                    .any(|i| matches!(i.inner, InjectionInner::Fence))
             {
-                let _ = lang.fence_candidates.set(registry.languages.clone());
+                let _ = lang.injection_candidates.set(registry.languages.clone());
             }
         }
         registry
     }
 
+    /// The registered language for `path`, detected from its extension through
+    /// [`LANGUAGE_EXTENSIONS`]. `None` when the extension is unknown or names a
+    /// language with no bundled grammar.
     pub fn for_path(&self, path: &Path) -> Option<Arc<Language>> {
-        let ext = path.extension()?.to_str()?;
-        self.languages
-            .iter()
-            .find(|l| l.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
-            .cloned()
+        let name = language_name_for_path(path)?;
+        self.languages.iter().find(|l| l.name == name).cloned()
+    }
+
+    /// The registered language for a file at `path` holding `rope`, by
+    /// extension and then by shebang or modeline, see
+    /// [`crate::detect_language_name`].
+    pub fn detect(&self, path: &Path, rope: &stoat_text::Rope) -> Option<Arc<Language>> {
+        let name = crate::detect_language_name(path, rope)?;
+        self.languages.iter().find(|l| l.name == name).cloned()
     }
 
     pub fn languages(&self) -> &[Arc<Language>] {
@@ -177,6 +199,47 @@ impl LanguageRegistry {
     }
 }
 
+/// File extensions of every language known by name, matched case-insensitively.
+///
+/// The one table extension-based detection reads. A registered grammar takes
+/// its [`Language::extensions`] from its row. A language with no bundled
+/// grammar (python, go, ...) still detects here, so a buffer of it can be
+/// routed to a language server by name even though it renders unhighlighted.
+pub const LANGUAGE_EXTENSIONS: &[(&str, &[&str])] = &[
+    ("rust", &["rs"]),
+    ("json", &["json"]),
+    ("toml", &["toml"]),
+    ("markdown", &["md", "markdown"]),
+    ("yaml", &["yaml", "yml"]),
+    ("python", &["py", "pyi"]),
+    ("go", &["go"]),
+    ("typescript", &["ts", "mts", "cts"]),
+    ("c", &["c", "h"]),
+    ("stcfg", &["stcfg"]),
+];
+
+/// The language name [`LANGUAGE_EXTENSIONS`] assigns to `extension`.
+pub fn language_name_for_extension(extension: &str) -> Option<&'static str> {
+    LANGUAGE_EXTENSIONS
+        .iter()
+        .find(|(_, exts)| exts.iter().any(|e| e.eq_ignore_ascii_case(extension)))
+        .map(|&(name, _)| name)
+}
+
+/// The language name [`LANGUAGE_EXTENSIONS`] assigns to `path`'s extension.
+pub fn language_name_for_path(path: &Path) -> Option<&'static str> {
+    language_name_for_extension(path.extension()?.to_str()?)
+}
+
+/// Extensions [`LANGUAGE_EXTENSIONS`] lists for `name`, empty when it has no
+/// row (e.g. markdown-inline, which only runs injected).
+fn extensions_for(name: &str) -> &'static [&'static str] {
+    LANGUAGE_EXTENSIONS
+        .iter()
+        .find(|&&(n, _)| n == name)
+        .map_or(&[], |&(_, exts)| exts)
+}
+
 /// Resolve a fenced code block's info-string `token` to a language in
 /// `languages`, matching its name or one extension case-insensitively.
 ///
@@ -212,22 +275,22 @@ struct AuxQuerySources {
     textobjects: Option<&'static str>,
     outline: Option<&'static str>,
     tags: Option<&'static str>,
+    locals: Option<&'static str>,
+    injections: Option<&'static str>,
     line_comment: Option<&'static str>,
 }
 
 fn make_language(
     name: &'static str,
-    extensions: &'static [&'static str],
     grammar: TsLanguage,
     highlight_src: &str,
     aux: AuxQuerySources,
 ) -> Language {
-    make_language_with_injections(name, extensions, grammar, highlight_src, Vec::new(), aux)
+    make_language_with_injections(name, grammar, highlight_src, Vec::new(), aux)
 }
 
 fn make_language_with_injections(
     name: &'static str,
-    extensions: &'static [&'static str],
     grammar: TsLanguage,
     highlight_src: &str,
     injections: Vec<LanguageInjection>,
@@ -239,11 +302,13 @@ fn make_language_with_injections(
         textobjects,
         outline,
         tags,
+        locals,
+        injections: embed,
         line_comment,
     } = aux;
 
     // Each Query::new is tens of milliseconds against the rust grammar, and the
-    // seven are independent, so compile them concurrently to shrink the
+    // nine are independent, so compile them concurrently to shrink the
     // pre-first-frame path. The grammar and injections are shared immutably and
     // the scoped threads join before either is moved into the returned Language.
     let (
@@ -254,6 +319,8 @@ fn make_language_with_injections(
         textobjects_query,
         outline_query,
         tags_query,
+        locals_query,
+        embed_query,
     ) = std::thread::scope(|s| {
         let injection_handle = s.spawn(|| build_injection_query(name, &grammar, &injections));
         let bracket_handle =
@@ -267,6 +334,10 @@ fn make_language_with_injections(
             s.spawn(|| outline.and_then(|src| try_compile_query(name, "outline", &grammar, src)));
         let tags_handle =
             s.spawn(|| tags.and_then(|src| try_compile_query(name, "tags", &grammar, src)));
+        let locals_handle =
+            s.spawn(|| locals.and_then(|src| try_compile_query(name, "locals", &grammar, src)));
+        let embed_handle =
+            s.spawn(|| embed.and_then(|src| try_compile_query(name, "injections", &grammar, src)));
 
         let highlight_query = Query::new(&grammar, highlight_src)
             .unwrap_or_else(|e| panic!("highlight query for {name} failed to compile: {e}"));
@@ -287,24 +358,30 @@ fn make_language_with_injections(
                 .join()
                 .expect("outline query thread panicked"),
             tags_handle.join().expect("tags query thread panicked"),
+            locals_handle.join().expect("locals query thread panicked"),
+            embed_handle
+                .join()
+                .expect("injections query thread panicked"),
         )
     });
 
     Language {
         name,
-        extensions,
+        extensions: extensions_for(name),
         grammar,
         highlight_query,
         highlight_map: Mutex::new(HighlightMap::default()),
         injections,
         injection_query,
+        embed_query,
         bracket_query,
         indent_query,
         textobjects_query,
         outline_query,
         tags_query,
+        locals_query,
         line_comment,
-        fence_candidates: OnceLock::new(),
+        injection_candidates: OnceLock::new(),
     }
 }
 
@@ -357,7 +434,6 @@ fn build_injection_query(
 fn make_rust(markdown: Arc<Language>) -> Language {
     make_language_with_injections(
         "rust",
-        &["rs"],
         grammar::rust(),
         include_str!("../../vendor/zed/crates/languages/src/rust/highlights.scm"),
         // Doc comments host a combined markdown injection, so `/// **bold**`
@@ -381,6 +457,8 @@ fn make_rust(markdown: Arc<Language>) -> Language {
                 "../../vendor/zed/crates/languages/src/rust/outline.scm"
             )),
             tags: Some(include_str!("queries/rust/tags.scm")),
+            locals: Some(include_str!("queries/rust/locals.scm")),
+            injections: Some(include_str!("queries/rust/injections.scm")),
             line_comment: Some("//"),
         },
     )
@@ -389,7 +467,6 @@ fn make_rust(markdown: Arc<Language>) -> Language {
 fn make_json() -> Language {
     make_language(
         "json",
-        &["json"],
         grammar::json(),
         include_str!("../../vendor/zed/crates/languages/src/json/highlights.scm"),
         AuxQuerySources {
@@ -404,6 +481,8 @@ fn make_json() -> Language {
                 "../../vendor/zed/crates/languages/src/json/outline.scm"
             )),
             tags: None,
+            locals: None,
+            injections: None,
             line_comment: None,
         },
     )
@@ -412,7 +491,6 @@ fn make_json() -> Language {
 fn make_toml() -> Language {
     make_language(
         "toml",
-        &["toml"],
         grammar::toml(),
         include_str!("../../vendor/helix/runtime/queries/toml/highlights.scm"),
         AuxQuerySources {
@@ -433,7 +511,6 @@ fn make_markdown_with_injections(mut injections: Vec<LanguageInjection>) -> Lang
     });
     make_language_with_injections(
         "markdown",
-        &["md", "markdown"],
         grammar::markdown(),
         include_str!("../../vendor/zed/crates/languages/src/markdown/highlights.scm"),
         injections,
@@ -449,6 +526,8 @@ fn make_markdown_with_injections(mut injections: Vec<LanguageInjection>) -> Lang
                 "../../vendor/zed/crates/languages/src/markdown/outline.scm"
             )),
             tags: None,
+            locals: None,
+            injections: Some(include_str!("queries/markdown/injections.scm")),
             line_comment: None,
         },
     )
@@ -460,7 +539,6 @@ fn make_markdown_inline() -> Language {
     // reachable by name for injection lookup.
     make_language(
         "markdown-inline",
-        &[],
         grammar::markdown_inline(),
         include_str!("../../vendor/zed/crates/languages/src/markdown-inline/highlights.scm"),
         AuxQuerySources::default(),
@@ -469,7 +547,7 @@ fn make_markdown_inline() -> Language {
 
 #[cfg(test)]
 mod tests {
-    use super::LanguageRegistry;
+    use super::{extensions_for, language_name_for_path, LanguageRegistry};
     use std::path::Path;
 
     #[test]
@@ -488,6 +566,21 @@ mod tests {
         assert!(reg.for_path(Path::new("noext")).is_none());
     }
 
+    #[test]
+    fn extension_table_names_languages_without_grammars() {
+        let reg = LanguageRegistry::standard();
+        assert_eq!(language_name_for_path(Path::new("a.py")), Some("python"));
+        assert_eq!(language_name_for_path(Path::new("a.YML")), Some("yaml"));
+        assert_eq!(language_name_for_path(Path::new("a.txt")), None);
+        assert!(
+            reg.for_path(Path::new("a.go")).is_none(),
+            "a detected language without a grammar has no registry entry"
+        );
+        for lang in reg.languages() {
+            assert_eq!(lang.extensions, extensions_for(lang.name));
+        }
+    }
+
     #[test]
     fn language_for_fence_token_matches_name_or_extension() {
         let reg = LanguageRegistry::standard();
@@ -1,15 +1,23 @@
 pub mod brackets;
+pub mod breadcrumbs;
+pub mod detect;
+pub mod fold;
 pub mod grammar;
 pub mod highlight;
 pub mod highlight_map;
 pub mod indent;
 pub mod language;
+pub mod locals;
+pub mod query;
 pub mod structural_diff;
 pub mod symbols;
 pub mod syntax_map;
 pub mod textobject;
 
 pub use brackets::matching_bracket;
+pub use breadcrumbs::{breadcrumbs_at, Breadcrumb};
+pub use detect::{detect_language_name, language_name_for_rope};
+pub use fold::{syntax_fold_ranges, SyntaxFoldKind};
 pub use highlight::{
     drop_syntax_in_background, edit_tree, extract_highlights, extract_highlights_rope,
     extract_highlights_rope_with_cache, parse, parse_rope, parse_rope_range, parse_rope_within,
@@ -17,8 +25,13 @@ pub use highlight::{
 };
 pub use highlight_map::{HighlightId, HighlightMap};
 pub use indent::{line_leading_whitespace, newline_indent, suggested_indent};
-pub use language::{language_for_fence_token, Language, LanguageRegistry};
+pub use language::{
+    language_for_fence_token, language_name_for_extension, language_name_for_path, Language,
+    LanguageRegistry, LANGUAGE_EXTENSIONS,
+};
+pub use locals::{local_definition_at, local_references_at};
+pub use query::{capture_ranges_by_match, compile_query, run_query, QueryCapture};
 pub use symbols::{extract_references, extract_symbols, RefKind, RefSite, SymbolDef, SymbolKind};
 pub use syntax_map::{LayerKey, SyntaxLayer, SyntaxMap, SyntaxSnapshot};
 pub use textobject::{collect_capture_starts, find_smallest_capture_at};
-pub use tree_sitter::{Node, Tree};
+pub use tree_sitter::{Node, Query, QueryError, Tree};
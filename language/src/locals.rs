@@ -0,0 +1,247 @@
+//! Scope-aware definition lookup within one file from a `locals.scm` query.
+//!
+//! The query captures `@local.scope` for each scope (the captures of one
+//! match union into a single range, so a function can scope its parameters and
+//! body without its name), `@local.definition` for each name a scope binds,
+//! `@local.binding` around a definition that is only visible after it ends,
+//! and `@local.reference` for each name use. [`local_definition_at`] resolves
+//! the name under a byte offset to the binding it refers to, for a
+//! goto-definition that needs no language server, and [`local_references_at`]
+//! finds every use of that binding.
+
+use crate::query::{capture_ranges_by_match, run_query};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+};
+use stoat_text::Rope;
+use tree_sitter::{Node, Query};
+
+/// The definition the name at `offset` refers to, or `None` when no
+/// definition in scope binds it.
+///
+/// A definition's scope is the innermost `@local.scope` around it, and only
+/// names inside that scope see it. Among those, the one in the innermost scope
+/// wins. Within a scope the nearest definition already visible at the name
+/// wins, so a later `let` shadows an earlier one, and failing that the first,
+/// for items used before they are declared. A name that is itself a
+/// definition resolves to itself.
+pub fn local_definition_at(
+    query: &Query,
+    root: Node<'_>,
+    rope: &Rope,
+    offset: usize,
+) -> Option<Range<usize>> {
+    let locals = Locals::new(query, root, rope);
+    let (name, is_definition) = locals.name_at(offset)?;
+    locals.resolve(&name, is_definition, rope)
+}
+
+/// Every name that resolves, as [`local_definition_at`] does, to the same
+/// definition as the name at `offset`, the definition included, in source
+/// order. Empty when the name at `offset` does not resolve.
+pub fn local_references_at(
+    query: &Query,
+    root: Node<'_>,
+    rope: &Rope,
+    offset: usize,
+) -> Vec<Range<usize>> {
+    let locals = Locals::new(query, root, rope);
+    let Some((name, is_definition)) = locals.name_at(offset) else {
+        return Vec::new();
+    };
+    let Some(target) = locals.resolve(&name, is_definition, rope) else {
+        return Vec::new();
+    };
+    let text = text_of(rope, &name);
+    let mut out: Vec<Range<usize>> = locals
+        .names
+        .iter()
+        .filter(|(range, _)| range.len() == name.len() && text_of(rope, range) == text)
+        .filter(|(range, is_definition)| {
+            locals.resolve(range, *is_definition, rope).as_ref() == Some(&target)
+        })
+        .map(|(range, _)| range.clone())
+        .collect();
+    out.dedup();
+    out
+}
+
+/// One run of a locals query over a tree.
+struct Locals {
+    scopes: Vec<Range<usize>>,
+    /// Each definition with the offset it becomes visible from: the end of its
+    /// `@local.binding` when it has one, else its own start. Two patterns may
+    /// capture one name, so the later visibility wins.
+    definitions: BTreeMap<(usize, usize), usize>,
+    /// Every captured name in source order, flagged when it is a definition.
+    /// A name captured as both keeps the definition flag.
+    names: Vec<(Range<usize>, bool)>,
+}
+
+impl Locals {
+    fn new(query: &Query, root: Node<'_>, rope: &Rope) -> Self {
+        let captures = run_query(query, root, rope, 0..rope.len());
+        let scopes = capture_ranges_by_match(&captures, "local.scope");
+
+        let bindings: HashMap<usize, usize> = captures
+            .iter()
+            .filter(|c| c.name == "local.binding")
+            .map(|c| (c.match_index, c.byte_range.end))
+            .collect();
+        let mut definitions: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+        for def in captures.iter().filter(|c| c.name == "local.definition") {
+            let visible = bindings
+                .get(&def.match_index)
+                .copied()
+                .unwrap_or(def.byte_range.start);
+            let entry = definitions
+                .entry((def.byte_range.start, def.byte_range.end))
+                .or_insert(visible);
+            *entry = (*entry).max(visible);
+        }
+
+        let mut names: BTreeMap<(usize, usize), bool> = BTreeMap::new();
+        for capture in captures
+            .iter()
+            .filter(|c| c.name == "local.definition" || c.name == "local.reference")
+        {
+            *names
+                .entry((capture.byte_range.start, capture.byte_range.end))
+                .or_default() |= capture.name == "local.definition";
+        }
+
+        Self {
+            scopes,
+            definitions,
+            names: names
+                .into_iter()
+                .map(|((start, end), is_definition)| (start..end, is_definition))
+                .collect(),
+        }
+    }
+
+    /// The smallest captured name containing `offset`.
+    fn name_at(&self, offset: usize) -> Option<(Range<usize>, bool)> {
+        self.names
+            .iter()
+            .filter(|(range, _)| range.contains(&offset))
+            .min_by_key(|(range, _)| range.len())
+            .cloned()
+    }
+
+    /// The definition `name` refers to, ranked by innermost scope, then by
+    /// whether the definition is already visible at the name, then by the
+    /// nearest such one.
+    fn resolve(
+        &self,
+        name: &Range<usize>,
+        is_definition: bool,
+        rope: &Rope,
+    ) -> Option<Range<usize>> {
+        if is_definition {
+            return Some(name.clone());
+        }
+        let text = text_of(rope, name);
+        self.definitions
+            .iter()
+            .map(|(&(start, end), &visible)| (start..end, visible))
+            .filter(|(range, _)| range.len() == name.len() && text_of(rope, range) == text)
+            .filter_map(|(range, visible)| {
+                let scope = self
+                    .scopes
+                    .iter()
+                    .filter(|s| contains(s, &range))
+                    .min_by_key(|s| s.len())?;
+                contains(scope, name).then_some((range, visible, scope.len()))
+            })
+            .min_by_key(|(range, visible, scope_len)| {
+                let before = *visible <= name.start;
+                let nearness = if before {
+                    name.start - visible
+                } else {
+                    range.start
+                };
+                (*scope_len, !before, nearness)
+            })
+            .map(|(range, _, _)| range)
+    }
+}
+
+fn contains(outer: &Range<usize>, inner: &Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+fn text_of(rope: &Rope, range: &Range<usize>) -> String {
+    rope.chunks_in_range(range.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{local_definition_at, local_references_at};
+    use crate::LanguageRegistry;
+    use std::path::Path;
+    use stoat_text::Rope;
+
+    fn resolve(source: &str, at: &str) -> Option<usize> {
+        let lang = LanguageRegistry::standard()
+            .for_path(Path::new("a.rs"))
+            .unwrap();
+        let rope = Rope::from(source);
+        let tree = crate::parse(&lang, source, None).unwrap();
+        let query = lang.locals_query.as_ref().expect("rust ships locals.scm");
+        local_definition_at(query, tree.root_node(), &rope, source.find(at).unwrap())
+            .map(|r| r.start)
+    }
+
+    #[test]
+    fn resolves_bindings_by_scope() {
+        let source = "fn f(x: u32) -> u32 {\n    let y = x;\n    let y = y + 1;\n    {\n        let y = 0;\n    }\n    helper(y)\n}\nfn helper(v: u32) -> u32 { v }\n";
+        assert_eq!(resolve(source, "x;"), Some(5), "a parameter");
+        assert_eq!(
+            resolve(source, "y + 1"),
+            source.find("y = x"),
+            "the earlier let, before the shadowing one"
+        );
+        assert_eq!(
+            resolve(source, "y)"),
+            source.find("y = y"),
+            "the latest let, not one in a nested block"
+        );
+        assert_eq!(
+            resolve(source, "helper(y"),
+            source.find("helper(v"),
+            "an item declared after its use"
+        );
+        assert_eq!(resolve(source, "v }"), source.find("v: u32"));
+        assert_eq!(resolve(source, "u32"), None, "no local binds u32");
+    }
+
+    #[test]
+    fn references_share_one_definition() {
+        let source = "fn f() {\n    let y = 1;\n    g(y);\n    let y = y;\n    g(y);\n}\n";
+        let lang = LanguageRegistry::standard()
+            .for_path(Path::new("a.rs"))
+            .unwrap();
+        let rope = Rope::from(source);
+        let tree = crate::parse(&lang, source, None).unwrap();
+        let query = lang.locals_query.as_ref().unwrap();
+        let starts = |offset: usize| -> Vec<usize> {
+            local_references_at(query, tree.root_node(), &rope, offset)
+                .into_iter()
+                .map(|r| r.start)
+                .collect()
+        };
+
+        let first = source.find("y = 1").unwrap();
+        let second = source.find("y = y").unwrap();
+        let first_use = source.find("y);").unwrap();
+        let last_use = source.rfind("y);").unwrap();
+        assert_eq!(starts(first), vec![first, first_use, second + 4]);
+        assert_eq!(starts(last_use), vec![second, last_use]);
+        assert!(
+            starts(source.find("g(").unwrap()).is_empty(),
+            "g is unbound"
+        );
+    }
+}
@@ -0,0 +1,10 @@
+; Fenced code blocks and inline content are configured as node-kind
+; injections in language.rs. These cover the remaining embedded documents.
+((html_block) @injection.content
+  (#set! injection.language "html"))
+
+((minus_metadata) @injection.content
+  (#set! injection.language "yaml"))
+
+((plus_metadata) @injection.content
+  (#set! injection.language "toml"))
@@ -0,0 +1,45 @@
+; SQL in the string literals of sql!-style query macros.
+(macro_invocation
+  macro: [
+    (identifier) @_macro_name
+    (scoped_identifier
+      name: (identifier) @_macro_name)
+  ]
+  (token_tree
+    [
+      (string_literal
+        (string_content) @injection.content)
+      (raw_string_literal
+        (string_content) @injection.content)
+    ])
+  (#any-of? @_macro_name "sql" "query" "query_as" "query_scalar")
+  (#set! injection.language "sql"))
+
+; Patterns passed to regex!-style macros and Regex::new.
+(macro_invocation
+  macro: [
+    (identifier) @_macro_name
+    (scoped_identifier
+      name: (identifier) @_macro_name)
+  ]
+  (token_tree
+    [
+      (string_literal
+        (string_content) @injection.content)
+      (raw_string_literal
+        (string_content) @injection.content)
+    ])
+  (#any-of? @_macro_name "regex" "bytes_regex")
+  (#set! injection.language "regex"))
+
+(call_expression
+  function: (scoped_identifier) @_fn_path
+  arguments: (arguments
+    [
+      (string_literal
+        (string_content) @injection.content)
+      (raw_string_literal
+        (string_content) @injection.content)
+    ])
+  (#match? @_fn_path ".*Regex(Builder)?::new")
+  (#set! injection.language "regex"))
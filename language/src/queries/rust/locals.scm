@@ -0,0 +1,49 @@
+; Scopes. A function's scope is its parameters and body, captured as two
+; nodes of one match so the function's own name stays in the enclosing scope.
+
+(source_file) @local.scope
+(declaration_list) @local.scope
+(block) @local.scope
+(closure_expression) @local.scope
+(match_arm) @local.scope
+(for_expression) @local.scope
+(if_expression) @local.scope
+(while_expression) @local.scope
+
+(function_item
+  parameters: (_) @local.scope
+  body: (_) @local.scope)
+
+; Items.
+
+(function_item name: (identifier) @local.definition)
+(function_signature_item name: (identifier) @local.definition)
+(const_item name: (identifier) @local.definition)
+(static_item name: (identifier) @local.definition)
+(mod_item name: (identifier) @local.definition)
+(macro_definition name: (identifier) @local.definition)
+(struct_item name: (type_identifier) @local.definition)
+(enum_item name: (type_identifier) @local.definition)
+(union_item name: (type_identifier) @local.definition)
+(trait_item name: (type_identifier) @local.definition)
+(type_item name: (type_identifier) @local.definition)
+
+; Bindings. A `let` binding is only visible after its statement, captured as
+; @local.binding, so `let x = x + 1;` reads the earlier `x`.
+
+(let_declaration pattern: (identifier) @local.definition) @local.binding
+(let_declaration pattern: (_ (identifier) @local.definition)) @local.binding
+(parameter pattern: (identifier) @local.definition)
+(closure_parameters (identifier) @local.definition)
+(let_condition pattern: (identifier) @local.definition)
+(for_expression pattern: (identifier) @local.definition)
+(match_pattern (identifier) @local.definition)
+(mut_pattern (identifier) @local.definition)
+(ref_pattern (identifier) @local.definition)
+(tuple_pattern (identifier) @local.definition)
+(tuple_struct_pattern (identifier) @local.definition)
+
+; References.
+
+(identifier) @local.reference
+(type_identifier) @local.reference
@@ -0,0 +1,120 @@
+//! Running tree-sitter queries over a parsed buffer.
+//!
+//! [`compile_query`] turns `.scm` source into a [`Query`] for a language and
+//! [`run_query`] walks it over a tree, yielding each capture as its name and
+//! byte range. Features built on query captures (textobjects, folding,
+//! outline) read these instead of driving a query cursor themselves, so the
+//! cursor pooling and rope-backed predicate text stay in one place.
+
+use crate::{
+    highlight::{QueryCursorHandle, RopeTextProvider},
+    Language,
+};
+use std::ops::Range;
+use stoat_text::Rope;
+use tree_sitter::{Node, Query, QueryError, StreamingIterator};
+
+/// One capture yielded by [`run_query`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryCapture<'q> {
+    /// The capture's name in the query, without the leading `@`.
+    pub name: &'q str,
+    pub byte_range: Range<usize>,
+    /// Index of the query pattern the match came from.
+    pub pattern_index: usize,
+    /// Sequence number of the match within this run. Captures of one match
+    /// share it, so a caller can group e.g. a function's `@name` with its
+    /// `@item`.
+    pub match_index: usize,
+}
+
+/// Compile `source`, a query in tree-sitter's `.scm` syntax, against
+/// `language`'s grammar.
+pub fn compile_query(language: &Language, source: &str) -> Result<Query, QueryError> {
+    Query::new(&language.grammar, source)
+}
+
+/// Every capture of `query` over the tree under `root`, restricted to matches
+/// intersecting `byte_range`, in match order. `rope` supplies node text for
+/// predicates such as `#eq?` and `#match?`. Captures whose name starts with
+/// `_` are predicate helpers and are left out.
+pub fn run_query<'q>(
+    query: &'q Query,
+    root: Node<'_>,
+    rope: &Rope,
+    byte_range: Range<usize>,
+) -> Vec<QueryCapture<'q>> {
+    let names = query.capture_names();
+    let provider = RopeTextProvider { rope };
+    let mut cursor = QueryCursorHandle::new();
+    cursor.set_byte_range(byte_range);
+    let mut matches = cursor.matches(query, root, provider);
+    let mut out = Vec::new();
+    let mut match_index = 0;
+    while let Some(m) = matches.next() {
+        for capture in m.captures {
+            let name = names[capture.index as usize];
+            if name.starts_with('_') {
+                continue;
+            }
+            out.push(QueryCapture {
+                name,
+                byte_range: capture.node.byte_range(),
+                pattern_index: m.pattern_index,
+                match_index,
+            });
+        }
+        match_index += 1;
+    }
+    out
+}
+
+/// The union of each match's captures named `name`, one range per match that
+/// has any, in match order.
+pub fn capture_ranges_by_match(captures: &[QueryCapture<'_>], name: &str) -> Vec<Range<usize>> {
+    let mut out: Vec<(usize, Range<usize>)> = Vec::new();
+    for capture in captures.iter().filter(|c| c.name == name) {
+        let r = capture.byte_range.clone();
+        match out.last_mut() {
+            Some((index, union)) if *index == capture.match_index => {
+                *union = union.start.min(r.start)..union.end.max(r.end);
+            },
+            _ => out.push((capture.match_index, r)),
+        }
+    }
+    out.into_iter().map(|(_, r)| r).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capture_ranges_by_match, compile_query, run_query};
+    use crate::LanguageRegistry;
+    use std::path::Path;
+    use stoat_text::Rope;
+
+    #[test]
+    fn runs_an_arbitrary_query_with_ranges() {
+        let lang = LanguageRegistry::standard()
+            .for_path(Path::new("a.rs"))
+            .unwrap();
+        let source = "fn alpha() {}\nfn beta() {}\n";
+        let rope = Rope::from(source);
+        let tree = crate::parse(&lang, source, None).unwrap();
+        let query = compile_query(
+            &lang,
+            "(function_item name: (identifier) @name (#not-eq? @name \"beta\")) @item",
+        )
+        .unwrap();
+
+        let captures = run_query(&query, tree.root_node(), &rope, 0..rope.len());
+        let mut names: Vec<(&str, &str)> = captures
+            .iter()
+            .map(|c| (c.name, &source[c.byte_range.clone()]))
+            .collect();
+        names.sort();
+        assert_eq!(names, vec![("item", "fn alpha() {}"), ("name", "alpha")]);
+        assert_eq!(capture_ranges_by_match(&captures, "name"), vec![3..8]);
+
+        assert!(compile_query(&lang, "(no_such_node) @x").is_err());
+    }
+}
@@ -14,6 +14,7 @@
 use super::{ChangeKind, DiffChange, DiffResult, Side};
 use imara_diff::{intern::InternedInput, sources, Algorithm, Sink};
 use std::ops::Range;
+use stoat_text::patch::Edit;
 
 /// Compute a line-level diff between `lhs` and `rhs`. The returned
 /// changes carry rope byte ranges (relative to each input) so they can
@@ -44,6 +45,71 @@ pub fn diff_lines(lhs: &str, rhs: &str) -> DiffResult {
     }
 }
 
+/// The byte edits turning `old` into `new`, in ascending order.
+///
+/// Lines are matched with the same histogram pass as [`diff_lines`], and each
+/// changed run is then narrowed past the bytes its two sides share at either
+/// end, so retyping one word in a line yields an edit over that word only.
+/// Each `old` range indexes `old` and each `new` range indexes `new`, so
+/// applying them from last to first rebuilds `new` and leaves the text
+/// outside them, and whatever is anchored there, untouched.
+pub fn text_edits(old: &str, new: &str) -> Vec<Edit<usize>> {
+    let old_starts = line_starts(old);
+    let new_starts = line_starts(new);
+    let input = InternedInput::new(
+        sources::lines_with_terminator(old),
+        sources::lines_with_terminator(new),
+    );
+    let mut edits = Vec::new();
+    imara_diff::diff(
+        Algorithm::Histogram,
+        &input,
+        |before: Range<u32>, after: Range<u32>| {
+            let old_range = old_starts[before.start as usize]..old_starts[before.end as usize];
+            let new_range = new_starts[after.start as usize]..new_starts[after.end as usize];
+            edits.push(narrow(old, new, old_range, new_range));
+        },
+    );
+    edits
+}
+
+/// Byte offset of each line start in `text`, as split by
+/// [`sources::lines_with_terminator`], followed by `text.len()`.
+fn line_starts(text: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        offset += line.len();
+        starts.push(offset);
+    }
+    starts
+}
+
+/// Shrink a changed run to the bytes between its sides' common prefix and
+/// suffix, keeping both ends on character boundaries.
+fn narrow(old: &str, new: &str, old_range: Range<usize>, new_range: Range<usize>) -> Edit<usize> {
+    let (a, b) = (&old[old_range.clone()], &new[new_range.clone()]);
+    let mut prefix = a.bytes().zip(b.bytes()).take_while(|(x, y)| x == y).count();
+    while !a.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+    let max_suffix = a.len().min(b.len()) - prefix;
+    let mut suffix = a
+        .bytes()
+        .rev()
+        .zip(b.bytes().rev())
+        .take(max_suffix)
+        .take_while(|(x, y)| x == y)
+        .count();
+    while !a.is_char_boundary(a.len() - suffix) || !b.is_char_boundary(b.len() - suffix) {
+        suffix -= 1;
+    }
+    Edit {
+        old: old_range.start + prefix..old_range.end - suffix,
+        new: new_range.start + prefix..new_range.end - suffix,
+    }
+}
+
 /// The `(start, end)` byte offsets of one line in the source string.
 /// `end` excludes the trailing newline so adjacent lines don't share a
 /// boundary byte. imara-diff owns line comparison, so only the offsets
@@ -158,6 +224,47 @@ mod tests {
         diff_lines(lhs, rhs).changes
     }
 
+    fn edited(old: &str, new: &str) -> Vec<(String, String)> {
+        text_edits(old, new)
+            .into_iter()
+            .map(|e| (old[e.old].to_string(), new[e.new].to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn text_edits_narrow_to_the_changed_bytes() {
+        assert!(text_edits("a\nb\n", "a\nb\n").is_empty());
+        assert_eq!(
+            edited(
+                "let x = 1;\nkeep\nlet y = 2;\n",
+                "let x = 10;\nkeep\nlet z = 2;\n"
+            ),
+            vec![
+                (String::new(), "0".to_string()),
+                ("y".to_string(), "z".to_string()),
+            ]
+        );
+        assert_eq!(
+            edited("alpha\nbeta\n", "alpha\n"),
+            vec![("beta\n".to_string(), String::new())]
+        );
+        assert_eq!(
+            edited("é\n", "è\n"),
+            vec![("é".to_string(), "è".to_string())]
+        );
+    }
+
+    #[test]
+    fn text_edits_rebuild_the_new_text() {
+        let old = "fn a() {\n    1\n}\n\nfn b() {}\n";
+        let new = "fn a() {\n    2\n}\nfn c() {}\n\nfn b() { x }";
+        let mut text = old.to_string();
+        for edit in text_edits(old, new).into_iter().rev() {
+            text.replace_range(edit.old, &new[edit.new]);
+        }
+        assert_eq!(text, new);
+    }
+
     #[test]
     fn identical_inputs_produce_no_changes() {
         let lhs = "alpha\nbeta\ngamma\n";
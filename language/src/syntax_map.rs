@@ -32,7 +32,9 @@ use crate::{
     edit_tree,
     highlight::{QueryCursorHandle, RopeTextProvider},
     language::InjectionInner,
-    language_for_fence_token, parse_rope, parse_rope_range, Language,
+    language_for_fence_token, parse_rope, parse_rope_range,
+    query::{run_query, QueryCapture},
+    Language,
 };
 use std::{
     cmp::Reverse,
@@ -41,7 +43,7 @@ use std::{
     sync::Arc,
 };
 use stoat_text::{patch::Edit as PatchEdit, ContextLessSummary, Item, Rope, SumTree};
-use tree_sitter::{Node, Query, StreamingIterator, Tree};
+use tree_sitter::{Node, Query, QueryMatch, StreamingIterator, Tree};
 
 /// One parsed tree at a particular nesting depth, anchored to a
 /// `[start_offset, end_offset)` byte range in the host buffer.
@@ -99,9 +101,10 @@ impl Item for SyntaxLayer {
 }
 
 /// Immutable snapshot of every [`SyntaxLayer`] for one buffer version.
-/// Cheap to clone (the inner [`SumTree`] is `Arc`-backed). Held by
-/// [`SyntaxMap`]; threaded through the parse / highlight pipeline once
-/// the migration lands.
+/// Cheap to clone (the inner [`SumTree`] is `Arc`-backed), so a host can
+/// keep reading one parse while the [`SyntaxMap`] that produced it is
+/// interpolated and reparsed elsewhere. `parsed_version` names the buffer
+/// version it describes.
 #[derive(Clone, Default)]
 pub struct SyntaxSnapshot {
     pub layers: SumTree<SyntaxLayer>,
@@ -124,6 +127,61 @@ impl SyntaxSnapshot {
         self.layers.iter()
     }
 
+    /// The deepest layer whose range covers `offset`, the layer whose
+    /// grammar the text at `offset` is written in. Among equally deep
+    /// layers the first in layer order wins.
+    pub fn deepest_layer_at(&self, offset: usize) -> Option<&SyntaxLayer> {
+        self.layers.iter().fold(None::<&SyntaxLayer>, |acc, layer| {
+            let start = layer.start_offset as usize;
+            let end = layer.end_offset as usize;
+            if start <= offset && end >= offset {
+                match acc {
+                    Some(prev) if prev.depth >= layer.depth => acc,
+                    _ => Some(layer),
+                }
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Run `select(layer.language)` over every layer it returns a query for,
+    /// as [`crate::run_query`] does over a single tree, restricted to
+    /// `byte_range`. Layers are walked in layer order and each keeps its own
+    /// match order. Match indices stay unique across layers, so captures of
+    /// one match still group together.
+    pub fn query_captures<'a>(
+        &'a self,
+        byte_range: Range<usize>,
+        rope: &Rope,
+        select: impl Fn(&'a Language) -> Option<&'a Query>,
+    ) -> Vec<QueryCapture<'a>> {
+        let mut all: Vec<QueryCapture<'a>> = Vec::new();
+        // Match indices restart in each layer, so each layer's are offset
+        // past every match the earlier layers produced.
+        let mut next_match = 0;
+        for layer in self.layers.iter() {
+            if (layer.end_offset as usize) <= byte_range.start
+                || (layer.start_offset as usize) >= byte_range.end
+            {
+                continue;
+            }
+            let Some(query) = select(layer.language.as_ref()) else {
+                continue;
+            };
+            let base = next_match;
+            let captures = run_query(query, layer.tree.root_node(), rope, byte_range.clone());
+            if let Some(last) = captures.iter().map(|c| c.match_index).max() {
+                next_match = base + last + 1;
+            }
+            all.extend(captures.into_iter().map(|c| QueryCapture {
+                match_index: base + c.match_index,
+                ..c
+            }));
+        }
+        all
+    }
+
     /// Materialize captures matching `select(layer.language)` across
     /// every layer, restricted to `byte_range`, sorted in document
     /// order. The per-layer query is selected by the `select`
@@ -381,15 +439,20 @@ impl SyntaxMap {
             let parent_tree = parent.tree.clone();
             let parent_depth = parent.depth;
 
-            let Some(injection_query) = parent_lang.injection_query.as_ref() else {
-                continue;
-            };
-
             // Group injection matches by inner language so we can
             // emit combined-injection trees (one tree per language
-            // covering all of that language's host ranges).
+            // covering all of that language's host ranges). A fence, or an
+            // embed-query match not marked `injection.combined`, names its
+            // own language and is its own document, so it parses as a
+            // standalone layer instead.
             let mut grouped: HashMap<&'static str, (Arc<Language>, Vec<Range<usize>>)> =
                 HashMap::new();
+            let mut standalone: Vec<(Arc<Language>, Range<usize>)> = Vec::new();
+            let candidates = parent_lang
+                .injection_candidates
+                .get()
+                .map(Vec::as_slice)
+                .unwrap_or_default();
 
             let mut cursor = QueryCursorHandle::new();
             // When the caller has supplied changed-range filters,
@@ -403,93 +466,89 @@ impl SyntaxMap {
                 #[allow(clippy::single_range_in_vec_init)]
                 _ => vec![0..rope.len()],
             };
-            for filter in filter_ranges {
-                cursor.set_byte_range(filter);
-                let provider = RopeTextProvider { rope };
-                let mut matches =
-                    cursor.matches(injection_query, parent_tree.root_node(), provider);
-                while let Some(m) = matches.next() {
-                    let pattern_index = m.pattern_index;
-                    let Some(injection) = parent_lang.injections.get(pattern_index) else {
-                        continue;
-                    };
-                    match &injection.inner {
-                        InjectionInner::Fixed(inner_lang) => {
-                            for capture in m.captures {
-                                let inner_start = capture.node.start_byte();
-                                let inner_end = capture.node.end_byte();
-                                if inner_end <= inner_start {
-                                    continue;
+            if let Some(injection_query) = parent_lang.injection_query.as_ref() {
+                for filter in &filter_ranges {
+                    cursor.set_byte_range(filter.clone());
+                    let provider = RopeTextProvider { rope };
+                    let mut matches =
+                        cursor.matches(injection_query, parent_tree.root_node(), provider);
+                    while let Some(m) = matches.next() {
+                        let Some(injection) = parent_lang.injections.get(m.pattern_index) else {
+                            continue;
+                        };
+                        match &injection.inner {
+                            InjectionInner::Fixed(inner_lang) => {
+                                for capture in m.captures {
+                                    let inner_start = capture.node.start_byte();
+                                    let inner_end = capture.node.end_byte();
+                                    if inner_end <= inner_start {
+                                        continue;
+                                    }
+                                    grouped
+                                        .entry(inner_lang.name)
+                                        .or_insert_with(|| (inner_lang.clone(), Vec::new()))
+                                        .1
+                                        .push(inner_start..inner_end);
                                 }
-                                grouped
-                                    .entry(inner_lang.name)
-                                    .or_insert_with(|| (inner_lang.clone(), Vec::new()))
-                                    .1
-                                    .push(inner_start..inner_end);
-                            }
-                        },
-                        InjectionInner::Fence => {
-                            // A fence names its own language and is its own
-                            // document, so resolve the info string and parse the
-                            // content as a standalone layer per match rather than
-                            // grouping it with the combined injections above.
-                            let names = injection_query.capture_names();
-                            let mut token: Option<String> = None;
-                            let mut content: Option<Range<usize>> = None;
-                            for capture in m.captures {
-                                match names.get(capture.index as usize).copied() {
-                                    Some("injection.language") => {
-                                        token = Some(
-                                            rope.chunks_in_range(capture.node.byte_range())
-                                                .collect(),
-                                        );
-                                    },
-                                    Some("injection.content") => {
-                                        let r = capture.node.byte_range();
-                                        if r.end > r.start {
-                                            content = Some(r);
-                                        }
-                                    },
-                                    _ => {},
+                            },
+                            InjectionInner::Fence => {
+                                if let Some((inner_lang, content, _)) =
+                                    resolve_injection_match(injection_query, m, rope, candidates)
+                                {
+                                    standalone.push((inner_lang, content));
                                 }
-                            }
-                            let (Some(token), Some(content)) = (token, content) else {
-                                continue;
-                            };
-                            let Some(candidates) = parent_lang.fence_candidates.get() else {
-                                continue;
-                            };
-                            let Some(inner_lang) = language_for_fence_token(&token, candidates)
-                            else {
-                                continue;
-                            };
-                            let prior = prior_injections.iter().find(|p| {
-                                p.start_offset == content.start as u32
-                                    && p.end_offset == content.end as u32
-                                    && p.language_name == inner_lang.name
-                            });
-                            let Some(inner_tree) = parse_rope_range(
-                                &inner_lang,
-                                rope,
-                                content.clone(),
-                                prior.map(|p| &p.tree),
-                            ) else {
-                                continue;
-                            };
-                            new_layers.push(SyntaxLayer {
-                                depth: parent_depth + 1,
-                                start_offset: content.start as u32,
-                                end_offset: content.end as u32,
-                                language: inner_lang,
-                                tree: inner_tree,
-                            });
-                            queue.push_back(new_layers.len() - 1);
-                        },
+                            },
+                        }
+                    }
+                }
+            }
+            if let Some(embed_query) = parent_lang.embed_query.as_ref() {
+                for filter in &filter_ranges {
+                    cursor.set_byte_range(filter.clone());
+                    let provider = RopeTextProvider { rope };
+                    let mut matches =
+                        cursor.matches(embed_query, parent_tree.root_node(), provider);
+                    while let Some(m) = matches.next() {
+                        let Some((inner_lang, content, combined)) =
+                            resolve_injection_match(embed_query, m, rope, candidates)
+                        else {
+                            continue;
+                        };
+                        if combined {
+                            grouped
+                                .entry(inner_lang.name)
+                                .or_insert_with(|| (inner_lang.clone(), Vec::new()))
+                                .1
+                                .push(content);
+                        } else {
+                            standalone.push((inner_lang, content));
+                        }
                     }
                 }
             }
             drop(cursor);
 
+            for (inner_lang, content) in standalone {
+                let prior = prior_injections.iter().find(|p| {
+                    p.start_offset == content.start as u32
+                        && p.end_offset == content.end as u32
+                        && p.language_name == inner_lang.name
+                });
+                let Some(inner_tree) =
+                    parse_rope_range(&inner_lang, rope, content.clone(), prior.map(|p| &p.tree))
+                else {
+                    continue;
+                };
+                new_layers.push(SyntaxLayer {
+                    depth: parent_depth + 1,
+                    start_offset: content.start as u32,
+                    end_offset: content.end as u32,
+                    language: inner_lang,
+                    tree: inner_tree,
+                });
+                queue.push_back(new_layers.len() - 1);
+            }
+
             for (_, (inner_lang, ranges)) in grouped {
                 // Combined injections: if more than one host range,
                 // merge them into a single tree via set_included_ranges.
@@ -543,6 +602,48 @@ impl SyntaxMap {
     }
 }
 
+/// Resolve a match of an injection query written in the standard
+/// `injections.scm` form to the language it names, the byte range to parse
+/// as that language, and whether the pattern sets `injection.combined`.
+///
+/// The language comes from the text of an `@injection.language` capture or,
+/// failing that, a `#set! injection.language` property, and resolves against
+/// `candidates` by name or extension. `None` when the match has no non-empty
+/// `@injection.content` or names a language that is not registered, so a
+/// query may name grammars the editor does not bundle.
+fn resolve_injection_match(
+    query: &Query,
+    m: &QueryMatch<'_, '_>,
+    rope: &Rope,
+    candidates: &[Arc<Language>],
+) -> Option<(Arc<Language>, Range<usize>, bool)> {
+    let names = query.capture_names();
+    let mut token: Option<String> = None;
+    let mut content: Option<Range<usize>> = None;
+    for capture in m.captures {
+        match names.get(capture.index as usize).copied() {
+            Some("injection.language") => {
+                token = Some(rope.chunks_in_range(capture.node.byte_range()).collect());
+            },
+            Some("injection.content") => content = Some(capture.node.byte_range()),
+            _ => {},
+        }
+    }
+    let mut combined = false;
+    for property in query.property_settings(m.pattern_index) {
+        match &*property.key {
+            "injection.language" if token.is_none() => {
+                token = property.value.as_deref().map(str::to_owned);
+            },
+            "injection.combined" => combined = true,
+            _ => {},
+        }
+    }
+    let content = content.filter(|r| r.end > r.start)?;
+    let inner = language_for_fence_token(&token?, candidates)?;
+    Some((inner, content, combined))
+}
+
 /// Per-host injection tree from the previous parse, used as
 /// `old_tree` for incremental reparse when the same host range
 /// reappears in this parse.
@@ -958,6 +1059,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn query_captures_run_only_on_selected_layers() {
+        // A rust query selected for rust layers finds the fenced function and
+        // nothing in the surrounding markdown layers.
+        let lang = markdown_lang();
+        let source = "# fn\n\n```rust\nfn a() {}\nfn b() {}\n```\n";
+        let rope = Rope::from(source);
+        let mut map = SyntaxMap::new();
+        map.reparse(&rope, lang, 1).unwrap();
+        let query =
+            crate::compile_query(&rust_lang(), "(function_item name: (identifier) @name)").unwrap();
+
+        let captures = map.snapshot().query_captures(0..rope.len(), &rope, |l| {
+            (l.name == "rust").then_some(&query)
+        });
+        let names: Vec<&str> = captures
+            .iter()
+            .map(|c| &source[c.byte_range.clone()])
+            .collect();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_ne!(captures[0].match_index, captures[1].match_index);
+    }
+
+    #[test]
+    fn query_captures_number_matches_uniquely_across_layers() {
+        let lang = markdown_lang();
+        let source = "```rust\nfn a() {}\nfn b() {}\n```\n\n```rust\nfn c() {}\n```\n";
+        let rope = Rope::from(source);
+        let mut map = SyntaxMap::new();
+        map.reparse(&rope, lang, 1).unwrap();
+        let query =
+            crate::compile_query(&rust_lang(), "(function_item name: (identifier) @name)").unwrap();
+
+        let captures = map.snapshot().query_captures(0..rope.len(), &rope, |l| {
+            (l.name == "rust").then_some(&query)
+        });
+        let mut indices: Vec<usize> = captures.iter().map(|c| c.match_index).collect();
+        assert_eq!(indices.len(), 3);
+        indices.sort_unstable();
+        indices.dedup();
+        assert_eq!(
+            indices.len(),
+            3,
+            "each layer's matches are numbered past the last"
+        );
+    }
+
     #[test]
     fn reparse_markdown_unknown_fence_token_adds_no_layer() {
         // A fence naming no registered language stays literal. Only markdown
@@ -979,6 +1127,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reparse_markdown_front_matter_injects_toml_via_embed_query() {
+        // `+++` front matter matches the markdown injections query, which names
+        // toml by a `#set!` property. The html block names a grammar that is
+        // not registered, so it stays literal.
+        let lang = markdown_lang();
+        assert!(
+            lang.embed_query.is_some(),
+            "markdown injections.scm compiles"
+        );
+        let source = "+++\ntitle = \"x\"\n+++\n\n<div>\nhi\n</div>\n";
+        let rope = Rope::from(source);
+        let mut map = SyntaxMap::new();
+        map.reparse(&rope, lang, 1).unwrap();
+
+        let layers: Vec<(u32, &str)> = map
+            .snapshot()
+            .iter_layers()
+            .map(|l| (l.depth, l.language.name))
+            .collect();
+        assert!(
+            layers.iter().any(|&(d, n)| d == 1 && n == "toml"),
+            "depth-1 toml layer over the front matter, got {layers:?}"
+        );
+        assert!(
+            layers
+                .iter()
+                .all(|&(_, n)| n == "toml" || n.starts_with("markdown")),
+            "the html block injects nothing, got {layers:?}"
+        );
+
+        let key_start = source.find("title").unwrap();
+        let captures = map
+            .snapshot()
+            .captures(0..rope.len(), &rope, |l| Some(&l.highlight_query));
+        assert!(
+            captures
+                .iter()
+                .any(|c| c.language.name == "toml" && c.node.byte_range().start == key_start),
+            "expected a toml capture at the front matter key"
+        );
+    }
+
+    #[test]
+    fn rust_embed_query_skips_unregistered_languages() {
+        // The rust injections query targets sql and regex, neither of which is
+        // bundled, so the strings stay plain rust with no injected layer.
+        let lang = rust_lang();
+        assert!(lang.embed_query.is_some(), "rust injections.scm compiles");
+        let rope = Rope::from("fn f() { sql!(\"SELECT 1\"); Regex::new(\"a+\"); }\n");
+        let mut map = SyntaxMap::new();
+        map.reparse(&rope, lang, 1).unwrap();
+        assert_eq!(map.snapshot().layer_count(), 1);
+    }
+
     #[test]
     fn reparse_rust_doc_comment_fence_nests_three_layers() {
         // A rust fence inside a rust doc comment's markdown nests three layers:
@@ -2,26 +2,19 @@
 //!
 //! `select_textobject_around` / `select_textobject_inner` need to find
 //! the smallest tree-sitter capture (under a given name like
-//! `function.around`) that contains the cursor. This module wraps the
-//! query-cursor + rope-text-provider plumbing into a single function
-//! so handlers in the `stoat` crate do not have to construct a
-//! `QueryCursor` and `TextProvider` themselves.
+//! `function.around`) that contains the cursor. This module builds
+//! that on the captures of [`crate::run_query`], grouped per match, so
+//! handlers in the `stoat` crate do not have to drive a query
+//! themselves.
 //!
 //! Pure tree-sitter logic only -- paragraph (line-based) textobjects
 //! are handled in the `stoat` crate alongside the action handler.
 
-use crate::highlight::{QueryCursorHandle, RopeTextProvider};
+use crate::query::{capture_ranges_by_match, run_query};
 use std::ops::Range;
 use stoat_text::Rope;
-use tree_sitter::{Node, Query, StreamingIterator};
+use tree_sitter::{Node, Query};
 
-/// Smallest byte range under `capture_name` in `query` that contains
-/// `cursor`. Returns `None` if `capture_name` is unknown to the query
-/// or no matching capture brackets `cursor`.
-///
-/// Helix's textobject selection picks the innermost match by capture
-/// length; this function follows the same rule. `rope` is needed for
-/// query predicates (`#eq?`, `#match?`) that read node text.
 /// Sorted, deduplicated start byte offsets of every match's
 /// `capture_name` union range. Used by goto-next/prev navigation
 /// (`] f` / `[ f` / `] t` / `[ t`) to land on the keyword that
@@ -34,34 +27,23 @@ pub fn collect_capture_starts(
     rope: &Rope,
     capture_name: &str,
 ) -> Vec<usize> {
-    let mut out = Vec::new();
-    let Some(cap_idx) = query.capture_index_for_name(capture_name) else {
-        return out;
-    };
-    let provider = RopeTextProvider { rope };
-    let mut cursor_h = QueryCursorHandle::new();
-    let mut matches = cursor_h.matches(query, root, provider);
-    while let Some(m) = matches.next() {
-        let mut union: Option<Range<usize>> = None;
-        for cap in m.captures {
-            if cap.index != cap_idx {
-                continue;
-            }
-            let r = cap.node.byte_range();
-            union = Some(match union {
-                None => r,
-                Some(u) => u.start.min(r.start)..u.end.max(r.end),
-            });
-        }
-        if let Some(u) = union {
-            out.push(u.start);
-        }
-    }
+    let captures = run_query(query, root, rope, 0..rope.len());
+    let mut out: Vec<usize> = capture_ranges_by_match(&captures, capture_name)
+        .into_iter()
+        .map(|r| r.start)
+        .collect();
     out.sort_unstable();
     out.dedup();
     out
 }
 
+/// Smallest byte range under `capture_name` in `query` that contains
+/// `cursor`. Returns `None` if `capture_name` is unknown to the query
+/// or no matching capture brackets `cursor`.
+///
+/// Helix's textobject selection picks the innermost match by capture
+/// length; this function follows the same rule. `rope` is needed for
+/// query predicates (`#eq?`, `#match?`) that read node text.
 pub fn find_smallest_capture_at(
     query: &Query,
     root: Node<'_>,
@@ -69,32 +51,9 @@ pub fn find_smallest_capture_at(
     capture_name: &str,
     cursor: usize,
 ) -> Option<Range<usize>> {
-    let cap_idx = query.capture_index_for_name(capture_name)?;
-    let provider = RopeTextProvider { rope };
-    let mut cursor_h = QueryCursorHandle::new();
-    let mut matches = cursor_h.matches(query, root, provider);
-    let mut best: Option<Range<usize>> = None;
-    while let Some(m) = matches.next() {
-        let mut union: Option<Range<usize>> = None;
-        for cap in m.captures {
-            if cap.index != cap_idx {
-                continue;
-            }
-            let r = cap.node.byte_range();
-            union = Some(match union {
-                None => r,
-                Some(u) => u.start.min(r.start)..u.end.max(r.end),
-            });
-        }
-        let Some(u) = union else { continue };
-        if !(u.start <= cursor && cursor < u.end) {
-            continue;
-        }
-        let len = u.end - u.start;
-        match &best {
-            Some(b) if (b.end - b.start) <= len => {},
-            _ => best = Some(u),
-        }
-    }
-    best
+    let captures = run_query(query, root, rope, 0..rope.len());
+    capture_ranges_by_match(&captures, capture_name)
+        .into_iter()
+        .filter(|u| u.start <= cursor && cursor < u.end)
+        .min_by_key(|u| u.end - u.start)
 }
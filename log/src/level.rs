@@ -0,0 +1,158 @@
+//! Changing the log filter while the process runs.
+//!
+//! [`crate::init`] wraps its [`EnvFilter`] in a reload layer and hands the
+//! handle here along with the directives it started from. [`set_level`] then
+//! layers per-target overrides on top of those directives and swaps the
+//! rebuilt filter in, so a session can move to `debug` or `trace` for one
+//! crate without a restart.
+
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, OnceLock},
+};
+use tracing_subscriber::{
+    filter::{LevelFilter, ParseError},
+    reload, EnvFilter, Registry,
+};
+
+/// Targets a bare level such as `debug` applies to, matching the default
+/// filter's stoat crates.
+const STOAT_TARGETS: &[&str] = &["stoat", "stoat_bin"];
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum SetLevelError {
+    #[snafu(display("Logging is not initialized"))]
+    NotInitialized {
+        #[snafu(implicit)]
+        location: snafu::Location,
+    },
+
+    #[snafu(display("Invalid log level `{spec}`"))]
+    InvalidLevel {
+        spec: String,
+        #[snafu(implicit)]
+        location: snafu::Location,
+    },
+
+    #[snafu(display("Failed to parse log filter directive"))]
+    RebuildFilter {
+        source: ParseError,
+        #[snafu(implicit)]
+        location: snafu::Location,
+    },
+
+    #[snafu(display("Failed to swap the log filter"))]
+    ReloadFilter {
+        source: reload::Error,
+        #[snafu(implicit)]
+        location: snafu::Location,
+    },
+}
+
+struct LevelState {
+    handle: reload::Handle<EnvFilter, Registry>,
+    /// Directives the filter was built from at startup.
+    base: String,
+    /// Per-target levels set at runtime, appended after [`Self::base`] so
+    /// they replace its directive for the same target.
+    overrides: BTreeMap<String, String>,
+}
+
+static LEVEL: OnceLock<Mutex<LevelState>> = OnceLock::new();
+
+pub(crate) fn install(handle: reload::Handle<EnvFilter, Registry>, base: String) {
+    let _ = LEVEL.set(Mutex::new(LevelState {
+        handle,
+        base,
+        overrides: BTreeMap::new(),
+    }));
+}
+
+/// Apply `spec` to the running log filter and return the directives now in
+/// effect.
+///
+/// `spec` is one of:
+/// - a bare level (`debug`), which sets the stoat crates to that level;
+/// - `target=level` pairs separated by commas (`stoat::lsp=trace`), each overriding one target;
+/// - `reset`, which drops every override and restores the startup filter.
+pub fn set_level(spec: &str) -> Result<String, SetLevelError> {
+    let state = LEVEL.get().context(NotInitializedSnafu)?;
+    let mut state = state.lock().expect("log level mutex");
+    let mut overrides = state.overrides.clone();
+    apply_spec(&mut overrides, spec)?;
+    let directives = directives(&state.base, &overrides);
+    let filter = EnvFilter::try_new(&directives).context(RebuildFilterSnafu)?;
+    state.handle.reload(filter).context(ReloadFilterSnafu)?;
+    state.overrides = overrides;
+    Ok(directives)
+}
+
+fn apply_spec(overrides: &mut BTreeMap<String, String>, spec: &str) -> Result<(), SetLevelError> {
+    let spec = spec.trim();
+    if spec.eq_ignore_ascii_case("reset") {
+        overrides.clear();
+        return Ok(());
+    }
+    let mut pairs = Vec::new();
+    for part in spec.split(',').map(str::trim) {
+        let (targets, level) = match part.split_once('=') {
+            Some((target, level)) => (vec![target.trim()], level.trim()),
+            None => (STOAT_TARGETS.to_vec(), part),
+        };
+        let valid = targets.iter().all(|t| !t.is_empty()) && level.parse::<LevelFilter>().is_ok();
+        ensure!(valid, InvalidLevelSnafu { spec });
+        for target in targets {
+            pairs.push((target.to_string(), level.to_ascii_lowercase()));
+        }
+    }
+    overrides.extend(pairs);
+    Ok(())
+}
+
+fn directives(base: &str, overrides: &BTreeMap<String, String>) -> String {
+    let mut directives = base.to_string();
+    for (target, level) in overrides {
+        directives.push_str(&format!(",{target}={level}"));
+    }
+    directives
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(specs: &[&str]) -> Result<String, SetLevelError> {
+        let mut overrides = BTreeMap::new();
+        for spec in specs {
+            apply_spec(&mut overrides, spec)?;
+        }
+        Ok(directives("warn,stoat=info", &overrides))
+    }
+
+    #[test]
+    fn bare_levels_and_targets_override_the_base() {
+        assert_eq!(
+            applied(&["debug"]).unwrap(),
+            "warn,stoat=info,stoat=debug,stoat_bin=debug"
+        );
+        assert_eq!(
+            applied(&["debug", "stoat::lsp=TRACE, stoat=warn"]).unwrap(),
+            "warn,stoat=info,stoat=warn,stoat::lsp=trace,stoat_bin=debug"
+        );
+        assert_eq!(applied(&["trace", "reset"]).unwrap(), "warn,stoat=info");
+    }
+
+    #[test]
+    fn rejects_unknown_levels_and_empty_targets() {
+        assert!(matches!(
+            applied(&["loud"]),
+            Err(SetLevelError::InvalidLevel { .. })
+        ));
+        assert!(matches!(
+            applied(&["=debug"]),
+            Err(SetLevelError::InvalidLevel { .. })
+        ));
+    }
+}
@@ -18,18 +18,42 @@
 //! 1. `STOAT_LOG` (highest priority) - Stoat-specific logging control
 //! 2. `RUST_LOG` - Standard tracing environment variable
 //! 3. Default - `warn` globally, `info` for stoat crates
+//!
+//! ## Log files
+//!
+//! A file target rotates once it passes [`rotate::MAX_LOG_BYTES`], keeping
+//! [`rotate::LOG_BACKUPS`] numbered backups beside it. [`log_file`] returns the
+//! live file's path for display.
+//!
+//! ## Runtime level
+//!
+//! [`set_level`] changes the filter without a restart, from a bare level for
+//! the stoat crates or `target=level` overrides. The `:log-level` command
+//! wraps it.
 
 pub mod ident;
+pub mod level;
+mod panic;
 pub mod paths;
+pub mod rotate;
 pub mod text_proto;
 
+pub use level::{set_level, SetLevelError};
+pub use panic::install_panic_hook;
 pub use paths::{data_dir, state_dir, workspace_state_dir};
+use rotate::{RotatingFile, LOG_BACKUPS, MAX_LOG_BYTES};
 use snafu::{ResultExt, Snafu};
-use std::{fs, io, path::PathBuf};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
 pub use text_proto::{log_dir, TextProtoLog};
 use tracing_subscriber::{
     filter::ParseError,
     fmt,
+    layer::SubscriberExt,
+    reload,
     util::{SubscriberInitExt, TryInitError},
     EnvFilter,
 };
@@ -60,10 +84,14 @@ pub enum LogInitError {
     },
 }
 
+/// Path of the file [`init`] opened, read back through [`log_file`].
+static LOG_FILE: OnceLock<PathBuf> = OnceLock::new();
+
 /// Where [`init`] routes tracing output.
 pub enum LogTarget {
     /// Append to the file at this path with ANSI disabled, so the raw bytes
-    /// stay clean and tracing never corrupts the raw-mode terminal.
+    /// stay clean and tracing never corrupts the raw-mode terminal. The file
+    /// rotates by size through [`RotatingFile`].
     File(PathBuf),
     /// Write to stderr with ANSI enabled for live console viewing. The raw-mode
     /// TUI is corrupted by stderr unless the caller redirects it (`2>file`).
@@ -73,69 +101,67 @@ pub enum LogTarget {
 /// Initialize logging to `target`.
 ///
 /// `LogTarget::File` appends to the file (creating it if needed) with ANSI off,
-/// so tracing output never hits the raw-mode terminal. `LogTarget::Stderr`
-/// writes to stderr with ANSI on for live viewing, which the raw-mode TUI needs
-/// redirected (`2>file`) to stay readable.
+/// so tracing output never hits the raw-mode terminal, and records its path for
+/// [`log_file`]. `LogTarget::Stderr` writes to stderr with ANSI on for live
+/// viewing, which the raw-mode TUI needs redirected (`2>file`) to stay
+/// readable. Either way [`install_panic_hook`] runs so panics reach the log.
 ///
 /// `stoat_log` takes precedence over `rust_log`; both `None` falls back to the
 /// compiled-in default of `warn,stoat=info,stoat_bin=info`. Callers resolve env
 /// state and the log file path at the binary boundary, including ensuring the
 /// parent directory exists; this crate does not read the process environment or
 /// create directories.
+///
+/// The filter sits behind a reload handle, so [`set_level`] can change it
+/// afterwards.
 pub fn init(
     stoat_log: Option<String>,
     rust_log: Option<String>,
     target: LogTarget,
 ) -> Result<(), LogInitError> {
-    let filter = create_filter(stoat_log, rust_log)?;
+    let directives = filter_directives(stoat_log, rust_log);
+    let filter = EnvFilter::try_new(&directives).context(BuildEnvFilterSnafu)?;
+    let (filter, handle) = reload::Layer::new(filter);
+    let registry = tracing_subscriber::registry().with(filter);
     match target {
         LogTarget::File(path) => {
-            let file = fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(&path)
+            let file = RotatingFile::open(&path, MAX_LOG_BYTES, LOG_BACKUPS)
                 .with_context(|_| OpenLogFileSnafu { path: path.clone() })?;
-            fmt()
-                .with_env_filter(filter)
-                .with_writer(file)
-                .with_ansi(false)
-                .finish()
+            registry
+                .with(fmt::layer().with_writer(Mutex::new(file)).with_ansi(false))
                 .try_init()
                 .context(SetGlobalSubscriberSnafu)?;
+            let _ = LOG_FILE.set(path);
         },
         LogTarget::Stderr => {
-            fmt()
-                .with_env_filter(filter)
-                .with_writer(io::stderr)
-                .with_ansi(true)
-                .finish()
+            registry
+                .with(fmt::layer().with_writer(io::stderr).with_ansi(true))
                 .try_init()
                 .context(SetGlobalSubscriberSnafu)?;
         },
     }
+    level::install(handle, directives);
+    install_panic_hook();
     Ok(())
 }
 
-fn create_filter(
-    stoat_log: Option<String>,
-    rust_log: Option<String>,
-) -> Result<EnvFilter, LogInitError> {
-    if let Some(stoat_log) = stoat_log {
-        return expand_stoat_log(&stoat_log);
-    }
-
-    if let Some(rust_log) = rust_log {
-        return EnvFilter::try_new(rust_log).context(BuildEnvFilterSnafu);
-    }
-
-    Ok(EnvFilter::new("warn,stoat=info,stoat_bin=info"))
+/// The live log file of this process, or `None` when [`init`] has not run or
+/// logs to stderr. Rotation keeps the path stable, so it always names the
+/// newest output.
+pub fn log_file() -> Option<&'static Path> {
+    LOG_FILE.get().map(PathBuf::as_path)
 }
 
-fn expand_stoat_log(stoat_log: &str) -> Result<EnvFilter, LogInitError> {
-    if stoat_log.contains('=') || stoat_log.contains(':') || stoat_log.contains(',') {
-        return EnvFilter::try_new(stoat_log).context(BuildEnvFilterSnafu);
+/// The filter directives for the given env values. A bare `stoat_log` level
+/// expands to the stoat crates; anything shaped like a directive list passes
+/// through as written.
+fn filter_directives(stoat_log: Option<String>, rust_log: Option<String>) -> String {
+    if let Some(stoat_log) = stoat_log {
+        if stoat_log.contains('=') || stoat_log.contains(':') || stoat_log.contains(',') {
+            return stoat_log;
+        }
+        return format!("warn,stoat={stoat_log},stoat_bin={stoat_log}");
     }
 
-    EnvFilter::try_new(format!("warn,stoat={stoat_log},stoat_bin={stoat_log}"))
-        .context(BuildEnvFilterSnafu)
+    rust_log.unwrap_or_else(|| "warn,stoat=info,stoat_bin=info".to_string())
 }
@@ -0,0 +1,28 @@
+use std::{backtrace::Backtrace, panic, sync::Once};
+
+/// Chain a panic hook that logs the panic message, location, and a captured
+/// backtrace through [`tracing::error`] before running the previously
+/// installed hook. Installed by [`crate::init`], so the log keeps the panic
+/// after the terminal scrollback is gone. Idempotent across repeated calls.
+pub fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let prior = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let panic_message = match info.payload().downcast_ref::<&'static str>() {
+                Some(message) => *message,
+                None => match info.payload().downcast_ref::<String>() {
+                    Some(message) => message.as_str(),
+                    None => "Box<Any>",
+                },
+            };
+            let location = info
+                .location()
+                .map(|loc| format!("{}:{}", loc.file(), loc.line()));
+            let backtrace = Backtrace::force_capture();
+            tracing::error!(panic = true, ?location, %panic_message, %backtrace, "stoat panic");
+
+            prior(info);
+        }));
+    });
+}
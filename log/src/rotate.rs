@@ -0,0 +1,124 @@
+//! Size-capped log file that rolls over into numbered backups.
+//!
+//! [`RotatingFile`] appends to `<name>.log` until the next write would push it
+//! past its byte cap, then shifts `<name>.log.1` to `<name>.log.2` and so on,
+//! renames the live file to `<name>.log.1`, and starts a fresh one. The oldest
+//! backup past the configured count is deleted, so one session's logs never
+//! hold more than `max_bytes * (backups + 1)` bytes on disk.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Byte cap of the live log file before it rotates.
+pub const MAX_LOG_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Rotated files kept beside the live one.
+pub const LOG_BACKUPS: usize = 3;
+
+/// An append-only file writer that rotates by size.
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    /// Bytes in the live file, seeded from its length on open so a reopened
+    /// log rotates on the same schedule as a fresh one.
+    written: u64,
+    max_bytes: u64,
+    backups: usize,
+}
+
+impl RotatingFile {
+    /// Opens `path` for appending, creating it if needed. Callers are
+    /// responsible for ensuring the parent directory exists.
+    pub fn open(path: &Path, max_bytes: u64, backups: usize) -> io::Result<Self> {
+        let file = open_append(path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            written,
+            max_bytes,
+            backups,
+        })
+    }
+
+    fn backup(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{n}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.backups == 0 {
+            self.file = File::create(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.backup(self.backups));
+            for n in (1..self.backups).rev() {
+                let from = self.backup(n);
+                if from.exists() {
+                    fs::rename(&from, self.backup(n + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.backup(1))?;
+            self.file = open_append(&self.path)?;
+        }
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn rotates_past_the_cap_and_drops_the_oldest_backup() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("s.log");
+        let mut log = RotatingFile::open(&path, 8, 2).expect("open");
+        for line in ["one\n", "two\n", "three\n", "four\n", "five\n"] {
+            log.write_all(line.as_bytes()).expect("write");
+        }
+        log.flush().expect("flush");
+
+        let read = |p: PathBuf| fs::read_to_string(p).expect("read");
+        assert_eq!(read(path.clone()), "five\n");
+        assert_eq!(read(dir.path().join("s.log.1")), "four\n");
+        assert_eq!(read(dir.path().join("s.log.2")), "three\n");
+        assert!(!dir.path().join("s.log.3").exists());
+    }
+
+    #[test]
+    fn reopening_counts_the_existing_length() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("s.log");
+        fs::write(&path, "1234567").expect("seed");
+        let mut log = RotatingFile::open(&path, 8, 1).expect("open");
+        log.write_all(b"89").expect("write");
+        assert_eq!(fs::read_to_string(&path).expect("read"), "89");
+        assert!(dir.path().join("s.log.1").exists());
+    }
+}
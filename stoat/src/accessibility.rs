@@ -0,0 +1,56 @@
+//! Editor state for assistive technology.
+//!
+//! [`describe`] reduces the focused editor to what a screen reader announces:
+//! the cursor's line and column, that line's text, the mode, and the open
+//! modal. [`Stoat::emit_accessibility`](crate::app::Stoat) sends it to stoatty
+//! whenever it changes.
+
+use crate::{action_handlers, app::Stoat, keymap_state::modal_predicate};
+use stoatty_protocol::command::{AccessibilityCommand, MAX_ACCESSIBLE_LINE_BYTES};
+
+/// The announcement for the current frame. With no focused editor, such as a
+/// focused terminal pane, the position is zero and the line text empty.
+pub(crate) fn describe(stoat: &mut Stoat) -> AccessibilityCommand {
+    let mode = stoat.focused_mode().to_string();
+    let modal = modal_predicate(stoat).map(|name| name.replace('_', " "));
+    let (line, column, line_text) = match action_handlers::focused_editor_mut(stoat) {
+        Some(editor) => {
+            let snapshot = editor.display_map.snapshot();
+            let buffer_snapshot = snapshot.buffer_snapshot();
+            let rope = buffer_snapshot.rope();
+            let sel = editor.selections.newest_anchor();
+            let offset = stoat_text::cursor_offset(
+                rope,
+                buffer_snapshot.resolve_anchor(&sel.tail()),
+                buffer_snapshot.resolve_anchor(&sel.head()),
+            );
+            let point = rope.offset_to_point(offset);
+            let text = rope.line_at_row(point.row);
+            let text = text.trim_end_matches(['\n', '\r']);
+            let column = text
+                .get(..point.column as usize)
+                .map_or(0, |prefix| prefix.chars().count() as u32);
+            (point.row, column, truncate(text).to_string())
+        },
+        None => (0, 0, String::new()),
+    };
+    AccessibilityCommand {
+        line,
+        column,
+        mode,
+        modal,
+        line_text,
+    }
+}
+
+/// `text` cut to at most [`MAX_ACCESSIBLE_LINE_BYTES`] on a char boundary.
+fn truncate(text: &str) -> &str {
+    if text.len() <= MAX_ACCESSIBLE_LINE_BYTES {
+        return text;
+    }
+    let mut end = MAX_ACCESSIBLE_LINE_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
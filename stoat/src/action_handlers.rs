@@ -1,4 +1,4 @@
-mod agent;
+pub(crate) mod agent;
 pub(crate) mod code_search;
 mod commits;
 pub(crate) mod completion;
@@ -7,6 +7,7 @@ mod conflict_view;
 pub(crate) mod file;
 mod file_finder;
 pub(crate) mod filter_selections;
+pub(crate) mod fold;
 mod help;
 pub(crate) mod jump;
 pub(crate) mod lsp;
@@ -15,9 +16,11 @@ pub(crate) mod marks;
 pub(crate) mod movement;
 mod palette;
 mod pane;
+mod perf;
 pub(crate) mod picker;
 mod prompt;
 mod rebase;
+mod regex_playground;
 pub(crate) mod review;
 mod reword;
 mod run;
@@ -33,12 +36,14 @@ pub(crate) mod workspace;
 pub(crate) mod yank;
 
 use crate::{
+    agent_permission::PermissionDecision,
     app::{Stoat, UpdateEffect},
-    command_palette::CommandPalette,
+    command_palette::{CommandPalette, PaletteAlias},
     display_map::syntax_theme::SyntaxStyles,
     editor_state::{EditorId, EditorState},
     help::{build_help_bindings, Help, SnapshotState},
     host::FsHost,
+    input_draft::DraftKey,
     input_view::{InputView, SubmitTarget},
     jumplist::JumpList,
     keymap::{KeymapState, StateValue},
@@ -48,21 +53,23 @@ use crate::{
 };
 pub(crate) use commits::pump_commits;
 pub(crate) use file_finder::{
-    close_file_finder, file_finder_move_selection, sync_file_finder_preview,
+    close_file_finder, dismiss_file_finder, file_finder_move_selection, sync_file_finder_preview,
 };
 pub(crate) use lsp::pump_lsp_jumps;
-pub(crate) use palette::{palette_move_selection, sync_palette_picker};
+pub(crate) use palette::{dismiss_palette, palette_move_selection, sync_palette_picker};
 pub(crate) use pane::{close_pane_by_id, restore_pane_after_term_exit};
 #[cfg(test)]
 pub(crate) use review::install_review_session;
 pub(crate) use review::{pump_review_scan, PendingReviewScan};
 use std::{collections::HashMap, path::Path, sync::Arc};
 use stoat_action::{
-    Action, ActionKind, AutoReload, AutoReloadConfig, Dump, FocusPane, GotoTab, OpenBuffer,
-    OpenConfig, OpenFile, OpenReviewAgentEdits, OpenReviewCommit, OpenReviewCommitRange, RenameTab,
-    RenameWorkspace, ReviewExternalEdit, Run, SetCwd, SetTheme,
+    Action, ActionKind, AddBufferToGroup, AutoReload, AutoReloadConfig, DiffWithBuffer, Dump,
+    FocusPane, GotoTab, NotificationHistory, OpenBuffer, OpenConfig, OpenFile,
+    OpenReviewAgentEdits, OpenReviewCommit, OpenReviewCommitRange, RenameTab, RenameWorkspace,
+    ReviewExternalEdit, Run, RunFromHistory, RunTask, RunUserCommand, SetCwd, SetLogLevel,
+    SetTheme, SwitchBufferGroup,
 };
-use stoat_text::{Anchor, BufferId, Selection};
+use stoat_text::{Anchor, BufferId, LineEnding, Selection};
 pub(crate) use terminal::respawn_terminal_panes;
 
 pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
@@ -73,7 +80,32 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
     } else {
         begin_action_group(stoat)
     };
-    let effect = match action.kind() {
+    let mut effect = run_action(stoat, action);
+    // A count the handler left unread means it ignores counts. Repeat the
+    // editing actions where that reads naturally, inside the one undo group,
+    // so `3d` deletes three times and undoes in a single step. The count is
+    // capped so a typo like `99999999d` cannot stall the editor.
+    if let Some(count) = stoat.take_pending_count()
+        && repeats_with_count(action.kind())
+    {
+        for _ in 1..count.min(MAX_REPEAT_COUNT) {
+            effect = effect.merge(run_action(stoat, action));
+        }
+    }
+    end_action_group(stoat, group_buffer);
+    if matches!(effect, UpdateEffect::Redraw) && is_picker_open_kind(action.kind()) {
+        stoat.last_picker_action = Some(action.def().name());
+    }
+    effect
+}
+
+/// Run the handler for `action` once, without the undo grouping or count
+/// repetition [`dispatch`] wraps around it.
+fn run_action(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
+    if let Some(effect) = file::scroll_hex_view(stoat, action.kind()) {
+        return effect;
+    }
+    match action.kind() {
         ActionKind::Quit => {
             if pane::close_focused_pane(stoat) {
                 UpdateEffect::Redraw
@@ -86,6 +118,31 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::QuitAllCancel => quit_all_cancel(stoat),
         ActionKind::ShowVersion => show_version(stoat),
         ActionKind::OpenLogs => file::open_logs(stoat),
+        ActionKind::SetLogLevel => {
+            let action = action
+                .as_any()
+                .downcast_ref::<SetLogLevel>()
+                .expect("SetLogLevel action downcast");
+            match stoat_log::set_level(&action.level) {
+                Ok(filter) => stoat.set_status(format!("log filter: {filter}")),
+                Err(e) => stoat.set_status(e.to_string()),
+            }
+            UpdateEffect::Redraw
+        },
+        ActionKind::TogglePerfOverlay => perf::toggle_overlay(stoat),
+        ActionKind::ToggleProfileCapture => perf::toggle_capture(stoat),
+        ActionKind::RunToastAction => crate::toast::run_action(stoat),
+        ActionKind::DismissToasts => {
+            stoat.toasts.dismiss_all();
+            UpdateEffect::Redraw
+        },
+        ActionKind::NotificationHistory => {
+            let action = action
+                .as_any()
+                .downcast_ref::<NotificationHistory>()
+                .expect("NotificationHistory action downcast");
+            crate::toast::reshow(stoat, &action.notification)
+        },
         ActionKind::SplitRight => pane::split_pane(stoat, Axis::Vertical),
         ActionKind::SplitDown => pane::split_pane(stoat, Axis::Horizontal),
         ActionKind::SplitNewRight => pane::split_pane_new(stoat, Axis::Vertical),
@@ -208,6 +265,7 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
             file::open_config(stoat, open.target.as_deref());
             UpdateEffect::Redraw
         },
+        ActionKind::ShowEffectiveConfig => crate::project_config::show_effective(stoat),
         ActionKind::ToggleMinimap => {
             stoat.toggle_minimap();
             UpdateEffect::Redraw
@@ -216,6 +274,15 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
             stoat.toggle_wrap();
             UpdateEffect::Redraw
         },
+        ActionKind::ToggleCursorLine => {
+            stoat.toggle_cursor_line();
+            UpdateEffect::Redraw
+        },
+        ActionKind::ToggleRelativeNumbers => {
+            stoat.toggle_relative_numbers();
+            UpdateEffect::Redraw
+        },
+        ActionKind::OpenAsText => file::open_as_text(stoat),
         ActionKind::ToggleKeyHints => {
             stoat.key_hints_visible = !stoat.key_hints_visible;
             UpdateEffect::Redraw
@@ -269,18 +336,40 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::OpenCommandPalette => {
             let executor = stoat.executor.clone();
             let availability = crate::command_palette::Availability::from_stoat(stoat);
+            let aliases = PaletteAlias::resolve_all(&stoat.settings.palette_aliases);
+            let key_hints = crate::command_palette::key_hints(
+                &stoat.keymap,
+                &StoatKeymapState::from_stoat(stoat),
+            );
+            crate::overlay::push(stoat, crate::overlay::Overlay::CommandPalette);
             let ws = stoat.active_workspace_mut();
-            stoat.command_palette = Some(CommandPalette::new(ws, executor, availability));
+            let mut palette = CommandPalette::new(ws, executor, availability, aliases, key_hints);
+            if let Some(draft) = stoat.input_drafts.take(&DraftKey::Palette) {
+                palette
+                    .input
+                    .replace_text(stoat.active_workspace_mut(), &draft);
+            }
+            stoat.command_palette = Some(palette);
+            sync_palette_picker(stoat);
             UpdateEffect::Redraw
         },
         ActionKind::OpenHelp => {
             open_help(stoat);
             UpdateEffect::Redraw
         },
+        ActionKind::CheckKeymap => help::check_keymap(stoat),
         ActionKind::Diff => {
             review::toggle_diff_view(stoat);
             UpdateEffect::Redraw
         },
+        ActionKind::DiffWithBuffer => {
+            let diff = action
+                .as_any()
+                .downcast_ref::<DiffWithBuffer>()
+                .expect("DiffWithBuffer action downcast");
+            review::diff_with_buffer(stoat, &diff.path);
+            UpdateEffect::Redraw
+        },
         ActionKind::ToggleDiff => review::toggle_diff(stoat),
         ActionKind::Conflict => {
             conflict_view::open_conflict(stoat);
@@ -340,6 +429,7 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::StageLine => review::stage_line(stoat, review::HunkStage::Stage),
         ActionKind::UnstageLine => review::stage_line(stoat, review::HunkStage::Unstage),
         ActionKind::ToggleStageLine => review::stage_line(stoat, review::HunkStage::Toggle),
+        ActionKind::RevertHunk => review::revert_hunk(stoat),
         ActionKind::AddSelectionBelow => movement::add_selection_below(stoat),
         ActionKind::AddSelectionAbove => movement::add_selection_above(stoat),
         ActionKind::SplitSelectionOnNewline => movement::split_selection_on_newline(stoat),
@@ -358,10 +448,32 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::ShellInsertOutput => shell::open_insert_output(stoat),
         ActionKind::ShellAppendOutput => shell::open_append_output(stoat),
         ActionKind::ShellKeepPipe => shell::open_keep_pipe(stoat),
+        ActionKind::RunCommand => shell::open_run_command(stoat),
+        ActionKind::RunCommandWithSelection => shell::open_run_command_with_selection(stoat),
+        ActionKind::RerunCommand => shell::rerun_command(stoat),
+        ActionKind::InsertCommandOutput => shell::insert_command_output(stoat),
         ActionKind::SaveBuffer => file::save_buffer(stoat),
         ActionKind::ForceSaveBuffer => file::force_save_buffer(stoat),
         ActionKind::WriteQuit => file::write_quit(stoat),
         ActionKind::CloseBuffer => file::close_buffer(stoat),
+        ActionKind::CloseOtherBuffers => file::close_other_buffers(stoat),
+        ActionKind::TogglePinBuffer => file::toggle_pin_buffer(stoat),
+        ActionKind::AddBufferToGroup => {
+            let action = action
+                .as_any()
+                .downcast_ref::<AddBufferToGroup>()
+                .expect("AddBufferToGroup action downcast");
+            file::add_buffer_to_group(stoat, &action.name)
+        },
+        ActionKind::SwitchBufferGroup => {
+            let action = action
+                .as_any()
+                .downcast_ref::<SwitchBufferGroup>()
+                .expect("SwitchBufferGroup action downcast");
+            file::switch_buffer_group(stoat, &action.name)
+        },
+        ActionKind::SetLineEndingLf => file::set_line_ending(stoat, LineEnding::Unix),
+        ActionKind::SetLineEndingCrlf => file::set_line_ending(stoat, LineEnding::Windows),
         ActionKind::AutoReload => {
             let auto = action
                 .as_any()
@@ -392,6 +504,10 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::IndentSelection => movement::indent_selection(stoat),
         ActionKind::UnindentSelection => movement::unindent_selection(stoat),
         ActionKind::ToggleComments => movement::toggle_comments(stoat),
+        ActionKind::DuplicateAndCommentOriginal => movement::duplicate_and_comment_original(stoat),
+        ActionKind::DuplicateSelection => movement::duplicate_selection(stoat),
+        ActionKind::MoveLinesUp => movement::move_lines(stoat, movement::LineMoveDir::Up),
+        ActionKind::MoveLinesDown => movement::move_lines(stoat, movement::LineMoveDir::Down),
         ActionKind::ToggleSyntaxHighlight => {
             stoat.syntax_highlight = !stoat.syntax_highlight;
             UpdateEffect::Redraw
@@ -512,12 +628,17 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::JumpBackward => jump::jump_backward(stoat),
         ActionKind::JumpForward => jump::jump_forward(stoat),
         ActionKind::OpenJumplistPicker => open_jumplist_picker(stoat),
+        ActionKind::OpenUndoTree => open_undo_tree(stoat),
         ActionKind::OpenDiagnosticsPicker => open_diagnostics_picker(stoat),
         ActionKind::OpenWorkspaceDiagnosticsPicker => open_workspace_diagnostics_picker(stoat),
         ActionKind::JumplistPickerNext => picker::jumplist_picker_next(stoat),
         ActionKind::JumplistPickerPrev => picker::jumplist_picker_prev(stoat),
         ActionKind::JumplistPickerSelect => picker::jumplist_picker_select(stoat),
         ActionKind::JumplistPickerClose => picker::jumplist_picker_close(stoat),
+        ActionKind::UndoTreeNext => picker::undo_tree_next(stoat),
+        ActionKind::UndoTreePrev => picker::undo_tree_prev(stoat),
+        ActionKind::UndoTreeSelect => picker::undo_tree_select(stoat),
+        ActionKind::UndoTreeClose => picker::undo_tree_close(stoat),
         ActionKind::DiagnosticsPickerNext => picker::diagnostics_picker_next(stoat),
         ActionKind::DiagnosticsPickerPrev => picker::diagnostics_picker_prev(stoat),
         ActionKind::DiagnosticsPickerSelect => picker::diagnostics_picker_select(stoat),
@@ -540,6 +661,10 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
             UpdateEffect::Redraw
         },
         ActionKind::CodeSearchModeToggle => code_search::code_search_mode_toggle(stoat),
+        ActionKind::OpenRegexPlayground => regex_playground::open_regex_playground(stoat),
+        ActionKind::RegexPlaygroundFocusToggle => {
+            regex_playground::regex_playground_focus_toggle(stoat)
+        },
         ActionKind::FindNextChar => {
             movement::set_pending_find(stoat, movement::FindKind::NextChar, false)
         },
@@ -596,12 +721,15 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::OpenReverseSearchInput => search::open_reverse_search_input(stoat),
         ActionKind::SearchNext => search::search_next(stoat),
         ActionKind::SearchPrev => search::search_prev(stoat),
+        ActionKind::SearchHistoryPrev => search::search_history_prev(stoat),
+        ActionKind::SearchHistoryNext => search::search_history_next(stoat),
         ActionKind::Yank => yank::yank(stoat),
         ActionKind::PasteAfter => yank::paste_after(stoat),
         ActionKind::PasteBefore => yank::paste_before(stoat),
         ActionKind::ReplaceWithYanked => yank::replace_with_yanked(stoat),
         ActionKind::YankToClipboard => yank::yank_to_clipboard(stoat),
         ActionKind::YankMainToClipboard => yank::yank_main_to_clipboard(stoat),
+        ActionKind::CutToClipboard => yank::cut_to_clipboard(stoat),
         ActionKind::PasteClipboardAfter => yank::paste_clipboard_after(stoat),
         ActionKind::PasteClipboardBefore => yank::paste_clipboard_before(stoat),
         ActionKind::SelectRegister => yank::select_register(stoat),
@@ -663,6 +791,15 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::TrimSelections => movement::trim_selections(stoat),
         ActionKind::OpenRun => run::open_run(stoat),
         ActionKind::SpawnClaude => agent::spawn_claude_pane(stoat),
+        ActionKind::AgentPermissionAllow => {
+            agent::answer_agent_permission(stoat, PermissionDecision::AllowOnce)
+        },
+        ActionKind::AgentPermissionAlways => {
+            agent::answer_agent_permission(stoat, PermissionDecision::AllowAlways)
+        },
+        ActionKind::AgentPermissionDeny => {
+            agent::answer_agent_permission(stoat, PermissionDecision::Deny)
+        },
         ActionKind::Terminal => terminal::open_terminal_pane(stoat),
         ActionKind::RunSubmit => run::run_submit(stoat),
         ActionKind::RunInterrupt => run::run_interrupt(stoat),
@@ -744,6 +881,18 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::MarkTrailEnd => crate::code_index::nav::mark_trail_end(stoat),
         ActionKind::TrailNext => crate::code_index::nav::trail_next(stoat),
         ActionKind::TrailPrev => crate::code_index::nav::trail_prev(stoat),
+        ActionKind::EditColorAtCursor => crate::color_swatch::open_picker(stoat),
+        ActionKind::RenameLocal => crate::local_rename::open(stoat),
+        ActionKind::FoldFunctionBodies => {
+            fold::fold_syntax(stoat, stoat_language::SyntaxFoldKind::Functions)
+        },
+        ActionKind::FoldClassBodies => {
+            fold::fold_syntax(stoat, stoat_language::SyntaxFoldKind::Classes)
+        },
+        ActionKind::FoldImports => {
+            fold::fold_syntax(stoat, stoat_language::SyntaxFoldKind::Imports)
+        },
+        ActionKind::UnfoldAll => fold::unfold_all(stoat),
         ActionKind::Hover => lsp::hover(stoat),
         ActionKind::CodeAction => lsp::code_action(stoat),
         ActionKind::RenameSymbol => lsp::rename_symbol(stoat),
@@ -861,6 +1010,32 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
                 .expect("SetTheme action downcast");
             set_theme(stoat, &action.name)
         },
+        ActionKind::RunTask => {
+            let action = action
+                .as_any()
+                .downcast_ref::<RunTask>()
+                .expect("RunTask action downcast");
+            crate::task::run_task(stoat, &action.name)
+        },
+        ActionKind::RerunTask => crate::task::rerun_task(stoat),
+        ActionKind::GotoNextTaskProblem => crate::task::goto_next_problem(stoat),
+        ActionKind::GotoPrevTaskProblem => crate::task::goto_prev_problem(stoat),
+        ActionKind::RunTestAtCursor => crate::test_lens::run_test_at_cursor(stoat),
+        ActionKind::RepeatLastCommand => crate::command_log::repeat_last(stoat),
+        ActionKind::RunFromHistory => {
+            let action = action
+                .as_any()
+                .downcast_ref::<RunFromHistory>()
+                .expect("RunFromHistory action downcast");
+            crate::command_log::rerun_line(stoat, &action.line)
+        },
+        ActionKind::RunUserCommand => {
+            let action = action
+                .as_any()
+                .downcast_ref::<RunUserCommand>()
+                .expect("RunUserCommand action downcast");
+            crate::user_command::run_user_command(stoat, &action.line)
+        },
         ActionKind::NewWorkspace => workspace::new_workspace(stoat),
         ActionKind::CopyWorkspace => workspace::copy_workspace(stoat),
         ActionKind::SwitchWorkspace => {
@@ -932,12 +1107,25 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
         ActionKind::PaletteScopeToggle => palette::palette_scope_toggle(stoat),
         ActionKind::PaletteComplete => palette::palette_complete(stoat),
         ActionKind::OpenLastPicker => open_last_picker(stoat),
-    };
-    end_action_group(stoat, group_buffer);
-    if matches!(effect, UpdateEffect::Redraw) && is_picker_open_kind(action.kind()) {
-        stoat.last_picker_action = Some(action.def().name());
     }
-    effect
+}
+
+/// The most times [`dispatch`] repeats a [`repeats_with_count`] action for one
+/// count prefix.
+const MAX_REPEAT_COUNT: u32 = 1000;
+
+/// Actions whose handlers ignore a count prefix but read naturally repeated,
+/// so [`dispatch`] runs them once per count. Motions, paste, indent and
+/// undo/redo read the count themselves and are absent here.
+fn repeats_with_count(kind: ActionKind) -> bool {
+    matches!(
+        kind,
+        ActionKind::DeleteSelection
+            | ActionKind::DeleteSelectionNoYank
+            | ActionKind::JoinSelections
+            | ActionKind::JoinSelectionsSpace
+            | ActionKind::DuplicateSelection
+    )
 }
 
 /// Actions that drive the undo history themselves, so [`dispatch`] must not wrap
@@ -946,7 +1134,10 @@ pub fn dispatch(stoat: &mut Stoat, action: &dyn Action) -> UpdateEffect {
 fn manages_own_undo_group(kind: ActionKind) -> bool {
     matches!(
         kind,
-        ActionKind::Undo | ActionKind::Redo | ActionKind::CommitUndoCheckpoint
+        ActionKind::Undo
+            | ActionKind::Redo
+            | ActionKind::CommitUndoCheckpoint
+            | ActionKind::UndoTreeSelect
     )
 }
 
@@ -1012,6 +1203,7 @@ fn is_picker_open_kind(kind: ActionKind) -> bool {
             | ActionKind::OpenWorkspaceFileFinder
             | ActionKind::OpenCommandPalette
             | ActionKind::OpenJumplistPicker
+            | ActionKind::OpenUndoTree
             | ActionKind::OpenCodeSearch
             | ActionKind::OpenDiagnosticsPicker
             | ActionKind::OpenWorkspaceDiagnosticsPicker
@@ -1156,6 +1348,23 @@ fn open_jumplist_picker(stoat: &mut Stoat) -> UpdateEffect {
     UpdateEffect::Redraw
 }
 
+/// Drive [`ActionKind::OpenUndoTree`]. Snapshots the focused buffer's undo
+/// tree into [`Stoat::undo_tree_picker`]. No-op when focus is not on an editor.
+fn open_undo_tree(stoat: &mut Stoat) -> UpdateEffect {
+    let Some((_, buffer_id)) = stoat.focused_editor_ids() else {
+        return UpdateEffect::None;
+    };
+    let Some(buffer) = stoat.active_workspace().buffers.get(buffer_id) else {
+        return UpdateEffect::None;
+    };
+    let picker = {
+        let guard = buffer.read().expect("buffer poisoned");
+        crate::undo_tree_picker::UndoTreePicker::new(guard.undo_tree(), guard.undo_floor())
+    };
+    stoat.undo_tree_picker = Some(picker);
+    UpdateEffect::Redraw
+}
+
 /// Drive [`ActionKind::OpenWorkspaceDiagnosticsPicker`].
 /// Snapshots every `(path, diagnostic)` pair currently in
 /// `Stoat::diagnostics` and stores the workspace-scope picker
@@ -1282,7 +1491,7 @@ fn show_version(stoat: &mut Stoat) -> UpdateEffect {
         anchor: Anchor::BottomRight,
         state: BadgeState::Complete,
         label,
-        detail: None,
+        detail: stoat_log::log_file().map(|path| format!("log: {}", path.display())),
     });
     UpdateEffect::Redraw
 }
@@ -1297,6 +1506,7 @@ pub(crate) fn open_help(stoat: &mut Stoat) {
     let context = help_context(stoat);
     let bindings = build_help_bindings(&stoat.keymap, &context);
     let executor = stoat.executor.clone();
+    crate::overlay::push(stoat, crate::overlay::Overlay::Help);
     let ws = stoat.active_workspace_mut();
     stoat.help = Some(Help::new(&mode, active, bindings, context, ws, executor));
 }
@@ -1317,16 +1527,18 @@ fn help_context(stoat: &Stoat) -> SnapshotState {
     SnapshotState(fields)
 }
 
-/// Close the help modal, disposing its scratch editor and restoring the
-/// mode that was active before the modal opened. No-op when help is not
-/// open. Shared between `CancelPromptInput`, Ctrl-C cleanup, and the help
-/// `HelpOutcome::Close`/`HelpOutcome::Dispatch` paths.
+/// Close the help modal, disposing its scratch editor and popping it off the
+/// [`crate::overlay`] stack, which restores the mode that was active before
+/// the modal opened. No-op when help is not open. Shared between `CloseHelp`,
+/// Ctrl-C cleanup, and the help `HelpOutcome::Close`/`HelpOutcome::Dispatch`
+/// paths.
 pub(crate) fn close_help(stoat: &mut Stoat) {
     let Some(help) = stoat.help.take() else {
         return;
     };
     let active_idx = stoat.active_workspace;
     help.dispose(&mut stoat.workspaces[active_idx]);
+    crate::overlay::pop(stoat, crate::overlay::Overlay::Help);
 }
 
 /// Read `path` through the supplied [`FsHost`] as a UTF-8 string.
@@ -2599,6 +2811,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cursor_line_and_relative_number_toggles_override_the_settings() {
+        use stoat_config::LineNumbers;
+        let mut h = Stoat::test();
+        assert!(!h.stoat.cursor_line_enabled(), "off by default");
+        dispatch(&mut h.stoat, &stoat_action::ToggleCursorLine);
+        assert!(h.stoat.cursor_line_enabled());
+        h.stoat.settings.editor_cursor_line = Some(false);
+        assert!(
+            h.stoat.cursor_line_enabled(),
+            "the session override beats the setting"
+        );
+
+        assert_eq!(h.stoat.line_numbers(), LineNumbers::Relative);
+        dispatch(&mut h.stoat, &stoat_action::ToggleRelativeNumbers);
+        assert_eq!(h.stoat.line_numbers(), LineNumbers::Absolute);
+        dispatch(&mut h.stoat, &stoat_action::ToggleRelativeNumbers);
+        assert_eq!(h.stoat.line_numbers(), LineNumbers::Relative);
+
+        h.stoat.line_numbers_override = None;
+        h.stoat.settings.editor_line_numbers = Some(LineNumbers::Off);
+        dispatch(&mut h.stoat, &stoat_action::ToggleRelativeNumbers);
+        assert_eq!(
+            h.stoat.line_numbers(),
+            LineNumbers::Relative,
+            "a hidden gutter comes back relative"
+        );
+    }
+
     #[test]
     fn single_minimap_mode_resolves_and_survives_toggle() {
         use stoat_config::MinimapMode;
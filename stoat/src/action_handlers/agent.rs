@@ -1,13 +1,17 @@
 use crate::{
+    agent_permission::{AgentPermissionRequest, PermissionDecision},
     app::{Stoat, UpdateEffect},
     host::terminal::TerminalSession,
     pane::View,
     run::{spawn_claude, spawn_term_reader},
     term_screen::TermScreen,
     term_session::TermSession,
+    workspace::WorkspaceUid,
 };
 use futures::FutureExt;
+use serde_json::Value;
 use std::sync::Arc;
+use tokio::sync::oneshot;
 
 /// Dimensions the owned Claude PTY is opened at. The render/resize sibling
 /// later fits both the PTY and the emulator to the focused pane.
@@ -51,3 +55,50 @@ pub(super) fn spawn_claude_pane(stoat: &mut Stoat) -> UpdateEffect {
     spawn_term_reader(&executor, session, agent_id, pty_tx);
     UpdateEffect::Redraw
 }
+
+/// Queue a tool call the agent of session `uid` asked permission for.
+///
+/// A tool the owning workspace already allows always is answered at once.
+/// Otherwise the request joins [`Stoat::agent_permissions`] and shows as the
+/// permission modal once those ahead of it are answered. A request from a
+/// session with no live workspace is dropped unanswered, which the hook reads
+/// as a denial.
+pub(crate) fn request_agent_permission(
+    stoat: &mut Stoat,
+    uid: WorkspaceUid,
+    tool: String,
+    detail: Option<String>,
+    reply: oneshot::Sender<Value>,
+) -> UpdateEffect {
+    let Some(ws) = stoat.workspaces.values().find(|ws| ws.uid == uid) else {
+        return UpdateEffect::None;
+    };
+    let request = AgentPermissionRequest::new(uid, tool, detail, reply);
+    if ws.agent_allowed_tools.contains(&request.tool) {
+        request.answer(PermissionDecision::AllowAlways);
+        return UpdateEffect::None;
+    }
+    stoat.agent_permissions.push_back(request);
+    UpdateEffect::Redraw
+}
+
+/// Answer the permission modal's request with `decision`. An always-allow
+/// records the tool on the requesting session's workspace first.
+pub(super) fn answer_agent_permission(
+    stoat: &mut Stoat,
+    decision: PermissionDecision,
+) -> UpdateEffect {
+    let Some(request) = stoat.agent_permissions.pop_front() else {
+        return UpdateEffect::None;
+    };
+    if decision == PermissionDecision::AllowAlways
+        && let Some(ws) = stoat
+            .workspaces
+            .values_mut()
+            .find(|ws| ws.uid == request.uid)
+    {
+        ws.agent_allowed_tools.insert(request.tool.clone());
+    }
+    request.answer(decision);
+    UpdateEffect::Redraw
+}
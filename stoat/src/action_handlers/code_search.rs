@@ -32,14 +32,14 @@ pub(crate) fn open_code_search(stoat: &mut Stoat) -> UpdateEffect {
     let target_lang = focused_buffer_language(stoat);
     let executor = stoat.executor.clone();
     let ws = stoat.active_workspace_mut();
-    let finder = CodeSearchFinder::new(ws, executor, target_lang);
+    let finder = CodeSearchFinder::new(ws, executor, target_lang, "");
     stoat.code_search = Some(finder);
     UpdateEffect::Redraw
 }
 
 /// The language of the focused editor's buffer, or `None` when focus is not on a
 /// path-bound editor. Resolves the AST-mode target language at finder open.
-fn focused_buffer_language(stoat: &Stoat) -> Option<Arc<stoat_language::Language>> {
+pub(crate) fn focused_buffer_language(stoat: &Stoat) -> Option<Arc<stoat_language::Language>> {
     let ws = stoat.active_workspace();
     let View::Editor(editor_id) = &ws.panes.pane(ws.panes.focus()).view else {
         return None;
@@ -1,12 +1,15 @@
 use crate::{
-    action_handlers::read_string_via_host,
+    action_handlers::{pane::EditorDisposal, read_string_via_host},
     app::{Stoat, UpdateEffect},
     badge::{Anchor, Badge, BadgeSource, BadgeState},
     buffer::{BufferId, SharedBuffer},
     buffer_registry::AutoReloadMode,
+    display_map::highlights::SemanticTokenHighlight,
     editor_state::{EditorId, EditorState},
+    hex_view::{self, HexView},
     host::LanguageServerFeature,
-    pane::{PaneId, View},
+    pane::{Axis, PaneId, View},
+    toast::ToastLevel,
 };
 use lsp_types::{
     DidCloseTextDocumentParams, DidSaveTextDocumentParams, DocumentFormattingParams,
@@ -23,8 +26,9 @@ use std::{
     task::{Context, Poll},
     time::{Duration, SystemTime},
 };
+use stoat_action::ActionKind;
 use stoat_scheduler::{Executor, Task};
-use stoat_text::{Bias, SelectionGoal};
+use stoat_text::{Bias, LineEnding, SelectionGoal};
 
 /// Write the focused buffer to its backing file via
 /// [`crate::host::FsHost::write_atomic`], clear the dirty flag, and notify the
@@ -156,6 +160,9 @@ pub(crate) struct FormatOnSaveOutcome {
     path: PathBuf,
     uri: Uri,
     edits: Option<Vec<TextEdit>>,
+    /// Why the formatter failed, when it answered with an error rather than
+    /// edits or silence. The buffer is still written unformatted.
+    error: Option<String>,
 }
 
 /// Save-time budget for `format_on_save`. A formatting response slower than this
@@ -208,15 +215,19 @@ fn arm_format_on_save(
     let task = stoat.executor.spawn(async move {
         let format = std::pin::pin!(host.formatting(params));
         let timer = std::pin::pin!(executor.timer(FORMAT_ON_SAVE_BUDGET));
-        let edits = match futures::future::select(format, timer).await {
-            futures::future::Either::Left((Ok(Some(edits)), _)) if !edits.is_empty() => Some(edits),
-            _ => None,
+        let (edits, error) = match futures::future::select(format, timer).await {
+            futures::future::Either::Left((Ok(Some(edits)), _)) if !edits.is_empty() => {
+                (Some(edits), None)
+            },
+            futures::future::Either::Left((Err(err), _)) => (None, Some(err.to_string())),
+            _ => (None, None),
         };
         FormatOnSaveOutcome {
             buffer_id,
             path,
             uri,
             edits,
+            error,
         }
     });
     stoat.pending_format_on_save = Some(task);
@@ -248,8 +259,13 @@ pub(crate) fn pump_format_on_save(stoat: &mut Stoat) -> bool {
                         ?err,
                         "format-on-save edit failed to apply",
                     );
+                    stoat.notify(ToastLevel::Warning, format!("format failed: {err}"));
                 }
             }
+            if let Some(err) = outcome.error {
+                tracing::warn!(target: "stoat::lsp", %err, "format-on-save request failed");
+                stoat.notify(ToastLevel::Warning, format!("format failed: {err}"));
+            }
             let wrote = write_buffer_to_disk(stoat, outcome.buffer_id, &outcome.path);
             // A `:wq` that deferred behind this write quits once it lands, but
             // only if it succeeded, so a failed deferred write leaves the buffer
@@ -279,12 +295,13 @@ fn write_buffer_to_disk(stoat: &mut Stoat, buffer_id: BufferId, path: &Path) ->
     let Some(buffer) = stoat.active_workspace().buffers.get(buffer_id) else {
         return false;
     };
-    let text = {
+    let (text, line_ending) = {
         let guard = buffer.read().expect("buffer poisoned");
-        guard.rope().to_string()
+        (guard.rope().to_string(), guard.line_ending())
     };
+    let disk_text = line_ending.apply(&text);
 
-    if let Err(err) = stoat.fs_host.write_atomic(path, text.as_bytes()) {
+    if let Err(err) = stoat.fs_host.write_atomic(path, disk_text.as_bytes()) {
         tracing::warn!(target: "stoat::file", ?err, ?path, "buffer save failed");
         stoat.set_status(format!("save failed: {err}"));
         return false;
@@ -305,7 +322,9 @@ fn write_buffer_to_disk(stoat: &mut Stoat, buffer_id: BufferId, path: &Path) ->
             .buffers
             .set_disk_mtime(buffer_id, mtime);
     }
-    stoat.persist_saved_shard(buffer_id, path, &text);
+    stoat.persist_saved_shard(buffer_id, path, &disk_text);
+    let shown = crate::paths::display_relative(path, &stoat.active_workspace().git_root);
+    stoat.notify(ToastLevel::Success, format!("saved {shown}"));
     maybe_apply_config_save(
         stoat,
         path,
@@ -348,8 +367,9 @@ fn write_buffer_to_disk(stoat: &mut Stoat, buffer_id: BufferId, path: &Path) ->
 /// rather than resolved here so the decision stays independent of the
 /// environment.
 ///
-/// stoat re-applies its own config in-process. The hosting terminal's is only
-/// reported to it, since the terminal owns that file and re-reads it itself.
+/// stoat re-applies its own config, and the active root's project config, in
+/// process. The hosting terminal's is only reported to it, since the terminal
+/// owns that file and re-reads it itself.
 ///
 /// Does nothing when `config.auto_reload` is off, or when `path` is any other
 /// file.
@@ -365,6 +385,8 @@ fn maybe_apply_config_save(
     }
     if stoat_config == Some(path) {
         stoat.reload_user_config(text);
+    } else if crate::paths::project_config_path(&stoat.active_workspace().git_root) == path {
+        crate::project_config::reload(stoat, path, text);
     } else if stoatty_config == Some(path) {
         stoat.emit_config_reload();
         stoat.set_status("stoatty config reloaded");
@@ -463,9 +485,17 @@ pub(crate) fn pump_auto_reload(stoat: &mut Stoat) {
         if stoat.active_workspace().buffers.disk_mtime(id) == Some(mtime) {
             continue;
         }
-        let Ok(new) = read_string_via_host(&*stoat.fs_host, &path) else {
+        let Ok(disk) = read_string_via_host(&*stoat.fs_host, &path) else {
             continue;
         };
+        // Adopt the file's line ending first, so the edits below are
+        // normalized for it and a change of ending alone is no change at all.
+        let line_ending = LineEnding::detect(&disk);
+        buffer
+            .write()
+            .expect("buffer poisoned")
+            .adopt_line_ending(line_ending);
+        let new = line_ending.normalize(&disk);
 
         let (old_len, old_last_row, common) = {
             let guard = buffer.read().expect("buffer poisoned");
@@ -517,7 +547,8 @@ pub(crate) fn pump_auto_reload(stoat: &mut Stoat) {
             if appended {
                 guard.edit(old_len..old_len, &new[old_len..]);
             } else {
-                guard.edit(0..old_len, &new);
+                // Rewrite only what changed, so cursors past it stay on their text.
+                guard.set_text(&new);
             }
             guard.mark_clean();
         }
@@ -554,16 +585,15 @@ pub(crate) fn pump_auto_reload(stoat: &mut Stoat) {
 
 /// Open this session's log file and follow it as new lines are written.
 ///
-/// Resolves `stoat_log::log_dir()/stoat-<pid>.log` and delegates to
-/// [`open_log_buffer`]. Reports via [`Stoat::pending_message`] when the log
-/// directory cannot be resolved.
+/// Reads the path from [`stoat_log::log_file`] and delegates to
+/// [`open_log_buffer`]. Reports via [`Stoat::pending_message`] when the
+/// session has no log file, as when it logs to stderr.
 pub(super) fn open_logs(stoat: &mut Stoat) -> UpdateEffect {
-    let Ok(dir) = stoat_log::log_dir() else {
-        stoat.set_status("could not resolve the log directory");
+    let Some(path) = stoat_log::log_file() else {
+        stoat.set_status("no log file for this session; started with --log-stderr?");
         return UpdateEffect::Redraw;
     };
-    let path = dir.join(format!("stoat-{}.log", std::process::id()));
-    open_log_buffer(stoat, &path)
+    open_log_buffer(stoat, path)
 }
 
 /// Open `path` as an auto-reloading buffer tailing its end, or report when the
@@ -649,6 +679,32 @@ pub(super) fn set_buffer_auto_reload(stoat: &mut Stoat, state: &str) -> UpdateEf
     UpdateEffect::Redraw
 }
 
+/// Convert the focused buffer to `line_ending` for its next save, backing
+/// [`stoat_action::SetLineEndingLf`] and [`stoat_action::SetLineEndingCrlf`].
+///
+/// Refuses a read-only buffer, whose content is not the user's to save, and
+/// reports a buffer already on `line_ending` rather than silently no-oping.
+pub(super) fn set_line_ending(stoat: &mut Stoat, line_ending: LineEnding) -> UpdateEffect {
+    let Some(id) = super::focused_editor_mut(stoat).map(|e| e.buffer_id) else {
+        return UpdateEffect::None;
+    };
+    let Some(buffer) = stoat.active_workspace().buffers.get(id) else {
+        return UpdateEffect::None;
+    };
+    let status = {
+        let mut guard = buffer.write().expect("buffer poisoned");
+        if guard.read_only() {
+            "buffer is read-only"
+        } else if guard.set_line_ending(line_ending) {
+            "line endings converted"
+        } else {
+            "line endings unchanged"
+        }
+    };
+    stoat.set_status(format!("{status}: {}", line_ending.label()));
+    UpdateEffect::Redraw
+}
+
 /// Set whether saving a config file re-applies it, backing
 /// `:auto-reload-config`.
 ///
@@ -754,6 +810,13 @@ pub(super) fn close_buffer(stoat: &mut Stoat) -> UpdateEffect {
         return UpdateEffect::None;
     };
     let buffer_id = editor.buffer_id;
+    close_buffer_id(stoat, buffer_id)
+}
+
+/// Close `buffer_id` with [`close_buffer`] semantics, whether or not it is
+/// focused. Returns [`UpdateEffect::None`] when the buffer is unknown or
+/// dirty and was left open.
+fn close_buffer_id(stoat: &mut Stoat, buffer_id: BufferId) -> UpdateEffect {
     let buffer = match stoat.active_workspace().buffers.get(buffer_id) {
         Some(b) => b,
         None => return UpdateEffect::None,
@@ -778,6 +841,7 @@ pub(super) fn close_buffer(stoat: &mut Stoat) -> UpdateEffect {
         }
     }
 
+    cache_tokens_on_close(stoat, buffer_id);
     let path = stoat.active_workspace_mut().buffers.remove(buffer_id);
 
     // Purge the closed buffer from every pane's jumplist so a later walk can
@@ -833,6 +897,86 @@ pub(super) fn close_buffer(stoat: &mut Stoat) -> UpdateEffect {
     UpdateEffect::Redraw
 }
 
+/// Close each file buffer in `ids`, returning how many stayed open because
+/// they were dirty.
+fn close_buffers(stoat: &mut Stoat, ids: Vec<BufferId>) -> usize {
+    ids.into_iter()
+        .filter(|id| close_buffer_id(stoat, *id) == UpdateEffect::None)
+        .count()
+}
+
+fn report_kept_dirty(stoat: &mut Stoat, kept: usize) {
+    if kept > 0 {
+        let noun = if kept == 1 { "buffer" } else { "buffers" };
+        stoat.set_status(format!("kept {kept} unsaved {noun} open"));
+    }
+}
+
+pub(super) fn toggle_pin_buffer(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(editor) = super::focused_editor_mut(stoat) else {
+        return UpdateEffect::None;
+    };
+    let buffer_id = editor.buffer_id;
+    let pinned = stoat
+        .active_workspace_mut()
+        .buffers
+        .toggle_pinned(buffer_id);
+    stoat.set_status(if pinned { "pinned" } else { "unpinned" });
+    UpdateEffect::Redraw
+}
+
+pub(super) fn close_other_buffers(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(editor) = super::focused_editor_mut(stoat) else {
+        return UpdateEffect::None;
+    };
+    let keep = [editor.buffer_id];
+    let ids = stoat.active_workspace().buffers.unpinned_except(&keep);
+    let kept = close_buffers(stoat, ids);
+    report_kept_dirty(stoat, kept);
+    UpdateEffect::Redraw
+}
+
+pub(super) fn add_buffer_to_group(stoat: &mut Stoat, name: &str) -> UpdateEffect {
+    let Some(editor) = super::focused_editor_mut(stoat) else {
+        return UpdateEffect::None;
+    };
+    let buffer_id = editor.buffer_id;
+    let buffers = &mut stoat.active_workspace_mut().buffers;
+    let Some(path) = buffers.path_for(buffer_id).map(Path::to_path_buf) else {
+        stoat.set_status("buffer has no file to group");
+        return UpdateEffect::Redraw;
+    };
+    if buffers.add_to_group(name, &path) {
+        stoat.set_status(format!("added to group {name}"));
+    } else {
+        stoat.set_status(format!("already in group {name}"));
+    }
+    UpdateEffect::Redraw
+}
+
+/// Open every member of group `name`, leaving its first member focused, then
+/// close the clean, unpinned file buffers outside the group.
+pub(super) fn switch_buffer_group(stoat: &mut Stoat, name: &str) -> UpdateEffect {
+    let Some(members) = stoat
+        .active_workspace()
+        .buffers
+        .group(name)
+        .map(<[_]>::to_vec)
+    else {
+        stoat.set_status(format!("no buffer group named {name}"));
+        return UpdateEffect::Redraw;
+    };
+    let keep: Vec<BufferId> = members
+        .iter()
+        .rev()
+        .filter_map(|path| open_file(stoat, path))
+        .collect();
+    let ids = stoat.active_workspace().buffers.unpinned_except(&keep);
+    let kept = close_buffers(stoat, ids);
+    report_kept_dirty(stoat, kept);
+    UpdateEffect::Redraw
+}
+
 pub(crate) fn open_file(stoat: &mut Stoat, path: &Path) -> Option<BufferId> {
     let target = stoat.active_workspace().panes.focus();
     open_file_in_pane(stoat, target, path)
@@ -841,7 +985,8 @@ pub(crate) fn open_file(stoat: &mut Stoat, path: &Path) -> Option<BufferId> {
 /// Open a user config in the focused pane.
 ///
 /// [`None`] and `"stoat"` open stoat's own config, `"stoatty"` opens the
-/// hosting terminal's. Any other target, or a path that does not resolve, only
+/// hosting terminal's, and `"project"` opens the active root's
+/// `.stoat/config`. Any other target, or a path that does not resolve, only
 /// sets a status message. Delegates the seed-and-open to [`open_config_at`].
 pub(crate) fn open_config(stoat: &mut Stoat, target: Option<&str>) {
     let (path, seed) = match target {
@@ -850,8 +995,14 @@ pub(crate) fn open_config(stoat: &mut Stoat, target: Option<&str>) {
             crate::paths::stoatty_config_path(),
             crate::app::DEFAULT_STOATTY_CONFIG,
         ),
+        Some("project") => (
+            Some(crate::paths::project_config_path(
+                &stoat.active_workspace().git_root,
+            )),
+            crate::project_config::SEED,
+        ),
         Some(_) => {
-            stoat.set_status("open-config: expected stoat or stoatty");
+            stoat.set_status("open-config: expected stoat, stoatty, or project");
             return;
         },
     };
@@ -876,6 +1027,26 @@ pub(crate) fn open_config_at(stoat: &mut Stoat, path: &Path, seed: &str) {
     open_file(stoat, path);
 }
 
+/// Show `text` in a read-only scratch buffer in a new vertical split, for
+/// generated reports such as `:effective-config` and `:check-keymap`.
+pub(crate) fn open_read_only_text(stoat: &mut Stoat, text: &str) -> UpdateEffect {
+    let executor = stoat.executor.clone();
+    let ws = stoat.active_workspace_mut();
+    let pane_id = ws.panes.split(Axis::Vertical);
+    let (buffer_id, buffer) = ws.buffers.new_scratch_unseeded();
+    {
+        let mut guard = buffer.write().expect("buffer poisoned");
+        guard.owner_edit(0..0, text);
+        guard.mark_clean();
+        guard.set_read_only(true);
+    }
+    let editor_id = ws
+        .editors
+        .insert(EditorState::new(buffer_id, buffer, executor));
+    ws.panes.pane_mut(pane_id).view = View::Editor(editor_id);
+    UpdateEffect::Redraw
+}
+
 /// Largest file opened synchronously on the main thread.
 ///
 /// Files over this size read on the blocking pool and install once the read
@@ -894,7 +1065,7 @@ pub(crate) struct PendingFileOpen {
     target: PaneId,
     disk_mtime: Option<SystemTime>,
     _task: Task<()>,
-    result: Arc<Mutex<Option<std::io::Result<String>>>>,
+    result: Arc<Mutex<Option<std::io::Result<Vec<u8>>>>>,
 }
 
 pub(crate) fn open_file_in_pane(
@@ -915,15 +1086,16 @@ pub(crate) fn open_file_in_pane(
         return None;
     }
 
-    let content = match read_string_via_host(&*stoat.fs_host, &absolute) {
-        Ok(c) => c,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => "\n".to_string(),
+    let mut bytes = Vec::new();
+    match stoat.fs_host.read(&absolute, &mut bytes) {
+        Ok(()) => {},
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => bytes = b"\n".to_vec(),
         Err(e) => {
             tracing::error!("failed to read {}: {}", absolute.display(), e);
             return None;
         },
-    };
-    finish_open(stoat, target, &absolute, &content, disk_mtime)
+    }
+    finish_open_bytes(stoat, target, &absolute, bytes, disk_mtime)
 }
 
 /// Read `absolute` on the blocking pool and queue it for install.
@@ -940,14 +1112,15 @@ fn spawn_pending_open(
         return;
     }
 
-    let result: Arc<Mutex<Option<std::io::Result<String>>>> = Arc::new(Mutex::new(None));
+    let result: Arc<Mutex<Option<std::io::Result<Vec<u8>>>>> = Arc::new(Mutex::new(None));
     let task = {
         let result = result.clone();
         let fs_host = stoat.fs_host.clone();
         let redraw = stoat.redraw_notify.clone();
         let path = absolute.clone();
         stoat.executor.spawn_blocking(move || {
-            let content = read_string_via_host(&*fs_host, &path);
+            let mut bytes = Vec::new();
+            let content = fs_host.read(&path, &mut bytes).map(|()| bytes);
             *result.lock().expect("pending open mutex") = Some(content);
             redraw.notify_one();
         })
@@ -995,7 +1168,7 @@ pub(crate) fn install_pending_opens(stoat: &mut Stoat) {
     for pending in ready {
         let content = match pending.result.lock().expect("pending open mutex").take() {
             Some(Ok(c)) => c,
-            Some(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => "\n".to_string(),
+            Some(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => b"\n".to_vec(),
             Some(Err(e)) => {
                 tracing::error!("failed to read {}: {}", pending.path.display(), e);
                 continue;
@@ -1005,11 +1178,11 @@ pub(crate) fn install_pending_opens(stoat: &mut Stoat) {
         if !stoat.active_workspace().panes.contains(pending.target) {
             continue;
         }
-        finish_open(
+        finish_open_bytes(
             stoat,
             pending.target,
             &pending.path,
-            &content,
+            content,
             pending.disk_mtime,
         );
     }
@@ -1022,6 +1195,109 @@ pub(crate) fn install_pending_opens(stoat: &mut Stoat) {
     }
 }
 
+/// Open the bytes read from `absolute` in `target`: as a [`HexView`] when they
+/// look binary, else as a text buffer through [`finish_open`].
+///
+/// A path that already has a buffer opens as that buffer regardless, since it
+/// was either text when first opened or forced to text with `OpenAsText`.
+fn finish_open_bytes(
+    stoat: &mut Stoat,
+    target: PaneId,
+    absolute: &Path,
+    bytes: Vec<u8>,
+    disk_mtime: Option<SystemTime>,
+) -> Option<BufferId> {
+    if hex_view::is_binary(&bytes)
+        && stoat
+            .active_workspace()
+            .buffers
+            .id_for_path(absolute)
+            .is_none()
+    {
+        show_hex_view_in_pane(stoat, target, absolute, bytes.into());
+        stoat.set_status("binary file shown as hex, :open-as-text to edit it");
+        return None;
+    }
+    let content = String::from_utf8_lossy(&bytes);
+    finish_open(stoat, target, absolute, &content, disk_mtime)
+}
+
+/// Show `bytes` as a [`HexView`] of `absolute` in `target`, releasing the
+/// view the pane showed before.
+fn show_hex_view_in_pane(stoat: &mut Stoat, target: PaneId, absolute: &Path, bytes: Arc<[u8]>) {
+    let executor = stoat.executor.clone();
+    let ws = stoat.active_workspace_mut();
+    let hex_id = ws
+        .hex_views
+        .insert(HexView::new(absolute.to_path_buf(), bytes));
+    let old = std::mem::replace(&mut ws.panes.pane_mut(target).view, View::Hex(hex_id));
+    super::pane::dispose_view(ws, &executor, old, EditorDisposal::GcIfUnreferenced);
+}
+
+/// Replace the focused [`HexView`] with its file opened as text, decoding
+/// invalid UTF-8 lossily. Backs [`stoat_action::OpenAsText`]; a no-op when the
+/// focused pane is not a hex view.
+pub(super) fn open_as_text(stoat: &mut Stoat) -> UpdateEffect {
+    let ws = stoat.active_workspace();
+    let target = ws.panes.focus();
+    let View::Hex(hex_id) = ws.panes.pane(target).view else {
+        return UpdateEffect::None;
+    };
+    let Some(hex) = ws.hex_views.get(hex_id) else {
+        return UpdateEffect::None;
+    };
+    let path = hex.path().to_path_buf();
+    let content = String::from_utf8_lossy(hex.bytes()).into_owned();
+    let disk_mtime = stoat
+        .fs_host
+        .metadata(&path)
+        .ok()
+        .flatten()
+        .map(|m| m.modified);
+    finish_open(stoat, target, &path, &content, disk_mtime);
+    stoat.active_workspace_mut().hex_views.remove(hex_id);
+    UpdateEffect::Redraw
+}
+
+/// Scroll the focused [`HexView`] for the editor motions that read naturally
+/// on a dump: line and page moves, and jumps to either end. Returns `None`
+/// when the focused pane is not a hex view or `kind` is not one of those
+/// motions, so the action runs its usual handler.
+pub(super) fn scroll_hex_view(stoat: &mut Stoat, kind: ActionKind) -> Option<UpdateEffect> {
+    let ws = stoat.active_workspace();
+    if !matches!(ws.focus, crate::pane::FocusTarget::SplitPane) {
+        return None;
+    }
+    let pane = ws.panes.pane(ws.panes.focus());
+    let View::Hex(hex_id) = pane.view else {
+        return None;
+    };
+    let page = crate::render::layout::split_pane_status(pane.area)
+        .0
+        .height
+        .max(1) as isize;
+    let rows = match kind {
+        ActionKind::MoveDown | ActionKind::ScrollDown => 1,
+        ActionKind::MoveUp | ActionKind::ScrollUp => -1,
+        ActionKind::PageDown => page,
+        ActionKind::PageUp => -page,
+        ActionKind::HalfPageDown => (page + 1) / 2,
+        ActionKind::HalfPageUp => -(page + 1) / 2,
+        ActionKind::GotoLastLine => isize::MAX,
+        ActionKind::GotoFileStart => isize::MIN,
+        _ => return None,
+    };
+    let count = stoat.take_pending_count().unwrap_or(1) as isize;
+    let hex = stoat.active_workspace_mut().hex_views.get_mut(hex_id)?;
+    Some(
+        if hex.scroll_by(rows.saturating_mul(count), page as usize) {
+            UpdateEffect::Redraw
+        } else {
+            UpdateEffect::None
+        },
+    )
+}
+
 /// Open `content` as the buffer for `absolute` and show it in `target`.
 ///
 /// The shared tail of the sync and background open paths. It registers the
@@ -1034,7 +1310,7 @@ fn finish_open(
     content: &str,
     disk_mtime: Option<SystemTime>,
 ) -> Option<BufferId> {
-    let lang = stoat.language_registry.for_path(absolute);
+    let registry = stoat.language_registry.clone();
     let executor = stoat.executor.clone();
 
     let (buffer_id, buffer) = {
@@ -1043,20 +1319,102 @@ fn finish_open(
         if let Some(mtime) = disk_mtime {
             ws.buffers.set_disk_mtime(buffer_id, mtime);
         }
-        if let Some(lang) = lang
-            && ws.buffers.language_for(buffer_id).is_none()
+        if ws.buffers.language_for(buffer_id).is_none()
+            && let Some(lang) =
+                registry.detect(absolute, buffer.read().expect("buffer poisoned").rope())
         {
             ws.buffers.set_language(buffer_id, lang);
         }
         (buffer_id, buffer)
     };
 
+    seed_cached_tokens(stoat, buffer_id, absolute);
     super::lsp::notify_buffer_opened(stoat, buffer_id, absolute, content);
 
     super::jump::record_pane_switch(stoat, target, buffer_id);
     show_buffer_in_pane(stoat, target, buffer_id, buffer, executor)
 }
 
+/// Write the tree-sitter tokens of the path-backed buffer `buffer_id` to the
+/// [`crate::token_cache`] so a later open of the same text highlights at once.
+/// Skipped when the tokens lag the buffer's current version, since they would
+/// be filed under a hash of text they were not parsed from.
+fn cache_tokens_on_close(stoat: &Stoat, buffer_id: BufferId) {
+    let Some(cache_dir) = &stoat.token_cache_dir else {
+        return;
+    };
+    let buffers = &stoat.active_workspace().buffers;
+    let (Some(path), Some(buffer), Some((tokens, interner))) = (
+        buffers.path_for(buffer_id),
+        buffers.get(buffer_id),
+        buffers.tokens_for(buffer_id),
+    ) else {
+        return;
+    };
+    let guard = buffer.read().expect("buffer poisoned");
+    if buffers.syntax_version(buffer_id) != Some(guard.snapshot.version) {
+        return;
+    }
+    let ranges: Vec<_> = tokens
+        .iter()
+        .map(|token| {
+            let start = guard.snapshot.resolve_anchor(&token.range.start);
+            let end = guard.snapshot.resolve_anchor(&token.range.end);
+            (start..end.max(start), token.style)
+        })
+        .collect();
+    if let Err(err) = crate::token_cache::write_tokens(
+        cache_dir,
+        path,
+        guard.rope(),
+        &ranges,
+        &interner,
+        stoat.fs_host.as_ref(),
+    ) {
+        tracing::debug!(target: "stoat::file", ?err, path = %path.display(), "token cache write failed");
+    }
+}
+
+/// Install cached tokens for a freshly opened `buffer_id` whose text matches
+/// the [`crate::token_cache`] entry for `path`. A buffer that already holds
+/// tokens, as one reopened while still registered does, is left alone.
+fn seed_cached_tokens(stoat: &mut Stoat, buffer_id: BufferId, path: &Path) {
+    let Some(cache_dir) = stoat.token_cache_dir.clone() else {
+        return;
+    };
+    let interner = stoat.syntax_styles.interner.clone();
+    let fs = stoat.fs_host.clone();
+    let ws = stoat.active_workspace_mut();
+    if ws.buffers.tokens_for(buffer_id).is_some() {
+        return;
+    }
+    let Some(buffer) = ws.buffers.get(buffer_id) else {
+        return;
+    };
+    let tokens = {
+        let guard = buffer.read().expect("buffer poisoned");
+        let Some(cached) =
+            crate::token_cache::read_tokens(&cache_dir, path, guard.rope(), &interner, fs.as_ref())
+        else {
+            return;
+        };
+        let snapshot = &guard.snapshot;
+        let starts: Vec<usize> = cached.iter().map(|(range, _)| range.start).collect();
+        let ends: Vec<usize> = cached.iter().map(|(range, _)| range.end).collect();
+        let starts = snapshot.anchors_at_batch(&starts, Bias::Right);
+        let ends = snapshot.anchors_at_batch(&ends, Bias::Left);
+        cached
+            .into_iter()
+            .zip(starts.into_iter().zip(ends))
+            .map(|((_, style), (start, end))| SemanticTokenHighlight {
+                range: start..end,
+                style,
+            })
+            .collect::<Arc<[_]>>()
+    };
+    ws.buffers.store_tokens(buffer_id, tokens, interner);
+}
+
 /// Show `buffer_id` in `target` by swapping the pane's editor to a fresh
 /// [`EditorState`] over the buffer, garbage-collecting the outgoing one.
 ///
@@ -1114,7 +1472,8 @@ mod tests {
         sync::atomic::Ordering,
     };
     use stoat_action::{
-        CloseBuffer, ForceSaveBuffer, MoveDown, OpenBuffer, OpenFile, SaveBuffer, WriteQuit,
+        AddBufferToGroup, CloseBuffer, CloseOtherBuffers, ForceSaveBuffer, MoveDown, OpenBuffer,
+        OpenFile, SaveBuffer, SwitchBufferGroup, TogglePinBuffer, WriteQuit,
     };
 
     /// Open `name` (seeded with `seed`) under `root`, dirty the buffer with a
@@ -1159,6 +1518,70 @@ mod tests {
         guard.dirty
     }
 
+    fn focused_hex(stoat: &Stoat) -> Option<&crate::hex_view::HexView> {
+        let ws = stoat.active_workspace();
+        match ws.panes.pane(ws.panes.focus()).view {
+            crate::pane::View::Hex(id) => ws.hex_views.get(id),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn extensionless_script_detects_language_from_shebang() {
+        let mut h = TestHarness::with_size(80, 8);
+        let path = PathBuf::from("/bin/build");
+        h.fake_fs()
+            .insert_file(&path, b"#!/usr/bin/env rust-script\nfn main() {}\n");
+        dispatch(&mut h.stoat, &OpenFile { path: path.clone() });
+        h.settle();
+
+        let ws = h.stoat.active_workspace();
+        let id = ws.buffers.id_for_path(&path).expect("script opened");
+        assert_eq!(ws.buffers.language_for(id).map(|l| l.name), Some("rust"));
+    }
+
+    #[test]
+    fn binary_file_opens_as_hex_until_forced_to_text() {
+        let mut h = TestHarness::with_size(80, 8);
+        let path = PathBuf::from("/bin/blob");
+        let bytes: Vec<u8> = (0..=255u8).collect();
+        h.fake_fs().insert_file(&path, &bytes);
+        dispatch(&mut h.stoat, &OpenFile { path: path.clone() });
+        h.settle();
+
+        let hex = focused_hex(&h.stoat).expect("binary opens as a hex view");
+        assert_eq!(hex.row_count(), 16);
+        assert!(h
+            .stoat
+            .active_workspace()
+            .buffers
+            .id_for_path(&path)
+            .is_none());
+        let screen = h.render_composited();
+        let rows: Vec<String> = (0..8)
+            .map(|y| (0..80).map(|x| screen[(x, y)].symbol()).collect())
+            .collect();
+        assert!(
+            rows.iter().any(|row| row.starts_with("00000000  00 01 02")),
+            "{rows:#?}"
+        );
+
+        h.type_keys("j");
+        assert_eq!(focused_hex(&h.stoat).expect("hex").scroll_row, 1);
+
+        dispatch(&mut h.stoat, &stoat_action::OpenAsText);
+        assert!(focused_hex(&h.stoat).is_none());
+        assert!(h.stoat.active_workspace().hex_views.is_empty());
+        let text = crate::action_handlers::focused_editor_mut(&mut h.stoat)
+            .expect("text editor")
+            .display_map
+            .snapshot()
+            .buffer_snapshot()
+            .rope()
+            .to_string();
+        assert!(text.contains("ABC") && text.contains('\u{fffd}'));
+    }
+
     #[test]
     fn large_file_opens_on_the_background_pool() {
         use crate::badge::BadgeSource;
@@ -1465,6 +1888,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pump_auto_reload_keeps_a_cursor_past_the_change_on_its_text() {
+        let mut h = Stoat::test();
+        let root = PathBuf::from("/auto-reload-minimal");
+        let (path, id) = open_auto_reload(&mut h, &root, "log.txt", b"a\nb\nc\nd\n");
+        for _ in 0..3 {
+            dispatch(&mut h.stoat, &MoveDown);
+        }
+        assert_eq!(focused_cursor_row(&mut h), 3, "cursor starts on d");
+
+        h.fake_fs().insert_file(&path, b"a\nB\nB2\nc\nd\n");
+        arm_and_pump(&mut h);
+
+        assert_eq!(buffer_text(&h, id), "a\nB\nB2\nc\nd\n");
+        assert_eq!(
+            focused_cursor_row(&mut h),
+            4,
+            "the cursor moves with d instead of collapsing"
+        );
+    }
+
     #[test]
     fn pump_auto_reload_skips_dirty_buffers() {
         let mut h = Stoat::test();
@@ -2069,6 +2513,29 @@ mod tests {
         assert_eq!(written, b"edited original\n");
     }
 
+    #[test]
+    fn save_preserves_crlf_and_converts_on_request() {
+        let mut h = Stoat::test();
+        let root = PathBuf::from("/crlf");
+        let path = root.join("a.txt");
+        h.fake_fs().insert_file(&path, b"one\r\ntwo\r\n");
+        h.stoat.active_workspace_mut().git_root = root;
+        dispatch(&mut h.stoat, &OpenFile { path: path.clone() });
+        h.settle();
+
+        h.type_keys("i");
+        h.type_text("zero ");
+        h.type_keys("esc");
+        dispatch(&mut h.stoat, &SaveBuffer);
+        assert_eq!(on_disk(&h, &path), b"zero one\r\ntwo\r\n");
+
+        dispatch(&mut h.stoat, &stoat_action::SetLineEndingLf);
+        assert!(focused_dirty(&h.stoat), "a conversion is an unsaved change");
+        dispatch(&mut h.stoat, &SaveBuffer);
+        assert_eq!(on_disk(&h, &path), b"zero one\ntwo\n");
+        assert!(!focused_dirty(&h.stoat));
+    }
+
     fn enable_format_on_save(h: &mut TestHarness) {
         use lsp_types::{OneOf, ServerCapabilities};
         h.stoat.settings.format_on_save = Some(true);
@@ -2591,6 +3058,59 @@ mod tests {
         assert!(!h.stoat.lsp_opened.contains(&buffer_id));
     }
 
+    #[test]
+    fn reopen_highlights_from_the_token_cache_before_parsing() {
+        let mut h = Stoat::test();
+        h.stoat.token_cache_dir = Some(PathBuf::from("/state/tokens"));
+        let root = PathBuf::from("/close-test");
+        let path = root.join("main.rs");
+        h.fake_fs()
+            .insert_file(&path, b"fn main() { let x = 1; }\n");
+        h.stoat.active_workspace_mut().git_root = root;
+        let open_and_resolve = |h: &mut TestHarness| {
+            dispatch(&mut h.stoat, &OpenFile { path: path.clone() });
+            let buffer_id = crate::action_handlers::focused_editor_mut(&mut h.stoat)
+                .expect("editor")
+                .buffer_id;
+            let buffers = &h.stoat.active_workspace().buffers;
+            let parsed = buffers.syntax_version(buffer_id).is_some();
+            let snapshot = buffers
+                .get(buffer_id)
+                .expect("buffer")
+                .read()
+                .expect("poisoned")
+                .snapshot
+                .clone();
+            let tokens = buffers.tokens_for(buffer_id).map(|(tokens, _)| {
+                tokens
+                    .iter()
+                    .map(|t| {
+                        let start = snapshot.resolve_anchor(&t.range.start);
+                        (start..snapshot.resolve_anchor(&t.range.end), t.style)
+                    })
+                    .collect::<Vec<_>>()
+            });
+            (parsed, tokens)
+        };
+
+        let (parsed, first) = open_and_resolve(&mut h);
+        assert!(
+            !parsed && first.is_none(),
+            "no cache before the first close"
+        );
+        h.settle();
+        h.stoat.drive_background();
+        let (parsed, first) = open_and_resolve(&mut h);
+        assert!(parsed);
+        let first = first.expect("parsed tokens");
+        assert!(!first.is_empty());
+        dispatch(&mut h.stoat, &CloseBuffer);
+
+        let (parsed, reopened) = open_and_resolve(&mut h);
+        assert!(!parsed, "the reopen has not parsed yet");
+        assert_eq!(reopened, Some(first));
+    }
+
     #[test]
     fn close_buffer_refuses_when_dirty() {
         let mut h = Stoat::test();
@@ -2612,6 +3132,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn close_others_and_group_switch_keep_pinned_buffers() {
+        let mut h = Stoat::test();
+        let root = PathBuf::from("/group-test");
+        for name in ["a.rs", "b.rs", "c.rs"] {
+            h.fake_fs().insert_file(&root.join(name), b"x\n");
+        }
+        h.stoat.active_workspace_mut().git_root = root.clone();
+        let open = |h: &mut TestHarness, name: &str| {
+            dispatch(
+                &mut h.stoat,
+                &OpenFile {
+                    path: root.join(name),
+                },
+            );
+            h.settle();
+        };
+        open(&mut h, "a.rs");
+        dispatch(&mut h.stoat, &TogglePinBuffer);
+        dispatch(&mut h.stoat, &AddBufferToGroup { name: "g".into() });
+        open(&mut h, "b.rs");
+        dispatch(&mut h.stoat, &AddBufferToGroup { name: "g".into() });
+        open(&mut h, "c.rs");
+
+        dispatch(&mut h.stoat, &CloseOtherBuffers);
+        let paths = h.stoat.active_workspace().buffers.open_paths();
+        assert_eq!(paths, [root.join("a.rs"), root.join("c.rs")]);
+
+        dispatch(&mut h.stoat, &SwitchBufferGroup { name: "g".into() });
+        let buffers = &h.stoat.active_workspace().buffers;
+        assert_eq!(buffers.open_paths(), [root.join("a.rs"), root.join("b.rs")]);
+        let focused = crate::action_handlers::focused_editor_mut(&mut h.stoat)
+            .expect("editor")
+            .buffer_id;
+        let focused_path = h.stoat.active_workspace().buffers.path_for(focused);
+        assert_eq!(focused_path, Some(root.join("a.rs").as_path()));
+
+        dispatch(
+            &mut h.stoat,
+            &SwitchBufferGroup {
+                name: "nope".into(),
+            },
+        );
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("no buffer group named nope")
+        );
+    }
+
     #[test]
     fn close_buffer_on_scratch_buffer_succeeds() {
         let mut h = Stoat::test();
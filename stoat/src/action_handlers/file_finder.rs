@@ -1,12 +1,14 @@
 use crate::{
     app::{Stoat, UpdateEffect},
-    file_finder::{Browse, FileFinder, FinderScope, OpenIntent},
+    file_finder::{Browse, FileFinder, FinderScope, GitStatusMap, OpenIntent},
+    input_draft::DraftKey,
+    overlay::Overlay,
     picker::PathPicker,
 };
 use std::{collections::HashSet, ops::ControlFlow, path::PathBuf};
 use stoat_action::{OpenFile, SplitNewDown, SplitNewRight};
 use stoat_scheduler::Task;
-use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::{mpsc::UnboundedReceiver, oneshot};
 
 /// Load the open file finder's preview content ahead of the parse scheduler.
 ///
@@ -120,6 +122,7 @@ pub(super) fn open_file_finder(
     let initial_scope = forced_scope.unwrap_or_else(|| resolve_remembered_scope(stoat));
 
     stoat.set_focused_mode("normal".into());
+    crate::overlay::push(stoat, Overlay::FileFinder);
 
     let executor = stoat.executor.clone();
     let git_root = stoat.active_workspace().git_root.clone();
@@ -136,6 +139,7 @@ pub(super) fn open_file_finder(
 
     let modified_paths = crate::file_finder::query_modified(&*stoat.git_host, &git_root);
     let buffer_paths = stoat.active_workspace().buffers.open_paths();
+    let pinned_buffers = stoat.active_workspace().buffers.pinned_count();
     let finder_scopes = stoat.settings.finder_scopes.clone();
 
     let ws = stoat.active_workspace_mut();
@@ -149,11 +153,22 @@ pub(super) fn open_file_finder(
         walk_task,
         modified_paths,
         buffer_paths,
+        pinned_buffers,
         &finder_scopes,
     );
     if let Some(roots) = all_workspaces_roots {
         finder.core.picklist.display_roots = Some(roots);
     }
+    finder.git_status = stoat.active_workspace().git_status.clone();
+    finder.git_status_refresh = Some(spawn_git_status_refresh(
+        stoat,
+        finder.core.git_root.clone(),
+    ));
+    if let Some(draft) = stoat.input_drafts.take(&DraftKey::FileFinder) {
+        finder
+            .input
+            .replace_text(stoat.active_workspace_mut(), &draft);
+    }
     stoat.file_finder = Some(finder);
     UpdateEffect::Redraw
 }
@@ -191,11 +206,22 @@ fn resolve_remembered_scope(stoat: &Stoat) -> FinderScope {
 /// dropping it cancels the in-flight walk on runtimes that propagate
 /// cancellation. Each batch pings the redraw notifier so a live picker repaints
 /// as paths stream in.
+///
+/// A root matching the active workspace's, once its
+/// [`crate::workspace::Workspace::worktree_files`] list is known, skips the
+/// walk and yields that list as a single batch.
 pub(super) fn spawn_workspace_walk(
     stoat: &Stoat,
     git_root: PathBuf,
 ) -> (UnboundedReceiver<Vec<PathBuf>>, Task<()>) {
     let (walk_tx, walk_rx) = tokio::sync::mpsc::unbounded_channel();
+    let ws = stoat.active_workspace();
+    if ws.git_root == git_root
+        && let Some(files) = &ws.worktree_files
+    {
+        let _ = walk_tx.send(files.iter().map(|rel| git_root.join(rel)).collect());
+        return (walk_rx, Task::ready(()));
+    }
     let fs_host = stoat.fs_host.clone();
     let redraw_notify = stoat.redraw_notify.clone();
     let task = stoat.executor.spawn_blocking(move || {
@@ -210,6 +236,23 @@ pub(super) fn spawn_workspace_walk(
     (walk_rx, task)
 }
 
+/// Spawn a blocking query of the repo status at `git_root` for the finder's
+/// badges. The result lands on the receiver and pings the redraw notifier so
+/// the open finder repaints with it. The task must be held until then.
+fn spawn_git_status_refresh(
+    stoat: &Stoat,
+    git_root: PathBuf,
+) -> (oneshot::Receiver<GitStatusMap>, Task<()>) {
+    let (status_tx, status_rx) = oneshot::channel();
+    let git_host = stoat.git_host.clone();
+    let redraw_notify = stoat.redraw_notify.clone();
+    let task = stoat.executor.spawn_blocking(move || {
+        let _ = status_tx.send(crate::file_finder::query_git_status(&*git_host, &git_root));
+        redraw_notify.notify_one();
+    });
+    (status_rx, task)
+}
+
 /// The distinct roots a cross-workspace walk covers.
 ///
 /// Every open workspace's `git_root` comes first, then every persisted
@@ -331,13 +374,6 @@ pub(super) fn file_finder_submit(stoat: &mut Stoat) -> Option<UpdateEffect> {
     Some(dispatch_open_file(stoat, path))
 }
 
-/// Cancel the finder on Escape / Ctrl-C.
-pub(super) fn file_finder_cancel(stoat: &mut Stoat) -> Option<UpdateEffect> {
-    stoat.file_finder.as_ref()?;
-    close_file_finder(stoat);
-    Some(UpdateEffect::Redraw)
-}
-
 /// Complete the highlighted row into the finder query, replacing what was typed
 /// with exactly what the row shows.
 ///
@@ -456,6 +492,16 @@ fn dispatch_open_file(stoat: &mut Stoat, path: PathBuf) -> UpdateEffect {
     super::dispatch(stoat, &OpenFile { path })
 }
 
+/// Close the finder without opening anything, stashing its query as the next open's draft.
+pub(crate) fn dismiss_file_finder(stoat: &mut Stoat) {
+    let Some(finder) = stoat.file_finder.as_ref() else {
+        return;
+    };
+    let text = finder.input.text(stoat.active_workspace());
+    stoat.input_drafts.stash(DraftKey::FileFinder, text);
+    close_file_finder(stoat);
+}
+
 /// Dispose the finder's owned editors and restore the pre-open mode.
 ///
 /// Records the scope the finder closed in on the workspace so `space p`
@@ -470,5 +516,7 @@ pub(crate) fn close_file_finder(stoat: &mut Stoat) {
     if let Some(name) = finder.scope().persist_name() {
         stoat.workspaces[active_idx].last_finder_scope = Some(name);
     }
+    stoat.workspaces[active_idx].git_status = finder.git_status.clone();
     finder.dispose(&mut stoat.workspaces[active_idx]);
+    crate::overlay::pop(stoat, Overlay::FileFinder);
 }
@@ -0,0 +1,155 @@
+//! Folds driven by the buffer's syntax tree.
+//!
+//! `z f` / `z c` / `z i` fold every function body, type body or import run
+//! in the focused buffer, and `z R` unfolds everything. The ranges come from
+//! [`stoat_language::syntax_fold_ranges`] over each parsed layer, so an
+//! injected language folds by its own grammar, and the folds are labelled
+//! with the lines they hide through [`crate::display_map::FoldPlaceholder`].
+
+use crate::{
+    action_handlers::focused_editor_mut,
+    app::{Stoat, UpdateEffect},
+};
+use std::ops::Range;
+use stoat_language::SyntaxFoldKind;
+use stoat_text::{BufferId, Point};
+
+pub(crate) fn fold_syntax(stoat: &mut Stoat, kind: SyntaxFoldKind) -> UpdateEffect {
+    let Some(buffer_id) = focused_editor_mut(stoat).map(|editor| editor.buffer_id) else {
+        return UpdateEffect::None;
+    };
+    let ranges = syntax_ranges(stoat, buffer_id, &[kind]);
+    if ranges.is_empty() {
+        return UpdateEffect::None;
+    }
+    let Some(editor) = focused_editor_mut(stoat) else {
+        return UpdateEffect::None;
+    };
+    editor.display_map.fold_syntax_ranges(ranges);
+    UpdateEffect::Redraw
+}
+
+/// Fold or unfold at buffer `line` of the focused editor, as a click on the
+/// gutter's fold column does. A folded line unfolds. Otherwise the outermost
+/// syntax fold of any kind starting on the line folds.
+pub(crate) fn toggle_fold_at_line(stoat: &mut Stoat, line: u32) -> UpdateEffect {
+    let Some(editor) = focused_editor_mut(stoat) else {
+        return UpdateEffect::None;
+    };
+    let buffer_id = editor.buffer_id;
+    let snapshot = editor.display_map.snapshot();
+    if snapshot.is_line_folded(line) {
+        let end = Point::new(line, snapshot.buffer_snapshot().rope().line_len(line));
+        editor.display_map.unfold(vec![Point::new(line, 0)..end]);
+        return UpdateEffect::Redraw;
+    }
+
+    let kinds = [
+        SyntaxFoldKind::Functions,
+        SyntaxFoldKind::Classes,
+        SyntaxFoldKind::Imports,
+    ];
+    let ranges = syntax_ranges(stoat, buffer_id, &kinds);
+    let Some(editor) = focused_editor_mut(stoat) else {
+        return UpdateEffect::None;
+    };
+    let rope = editor
+        .display_map
+        .snapshot()
+        .buffer_snapshot()
+        .rope()
+        .clone();
+    let outermost = ranges
+        .into_iter()
+        .filter(|r| rope.offset_to_point(r.start).row == line)
+        .max_by_key(|r| r.len());
+    let Some(range) = outermost else {
+        stoat.set_status(format!("nothing to fold on line {}", line + 1));
+        return UpdateEffect::Redraw;
+    };
+    editor.display_map.fold_syntax_ranges(vec![range]);
+    UpdateEffect::Redraw
+}
+
+/// Byte ranges of every `kinds` fold in `buffer_id`, across each parsed
+/// syntax layer. Empty while the buffer has no syntax map.
+fn syntax_ranges(
+    stoat: &Stoat,
+    buffer_id: BufferId,
+    kinds: &[SyntaxFoldKind],
+) -> Vec<Range<usize>> {
+    let ws = stoat.active_workspace();
+    let (Some(syntax_map), Some(buffer)) =
+        (ws.buffers.syntax_map(buffer_id), ws.buffers.get(buffer_id))
+    else {
+        return Vec::new();
+    };
+    let Ok(guard) = buffer.read() else {
+        return Vec::new();
+    };
+    let mut ranges = Vec::new();
+    for layer in syntax_map.snapshot().iter_layers() {
+        for &kind in kinds {
+            ranges.extend(stoat_language::syntax_fold_ranges(
+                &layer.language,
+                layer.tree.root_node(),
+                guard.rope(),
+                kind,
+            ));
+        }
+    }
+    ranges
+}
+
+pub(crate) fn unfold_all(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(editor) = focused_editor_mut(stoat) else {
+        return UpdateEffect::None;
+    };
+    editor.display_map.unfold_all();
+    UpdateEffect::Redraw
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{action_handlers::focused_editor_mut, test_harness::TestHarness};
+    use std::path::PathBuf;
+    use stoat_action::{FoldFunctionBodies, FoldImports, OpenFile, UnfoldAll};
+
+    const SOURCE: &str = "use a::b;\nuse c::d;\n\nfn one() {\n    1;\n    2;\n}\n";
+
+    fn open(h: &mut TestHarness) {
+        let root = PathBuf::from("/fold-test");
+        let path = root.join("lib.rs");
+        h.fake_fs()
+            .insert_files(std::iter::once((path.clone(), SOURCE.as_bytes())));
+        h.stoat.active_workspace_mut().git_root = root;
+        crate::action_handlers::dispatch(&mut h.stoat, &OpenFile { path });
+        h.stoat.drive_background();
+        let _ = h.stoat.render();
+        h.settle();
+        h.stoat.drive_background();
+    }
+
+    fn shown(h: &mut TestHarness) -> Vec<String> {
+        let editor = focused_editor_mut(&mut h.stoat).expect("editor");
+        let snapshot = editor.display_map.snapshot();
+        (0..snapshot.line_count())
+            .map(|row| snapshot.display_line(row))
+            .collect()
+    }
+
+    #[test]
+    fn folds_bodies_and_imports_then_unfolds() {
+        let mut h = TestHarness::with_size(60, 12);
+        open(&mut h);
+
+        crate::action_handlers::dispatch(&mut h.stoat, &FoldFunctionBodies);
+        assert_eq!(shown(&mut h)[3], "fn one() { … 4 lines }");
+
+        crate::action_handlers::dispatch(&mut h.stoat, &FoldImports);
+        assert_eq!(shown(&mut h)[..2], ["… 2 lines", ""]);
+
+        crate::action_handlers::dispatch(&mut h.stoat, &UnfoldAll);
+        assert_eq!(shown(&mut h).len(), SOURCE.lines().count() + 1);
+    }
+}
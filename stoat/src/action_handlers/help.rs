@@ -1,4 +1,7 @@
-use crate::app::{Stoat, UpdateEffect};
+use crate::{
+    app::{Stoat, UpdateEffect},
+    keymap::{BindingSummary, KeymapIssue},
+};
 
 pub(super) fn help_select_prev(stoat: &mut Stoat) -> UpdateEffect {
     apply_to_help(stoat, |h| h.move_selection(-1))
@@ -94,3 +97,59 @@ pub(super) fn help_cancel(stoat: &mut Stoat) -> UpdateEffect {
     super::close_help(stoat);
     UpdateEffect::Redraw
 }
+
+/// Run [`crate::keymap::Keymap::check`] over the loaded keymap and open the
+/// report in a read-only split, backing `:check-keymap`.
+pub(super) fn check_keymap(stoat: &mut Stoat) -> UpdateEffect {
+    let text = keymap_report(&stoat.keymap.check());
+    super::file::open_read_only_text(stoat, &text)
+}
+
+fn keymap_report(issues: &[KeymapIssue]) -> String {
+    let mut text = String::from("# Keymap check\n");
+    if issues.is_empty() {
+        text.push_str("\nNo duplicate or shadowed bindings, and every mode is reachable.\n");
+        return text;
+    }
+    let mut section = "";
+    for issue in issues {
+        let heading = match issue {
+            KeymapIssue::Duplicate { .. } => "Duplicate bindings",
+            KeymapIssue::Shadowed { .. } => "Shadowed bindings",
+            KeymapIssue::UnreachableMode(_) => "Unreachable modes",
+        };
+        if heading != section {
+            text.push_str(&format!("\n## {heading}\n"));
+            section = heading;
+        }
+        match issue {
+            KeymapIssue::Duplicate { kept, dropped } => {
+                text.push_str(&format!("{}\n", describe(dropped)));
+                text.push_str(&format!("  never runs; {} wins\n", describe(kept)));
+            },
+            KeymapIssue::Shadowed { binding, by } => {
+                text.push_str(&format!("{}\n", describe(binding)));
+                text.push_str(&format!("  shadowed by {}\n", describe(by)));
+            },
+            KeymapIssue::UnreachableMode(mode) => {
+                text.push_str(&format!("{mode}: no SetMode chain from normal or insert\n"));
+            },
+        }
+    }
+    text
+}
+
+/// `label [scope] -> Actions (block N)` for one report line.
+fn describe(binding: &BindingSummary) -> String {
+    let scope = if binding.scope.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", binding.scope)
+    };
+    format!(
+        "{}{scope} -> {} (block {})",
+        binding.label,
+        binding.actions.join(", "),
+        binding.block
+    )
+}
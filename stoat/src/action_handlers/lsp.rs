@@ -20,6 +20,7 @@ use crate::{
     },
     editor_state::EditorId,
     host::{LanguageServerFeature, LocalLsp, LspHost, LspTranscript, OffsetEncoding},
+    input_draft::DraftKey,
     location_picker::{LocationEntry, LocationPicker},
     lsp::{servers::ServerSource, LspSymbolKind},
     picker::PreviewSource,
@@ -129,14 +130,20 @@ pub(crate) fn goto_diagnostic(stoat: &mut Stoat, direction: DiagnosticDirection)
 /// A grammar-backed buffer uses its tree-sitter language's name. A buffer with
 /// no grammar (e.g. `.stcfg`) falls back to an extension-keyed LSP identity via
 /// [`crate::lsp::servers::lsp_language_for_extension`], so an in-process server
-/// can still serve it. `None` when neither resolves, leaving the buffer without
-/// a language server.
+/// can still serve it. An extension-less script falls back further to its
+/// shebang or modeline. `None` when nothing resolves, leaving the buffer
+/// without a language server.
 pub(crate) fn lsp_language_name(buffers: &BufferRegistry, buffer_id: BufferId) -> Option<String> {
     if let Some(language) = buffers.language_for(buffer_id) {
         return Some(language.name.to_string());
     }
-    let extension = buffers.path_for(buffer_id)?.extension()?.to_str()?;
-    crate::lsp::servers::lsp_language_for_extension(extension).map(str::to_string)
+    let path = buffers.path_for(buffer_id)?;
+    if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+        return crate::lsp::servers::lsp_language_for_extension(extension).map(str::to_string);
+    }
+    let buffer = buffers.get(buffer_id)?;
+    let guard = buffer.read().expect("buffer lock");
+    stoat_language::language_name_for_rope(guard.rope()).map(str::to_string)
 }
 
 /// Notify the workspace's LSP host that `buffer_id` was just opened.
@@ -758,10 +765,49 @@ impl LspJumpKind {
 
 /// Issue a `textDocument/definition` request for the symbol under the
 /// focused editor's primary cursor. Thin wrapper over [`lsp_jump`].
+///
+/// Falls back to [`goto_local_definition`] when no server advertises
+/// `definition`, so a name bound in the same file still resolves with no
+/// language server.
 pub(crate) fn goto_definition(stoat: &mut Stoat) -> UpdateEffect {
     lsp_jump(stoat, LspJumpKind::Definition)
 }
 
+/// Jump to the in-file definition of the name under the focused editor's
+/// cursor, resolved by the syntax layer's locals query through
+/// [`stoat_language::local_definition_at`]. `None` when the editor is a
+/// review, the buffer has no path or no parsed syntax, its language ships no
+/// locals query, or nothing in scope binds the name.
+fn goto_local_definition(stoat: &mut Stoat) -> Option<UpdateEffect> {
+    let editor = crate::action_handlers::focused_editor_mut(stoat)?;
+    if editor.review_view.is_some() {
+        return None;
+    }
+    let buffer_id = editor.buffer_id;
+    let (rope, cursor) = {
+        let display_snapshot = editor.display_map.snapshot();
+        let buffer_snapshot = display_snapshot.buffer_snapshot();
+        let sel = editor.selections.newest_anchor();
+        let rope = buffer_snapshot.rope().clone();
+        let cursor = stoat_text::cursor_offset(
+            &rope,
+            buffer_snapshot.resolve_anchor(&sel.tail()),
+            buffer_snapshot.resolve_anchor(&sel.head()),
+        );
+        (rope, cursor)
+    };
+
+    let ws = stoat.active_workspace();
+    let path = ws.buffers.path_for(buffer_id)?.to_path_buf();
+    let snapshot = ws.buffers.syntax_map(buffer_id)?.snapshot();
+    let layer = snapshot.deepest_layer_at(cursor)?;
+    let query = layer.language.locals_query.as_ref()?;
+    let target = stoat_language::local_definition_at(query, layer.tree.root_node(), &rope, cursor)?;
+
+    apply_jump(stoat, &path, target.start);
+    Some(UpdateEffect::Redraw)
+}
+
 /// Issue a `textDocument/declaration` request for the symbol under the
 /// focused editor's primary cursor. Thin wrapper over [`lsp_jump`].
 pub(crate) fn goto_declaration(stoat: &mut Stoat) -> UpdateEffect {
@@ -867,13 +913,13 @@ pub(crate) fn goto_references(stoat: &mut Stoat) -> UpdateEffect {
 fn review_lsp_source(stoat: &mut Stoat) -> Option<(PathBuf, Rope, usize)> {
     let (path, line, col) = super::review::review_cursor_file_position(stoat)?;
     let content = super::read_string_via_host(&*stoat.fs_host, &path).ok()?;
-    let lang = stoat.language_registry.for_path(&path);
+    let registry = stoat.language_registry.clone();
 
     let (buffer_id, buffer) = {
         let ws = stoat.active_workspace_mut();
         let (buffer_id, buffer) = ws.buffers.open(&path, &content);
-        if let Some(lang) = lang
-            && ws.buffers.language_for(buffer_id).is_none()
+        if ws.buffers.language_for(buffer_id).is_none()
+            && let Some(lang) = registry.detect(&path, buffer.read().expect("buffer lock").rope())
         {
             ws.buffers.set_language(buffer_id, lang);
         }
@@ -954,8 +1000,10 @@ fn lsp_request_site(stoat: &mut Stoat) -> Option<LspRequestSite> {
 ///
 /// No-op when the focused pane is not an editor or the buffer has no
 /// path. When the server does not advertise the matching
-/// [`LanguageServerFeature`], reports the language-server state to the
-/// status bar via [`report_lsp_unavailable`] instead of doing nothing.
+/// [`LanguageServerFeature`], a definition jump tries
+/// [`goto_local_definition`] first. Anything it cannot resolve reports the
+/// language-server state to the status bar via [`report_lsp_unavailable`]
+/// instead of doing nothing.
 ///
 /// Replacing the prior pending task drops it and cancels its spawned
 /// future, so only one in-flight jump is tracked at a time.
@@ -965,6 +1013,11 @@ fn lsp_jump(stoat: &mut Stoat, kind: LspJumpKind) -> UpdateEffect {
     };
     let hosts = stoat.feature_hosts(site.buffer_id, kind.feature());
     if hosts.is_empty() {
+        if matches!(kind, LspJumpKind::Definition)
+            && let Some(effect) = goto_local_definition(stoat)
+        {
+            return effect;
+        }
         return report_lsp_unavailable(stoat, &format!("goto {}", kind.status_label()));
     }
     let Some(source_uri) = path_to_uri(&site.path) else {
@@ -1332,9 +1385,13 @@ pub(crate) fn hover(stoat: &mut Stoat) -> UpdateEffect {
 /// Answer a runtime [`AgentQuery`] from live session state, firing `reply` with
 /// the JSON result.
 ///
-/// `lsp-status` and `diagnostics` reply synchronously. `hover` requires the path
-/// to be open in the `uid` session (otherwise `{"error":"not open"}`) and runs
-/// the request on a detached task so the event loop never blocks on the server.
+/// `lsp-status` and `diagnostics` reply synchronously. `hover` and
+/// `read-buffer` require the path to be open in the `uid` session (otherwise
+/// `{"error":"not open"}`). `hover` runs the request on a detached task so the
+/// event loop never blocks on the server, and `search` walks the workspace on a
+/// blocking thread, replying with at most [`MATCH_CAP`] matches.
+///
+/// [`MATCH_CAP`]: crate::code_search::MATCH_CAP
 pub(crate) fn answer_agent_query(
     stoat: &mut Stoat,
     uid: WorkspaceUid,
@@ -1413,6 +1470,73 @@ pub(crate) fn answer_agent_query(
                 })
                 .detach();
         },
+        AgentQuery::ReadBuffer { path } => {
+            let buffer = stoat
+                .workspaces
+                .iter()
+                .find(|(_, ws)| ws.uid == uid)
+                .and_then(|(_, ws)| ws.buffers.get(ws.buffers.id_for_path(&path)?));
+            let Some(buffer) = buffer else {
+                let _ = reply.send(json!({ "error": "not open" }));
+                return;
+            };
+            let guard = buffer.read().expect("buffer poisoned");
+            let _ = reply.send(json!({
+                "path": path,
+                "text": guard.rope().to_string(),
+                "dirty": guard.dirty,
+            }));
+        },
+        AgentQuery::Search { pattern } => {
+            use crate::code_search::{scan_file, MATCH_CAP};
+            use std::ops::ControlFlow;
+
+            let regex = match regex::Regex::new(&pattern) {
+                Ok(regex) => regex,
+                Err(err) => {
+                    let _ = reply.send(json!({ "error": err.to_string() }));
+                    return;
+                },
+            };
+            let Some(root) = stoat
+                .workspaces
+                .iter()
+                .find(|(_, ws)| ws.uid == uid)
+                .map(|(_, ws)| ws.git_root.clone())
+            else {
+                let _ = reply.send(json!({ "error": "no session" }));
+                return;
+            };
+            let fs_host = stoat.fs_host.clone();
+            stoat
+                .executor
+                .spawn_blocking(move || {
+                    let mut matches = Vec::new();
+                    fs_host.walk_workspace_files_streaming(&root, &mut |batch| {
+                        for path in batch {
+                            scan_file(&*fs_host, &regex, &path, &mut matches);
+                        }
+                        if matches.len() >= MATCH_CAP {
+                            return ControlFlow::Break(());
+                        }
+                        ControlFlow::Continue(())
+                    });
+                    matches.truncate(MATCH_CAP);
+                    let value: Vec<Value> = matches
+                        .into_iter()
+                        .map(|m| {
+                            json!({
+                                "path": m.path,
+                                "line": m.line,
+                                "column": m.column,
+                                "snippet": m.snippet,
+                            })
+                        })
+                        .collect();
+                    let _ = reply.send(Value::Array(value));
+                })
+                .detach();
+        },
     }
 }
 
@@ -2023,9 +2147,9 @@ fn inlay_hint_text(hint: &InlayHint) -> String {
     text
 }
 
-/// Poll any in-flight inlay-hint request and splice the results into the focused
-/// editor's display map, replacing the buffer's previous hint inlays. Returns
-/// true when state changed.
+/// Poll any in-flight inlay-hint request and reconcile the results into the
+/// focused editor's display map against the buffer's previous hint inlays.
+/// Returns true when state changed.
 pub(crate) fn pump_lsp_inlay_hints(stoat: &mut Stoat) -> bool {
     let Some(mut task) = stoat.pending_inlay_hint_request.take() else {
         return false;
@@ -2063,7 +2187,7 @@ fn apply_inlay_hints(stoat: &mut Stoat, buffer_id: BufferId, items: Vec<InlayHin
     };
 
     let prev = std::mem::take(&mut editor.hint_inlay_ids);
-    editor.hint_inlay_ids = editor.display_map.splice_inlays(prev, inserts);
+    editor.hint_inlay_ids = editor.display_map.reconcile_inlays(prev, inserts);
 }
 
 /// Remove every inlay hint from every editor's display map, across all
@@ -3065,7 +3189,9 @@ fn apply_folding_ranges(
 /// how the entry's effect is obtained: applied from a directly
 /// supplied [`WorkspaceEdit`] (with an optional chained command),
 /// resolved via a follow-up `codeAction/resolve` call, or dispatched
-/// as a `workspace/executeCommand`.
+/// as a `workspace/executeCommand`. The spell checker contributes
+/// `Spelling` and `AddToDictionary` entries, applied locally without
+/// a server round trip.
 #[derive(Debug, Clone)]
 pub(crate) enum CodeActionEntry {
     Direct {
@@ -3084,6 +3210,15 @@ pub(crate) enum CodeActionEntry {
         command: lsp_types::Command,
         server: String,
     },
+    Spelling {
+        title: String,
+        range: std::ops::Range<usize>,
+        replacement: String,
+    },
+    AddToDictionary {
+        title: String,
+        word: String,
+    },
 }
 
 impl CodeActionEntry {
@@ -3091,7 +3226,9 @@ impl CodeActionEntry {
         match self {
             Self::Direct { title, .. }
             | Self::NeedsResolve { title, .. }
-            | Self::Command { title, .. } => title,
+            | Self::Command { title, .. }
+            | Self::Spelling { title, .. }
+            | Self::AddToDictionary { title, .. } => title,
         }
     }
 }
@@ -3117,9 +3254,11 @@ pub(crate) struct CodeActionPicker {
 /// [`pump_lsp_code_actions`] on the next render tick.
 ///
 /// No-op when the focused pane is not an editor or the buffer has no
-/// path. When the server does not advertise
-/// [`LanguageServerFeature::CodeAction`], reports the language-server
-/// state to the status bar instead. Replacing the prior pending
+/// path. A spelling diagnostic under the cursor seeds the picker with
+/// its suggestions ahead of the server's actions. When the server does
+/// not advertise [`LanguageServerFeature::CodeAction`], the picker
+/// opens with just those, or reports the language-server state to the
+/// status bar when there are none. Replacing the prior pending
 /// task drops it, cancelling its spawned future -- only one in-flight
 /// code-action request is tracked at a time.
 pub(crate) fn code_action(stoat: &mut Stoat) -> UpdateEffect {
@@ -3142,13 +3281,24 @@ pub(crate) fn code_action(stoat: &mut Stoat) -> UpdateEffect {
         };
         ((lo, hi), head, editor.buffer_id, buf_snap.rope().clone())
     };
+    let spelling = spelling_entries(stoat, buffer_id, &source_rope, anchor_offset);
 
     let Some((server, host)) = stoat
         .feature_hosts(buffer_id, LanguageServerFeature::CodeAction)
         .into_iter()
         .next()
     else {
-        return report_lsp_unavailable(stoat, "code actions");
+        if spelling.is_empty() {
+            return report_lsp_unavailable(stoat, "code actions");
+        }
+        stoat.pending_code_action_request = None;
+        stoat.pending_code_action_picker = Some(CodeActionPicker {
+            entries: spelling,
+            anchor_offset,
+            selected_idx: 0,
+            server: String::new(),
+        });
+        return UpdateEffect::None;
     };
     let encoding = host.offset_encoding();
 
@@ -3194,22 +3344,51 @@ pub(crate) fn code_action(stoat: &mut Stoat) -> UpdateEffect {
     });
     stoat.pending_code_action_request = Some(task);
     stoat.pending_code_action_picker = Some(CodeActionPicker {
-        entries: Vec::new(),
+        entries: spelling,
         anchor_offset,
         selected_idx: 0,
         server,
     });
-    // The picker is reset to an empty list above so a stale popup
-    // from a prior request does not persist while the new one is
-    // in flight; pump_lsp_code_actions overwrites it on response.
+    // The picker is reset to the spelling entries above so a stale
+    // popup from a prior request does not persist while the new one is
+    // in flight; pump_lsp_code_actions appends the server's on response.
     UpdateEffect::None
 }
 
+/// Picker entries for the spelling diagnostic covering `offset` in
+/// `buffer_id`: one per suggested replacement, then one adding the word
+/// to the workspace dictionary. Empty when no misspelling is there.
+fn spelling_entries(
+    stoat: &Stoat,
+    buffer_id: BufferId,
+    rope: &Rope,
+    offset: usize,
+) -> Vec<CodeActionEntry> {
+    let Some(misspelling) = crate::spell::misspelling_at(stoat, buffer_id, rope, offset) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<CodeActionEntry> = misspelling
+        .suggestions
+        .into_iter()
+        .map(|replacement| CodeActionEntry::Spelling {
+            title: format!("Change to \"{replacement}\""),
+            range: misspelling.range.clone(),
+            replacement,
+        })
+        .collect();
+    entries.push(CodeActionEntry::AddToDictionary {
+        title: format!("Add \"{}\" to workspace dictionary", misspelling.word),
+        word: misspelling.word,
+    });
+    entries
+}
+
 /// Poll any in-flight code-action request
 /// ([`Stoat::pending_code_action_request`]) and translate the result
 /// into a [`CodeActionPicker`]. Filters out `Command`-only entries
 /// and `CodeAction` items that have neither a `WorkspaceEdit` nor a
-/// resolve trigger. Clears the picker when no actionable entries
+/// resolve trigger. The server's entries follow any spelling entries
+/// already in the picker. Clears the picker when no actionable entries
 /// remain.
 pub(crate) fn pump_lsp_code_actions(stoat: &mut Stoat) -> bool {
     let Some(mut task) = stoat.pending_code_action_request.take() else {
@@ -3255,17 +3434,14 @@ pub(crate) fn pump_lsp_code_actions(stoat: &mut Stoat) -> bool {
                     }),
                 })
                 .collect();
-            if entries.is_empty() {
-                set_lsp_status(stoat, "lsp: no code actions available".to_string());
-                stoat.pending_code_action_picker = None;
-            } else if let Some(picker) = stoat.pending_code_action_picker.as_mut() {
-                picker.entries = entries;
+            if let Some(picker) = stoat.pending_code_action_picker.as_mut() {
+                picker.entries.extend(entries);
             }
+            clear_empty_code_action_picker(stoat);
             true
         },
         Poll::Ready(None) => {
-            set_lsp_status(stoat, "lsp: no code actions available".to_string());
-            stoat.pending_code_action_picker = None;
+            clear_empty_code_action_picker(stoat);
             true
         },
         Poll::Pending => {
@@ -3275,6 +3451,19 @@ pub(crate) fn pump_lsp_code_actions(stoat: &mut Stoat) -> bool {
     }
 }
 
+/// Close the code-action picker and report so when the response left it
+/// without entries.
+fn clear_empty_code_action_picker(stoat: &mut Stoat) {
+    let empty = stoat
+        .pending_code_action_picker
+        .as_ref()
+        .is_none_or(|picker| picker.entries.is_empty());
+    if empty {
+        set_lsp_status(stoat, "lsp: no code actions available".to_string());
+        stoat.pending_code_action_picker = None;
+    }
+}
+
 /// Poll any in-flight `codeAction/resolve` task
 /// ([`Stoat::pending_code_action_resolve`]). On `Ready(Some(edit))`
 /// applies the edit via [`crate::lsp::edit_apply::apply_workspace_edit`];
@@ -3317,7 +3506,8 @@ fn apply_code_action_edit(stoat: &mut Stoat, edit: WorkspaceEdit) {
 /// User has picked entry `index` from the open code-action picker.
 /// `Direct` entries apply immediately; `NeedsResolve` entries spawn
 /// a `codeAction/resolve` task whose result is applied by
-/// [`pump_lsp_code_action_resolve`]. Clears the picker either way.
+/// [`pump_lsp_code_action_resolve`]. Spelling entries edit the buffer
+/// or the workspace dictionary directly. Clears the picker either way.
 /// No-op when no picker is open or `index` is out of range.
 pub(crate) fn pick_code_action(stoat: &mut Stoat, index: usize) -> bool {
     let Some(picker) = stoat.pending_code_action_picker.take() else {
@@ -3361,6 +3551,16 @@ pub(crate) fn pick_code_action(stoat: &mut Stoat, index: usize) -> bool {
         } => {
             dispatch_execute_command(stoat, &server, buffer_id, command);
         },
+        CodeActionEntry::Spelling {
+            range, replacement, ..
+        } => {
+            if let Some(buffer_id) = buffer_id {
+                crate::spell::replace_word(stoat, buffer_id, range, &replacement);
+            }
+        },
+        CodeActionEntry::AddToDictionary { word, .. } => {
+            crate::spell::add_to_dictionary(stoat, &word);
+        },
     }
     true
 }
@@ -3760,7 +3960,7 @@ pub(crate) fn open_symbol_picker(stoat: &mut Stoat) -> UpdateEffect {
     stoat.pending_symbol_picker_request = Some(task);
     stoat.set_focused_mode("normal".into());
     let executor = stoat.executor.clone();
-    let finder = {
+    let mut finder = {
         let ws = stoat.active_workspace_mut();
         SymbolFinder::new(
             ws,
@@ -3770,6 +3970,14 @@ pub(crate) fn open_symbol_picker(stoat: &mut Stoat) -> UpdateEffect {
             Vec::new(),
         )
     };
+    if let Some(draft) = stoat
+        .input_drafts
+        .take(&DraftKey::SymbolFinder(SymbolFinderScope::Document))
+    {
+        finder
+            .input
+            .replace_text(stoat.active_workspace_mut(), &draft);
+    }
     stoat.symbol_finder = Some(finder);
     UpdateEffect::None
 }
@@ -4136,12 +4344,24 @@ pub(crate) fn symbol_finder_submit(stoat: &mut Stoat) -> Option<UpdateEffect> {
 /// through to the next probe.
 pub(crate) fn symbol_finder_cancel(stoat: &mut Stoat) -> Option<UpdateEffect> {
     if stoat.symbol_finder.is_some() {
-        close_symbol_finder(stoat);
+        dismiss_symbol_finder(stoat);
         return Some(UpdateEffect::Redraw);
     }
     None
 }
 
+/// Close the symbol finder without jumping, stashing its query as the draft
+/// the next open of the same scope restores.
+pub(crate) fn dismiss_symbol_finder(stoat: &mut Stoat) {
+    let Some(finder) = stoat.symbol_finder.as_ref() else {
+        return;
+    };
+    let key = DraftKey::SymbolFinder(finder.scope);
+    let text = finder.input.text(stoat.active_workspace());
+    stoat.input_drafts.stash(key, text);
+    close_symbol_finder(stoat);
+}
+
 /// Close the symbol finder, disposing its input editor and dropping any
 /// in-flight document or workspace request so a late response is discarded.
 pub(crate) fn close_symbol_finder(stoat: &mut Stoat) {
@@ -4294,7 +4514,7 @@ pub(crate) fn open_workspace_symbol_picker(stoat: &mut Stoat) -> UpdateEffect {
     stoat.set_focused_mode("normal".into());
 
     let executor = stoat.executor.clone();
-    let finder = {
+    let mut finder = {
         let ws = stoat.active_workspace_mut();
         SymbolFinder::new(
             ws,
@@ -4304,6 +4524,14 @@ pub(crate) fn open_workspace_symbol_picker(stoat: &mut Stoat) -> UpdateEffect {
             servers,
         )
     };
+    if let Some(draft) = stoat
+        .input_drafts
+        .take(&DraftKey::SymbolFinder(SymbolFinderScope::Workspace))
+    {
+        finder
+            .input
+            .replace_text(stoat.active_workspace_mut(), &draft);
+    }
     stoat.symbol_finder = Some(finder);
     UpdateEffect::Redraw
 }
@@ -6829,6 +7057,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn goto_definition_without_a_server_resolves_locals() {
+        let mut h = TestHarness::with_size(80, 24);
+        h.allow_host_swap();
+        h.stoat
+            .set_lsp_host(std::sync::Arc::new(crate::host::NoopLsp));
+        let source = "fn main() {\n    let count = 1;\n    println!(\"{}\", count);\n}\n";
+        let root = seed(&mut h, &[("main.rs", source)]);
+        open_buffer(&mut h, root.join("main.rs"));
+        h.stoat.drive_background();
+        h.settle();
+        crate::action_handlers::movement::jump_to_offset(
+            &mut h.stoat,
+            source.rfind("count").unwrap(),
+        );
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::GotoDefinition);
+
+        assert_eq!(cursor_offset(&mut h), source.find("count").unwrap());
+        assert!(h.stoat.pending_lsp_jump.is_none());
+        assert!(
+            h.stoat.pending_message.is_none(),
+            "a resolved local reports nothing"
+        );
+    }
+
     #[test]
     fn unsupported_feature_with_two_servers_reports_does_not_support() {
         use lsp_types::ServerCapabilities;
@@ -7130,6 +7384,53 @@ mod tests {
         assert_eq!(markup.value, "hover text");
     }
 
+    #[test]
+    fn query_read_buffer_returns_unsaved_text() {
+        let mut h = TestHarness::with_size(80, 24);
+        let root = seed(&mut h, &[("main.rs", "abc\n")]);
+        let path = root.join("main.rs");
+        open_buffer(&mut h, path.clone());
+        edit_buffer(&mut h, 0..0, "x");
+
+        let uid = h.stoat.active_workspace().uid();
+        let (reply_tx, mut reply_rx) = oneshot::channel();
+        h.stoat.handle_agent_control(AgentControl::Query {
+            uid,
+            request: AgentQuery::ReadBuffer { path: path.clone() },
+            reply: reply_tx,
+        });
+
+        let value = reply_rx.try_recv().expect("synchronous read-buffer reply");
+        assert_eq!(value["text"], "xabc\n");
+        assert_eq!(value["dirty"], true);
+    }
+
+    #[test]
+    fn query_search_returns_workspace_matches() {
+        let mut h = TestHarness::with_size(80, 24);
+        seed(
+            &mut h,
+            &[("a.rs", "fn alpha() {}\n"), ("b.rs", "fn beta() {}\n")],
+        );
+
+        let uid = h.stoat.active_workspace().uid();
+        let (reply_tx, mut reply_rx) = oneshot::channel();
+        h.stoat.handle_agent_control(AgentControl::Query {
+            uid,
+            request: AgentQuery::Search {
+                pattern: "fn b\\w+".into(),
+            },
+            reply: reply_tx,
+        });
+        h.settle();
+
+        let value = reply_rx.try_recv().expect("search reply");
+        let matches = value.as_array().expect("match array");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0]["snippet"], "fn beta() {}");
+        assert_eq!(matches[0]["line"], 1);
+    }
+
     #[test]
     fn query_hover_on_unopened_path_replies_error() {
         let mut h = TestHarness::with_size(40, 10);
@@ -2252,7 +2252,7 @@ pub(super) fn commit_undo_checkpoint(stoat: &mut Stoat) -> UpdateEffect {
     UpdateEffect::None
 }
 
-fn apply_buffer_history<F>(stoat: &mut Stoat, count: u32, op: F) -> UpdateEffect
+pub(super) fn apply_buffer_history<F>(stoat: &mut Stoat, count: u32, op: F) -> UpdateEffect
 where
     F: Fn(&mut crate::buffer::TextBuffer) -> Option<Vec<Selection<Anchor>>>,
 {
@@ -2396,6 +2396,481 @@ pub(super) fn toggle_comments(stoat: &mut Stoat) -> UpdateEffect {
     UpdateEffect::Redraw
 }
 
+/// Copy the lines each selection touches and keep the copy live below a
+/// commented-out original.
+///
+/// The commented block is inserted above the selected lines rather than a copy
+/// below them, so the live text is the text the selections already cover and
+/// they only shift down past the insertion. Blank lines are copied uncommented.
+/// Buffers whose language has no `line_comment` are a no-op, like
+/// [`toggle_comments`].
+pub(super) fn duplicate_and_comment_original(stoat: &mut Stoat) -> UpdateEffect {
+    let ws = stoat.active_workspace_mut();
+    let focused = ws.panes.focus();
+    let editor_id = match ws.panes.pane(focused).view {
+        View::Editor(id) => id,
+        _ => return UpdateEffect::None,
+    };
+
+    let buffer_id = ws.editors.get(editor_id).expect("editor").buffer_id;
+    let Some(language) = ws.buffers.language_for(buffer_id) else {
+        return UpdateEffect::None;
+    };
+    let Some(prefix) = language.line_comment else {
+        return UpdateEffect::None;
+    };
+
+    let editor = ws.editors.get_mut(editor_id).expect("editor");
+    let display_snapshot = editor.display_map.snapshot();
+    let buffer_snapshot = display_snapshot.buffer_snapshot();
+    let rope = buffer_snapshot.rope();
+
+    let spans: Vec<(usize, usize, usize)> = editor
+        .selections
+        .all_anchors()
+        .iter()
+        .map(|sel| {
+            (
+                sel.id,
+                buffer_snapshot.resolve_anchor(&sel.start),
+                buffer_snapshot.resolve_anchor(&sel.end),
+            )
+        })
+        .collect();
+
+    // Selections on overlapping or adjacent rows share one block, so the
+    // original stays contiguous above a single live copy.
+    let merged = touched_row_blocks(rope, buffer_snapshot, editor.selections.all_anchors());
+
+    let mut inserts: Vec<(usize, String)> = Vec::with_capacity(merged.len());
+    for (start_row, end_row) in merged {
+        let mut commented = String::new();
+        for row in start_row..=end_row {
+            let line_start = rope.point_to_offset(Point::new(row, 0));
+            let line_end = line_start + rope.line_len(row) as usize;
+            let line = rope.slice(line_start..line_end).to_string();
+            let content = line.trim_start();
+            if content.is_empty() {
+                commented.push_str(&line);
+            } else {
+                commented.push_str(&line[..line.len() - content.len()]);
+                commented.push_str(prefix);
+                commented.push(' ');
+                commented.push_str(content);
+            }
+            commented.push('\n');
+        }
+        inserts.push((rope.point_to_offset(Point::new(start_row, 0)), commented));
+    }
+
+    if inserts.is_empty() {
+        return UpdateEffect::None;
+    }
+
+    {
+        let buffer = ws.buffers.get(buffer_id).expect("buffer");
+        let mut guard = buffer.write().expect("poisoned");
+        for (offset, text) in inserts.iter().rev() {
+            guard.edit(*offset..*offset, text);
+        }
+    }
+
+    // Every insertion sits at a block's first line start, at or before the
+    // selections in that block, so a selection shifts by the length of each
+    // insertion at or before its own offsets.
+    let shifted_by = |offset: usize| -> usize {
+        inserts
+            .iter()
+            .filter(|(at, _)| *at <= offset)
+            .map(|(_, text)| text.len())
+            .sum()
+    };
+    let new_ranges: std::collections::HashMap<usize, (usize, usize)> = spans
+        .iter()
+        .map(|&(id, start, end)| (id, (start + shifted_by(start), end + shifted_by(end))))
+        .collect();
+
+    let editor = ws.editors.get_mut(editor_id).expect("editor still exists");
+    let new_display = editor.display_map.snapshot();
+    let new_buf = new_display.buffer_snapshot();
+    editor.selections.transform(new_buf, |sel| {
+        let mut new = sel.clone();
+        if let Some(&(start, end)) = new_ranges.get(&sel.id) {
+            new.start = new_buf.anchor_at(start, Bias::Right);
+            new.end = new_buf.anchor_at(end, Bias::Right);
+        }
+        new
+    });
+    UpdateEffect::Redraw
+}
+
+/// Duplicate each selection and leave it on the copy.
+///
+/// A bare block cursor or a line-shaped selection duplicates every line it
+/// touches, with selections on overlapping or adjacent lines sharing one block.
+/// Any other selection duplicates just its text. As in
+/// [`duplicate_and_comment_original`], the copy is inserted before the original
+/// so the selections only shift past the insertion and end up on the second of
+/// the two identical spans.
+pub(super) fn duplicate_selection(stoat: &mut Stoat) -> UpdateEffect {
+    let ws = stoat.active_workspace_mut();
+    let focused = ws.panes.focus();
+    let editor_id = match ws.panes.pane(focused).view {
+        View::Editor(id) => id,
+        _ => return UpdateEffect::None,
+    };
+
+    let editor = ws.editors.get_mut(editor_id).expect("editor");
+    let buffer_id = editor.buffer_id;
+    let display_snapshot = editor.display_map.snapshot();
+    let buffer_snapshot = display_snapshot.buffer_snapshot();
+    let rope = buffer_snapshot.rope();
+    let max_row = rope.max_point().row;
+
+    let mut spans: Vec<(usize, usize, usize)> = Vec::new();
+    let mut line_sels: Vec<Selection<Anchor>> = Vec::new();
+    let mut inserts: Vec<(usize, String)> = Vec::new();
+    for sel in editor.selections.all_anchors() {
+        let start = buffer_snapshot.resolve_anchor(&sel.start);
+        let end = buffer_snapshot.resolve_anchor(&sel.end);
+        spans.push((sel.id, start, end));
+        let line_shaped = rope.offset_to_point(start).column == 0
+            && end > start
+            && (rope.offset_to_point(end).column == 0 || end == rope.len());
+        if line_shaped || end <= next_char_boundary(rope, start) {
+            line_sels.push(sel.clone());
+        } else {
+            inserts.push((start, rope.slice(start..end).to_string()));
+        }
+    }
+    for (start_row, end_row) in touched_row_blocks(rope, buffer_snapshot, &line_sels) {
+        let start = rope.point_to_offset(Point::new(start_row, 0));
+        let end = if end_row < max_row {
+            rope.point_to_offset(Point::new(end_row + 1, 0))
+        } else {
+            rope.len()
+        };
+        let mut text = rope.slice(start..end).to_string();
+        if !text.ends_with('\n') {
+            text.push('\n');
+        }
+        inserts.push((start, text));
+    }
+    inserts.sort_by_key(|(at, _)| *at);
+
+    {
+        let buffer = ws.buffers.get(buffer_id).expect("buffer");
+        let mut guard = buffer.write().expect("poisoned");
+        for (offset, text) in inserts.iter().rev() {
+            guard.edit(*offset..*offset, text);
+        }
+    }
+
+    // An insertion at a selection's start belongs to that selection and shifts
+    // both ends, while one at its end belongs to the next selection and leaves
+    // the end in place.
+    let shifted = |start: usize, end: usize| -> (usize, usize) {
+        let mut by = (0, 0);
+        for (at, text) in &inserts {
+            if *at <= start {
+                by.0 += text.len();
+            }
+            if *at < end || *at <= start {
+                by.1 += text.len();
+            }
+        }
+        (start + by.0, end + by.1)
+    };
+    let new_ranges: std::collections::HashMap<usize, (usize, usize)> = spans
+        .iter()
+        .map(|&(id, start, end)| (id, shifted(start, end)))
+        .collect();
+
+    let editor = ws.editors.get_mut(editor_id).expect("editor still exists");
+    let new_display = editor.display_map.snapshot();
+    let new_buf = new_display.buffer_snapshot();
+    editor.selections.transform(new_buf, |sel| {
+        let mut new = sel.clone();
+        if let Some(&(start, end)) = new_ranges.get(&sel.id) {
+            new.start = new_buf.anchor_at(start, Bias::Right);
+            new.end = new_buf.anchor_at(end, Bias::Right);
+        }
+        new
+    });
+    UpdateEffect::Redraw
+}
+
+/// Rows each of `sels` touches, merged so overlapping or adjacent rows form one
+/// `(start_row, end_row)` block, in ascending order. A selection ending at column
+/// 0 past its start stops on the row above, so a line-shaped selection does not
+/// drag the following line along.
+fn touched_row_blocks(
+    rope: &Rope,
+    buffer_snapshot: &MultiBufferSnapshot,
+    sels: &[Selection<Anchor>],
+) -> Vec<(u32, u32)> {
+    let mut blocks: Vec<(u32, u32)> = sels
+        .iter()
+        .map(|sel| {
+            let start_offset = buffer_snapshot.resolve_anchor(&sel.start);
+            let end_offset = buffer_snapshot.resolve_anchor(&sel.end);
+            let start_row = rope.offset_to_point(start_offset).row;
+            let end_point = rope.offset_to_point(end_offset);
+            let end_row = if end_offset > start_offset && end_point.column == 0 {
+                end_point.row.saturating_sub(1)
+            } else {
+                end_point.row
+            };
+            (start_row, end_row.max(start_row))
+        })
+        .collect();
+    blocks.sort_unstable();
+    let mut merged: Vec<(u32, u32)> = Vec::with_capacity(blocks.len());
+    for (start, end) in blocks {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum LineMoveDir {
+    Up,
+    Down,
+}
+
+/// The row order after `steps` single-row moves of every run of `in_block` rows
+/// in `dir`, as `order[new_row] = old_row`.
+///
+/// Each step swaps a run with the row beside it. A run already against the
+/// buffer edge stays put, and runs that meet travel on together. No run can
+/// travel further than the buffer has rows, so `steps` is capped there, and
+/// the walk stops at the first step that moves nothing.
+fn shifted_line_order(in_block: &[bool], dir: LineMoveDir, steps: u32) -> Vec<u32> {
+    let mut order: Vec<u32> = (0..in_block.len() as u32).collect();
+    let moving = |order: &[u32], i: usize| in_block[order[i] as usize];
+    let steps = steps.min(u32::try_from(in_block.len()).unwrap_or(u32::MAX));
+    for _ in 0..steps {
+        let mut moved = false;
+        let mut i = 0;
+        while i < order.len() {
+            if !moving(&order, i) {
+                i += 1;
+                continue;
+            }
+            let mut j = i;
+            while j + 1 < order.len() && moving(&order, j + 1) {
+                j += 1;
+            }
+            match dir {
+                LineMoveDir::Up if i > 0 => {
+                    order[i - 1..=j].rotate_left(1);
+                    moved = true;
+                },
+                LineMoveDir::Down if j + 1 < order.len() => {
+                    order[i..=j + 1].rotate_right(1);
+                    moved = true;
+                },
+                _ => {},
+            }
+            i = j + 2;
+        }
+        if !moved {
+            break;
+        }
+    }
+    order
+}
+
+/// Move every line the selections touch up or down by the count, one row by
+/// default, keeping each selection on its moved text.
+///
+/// Selections on overlapping or adjacent lines move as one block, and a block
+/// already at the buffer edge stays put. When the buffer's indent query puts a
+/// block's new position at a different depth than its old one, as it does when
+/// the block crosses a line that opens or closes a block, the moved lines are
+/// shifted by the difference so their relative indentation is kept. Buffers
+/// without a fresh indent tree move lines verbatim.
+pub(super) fn move_lines(stoat: &mut Stoat, dir: LineMoveDir) -> UpdateEffect {
+    let steps = stoat.take_pending_count().unwrap_or(1);
+    let editor_id = {
+        let ws = stoat.active_workspace();
+        match ws.panes.pane(ws.panes.focus()).view {
+            View::Editor(id) => id,
+            _ => return UpdateEffect::None,
+        }
+    };
+
+    let (buffer_id, spans, in_block, order, first, last, lines) = {
+        let ws = stoat.active_workspace_mut();
+        let editor = ws.editors.get_mut(editor_id).expect("editor");
+        let display_snapshot = editor.display_map.snapshot();
+        let buffer_snapshot = display_snapshot.buffer_snapshot();
+        let rope = buffer_snapshot.rope();
+        // A trailing newline ends the last line rather than starting another,
+        // so the empty row after it is not a line that can move.
+        let max_row = rope.max_point().row;
+        let row_count = if max_row > 0 && rope.line_len(max_row) == 0 {
+            max_row as usize
+        } else {
+            max_row as usize + 1
+        };
+
+        let mut in_block = vec![false; row_count];
+        for (start, end) in
+            touched_row_blocks(rope, buffer_snapshot, editor.selections.all_anchors())
+        {
+            for row in start..=end.min(row_count as u32 - 1) {
+                in_block[row as usize] = true;
+            }
+        }
+        let order = shifted_line_order(&in_block, dir, steps);
+        let Some(first) = (0..row_count).find(|&i| order[i] as usize != i) else {
+            return UpdateEffect::None;
+        };
+        let last = (0..row_count)
+            .rfind(|&i| order[i] as usize != i)
+            .expect("first found");
+        let lines: Vec<String> = (first..=last)
+            .map(|row| {
+                let row = row as u32;
+                let start = rope.point_to_offset(Point::new(row, 0));
+                rope.slice(start..start + rope.line_len(row) as usize)
+                    .to_string()
+            })
+            .collect();
+        let spans: Vec<(usize, Point, Point)> = editor
+            .selections
+            .all_anchors()
+            .iter()
+            .map(|sel| {
+                (
+                    sel.id,
+                    rope.offset_to_point(buffer_snapshot.resolve_anchor(&sel.start)),
+                    rope.offset_to_point(buffer_snapshot.resolve_anchor(&sel.end)),
+                )
+            })
+            .collect();
+        (editor.buffer_id, spans, in_block, order, first, last, lines)
+    };
+
+    // Re-indent each moved run by how far the syntax depth above it changed.
+    // Both depths are read from the pre-move tree, which still describes the
+    // unmoved rows the run now sits below. Rows displaced by the move keep
+    // their indentation.
+    let mut reindent: std::collections::HashMap<u32, (usize, String)> =
+        std::collections::HashMap::new();
+    let mut pos = first;
+    while pos <= last {
+        let row = order[pos];
+        if row as usize == pos || !in_block[row as usize] {
+            pos += 1;
+            continue;
+        }
+        let mut end = pos;
+        while end < last && order[end + 1] == order[end] + 1 {
+            end += 1;
+        }
+        let before = stoat.syntax_indent_below(buffer_id, row.checked_sub(1));
+        let after = stoat.syntax_indent_below(buffer_id, pos.checked_sub(1).map(|p| order[p]));
+        if let (Some(before), Some(after)) = (before, after) {
+            for &moved in &order[pos..=end] {
+                let line = &lines[moved as usize - first];
+                let leading = line.len() - line.trim_start_matches([' ', '\t']).len();
+                if leading == line.len() {
+                    continue;
+                }
+                if let Some(added) = after.strip_prefix(before.as_str()) {
+                    if !added.is_empty() {
+                        reindent.insert(moved, (0, added.to_string()));
+                    }
+                } else if before.starts_with(after.as_str()) {
+                    let removed = (before.len() - after.len()).min(leading);
+                    reindent.insert(moved, (removed, String::new()));
+                }
+            }
+        }
+        pos = end + 1;
+    }
+
+    let mut text = String::new();
+    for (i, &row) in order[first..=last].iter().enumerate() {
+        if i > 0 {
+            text.push('\n');
+        }
+        let line = &lines[row as usize - first];
+        match reindent.get(&row) {
+            Some((removed, added)) => {
+                text.push_str(added);
+                text.push_str(&line[*removed..]);
+            },
+            None => text.push_str(line),
+        }
+    }
+
+    let mut new_row = vec![0u32; order.len()];
+    for (new, &old) in order.iter().enumerate() {
+        new_row[old as usize] = new as u32;
+    }
+    let map_point = |point: Point| -> Point {
+        let column = match reindent.get(&point.row) {
+            Some((removed, added)) => {
+                (point.column as usize).saturating_sub(*removed) as u32 + added.len() as u32
+            },
+            None => point.column,
+        };
+        let row = new_row
+            .get(point.row as usize)
+            .copied()
+            .unwrap_or(point.row);
+        Point::new(row, column)
+    };
+
+    let ws = stoat.active_workspace_mut();
+    {
+        let editor = ws.editors.get_mut(editor_id).expect("editor");
+        let display_snapshot = editor.display_map.snapshot();
+        let rope = display_snapshot.buffer_snapshot().rope();
+        let start = rope.point_to_offset(Point::new(first as u32, 0));
+        let last_row = last as u32;
+        let end = rope.point_to_offset(Point::new(last_row, rope.line_len(last_row)));
+        let buffer = ws.buffers.get(buffer_id).expect("buffer");
+        buffer.write().expect("poisoned").edit(start..end, &text);
+    }
+
+    let editor = ws.editors.get_mut(editor_id).expect("editor still exists");
+    let new_display = editor.display_map.snapshot();
+    let new_buf = new_display.buffer_snapshot();
+    let rope = new_buf.rope();
+    // A selection ending at column 0 past its start covers its last line's
+    // newline, so its end follows that line to just past its moved newline
+    // rather than the unrelated row below.
+    let new_ranges: std::collections::HashMap<usize, (usize, usize)> = spans
+        .iter()
+        .map(|&(id, start, end)| {
+            let start_offset = rope.point_to_offset(map_point(start));
+            let end_offset = if end.column == 0 && end > start {
+                let above = map_point(Point::new(end.row - 1, 0));
+                rope.point_to_offset(Point::new(above.row, rope.line_len(above.row))) + 1
+            } else {
+                rope.point_to_offset(map_point(end))
+            };
+            (id, (start_offset, end_offset.min(rope.len())))
+        })
+        .collect();
+    editor.selections.transform(new_buf, |sel| {
+        let mut new = sel.clone();
+        if let Some(&(start, end)) = new_ranges.get(&sel.id) {
+            new.start = new_buf.anchor_at(start, Bias::Right);
+            new.end = new_buf.anchor_at(end, Bias::Right);
+        }
+        new
+    });
+    UpdateEffect::Redraw
+}
+
 pub(super) fn indent_selection(stoat: &mut Stoat) -> UpdateEffect {
     apply_line_indent(stoat, IndentDir::In)
 }
@@ -3350,6 +3825,14 @@ pub(super) fn goto_change(stoat: &mut Stoat, dir: ChangeDir) -> UpdateEffect {
             .path_for(buffer_id)
             .map(Path::to_path_buf)
     });
+    // A buffer diffed against another buffer has no repository changes to
+    // cross into, so its walk stops at the first and last hunk.
+    let paired = stoat.focused_editor_ids().is_some_and(|(_, buffer_id)| {
+        stoat
+            .active_workspace()
+            .buffer_diff_bases
+            .contains_key(&buffer_id)
+    });
     let Some(editor) = focused_editor_mut(stoat) else {
         return UpdateEffect::None;
     };
@@ -3389,6 +3872,10 @@ pub(super) fn goto_change(stoat: &mut Stoat, dir: ChangeDir) -> UpdateEffect {
         },
     });
     let Some(target_row) = target_row else {
+        if paired {
+            stoat.set_status("no more changes");
+            return UpdateEffect::Redraw;
+        }
         return goto_change_across_files(stoat, dir, current_path, source_diff_view, origin);
     };
 
@@ -3794,6 +4281,40 @@ pub(super) fn goto_line_number(stoat: &mut Stoat) -> UpdateEffect {
     UpdateEffect::Redraw
 }
 
+/// Land the focused editor's cursor on 1-based `line` and `column` and scroll
+/// it into view, as a `file:line:col` startup argument asks. The line clamps
+/// to the last line and the column, counted in chars, to the line's end.
+pub(crate) fn goto_line_column(stoat: &mut Stoat, line: u32, column: u32) -> UpdateEffect {
+    let scrolloff = stoat.settings.scrolloff.unwrap_or(3);
+    let Some(editor) = focused_editor_mut(stoat) else {
+        return UpdateEffect::None;
+    };
+    let display_snapshot = editor.display_map.snapshot();
+    let buffer_snapshot = display_snapshot.buffer_snapshot();
+    let rope = buffer_snapshot.rope();
+    let mut last_row = rope.max_point().row;
+    if last_row > 0 && rope.line_len(last_row) == 0 {
+        last_row -= 1;
+    }
+    let row = line.saturating_sub(1).min(last_row);
+    let line_start = rope.point_to_offset(Point::new(row, 0));
+    let line_end = rope.point_to_offset(Point::new(row, rope.line_len(row)));
+    let mut target_offset = line_start;
+    for ch in rope
+        .chars_at(line_start)
+        .take(column.saturating_sub(1) as usize)
+    {
+        let next = target_offset + ch.len_utf8();
+        if next > line_end {
+            break;
+        }
+        target_offset = next;
+    }
+    apply_primary_range(editor, target_offset..target_offset);
+    ensure_cursor_in_view(editor, scrolloff);
+    UpdateEffect::Redraw
+}
+
 pub(super) fn goto_column(stoat: &mut Stoat, extend: bool) -> UpdateEffect {
     let count = stoat.take_pending_count().unwrap_or(1);
     let Some(editor) = focused_editor_mut(stoat) else {
@@ -5839,4 +6360,29 @@ mod tests {
         assert_eq!(buffer_string(&mut h), "ab cd\n");
         assert_ne!(h.selection_spans(), vec![(2, 3, false)]);
     }
+
+    #[test]
+    fn count_prefix_repeats_delete_as_one_undo_step() {
+        let mut h = TestHarness::with_size(20, 5);
+        let path = h.write_file("s.txt", "abcdef\n");
+        h.open_file(&path);
+        h.type_keys("3 d");
+        assert_eq!(buffer_string(&mut h), "def\n");
+        h.type_keys("u");
+        assert_eq!(buffer_string(&mut h), "abcdef\n");
+    }
+
+    #[test]
+    fn count_prefix_repeat_is_capped() {
+        let mut h = TestHarness::with_size(20, 5);
+        let text = format!("{}\n", "a".repeat(1500));
+        let path = h.write_file("s.txt", &text);
+        h.open_file(&path);
+        h.type_keys("9 9 9 9 9 9 9 9 d");
+        assert_eq!(
+            buffer_string(&mut h),
+            format!("{}\n", "a".repeat(500)),
+            "the repeat stops at MAX_REPEAT_COUNT deletions"
+        );
+    }
 }
@@ -3,6 +3,8 @@ use crate::{
     command_palette::PaletteOutcome,
     file_finder::Browse,
     host::FsHost,
+    input_draft::DraftKey,
+    overlay::Overlay,
     picker::PathPicker,
 };
 use std::path::{Path, PathBuf};
@@ -62,6 +64,39 @@ pub(super) fn arg_candidates(stoat: &Stoat, source: ValueSource) -> Option<ArgCa
                 .collect();
             Some(ArgCandidates::Paths(names))
         },
+        ValueSource::Tasks => Some(ArgCandidates::Paths(
+            stoat
+                .settings
+                .task_commands
+                .keys()
+                .map(PathBuf::from)
+                .collect(),
+        )),
+        ValueSource::CommandHistory => Some(ArgCandidates::Paths(
+            crate::command_log::lines(stoat)
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+        )),
+        ValueSource::BufferGroups => Some(ArgCandidates::Paths(
+            stoat
+                .active_workspace()
+                .buffers
+                .group_names()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+        )),
+        ValueSource::Notifications => Some(ArgCandidates::Paths(
+            stoat
+                .toasts
+                .history()
+                .map(|toast| PathBuf::from(toast.line()))
+                .collect(),
+        )),
+        ValueSource::Commands => Some(ArgCandidates::Paths(
+            stoat.settings.commands.keys().map(PathBuf::from).collect(),
+        )),
         ValueSource::Values(values) => Some(ArgCandidates::Paths(
             values.iter().map(PathBuf::from).collect(),
         )),
@@ -280,12 +315,6 @@ pub(super) fn palette_submit(stoat: &mut Stoat) -> Option<UpdateEffect> {
     Some(apply_outcome(stoat, outcome))
 }
 
-/// Cancel the currently open palette, closing it and restoring the previous
-/// mode.
-pub(super) fn palette_cancel(stoat: &mut Stoat) -> Option<UpdateEffect> {
-    close_palette(stoat).then_some(UpdateEffect::Redraw)
-}
-
 /// Insert a literal newline in the palette's active [`InputView`].
 pub(super) fn palette_insert_newline(stoat: &mut Stoat) -> Option<UpdateEffect> {
     stoat.command_palette.as_ref()?;
@@ -487,8 +516,12 @@ fn apply_outcome(stoat: &mut Stoat, outcome: PaletteOutcome) -> UpdateEffect {
             UpdateEffect::Redraw
         },
         PaletteOutcome::Dispatch(entry, params, line) => {
-            stoat.active_workspace_mut().palette_history.push(line);
+            stoat
+                .active_workspace_mut()
+                .palette_history
+                .push(line.clone());
             close_palette(stoat);
+            crate::command_log::record(stoat, entry.def, &params, line);
             match (entry.create)(&params) {
                 Ok(action) => super::dispatch(stoat, &*action),
                 Err(e) => {
@@ -500,8 +533,23 @@ fn apply_outcome(stoat: &mut Stoat, outcome: PaletteOutcome) -> UpdateEffect {
     }
 }
 
-/// Close the palette, disposing its [`InputView`] and restoring the saved
-/// pre-palette mode. Returns `true` if a palette was open, `false` otherwise.
+/// Close the palette without running anything, stashing its query as the
+/// [`DraftKey::Palette`] draft the next [`ActionKind::OpenCommandPalette`]
+/// restores.
+///
+/// [`ActionKind::OpenCommandPalette`]: stoat_action::ActionKind::OpenCommandPalette
+pub(crate) fn dismiss_palette(stoat: &mut Stoat) -> bool {
+    let Some(palette) = stoat.command_palette.as_ref() else {
+        return false;
+    };
+    let text = palette.input.text(stoat.active_workspace());
+    stoat.input_drafts.stash(DraftKey::Palette, text);
+    close_palette(stoat)
+}
+
+/// Close the palette, disposing its [`InputView`] and popping it off the
+/// [`crate::overlay`] stack, which restores the mode it opened over. Returns
+/// `true` if a palette was open, `false` otherwise.
 fn close_palette(stoat: &mut Stoat) -> bool {
     let Some(palette) = stoat.command_palette.take() else {
         return false;
@@ -512,6 +560,7 @@ fn close_palette(stoat: &mut Stoat) -> bool {
         palette.dispose(&mut workspaces[active_idx]);
         workspaces[active_idx].palette_history.reset();
     }
+    crate::overlay::pop(stoat, Overlay::CommandPalette);
     true
 }
 
@@ -118,8 +118,9 @@ pub(crate) enum EditorDisposal {
     GcIfUnreferenced,
 }
 
-/// Release whatever `view` owned, which is an editor, a run's shell, or a
-/// terminal's PTY child. A label owns nothing.
+/// Release whatever `view` owned, which is an editor, a run's shell, a
+/// terminal's PTY child, a hex view's bytes, or a regex playground's inputs. A
+/// label owns nothing.
 ///
 /// Shared by pane close and tab close, which differ only in how they treat the
 /// editor. Killing a PTY is spawned onto `executor` rather than awaited, so the
@@ -171,6 +172,14 @@ pub(crate) fn dispose_view(
                     .detach();
             }
         },
+        View::Hex(id) => {
+            ws.hex_views.remove(id);
+        },
+        View::RegexPlayground(id) => {
+            if let Some(playground) = ws.regex_playgrounds.remove(id) {
+                playground.dispose(ws);
+            }
+        },
         View::Label(_) => {},
     }
 }
@@ -193,6 +202,8 @@ pub(crate) fn restore_pane_after_term_exit(stoat: &mut Stoat, pane_id: PaneId) {
         View::Editor(id) => ws.editors.contains_key(*id),
         View::Run(id) => ws.runs.contains_key(*id),
         View::Agent(id) | View::Terminal(id) => ws.terms.contains_key(*id),
+        View::Hex(id) => ws.hex_views.contains_key(*id),
+        View::RegexPlayground(id) => ws.regex_playgrounds.contains_key(*id),
         View::Label(_) => false,
     });
 
@@ -0,0 +1,104 @@
+use crate::app::{Stoat, UpdateEffect};
+
+/// Show or hide the perf overlay.
+pub(super) fn toggle_overlay(stoat: &mut Stoat) -> UpdateEffect {
+    #[cfg(feature = "perf")]
+    {
+        stoat.perf_overlay = !stoat.perf_overlay;
+    }
+    #[cfg(not(feature = "perf"))]
+    stoat.set_status("perf overlay unavailable: built without the perf feature");
+    UpdateEffect::Redraw
+}
+
+/// Start a profile capture, or finish the running one and write it to the log
+/// directory as a Chrome trace.
+pub(super) fn toggle_capture(stoat: &mut Stoat) -> UpdateEffect {
+    #[cfg(feature = "perf")]
+    {
+        let Some(capture) = stoat.perf.finish_capture() else {
+            stoat.perf.start_capture();
+            stoat.set_status("profile capture started; run it again to write the trace");
+            return UpdateEffect::Redraw;
+        };
+        let message = match stoat_log::log_dir() {
+            Ok(dir) => write_trace(stoat, &dir, &capture),
+            Err(e) => format!("profile not written: {e}"),
+        };
+        stoat.set_status(message);
+    }
+    #[cfg(not(feature = "perf"))]
+    stoat.set_status("profile capture unavailable: built without the perf feature");
+    UpdateEffect::Redraw
+}
+
+/// Write `capture` into `dir` as `profile-<unix seconds>.json` and describe
+/// the outcome for the status line.
+#[cfg(feature = "perf")]
+fn write_trace(stoat: &Stoat, dir: &std::path::Path, capture: &crate::perf::Capture) -> String {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let path = dir.join(format!("profile-{stamp}.json"));
+    let written = stoat.fs_host.create_dir_all(dir).and_then(|()| {
+        stoat
+            .fs_host
+            .write(&path, capture.to_chrome_trace().as_bytes())
+    });
+    match written {
+        Ok(()) => format!(
+            "profile: {} events over {:.1}s written to {}",
+            capture.len(),
+            capture.duration().as_secs_f64(),
+            path.display()
+        ),
+        Err(e) => format!("profile not written to {}: {e}", path.display()),
+    }
+}
+
+#[cfg(all(test, feature = "perf"))]
+mod tests {
+    use super::*;
+    use crate::test_harness::TestHarness;
+    use std::time::Duration;
+
+    #[test]
+    fn overlay_paints_the_recorded_metrics() {
+        let mut h = TestHarness::with_size(80, 24);
+        h.stoat.perf.record_frame(Duration::from_micros(900));
+        h.stoat.perf.record_layout(Duration::from_micros(40));
+        toggle_overlay(&mut h.stoat);
+
+        h.snapshot();
+        let text = h.rendered_text();
+        assert!(text.contains(" perf "), "{text}");
+        assert!(text.contains("frame     900"), "{text}");
+        assert!(text.contains("layout"), "{text}");
+
+        toggle_overlay(&mut h.stoat);
+        h.snapshot();
+        assert!(!h.rendered_text().contains(" perf "));
+    }
+
+    #[test]
+    fn finished_capture_is_written_as_a_chrome_trace() {
+        let mut h = TestHarness::with_size(80, 24);
+        toggle_capture(&mut h.stoat);
+        assert!(h.stoat.perf.is_capturing());
+        h.stoat.perf.record_paint(Duration::from_micros(10));
+
+        let capture = h.stoat.perf.finish_capture().expect("capture running");
+        let dir = std::path::Path::new("/state/stoat/logs");
+        let message = write_trace(&h.stoat, dir, &capture);
+
+        assert!(message.starts_with("profile: 1 events"), "{message}");
+        let path = message.rsplit(" written to ").next().expect("path");
+        let mut bytes = Vec::new();
+        h.stoat
+            .fs_host
+            .read(std::path::Path::new(path), &mut bytes)
+            .expect("trace written");
+        let trace: serde_json::Value = serde_json::from_slice(&bytes).expect("json");
+        assert_eq!(trace["traceEvents"][0]["name"], "paint");
+    }
+}
@@ -39,6 +39,36 @@ pub(super) fn jumplist_picker_select(stoat: &mut Stoat) -> UpdateEffect {
     UpdateEffect::Redraw
 }
 
+pub(super) fn undo_tree_next(stoat: &mut Stoat) -> UpdateEffect {
+    if let Some(picker) = stoat.undo_tree_picker.as_mut() {
+        picker.select_next();
+    }
+    UpdateEffect::Redraw
+}
+
+pub(super) fn undo_tree_prev(stoat: &mut Stoat) -> UpdateEffect {
+    if let Some(picker) = stoat.undo_tree_picker.as_mut() {
+        picker.select_prev();
+    }
+    UpdateEffect::Redraw
+}
+
+pub(super) fn undo_tree_close(stoat: &mut Stoat) -> UpdateEffect {
+    stoat.undo_tree_picker = None;
+    UpdateEffect::Redraw
+}
+
+/// Restore the focused buffer to the undo tree state under the selection,
+/// restoring the selections captured when that step was made.
+pub(super) fn undo_tree_select(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(picker) = stoat.undo_tree_picker.take() else {
+        return UpdateEffect::None;
+    };
+    let target = picker.selected_node();
+    super::movement::apply_buffer_history(stoat, 1, |buf| buf.restore(target));
+    UpdateEffect::Redraw
+}
+
 pub(super) fn diagnostics_picker_next(stoat: &mut Stoat) -> UpdateEffect {
     if let Some(picker) = stoat.diagnostics_picker.as_mut() {
         picker.select_next();
@@ -11,15 +11,27 @@ pub(super) fn submit_prompt_input(stoat: &mut Stoat) -> UpdateEffect {
     if super::code_search::code_search_select(stoat) {
         return UpdateEffect::Redraw;
     }
+    if super::regex_playground::submit(stoat) {
+        return UpdateEffect::Redraw;
+    }
     if super::split_selection::submit(stoat) {
         return UpdateEffect::Redraw;
     }
     if super::filter_selections::submit(stoat) {
         return UpdateEffect::Redraw;
     }
+    if super::surround::submit_tag(stoat) {
+        return UpdateEffect::Redraw;
+    }
     if super::shell::submit(stoat) {
         return UpdateEffect::Redraw;
     }
+    if crate::color_swatch::submit(stoat) {
+        return UpdateEffect::Redraw;
+    }
+    if crate::local_rename::submit(stoat) {
+        return UpdateEffect::Redraw;
+    }
     if super::lsp::rename_input_submit(stoat) {
         return UpdateEffect::Redraw;
     }
@@ -62,31 +74,38 @@ pub(super) fn cancel_prompt_input(stoat: &mut Stoat) -> UpdateEffect {
     if super::code_search::close_code_search(stoat) {
         return UpdateEffect::Redraw;
     }
+    if super::regex_playground::close(stoat) {
+        return UpdateEffect::Redraw;
+    }
     if super::split_selection::cancel(stoat) {
         return UpdateEffect::Redraw;
     }
     if super::filter_selections::cancel(stoat) {
         return UpdateEffect::Redraw;
     }
+    if super::surround::cancel_tag(stoat) {
+        return UpdateEffect::Redraw;
+    }
     if super::shell::cancel(stoat) {
         return UpdateEffect::Redraw;
     }
+    if crate::color_swatch::cancel(stoat) {
+        return UpdateEffect::Redraw;
+    }
+    if crate::local_rename::cancel(stoat) {
+        return UpdateEffect::Redraw;
+    }
     if super::lsp::rename_input_cancel(stoat) {
         return UpdateEffect::Redraw;
     }
     if let Some(effect) = super::lsp::symbol_finder_cancel(stoat) {
         return effect;
     }
-    if let Some(effect) = super::file_finder::file_finder_cancel(stoat) {
-        return effect;
-    }
-    if let Some(effect) = super::palette::palette_cancel(stoat) {
+    if let Some(effect) = crate::overlay::cancel_top(stoat) {
         return effect;
     }
-    // Help handles Escape in two stages. The first leaves the search input in
-    // normal sub-mode so the list can be navigated with hjkl, and a second
-    // Escape - routed via `modal == help && mode == normal { Escape -> ... }` -
-    // closes it. Every other input was disposed by a cancel above.
+    // Every input was disposed by a cancel above, so an insert mode left here
+    // belongs to a pane. Escape drops it to normal.
     if stoat.focused_mode() == "insert" {
         stoat.set_focused_mode("normal".into());
         return UpdateEffect::Redraw;
@@ -213,7 +213,12 @@ pub(super) fn drive_rebase(stoat: &mut Stoat) -> UpdateEffect {
                             match entry.op {
                                 RebaseTodoOp::Pick => continue,
                                 RebaseTodoOp::Reword => {
-                                    install_reword_pause(stoat, new_sha, message.clone());
+                                    install_reword_pause(
+                                        stoat,
+                                        new_sha,
+                                        entry.commit.sha.clone(),
+                                        message.clone(),
+                                    );
                                     return UpdateEffect::Redraw;
                                 },
                                 RebaseTodoOp::Edit => {
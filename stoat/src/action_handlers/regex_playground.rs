@@ -0,0 +1,124 @@
+use crate::{
+    app::{Stoat, UpdateEffect},
+    code_search::CodeSearchFinder,
+    pane::{Axis, FocusTarget, PaneId, View},
+    regex_playground::{PlaygroundField, PlaygroundId, RegexPlayground},
+};
+
+/// Open the regex playground in a new split pane beside the focused one, unless
+/// the focused pane already shows one.
+///
+/// The pattern is seeded from the last submitted search and the sample from the
+/// focused editor's primary selection, so a selection can be tested against
+/// the pattern that just found it while both stay on screen.
+pub(crate) fn open_regex_playground(stoat: &mut Stoat) -> UpdateEffect {
+    if focused_playground(stoat).is_some() {
+        return UpdateEffect::None;
+    }
+    let pattern = stoat
+        .last_search
+        .as_ref()
+        .map(|s| s.query.clone())
+        .unwrap_or_default();
+    let sample = focused_selection_text(stoat).unwrap_or_default();
+    let executor = stoat.executor.clone();
+    let ws = stoat.active_workspace_mut();
+    let playground = RegexPlayground::new(ws, executor, &pattern, &sample);
+    let id = ws.regex_playgrounds.insert(playground);
+    ws.focus = FocusTarget::SplitPane;
+    let pane_id = ws.panes.split(Axis::Vertical);
+    ws.panes.pane_mut(pane_id).view = View::RegexPlayground(id);
+    UpdateEffect::Redraw
+}
+
+/// The focused split pane and the playground it shows, or `None` when it shows
+/// something else.
+fn focused_playground(stoat: &Stoat) -> Option<(PaneId, PlaygroundId)> {
+    let ws = stoat.active_workspace();
+    if !matches!(ws.focus, FocusTarget::SplitPane) {
+        return None;
+    }
+    let pane_id = ws.panes.focus();
+    match ws.panes.pane(pane_id).view {
+        View::RegexPlayground(id) => Some((pane_id, id)),
+        _ => None,
+    }
+}
+
+/// Text under the focused editor's newest selection, or `None` when it is
+/// empty.
+fn focused_selection_text(stoat: &mut Stoat) -> Option<String> {
+    let editor = super::focused_editor_mut(stoat)?;
+    let snapshot = editor.display_map.snapshot();
+    let buffer_snapshot = snapshot.buffer_snapshot();
+    let sel = editor.selections.newest_anchor();
+    let start = buffer_snapshot.resolve_anchor(&sel.start);
+    let end = buffer_snapshot.resolve_anchor(&sel.end);
+    if end <= start {
+        return None;
+    }
+    Some(buffer_snapshot.rope().slice(start..end).to_string())
+}
+
+/// Move typing between the pattern and sample inputs.
+pub(crate) fn regex_playground_focus_toggle(stoat: &mut Stoat) -> UpdateEffect {
+    let Some((_, id)) = focused_playground(stoat) else {
+        return UpdateEffect::None;
+    };
+    let Some(playground) = stoat.active_workspace_mut().regex_playgrounds.get_mut(id) else {
+        return UpdateEffect::None;
+    };
+    playground.toggle_focus();
+    UpdateEffect::Redraw
+}
+
+/// Route Enter inside a focused playground pane. Returns whether one was
+/// focused.
+///
+/// In the sample pane Enter breaks the line, since the sample is multi-line
+/// text. On the pattern it closes the playground and opens workspace code
+/// search seeded with the pattern, so a pattern proven here runs project-wide
+/// without retyping. An empty pattern just closes.
+pub(crate) fn submit(stoat: &mut Stoat) -> bool {
+    let Some((_, id)) = focused_playground(stoat) else {
+        return false;
+    };
+    let ws = stoat.active_workspace_mut();
+    let Some(playground) = ws.regex_playgrounds.get(id) else {
+        return false;
+    };
+    if playground.focus == PlaygroundField::Sample {
+        let sample = playground.sample.clone();
+        sample.insert_at_cursor(ws, "\n");
+        return true;
+    }
+
+    let pattern = playground.pattern.text(ws);
+    close(stoat);
+    if pattern.is_empty() || stoat.code_search.is_some() {
+        return true;
+    }
+    let target_lang = super::code_search::focused_buffer_language(stoat);
+    let executor = stoat.executor.clone();
+    let ws = stoat.active_workspace_mut();
+    stoat.code_search = Some(CodeSearchFinder::new(ws, executor, target_lang, &pattern));
+    true
+}
+
+/// Close the focused playground pane, disposing both inputs. Returns whether
+/// one was focused.
+pub(crate) fn close(stoat: &mut Stoat) -> bool {
+    let Some((pane_id, id)) = focused_playground(stoat) else {
+        return false;
+    };
+    if !super::pane::close_pane_by_id(stoat, pane_id) {
+        // The last split pane cannot close, so it falls back to a scratch
+        // editor instead.
+        let ws = stoat.active_workspace_mut();
+        if let Some(playground) = ws.regex_playgrounds.remove(id) {
+            playground.dispose(ws);
+        }
+        super::pane::restore_pane_after_term_exit(stoat, pane_id);
+    }
+    true
+}
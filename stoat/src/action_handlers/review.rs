@@ -1,6 +1,8 @@
 use super::movement::ChangeDir;
 use crate::{
+    agent_ipc::ProposedEdit,
     app::{Stoat, UpdateEffect},
+    buffer::BufferId,
     diff_cache::{DiffCache, DiffCacheKey},
     display_map::{BlockPlacement, BlockProperties, BlockStyle, RenderBlock},
     editor_state::{EditorId, EditorState, ScrollGlide},
@@ -11,13 +13,15 @@ use crate::{
     review_session::{
         ChunkIdentity, ChunkStatus, ReviewProgress, ReviewSession, ReviewSource, ReviewViewState,
     },
-    workspace::Workspace,
+    workspace::{Workspace, WorkspaceUid},
 };
 use ratatui::{
     style::{Color, Style},
     text::Line,
 };
+use serde_json::{json, Value};
 use std::{
+    ops::Range,
     path::Path,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -26,7 +30,8 @@ use std::{
 };
 use stoat_language::{Language, LanguageRegistry};
 use stoat_scheduler::Task;
-use stoat_text::{Bias, Point, SelectionGoal};
+use stoat_text::{Bias, LineEnding, Point, SelectionGoal};
+use tokio::sync::oneshot;
 
 /// A message streamed from a running review scan.
 ///
@@ -397,6 +402,60 @@ pub(super) fn open_review_agent_edits(stoat: &mut Stoat, edits: &[stoat_action::
     install_review_session(stoat, session);
 }
 
+/// Open the files an owned agent proposed over IPC as an agent-edit review in
+/// the workspace whose session matches `uid`, replying with how many files
+/// differ from disk.
+///
+/// Each proposal's base is the file's current disk text, or empty when the
+/// file does not exist yet. A file that cannot be read as text is left out.
+/// Nothing is written here. [`review_apply_staged`] writes the chunks the user
+/// accepts.
+pub(crate) fn propose_agent_edits(
+    stoat: &mut Stoat,
+    uid: WorkspaceUid,
+    edits: Vec<ProposedEdit>,
+    reply: oneshot::Sender<Value>,
+) -> UpdateEffect {
+    let Some(ws_id) = stoat
+        .workspaces
+        .iter()
+        .find(|(_, ws)| ws.uid == uid)
+        .map(|(id, _)| id)
+    else {
+        let _ = reply.send(json!({ "error": "no session" }));
+        return UpdateEffect::None;
+    };
+    stoat.active_workspace = ws_id;
+
+    let root = stoat.active_workspace().git_root.clone();
+    let edits: Vec<stoat_action::AgentEdit> = edits
+        .into_iter()
+        .filter_map(|edit| {
+            let path = root.join(&edit.path);
+            let base = disk_text(stoat, &path).ok()?;
+            (base != edit.text).then(|| stoat_action::AgentEdit {
+                path,
+                base_text: Arc::new(base),
+                proposed_text: Arc::new(edit.text),
+            })
+        })
+        .collect();
+    let files = edits.len();
+    if files > 0 {
+        open_review_agent_edits(stoat, &edits);
+    }
+    let _ = reply.send(json!({ "files": files }));
+    UpdateEffect::Redraw
+}
+
+/// Text of `path` on disk, or empty when it does not exist.
+fn disk_text(stoat: &Stoat, path: &Path) -> std::io::Result<String> {
+    match super::read_string_via_host(&*stoat.fs_host, path) {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        result => result,
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub(super) enum ReviewStep {
     Next,
@@ -585,6 +644,15 @@ pub(super) fn review_apply_staged(stoat: &mut Stoat) -> UpdateEffect {
         review_session::{ChunkStatus, ReviewChunkId},
     };
 
+    let agent_edits = stoat
+        .active_workspace()
+        .review
+        .as_ref()
+        .is_some_and(|s| matches!(s.source, ReviewSource::AgentEdits { .. }));
+    if agent_edits {
+        return apply_agent_edits(stoat);
+    }
+
     let (staged, workdir): (Vec<(ReviewChunkId, String)>, std::path::PathBuf) = {
         let ws = stoat.active_workspace();
         let Some(session) = ws.review.as_ref() else {
@@ -666,6 +734,135 @@ pub(super) fn review_apply_staged(stoat: &mut Stoat) -> UpdateEffect {
     UpdateEffect::Redraw
 }
 
+/// Write the accepted chunks of an agent-edit review to disk.
+///
+/// Proposals stay in memory until this runs. Per file, staged chunks are
+/// written over the base the agent proposed against, unstaged chunks are
+/// dropped as rejected, and pending or skipped chunks stay in the reinstalled
+/// review as a smaller proposal. A file whose disk text no longer matches that
+/// base is not written, so an approval never clobbers an edit made since.
+fn apply_agent_edits(stoat: &mut Stoat) -> UpdateEffect {
+    use crate::{
+        badge::{Anchor, Badge, BadgeSource, BadgeState},
+        review_apply::remove_chunks_from_buffer,
+        review_session::{AgentEditProposal, ReviewChunk},
+    };
+
+    struct FileOutcome {
+        path: std::path::PathBuf,
+        base: Arc<String>,
+        accepted: String,
+        proposed: String,
+    }
+
+    let outcomes: Vec<FileOutcome> = {
+        let Some(session) = stoat.active_workspace().review.as_ref() else {
+            return UpdateEffect::None;
+        };
+        session
+            .files
+            .iter()
+            .map(|file| {
+                let chunks: Vec<&ReviewChunk> = file
+                    .chunks
+                    .iter()
+                    .filter_map(|id| session.chunks.get(id))
+                    .collect();
+                let not_accepted: Vec<&ReviewChunk> = chunks
+                    .iter()
+                    .copied()
+                    .filter(|c| c.status != ChunkStatus::Staged)
+                    .collect();
+                let rejected: Vec<&ReviewChunk> = chunks
+                    .iter()
+                    .copied()
+                    .filter(|c| c.status == ChunkStatus::Unstaged)
+                    .collect();
+                FileOutcome {
+                    path: file.path.clone(),
+                    base: file.base_text.clone(),
+                    accepted: remove_chunks_from_buffer(
+                        &file.base_text,
+                        &file.buffer_text,
+                        &not_accepted,
+                    ),
+                    proposed: remove_chunks_from_buffer(
+                        &file.base_text,
+                        &file.buffer_text,
+                        &rejected,
+                    ),
+                }
+            })
+            .collect()
+    };
+
+    let mut written = 0usize;
+    let mut failures: Vec<String> = Vec::new();
+    let mut remaining: Vec<AgentEditProposal> = Vec::new();
+    for outcome in outcomes {
+        let mut base = outcome.base;
+        if outcome.accepted != *base {
+            let current = disk_text(stoat, &outcome.path);
+            let result = match current {
+                Ok(current) if current == *base => {
+                    if let Some(parent) = outcome.path.parent() {
+                        let _ = stoat.fs_host.create_dir_all(parent);
+                    }
+                    stoat
+                        .fs_host
+                        .write_atomic(&outcome.path, outcome.accepted.as_bytes())
+                        .map_err(|err| err.to_string())
+                },
+                Ok(_) => Err("changed on disk since the proposal".to_string()),
+                Err(err) => Err(err.to_string()),
+            };
+            match result {
+                Ok(()) => {
+                    written += 1;
+                    base = Arc::new(outcome.accepted);
+                },
+                Err(reason) => failures.push(format!("{}: {reason}", outcome.path.display())),
+            }
+        }
+        if *base != outcome.proposed {
+            remaining.push(AgentEditProposal {
+                path: outcome.path,
+                base_text: base,
+                proposed_text: Arc::new(outcome.proposed),
+            });
+        }
+    }
+
+    if written == 0 && failures.is_empty() {
+        tracing::info!("ReviewApplyStaged: nothing accepted");
+        return UpdateEffect::None;
+    }
+
+    let effect = match scan_agent_edits(stoat, &remaining) {
+        Some(session) => {
+            install_review_session(stoat, session);
+            UpdateEffect::Redraw
+        },
+        None => close_review(stoat),
+    };
+
+    let ws = stoat.active_workspace_mut();
+    ws.badges.remove_by_source(BadgeSource::Review);
+    let (state, detail) = if failures.is_empty() {
+        (BadgeState::Complete, None)
+    } else {
+        (BadgeState::Error, failures.first().cloned())
+    };
+    ws.badges.insert(Badge {
+        source: BadgeSource::Review,
+        anchor: Anchor::BottomRight,
+        state,
+        label: format!("wrote {written} file{}", plural(written)),
+        detail,
+    });
+    effect
+}
+
 fn plural(n: usize) -> &'static str {
     if n == 1 {
         ""
@@ -939,21 +1136,14 @@ pub(super) fn toggle_diff_view(stoat: &mut Stoat) {
         editor.set_diff_view(on);
         on
     };
-
-    // Give the diff its own full width. Opening widens the focused pane when the
-    // layout allows a clean cover. An unwidenable layout stays put and rides the
-    // unified fallback. Closing restores only when this pane is the widened one.
-    {
-        let panes = &mut stoat.active_workspace_mut().panes;
-        let focus = panes.focus();
-        if turned_on {
-            panes.widen(focus);
-        } else if panes.widened() == Some(focus) {
-            panes.unwiden();
-        }
-    }
+    widen_for_diff_view(stoat, turned_on);
 
     if !turned_on {
+        // A buffer-pair diff ends with its view, handing the buffer back to
+        // the HEAD diff.
+        stoat
+            .active_workspace_mut()
+            .clear_buffer_diff_base(buffer_id);
         return;
     }
 
@@ -961,12 +1151,17 @@ pub(super) fn toggle_diff_view(stoat: &mut Stoat) {
     // marks), so only compute it here when the fast path is empty. A map left
     // over from before an external git mutation counts as empty. It describes a
     // base that has since moved, so trusting it would open the view on hunks
-    // measured against a HEAD that no longer exists.
+    // measured against a HEAD that no longer exists. A buffer-pair map is kept
+    // current by the pair sync instead.
+    let paired = stoat
+        .active_workspace()
+        .buffer_diff_bases
+        .contains_key(&buffer_id);
     let map_current = stoat.active_workspace().diff_map_current(buffer_id);
     let has_map = super::focused_editor_mut(stoat)
         .map(|editor| editor.display_map.snapshot().diff_map().is_some())
         .unwrap_or(false);
-    if !has_map || !map_current {
+    if !paired && (!has_map || !map_current) {
         let git_host = stoat.git_host.clone();
         let language_registry = stoat.language_registry.clone();
         let syntax_styles = stoat.syntax_styles.clone();
@@ -980,6 +1175,40 @@ pub(super) fn toggle_diff_view(stoat: &mut Stoat) {
         );
     }
 
+    let jumped = land_on_first_hunk(stoat);
+    if jumped && let Some(entry) = origin {
+        super::jump::push_entry(stoat, entry);
+    }
+
+    // A buffer with no changes of its own has no hunk to land on, so cross into
+    // the first changed file. This makes `:diff` from a scratch or unchanged
+    // buffer open a real diff instead of silently toggling empty columns.
+    if !jumped {
+        let _ = super::movement::goto_change(stoat, ChangeDir::Next);
+    }
+}
+
+/// Give the diff view its own full width. Opening widens the focused pane when
+/// the layout allows a clean cover. An unwidenable layout stays put and rides
+/// the unified fallback. Closing restores only when this pane is the widened
+/// one.
+fn widen_for_diff_view(stoat: &mut Stoat, turned_on: bool) {
+    let panes = &mut stoat.active_workspace_mut().panes;
+    let focus = panes.focus();
+    if turned_on {
+        panes.widen(focus);
+    } else if panes.widened() == Some(focus) {
+        panes.unwiden();
+    }
+}
+
+/// Land the focused editor's cursor on its buffer's first diff hunk and pull
+/// the view onto it. Returns false, leaving the cursor alone, when the buffer
+/// has no hunk.
+///
+/// The scroll matters for a non-key dispatch (the `stoat review` startup, a
+/// mouse palette accept), which does not run the Key-event epilogue.
+fn land_on_first_hunk(stoat: &mut Stoat) -> bool {
     let jumped = super::focused_editor_mut(stoat).is_some_and(|editor| {
         let display_snapshot = editor.display_map.snapshot();
         let buffer_snapshot = display_snapshot.buffer_snapshot();
@@ -1006,26 +1235,80 @@ pub(super) fn toggle_diff_view(stoat: &mut Stoat) {
         });
         true
     });
-
-    // The jump only moves the selection. Pull the view onto it here so a
-    // non-key dispatch (the `stoat review` startup, a mouse palette accept)
-    // lands scrolled, not relying on the Key-event epilogue.
     if jumped {
         let scrolloff = stoat.settings.scrolloff.unwrap_or(3);
         if let Some(editor) = super::focused_editor_mut(stoat) {
             super::movement::ensure_cursor_in_view(editor, scrolloff);
         }
     }
+    jumped
+}
 
-    if jumped && let Some(entry) = origin {
-        super::jump::push_entry(stoat, entry);
+/// Open the side-by-side diff of the focused buffer against the buffer at
+/// `path`, driven by [`stoat_action::DiffWithBuffer`].
+///
+/// The named buffer fills the left column and the focused one the right, with
+/// hunks aligned through the same deleted-block splicing as the HEAD diff.
+/// Both columns live in the one editor, so they scroll together. A file that
+/// is not open yet is read into a buffer without being shown. The pair stays
+/// in [`crate::workspace::Workspace::buffer_diff_bases`] until [`toggle_diff_view`]
+/// closes the view, re-diffing after edits to either side.
+pub(super) fn diff_with_buffer(stoat: &mut Stoat, path: &Path) {
+    let origin = super::jump::live_entry(stoat);
+    let Some(buffer_id) = super::focused_editor_mut(stoat).map(|editor| editor.buffer_id) else {
+        return;
+    };
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        stoat.active_workspace().git_root.join(path)
+    };
+    let base_id = match stoat.active_workspace().buffers.id_for_path(&absolute) {
+        Some(id) => id,
+        None => match super::read_string_via_host(&*stoat.fs_host, &absolute) {
+            Ok(text) => {
+                stoat
+                    .active_workspace_mut()
+                    .buffers
+                    .open(&absolute, &text)
+                    .0
+            },
+            Err(err) => {
+                stoat.set_status(format!("diff-with: {}: {err}", absolute.display()));
+                return;
+            },
+        },
+    };
+    if base_id == buffer_id {
+        stoat.set_status("diff-with: cannot diff a buffer against itself");
+        return;
     }
 
-    // A buffer with no changes of its own has no hunk to land on, so cross into
-    // the first changed file. This makes `:diff` from a scratch or unchanged
-    // buffer open a real diff instead of silently toggling empty columns.
-    if !jumped {
-        let _ = super::movement::goto_change(stoat, ChangeDir::Next);
+    let language_registry = stoat.language_registry.clone();
+    let syntax_styles = stoat.syntax_styles.clone();
+    let base_cache = stoat.base_highlights_cache.clone();
+    stoat.active_workspace_mut().set_buffer_diff_base(
+        buffer_id,
+        base_id,
+        &language_registry,
+        &syntax_styles,
+        &base_cache,
+    );
+
+    let was_on = super::focused_editor_mut(stoat).is_some_and(|editor| {
+        let was_on = editor.diff_view;
+        editor.set_diff_view(true);
+        was_on
+    });
+    if !was_on {
+        widen_for_diff_view(stoat, true);
+    }
+    if land_on_first_hunk(stoat) {
+        if let Some(entry) = origin {
+            super::jump::push_entry(stoat, entry);
+        }
+    } else {
+        stoat.set_status("diff-with: buffers are identical");
     }
 }
 
@@ -1065,6 +1348,11 @@ pub(super) fn stage_hunk(stoat: &mut Stoat, mode: HunkStage) -> UpdateEffect {
         let cursor_row = buffer_snapshot.rope().offset_to_point(head).row;
         (cursor_row, buffer_snapshot.rope().to_string())
     };
+    // Diff the text as it is saved, so hunks line up with HEAD and the index
+    // and the patch carries the file's own line breaks.
+    let buffer_text = buffer_line_ending(stoat, buffer_id)
+        .apply(&buffer_text)
+        .into_owned();
 
     let Some(path) = stoat
         .active_workspace()
@@ -1140,6 +1428,161 @@ pub(super) fn stage_hunk(stoat: &mut Stoat, mode: HunkStage) -> UpdateEffect {
     UpdateEffect::Redraw
 }
 
+/// Restore the HEAD version of the hunk under the cursor into the focused
+/// buffer, leaving the rest of the working copy and the git index untouched.
+///
+/// The hunk is found as in [`stage_hunk`], by diffing HEAD against the live
+/// buffer, and its buffer span is spliced back to the base span in one edit so
+/// the dispatch-level undo group makes the revert a single undo step. A
+/// pure-deletion hunk has no buffer rows, so it is found on the row its removed
+/// lines sat above, where the gutter marks it, and reverting re-inserts them
+/// there. A missing repo, a file not tracked at HEAD, or a cursor on no hunk
+/// sets a status message and changes nothing.
+pub(super) fn revert_hunk(stoat: &mut Stoat) -> UpdateEffect {
+    let Some((editor_id, buffer_id)) = stoat.focused_editor_ids() else {
+        return UpdateEffect::None;
+    };
+
+    let (cursor_row, buffer_text) = {
+        let Some(editor) = super::focused_editor_mut(stoat) else {
+            return UpdateEffect::None;
+        };
+        let snapshot = editor.display_map.snapshot();
+        let buffer_snapshot = snapshot.buffer_snapshot();
+        let sel = editor.selections.newest_anchor().clone();
+        let head = buffer_snapshot.resolve_anchor(&sel.head());
+        let cursor_row = buffer_snapshot.rope().offset_to_point(head).row;
+        (cursor_row, buffer_snapshot.rope().to_string())
+    };
+
+    let Some(path) = stoat
+        .active_workspace()
+        .buffers
+        .path_for(buffer_id)
+        .map(Path::to_path_buf)
+    else {
+        return UpdateEffect::None;
+    };
+    let git_root = stoat.active_workspace().git_root.clone();
+
+    let Some(repo) = stoat.git_host.discover(&git_root) else {
+        stoat.set_status("not in a git repository");
+        return UpdateEffect::Redraw;
+    };
+    let Some(base_text) = repo.head_content(&path) else {
+        stoat.set_status("file is not tracked at HEAD");
+        return UpdateEffect::Redraw;
+    };
+    // The revert splices HEAD text into the buffer, so it is diffed in the
+    // buffer's own form.
+    let base_text = buffer_line_ending(stoat, buffer_id)
+        .normalize(&base_text)
+        .into_owned();
+
+    let rel = path
+        .strip_prefix(&git_root)
+        .unwrap_or(&path)
+        .to_string_lossy()
+        .into_owned();
+    let mut session = ReviewSession::new(ReviewSource::InMemory {
+        files: Arc::new(Vec::new()),
+    });
+    session.add_files(vec![ReviewFileInput {
+        path,
+        rel_path: rel,
+        language: None,
+        base_text: Arc::new(base_text),
+        buffer_text: Arc::new(buffer_text),
+    }]);
+
+    let Some((range, replacement)) = revert_target(&session, cursor_row) else {
+        stoat.set_status("no hunk under the cursor");
+        return UpdateEffect::Redraw;
+    };
+
+    let Some(buffer) = stoat.active_workspace().buffers.get(buffer_id) else {
+        return UpdateEffect::None;
+    };
+    let landing = range.start;
+    buffer
+        .write()
+        .expect("buffer poisoned")
+        .edit(range, &replacement);
+
+    if let Some(editor) = stoat.active_workspace_mut().editors.get_mut(editor_id) {
+        let snapshot = editor.display_map.snapshot();
+        let buffer_snapshot = snapshot.buffer_snapshot();
+        editor.selections.transform(buffer_snapshot, |sel| {
+            super::movement::land_block_cursor(
+                sel.id,
+                landing,
+                SelectionGoal::None,
+                buffer_snapshot.rope(),
+                buffer_snapshot,
+            )
+        });
+    }
+
+    stoat.active_workspace_mut().invalidate_diff(buffer_id);
+    stoat.set_status("reverted hunk");
+    UpdateEffect::Redraw
+}
+
+/// The line ending `buffer_id` is saved with, the default for a buffer that
+/// has closed.
+fn buffer_line_ending(stoat: &Stoat, buffer_id: BufferId) -> LineEnding {
+    stoat
+        .active_workspace()
+        .buffers
+        .get(buffer_id)
+        .map(|buffer| buffer.read().expect("buffer poisoned").line_ending())
+        .unwrap_or_default()
+}
+
+/// The buffer byte range [`revert_hunk`] replaces and the HEAD text it puts
+/// back, for the chunk of a single-file `session` under `cursor_row`.
+///
+/// A pure-deletion chunk carries an empty buffer range, so its anchor row is
+/// rebuilt from its base rows plus the line delta of every chunk above it. The
+/// cursor matches it on that anchor, clamped to the last line so a deletion at
+/// the end of the file stays reachable, and the range is the empty span at the
+/// anchor's line start.
+fn revert_target(session: &ReviewSession, cursor_row: u32) -> Option<(Range<usize>, String)> {
+    let mut shift: i64 = 0;
+    for id in &session.order {
+        let chunk = &session.chunks[id];
+        let file = &session.files[chunk.file_index];
+        let buffer_len = chunk.buffer_line_range.len() as i64;
+        let base_len = chunk.base_line_range.len() as i64;
+        let range = if buffer_len > 0 {
+            chunk
+                .buffer_line_range
+                .contains(&cursor_row)
+                .then(|| chunk.buffer_byte_range.clone())
+        } else {
+            let anchor = (chunk.base_line_range.start as i64 + shift).max(0) as usize;
+            let last_row = file.buffer_text.lines().count().saturating_sub(1);
+            (anchor.min(last_row) == cursor_row as usize).then(|| {
+                let start: usize = file
+                    .buffer_text
+                    .split_inclusive('\n')
+                    .take(anchor)
+                    .map(str::len)
+                    .sum();
+                start..start
+            })
+        };
+        if let Some(range) = range {
+            return Some((
+                range,
+                file.base_text[chunk.base_byte_range.clone()].to_string(),
+            ));
+        }
+        shift += buffer_len - base_len;
+    }
+    None
+}
+
 /// Stage, unstage, or toggle the git-index state of only the cursor line's
 /// change, in the focused editor on any git-tracked file.
 ///
@@ -1164,6 +1607,10 @@ pub(super) fn stage_line(stoat: &mut Stoat, mode: HunkStage) -> UpdateEffect {
         let cursor_row = buffer_snapshot.rope().offset_to_point(head).row;
         (cursor_row, buffer_snapshot.rope().to_string())
     };
+    // Diff the text as it is saved, as in `stage_hunk`.
+    let buffer_text = buffer_line_ending(stoat, buffer_id)
+        .apply(&buffer_text)
+        .into_owned();
 
     let Some(path) = stoat
         .active_workspace()
@@ -2176,6 +2623,51 @@ mod tests {
     };
     use std::path::{Path, PathBuf};
 
+    #[test]
+    fn proposed_agent_edits_write_only_accepted_chunks() {
+        use crate::{
+            action_handlers::read_string_via_host,
+            agent_ipc::{AgentControl, ProposedEdit},
+            test_harness::{REVIEW_TWO_HUNK_BASE, REVIEW_TWO_HUNK_BUFFER},
+        };
+
+        let mut h = TestHarness::with_size(80, 14);
+        let path = PathBuf::from("/proj/a.rs");
+        h.fake_fs().insert_file(&path, REVIEW_TWO_HUNK_BASE);
+        let uid = h.stoat.active_workspace().uid();
+        let (reply_tx, mut reply_rx) = tokio::sync::oneshot::channel();
+        h.stoat.handle_agent_control(AgentControl::ProposeEdits {
+            uid,
+            edits: vec![ProposedEdit {
+                path: path.clone(),
+                text: REVIEW_TWO_HUNK_BUFFER.to_string(),
+            }],
+            reply: reply_tx,
+        });
+        h.settle();
+        assert_eq!(
+            reply_rx.try_recv().unwrap(),
+            serde_json::json!({ "files": 1 })
+        );
+        let read = |h: &TestHarness| read_string_via_host(&**h.fake_fs(), &path).unwrap();
+        assert_eq!(read(&h), REVIEW_TWO_HUNK_BASE, "proposing writes nothing");
+
+        h.set_review_status(0, ChunkStatus::Staged);
+        h.set_review_status(1, ChunkStatus::Unstaged);
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::ReviewApplyStaged);
+        h.settle();
+
+        assert_eq!(
+            read(&h),
+            REVIEW_TWO_HUNK_BASE.replacen("a\n", "A\n", 1),
+            "only the accepted first chunk lands"
+        );
+        assert!(
+            h.stoat.active_workspace().review.is_none(),
+            "every chunk was decided, so the review closes"
+        );
+    }
+
     #[test]
     fn install_review_session_populates_diff_cache() {
         let mut h = TestHarness::with_size(80, 10);
@@ -2424,6 +2916,110 @@ mod tests {
         );
     }
 
+    /// Open `/pair/left.txt` and then `/pair/right.txt`, leaving the right
+    /// one focused, and diff it against the left.
+    fn open_buffer_pair(h: &mut TestHarness, left: &str, right: &str) {
+        h.stoat.active_workspace_mut().git_root = PathBuf::from("/pair");
+        h.fake_fs().insert_file("/pair/left.txt", left);
+        h.fake_fs().insert_file("/pair/right.txt", right);
+        h.open_file(Path::new("/pair/left.txt"));
+        h.open_file(Path::new("/pair/right.txt"));
+        crate::action_handlers::dispatch(
+            &mut h.stoat,
+            &stoat_action::DiffWithBuffer {
+                path: PathBuf::from("left.txt"),
+            },
+        );
+    }
+
+    fn focused_hunk_count(h: &mut TestHarness) -> usize {
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        editor
+            .display_map
+            .snapshot()
+            .diff_map()
+            .map_or(0, |dm| dm.hunks_in_range(0..u32::MAX).len())
+    }
+
+    #[test]
+    fn diff_with_buffer_opens_the_pair_on_its_first_hunk() {
+        let mut h = TestHarness::with_size(120, 14);
+        open_buffer_pair(&mut h, "keep\nold\ntail\nend\n", "keep\nnew\ntail\nEND\n");
+
+        let diff_view = crate::action_handlers::focused_editor_mut(&mut h.stoat)
+            .expect("editor")
+            .diff_view;
+        assert!(diff_view, "the focused editor shows the side-by-side view");
+        assert_eq!(focused_hunk_count(&mut h), 2);
+        assert_eq!(
+            review_cursor_row(&mut h),
+            1,
+            "the cursor lands on the first hunk"
+        );
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::GotoNextChange);
+        assert_eq!(review_cursor_row(&mut h), 3);
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::GotoNextChange);
+        assert_eq!(
+            review_cursor_row(&mut h),
+            3,
+            "the walk stops at the last hunk instead of crossing files",
+        );
+        assert_eq!(h.stoat.pending_message.as_deref(), Some("no more changes"));
+    }
+
+    #[test]
+    fn diff_with_buffer_follows_edits_to_the_base() {
+        let mut h = TestHarness::with_size(120, 14);
+        open_buffer_pair(&mut h, "keep\nold\ntail\n", "keep\nnew\ntail\n");
+        assert_eq!(focused_hunk_count(&mut h), 1);
+
+        let left = {
+            let buffers = &h.stoat.active_workspace().buffers;
+            let id = buffers
+                .id_for_path(Path::new("/pair/left.txt"))
+                .expect("left open");
+            buffers.get(id).expect("left buffer")
+        };
+        left.write().expect("poisoned").edit(5..8, "new");
+        h.stoat.drive_background();
+        assert_eq!(
+            focused_hunk_count(&mut h),
+            0,
+            "the pair re-diffs after the edit"
+        );
+    }
+
+    #[test]
+    fn closing_the_diff_view_ends_the_buffer_pair() {
+        let mut h = TestHarness::with_size(120, 14);
+        open_buffer_pair(&mut h, "keep\nold\ntail\n", "keep\nnew\ntail\n");
+        assert_eq!(h.stoat.active_workspace().buffer_diff_bases.len(), 1);
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::Diff);
+        assert!(h.stoat.active_workspace().buffer_diff_bases.is_empty());
+        assert_eq!(focused_hunk_count(&mut h), 0, "the pair's map is dropped");
+    }
+
+    #[test]
+    fn diff_with_buffer_refuses_the_focused_buffer_itself() {
+        let mut h = TestHarness::with_size(120, 14);
+        h.stoat.active_workspace_mut().git_root = PathBuf::from("/pair");
+        h.fake_fs().insert_file("/pair/left.txt", "a\n");
+        h.open_file(Path::new("/pair/left.txt"));
+        crate::action_handlers::dispatch(
+            &mut h.stoat,
+            &stoat_action::DiffWithBuffer {
+                path: PathBuf::from("/pair/left.txt"),
+            },
+        );
+        assert!(h.stoat.active_workspace().buffer_diff_bases.is_empty());
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("diff-with: cannot diff a buffer against itself"),
+        );
+    }
+
     #[test]
     fn toggle_diff_back_restores_the_diff_with_staging_intact() {
         let mut h = TestHarness::with_size(80, 14);
@@ -2726,6 +3322,158 @@ mod tests {
         );
     }
 
+    fn open_crlf_file_at_cursor(h: &mut TestHarness, row: u32) -> PathBuf {
+        let workdir = PathBuf::from("/work");
+        h.stage_review_scenario(
+            &workdir,
+            &[("a.rs", "a\r\nb\r\nc\r\nd\r\n", "a\r\nb\r\nX\r\nd\r\n")],
+        );
+        h.open_file(&workdir.join("a.rs"));
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        crate::action_handlers::movement::set_cursor_row(editor, row);
+        workdir
+    }
+
+    #[test]
+    fn stage_hunk_on_a_crlf_file_patches_with_its_line_breaks() {
+        let mut h = TestHarness::with_size(80, 14);
+        let workdir = open_crlf_file_at_cursor(&mut h, 2);
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::StageHunk);
+
+        let patches = h.fake_git().applied_patches(&workdir);
+        assert_eq!(patches.len(), 1, "exactly one patch applied: {patches:?}");
+        let patch = &patches[0];
+        assert!(
+            patch.contains("-c\r\n"),
+            "removes the CRLF base line: {patch}"
+        );
+        assert!(patch.contains("+X\r\n"), "adds the line as saved: {patch}");
+        assert!(
+            !patch.contains("-a"),
+            "unchanged lines stay context: {patch}"
+        );
+    }
+
+    #[test]
+    fn revert_hunk_on_a_crlf_file_touches_only_the_edited_line() {
+        let mut h = TestHarness::with_size(80, 14);
+        open_crlf_file_at_cursor(&mut h, 0);
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::RevertHunk);
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("no hunk under the cursor"),
+            "an unchanged CRLF line is no hunk"
+        );
+
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        crate::action_handlers::movement::set_cursor_row(editor, 2);
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::RevertHunk);
+        assert_eq!(focused_text(&mut h), "a\nb\nc\nd\n");
+        assert_eq!(h.stoat.pending_message.as_deref(), Some("reverted hunk"));
+    }
+
+    fn focused_text(h: &mut TestHarness) -> String {
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        let snapshot = editor.display_map.snapshot();
+        snapshot.buffer_snapshot().rope().to_string()
+    }
+
+    #[test]
+    fn revert_hunk_restores_head_text_without_touching_the_index() {
+        let mut h = TestHarness::with_size(80, 14);
+        let workdir = open_git_file_at_cursor(&mut h, 2);
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::RevertHunk);
+
+        assert_eq!(focused_text(&mut h), "a\nb\nc\nd\n");
+        assert!(
+            h.fake_git().applied_patches(&workdir).is_empty(),
+            "a revert edits the buffer, not the index"
+        );
+        assert_eq!(h.stoat.pending_message.as_deref(), Some("reverted hunk"));
+    }
+
+    #[test]
+    fn revert_hunk_is_a_single_undo_step() {
+        let mut h = TestHarness::with_size(80, 14);
+        open_git_file_at_cursor(&mut h, 2);
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::RevertHunk);
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::Undo);
+
+        assert_eq!(focused_text(&mut h), "a\nb\nX\nd\n");
+    }
+
+    #[test]
+    fn revert_hunk_off_a_hunk_is_a_message_only_noop() {
+        let mut h = TestHarness::with_size(80, 14);
+        let workdir = PathBuf::from("/work");
+        h.stage_review_scenario(
+            &workdir,
+            &[(
+                "a.rs",
+                "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n",
+                "a\nb\nc\nd\ne\nf\ng\nh\ni\nJ\n",
+            )],
+        );
+        h.open_file(&workdir.join("a.rs"));
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::RevertHunk);
+
+        assert_eq!(focused_text(&mut h), "a\nb\nc\nd\ne\nf\ng\nh\ni\nJ\n");
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("no hunk under the cursor")
+        );
+    }
+
+    #[test]
+    fn revert_hunk_restores_a_deletion_only_hunk() {
+        let mut h = TestHarness::with_size(80, 20);
+        let workdir = PathBuf::from("/work");
+        let head = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n";
+        h.stage_review_scenario(
+            &workdir,
+            &[("a.rs", head, "A\nA2\nb\nc\nd\ne\nf\ng\nh\ni\nj\nm\n")],
+        );
+        h.open_file(&workdir.join("a.rs"));
+        // `k` and `l` are gone, so `m` sits on row 11: row 10 in HEAD, shifted
+        // one row down by the line the first hunk added.
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        crate::action_handlers::movement::set_cursor_row(editor, 11);
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::RevertHunk);
+
+        assert_eq!(
+            focused_text(&mut h),
+            "A\nA2\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n",
+            "the deleted lines return and the other hunk is left alone"
+        );
+        assert_eq!(h.stoat.pending_message.as_deref(), Some("reverted hunk"));
+    }
+
+    #[test]
+    fn revert_hunk_on_a_file_new_since_head_says_so() {
+        let mut h = TestHarness::with_size(80, 14);
+        let workdir = PathBuf::from("/work");
+        h.stoat.active_workspace_mut().git_root = workdir.clone();
+        h.fake_git()
+            .add_repo(&workdir)
+            .with_fs(h.fake_fs())
+            .added("new.rs", "x\n");
+        h.open_file(&workdir.join("new.rs"));
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::RevertHunk);
+
+        assert_eq!(focused_text(&mut h), "x\n");
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("file is not tracked at HEAD")
+        );
+    }
+
     #[test]
     fn stage_line_stages_only_the_cursor_line() {
         let mut h = TestHarness::with_size(80, 14);
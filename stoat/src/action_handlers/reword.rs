@@ -1,22 +1,41 @@
 use crate::{
     action_handlers::rebase::{drive_rebase, emit_rebase_error},
     app::{Stoat, UpdateEffect},
+    input_draft::DraftKey,
     input_view::{InputView, SubmitTarget},
 };
 
 pub(super) fn reword_abort(stoat: &mut Stoat) -> UpdateEffect {
     use crate::rebase::RebasePause;
-    let input = {
+    let pause = {
         let ws = stoat.active_workspace();
         ws.rebase_active
             .as_ref()
             .and_then(|a| a.pause.as_ref())
             .and_then(|p| match p {
-                RebasePause::Reword { input, .. } => Some(input.clone()),
+                RebasePause::Reword {
+                    input,
+                    original_message,
+                    source_commit,
+                    ..
+                } => Some((
+                    input.clone(),
+                    original_message.clone(),
+                    source_commit.clone(),
+                )),
                 _ => None,
             })
     };
-    if let Some(input) = input {
+    if let Some((input, original_message, source_commit)) = pause {
+        // Keep an edited message as a draft so rewording the same commit
+        // again picks up where the abort left off. An untouched message
+        // stashes nothing and the next reword seeds the original as usual.
+        let text = input.text(stoat.active_workspace());
+        if text != original_message {
+            stoat
+                .input_drafts
+                .stash(DraftKey::Reword(source_commit), text);
+        }
         input.dispose(stoat.active_workspace_mut());
     }
     stoat.active_workspace_mut().rebase_active = None;
@@ -101,7 +120,8 @@ pub(super) fn reword_confirm(stoat: &mut Stoat) -> UpdateEffect {
     }
 }
 
-/// Create an [`InputView`] seeded with `original_message`, place the cursor
+/// Create an [`InputView`] seeded with `original_message`, or with the draft
+/// an earlier [`reword_abort`] stashed for `source_commit`, place the cursor
 /// at end, and install a [`crate::rebase::RebasePause::Reword`] pointing at
 /// the new input. The input is born in normal mode, and the `reword` screen is
 /// derived from the pause, so the Helix-scratch-buffer workflow (normal mode
@@ -110,10 +130,15 @@ pub(super) fn reword_confirm(stoat: &mut Stoat) -> UpdateEffect {
 pub(super) fn install_reword_pause(
     stoat: &mut Stoat,
     cherry_picked_commit: String,
+    source_commit: String,
     original_message: String,
 ) {
     use crate::rebase::RebasePause;
 
+    let seed = stoat
+        .input_drafts
+        .take(&DraftKey::Reword(source_commit.clone()))
+        .unwrap_or_else(|| original_message.clone());
     let executor = stoat.executor.clone();
     let ws = stoat.active_workspace_mut();
 
@@ -121,7 +146,7 @@ pub(super) fn install_reword_pause(
         ws,
         executor,
         SubmitTarget::Reword,
-        &original_message,
+        &seed,
         "normal",
         u16::MAX,
     );
@@ -133,6 +158,7 @@ pub(super) fn install_reword_pause(
     active.pause = Some(RebasePause::Reword {
         cherry_picked_commit,
         original_message,
+        source_commit,
         input,
     });
 }
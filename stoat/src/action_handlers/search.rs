@@ -1,7 +1,10 @@
 use crate::{
     app::{Stoat, UpdateEffect},
+    buffer_search::Matches,
+    input_history::InputHistory,
     input_view::{InputView, SubmitTarget},
 };
+use stoat_text::Rope;
 
 /// Direction the search was opened in. Forward (`/`) finds matches at
 /// or after the cursor; Reverse (`?`) finds matches before the cursor.
@@ -69,10 +72,12 @@ pub(crate) fn search_submit(stoat: &mut Stoat) -> bool {
     let direction = state.direction;
     let ws = stoat.active_workspace_mut();
     state.input.dispose(ws);
+    ws.search_history.reset();
 
     if query.is_empty() {
         return true;
     }
+    ws.search_history.push(query.clone());
 
     let origin = super::jump::live_entry(stoat);
     if jump_to_match(stoat, &query, direction)
@@ -92,9 +97,37 @@ pub(crate) fn search_cancel(stoat: &mut Stoat) -> bool {
     };
     let ws = stoat.active_workspace_mut();
     state.input.dispose(ws);
+    ws.search_history.reset();
     true
 }
 
+/// Recall the previous query from the workspace's search history into the
+/// open search input, fish-style. Text edited since the last recall becomes
+/// the needle of a fresh walk. A no-op when no search input is open.
+pub(super) fn search_history_prev(stoat: &mut Stoat) -> UpdateEffect {
+    recall(stoat, InputHistory::prev)
+}
+
+/// Recall the next query toward the newest, restoring the originally-typed
+/// text past the newest match. A no-op when no search input is open.
+pub(super) fn search_history_next(stoat: &mut Stoat) -> UpdateEffect {
+    recall(stoat, InputHistory::next)
+}
+
+fn recall(stoat: &mut Stoat, step: fn(&mut InputHistory, &str) -> Option<String>) -> UpdateEffect {
+    let active_idx = stoat.active_workspace;
+    let Some(state) = stoat.search_input.as_ref() else {
+        return UpdateEffect::None;
+    };
+    let ws = &mut stoat.workspaces[active_idx];
+    let current = state.input.text(ws);
+    ws.search_history.reset_if_edited(&current);
+    if let Some(recalled) = step(&mut ws.search_history, &current) {
+        state.input.replace_text(ws, &recalled);
+    }
+    UpdateEffect::Redraw
+}
+
 pub(super) fn search_next(stoat: &mut Stoat) -> UpdateEffect {
     let Some(last) = stoat.last_search.clone() else {
         return UpdateEffect::None;
@@ -139,18 +172,16 @@ fn jump_to_match(stoat: &mut Stoat, query: &str, direction: SearchDirection) ->
     let snapshot = editor.display_map.snapshot();
     let buffer_snapshot = snapshot.buffer_snapshot();
     let rope = buffer_snapshot.rope();
-    let text = rope.to_string();
     let sel = editor.selections.newest_anchor();
     let cursor = stoat_text::cursor_offset(
         rope,
         buffer_snapshot.resolve_anchor(&sel.tail()),
         buffer_snapshot.resolve_anchor(&sel.head()),
     );
-    let len = text.len();
 
     let target = match direction {
-        SearchDirection::Forward => find_forward(&regex, &text, cursor, len),
-        SearchDirection::Reverse => find_reverse(&regex, &text, cursor),
+        SearchDirection::Forward => find_forward(&regex, rope, cursor),
+        SearchDirection::Reverse => find_reverse(&regex, rope, cursor),
     };
     let Some(target) = target else { return false };
 
@@ -161,40 +192,31 @@ fn jump_to_match(stoat: &mut Stoat, query: &str, direction: SearchDirection) ->
     true
 }
 
-fn find_forward(regex: &regex::Regex, text: &str, head: usize, len: usize) -> Option<usize> {
-    let start = head.saturating_add(1).min(len);
-    if let Some(m) = next_match_at_or_after(regex, text, start) {
-        return Some(m);
-    }
-    next_match_at_or_after(regex, text, 0)
+/// Start of the first match after `head`, wrapping to the first match in
+/// the buffer.
+fn find_forward(regex: &regex::Regex, rope: &Rope, head: usize) -> Option<usize> {
+    let start = head.saturating_add(1).min(rope.len());
+    Matches::new(rope, regex, start)
+        .next()
+        .or_else(|| Matches::new(rope, regex, 0).next())
+        .map(|m| m.start)
 }
 
-fn find_reverse(regex: &regex::Regex, text: &str, head: usize) -> Option<usize> {
-    let starts: Vec<usize> = regex.find_iter(text).map(|m| m.start()).collect();
-    if starts.is_empty() {
-        return None;
-    }
-    starts
-        .iter()
-        .rev()
-        .find(|&&pos| pos < head)
-        .copied()
-        .or_else(|| starts.last().copied())
-}
-
-/// Finds the first regex match whose start is at or after `at`.
-/// Walks forward via `find_at` and skips matches that pre-date `at`
-/// (which can happen for zero-width patterns).
-fn next_match_at_or_after(regex: &regex::Regex, text: &str, at: usize) -> Option<usize> {
-    if at > text.len() {
-        return None;
-    }
-    let m = regex.find_at(text, at)?;
-    if m.start() >= at {
-        Some(m.start())
-    } else {
-        None
+/// Start of the last match before `head`, wrapping to the last match in the
+/// buffer.
+fn find_reverse(regex: &regex::Regex, rope: &Rope, head: usize) -> Option<usize> {
+    let mut before = None;
+    let mut last = None;
+    for m in Matches::new(rope, regex, 0) {
+        if m.start >= head && before.is_some() {
+            break;
+        }
+        if m.start < head {
+            before = Some(m.start);
+        }
+        last = Some(m.start);
     }
+    before.or(last)
 }
 
 /// Compile `pattern` into a [`regex::Regex`] with multiline mode on,
@@ -286,6 +308,36 @@ mod tests {
         assert_eq!(cursor_offset(&mut h), 0);
     }
 
+    #[test]
+    fn up_and_down_walk_the_search_history() {
+        let mut h = TestHarness::with_size(40, 10);
+        seed(&mut h, "abc def abc xyz\n");
+        for query in ["abc", "def", "xyz"] {
+            h.type_keys("/");
+            h.type_text(query);
+            h.type_keys("enter");
+        }
+        assert_eq!(
+            h.stoat.active_workspace().search_history.entries(),
+            ["abc", "def", "xyz"]
+        );
+
+        h.type_keys("/");
+        h.type_text("b");
+        h.type_keys("up");
+        let input_text = |h: &TestHarness| {
+            let state = h.stoat.search_input.as_ref().expect("search input open");
+            state.input.text(h.stoat.active_workspace())
+        };
+        assert_eq!(input_text(&h), "abc", "the typed text filters the walk");
+        h.type_keys("down");
+        assert_eq!(
+            input_text(&h),
+            "b",
+            "past the newest restores the typed text"
+        );
+    }
+
     #[test]
     fn search_next_repeats_forward_search() {
         let mut h = TestHarness::with_size(40, 10);
@@ -1,8 +1,16 @@
 use crate::{
     app::{Stoat, UpdateEffect},
+    buffer::BufferId,
+    editor_state::{EditorId, EditorState},
+    host::{ShellOutput, ShellStream},
     input_view::{InputView, SubmitTarget},
+    pane::{Axis, View},
+    workspace::Workspace,
 };
+use std::{io, path::PathBuf};
+use stoat_scheduler::Task;
 use stoat_text::{Anchor, Bias, Selection, SelectionGoal};
+use tokio::sync::mpsc::{error::TryRecvError, UnboundedReceiver};
 
 /// Which shell-integration operation the input modal will perform on
 /// submit.
@@ -13,6 +21,43 @@ pub(crate) enum ShellAction {
     InsertOutput,
     AppendOutput,
     KeepPipe,
+    RunCommand,
+    RunCommandWithSelection,
+}
+
+/// A read-only scratch buffer holding the output of a [`ShellAction::RunCommand`]
+/// run, keyed by its buffer in [`Workspace::command_outputs`].
+pub(crate) struct CommandOutput {
+    command: String,
+    stdin: Vec<u8>,
+    /// Directory the command runs in, `None` for the process cwd.
+    cwd: Option<PathBuf>,
+    /// Editor the command was run from, the target of `InsertCommandOutput`.
+    source_editor: EditorId,
+    /// Stdout of the last run, complete once the run has finished.
+    stdout: String,
+    pending: Option<PendingCommand>,
+}
+
+/// A run in flight on the blocking pool. The task is held so the run is not
+/// cancelled; the job streams its output and exit status over `rx`, waking
+/// the event loop after each message.
+struct PendingCommand {
+    _task: Task<()>,
+    rx: UnboundedReceiver<CommandEvent>,
+    /// Stream of the last chunk shown, so a switch between stdout and stderr
+    /// starts on a fresh line.
+    last_stream: Option<ShellStream>,
+    /// Whether the output shown so far ends with a newline.
+    at_line_start: bool,
+}
+
+/// One message from a running command's job.
+enum CommandEvent {
+    /// Decoded output, in the order the command produced it.
+    Chunk(ShellStream, String),
+    /// The run finished with this exit code, or failed to run at all.
+    Exit(io::Result<i32>),
 }
 
 /// Active state while the user is typing the shell command.
@@ -41,6 +86,14 @@ pub(super) fn open_keep_pipe(stoat: &mut Stoat) -> UpdateEffect {
     open_with(stoat, ShellAction::KeepPipe)
 }
 
+pub(super) fn open_run_command(stoat: &mut Stoat) -> UpdateEffect {
+    open_with(stoat, ShellAction::RunCommand)
+}
+
+pub(super) fn open_run_command_with_selection(stoat: &mut Stoat) -> UpdateEffect {
+    open_with(stoat, ShellAction::RunCommandWithSelection)
+}
+
 fn open_with(stoat: &mut Stoat, action: ShellAction) -> UpdateEffect {
     if stoat.shell_input.is_some() {
         return UpdateEffect::None;
@@ -74,6 +127,8 @@ pub(crate) fn submit(stoat: &mut Stoat) -> bool {
         ShellAction::InsertOutput => apply_insert_output(stoat, &*shell_host, &cmd),
         ShellAction::AppendOutput => apply_append_output(stoat, &*shell_host, &cmd),
         ShellAction::KeepPipe => apply_keep_pipe(stoat, &*shell_host, &cmd),
+        ShellAction::RunCommand => open_command_output(stoat, &cmd, false),
+        ShellAction::RunCommandWithSelection => open_command_output(stoat, &cmd, true),
     }
     true
 }
@@ -193,7 +248,16 @@ fn apply_insert_output(stoat: &mut Stoat, shell_host: &dyn crate::host::ShellHos
     if output.is_empty() {
         return;
     }
-    let Some(editor) = super::focused_editor_mut(stoat) else {
+    let Some((editor_id, _)) = stoat.focused_editor_ids() else {
+        return;
+    };
+    insert_at_heads(stoat.active_workspace_mut(), editor_id, &output);
+}
+
+/// Insert `text` at every selection head of `editor_id`, back to front so
+/// earlier offsets stay valid.
+fn insert_at_heads(ws: &mut Workspace, editor_id: EditorId, text: &str) {
+    let Some(editor) = ws.editors.get_mut(editor_id) else {
         return;
     };
     let buffer_id = editor.buffer_id;
@@ -208,13 +272,12 @@ fn apply_insert_output(stoat: &mut Stoat, shell_host: &dyn crate::host::ShellHos
     heads.sort_unstable();
     heads.dedup();
     heads.reverse();
-    let buffer = match stoat.active_workspace().buffers.get(buffer_id) {
-        Some(b) => b,
-        None => return,
+    let Some(buffer) = ws.buffers.get(buffer_id) else {
+        return;
     };
     let mut guard = buffer.write().expect("buffer poisoned");
     for head in &heads {
-        guard.edit(*head..*head, &output);
+        guard.edit(*head..*head, text);
     }
 }
 
@@ -281,17 +344,339 @@ fn apply_keep_pipe(stoat: &mut Stoat, shell_host: &dyn crate::host::ShellHost, c
     editor.selections.replace_with(kept, buffer_snapshot);
 }
 
+/// Open a read-only scratch buffer in a vertical split and start `cmd` into
+/// it. With `pipe_selection`, the primary selection of the focused editor is
+/// the command's stdin.
+fn open_command_output(stoat: &mut Stoat, cmd: &str, pipe_selection: bool) {
+    let Some((source_editor, _)) = stoat.focused_editor_ids() else {
+        return;
+    };
+    let stdin = if pipe_selection {
+        super::focused_editor_mut(stoat)
+            .map(|editor| {
+                let display_snapshot = editor.display_map.snapshot();
+                let buffer_snapshot = display_snapshot.buffer_snapshot();
+                let sel = editor.selections.newest_anchor();
+                let start = buffer_snapshot.resolve_anchor(&sel.start);
+                let end = buffer_snapshot.resolve_anchor(&sel.end);
+                buffer_snapshot
+                    .rope()
+                    .chunks_in_range(start..end)
+                    .collect::<String>()
+                    .into_bytes()
+            })
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let buffer_id = open_output_buffer(stoat, cmd, stdin, None, source_editor);
+    start_command(stoat, buffer_id);
+}
+
+/// Open an empty read-only output buffer for `command` in a vertical split,
+/// registered so `RerunCommand` and `InsertCommandOutput` act on it.
+pub(crate) fn open_output_buffer(
+    stoat: &mut Stoat,
+    command: &str,
+    stdin: Vec<u8>,
+    cwd: Option<PathBuf>,
+    source_editor: EditorId,
+) -> BufferId {
+    let executor = stoat.executor.clone();
+    let ws = stoat.active_workspace_mut();
+    let pane_id = ws.panes.split(Axis::Vertical);
+    let (buffer_id, buffer) = ws.buffers.new_scratch_unseeded();
+    buffer.write().expect("buffer poisoned").set_read_only(true);
+    let editor_id = ws
+        .editors
+        .insert(EditorState::new(buffer_id, buffer, executor));
+    ws.panes.pane_mut(pane_id).view = View::Editor(editor_id);
+    ws.command_outputs.insert(
+        buffer_id,
+        CommandOutput {
+            command: command.to_owned(),
+            stdin,
+            cwd,
+            source_editor,
+            stdout: String::new(),
+            pending: None,
+        },
+    );
+    buffer_id
+}
+
+/// Point the output buffer `buffer_id` at `command`, so the next run or
+/// [`show_output`] reports it. Returns `false` when `buffer_id` is not an
+/// output buffer, such as one closed since.
+pub(crate) fn retarget_output(ws: &mut Workspace, buffer_id: BufferId, command: &str) -> bool {
+    let Some(output) = ws.command_outputs.get_mut(&buffer_id) else {
+        return false;
+    };
+    output.command = command.to_owned();
+    true
+}
+
+/// Run the command of the output buffer `buffer_id` on the blocking pool,
+/// replacing the buffer's content with a header that
+/// [`install_command_outputs`] extends as output streams in.
+fn start_command(stoat: &mut Stoat, buffer_id: BufferId) {
+    let shell_host = stoat.shell_host.clone();
+    let redraw = stoat.redraw_notify.clone();
+    let executor = stoat.executor.clone();
+    let ws = stoat.active_workspace_mut();
+    let env = ws.env.diff.clone();
+    let Some(output) = ws.command_outputs.get_mut(&buffer_id) else {
+        return;
+    };
+    let command = output.command.clone();
+    let stdin = output.stdin.clone();
+    let cwd = output.cwd.clone();
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let task = executor.spawn_blocking(move || {
+        let mut stdout_carry = Vec::new();
+        let mut stderr_carry = Vec::new();
+        let status = shell_host.run_streaming(
+            &command,
+            &stdin,
+            cwd.as_deref(),
+            &env,
+            &mut |stream, bytes| {
+                let carry = match stream {
+                    ShellStream::Stdout => &mut stdout_carry,
+                    ShellStream::Stderr => &mut stderr_carry,
+                };
+                let text = decode_chunk(carry, bytes);
+                if !text.is_empty() {
+                    let _ = tx.send(CommandEvent::Chunk(stream, text));
+                    redraw.notify_one();
+                }
+            },
+        );
+        for (stream, rest) in [
+            (ShellStream::Stdout, stdout_carry),
+            (ShellStream::Stderr, stderr_carry),
+        ] {
+            if !rest.is_empty() {
+                let text = String::from_utf8_lossy(&rest).into_owned();
+                let _ = tx.send(CommandEvent::Chunk(stream, text));
+            }
+        }
+        let _ = tx.send(CommandEvent::Exit(status));
+        redraw.notify_one();
+    });
+    output.stdout.clear();
+    output.pending = Some(PendingCommand {
+        _task: task,
+        rx,
+        last_stream: None,
+        at_line_start: true,
+    });
+    let header = format!("$ {}\n", output.command);
+    write_output(ws, buffer_id, &header);
+}
+
+/// Append `bytes` to `carry` and take back the longest prefix that decodes,
+/// leaving a character split across chunks for the next call. Bytes that can
+/// never decode are replaced rather than held.
+fn decode_chunk(carry: &mut Vec<u8>, bytes: &[u8]) -> String {
+    carry.extend_from_slice(bytes);
+    let complete = match std::str::from_utf8(carry) {
+        Err(err) if err.error_len().is_none() => err.valid_up_to(),
+        _ => carry.len(),
+    };
+    let text = String::from_utf8_lossy(&carry[..complete]).into_owned();
+    carry.drain(..complete);
+    text
+}
+
+/// Replace the whole content of the read-only output buffer `buffer_id`,
+/// leaving it clean so closing it never prompts.
+fn write_output(ws: &Workspace, buffer_id: BufferId, text: &str) {
+    let Some(buffer) = ws.buffers.get(buffer_id) else {
+        return;
+    };
+    let mut guard = buffer.write().expect("buffer poisoned");
+    let len = guard.rope().len();
+    guard.owner_edit(0..len, text);
+    guard.mark_clean();
+}
+
+/// Append `text` to the end of the read-only output buffer `buffer_id`,
+/// leaving it clean.
+fn append_output(ws: &Workspace, buffer_id: BufferId, text: &str) {
+    let Some(buffer) = ws.buffers.get(buffer_id) else {
+        return;
+    };
+    let mut guard = buffer.write().expect("buffer poisoned");
+    let len = guard.rope().len();
+    guard.owner_edit(len..len, text);
+    guard.mark_clean();
+}
+
+/// Append streamed output of running commands to their output buffers, in
+/// every workspace, and forget outputs whose buffer has been closed. Called
+/// from [`Stoat::drive_background`].
+pub(crate) fn install_command_outputs(stoat: &mut Stoat) {
+    for ws in stoat.workspaces.values_mut() {
+        pump_command_outputs(ws);
+    }
+}
+
+fn pump_command_outputs(ws: &mut Workspace) {
+    ws.command_outputs
+        .retain(|buffer_id, _| ws.buffers.get(*buffer_id).is_some());
+    let mut appends: Vec<(BufferId, String)> = Vec::new();
+    for (buffer_id, output) in ws.command_outputs.iter_mut() {
+        let Some(pending) = output.pending.as_mut() else {
+            continue;
+        };
+        let mut text = String::new();
+        let mut finished = None;
+        loop {
+            match pending.rx.try_recv() {
+                Ok(CommandEvent::Chunk(stream, chunk)) => {
+                    if pending.last_stream.is_some_and(|last| last != stream)
+                        && !pending.at_line_start
+                    {
+                        text.push('\n');
+                    }
+                    if stream == ShellStream::Stdout {
+                        output.stdout.push_str(&chunk);
+                    }
+                    pending.last_stream = Some(stream);
+                    pending.at_line_start = chunk.ends_with('\n');
+                    text.push_str(&chunk);
+                },
+                Ok(CommandEvent::Exit(status)) => {
+                    finished = Some(status);
+                    break;
+                },
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = Some(Err(io::Error::other("command job ended without a status")));
+                    break;
+                },
+            }
+        }
+        if let Some(status) = finished {
+            if status.is_err() {
+                output.stdout.clear();
+            }
+            if !pending.at_line_start && !matches!(status, Ok(0)) {
+                text.push('\n');
+            }
+            push_status(&mut text, status);
+            output.pending = None;
+        }
+        if !text.is_empty() {
+            appends.push((*buffer_id, text));
+        }
+    }
+    for (buffer_id, text) in appends {
+        append_output(ws, buffer_id, &text);
+    }
+}
+
+/// Append the trailer for a run that ended with `status`: nothing for a
+/// success, the exit code for a failure, or the error it could not run with.
+fn push_status(text: &mut String, status: io::Result<i32>) {
+    match status {
+        Ok(0) => {},
+        Ok(code) => text.push_str(&format!("[exit {code}]\n")),
+        Err(err) => text.push_str(&format!("[error: {err}]\n")),
+    }
+}
+
+/// Fill the output buffer `buffer_id` with a finished run of its command.
+/// No-op when `buffer_id` is not an output buffer.
+pub(crate) fn show_output(
+    ws: &mut Workspace,
+    buffer_id: BufferId,
+    result: io::Result<ShellOutput>,
+) {
+    let Some(output) = ws.command_outputs.get_mut(&buffer_id) else {
+        return;
+    };
+    let mut text = format!("$ {}\n", output.command);
+    match result {
+        Ok(out) => {
+            output.stdout = String::from_utf8_lossy(&out.stdout).into_owned();
+            text.push_str(&output.stdout);
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            if !stderr.is_empty() {
+                if !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str(&stderr);
+            }
+            if out.exit_code != 0 && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            push_status(&mut text, Ok(out.exit_code));
+        },
+        Err(err) => {
+            output.stdout.clear();
+            push_status(&mut text, Err(err));
+        },
+    }
+    write_output(ws, buffer_id, &text);
+}
+
+/// Run the focused output buffer's command again.
+pub(super) fn rerun_command(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(buffer_id) = focused_command_output(stoat) else {
+        stoat.set_status("not a command output buffer");
+        return UpdateEffect::Redraw;
+    };
+    start_command(stoat, buffer_id);
+    UpdateEffect::Redraw
+}
+
+/// Insert the focused output buffer's last stdout at the cursors of the editor
+/// the command was run from.
+pub(super) fn insert_command_output(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(buffer_id) = focused_command_output(stoat) else {
+        stoat.set_status("not a command output buffer");
+        return UpdateEffect::Redraw;
+    };
+    let ws = stoat.active_workspace_mut();
+    let output = &ws.command_outputs[&buffer_id];
+    if output.pending.is_some() {
+        stoat.set_status("command still running");
+        return UpdateEffect::Redraw;
+    }
+    let (source_editor, stdout) = (output.source_editor, output.stdout.clone());
+    if !ws.editors.contains_key(source_editor) {
+        stoat.set_status("source editor closed");
+        return UpdateEffect::Redraw;
+    }
+    insert_at_heads(ws, source_editor, &stdout);
+    stoat.set_status("inserted command output");
+    UpdateEffect::Redraw
+}
+
+fn focused_command_output(stoat: &Stoat) -> Option<BufferId> {
+    let (_, buffer_id) = stoat.focused_editor_ids()?;
+    stoat
+        .active_workspace()
+        .command_outputs
+        .contains_key(&buffer_id)
+        .then_some(buffer_id)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{decode_chunk, write_output, CommandEvent, PendingCommand};
     use crate::{
         action_handlers::dispatch,
-        host::{FakeShell, ShellOutput},
+        host::{FakeShell, ShellOutput, ShellStream},
         test_harness::{editor, keys, TestHarness},
         Stoat,
     };
     use crossterm::event::{Event, KeyCode};
     use std::sync::Arc;
     use stoat_action as action;
+    use tokio::sync::mpsc::UnboundedSender;
 
     fn install_fake(h: &mut TestHarness) -> Arc<FakeShell> {
         let fake = Arc::new(FakeShell::new());
@@ -467,6 +852,176 @@ mod tests {
         assert!(h.stoat.shell_input.is_none());
     }
 
+    fn run_command(h: &mut TestHarness, action: &dyn stoat_action::Action, cmd: &str) {
+        dispatch(&mut h.stoat, action);
+        h.type_text(cmd);
+        h.stoat.update(Event::Key(keys::key(KeyCode::Enter)));
+        h.settle();
+        h.stoat.drive_background();
+    }
+
+    #[test]
+    fn run_command_writes_output_to_read_only_buffer() {
+        let mut h = Stoat::test();
+        let fake = install_fake(&mut h);
+        fake.set_response(
+            "ls",
+            ShellOutput {
+                stdout: b"a.rs\nb.rs\n".to_vec(),
+                stderr: b"warning\n".to_vec(),
+                exit_code: 2,
+            },
+        );
+        h.seed_focused_buffer("source");
+        run_command(&mut h, &action::RunCommand, "ls");
+        assert_eq!(buffer_text(&mut h), "$ ls\na.rs\nb.rs\nwarning\n[exit 2]\n");
+        assert_eq!(fake.invocations()[0].stdin, b"");
+
+        h.type_text("ix");
+        assert_eq!(
+            buffer_text(&mut h),
+            "$ ls\na.rs\nb.rs\nwarning\n[exit 2]\n",
+            "the output buffer refuses edits"
+        );
+    }
+
+    #[test]
+    fn run_command_with_selection_pipes_primary_selection() {
+        let mut h = Stoat::test();
+        let fake = install_fake(&mut h);
+        h.seed_focused_buffer("hello world");
+        select_range(&mut h, 6, 11);
+        run_command(&mut h, &action::RunCommandWithSelection, "wc -c");
+        assert_eq!(fake.invocations().len(), 1);
+        assert_eq!(fake.invocations()[0].stdin, b"world");
+    }
+
+    #[test]
+    fn rerun_command_runs_again() {
+        let mut h = Stoat::test();
+        let fake = install_fake(&mut h);
+        h.seed_focused_buffer("source");
+        run_command(&mut h, &action::RunCommand, "date");
+        dispatch(&mut h.stoat, &action::RerunCommand);
+        h.settle();
+        h.stoat.drive_background();
+        assert_eq!(fake.invocations().len(), 2);
+        assert_eq!(fake.invocations()[1].cmd, "date");
+    }
+
+    #[test]
+    fn rerun_outside_output_buffer_reports() {
+        let mut h = Stoat::test();
+        install_fake(&mut h);
+        h.seed_focused_buffer("source");
+        dispatch(&mut h.stoat, &action::RerunCommand);
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("not a command output buffer")
+        );
+    }
+
+    #[test]
+    fn insert_command_output_edits_source_buffer() {
+        let mut h = Stoat::test();
+        let fake = install_fake(&mut h);
+        fake.set_response(
+            "echo hi",
+            ShellOutput {
+                stdout: b"hi".to_vec(),
+                stderr: Vec::new(),
+                exit_code: 0,
+            },
+        );
+        h.seed_focused_buffer("xy");
+        select_range(&mut h, 1, 1);
+        let (_, source_buffer) = h.stoat.focused_editor_ids().expect("editor");
+        run_command(&mut h, &action::RunCommand, "echo hi");
+        dispatch(&mut h.stoat, &action::InsertCommandOutput);
+        let buffer = h
+            .stoat
+            .active_workspace()
+            .buffers
+            .get(source_buffer)
+            .expect("source buffer");
+        assert_eq!(buffer.read().unwrap().rope().to_string(), "xhiy");
+    }
+
+    /// Replace the focused output buffer's run with one the test drives,
+    /// returning the sender standing in for the job.
+    fn stub_run(h: &mut TestHarness) -> UnboundedSender<CommandEvent> {
+        let (_, buffer_id) = h.stoat.focused_editor_ids().expect("editor");
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let task = h.stoat.executor.spawn(async {});
+        let ws = h.stoat.active_workspace_mut();
+        let output = ws
+            .command_outputs
+            .get_mut(&buffer_id)
+            .expect("output buffer");
+        output.pending = Some(PendingCommand {
+            _task: task,
+            rx,
+            last_stream: None,
+            at_line_start: true,
+        });
+        write_output(ws, buffer_id, "$ build\n");
+        tx
+    }
+
+    fn chunk(stream: ShellStream, text: &str) -> CommandEvent {
+        CommandEvent::Chunk(stream, text.to_owned())
+    }
+
+    #[test]
+    fn run_command_streams_output_before_exit() {
+        let mut h = Stoat::test();
+        install_fake(&mut h);
+        h.seed_focused_buffer("source");
+        run_command(&mut h, &action::RunCommand, "build");
+        let tx = stub_run(&mut h);
+
+        tx.send(chunk(ShellStream::Stdout, "compiling")).unwrap();
+        h.stoat.drive_background();
+        assert_eq!(buffer_text(&mut h), "$ build\ncompiling");
+
+        tx.send(chunk(ShellStream::Stderr, "warning\n")).unwrap();
+        tx.send(chunk(ShellStream::Stdout, "done\n")).unwrap();
+        tx.send(CommandEvent::Exit(Ok(1))).unwrap();
+        h.stoat.drive_background();
+        assert_eq!(
+            buffer_text(&mut h),
+            "$ build\ncompiling\nwarning\ndone\n[exit 1]\n"
+        );
+    }
+
+    #[test]
+    fn command_output_streams_in_an_inactive_workspace() {
+        let mut h = Stoat::test();
+        install_fake(&mut h);
+        h.seed_focused_buffer("source");
+        run_command(&mut h, &action::RunCommand, "build");
+        let tx = stub_run(&mut h);
+        let home = h.stoat.active_workspace;
+        let other = h.create_workspace();
+        h.set_active_workspace(other);
+
+        tx.send(chunk(ShellStream::Stdout, "done\n")).unwrap();
+        tx.send(CommandEvent::Exit(Ok(0))).unwrap();
+        h.stoat.drive_background();
+
+        h.set_active_workspace(home);
+        assert_eq!(buffer_text(&mut h), "$ build\ndone\n");
+    }
+
+    #[test]
+    fn decode_chunk_holds_a_split_character() {
+        let mut carry = Vec::new();
+        let bytes = "é!".as_bytes();
+        assert_eq!(decode_chunk(&mut carry, &bytes[..1]), "");
+        assert_eq!(decode_chunk(&mut carry, &bytes[1..]), "é!");
+        assert!(carry.is_empty());
+    }
+
     #[test]
     fn escape_cancels_input() {
         let mut h = Stoat::test();
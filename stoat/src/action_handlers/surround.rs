@@ -1,8 +1,21 @@
 use crate::{
     app::{Stoat, UpdateEffect},
+    input_view::{InputView, SubmitTarget},
     pane::View,
 };
-use stoat_text::{Bias, Rope, SelectionGoal};
+use std::{ops::Range, sync::LazyLock};
+use stoat_text::{Bias, Point, Rope, SelectionGoal};
+
+/// The chord char naming an HTML / XML tag rather than a literal `t...t` pair,
+/// in the same way `m` names the closest pair of any type.
+pub(crate) const TAG_CHAR: char = 't';
+
+/// An opening or closing tag: `<`, an optional `/`, the name, attributes whose
+/// quoted values may hold `<` or `>`, an optional self-closing `/`, then `>`.
+static TAG_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r#"<(/)?([A-Za-z][A-Za-z0-9:._-]*)(?:"[^"]*"|'[^']*'|[^'"<>])*?(/)?>"#)
+        .expect("tag regex compiles")
+});
 
 /// Two-step capture state for [`surround_replace`]: arms after the action
 /// fires, transitions to [`SurroundReplaceStage::AwaitTo`] once the user
@@ -31,14 +44,89 @@ pub(super) fn surround_delete(stoat: &mut Stoat) -> UpdateEffect {
     UpdateEffect::Redraw
 }
 
+/// What the tag-name prompt does with the typed tag once submitted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SurroundTagOp {
+    /// Wrap every non-empty selection in the tag.
+    Add,
+    /// Replace the enclosing pair named by `from` with the tag.
+    Replace { from: char },
+}
+
+/// Active state while the user types the tag for a `t` surround add or
+/// replace. Disposed by [`submit_tag`] / [`cancel_tag`].
+pub(crate) struct SurroundTagInputState {
+    pub(crate) input: InputView,
+    op: SurroundTagOp,
+}
+
+fn open_tag_input(stoat: &mut Stoat, op: SurroundTagOp) -> UpdateEffect {
+    if stoat.surround_tag_input.is_some() {
+        return UpdateEffect::None;
+    }
+    let executor = stoat.executor.clone();
+    let ws = stoat.active_workspace_mut();
+    let input = InputView::create(ws, executor, SubmitTarget::SurroundTag, "", "insert", 1);
+    stoat.surround_tag_input = Some(SurroundTagInputState { input, op });
+    UpdateEffect::Redraw
+}
+
+/// Submit the tag-name prompt. The typed text is the opening tag's contents,
+/// so `div class="x"` wraps in `<div class="x">` and `</div>`. An empty tag
+/// closes the prompt without editing. Returns `true` when the prompt was open.
+pub(crate) fn submit_tag(stoat: &mut Stoat) -> bool {
+    let Some(state) = stoat.surround_tag_input.take() else {
+        return false;
+    };
+    let typed = state.input.text(stoat.active_workspace());
+    state.input.dispose(stoat.active_workspace_mut());
+    let typed = typed.trim();
+    let Some(name) = typed.split_whitespace().next() else {
+        return true;
+    };
+    let open = format!("<{typed}>");
+    let close = format!("</{name}>");
+    match state.op {
+        SurroundTagOp::Add => {
+            wrap_selections(stoat, &open, &close);
+        },
+        SurroundTagOp::Replace { from: TAG_CHAR } if name == typed => {
+            rename_tags(stoat, name);
+        },
+        SurroundTagOp::Replace { from } => {
+            replace_surround(stoat, from, &open, &close);
+        },
+    }
+    true
+}
+
+/// Cancel the tag-name prompt without editing. Returns `true` when the prompt
+/// was open.
+pub(crate) fn cancel_tag(stoat: &mut Stoat) -> bool {
+    let Some(state) = stoat.surround_tag_input.take() else {
+        return false;
+    };
+    state.input.dispose(stoat.active_workspace_mut());
+    true
+}
+
 /// Apply the consumed-char keypress to the pending surround_add chord:
 /// wrap every non-empty selection in the focused editor with the pair
-/// returned by [`surround_pair_for`]. Empty (collapsed) selections are
-/// skipped. After the wrap, each affected selection's range covers the
-/// original content (between the inserted open and close), preserving
-/// the original `reversed` direction.
+/// returned by [`surround_pair_for`]. [`TAG_CHAR`] instead opens a prompt
+/// for the tag to wrap with.
 pub(crate) fn execute_surround_add(stoat: &mut Stoat, ch: char) -> UpdateEffect {
+    if ch == TAG_CHAR {
+        return open_tag_input(stoat, SurroundTagOp::Add);
+    }
     let (open, close) = surround_pair_for(ch);
+    wrap_selections(stoat, &open.to_string(), &close.to_string())
+}
+
+/// Wrap every non-empty selection in `open` and `close`. Empty (collapsed)
+/// selections are skipped. After the wrap, each affected selection's range
+/// covers the original content (between the inserted open and close),
+/// preserving the original `reversed` direction.
+fn wrap_selections(stoat: &mut Stoat, open: &str, close: &str) -> UpdateEffect {
     let ws = stoat.active_workspace_mut();
     let focused = ws.panes.focus();
     let editor_id = match ws.panes.pane(focused).view {
@@ -73,20 +161,17 @@ pub(crate) fn execute_surround_add(stoat: &mut Stoat, ch: char) -> UpdateEffect
 
     entries.sort_by_key(|(_, s, _, _)| *s);
 
-    let open_str = open.to_string();
-    let close_str = close.to_string();
-
     {
         let buffer = ws.buffers.get(buffer_id).expect("buffer");
         let mut guard = buffer.write().expect("poisoned");
         for (_, s, e, _) in entries.iter().rev() {
-            guard.edit(*e..*e, &close_str);
-            guard.edit(*s..*s, &open_str);
+            guard.edit(*e..*e, close);
+            guard.edit(*s..*s, open);
         }
     }
 
-    let open_len = open.len_utf8();
-    let close_len = close.len_utf8();
+    let open_len = open.len();
+    let close_len = close.len();
     let mut id_to_range: std::collections::HashMap<usize, (usize, usize, bool)> =
         std::collections::HashMap::with_capacity(entries.len());
     let mut shift: i64 = 0;
@@ -127,57 +212,233 @@ pub(crate) fn surround_pair_for(ch: char) -> (char, char) {
 /// Apply the consumed-char keypress to the pending surround_delete
 /// chord. For every selection's primary cursor, find the nearest
 /// enclosing surround pair and remove its open / close. `ch` names the
-/// pair type, except `m`, which means the nearest pair of any type (so a
-/// literal `m...m` pair is unreachable). Selections whose cursor is not
-/// enclosed by a matching pair are skipped. Pairs are deduped before
-/// edits run, so two cursors inside the same pair produce one edit.
+/// pair type, except `m`, which means the nearest pair of any type, and
+/// [`TAG_CHAR`], which means the nearest enclosing tag (so literal
+/// `m...m` and `t...t` pairs are unreachable). Selections whose cursor
+/// is not enclosed by a matching pair are skipped. Pairs are deduped
+/// before edits run, so two cursors inside the same pair produce one
+/// edit.
 pub(crate) fn execute_surround_delete(stoat: &mut Stoat, ch: char) -> UpdateEffect {
-    let pair = (ch != 'm').then(|| surround_pair_for(ch));
-    let pairs = match collect_surround_pairs(stoat, pair) {
-        Some(p) if !p.is_empty() => p,
-        _ => return UpdateEffect::None,
-    };
-
-    let buffer_id = focused_buffer_id(stoat).expect("checked by collect_surround_pairs");
-    let ws = stoat.active_workspace_mut();
-    let buffer = ws.buffers.get(buffer_id).expect("buffer");
-    let mut guard = buffer.write().expect("poisoned");
-    for (open_off, close_off, open, close) in pairs.iter().rev() {
-        guard.edit(*close_off..*close_off + close.len_utf8(), "");
-        guard.edit(*open_off..*open_off + open.len_utf8(), "");
-    }
-    UpdateEffect::Redraw
+    replace_surround(stoat, ch, "", "")
 }
 
 /// Apply the consumed two-char keypresses to the pending
 /// surround_replace chord. For every selection's primary cursor, find
 /// the nearest enclosing surround pair and replace its open / close with
-/// the canonical pair for `to`. `from` names the pair type, except `m`,
-/// which means the nearest pair of any type. `to` is always a literal
-/// pair char. Selections whose cursor is not enclosed by a matching pair
-/// are skipped. Pairs are deduped before edits run.
+/// the canonical pair for `to`. `from` names the pair type as in
+/// [`execute_surround_delete`]. A `to` of [`TAG_CHAR`] opens a prompt for
+/// the replacement tag, and any other `to` is a literal pair char.
+/// Selections whose cursor is not enclosed by a matching pair are
+/// skipped. Pairs are deduped before edits run.
 pub(crate) fn execute_surround_replace(stoat: &mut Stoat, from: char, to: char) -> UpdateEffect {
-    let from_pair = (from != 'm').then(|| surround_pair_for(from));
+    if to == TAG_CHAR {
+        return open_tag_input(stoat, SurroundTagOp::Replace { from });
+    }
     let (new_open, new_close) = surround_pair_for(to);
-    let pairs = match collect_surround_pairs(stoat, from_pair) {
-        Some(p) if !p.is_empty() => p,
-        _ => return UpdateEffect::None,
+    replace_surround(stoat, from, &new_open.to_string(), &new_close.to_string())
+}
+
+/// Replace the open and close of the enclosing pair named by `from` around
+/// every cursor with `new_open` and `new_close`. Empty replacements delete
+/// the pair.
+fn replace_surround(
+    stoat: &mut Stoat,
+    from: char,
+    new_open: &str,
+    new_close: &str,
+) -> UpdateEffect {
+    let pairs: Vec<(Range<usize>, Range<usize>)> = if from == TAG_CHAR {
+        match collect_tag_pairs(stoat) {
+            Some(tags) => tags.into_iter().map(|tag| (tag.open, tag.close)).collect(),
+            None => return UpdateEffect::None,
+        }
+    } else {
+        let pair = (from != 'm').then(|| surround_pair_for(from));
+        match collect_surround_pairs(stoat, pair) {
+            Some(pairs) => pairs
+                .into_iter()
+                .map(|(open_off, close_off, open, close)| {
+                    (
+                        open_off..open_off + open.len_utf8(),
+                        close_off..close_off + close.len_utf8(),
+                    )
+                })
+                .collect(),
+            None => return UpdateEffect::None,
+        }
     };
+    if pairs.is_empty() {
+        return UpdateEffect::None;
+    }
 
-    let buffer_id = focused_buffer_id(stoat).expect("checked by collect_surround_pairs");
-    let ws = stoat.active_workspace_mut();
-    let buffer = ws.buffers.get(buffer_id).expect("buffer");
+    let buffer_id = focused_buffer_id(stoat).expect("checked by pair collection");
+    let edits = pairs
+        .into_iter()
+        .flat_map(|(open, close)| [(open, new_open), (close, new_close)])
+        .collect();
+    apply_edits(stoat, buffer_id, edits);
+    UpdateEffect::Redraw
+}
+
+/// Rename the enclosing tag around every cursor to `name`, keeping the
+/// opening tag's attributes.
+fn rename_tags(stoat: &mut Stoat, name: &str) {
+    let Some(tags) = collect_tag_pairs(stoat) else {
+        return;
+    };
+    let Some(buffer_id) = focused_buffer_id(stoat) else {
+        return;
+    };
+    let edits = tags
+        .into_iter()
+        .flat_map(|tag| [(tag.open_name, name), (tag.close_name, name)])
+        .collect();
+    apply_edits(stoat, buffer_id, edits);
+}
+
+/// Apply `edits`, given as ranges of the unedited text, to `buffer_id` as one
+/// batch.
+///
+/// Pairs found from different cursors may nest or repeat, so their delimiters
+/// interleave. The edits are deduped and applied from the end of the buffer
+/// back, which keeps every range still pending in front of the text already
+/// rewritten. An edit overlapping one already applied is dropped.
+fn apply_edits(
+    stoat: &mut Stoat,
+    buffer_id: crate::buffer::BufferId,
+    mut edits: Vec<(Range<usize>, &str)>,
+) {
+    edits.sort_unstable_by(|(a, _), (b, _)| (b.start, b.end).cmp(&(a.start, a.end)));
+    edits.dedup_by(|(a, _), (b, _)| a == b);
+    let Some(buffer) = stoat.active_workspace().buffers.get(buffer_id) else {
+        return;
+    };
     let mut guard = buffer.write().expect("poisoned");
-    let new_open_str = new_open.to_string();
-    let new_close_str = new_close.to_string();
-    for (open_off, close_off, old_open, old_close) in pairs.iter().rev() {
-        guard.edit(
-            *close_off..*close_off + old_close.len_utf8(),
-            &new_close_str,
-        );
-        guard.edit(*open_off..*open_off + old_open.len_utf8(), &new_open_str);
+    let mut applied_from = usize::MAX;
+    for (range, text) in edits {
+        if range.end > applied_from {
+            continue;
+        }
+        applied_from = range.start;
+        guard.edit(range, text);
+    }
+}
+
+/// An element's opening and closing tags, as byte ranges of each whole tag and
+/// of the name inside it.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct TagPair {
+    pub(crate) open: Range<usize>,
+    pub(crate) open_name: Range<usize>,
+    pub(crate) close: Range<usize>,
+    pub(crate) close_name: Range<usize>,
+}
+
+/// The enclosing tag around every selection's primary cursor in the focused
+/// editor, deduped and sorted ascending. `None` when the focused pane is not an
+/// editor.
+fn collect_tag_pairs(stoat: &mut Stoat) -> Option<Vec<TagPair>> {
+    let (buffer_id, cursors) = focused_cursors(stoat)?;
+    let ws = stoat.active_workspace();
+    let buffer = ws.buffers.get(buffer_id)?;
+    let rope = buffer.read().expect("poisoned").rope().clone();
+    let snapshot = ws.buffers.syntax_map(buffer_id).map(|m| m.snapshot());
+    let mut tags: Vec<TagPair> = cursors
+        .into_iter()
+        .filter_map(|cursor| find_tag_pair(&rope, cursor, deepest_tree_at(snapshot, cursor)))
+        .collect();
+    tags.sort_unstable();
+    tags.dedup();
+    Some(tags)
+}
+
+/// The innermost element whose tags enclose `cursor`, with the cursor anywhere
+/// from the opening tag's `<` through the closing tag's `>`.
+///
+/// Opening tags are matched to closing tags of the same name with a stack, so
+/// nested elements of the same name pair correctly. An opening tag without a
+/// closing tag, like HTML's `<br>`, is dropped when an enclosing element
+/// closes, and self-closing tags never pair. When `tree` is `Some`, tags that
+/// start inside a string or comment node are ignored, like the bracket walk in
+/// [`find_surround_pair`].
+pub(crate) fn find_tag_pair(
+    rope: &Rope,
+    cursor: usize,
+    tree: Option<&stoat_language::Tree>,
+) -> Option<TagPair> {
+    let len = rope.len();
+    let mut radius = TAG_SCAN_RADIUS;
+    loop {
+        // Start the window on a line boundary so it never opens partway
+        // through a tag.
+        let start = cursor.saturating_sub(radius);
+        let start = rope.point_to_offset(Point::new(rope.offset_to_point(start).row, 0));
+        let end = rope.clip_offset(cursor.saturating_add(radius).min(len), Bias::Right);
+        let pair = scan_tag_pair(rope, start..end, cursor, tree);
+        if pair.is_some() || (start == 0 && end == len) {
+            return pair;
+        }
+        radius = radius.saturating_mul(2);
     }
-    UpdateEffect::Redraw
+}
+
+/// Bytes on each side of the cursor that [`find_tag_pair`] scans first. The
+/// window doubles until it holds an enclosing pair or the whole buffer.
+const TAG_SCAN_RADIUS: usize = 4 * 1024;
+
+/// [`find_tag_pair`] over the `window` slice of `rope`.
+///
+/// A closing tag with no opening tag in the window closes one that opened
+/// before it. Every opening tag still on the stack is then inside that
+/// element and gets dropped, as a scan from the start of the buffer would.
+fn scan_tag_pair(
+    rope: &Rope,
+    window: Range<usize>,
+    cursor: usize,
+    tree: Option<&stoat_language::Tree>,
+) -> Option<TagPair> {
+    let base = window.start;
+    let text: String = rope.chunks_in_range(window).collect();
+    let shift = |r: Range<usize>| r.start + base..r.end + base;
+    let mut open_tags: Vec<(Range<usize>, Range<usize>)> = Vec::new();
+    let mut best: Option<TagPair> = None;
+    for caps in TAG_RE.captures_iter(&text) {
+        let whole = caps.get(0).expect("group 0").range();
+        if in_skip_zone(tree, whole.start + base) {
+            continue;
+        }
+        let name = caps.get(2).expect("tag name").range();
+        if caps.get(1).is_none() {
+            if caps.get(3).is_none() {
+                open_tags.push((whole, name));
+            }
+            continue;
+        }
+        let Some(i) = open_tags
+            .iter()
+            .rposition(|(_, open_name)| text[open_name.clone()] == text[name.clone()])
+        else {
+            if base > 0 {
+                open_tags.clear();
+            }
+            continue;
+        };
+        let (open, open_name) = open_tags[i].clone();
+        open_tags.truncate(i);
+        let (open, close) = (shift(open), shift(whole));
+        // Inner elements close first, so a later enclosing pair only wins when
+        // it opened after the current best.
+        let innermost = best.as_ref().is_none_or(|b| b.open.start < open.start);
+        if open.start <= cursor && cursor < close.end && innermost {
+            best = Some(TagPair {
+                open,
+                open_name: shift(open_name),
+                close,
+                close_name: shift(name),
+            });
+        }
+    }
+    best
 }
 
 /// Walk every selection's primary cursor in the focused editor and
@@ -196,30 +457,7 @@ fn collect_surround_pairs(
     stoat: &mut Stoat,
     pair: Option<(char, char)>,
 ) -> Option<Vec<(usize, usize, char, char)>> {
-    let (buffer_id, cursors) = {
-        let ws = stoat.active_workspace_mut();
-        let focused = ws.panes.focus();
-        let editor_id = match ws.panes.pane(focused).view {
-            View::Editor(id) => id,
-            _ => return None,
-        };
-        let editor = ws.editors.get_mut(editor_id).expect("editor");
-        let buffer_id = editor.buffer_id;
-        let snapshot = editor.display_map.snapshot();
-        let buffer_snapshot = snapshot.buffer_snapshot();
-        let cursors: Vec<usize> = editor
-            .selections
-            .all_anchors()
-            .iter()
-            .map(|sel| {
-                let tail_off = buffer_snapshot.resolve_anchor(&sel.tail());
-                let head_off = buffer_snapshot.resolve_anchor(&sel.head());
-                stoat_text::cursor_offset(buffer_snapshot.rope(), tail_off, head_off)
-            })
-            .collect();
-        (buffer_id, cursors)
-    };
-
+    let (buffer_id, cursors) = focused_cursors(stoat)?;
     let ws = stoat.active_workspace();
     let mut pairs: Vec<(usize, usize, char, char)> = cursors
         .into_iter()
@@ -234,6 +472,31 @@ fn collect_surround_pairs(
     Some(pairs)
 }
 
+/// The focused editor's buffer and the primary cursor offset of each of its
+/// selections. `None` when the focused pane is not an editor.
+fn focused_cursors(stoat: &mut Stoat) -> Option<(crate::buffer::BufferId, Vec<usize>)> {
+    let ws = stoat.active_workspace_mut();
+    let focused = ws.panes.focus();
+    let editor_id = match ws.panes.pane(focused).view {
+        View::Editor(id) => id,
+        _ => return None,
+    };
+    let editor = ws.editors.get_mut(editor_id).expect("editor");
+    let snapshot = editor.display_map.snapshot();
+    let buffer_snapshot = snapshot.buffer_snapshot();
+    let cursors: Vec<usize> = editor
+        .selections
+        .all_anchors()
+        .iter()
+        .map(|sel| {
+            let tail_off = buffer_snapshot.resolve_anchor(&sel.tail());
+            let head_off = buffer_snapshot.resolve_anchor(&sel.head());
+            stoat_text::cursor_offset(buffer_snapshot.rope(), tail_off, head_off)
+        })
+        .collect();
+    Some((editor.buffer_id, cursors))
+}
+
 /// The nearest enclosing `(open, close)` pair around `cursor` in the
 /// buffer's live rope, resolved against the deepest syntax layer
 /// covering the cursor so brackets inside string / comment nodes are
@@ -938,6 +1201,137 @@ mod tests {
         assert_eq!(buffer_text(&h, &path), "abc\n");
     }
 
+    #[test]
+    fn find_tag_pair_picks_innermost_same_name() {
+        let r = rope("<b><b>x</b></b>");
+        let tag = find_tag_pair(&r, 6, None).expect("enclosed");
+        assert_eq!((tag.open, tag.close), (3..6, 7..11));
+        let tag = find_tag_pair(&r, 1, None).expect("enclosed");
+        assert_eq!((tag.open, tag.close), (0..3, 11..15));
+    }
+
+    #[test]
+    fn find_tag_pair_skips_void_and_self_closing_tags() {
+        let src = "<p>a<br>b<img src=\"x>y\"/>c</p>";
+        let r = rope(src);
+        let cursor = src.find('c').expect("target present");
+        let tag = find_tag_pair(&r, cursor, None).expect("enclosed");
+        assert_eq!(tag.open, 0..3);
+        assert_eq!(tag.open_name, 1..2);
+        assert_eq!(tag.close, src.len() - 4..src.len());
+        assert_eq!(find_tag_pair(&rope("a<br>b"), 3, None), None);
+    }
+
+    #[test]
+    fn find_tag_pair_widens_window_past_a_foreign_close() {
+        let filler = "x\n".repeat(10_000);
+        let src = format!("<b>{filler}<i>y</b>z</i>");
+        let r = rope(&src);
+        let cursor = src.find('y').expect("target present");
+        let tag = find_tag_pair(&r, cursor, None).expect("enclosed");
+        let close = src.find("</b>").expect("close present");
+        assert_eq!((tag.open, tag.close), (0..3, close..close + 4));
+    }
+
+    fn add_cursor_at(h: &mut TestHarness, offset: usize) {
+        let editor = focused_editor_mut(&mut h.stoat).expect("editor");
+        let snapshot = editor.display_map.snapshot();
+        let buf_snap = snapshot.buffer_snapshot();
+        let anchor = buf_snap.anchor_at(offset, Bias::Left);
+        editor
+            .selections
+            .insert_cursor(anchor, SelectionGoal::None, buf_snap);
+    }
+
+    #[test]
+    fn surround_delete_nested_pairs_from_two_cursors() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "((a) b)\n");
+        crate::action_handlers::movement::jump_to_offset(&mut h.stoat, 2);
+        add_cursor_at(&mut h, 5);
+        h.type_keys("m d (");
+        assert_eq!(buffer_text(&h, &path), "a b\n");
+    }
+
+    #[test]
+    fn surround_rename_nested_tags_from_two_cursors() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "<a><b>x</b>y</a>\n");
+        crate::action_handlers::movement::jump_to_offset(&mut h.stoat, 6);
+        add_cursor_at(&mut h, 11);
+        h.type_keys("m r t t");
+        h.type_text("span");
+        h.type_keys("enter");
+        assert_eq!(buffer_text(&h, &path), "<span><span>x</span>y</span>\n");
+    }
+
+    #[test]
+    fn surround_delete_tag() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "<div class=\"x\">abc</div>\n");
+        crate::action_handlers::movement::jump_to_offset(&mut h.stoat, 16);
+        h.type_keys("m d t");
+        assert_eq!(buffer_text(&h, &path), "abc\n");
+    }
+
+    #[test]
+    fn surround_replace_tag_with_pair() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "<b>abc</b>\n");
+        crate::action_handlers::movement::jump_to_offset(&mut h.stoat, 4);
+        h.type_keys("m r t (");
+        assert_eq!(buffer_text(&h, &path), "(abc)\n");
+    }
+
+    #[test]
+    fn surround_add_tag_prompts_for_name() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "abc\n");
+        h.type_keys("v l l");
+        h.type_keys("escape");
+        h.type_keys("m s t");
+        assert!(h.stoat.surround_tag_input.is_some());
+        h.type_text("a href=\"#\"");
+        h.type_keys("enter");
+        assert!(h.stoat.surround_tag_input.is_none());
+        assert_eq!(buffer_text(&h, &path), "<a href=\"#\">abc</a>\n");
+    }
+
+    #[test]
+    fn surround_replace_tag_with_tag_name_keeps_attributes() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "<i id=\"x\">abc</i>\n");
+        crate::action_handlers::movement::jump_to_offset(&mut h.stoat, 12);
+        h.type_keys("m r t t");
+        h.type_text("em");
+        h.type_keys("enter");
+        assert_eq!(buffer_text(&h, &path), "<em id=\"x\">abc</em>\n");
+    }
+
+    #[test]
+    fn surround_replace_pair_with_tag() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "(abc)\n");
+        crate::action_handlers::movement::jump_to_offset(&mut h.stoat, 2);
+        h.type_keys("m r ( t");
+        h.type_text("b");
+        h.type_keys("enter");
+        assert_eq!(buffer_text(&h, &path), "<b>abc</b>\n");
+    }
+
+    #[test]
+    fn surround_tag_prompt_cancel_leaves_buffer() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "abc\n");
+        h.type_keys("v l l");
+        h.type_keys("escape");
+        h.type_keys("m s t");
+        h.type_text("b");
+        h.type_keys("escape");
+        assert!(h.stoat.surround_tag_input.is_none());
+        assert_eq!(buffer_text(&h, &path), "abc\n");
+    }
+
     #[test]
     fn surround_replace_closest_rewrites_innermost() {
         let mut h = TestHarness::with_size(40, 10);
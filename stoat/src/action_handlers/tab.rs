@@ -1,7 +1,8 @@
 use crate::{
     action_handlers::pane::{dispose_view, EditorDisposal},
     app::{Stoat, UpdateEffect},
-    command_palette::{Availability, CommandPalette},
+    command_palette::{key_hints, Availability, CommandPalette, PaletteAlias},
+    keymap_state::StoatKeymapState,
 };
 use stoat_config::TabBarMode;
 
@@ -142,9 +143,18 @@ pub(super) fn rename_tab(stoat: &mut Stoat, name: Option<&str>) -> UpdateEffect
 fn open_rename_palette(stoat: &mut Stoat) -> UpdateEffect {
     let executor = stoat.executor.clone();
     let availability = Availability::from_stoat(stoat);
+    let aliases = PaletteAlias::resolve_all(&stoat.settings.palette_aliases);
+    let key_hints = key_hints(&stoat.keymap, &StoatKeymapState::from_stoat(stoat));
+    crate::overlay::push(stoat, crate::overlay::Overlay::CommandPalette);
     {
         let ws = stoat.active_workspace_mut();
-        stoat.command_palette = Some(CommandPalette::new(ws, executor, availability));
+        stoat.command_palette = Some(CommandPalette::new(
+            ws,
+            executor,
+            availability,
+            aliases,
+            key_hints,
+        ));
     }
 
     let active_idx = stoat.active_workspace;
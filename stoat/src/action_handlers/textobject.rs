@@ -168,22 +168,7 @@ fn find_textobject_treesitter(
     mode: TextobjectMode,
 ) -> Option<std::ops::Range<usize>> {
     let syntax_map = ws.buffers.syntax_map(buffer_id)?;
-    let snapshot = syntax_map.snapshot();
-    let layer =
-        snapshot
-            .iter_layers()
-            .fold(None::<&stoat_language::SyntaxLayer>, |acc, layer| {
-                let start = layer.start_offset as usize;
-                let end = layer.end_offset as usize;
-                if start <= cursor && end >= cursor {
-                    match acc {
-                        Some(prev) if prev.depth >= layer.depth => acc,
-                        _ => Some(layer),
-                    }
-                } else {
-                    acc
-                }
-            })?;
+    let layer = syntax_map.snapshot().deepest_layer_at(cursor)?;
     let query = layer.language.textobjects_query.as_ref()?;
     let buffer = ws.buffers.get(buffer_id)?;
     let guard = buffer.read().ok()?;
@@ -82,22 +82,7 @@ fn collect_capture_starts_for_buffer(
     let Some(syntax_map) = ws.buffers.syntax_map(buffer_id) else {
         return Vec::new();
     };
-    let snapshot = syntax_map.snapshot();
-    let layer = snapshot
-        .iter_layers()
-        .fold(None::<&stoat_language::SyntaxLayer>, |acc, layer| {
-            let start = layer.start_offset as usize;
-            let end = layer.end_offset as usize;
-            if start <= cursor && end >= cursor {
-                match acc {
-                    Some(prev) if prev.depth >= layer.depth => acc,
-                    _ => Some(layer),
-                }
-            } else {
-                acc
-            }
-        });
-    let Some(layer) = layer else {
+    let Some(layer) = syntax_map.snapshot().deepest_layer_at(cursor) else {
         return Vec::new();
     };
     let Some(query) = layer.language.textobjects_query.as_ref() else {
@@ -54,7 +54,14 @@ pub(super) fn close_workspace(stoat: &mut Stoat) -> UpdateEffect {
     if !stoat.persistence_disabled {
         let ws = &stoat.workspaces[active_id];
         stoat.save_workspace(ws);
-        if let Ok(path) = crate::workspace::state_path_for(&ws.git_root, ws.uid, &*stoat.fs_host) {
+        // Every format, since a copy saved before `workspace.state_format`
+        // changed would otherwise resurrect the closed workspace.
+        for format in crate::workspace::store::FORMATS {
+            let Ok(path) =
+                crate::workspace::state_path_for(&ws.git_root, ws.uid, format, &*stoat.fs_host)
+            else {
+                continue;
+            };
             let meta_path = crate::workspace::registry::meta_path_for(&path);
             for target in [path, meta_path] {
                 if stoat.fs_host.exists(&target)
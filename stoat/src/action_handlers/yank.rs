@@ -41,18 +41,26 @@ pub(super) fn yank(stoat: &mut Stoat) -> UpdateEffect {
 /// (search, selection index, last insert) drop them.
 ///
 /// Shared by yank and by delete, which yanks the removed text before deleting
-/// it.
+/// it. With `clipboard.mirror` on, an unnamed-register write also reaches the
+/// system clipboard.
 pub(crate) fn write_fragments_to_register(
     stoat: &mut Stoat,
     target: Register,
     fragments: Vec<String>,
 ) {
+    if target == Register::Unnamed && stoat.settings.clipboard_mirror == Some(true) {
+        crate::host::clipboard_copy(
+            stoat.clipboard_host().as_ref(),
+            stoat.env_host().as_ref(),
+            &clipboard_text(&fragments),
+        );
+    }
     match target {
         Register::Clipboard => {
             crate::host::clipboard_copy(
                 stoat.clipboard_host().as_ref(),
                 stoat.env_host().as_ref(),
-                &fragments.join("\n"),
+                &clipboard_text(&fragments),
             );
         },
         Register::Blackhole => {},
@@ -63,6 +71,20 @@ pub(crate) fn write_fragments_to_register(
     }
 }
 
+/// Flatten per-selection `fragments` into one clipboard string.
+///
+/// Line-shaped fragments (every one ends with a line ending) concatenate as-is
+/// so the clipboard text stays line-shaped and a clipboard paste inserts whole
+/// lines, as [`paste_text`] does for a line-shaped register. Anything else
+/// joins with newlines so a later paste can split back per line.
+fn clipboard_text(fragments: &[String]) -> String {
+    if fragments.iter().all(|f| f.ends_with('\n')) {
+        fragments.concat()
+    } else {
+        fragments.join("\n")
+    }
+}
+
 pub(super) fn select_register(stoat: &mut Stoat) -> UpdateEffect {
     stoat.pending_register_select = true;
     UpdateEffect::Redraw
@@ -215,7 +237,7 @@ pub(super) fn yank_to_clipboard(stoat: &mut Stoat) -> UpdateEffect {
     crate::host::clipboard_copy(
         stoat.clipboard_host().as_ref(),
         stoat.env_host().as_ref(),
-        &fragments.join("\n"),
+        &clipboard_text(&fragments),
     );
     UpdateEffect::None
 }
@@ -237,6 +259,25 @@ pub(super) fn yank_main_to_clipboard(stoat: &mut Stoat) -> UpdateEffect {
     UpdateEffect::None
 }
 
+/// Write every non-collapsed selection's content to the system clipboard as
+/// [`yank_to_clipboard`] does, then delete the selections. The register store
+/// is bypassed, so the unnamed register keeps its previous content. No-op when
+/// every selection is collapsed.
+pub(super) fn cut_to_clipboard(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(fragments) = selection_fragments(stoat) else {
+        return UpdateEffect::None;
+    };
+    if fragments.iter().all(String::is_empty) {
+        return UpdateEffect::None;
+    }
+    crate::host::clipboard_copy(
+        stoat.clipboard_host().as_ref(),
+        stoat.env_host().as_ref(),
+        &clipboard_text(&fragments),
+    );
+    super::movement::delete_selection_no_yank(stoat)
+}
+
 pub(super) fn paste_clipboard_after(stoat: &mut Stoat) -> UpdateEffect {
     paste_clipboard(stoat, PasteSide::After)
 }
@@ -1020,6 +1061,64 @@ mod tests {
         assert_eq!(buffer_text(&h, &path), "abc\n");
     }
 
+    #[test]
+    fn cut_to_clipboard_writes_then_deletes_selection() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "abc\ndef\n");
+        h.type_keys("v l l");
+        crate::action_handlers::dispatch(&mut h.stoat, &action::CutToClipboard);
+        assert_eq!(h.fake_clipboard().writes(), vec!["abc".to_string()]);
+        assert_eq!(buffer_text(&h, &path), "\ndef\n");
+        assert!(
+            h.stoat
+                .registers
+                .read(crate::register::Register::Unnamed)
+                .is_none(),
+            "a clipboard cut leaves the unnamed register alone"
+        );
+    }
+
+    #[test]
+    fn line_yank_round_trips_through_clipboard_as_a_new_line() {
+        let mut h = TestHarness::with_size(40, 10);
+        let path = seed(&mut h, "X\nY\n");
+        h.type_keys("x");
+        crate::action_handlers::dispatch(&mut h.stoat, &action::YankToClipboard);
+        assert_eq!(h.fake_clipboard().writes(), vec!["X\n".to_string()]);
+        crate::action_handlers::dispatch(&mut h.stoat, &action::PasteClipboardAfter);
+        assert_eq!(buffer_text(&h, &path), "X\nX\nY\n");
+    }
+
+    #[test]
+    fn clipboard_text_keeps_line_shaped_fragments_line_shaped() {
+        let lines = vec!["a\n".to_string(), "b\n".to_string()];
+        assert_eq!(super::clipboard_text(&lines), "a\nb\n");
+        let spans = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(super::clipboard_text(&spans), "a\nb");
+    }
+
+    #[test]
+    fn clipboard_mirror_copies_unnamed_yanks() {
+        let mut h = TestHarness::with_size(40, 10);
+        seed(&mut h, "abc\n");
+        h.stoat.settings.clipboard_mirror = Some(true);
+        h.type_keys("v l l");
+        crate::action_handlers::dispatch(&mut h.stoat, &action::Yank);
+        assert_eq!(h.fake_clipboard().writes(), vec!["abc".to_string()]);
+    }
+
+    #[test]
+    fn clipboard_mirror_skips_named_registers_and_defaults_off() {
+        let mut h = TestHarness::with_size(40, 10);
+        seed(&mut h, "abc\n");
+        h.type_keys("v l l");
+        crate::action_handlers::dispatch(&mut h.stoat, &action::Yank);
+        h.stoat.settings.clipboard_mirror = Some(true);
+        super::execute_select_register(&mut h.stoat, 'a');
+        crate::action_handlers::dispatch(&mut h.stoat, &action::Yank);
+        assert!(h.fake_clipboard().writes().is_empty());
+    }
+
     #[test]
     fn yank_to_clipboard_via_space_dquote_y_binding() {
         let mut h = TestHarness::with_size(40, 10);
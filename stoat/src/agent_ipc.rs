@@ -52,6 +52,30 @@ pub enum AgentControl {
         request: AgentQuery,
         reply: oneshot::Sender<Value>,
     },
+    /// Open the agent's proposed file contents as a review in the session's
+    /// workspace and fire `reply` once the review is up. Nothing reaches disk
+    /// until the user accepts chunks and applies them.
+    ProposeEdits {
+        uid: WorkspaceUid,
+        edits: Vec<ProposedEdit>,
+        reply: oneshot::Sender<Value>,
+    },
+    /// Ask the user whether the agent may run `tool`, and fire `reply` with the
+    /// decision. The hook that sent the request stays blocked until then.
+    Permission {
+        uid: WorkspaceUid,
+        tool: String,
+        detail: Option<String>,
+        reply: oneshot::Sender<Value>,
+    },
+}
+
+/// One file of a `propose-edits` request: the full text the agent wants
+/// `path` to hold. A relative `path` resolves against the workspace root.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ProposedEdit {
+    pub path: PathBuf,
+    pub text: String,
 }
 
 /// A read-only interrogation of live session state, answered by the event loop.
@@ -67,6 +91,10 @@ pub enum AgentQuery {
     Diagnostics { path: Option<PathBuf> },
     /// Hover at an LSP UTF-16 `line`/`col` within `path`.
     Hover { path: PathBuf, line: u32, col: u32 },
+    /// The live text of `path` as open in the session, unsaved edits included.
+    ReadBuffer { path: PathBuf },
+    /// Regex matches across the session's workspace files.
+    Search { pattern: String },
 }
 
 /// A request decoded from one socket line.
@@ -87,6 +115,19 @@ enum AgentRequest {
     /// `{"req":"hover","path":"...","line":N,"col":N}`. `line`/`col` are LSP
     /// UTF-16 positions, forwarded to the server unconverted.
     Hover { path: PathBuf, line: u32, col: u32 },
+    /// `{"req":"read-buffer","path":"..."}`.
+    ReadBuffer { path: PathBuf },
+    /// `{"req":"search","pattern":"..."}`. `pattern` is a regex.
+    Search { pattern: String },
+    /// `{"req":"propose-edits","edits":[{"path":"...","text":"..."}]}`.
+    ProposeEdits { edits: Vec<ProposedEdit> },
+    /// `{"req":"permission","tool":"...","detail":"..."}`. `detail` is
+    /// optional.
+    Permission {
+        tool: String,
+        #[serde(default)]
+        detail: Option<String>,
+    },
 }
 
 /// Bind the per-session socket at `socket_path` and forward decoded hook events
@@ -164,7 +205,8 @@ async fn serve_connection<R>(
         }
 
         if let Ok(request) = serde_json::from_str::<AgentRequest>(trimmed) {
-            let query = match request {
+            let (reply_tx, reply_rx) = oneshot::channel();
+            let control = match request {
                 AgentRequest::OpenEditor { path } => {
                     let (done_tx, done_rx) = oneshot::channel();
                     if control_tx
@@ -184,21 +226,45 @@ async fn serve_connection<R>(
                         .await;
                     return;
                 },
-                AgentRequest::LspStatus => AgentQuery::LspStatus,
-                AgentRequest::Diagnostics { path } => AgentQuery::Diagnostics { path },
-                AgentRequest::Hover { path, line, col } => AgentQuery::Hover { path, line, col },
-            };
-
-            let (reply_tx, reply_rx) = oneshot::channel();
-            if control_tx
-                .send(AgentControl::Query {
+                AgentRequest::ProposeEdits { edits } => AgentControl::ProposeEdits {
+                    uid,
+                    edits,
+                    reply: reply_tx,
+                },
+                AgentRequest::Permission { tool, detail } => AgentControl::Permission {
+                    uid,
+                    tool,
+                    detail,
+                    reply: reply_tx,
+                },
+                AgentRequest::LspStatus => AgentControl::Query {
+                    uid,
+                    request: AgentQuery::LspStatus,
+                    reply: reply_tx,
+                },
+                AgentRequest::Diagnostics { path } => AgentControl::Query {
+                    uid,
+                    request: AgentQuery::Diagnostics { path },
+                    reply: reply_tx,
+                },
+                AgentRequest::Hover { path, line, col } => AgentControl::Query {
+                    uid,
+                    request: AgentQuery::Hover { path, line, col },
+                    reply: reply_tx,
+                },
+                AgentRequest::ReadBuffer { path } => AgentControl::Query {
+                    uid,
+                    request: AgentQuery::ReadBuffer { path },
+                    reply: reply_tx,
+                },
+                AgentRequest::Search { pattern } => AgentControl::Query {
                     uid,
-                    request: query,
+                    request: AgentQuery::Search { pattern },
                     reply: reply_tx,
-                })
-                .await
-                .is_err()
-            {
+                },
+            };
+
+            if control_tx.send(control).await.is_err() {
                 return;
             }
             let value = match reply_rx.await {
@@ -349,6 +415,46 @@ mod tests {
         conn.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn propose_edits_request_routes_to_control() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let (tx, _rx) = tokio::sync::mpsc::channel(8);
+        let (control_tx, mut control_rx) = tokio::sync::mpsc::channel(8);
+        let (client, server) = tokio::io::duplex(256);
+        let (client_read, mut client_write) = tokio::io::split(client);
+        let conn = tokio::spawn(async move {
+            serve_connection(server, WorkspaceUid(3), &tx, &control_tx).await;
+        });
+
+        client_write
+            .write_all(b"{\"req\":\"propose-edits\",\"edits\":[{\"path\":\"a.rs\",\"text\":\"new\\n\"}]}\n")
+            .await
+            .unwrap();
+        let AgentControl::ProposeEdits { edits, reply, .. } =
+            control_rx.recv().await.expect("control message")
+        else {
+            panic!("expected a propose-edits control message");
+        };
+        assert_eq!(
+            edits,
+            vec![ProposedEdit {
+                path: PathBuf::from("a.rs"),
+                text: "new\n".into(),
+            }]
+        );
+        reply.send(serde_json::json!({ "files": 1 })).unwrap();
+        let mut replies = BufReader::new(client_read).lines();
+        assert_eq!(
+            replies.next_line().await.unwrap().unwrap(),
+            r#"{"files":1}"#
+        );
+
+        drop(client_write);
+        drop(replies);
+        conn.await.unwrap();
+    }
+
     #[tokio::test]
     async fn query_requests_route_to_control_and_reply_over_one_connection() {
         use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
@@ -0,0 +1,115 @@
+//! Prompts for tool calls an owned agent asks permission to run.
+//!
+//! A `PreToolUse` hook that runs `stoat agent-api permission` sends a
+//! `permission` request over the session socket and blocks until it hears
+//! back. The event loop queues each request as an [`AgentPermissionRequest`]
+//! and shows the oldest as a modal, so the user allows it once, allows the tool
+//! for the rest of the workspace's life, or denies it. A tool allowed with
+//! "always" is recorded on the workspace and persisted with it, and later
+//! requests for that tool are answered without a prompt.
+
+use crate::workspace::WorkspaceUid;
+use serde_json::{json, Value};
+use tokio::sync::oneshot;
+
+/// How the user answered a permission prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PermissionDecision {
+    AllowOnce,
+    AllowAlways,
+    Deny,
+}
+
+/// A tool call an owned agent is blocked on until the user decides.
+///
+/// Dropping a request without [`Self::answer`] closes the hook's connection
+/// with no reply, which the hook client treats as a denial, so a request lost
+/// to a closed workspace never lets the tool run.
+pub(crate) struct AgentPermissionRequest {
+    pub uid: WorkspaceUid,
+    pub tool: String,
+    /// What the call would do, e.g. the command line or the target path, as
+    /// the hook reported it.
+    pub detail: Option<String>,
+    reply: oneshot::Sender<Value>,
+}
+
+impl AgentPermissionRequest {
+    pub(crate) fn new(
+        uid: WorkspaceUid,
+        tool: String,
+        detail: Option<String>,
+        reply: oneshot::Sender<Value>,
+    ) -> Self {
+        Self {
+            uid,
+            tool,
+            detail,
+            reply,
+        }
+    }
+
+    /// Reply to the waiting hook with `decision`.
+    pub(crate) fn answer(self, decision: PermissionDecision) {
+        let _ = self.reply.send(decision_reply(decision));
+    }
+}
+
+/// The socket reply for `decision`, `{"decision":"allow"}` or
+/// `{"decision":"deny"}`.
+pub(crate) fn decision_reply(decision: PermissionDecision) -> Value {
+    let decision = match decision {
+        PermissionDecision::AllowOnce | PermissionDecision::AllowAlways => "allow",
+        PermissionDecision::Deny => "deny",
+    };
+    json!({ "decision": decision })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{agent_ipc::AgentControl, keymap_state::modal_predicate};
+    use serde_json::json;
+    use tokio::sync::oneshot;
+
+    fn request(h: &mut crate::test_harness::TestHarness) -> oneshot::Receiver<serde_json::Value> {
+        let uid = h.stoat.active_workspace().uid();
+        let (reply_tx, reply_rx) = oneshot::channel();
+        h.stoat.handle_agent_control(AgentControl::Permission {
+            uid,
+            tool: "Bash".into(),
+            detail: Some("cargo test".into()),
+            reply: reply_tx,
+        });
+        reply_rx
+    }
+
+    #[test]
+    fn always_allow_answers_and_skips_later_prompts() {
+        let mut h = crate::Stoat::test();
+        let mut first = request(&mut h);
+        assert_eq!(modal_predicate(&h.stoat), Some("agent_permission"));
+        assert!(first.try_recv().is_err(), "blocked until the user decides");
+
+        h.type_keys("a");
+        assert_eq!(first.try_recv().unwrap(), json!({ "decision": "allow" }));
+        assert!(h.stoat.agent_permissions.is_empty());
+        assert!(h
+            .stoat
+            .active_workspace()
+            .agent_allowed_tools
+            .contains("Bash"));
+
+        let mut second = request(&mut h);
+        assert_eq!(second.try_recv().unwrap(), json!({ "decision": "allow" }));
+        assert!(h.stoat.agent_permissions.is_empty(), "no second prompt");
+    }
+
+    #[test]
+    fn deny_answers_and_records_nothing() {
+        let mut h = crate::Stoat::test();
+        let mut reply = request(&mut h);
+        h.type_keys("n");
+        assert_eq!(reply.try_recv().unwrap(), json!({ "decision": "deny" }));
+        assert!(h.stoat.active_workspace().agent_allowed_tools.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     action_handlers,
     agent_ipc::{AgentControl, AgentEvent},
+    agent_permission::{AgentPermissionRequest, PermissionDecision},
     agent_status::AgentStatus,
     badge::BadgeTray,
     buffer::{BufferId, TextBufferSnapshot},
@@ -14,17 +15,22 @@ use crate::{
         EnvHost, FsHost, FsWatchHost, GitHost, GitRepo, LanguageServerFeature, LocalEnv, LocalFs,
         LocalGit, LspHost, NoopFsWatcher,
     },
-    keymap::{Keymap, ResolvedAction, StateValue},
-    keymap_state::{normalize_shift_event, resolve_action, StoatKeymapState},
+    input_draft::DraftStack,
+    keymap::{CompiledKey, Keymap, ResolvedAction, SequenceMatch, StateValue},
+    keymap_state::{binding_display_desc, normalize_shift_event, resolve_action, StoatKeymapState},
     pane::{DockId, DockVisibility, FocusTarget, NodeId, PaneId, PaneTree, Placement, View},
     quit_all_confirm::QuitAllConfirm,
     rebase::RebasePause,
     register,
-    render::undercurl::{self, UndercurlSpan},
+    render::{
+        editor::GutterZone,
+        undercurl::{self, UndercurlSpan},
+    },
     review_session::ReviewSource,
     run::{CommandMark, GridSelection, PtyNotification, RunId},
     symbol_finder::SymbolFinder,
     term_session::{TermId, TermReturnFocus, TermSelection},
+    toast::{Toast, ToastAction, ToastLevel},
     ui::RenderFrame,
     workspace::{Workspace, WorkspaceId, WorkspaceUid},
     workspace_picker::WorkspacePicker,
@@ -39,15 +45,17 @@ use ratatui::{
 };
 use slotmap::SlotMap;
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, BTreeSet, VecDeque},
     hash::{Hash, Hasher},
     io,
     ops::Range,
     path::{Path, PathBuf},
     sync::{atomic::AtomicBool, Arc},
 };
-use stoat_action::{Conflict, Diff, OpenFile, ReviewExternalEdit, ReviewRefresh};
-use stoat_config::{LineNumbers, MinimapMode, Settings, Spanned, TabBarMode, ThemeBlock, WrapMode};
+use stoat_action::{Conflict, Diff, DiffWithBuffer, OpenFile, ReviewExternalEdit, ReviewRefresh};
+use stoat_config::{
+    LineNumbers, MinimapMode, Settings, Spanned, StateFormat, TabBarMode, ThemeBlock, WrapMode,
+};
 use stoat_language::{self as language, Language, LanguageRegistry, SyntaxState};
 use stoat_scheduler::Executor;
 use stoat_text::{Anchor, Bias, IndentStyle, Selection};
@@ -61,7 +69,8 @@ use tokio::{
     },
 };
 
-pub(crate) const DEFAULT_KEYMAP: &str = include_str!("../../config.stcfg");
+/// The built-in config, used when the user has none or theirs fails to parse.
+pub const DEFAULT_KEYMAP: &str = include_str!("../../config.stcfg");
 
 /// The default stoatty config, embedded so `:open-config stoatty` can seed a
 /// missing one with the same file the terminal ships.
@@ -254,6 +263,11 @@ pub(crate) const AUTO_RELOAD_POLL: std::time::Duration = std::time::Duration::fr
 /// wakes the run loop so [`crate::render::frame`] can clear the expired message.
 const STATUS_MESSAGE_TTL: std::time::Duration = std::time::Duration::from_secs(4);
 
+/// How long a started key sequence such as `g g` waits for its next chord.
+/// When it elapses, [`Stoat::expire_pending_keys`] runs the binding the
+/// chords pressed so far complete, if any, and drops the rest.
+const KEY_SEQUENCE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(1000);
+
 /// Maximum index updates [`Stoat::drain_index_updates`] processes in one call.
 /// Bounds the graph work per event-loop turn so a large reindex burst cannot
 /// stall input. On hitting the cap the drain reschedules itself to finish the
@@ -283,6 +297,37 @@ pub enum UpdateEffect {
 /// names the specific pane at the point, which is not necessarily the focused
 /// one, so [`Stoat::target_at`] must return the id even though `ws.focus` no
 /// longer stores it.
+/// Chords pressed toward a sequence binding, awaiting the next one.
+pub(crate) struct PendingKeys {
+    pub(crate) presses: Vec<KeyEvent>,
+    /// The binding `presses` already complete, run if the sequence breaks or
+    /// times out.
+    exact: Option<(Arc<[ResolvedAction]>, Option<f64>)>,
+    /// `(remaining chords, description)` rows for the which-key box.
+    pub(crate) hints: Vec<(String, String)>,
+    deadline: std::time::Instant,
+    /// Wakes the run loop at `deadline`. Dropping it cancels the timer.
+    _expiry: stoat_scheduler::Task<()>,
+}
+
+impl PendingKeys {
+    /// The chords pressed so far, as the which-key box titles them.
+    pub(crate) fn label(&self) -> String {
+        self.presses
+            .iter()
+            .map(|press| {
+                CompiledKey {
+                    code: press.code,
+                    modifiers: press.modifiers,
+                    any_digit: false,
+                }
+                .display_label()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PanelHit {
     Pane(PaneId),
@@ -295,7 +340,7 @@ impl UpdateEffect {
     /// A coalesced batch applies several messages in one loop iteration and
     /// must act on the strongest result. Quit outranks Redraw, which outranks
     /// None. The result does not depend on argument order.
-    fn merge(self, other: UpdateEffect) -> UpdateEffect {
+    pub(crate) fn merge(self, other: UpdateEffect) -> UpdateEffect {
         match (self, other) {
             (UpdateEffect::Quit, _) | (_, UpdateEffect::Quit) => UpdateEffect::Quit,
             (UpdateEffect::Redraw, _) | (_, UpdateEffect::Redraw) => UpdateEffect::Redraw,
@@ -374,9 +419,25 @@ pub struct Stoat {
     /// theme directory. Retained so a config reload rebuilds the same pool
     /// without re-reading or re-parsing the theme files.
     pub(crate) imported_theme_blocks: Vec<Spanned<ThemeBlock>>,
+    /// Source of the user config in effect, or [`None`] while the embedded
+    /// default is. Retained so the project layer can be re-applied over it.
+    pub(crate) user_config_source: Option<String>,
+    /// The environment's theme name passed at startup, retained so a
+    /// re-layered config resolves it the same way.
+    pub(crate) env_theme: Option<String>,
+    /// The active root's `.stoat/config`, layered over the user config by
+    /// [`Self::apply_config_layers`] and re-evaluated by
+    /// [`crate::project_config::sync`] when the root changes.
+    pub(crate) project_config: crate::project_config::ProjectConfig,
     pub(crate) command_palette: Option<CommandPalette>,
+    /// Input text of pickers and the reword modal dismissed without submitting,
+    /// restored when the same modal reopens this session.
+    pub(crate) input_drafts: DraftStack,
     pub(crate) help: Option<Help>,
     pub(crate) file_finder: Option<FileFinder>,
+    /// Open order of the finder, palette, and help overlays, with the mode each
+    /// covered. See [`crate::overlay`].
+    pub(crate) overlays: crate::overlay::OverlayStack,
     /// Open document-symbol finder modal, or `None`. Fed by
     /// [`action_handlers::lsp::pump_lsp_symbol_picker`] and refiltered on the
     /// render path.
@@ -387,10 +448,18 @@ pub struct Stoat {
     /// the user is being prompted to discard or cancel; cleared on
     /// cancel and stays `Some` on confirm (the app exits anyway).
     pub(crate) quit_all_confirm: Option<QuitAllConfirm>,
+    /// Tool calls owned agents are blocked on, oldest first. The front one
+    /// shows as the permission modal. Each is answered by
+    /// [`stoat_action::defs::agent::AgentPermissionAllow`] and its siblings,
+    /// and the next one surfaces once it is.
+    pub(crate) agent_permissions: VecDeque<AgentPermissionRequest>,
     /// Modal listing the focused editor's jumplist entries; opened by
     /// [`stoat_action::OpenJumplistPicker`] and dismissed on jump or
     /// cancel.
     pub(crate) jumplist_picker: Option<crate::jumplist_picker::JumplistPicker>,
+    /// Modal drawing the focused buffer's undo tree; opened by
+    /// [`stoat_action::OpenUndoTree`] and dismissed on restore or cancel.
+    pub(crate) undo_tree_picker: Option<crate::undo_tree_picker::UndoTreePicker>,
     /// Active diagnostics picker modal (`space l d`). `Some` while
     /// the modal is open; cleared on Esc, on selection (after
     /// jumping the focused editor's cursor), and on Ctrl-C.
@@ -417,6 +486,10 @@ pub struct Stoat {
     /// on submit or cancel.
     pub(crate) filter_selections_input:
         Option<action_handlers::filter_selections::FilterSelectionsInputState>,
+    /// Active input modal for typing the tag a `t` surround add or
+    /// replace wraps with. `Some` while the user composes the tag;
+    /// cleared on submit or cancel.
+    pub(crate) surround_tag_input: Option<action_handlers::surround::SurroundTagInputState>,
     /// Active macro recording. `Some` between two `Q` presses;
     /// every key dispatched in the meantime is appended via
     /// [`action_handlers::macro_recording::capture`].
@@ -432,6 +505,12 @@ pub struct Stoat {
     /// Active input modal for typing a shell command. `Some` while
     /// the user composes the command; cleared on submit or cancel.
     pub(crate) shell_input: Option<action_handlers::shell::ShellInputState>,
+    /// Color picker prompt opened by `EditColorAtCursor` on a color literal.
+    /// `Some` while the user edits the value; cleared on submit or cancel.
+    pub(crate) color_input: Option<crate::color_swatch::ColorInputState>,
+    /// Rename prompt opened by `RenameLocal` on a local binding. `Some`
+    /// while the user types the new name; cleared on submit or cancel.
+    pub(crate) local_rename: Option<crate::local_rename::LocalRenameState>,
     /// Subprocess executor used by the shell-integration actions.
     /// Tests install [`crate::host::FakeShell`].
     pub(crate) shell_host: Arc<dyn crate::host::ShellHost>,
@@ -443,6 +522,10 @@ pub struct Stoat {
     /// no-ops. Set by the test harness so test runs can't read or write the
     /// real `$XDG_STATE_HOME/stoat/workspaces/` directory.
     pub(crate) persistence_disabled: bool,
+    /// Directory of the per-file highlight token cache read and written by
+    /// [`crate::token_cache`], or `None` to leave it off. Defaults to
+    /// `$XDG_STATE_HOME/stoat/tokens/`. The test harness clears it.
+    pub(crate) token_cache_dir: Option<PathBuf>,
     pub(crate) language_registry: Arc<LanguageRegistry>,
     pub(crate) syntax_styles: SyntaxStyles,
     pub(crate) workspaces: SlotMap<WorkspaceId, Workspace>,
@@ -508,6 +591,10 @@ pub struct Stoat {
     /// steps. Only present under the `perf` feature.
     #[cfg(feature = "perf")]
     pub(crate) perf: crate::perf::PerfStats,
+    /// Whether the perf overlay paints over the panes, flipped by
+    /// `TogglePerfOverlay`. Only present under the `perf` feature.
+    #[cfg(feature = "perf")]
+    pub(crate) perf_overlay: bool,
     /// In-flight working-tree review scan. The git2 diff runs on a
     /// blocking thread; [`pump_review_scan`](action_handlers::pump_review_scan)
     /// polls the ready [`ReviewSession`](crate::review_session::ReviewSession)
@@ -534,6 +621,12 @@ pub struct Stoat {
     /// `ToggleMinimap`. `None` follows the `editor.minimap` setting; `Some`
     /// wins for the session. Not persisted.
     pub(crate) minimap_override: Option<bool>,
+    /// Runtime override of the `editor.line_numbers` setting, set by
+    /// `ToggleRelativeNumbers`. `None` follows the setting. Not persisted.
+    pub(crate) line_numbers_override: Option<LineNumbers>,
+    /// Runtime override of the `editor.cursor_line` setting, set by
+    /// `ToggleCursorLine`. `None` follows the setting. Not persisted.
+    pub(crate) cursor_line_override: Option<bool>,
     /// Session-only override of the `ui.tab_bar` setting, set by `:tabs`.
     /// `None` leaves the configured mode in force.
     pub(crate) tab_bar_override: Option<TabBarMode>,
@@ -545,6 +638,11 @@ pub struct Stoat {
     /// paint. `Some` only when a badge painted, `None` when the focused bar shows
     /// no server badge. The badge-hover hit test consumes it.
     pub(crate) lsp_badge_rect: Option<Rect>,
+    /// The focused pane's status-bar breadcrumbs, each painted crumb's cells
+    /// paired with the byte range of the syntax item it names. Stamped every
+    /// paint and empty when `editor.breadcrumbs` is off. A left click on one
+    /// selects that range.
+    pub(crate) breadcrumb_rects: Vec<(Rect, std::ops::Range<usize>)>,
     /// Whether the detailed LSP status popout is pinned open, toggled by
     /// `ToggleLspStatus`. Off by default. A runtime session flag, not persisted.
     pub(crate) lsp_status_pinned: bool,
@@ -626,11 +724,26 @@ pub struct Stoat {
     /// idle screen retires the message without waiting for input.
     /// Replacing it cancels the prior timer.
     pub(crate) pending_message_expiry: Option<stoat_scheduler::Task<()>>,
+    /// Toast notifications stacked in the editor's top-right corner, plus the
+    /// history of every one raised. Fed by [`Self::notify`] and retired by
+    /// [`crate::render::frame`] as their deadlines pass.
+    pub(crate) toasts: crate::toast::Toasts,
     /// Accumulated digit prefix for the next motion (Vim-style
     /// `<count>j` etc.). Filled by `handle_key` when a digit press
     /// hits an unbound key in normal mode; consumed once via
     /// `take_pending_count` and cleared after every action dispatch.
     pub(crate) pending_count: Option<u32>,
+    /// Chords of a sequence binding such as `g g` pressed so far. Set by
+    /// [`Self::handle_key`] when a press starts a longer binding and cleared
+    /// once the sequence completes, breaks or times out.
+    pub(crate) pending_keys: Option<PendingKeys>,
+    /// When the current key prefix began: the last chord of a pending
+    /// sequence, or the switch into the focused mode. The which-key box waits
+    /// `ui.which_key_delay` past it. `None` until the first prefix.
+    which_key_since: Option<std::time::Instant>,
+    /// Wakes the run loop once the which-key delay passes, so the box paints
+    /// without another key. Dropping it cancels the timer.
+    which_key_wake: Option<stoat_scheduler::Task<()>>,
     /// Pending Vim-style find-char prefix (`f`/`F`/`t`/`T`). When
     /// Some, the next printable char keypress runs the matching
     /// find on the focused editor and clears this field. The
@@ -766,6 +879,10 @@ pub struct Stoat {
     /// Index of the diagnostic the mouse last resolved to, used to redraw only
     /// when the hovered diagnostic changes rather than on every motion event.
     pub(crate) hover_diag: Option<usize>,
+    /// Gutter zone and pane-relative row the mouse last rested over in the
+    /// focused editor, used to redraw the gutter's hover affordance only when
+    /// the pointer moves onto another gutter cell.
+    pub(crate) gutter_hover: Option<(GutterZone, u16)>,
     /// Set on `MouseEventKind::Down(Left)` over a split divider. While `Some`,
     /// `Drag(Left)` moves that boundary via `set_divider` and `Up(Left)` clears
     /// it. Takes over the pointer so pane handlers never see the drag.
@@ -775,6 +892,9 @@ pub struct Stoat {
     /// position and `Up(Left)` clears it. Takes over the pointer so the press
     /// never reaches the text-area cursor or selection handling.
     pub(crate) minimap_drag: Option<EditorId>,
+    /// Viewport row the pointer holds during a [`Self::minimap_drag`]: where
+    /// the press landed inside the visible region, or its middle after a jump.
+    pub(crate) minimap_grab: u32,
     /// Buffers for which `LspHost::did_open` has been dispatched.
     /// Dedupes re-opens of the same path: [`crate::buffer_registry::BufferRegistry::open`]
     /// returns the existing entry on second open, but the LSP
@@ -827,6 +947,22 @@ pub struct Stoat {
     /// [`Self::drain_lsp_notifications`]; surfaced by the status bar
     /// for the focused buffer.
     pub(crate) diagnostics: crate::diagnostics::DiagnosticSet,
+    /// Loaded word lists and per-path check history for the comment and
+    /// string spell checker. Refreshed by [`crate::spell::refresh`] in
+    /// [`Self::drive_background`], which publishes into
+    /// [`Self::diagnostics`].
+    pub(crate) spell: crate::spell::SpellState,
+    /// The running task, the last finished run's problems, and the jump cursor
+    /// over them. Installed by [`crate::task::pump`] in
+    /// [`Self::drive_background`].
+    pub(crate) tasks: crate::task::TaskState,
+    /// Per-test results and in-flight runs behind the `test.lenses` code
+    /// lenses. Installed by [`crate::test_lens::pump`] in
+    /// [`Self::drive_background`].
+    pub(crate) test_lenses: crate::test_lens::TestLensState,
+    /// Lens providers and the lenses installed per editor. Rebuilt by
+    /// [`crate::code_lens::refresh`] in [`Self::drive_background`].
+    pub(crate) code_lenses: crate::code_lens::CodeLensState,
     /// Most recent `(FindKind, char)` consumed by `execute_find`.
     /// `RepeatLastMotion` (Alt-.) replays this pair without
     /// reading another keypress.
@@ -1177,6 +1313,13 @@ pub struct Stoat {
     /// [`Self::set_apc_tx`] installs it, which startup does after construction
     /// and a test need not do at all.
     pub(crate) apc_tx: Option<UnboundedSender<Vec<u8>>>,
+    /// The `editor.cursor_animation` last sent to stoatty, so
+    /// [`Self::emit_cursor_animation`] sends it once per change rather than
+    /// every frame.
+    pub(crate) cursor_animation_sent: Option<stoat_config::CursorAnimation>,
+    /// The accessibility state last sent to stoatty, so
+    /// [`Self::emit_accessibility`] announces only changes.
+    pub(crate) accessibility_sent: Option<stoatty_protocol::command::AccessibilityCommand>,
     /// Reused per-frame APC decoration buffer. Widgets append their component
     /// frames while painting; [`Self::emit_apc_scene`] diffs it against the last
     /// flush so unchanged decoration costs no bytes. Empty until a widget appends.
@@ -1229,6 +1372,8 @@ pub(crate) struct ParseJobOutput {
     /// while consumers migrate.
     pub(crate) syntax_map: stoat_language::SyntaxMap,
     pub(crate) tokens: Arc<[SemanticTokenHighlight]>,
+    /// Identifier index for word completion, built from the same tree.
+    pub(crate) words: Arc<crate::completion::word::BufferWords>,
 }
 
 impl Stoat {
@@ -1295,10 +1440,15 @@ impl Stoat {
         user_themes: Vec<(String, String)>,
         env_theme: Option<String>,
     ) -> Self {
+        let mut user_config_source = None;
         let (config, theme_base, mut config_error) = match user_config {
             Some(source) => {
                 let (parsed, errors) = stoat_config::parse(&source);
                 if errors.is_empty() {
+                    if let Some(config) = &parsed {
+                        Self::warn_config_problems(&source, config);
+                    }
+                    user_config_source = Some(source);
                     (parsed, Self::parse_default_keymap(), None)
                 } else {
                     tracing::error!(
@@ -1358,7 +1508,7 @@ impl Stoat {
             theme_base,
             &imported_theme_blocks,
             cli_settings.clone(),
-            env_theme,
+            env_theme.clone(),
         );
 
         let highlight_retention = settings
@@ -1404,26 +1554,37 @@ impl Stoat {
             theme: Arc::new(theme),
             theme_blocks,
             imported_theme_blocks,
+            user_config_source,
+            env_theme,
+            project_config: crate::project_config::ProjectConfig::default(),
             command_palette: None,
+            input_drafts: DraftStack::default(),
             help: None,
             file_finder: None,
+            overlays: crate::overlay::OverlayStack::default(),
             symbol_finder: None,
             workspace_picker: None,
             quit_all_confirm: None,
+            agent_permissions: VecDeque::new(),
             jumplist_picker: None,
+            undo_tree_picker: None,
             diagnostics_picker: None,
             location_picker: None,
             last_picker_action: None,
             code_search: None,
             split_selection_input: None,
+            surround_tag_input: None,
             filter_selections_input: None,
             macro_recording: None,
             macros: std::collections::HashMap::new(),
             pending_macro_replay: false,
             shell_input: None,
+            color_input: None,
+            local_rename: None,
             shell_host: Arc::new(crate::host::LocalShell),
             terminal_host: Arc::new(crate::host::LocalTerminalHost),
             persistence_disabled: false,
+            token_cache_dir: stoat_log::state_dir().ok().map(|dir| dir.join("tokens")),
             language_registry,
             syntax_styles,
             workspaces,
@@ -1447,6 +1608,8 @@ impl Stoat {
             shutdown_notify: Arc::new(tokio::sync::Notify::new()),
             #[cfg(feature = "perf")]
             perf: crate::perf::PerfStats::default(),
+            #[cfg(feature = "perf")]
+            perf_overlay: false,
             pending_review_scan: None,
             pending_code_search: None,
             code_search_debounce: None,
@@ -1456,9 +1619,12 @@ impl Stoat {
             modal_run: None,
             syntax_highlight: true,
             minimap_override: None,
+            line_numbers_override: None,
+            cursor_line_override: None,
             tab_bar_override: None,
             single_minimap_rect: None,
             lsp_badge_rect: None,
+            breadcrumb_rects: Vec::new(),
             lsp_status_pinned: false,
             lsp_badge_hovered: false,
             key_hints_visible: false,
@@ -1480,7 +1646,11 @@ impl Stoat {
             pending_message: None,
             pending_message_deadline: None,
             pending_message_expiry: None,
+            toasts: crate::toast::Toasts::default(),
             pending_count: None,
+            pending_keys: None,
+            which_key_since: None,
+            which_key_wake: None,
             pending_find: None,
             pending_mark: None,
             marks: std::collections::HashMap::new(),
@@ -1506,8 +1676,10 @@ impl Stoat {
             terminal_drag: None,
             hover_cell: None,
             hover_diag: None,
+            gutter_hover: None,
             divider_drag: None,
             minimap_drag: None,
+            minimap_grab: 0,
             lsp_opened: std::collections::HashSet::new(),
             lsp_buffer_versions: std::collections::HashMap::new(),
             lsp_pending_changes: std::collections::HashMap::new(),
@@ -1521,6 +1693,10 @@ impl Stoat {
                 std::collections::HashMap::new(),
             )),
             diagnostics: crate::diagnostics::DiagnosticSet::new(),
+            spell: crate::spell::SpellState::default(),
+            tasks: crate::task::TaskState::default(),
+            test_lenses: crate::test_lens::TestLensState::default(),
+            code_lenses: crate::code_lens::CodeLensState::default(),
             last_find: None,
             fs_host: Arc::new(LocalFs),
             fs_watch_host: Arc::new(NoopFsWatcher::new()),
@@ -1588,6 +1764,8 @@ impl Stoat {
             version_info: "unknown",
             next_aux_window: 1,
             apc_tx: None,
+            cursor_animation_sent: None,
+            accessibility_sent: None,
             apc_scene: ApcScene::new(),
             pending_undercurls: Vec::new(),
             smooth_scroll: crate::smooth_scroll::SmoothScrollState::default(),
@@ -1618,6 +1796,19 @@ impl Stoat {
         config
     }
 
+    /// Log what [`stoat_config::check`] finds wrong in a user config that
+    /// parsed. Those problems do not stop it loading, so they are only
+    /// warned about.
+    fn warn_config_problems(source: &str, config: &stoat_config::Config) {
+        let problems = stoat_config::check(config);
+        if !problems.is_empty() {
+            tracing::warn!(
+                "user config has problems: {}",
+                stoat_config::format_errors(source, &problems)
+            );
+        }
+    }
+
     /// Re-resolve the user config from `source` and swap the running keymap,
     /// settings, theme, and theme-derived tables.
     ///
@@ -1641,6 +1832,24 @@ impl Stoat {
             self.set_status("config parse failed; keeping the current config");
             return;
         }
+        if let Some(config) = &config {
+            Self::warn_config_problems(source, config);
+        }
+
+        self.user_config_source = Some(source.to_string());
+        self.apply_config_layers();
+        self.set_status("config reloaded");
+    }
+
+    /// Rebuild the keymap, settings, theme, and theme-derived tables from the
+    /// user config (or the embedded default) with the project layer over it,
+    /// then the CLI overrides over both.
+    pub(crate) fn apply_config_layers(&mut self) {
+        let (base, theme_base) = match &self.user_config_source {
+            Some(source) => (stoat_config::parse(source).0, Self::parse_default_keymap()),
+            None => (Self::parse_default_keymap(), None),
+        };
+        let config = self.project_config.layer_over(base);
 
         let ConfigArtifacts {
             keymap,
@@ -1651,10 +1860,10 @@ impl Stoat {
             minimap_class_table,
         } = build_config_artifacts(
             config,
-            Self::parse_default_keymap(),
+            theme_base,
             &self.imported_theme_blocks,
             self.cli_settings.clone(),
-            None,
+            self.env_theme.clone(),
         );
 
         self.keymap = keymap;
@@ -1666,7 +1875,6 @@ impl Stoat {
 
         install_highlight_maps(&self.language_registry, &self.syntax_styles);
         self.minimap_content.clear();
-        self.set_status("config reloaded");
     }
 
     /// Look up a previously-cached diff by content hashes plus
@@ -1881,6 +2089,38 @@ impl Stoat {
         self.minimap_override = Some(!self.minimap_enabled());
     }
 
+    /// How editor gutters number lines: the session override when set, else
+    /// the `editor.line_numbers` setting, defaulting to
+    /// [`LineNumbers::Relative`].
+    pub(crate) fn line_numbers(&self) -> LineNumbers {
+        self.line_numbers_override
+            .or(self.settings.editor_line_numbers)
+            .unwrap_or(LineNumbers::Relative)
+    }
+
+    /// Switch the gutter between relative and absolute numbering for the
+    /// session. A hidden gutter comes back relative.
+    pub(crate) fn toggle_relative_numbers(&mut self) {
+        self.line_numbers_override = Some(match self.line_numbers() {
+            LineNumbers::Relative => LineNumbers::Absolute,
+            LineNumbers::Absolute | LineNumbers::Off => LineNumbers::Relative,
+        });
+    }
+
+    /// Whether the focused editor highlights its cursor line: the session
+    /// override when set, else the `editor.cursor_line` setting, defaulting to
+    /// off.
+    pub(crate) fn cursor_line_enabled(&self) -> bool {
+        self.cursor_line_override
+            .or(self.settings.editor_cursor_line)
+            .unwrap_or(false)
+    }
+
+    /// Flip the cursor-line highlight for the session, overriding the setting.
+    pub(crate) fn toggle_cursor_line(&mut self) {
+        self.cursor_line_override = Some(!self.cursor_line_enabled());
+    }
+
     /// Flip the focused editor's soft-wrap override.
     ///
     /// A first toggle overrides the configured `editor.wrap` mode with its
@@ -2176,6 +2416,24 @@ impl Stoat {
         action_handlers::dispatch(self, &action);
     }
 
+    /// Open `path` like [`Self::open_file`], then place the cursor on 1-based
+    /// `line` and `column` with the view scrolled to it. Backs the
+    /// `stoat file:line:col` and `stoat +line file` startup forms.
+    pub fn open_file_at(&mut self, path: &Path, line: u32, column: u32) {
+        self.open_file(path);
+        action_handlers::movement::goto_line_column(self, line, column);
+    }
+
+    /// Open `path` and show it side by side against `base`, as `:diff-with`
+    /// does, for the `stoat diff <a> <b>` entry point.
+    pub fn open_file_diff(&mut self, base: &Path, path: &Path) {
+        self.open_file(path);
+        let action = DiffWithBuffer {
+            path: base.to_path_buf(),
+        };
+        action_handlers::dispatch(self, &action);
+    }
+
     /// Toggle the side-by-side diff view on the focused editor, as the `:diff`
     /// command does.
     pub fn toggle_diff_view(&mut self) {
@@ -2304,6 +2562,8 @@ impl Stoat {
 
             match effect {
                 UpdateEffect::Redraw => {
+                    #[cfg(feature = "perf")]
+                    let framed = std::time::Instant::now();
                     self.drive_background();
                     // A `:wq` deferred behind a format-on-save write sets
                     // `quit_requested` from the pump inside `drive_background`
@@ -2333,6 +2593,7 @@ impl Stoat {
                     recycled = render.send_replace(Some(RenderFrame {
                         buffer,
                         cursor,
+                        cursor_style: self.cursor_style(),
                         undercurl,
                         #[cfg(feature = "perf")]
                         input_time: t_event,
@@ -2341,7 +2602,11 @@ impl Stoat {
                     if let Some(started) = t_event {
                         self.perf.record_input_to_publish(started.elapsed());
                     }
+                    #[cfg(feature = "perf")]
+                    self.perf.record_frame(framed.elapsed());
                     self.emit_apc_scene();
+                    self.emit_cursor_animation();
+                    self.emit_accessibility();
                     self.emit_windows();
                     self.emit_smooth_scroll();
                     self.emit_minimap();
@@ -2371,7 +2636,10 @@ impl Stoat {
     fn log_perf_table(&self) {
         let metrics = [
             ("update", self.perf.update_stats()),
+            ("frame", self.perf.frame_stats()),
+            ("layout", self.perf.layout_stats()),
             ("paint", self.perf.paint_stats()),
+            ("lines", self.perf.lines_stats()),
             ("input_to_publish", self.perf.input_to_publish_stats()),
             ("coalesced", self.perf.coalesced_stats()),
             ("anim_tick", self.perf.anim_tick_stats()),
@@ -2595,6 +2863,10 @@ impl Stoat {
             return;
         }
         let warm = self.warm_index_load(&git_root);
+        if self.active_workspace().worktree_files.is_none() {
+            let files = self.warm_file_list_load(&git_root);
+            self.active_workspace_mut().worktree_files = files;
+        }
         tracing::info!(
             target: "stoat::app",
             root = %git_root.display(),
@@ -2650,6 +2922,17 @@ impl Stoat {
         (manifest.schema_version == codegraph::SCHEMA_VERSION).then_some((dir, manifest))
     }
 
+    /// Read the persisted worktree file list, so the file finder lists the
+    /// workspace before the build's walk finishes. `None` when persistence is
+    /// disabled or no list was written yet.
+    fn warm_file_list_load(&self, git_root: &Path) -> Option<BTreeSet<String>> {
+        if self.persistence_disabled {
+            return None;
+        }
+        let dir = crate::code_index::store::index_dir_for(git_root, self.fs_host.as_ref()).ok()?;
+        crate::code_index::store::read_file_list(&dir, self.fs_host.as_ref()).ok()
+    }
+
     /// Merge pending index updates into their workspace graphs.
     ///
     /// Each shard is inserted and, in non-test runs, written to disk. Reindex
@@ -2705,6 +2988,7 @@ impl Stoat {
                 IndexUpdate::Complete {
                     workspace,
                     manifest,
+                    files,
                 } => {
                     resolve_pending.insert(workspace);
                     completed.insert(workspace);
@@ -2721,6 +3005,11 @@ impl Stoat {
                                 &manifest,
                                 self.fs_host.as_ref(),
                             );
+                            let _ = crate::code_index::store::write_file_list(
+                                &dir,
+                                &files,
+                                self.fs_host.as_ref(),
+                            );
                             if let Ok(pruned) = crate::code_index::store::prune_shards(
                                 &dir,
                                 &manifest,
@@ -2735,6 +3024,9 @@ impl Stoat {
                             }
                         }
                     }
+                    if let Some(ws) = self.workspaces.get_mut(workspace) {
+                        ws.worktree_files = Some(files);
+                    }
                 },
                 IndexUpdate::Reindex {
                     workspace,
@@ -3018,13 +3310,15 @@ impl Stoat {
         if ws.is_fresh() {
             return;
         }
-        let path = match crate::workspace::state_path_for(&ws.git_root, ws.uid, &*self.fs_host) {
-            Ok(p) => p,
-            Err(err) => {
-                tracing::warn!(?err, "could not resolve workspace state path");
-                return;
-            },
-        };
+        let format = self.settings.state_format.unwrap_or(StateFormat::Ron);
+        let path =
+            match crate::workspace::state_path_for(&ws.git_root, ws.uid, format, &*self.fs_host) {
+                Ok(p) => p,
+                Err(err) => {
+                    tracing::warn!(?err, "could not resolve workspace state path");
+                    return;
+                },
+            };
         if let Err(err) = ws.save_state(&path, &*self.fs_host) {
             tracing::warn!(?path, ?err, "failed to save workspace state");
         }
@@ -3126,7 +3420,7 @@ impl Stoat {
     /// pathological burst can't starve the event loop.
     pub(crate) fn drain_fs_watch_events(&mut self) {
         let host = self.fs_watch_host.clone();
-        let mut paths: Vec<PathBuf> = Vec::new();
+        let mut events: Vec<crate::host::FsWatchEvent> = Vec::new();
         for _ in 0..256 {
             let Some(event) = host.try_recv() else {
                 break;
@@ -3137,10 +3431,10 @@ impl Stoat {
                 kind = ?event.kind,
                 "fs watch event observed",
             );
-            paths.push(event.path);
+            events.push(event);
         }
 
-        if paths.is_empty() {
+        if events.is_empty() {
             return;
         }
         // `review.follow` gates every automatic refresh below. A manual `r`
@@ -3155,7 +3449,14 @@ impl Stoat {
         let git_root = self.active_workspace().git_root.clone();
         let git_dir = git_root.join(".git");
         let mut repo: Option<Option<Arc<dyn GitRepo>>> = None;
-        for path in paths {
+        let mut files_changed = false;
+        for crate::host::FsWatchEvent { path, kind } in events {
+            if kind != crate::host::FsEventKind::Modified
+                && !path.starts_with(&git_dir)
+                && path.starts_with(&git_root)
+            {
+                files_changed |= self.sync_worktree_file(&path, &mut repo);
+            }
             if path.starts_with(&git_dir) {
                 // A .git write (a commit, reset, rebase step, or branch switch)
                 // moved HEAD and staled every diff base, so it refreshes through
@@ -3194,6 +3495,56 @@ impl Stoat {
                 self.arm_index_external_edit_debounce(path);
             }
         }
+        if files_changed
+            && !self.persistence_disabled
+            && let Some(files) = &self.active_workspace().worktree_files
+            && let Ok(dir) =
+                crate::code_index::store::index_dir_for(&git_root, self.fs_host.as_ref())
+        {
+            let _ = crate::code_index::store::write_file_list(&dir, files, self.fs_host.as_ref());
+        }
+    }
+
+    /// Fold a created, removed, or renamed path into the active workspace's
+    /// [`Workspace::worktree_files`], returning whether the list changed.
+    ///
+    /// The path's current state on disk decides the outcome, since a rename
+    /// arrives as one event per side. A file still present joins the list
+    /// unless gitignored. A path that is gone leaves it, along with every file
+    /// under it in case it was a directory. A no-op until the list exists.
+    fn sync_worktree_file(
+        &mut self,
+        path: &Path,
+        repo: &mut Option<Option<Arc<dyn GitRepo>>>,
+    ) -> bool {
+        let git_root = self.active_workspace().git_root.clone();
+        let Some(rel_path) = crate::code_index::build::relpath(&git_root, path) else {
+            return false;
+        };
+        if rel_path.is_empty() || self.active_workspace().worktree_files.is_none() {
+            return false;
+        }
+        let present = match self.fs_host.metadata(path) {
+            Ok(Some(meta)) if meta.is_dir => return false,
+            Ok(Some(_)) => {
+                let repo = repo.get_or_insert_with(|| self.git_host.discover(&git_root));
+                if repo.as_ref().is_some_and(|r| r.is_path_ignored(path)) {
+                    return false;
+                }
+                true
+            },
+            _ => false,
+        };
+        let Some(files) = self.active_workspace_mut().worktree_files.as_mut() else {
+            return false;
+        };
+        if present {
+            return files.insert(rel_path);
+        }
+        let before = files.len();
+        let dir_prefix = format!("{rel_path}/");
+        files.retain(|file| *file != rel_path && !file.starts_with(&dir_prefix));
+        files.len() != before
     }
 
     /// Spawn `future` on the executor and wake the run loop once it
@@ -3228,6 +3579,44 @@ impl Stoat {
         }));
     }
 
+    /// Raise a toast of `level` showing `message` for the level's
+    /// [`ToastLevel::ttl`].
+    ///
+    /// Like [`Self::set_status`], arms a timer that wakes the run loop at the
+    /// deadline so an idle screen retires the toast on its own.
+    pub(crate) fn notify(&mut self, level: ToastLevel, message: impl Into<String>) {
+        self.show_toast(Toast {
+            level,
+            message: message.into(),
+            action: None,
+        });
+    }
+
+    /// Raise a toast like [`Self::notify`] that also offers `action`, run by
+    /// `RunToastAction` while the toast is up.
+    pub(crate) fn notify_with_action(
+        &mut self,
+        level: ToastLevel,
+        message: impl Into<String>,
+        action: ToastAction,
+    ) {
+        self.show_toast(Toast {
+            level,
+            message: message.into(),
+            action: Some(action),
+        });
+    }
+
+    pub(crate) fn show_toast(&mut self, toast: Toast) {
+        let ttl = toast.level.ttl();
+        let timer = self.executor.timer(ttl);
+        let expiry = self.spawn_woken(async move {
+            timer.await;
+        });
+        let deadline = self.executor.now() + ttl;
+        self.toasts.push(toast, deadline, expiry);
+    }
+
     /// Schedule a debounced [`ReviewExternalEdit`] dispatch for
     /// `path`. Inserting into [`Self::review_pending_external_edits`]
     /// drops any prior task for the same path, which cancels the
@@ -3688,8 +4077,8 @@ impl Stoat {
     /// [`action_handlers::lsp::notify_buffer_opened`] park ready
     /// [`crate::host::LocalLsp`] hosts in [`Self::pending_lsp_host`]. This
     /// drains the queue. Each ready host is registered via
-    /// [`Self::install_ready_server`], and a failed spawn surfaces in the
-    /// message row while its language keeps the [`NoopLsp`] placeholder.
+    /// [`Self::install_ready_server`], and a failed spawn raises an error toast
+    /// while its language keeps the [`NoopLsp`] placeholder.
     fn install_pending_lsp_host(&mut self) {
         let pending = std::mem::take(
             &mut *self
@@ -3702,11 +4091,15 @@ impl Stoat {
                 Ok(host) => self.install_ready_server(spawn.server, spawn.language, host),
                 Err(msg) => {
                     // The server never came up, so its language keeps the noop
-                    // placeholder and the failure surfaces in the status bar
-                    // rather than only the log. Retained so a later LSP action
-                    // can restate why no server is up.
+                    // placeholder and the failure surfaces as an error toast
+                    // offering the log, rather than only in the log. Retained so
+                    // a later LSP action can restate why no server is up.
                     self.lsp_spawn_failed = Some(msg.clone());
-                    self.set_status(format!("lsp: {msg}"));
+                    self.notify_with_action(
+                        ToastLevel::Error,
+                        format!("lsp: {msg}"),
+                        ToastAction::new("logs", "OpenLogs"),
+                    );
                 },
             }
         }
@@ -3922,6 +4315,17 @@ impl Stoat {
             return effect;
         }
 
+        // A left click on a status-bar breadcrumb selects its syntax item in
+        // the focused editor the crumbs were built from.
+        if let MouseEventKind::Down(MouseButton::Left) = mouse.kind
+            && let Some((_, range)) = self
+                .breadcrumb_rects
+                .iter()
+                .find(|(rect, _)| rect.contains(Position::new(mouse.column, mouse.row)))
+        {
+            return self.select_breadcrumb(range.clone());
+        }
+
         // Every button focuses the pane under the pointer, not just the left.
         // The translation below is relative to the focused pane, so a middle or
         // right click elsewhere would otherwise land in the wrong buffer.
@@ -3975,19 +4379,48 @@ impl Stoat {
         UpdateEffect::None
     }
 
+    /// Select `range`, a clicked breadcrumb's item, in the focused editor.
+    fn select_breadcrumb(&mut self, range: std::ops::Range<usize>) -> UpdateEffect {
+        let ws = self.active_workspace_mut();
+        let View::Editor(editor_id) = ws.panes.pane(ws.panes.focus()).view else {
+            return UpdateEffect::None;
+        };
+        let Some(editor) = ws.editors.get_mut(editor_id) else {
+            return UpdateEffect::None;
+        };
+        let display = editor.display_map.snapshot();
+        let buffer = display.buffer_snapshot();
+        let end = range.end.min(buffer.rope().len());
+        let start = buffer.anchor_at(range.start.min(end), Bias::Right);
+        let end = buffer.anchor_at(end, Bias::Left);
+        editor.selections.transform(buffer, |sel| {
+            let mut new = sel.clone();
+            new.start = start;
+            new.end = end;
+            new.reversed = false;
+            new.goal = stoat_text::SelectionGoal::None;
+            new
+        });
+        UpdateEffect::Redraw
+    }
+
     /// Route a left press or drag over a pane's minimap strip to a viewport
     /// scrub. Returns `Some` when the event is consumed, `None` when it should
     /// fall through to focus and the text-area handlers.
     ///
-    /// Once a press arms [`Self::minimap_drag`], every drag re-scrubs the named
-    /// editor and the release clears the field, so the strip owns the pointer for
-    /// the whole gesture and the text area never sees it.
+    /// A press on the strip outside the visible region jumps the viewport to
+    /// center the pointed-at line. A press inside the region grabs it where it
+    /// was pressed and leaves the viewport still. Once a press arms
+    /// [`Self::minimap_drag`], every drag scrolls the named editor so the
+    /// grabbed row follows the pointer, and the release clears the field, so the
+    /// strip owns the pointer for the whole gesture and the text area never sees
+    /// it.
     fn handle_minimap_mouse(&mut self, mouse: MouseEvent) -> Option<UpdateEffect> {
         if let Some(editor_id) = self.minimap_drag {
             match mouse.kind {
                 MouseEventKind::Drag(MouseButton::Left) => {
                     if let Some(strip) = self.minimap_strip_for(editor_id) {
-                        self.scrub_minimap_editor(editor_id, strip, mouse.row);
+                        self.scrub_minimap_editor(editor_id, strip, mouse.row, ScrollGlide::Wheel);
                     }
                 },
                 MouseEventKind::Up(MouseButton::Left) => self.minimap_drag = None,
@@ -4018,7 +4451,17 @@ impl Stoat {
                 && let Some(strip) = self.minimap_strip_for(editor_id)
             {
                 self.minimap_drag = Some(editor_id);
-                self.scrub_minimap_editor(editor_id, strip, mouse.row);
+                match self.minimap_region_grab(editor_id, strip, mouse.row) {
+                    Some(grab) => self.minimap_grab = grab,
+                    None => {
+                        self.minimap_grab = self.active_workspace().editors[editor_id]
+                            .viewport_rows
+                            .unwrap_or(strip.height as u32)
+                            .max(1)
+                            / 2;
+                        self.scrub_minimap_editor(editor_id, strip, mouse.row, ScrollGlide::Page);
+                    },
+                }
                 return Some(UpdateEffect::Redraw);
             }
         }
@@ -4037,19 +4480,20 @@ impl Stoat {
         }
     }
 
-    /// Ease `editor_id`'s viewport onto the file line the `strip` row under
-    /// `screen_row` points at, centered in the viewport.
+    /// The file line the `strip` row under `screen_row` points at for
+    /// `editor_id`, with the editor's viewport height and display line count.
     ///
     /// Maps the strip-local cell row to a line with the same proportional math
-    /// the strip renders with, then jumps `scroll_row` and glides the offset up
-    /// to it like a page motion. `strip` is the caller-resolved band the editor
+    /// the strip renders with. `strip` is the caller-resolved band the editor
     /// scrubs against (a per-pane rect or the shared single-mode band).
-    fn scrub_minimap_editor(&mut self, editor_id: EditorId, strip: Rect, screen_row: u16) {
+    fn minimap_target(
+        &mut self,
+        editor_id: EditorId,
+        strip: Rect,
+        screen_row: u16,
+    ) -> Option<(u32, u32, u32)> {
         let ws = &mut self.workspaces[self.active_workspace];
-        let Some(editor) = ws.editors.get_mut(editor_id) else {
-            return;
-        };
-
+        let editor = ws.editors.get_mut(editor_id)?;
         let total = editor.display_map.snapshot().line_count();
         let viewport = editor.viewport_rows.unwrap_or(strip.height as u32).max(1);
         let strip_local_row = screen_row.saturating_sub(strip.y);
@@ -4060,18 +4504,59 @@ impl Stoat {
             editor.scroll_offset,
             viewport as f32,
         );
+        Some((target_line, viewport, total))
+    }
+
+    /// The viewport row a press at `screen_row` grabs when it lands inside
+    /// `editor_id`'s visible region on the strip, or `None` outside it.
+    fn minimap_region_grab(
+        &mut self,
+        editor_id: EditorId,
+        strip: Rect,
+        screen_row: u16,
+    ) -> Option<u32> {
+        let (target_line, viewport, _) = self.minimap_target(editor_id, strip, screen_row)?;
+        let top = self.active_workspace().editors.get(editor_id)?.scroll_row;
+        (top..top + viewport)
+            .contains(&target_line)
+            .then(|| target_line - top)
+    }
+
+    /// Scroll `editor_id`'s viewport so the row [`Self::minimap_grab`] lands on
+    /// the file line the `strip` row under `screen_row` points at.
+    ///
+    /// Jumps `scroll_row` and lets `glide` ease the offset up to it: a page
+    /// glide for a jump, a wheel glide for drag reports, so a stream of them
+    /// overlaps into continuous motion.
+    fn scrub_minimap_editor(
+        &mut self,
+        editor_id: EditorId,
+        strip: Rect,
+        screen_row: u16,
+        glide: ScrollGlide,
+    ) {
+        let Some((target_line, viewport, total)) =
+            self.minimap_target(editor_id, strip, screen_row)
+        else {
+            return;
+        };
+        let grab = self.minimap_grab;
+        let ws = &mut self.workspaces[self.active_workspace];
+        let Some(editor) = ws.editors.get_mut(editor_id) else {
+            return;
+        };
 
         let max_scroll = total
             .saturating_sub(1)
             .saturating_sub(viewport.saturating_sub(1));
-        let target_row = target_line.saturating_sub(viewport / 2).min(max_scroll);
+        let target_row = target_line.saturating_sub(grab).min(max_scroll);
 
         let prev = editor.scroll_row;
         editor.scroll_row = target_row;
         if editor.scroll_offset.floor() as u32 != prev {
             editor.scroll_offset = prev as f32;
         }
-        editor.scroll_glide = ScrollGlide::Page;
+        editor.scroll_glide = glide;
     }
 
     /// Route a left-button press over the open hover popup to its text
@@ -4170,7 +4655,8 @@ impl Stoat {
     /// A `View::Editor` split pane gets inertial velocity, so a notch starts
     /// or accelerates a momentum glide. A `View::Run` pane (split or dock) does
     /// plain stepped scrolling of its output, three rows per notch, clamped to
-    /// the top. Anything else drops the event.
+    /// the top, and a `View::Hex` pane steps its dump the same way. Anything
+    /// else drops the event.
     fn handle_mouse_scroll(&mut self, mouse: MouseEvent) -> UpdateEffect {
         // The location picker is modal, so it owns the wheel wherever the
         // pointer sits and browses its candidates with it.
@@ -4298,6 +4784,17 @@ impl Stoat {
                 };
                 UpdateEffect::Redraw
             },
+            View::Hex(id) => {
+                let Some(hex) = ws.hex_views.get_mut(id) else {
+                    return UpdateEffect::None;
+                };
+                let page = crate::render::layout::split_pane_status(area).0.height as usize;
+                if hex.scroll_by(if down { 3 } else { -3 }, page) {
+                    UpdateEffect::Redraw
+                } else {
+                    UpdateEffect::None
+                }
+            },
             _ => UpdateEffect::None,
         }
     }
@@ -4464,6 +4961,9 @@ impl Stoat {
     /// changes, so mouse motion within one span does not repaint every event.
     fn handle_hover(&mut self, column: u16, row: u16) -> UpdateEffect {
         self.hover_cell = Some((column, row));
+        let gutter = self.gutter_hover_at(column, row);
+        let gutter_changed = self.gutter_hover != gutter;
+        self.gutter_hover = gutter;
         let resolved = self.resolve_hover_diagnostic(column, row);
         let badge_hovered = self.lsp_badge_rect.is_some_and(|rect| {
             column >= rect.x
@@ -4471,7 +4971,8 @@ impl Stoat {
                 && row >= rect.y
                 && row < rect.y + rect.height
         });
-        if self.hover_diag == resolved && self.lsp_badge_hovered == badge_hovered {
+        if self.hover_diag == resolved && self.lsp_badge_hovered == badge_hovered && !gutter_changed
+        {
             return UpdateEffect::None;
         }
         self.hover_diag = resolved;
@@ -4479,6 +4980,72 @@ impl Stoat {
         UpdateEffect::Redraw
     }
 
+    /// The gutter zone and pane-relative row under terminal cell
+    /// `(column, row)` in the focused editor, or `None` off its gutter.
+    fn gutter_hover_at(&self, column: u16, row: u16) -> Option<(GutterZone, u16)> {
+        let (editor_id, area) = self.focused_editor_target()?;
+        if !area.contains(Position::new(column, row)) {
+            return None;
+        }
+        let editor = self.active_workspace().editors.get(editor_id)?;
+        let zone = crate::render::editor::gutter_zone(editor.gutter_width, column - area.x)?;
+        Some((zone, row - area.y))
+    }
+
+    /// Act on a left click in the focused editor's line-number gutter,
+    /// returning `true` when the click landed there. The number column selects
+    /// the clicked line, and the fold column folds or unfolds it. The diff,
+    /// conflict, and review views lay their gutters out differently and are
+    /// left to the text-area handling.
+    fn handle_gutter_click(&mut self, editor_id: EditorId, area: Rect, col: u16, row: u16) -> bool {
+        if row >= area.height {
+            return false;
+        }
+        let ws = self.active_workspace_mut();
+        let Some(editor) = ws.editors.get_mut(editor_id) else {
+            return false;
+        };
+        if editor.diff_view || editor.conflict_view.is_some() || editor.review_view.is_some() {
+            return false;
+        }
+        let Some(zone) = crate::render::editor::gutter_zone(editor.gutter_width, col) else {
+            return false;
+        };
+        let snapshot = editor.display_map.snapshot();
+        let display_row = editor.scroll_row + row as u32;
+        if display_row >= snapshot.line_count() {
+            return false;
+        }
+        let Some(line) = snapshot
+            .display_to_buffer(crate::display_map::DisplayPoint::new(display_row, 0))
+            .map(|point| point.row)
+        else {
+            return false;
+        };
+
+        match zone {
+            GutterZone::Number => {
+                let buffer = snapshot.buffer_snapshot();
+                let rope = buffer.rope();
+                let start = rope.point_to_offset(stoat_text::Point::new(line, 0));
+                let end = if line >= rope.max_point().row {
+                    rope.len()
+                } else {
+                    rope.point_to_offset(stoat_text::Point::new(line + 1, 0))
+                };
+                editor.selections.set_single_range(
+                    buffer.anchor_at(start, Bias::Left),
+                    buffer.anchor_at(end, Bias::Right),
+                    stoat_text::SelectionGoal::None,
+                );
+            },
+            GutterZone::Fold => {
+                action_handlers::fold::toggle_fold_at_line(self, line);
+            },
+        }
+        true
+    }
+
     /// Collapse the focused editor's selection to a block cursor on the cell at
     /// `col`/`row`, returning the buffer it landed in.
     ///
@@ -4511,6 +5078,12 @@ impl Stoat {
 
         match kind {
             MouseEventKind::Down(MouseButton::Left) => {
+                if self.handle_gutter_click(editor_id, area, col, row) {
+                    return true;
+                }
+                if crate::code_lens::click(self, editor_id, area, col, row) {
+                    return true;
+                }
                 let Some(buffer_id) = self.place_cursor_at_click(editor_id, area, col, row) else {
                     return false;
                 };
@@ -4850,21 +5423,11 @@ impl Stoat {
                 }
                 return UpdateEffect::Redraw;
             }
-            if self.help.is_some() {
-                action_handlers::close_help(self);
-                return UpdateEffect::Redraw;
-            }
-            if self.file_finder.is_some() {
-                action_handlers::close_file_finder(self);
+            if crate::overlay::dismiss_top(self) {
                 return UpdateEffect::Redraw;
             }
             if self.symbol_finder.is_some() {
-                action_handlers::lsp::close_symbol_finder(self);
-                return UpdateEffect::Redraw;
-            }
-            if let Some(palette) = self.command_palette.take() {
-                let active_idx = self.active_workspace;
-                palette.dispose(&mut self.workspaces[active_idx]);
+                action_handlers::lsp::dismiss_symbol_finder(self);
                 return UpdateEffect::Redraw;
             }
             if self.workspace_picker.is_some() {
@@ -4875,9 +5438,16 @@ impl Stoat {
                 self.quit_all_confirm = None;
                 return UpdateEffect::Redraw;
             }
+            if let Some(request) = self.agent_permissions.pop_front() {
+                request.answer(PermissionDecision::Deny);
+                return UpdateEffect::Redraw;
+            }
             if self.jumplist_picker.take().is_some() {
                 return UpdateEffect::Redraw;
             }
+            if self.undo_tree_picker.take().is_some() {
+                return UpdateEffect::Redraw;
+            }
             if self.diagnostics_picker.take().is_some() {
                 return UpdateEffect::Redraw;
             }
@@ -5260,6 +5830,11 @@ impl Stoat {
             return UpdateEffect::Redraw;
         }
 
+        let (flushed, consumed) = self.advance_key_sequence(key);
+        if consumed || flushed == UpdateEffect::Quit {
+            return flushed;
+        }
+
         let Some(actions) = looked_up else {
             if count_active_mode
                 && let KeyCode::Char(ch) = key.code
@@ -5270,9 +5845,139 @@ impl Stoat {
                 self.pending_count = Some(digit);
                 return UpdateEffect::Redraw;
             }
-            return UpdateEffect::None;
+            return flushed;
+        };
+
+        flushed.merge(self.dispatch_bound_actions(&actions, captured_digit))
+    }
+
+    /// Feed `key` to the chord sequence in progress, starting one when the
+    /// key leads a longer binding.
+    ///
+    /// The flag is whether the key was consumed: it started, extended or
+    /// completed a sequence, or `Esc` cancelled one. Otherwise the key
+    /// resolves as a single chord. A sequence the key breaks first runs the
+    /// binding its earlier chords complete, whose effect is returned either
+    /// way. Sequences do not start in insert mode, where printable keys type.
+    fn advance_key_sequence(&mut self, key: KeyEvent) -> (UpdateEffect, bool) {
+        let mut flushed = UpdateEffect::None;
+        if let Some(pending) = self.pending_keys.take() {
+            if key.code == KeyCode::Esc {
+                return (UpdateEffect::Redraw, true);
+            }
+            let mut presses = pending.presses;
+            presses.push(key);
+            let state = StoatKeymapState::from_stoat(self);
+            match self.keymap.lookup_sequence(&state, &presses) {
+                SequenceMatch::Pending { exact } => {
+                    self.arm_pending_keys(presses, exact);
+                    return (UpdateEffect::Redraw, true);
+                },
+                SequenceMatch::Matched(actions, captured) => {
+                    return (self.dispatch_bound_actions(&actions, captured), true);
+                },
+                SequenceMatch::Unbound => {
+                    flushed = match pending.exact {
+                        Some((actions, captured)) => {
+                            self.dispatch_bound_actions(&actions, captured)
+                        },
+                        None => UpdateEffect::Redraw,
+                    };
+                },
+            }
+        }
+        if self.focused_mode() == "insert" || !self.keymap.has_sequences() {
+            return (flushed, false);
+        }
+        let state = StoatKeymapState::from_stoat(self);
+        match self.keymap.lookup_sequence(&state, &[key]) {
+            SequenceMatch::Pending { exact } => {
+                self.arm_pending_keys(vec![key], exact);
+                (flushed.merge(UpdateEffect::Redraw), true)
+            },
+            SequenceMatch::Matched(..) | SequenceMatch::Unbound => (flushed, false),
+        }
+    }
+
+    /// Hold `presses` as the pending sequence and arm its timeout, collecting
+    /// the which-key rows for the bindings that continue it.
+    fn arm_pending_keys(
+        &mut self,
+        presses: Vec<KeyEvent>,
+        exact: Option<(Arc<[ResolvedAction]>, Option<f64>)>,
+    ) {
+        let hints = {
+            let state = StoatKeymapState::from_stoat(self);
+            self.keymap
+                .sequence_continuations(&state, &presses)
+                .into_iter()
+                .map(|(keys, actions)| (keys, binding_display_desc(actions)))
+                .collect()
         };
+        let timer = self.executor.timer(KEY_SEQUENCE_TIMEOUT);
+        let expiry = self.spawn_woken(async move {
+            timer.await;
+        });
+        self.pending_keys = Some(PendingKeys {
+            presses,
+            exact,
+            hints,
+            deadline: self.executor.now() + KEY_SEQUENCE_TIMEOUT,
+            _expiry: expiry,
+        });
+        self.start_which_key_delay();
+    }
+
+    fn which_key_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.settings.ui_which_key_delay.unwrap_or(0).into())
+    }
+
+    /// Restart the which-key delay for a prefix that begins now, arming a
+    /// wake for when it passes.
+    fn start_which_key_delay(&mut self) {
+        self.which_key_since = Some(self.executor.now());
+        let delay = self.which_key_delay();
+        self.which_key_wake = (!delay.is_zero()).then(|| {
+            let timer = self.executor.timer(delay);
+            self.spawn_woken(async move {
+                timer.await;
+            })
+        });
+    }
+
+    /// Whether the current prefix has been held for `ui.which_key_delay`, so
+    /// its which-key box should paint.
+    pub(crate) fn which_key_due(&self) -> bool {
+        self.which_key_since
+            .is_none_or(|since| self.executor.now() >= since + self.which_key_delay())
+    }
+
+    /// Drop a pending key sequence whose [`KEY_SEQUENCE_TIMEOUT`] has passed,
+    /// running the binding its chords complete. Polled from
+    /// [`Self::drive_background`], which the timeout's timer wakes.
+    pub(crate) fn expire_pending_keys(&mut self) {
+        let expired = self
+            .pending_keys
+            .as_ref()
+            .is_some_and(|pending| self.executor.now() >= pending.deadline);
+        if !expired {
+            return;
+        }
+        let exact = self.pending_keys.take().and_then(|pending| pending.exact);
+        if let Some((actions, captured)) = exact
+            && self.dispatch_bound_actions(&actions, captured) == UpdateEffect::Quit
+        {
+            self.quit_requested = true;
+        }
+    }
 
+    /// Run the actions of a resolved binding, with `captured_digit` bound to
+    /// `$num`, and clear the transient pickers they did not keep open.
+    pub(crate) fn dispatch_bound_actions(
+        &mut self,
+        actions: &[ResolvedAction],
+        captured_digit: Option<f64>,
+    ) -> UpdateEffect {
         let mut effect = UpdateEffect::None;
         let mut dispatched_action = false;
         let mut dispatched_hover = false;
@@ -5507,8 +6212,8 @@ impl Stoat {
     pub(crate) fn focused_editor_ids(&self) -> Option<(EditorId, BufferId)> {
         let ws = self.active_workspace();
 
-        if let Some(finder) = &self.file_finder {
-            return Some((finder.input.editor_id, finder.input.buffer_id));
+        if let Some(input) = crate::overlay::top(self).and_then(|overlay| overlay.input(self)) {
+            return Some((input.editor_id, input.buffer_id));
         }
 
         if let Some(finder) = &self.symbol_finder {
@@ -5523,16 +6228,6 @@ impl Stoat {
             return Some((picker.input.editor_id, picker.input.buffer_id));
         }
 
-        if let Some(palette) = &self.command_palette
-            && let Some(input) = palette.focused_input()
-        {
-            return Some((input.editor_id, input.buffer_id));
-        }
-
-        if let Some(help) = &self.help {
-            return Some((help.input.editor_id, help.input.buffer_id));
-        }
-
         if let Some(rename) = &self.rename_input {
             return Some((rename.input.editor_id, rename.input.buffer_id));
         }
@@ -5549,10 +6244,22 @@ impl Stoat {
             return Some((fs.input.editor_id, fs.input.buffer_id));
         }
 
+        if let Some(st) = &self.surround_tag_input {
+            return Some((st.input.editor_id, st.input.buffer_id));
+        }
+
         if let Some(sh) = &self.shell_input {
             return Some((sh.input.editor_id, sh.input.buffer_id));
         }
 
+        if let Some(color) = &self.color_input {
+            return Some((color.input.editor_id, color.input.buffer_id));
+        }
+
+        if let Some(rename) = &self.local_rename {
+            return Some((rename.input.editor_id, rename.input.buffer_id));
+        }
+
         if let Some((editor_id, buffer_id)) = ws
             .rebase_active
             .as_ref()
@@ -5584,6 +6291,10 @@ impl Stoat {
                 let run_state = ws.runs.get(id)?;
                 Some((run_state.input.editor_id, run_state.input.buffer_id))
             },
+            View::RegexPlayground(id) => {
+                let input = ws.regex_playgrounds.get(id)?.focused_input();
+                Some((input.editor_id, input.buffer_id))
+            },
             _ => None,
         }
     }
@@ -5596,6 +6307,24 @@ impl Stoat {
     /// cursor cell would otherwise be drawn. `None` for finder/palette/dock/run
     /// focus, where the editor paints its own cursor cell and the terminal
     /// cursor stays hidden. Must be called after a render.
+    /// The terminal cursor's shape for the focused mode, from
+    /// `editor.cursor_shape.<mode>`, and its blink interval, from
+    /// `editor.cursor_blink`. A zero interval keeps it steady.
+    pub(crate) fn cursor_style(&self) -> crate::ui::CursorStyle {
+        crate::ui::CursorStyle {
+            shape: self
+                .settings
+                .cursor_shapes
+                .get(self.focused_mode())
+                .copied(),
+            blink: self
+                .settings
+                .editor_cursor_blink
+                .filter(|&ms| ms > 0)
+                .map(|ms| std::time::Duration::from_millis(u64::from(ms))),
+        }
+    }
+
     pub(crate) fn primary_cursor_screen_pos(&self) -> Option<(u16, u16)> {
         let (focused_id, _) = self.focused_editor_ids()?;
         let ws = self.active_workspace();
@@ -5789,6 +6518,9 @@ impl Stoat {
     /// the raw setter, without the insert-run bookkeeping
     /// [`Self::transition_mode`] layers on top.
     pub(crate) fn set_focused_mode(&mut self, mode: String) {
+        if self.focused_mode() != mode {
+            self.start_which_key_delay();
+        }
         if let Some((editor_id, _)) = self.focused_editor_ids()
             && let Some(editor) = self.active_workspace_mut().editors.get_mut(editor_id)
         {
@@ -6376,6 +7108,40 @@ impl Stoat {
         }
     }
 
+    /// The leading whitespace a line placed directly below `row` would take
+    /// from the buffer's `indents.scm` query, with `None` for `row` meaning the
+    /// top of the buffer.
+    ///
+    /// Returns `None` when the tree is stale or the language has no indent
+    /// query. Unlike [`Self::newline_indent_string`] there is no copy-the-row
+    /// fallback, so callers that re-indent existing text leave it alone rather
+    /// than guess.
+    pub(crate) fn syntax_indent_below(
+        &self,
+        buffer_id: BufferId,
+        row: Option<u32>,
+    ) -> Option<String> {
+        let buffers = &self.active_workspace().buffers;
+        let buffer = buffers.get(buffer_id)?;
+        let guard = buffer.read().expect("buffer poisoned");
+        let lang = buffers.language_for(buffer_id)?;
+        let query = lang.indent_query.as_ref()?;
+        let syntax = buffers
+            .syntax(buffer_id)
+            .filter(|syntax| syntax.version == guard.version())?;
+        let Some(row) = row else {
+            return Some(String::new());
+        };
+        let rope = &syntax.rope_snapshot;
+        let row_end = rope.point_to_offset(stoat_text::Point::new(row, rope.line_len(row)));
+        Some(language::newline_indent(
+            query,
+            syntax.tree.root_node(),
+            rope,
+            row_end,
+        ))
+    }
+
     fn editor_backspace(&mut self, editor_id: EditorId, buffer_id: BufferId) {
         let indent_width = self.buffer_indent_style(buffer_id).indent_width(TAB_WIDTH);
         self.editor_delete_ranges(editor_id, buffer_id, move |rope, cursor| {
@@ -6598,6 +7364,15 @@ impl Stoat {
                 action_handlers::lsp::answer_agent_query(self, uid, request, reply);
                 UpdateEffect::None
             },
+            AgentControl::ProposeEdits { uid, edits, reply } => {
+                action_handlers::review::propose_agent_edits(self, uid, edits, reply)
+            },
+            AgentControl::Permission {
+                uid,
+                tool,
+                detail,
+                reply,
+            } => action_handlers::agent::request_agent_permission(self, uid, tool, detail, reply),
         }
     }
 
@@ -6853,6 +7628,27 @@ impl Stoat {
         );
     }
 
+    /// Recompute the active workspace's buffer-pair diffs opened by
+    /// `DiffWithBuffer` after an edit to either side.
+    ///
+    /// Not gated on [`Self::diff_warm_auto`]: a pair diffs two in-memory
+    /// buffers and spawns nothing, and the view it feeds is already open.
+    pub(crate) fn sync_buffer_diffs(&mut self) {
+        let Self {
+            workspaces,
+            active_workspace,
+            language_registry,
+            syntax_styles,
+            base_highlights_cache,
+            ..
+        } = self;
+        workspaces[*active_workspace].sync_buffer_diffs(
+            language_registry,
+            syntax_styles,
+            base_highlights_cache,
+        );
+    }
+
     /// Paint the current state into a fresh [`Buffer`] and return it.
     ///
     /// A convenience wrapper over [`Self::paint_into`] for the test harness,
@@ -6915,16 +7711,67 @@ impl Stoat {
         }
     }
 
+    /// Tell stoatty how to animate the cursor when `editor.cursor_animation`
+    /// names a motion it has not been sent yet.
+    ///
+    /// A no-op unless running inside stoatty, and while the setting is unset,
+    /// which leaves the terminal's own `cursor_animation` in charge.
+    fn emit_cursor_animation(&mut self) {
+        let Some(apc_tx) = self.apc_tx.clone() else {
+            return;
+        };
+        let Some(animation) = self.settings.editor_cursor_animation else {
+            return;
+        };
+        if self.cursor_animation_sent == Some(animation) {
+            return;
+        }
+        self.cursor_animation_sent = Some(animation);
+
+        let motion = match animation {
+            stoat_config::CursorAnimation::Smooth => {
+                stoatty_protocol::command::CursorAnimation::Block
+            },
+            stoat_config::CursorAnimation::Smear => {
+                stoatty_protocol::command::CursorAnimation::Warp
+            },
+        };
+        let mut out = Vec::new();
+        stoatty_protocol::command::encode_cursor_animation_into(&mut out, motion);
+        let _ = apc_tx.send(out);
+    }
+
+    /// Tell stoatty what a screen reader should announce when the cursor's
+    /// line or column, the line's text, the mode, or the open modal changed
+    /// since the last frame.
+    ///
+    /// A no-op unless running inside stoatty.
+    fn emit_accessibility(&mut self) {
+        let Some(apc_tx) = self.apc_tx.clone() else {
+            return;
+        };
+        let state = crate::accessibility::describe(self);
+        if self.accessibility_sent.as_ref() == Some(&state) {
+            return;
+        }
+        let mut out = Vec::new();
+        stoatty_protocol::command::encode_accessibility_into(&mut out, &state);
+        let _ = apc_tx.send(out);
+        self.accessibility_sent = Some(state);
+    }
+
     /// Tell the hosting terminal its config file changed on disk, so it re-reads
     /// and re-applies it.
     ///
-    /// A no-op unless running inside stoatty. Unlike the frame-seam emitters
-    /// this carries no state to reconcile. Each call reports one save, and the
-    /// terminal reads the file itself.
-    pub(crate) fn emit_config_reload(&self) {
+    /// A no-op unless running inside stoatty. Each call reports one save, and
+    /// the terminal reads the file itself. The reload restores the terminal's
+    /// configured cursor animation, so the next frame seam sends
+    /// `editor.cursor_animation` again.
+    pub(crate) fn emit_config_reload(&mut self) {
         let Some(apc_tx) = self.apc_tx.clone() else {
             return;
         };
+        self.cursor_animation_sent = None;
 
         let mut out = Vec::new();
         stoatty_protocol::command::encode_config_reload_into(&mut out);
@@ -7050,13 +7897,16 @@ impl Stoat {
             lsp_registry: &self.lsp_registry,
             search_query: None,
             line_numbers: LineNumbers::Relative,
+            cursor_line: false,
             wrap_mode: WrapMode::EditorWidth,
             wrap_column: 80,
+            wrap_rules: crate::display_map::WrapRules::default(),
             inactive_dim: 0.0,
             minimap_enabled: false,
             minimap_chrome: None,
             minimap_band: None,
             hover_cell: None,
+            breadcrumbs: false,
             home: self.home.as_deref(),
             #[cfg(feature = "perf")]
             perf: None,
@@ -7094,12 +7944,14 @@ impl Stoat {
                             buffers: &ws.buffers,
                             runs: &ws.runs,
                             terms: &ws.terms,
+                            hex_views: &ws.hex_views,
+                            regex_playgrounds: &mut ws.regex_playgrounds,
                         },
                         frame,
                         &mut buf,
                         &mut scene,
                         &mut undercurls,
-                        &mut None,
+                        &mut Default::default(),
                     );
                 }
 
@@ -7587,10 +8439,7 @@ impl Stoat {
         }
         let mut async_jobs: Vec<PoolFill> = Vec::new();
         let syntax_highlight = self.syntax_highlight;
-        let line_numbers = self
-            .settings
-            .editor_line_numbers
-            .unwrap_or(LineNumbers::Relative);
+        let line_numbers = self.line_numbers();
         let inactive_dim = self
             .settings
             .ui_inactive_dim
@@ -7868,6 +8717,8 @@ impl Stoat {
         if let (Some(list), Some(palette)) = (palette_list, self.command_palette.as_ref()) {
             let filtered = &palette.filtered;
             let match_indices = &palette.match_indices;
+            let labels = &palette.labels;
+            let key_hints = &palette.key_hints;
             let selected = &palette.selected;
             let region = stoatty_protocol::command::PoolRegionCommand {
                 pool: crate::smooth_scroll::non_pane_pool::PALETTE,
@@ -7899,6 +8750,8 @@ impl Stoat {
                     crate::smooth_scroll::render_palette_page(
                         filtered,
                         match_indices,
+                        labels,
+                        key_hints,
                         *selected,
                         page,
                         theme,
@@ -8372,6 +9225,7 @@ impl Stoat {
     /// keeping [`Self::render`] a pure paint. Tests that previously relied on
     /// `render` to drive this call it directly.
     pub(crate) fn drive_background(&mut self) {
+        self.expire_pending_keys();
         self.drain_lsp_notifications();
         self.drain_lsp_incoming_requests();
         self.install_pending_lsp_host();
@@ -8385,7 +9239,17 @@ impl Stoat {
         action_handlers::sync_file_finder_preview(self);
         action_handlers::file::pump_auto_reload(self);
         self.drive_parse_jobs();
+        crate::spell::refresh(self);
         self.drive_diff_jobs();
+        self.sync_buffer_diffs();
+        crate::collab::pump(self);
+        action_handlers::shell::install_command_outputs(self);
+        crate::task::pump(self);
+        crate::test_lens::pump(self);
+        crate::project_config::sync(self);
+        crate::code_lens::refresh(self);
+        crate::color_swatch::refresh(self);
+        crate::occurrence_highlight::refresh(self);
         action_handlers::pump_commits(self);
         action_handlers::pump_review_scan(self);
         action_handlers::code_search::pump_code_search(self);
@@ -8562,14 +9426,6 @@ fn minimap_line_tokens(
     if rows.start > last_row {
         return by_row;
     }
-    // The byte span of `rows`, so each channel resolves anchors only for the
-    // tokens that can overlap it instead of the whole buffer.
-    let byte_range = {
-        let start = rope.point_to_offset(stoat_text::Point::new(rows.start, 0));
-        let end = rope.point_to_offset(stoat_text::Point::new(last_row, rope.line_len(last_row)));
-        start..end
-    };
-
     for highlights in [
         snapshot.semantic_token_highlights(),
         snapshot.lsp_token_highlights(),
@@ -8577,20 +9433,16 @@ fn minimap_line_tokens(
         let Some(channel) = highlights.get(&buffer_id) else {
             continue;
         };
-        let bounds =
-            channel.overlap_bounds(&byte_range, |anchor| buffer_snap.resolve_anchor(anchor));
-        for span in &channel.tokens[bounds] {
+        let spans = channel.tokens_in_lines(rope, rows.start..last_row + 1, |anchor| {
+            buffer_snap.resolve_anchor(anchor)
+        });
+        for (span, Range { start, end }) in spans {
             let class = channel.interner[span.style]
                 .foreground
                 .map_or(0, |fg| class_table.class_of_color(fg));
             if class == 0 {
                 continue;
             }
-            let start = buffer_snap.resolve_anchor(&span.range.start);
-            let end = buffer_snap.resolve_anchor(&span.range.end);
-            if start >= end {
-                continue;
-            }
 
             let start_row = rope.offset_to_point(start).row.max(rows.start);
             let end_row = rope.offset_to_point(end).row.min(last_row);
@@ -8938,42 +9790,42 @@ pub(crate) fn parse_buffer_step(
     let mut syntax_map = stoat_language::SyntaxMap::default();
     let _ = syntax_map.reparse(&new_rope, lang.clone(), cur_version);
 
-    // A capture resolves to a theme key index through its originating layer's
-    // highlight_map(). A DEFAULT id (capture absent from the active theme)
-    // carries no style and is skipped. captures() document order
-    // (start, Reverse(end), depth) is kept so deeper injection layers land later
-    // and win under the display map's endpoint precedence. highlight_map() clones
-    // a locked map, so memoize it per layer language.
+    // Converting captures to anchored tokens walks the whole file, so a big one
+    // splits at top-level nodes and converts the parts on scoped threads.
+    // Each part keeps only the captures starting in it, so concatenating them
+    // in part order reproduces the single-threaded token list.
     let tokens: Arc<[SemanticTokenHighlight]> = {
-        use std::collections::HashMap;
-
-        let mut highlight_maps = HashMap::new();
-        syntax_map
-            .snapshot()
-            .captures(0..new_rope.len(), &new_rope, |l| Some(&l.highlight_query))
-            .into_iter()
-            .filter_map(|cap| {
-                let range = cap.node.byte_range();
-                if range.start == range.end {
-                    return None;
-                }
-                let map = highlight_maps
-                    .entry(cap.language as *const Language as usize)
-                    .or_insert_with(|| cap.language.highlight_map());
-                let style_id = styles.id_for_highlight(map.get(cap.index))?;
-                Some(SemanticTokenHighlight {
-                    // Insertions at the start of a token attach to the previous
-                    // span, not this one; insertions at the end attach to the
-                    // next span. Keeps a typed character from silently extending
-                    // a keyword or string into neighboring text.
-                    range: snapshot.anchor_at(range.start, Bias::Right)
-                        ..snapshot.anchor_at(range.end, Bias::Left),
-                    style: style_id,
-                })
+        let syntax = syntax_map.snapshot();
+        let len = new_rope.len();
+        let parts = if len >= PARALLEL_TOKENS_MIN_BYTES {
+            std::thread::available_parallelism().map_or(1, |n| n.get().min(8))
+        } else {
+            1
+        };
+        let ranges = token_ranges(tree.root_node(), len, parts);
+        if ranges.len() == 1 {
+            highlight_tokens(syntax, &snapshot, &new_rope, styles, 0..len).into()
+        } else {
+            let (snapshot, rope) = (&snapshot, &new_rope);
+            std::thread::scope(|s| {
+                let handles: Vec<_> = ranges
+                    .into_iter()
+                    .map(|range| {
+                        s.spawn(move || highlight_tokens(syntax, snapshot, rope, styles, range))
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .flat_map(|h| h.join().expect("token conversion thread panicked"))
+                    .collect()
             })
-            .collect()
+        }
     };
 
+    let words = Arc::new(crate::completion::word::BufferWords::index(
+        &tree, &new_rope,
+    ));
+
     Some(ParseJobOutput {
         buffer_id,
         syntax: SyntaxState {
@@ -8983,9 +9835,76 @@ pub(crate) fn parse_buffer_step(
         },
         syntax_map,
         tokens,
+        words,
     })
 }
 
+/// Smallest buffer whose captures convert to tokens on several threads. Below
+/// it the spawn cost outweighs the walk.
+const PARALLEL_TOKENS_MIN_BYTES: usize = 1024 * 1024;
+
+/// Split `0..len` into at most `parts` contiguous ranges, breaking only where
+/// a top-level child of `root` starts, so each range covers whole top-level
+/// items.
+fn token_ranges(root: stoat_language::Node<'_>, len: usize, parts: usize) -> Vec<Range<usize>> {
+    let mut cursor = root.walk();
+    let starts: Vec<usize> = root.children(&mut cursor).map(|c| c.start_byte()).collect();
+    let mut bounds = vec![0];
+    for i in 1..parts {
+        let target = len * i / parts;
+        let next = starts.get(starts.partition_point(|&s| s < target));
+        if let Some(&start) = next.filter(|&&s| s > bounds[bounds.len() - 1] && s < len) {
+            bounds.push(start);
+        }
+    }
+    bounds.push(len);
+    bounds.windows(2).map(|w| w[0]..w[1]).collect()
+}
+
+/// The styled, anchored highlight tokens of the captures starting in `range`.
+///
+/// A capture resolves to a theme key index through its originating layer's
+/// highlight_map(). A DEFAULT id (capture absent from the active theme)
+/// carries no style and is skipped. captures() document order
+/// (start, Reverse(end), depth) is kept so deeper injection layers land later
+/// and win under the display map's endpoint precedence. highlight_map() clones
+/// a locked map, so memoize it per layer language.
+fn highlight_tokens(
+    syntax: &stoat_language::SyntaxSnapshot,
+    snapshot: &TextBufferSnapshot,
+    rope: &stoat_text::Rope,
+    styles: &SyntaxStyles,
+    range: Range<usize>,
+) -> Vec<SemanticTokenHighlight> {
+    use std::collections::HashMap;
+
+    let mut highlight_maps = HashMap::new();
+    syntax
+        .captures(range.clone(), rope, |l| Some(&l.highlight_query))
+        .into_iter()
+        .filter_map(|cap| {
+            let node_range = cap.node.byte_range();
+            // A capture reaching in from an earlier range belongs to that one.
+            if node_range.start == node_range.end || node_range.start < range.start {
+                return None;
+            }
+            let map = highlight_maps
+                .entry(cap.language as *const Language as usize)
+                .or_insert_with(|| cap.language.highlight_map());
+            let style_id = styles.id_for_highlight(map.get(cap.index))?;
+            Some(SemanticTokenHighlight {
+                // Insertions at the start of a token attach to the previous
+                // span, not this one; insertions at the end attach to the
+                // next span. Keeps a typed character from silently extending
+                // a keyword or string into neighboring text.
+                range: snapshot.anchor_at(node_range.start, Bias::Right)
+                    ..snapshot.anchor_at(node_range.end, Bias::Left),
+                style: style_id,
+            })
+        })
+        .collect()
+}
+
 /// Background parse worker. Owns all inputs by value so the future is `Send`
 /// and can run on any executor thread.
 pub(crate) async fn parse_buffer_async(
@@ -9926,7 +10845,37 @@ mod tests {
     }
 
     #[test]
-    fn index_build_watches_the_repo_root_off_the_render_thread() {
+    fn index_build_watches_the_repo_root_off_the_render_thread() {
+        use crate::host::{FakeFs, FakeFsWatcher, FakeGit};
+
+        let scheduler = Arc::new(stoat_scheduler::TestScheduler::new());
+        let mut stoat = Stoat::new(
+            scheduler.executor(),
+            Settings::default(),
+            PathBuf::from("/repo"),
+        );
+
+        let fs = Arc::new(FakeFs::new());
+        fs.insert_file("/repo/src/a.rs", "fn foo() {}\n");
+        stoat.set_fs_host(fs);
+        let git = FakeGit::new();
+        git.add_repo("/repo");
+        stoat.set_git_host(Arc::new(git));
+        let watcher = Arc::new(FakeFsWatcher::new());
+        stoat.set_fs_watch_host(watcher.clone());
+
+        let git_root = stoat.active_workspace().git_root.clone();
+        stoat.start_index_build();
+        scheduler.run_until_parked();
+
+        assert!(
+            watcher.is_watching(&git_root),
+            "the repo root is watched once the blocking registration runs"
+        );
+    }
+
+    #[test]
+    fn persistence_disabled_index_build_registers_no_watch() {
         use crate::host::{FakeFs, FakeFsWatcher, FakeGit};
 
         let scheduler = Arc::new(stoat_scheduler::TestScheduler::new());
@@ -9935,6 +10884,7 @@ mod tests {
             Settings::default(),
             PathBuf::from("/repo"),
         );
+        stoat.persistence_disabled = true;
 
         let fs = Arc::new(FakeFs::new());
         fs.insert_file("/repo/src/a.rs", "fn foo() {}\n");
@@ -9950,14 +10900,14 @@ mod tests {
         scheduler.run_until_parked();
 
         assert!(
-            watcher.is_watching(&git_root),
-            "the repo root is watched once the blocking registration runs"
+            !watcher.is_watching(&git_root),
+            "a persistence-disabled build registers no fs-watch"
         );
     }
 
     #[test]
-    fn persistence_disabled_index_build_registers_no_watch() {
-        use crate::host::{FakeFs, FakeFsWatcher, FakeGit};
+    fn worktree_files_follow_the_build_and_fs_events() {
+        use crate::host::{FakeFs, FakeFsWatcher, FakeGit, FsEventKind};
 
         let scheduler = Arc::new(stoat_scheduler::TestScheduler::new());
         let mut stoat = Stoat::new(
@@ -9969,20 +10919,36 @@ mod tests {
 
         let fs = Arc::new(FakeFs::new());
         fs.insert_file("/repo/src/a.rs", "fn foo() {}\n");
-        stoat.set_fs_host(fs);
+        fs.insert_file("/repo/README.md", "# repo\n");
+        stoat.set_fs_host(fs.clone());
         let git = FakeGit::new();
         git.add_repo("/repo");
         stoat.set_git_host(Arc::new(git));
         let watcher = Arc::new(FakeFsWatcher::new());
         stoat.set_fs_watch_host(watcher.clone());
 
-        let git_root = stoat.active_workspace().git_root.clone();
         stoat.start_index_build();
         scheduler.run_until_parked();
+        stoat.drain_index_updates();
+        let files = |stoat: &Stoat| {
+            let files = stoat.active_workspace().worktree_files.clone();
+            files.map(|files| files.into_iter().collect::<Vec<_>>())
+        };
+        assert_eq!(
+            files(&stoat),
+            Some(vec!["README.md".to_string(), "src/a.rs".to_string()]),
+            "the build records every walked file, indexable or not",
+        );
 
-        assert!(
-            !watcher.is_watching(&git_root),
-            "a persistence-disabled build registers no fs-watch"
+        fs.insert_file("/repo/notes.txt", "todo\n");
+        watcher.inject(Path::new("/repo/notes.txt"), FsEventKind::Created);
+        fs.remove_file(Path::new("/repo/src/a.rs")).unwrap();
+        watcher.inject(Path::new("/repo/src/a.rs"), FsEventKind::Removed);
+        stoat.drain_fs_watch_events();
+        assert_eq!(
+            files(&stoat),
+            Some(vec!["README.md".to_string(), "notes.txt".to_string()]),
+            "a created file joins the list and a removed one leaves it",
         );
     }
 
@@ -10897,6 +11863,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn key_sequences_wait_for_their_next_chord() {
+        let mut h = Stoat::test();
+        h.stoat.keymap = compile_keymap(
+            r#"on key {
+                g -> SetVar(last, single);
+                g g -> SetVar(last, double);
+                Space f -> SetVar(last, finder);
+            }"#,
+        );
+        h.stoat.hints_cache = None;
+        let last = |h: &crate::test_harness::TestHarness| h.stoat.user_vars.get("last").cloned();
+
+        h.stoat.handle_key(bare(KeyCode::Char('g')));
+        assert_eq!(last(&h), None, "`g` waits for a possible second chord");
+        let pending = h.stoat.pending_keys.as_ref().expect("pending sequence");
+        assert_eq!(pending.label(), "g");
+        assert_eq!(pending.hints.len(), 1);
+        h.stoat.handle_key(bare(KeyCode::Char('g')));
+        assert_eq!(last(&h), Some(StateValue::String("double".into())));
+        assert!(h.stoat.pending_keys.is_none());
+
+        // A chord that continues nothing runs what the earlier ones completed.
+        h.stoat.handle_key(bare(KeyCode::Char('g')));
+        h.stoat.handle_key(bare(KeyCode::Char('x')));
+        assert_eq!(last(&h), Some(StateValue::String("single".into())));
+
+        // Escape abandons a sequence without running anything.
+        h.stoat.user_vars.clear();
+        h.stoat.handle_key(bare(KeyCode::Char(' ')));
+        h.stoat.handle_key(bare(KeyCode::Esc));
+        assert!(h.stoat.pending_keys.is_none());
+        assert_eq!(last(&h), None);
+
+        // The timeout runs the binding the pressed chords complete.
+        h.stoat.handle_key(bare(KeyCode::Char('g')));
+        h.advance_clock(KEY_SEQUENCE_TIMEOUT);
+        h.stoat.expire_pending_keys();
+        assert!(h.stoat.pending_keys.is_none());
+        assert_eq!(last(&h), Some(StateValue::String("single".into())));
+    }
+
+    #[test]
+    fn lang_scoped_bindings_follow_the_focused_buffer() {
+        let mut h = Stoat::test();
+        h.stoat.keymap = compile_keymap(
+            r#"on key {
+                g d -> SetVar(last, definition);
+                lang == markdown { g d -> SetVar(last, heading); }
+            }"#,
+        );
+        h.stoat.hints_cache = None;
+        let last = |h: &crate::test_harness::TestHarness| h.stoat.user_vars.get("last").cloned();
+        h.fake_fs().insert_file("/lang/a.rs", b"fn main() {}\n");
+        h.fake_fs().insert_file("/lang/b.md", b"# Title\n");
+
+        h.open_file(Path::new("/lang/b.md"));
+        h.settle();
+        h.stoat.handle_key(bare(KeyCode::Char('g')));
+        h.stoat.handle_key(bare(KeyCode::Char('d')));
+        assert_eq!(last(&h), Some(StateValue::String("heading".into())));
+
+        h.open_file(Path::new("/lang/a.rs"));
+        h.settle();
+        h.stoat.handle_key(bare(KeyCode::Char('g')));
+        h.stoat.handle_key(bare(KeyCode::Char('d')));
+        assert_eq!(last(&h), Some(StateValue::String("definition".into())));
+    }
+
+    #[test]
+    fn which_key_box_waits_for_the_configured_delay() {
+        let mut h = Stoat::test();
+        h.stoat.keymap = compile_keymap("on key { g g -> SetVar(last, double); }");
+        h.stoat.hints_cache = None;
+        h.stoat.settings.ui_which_key_delay = Some(300);
+
+        h.stoat.handle_key(bare(KeyCode::Char('g')));
+        assert!(h.stoat.pending_keys.is_some());
+        assert!(!h.stoat.which_key_due(), "a fresh prefix hides the box");
+        h.advance_clock(std::time::Duration::from_millis(300));
+        assert!(
+            h.stoat.which_key_due(),
+            "the box shows once the delay passes"
+        );
+
+        h.stoat.settings.ui_which_key_delay = None;
+        h.stoat.handle_key(bare(KeyCode::Char('g')));
+        h.stoat.handle_key(bare(KeyCode::Char('g')));
+        assert!(h.stoat.which_key_due(), "no delay shows it at once");
+    }
+
     #[test]
     fn set_var_collision_with_builtin_is_ignored() {
         let mut h = Stoat::test();
@@ -11607,6 +12664,61 @@ mod tests {
         );
     }
 
+    /// Converting a file in top-level parts yields the same tokens, in the
+    /// same order, as converting it whole.
+    #[test]
+    fn split_token_conversion_matches_the_whole_file() {
+        let h = Stoat::test();
+        let lang = h
+            .stoat
+            .language_registry
+            .for_path(Path::new("a.rs"))
+            .expect("rust");
+        let text = "/// doc\nfn a() { let s = \"x\"; }\n\nstruct B { c: u8 }\n".repeat(20);
+        let buf = TextBuffer::with_text(BufferId::new(1), &text);
+        let snapshot = buf.snapshot.clone();
+        let rope = snapshot.visible_text.clone();
+        let tree = language::parse_rope(&lang, &rope, None).expect("parsed");
+        let mut map = stoat_language::SyntaxMap::default();
+        map.reparse(&rope, lang.clone(), snapshot.version)
+            .expect("reparsed");
+        let styles = &h.stoat.syntax_styles;
+        let resolved = |tokens: Vec<SemanticTokenHighlight>| -> Vec<_> {
+            tokens
+                .iter()
+                .map(|t| {
+                    let start = snapshot.resolve_anchor(&t.range.start);
+                    (start..snapshot.resolve_anchor(&t.range.end), t.style)
+                })
+                .collect()
+        };
+
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let starts: Vec<usize> = root.children(&mut cursor).map(|c| c.start_byte()).collect();
+        let ranges = token_ranges(root, rope.len(), 4);
+        assert_eq!(ranges.len(), 4);
+        assert!(
+            ranges[1..].iter().all(|r| starts.contains(&r.start)),
+            "parts break at top-level items"
+        );
+        let whole = resolved(highlight_tokens(
+            map.snapshot(),
+            &snapshot,
+            &rope,
+            styles,
+            0..rope.len(),
+        ));
+        assert!(!whole.is_empty());
+        let split = resolved(
+            ranges
+                .into_iter()
+                .flat_map(|r| highlight_tokens(map.snapshot(), &snapshot, &rope, styles, r))
+                .collect(),
+        );
+        assert_eq!(split, whole);
+    }
+
     /// When `parse_buffer_step` aborts on the deadline, the prior state
     /// passed via `&mut Option<_>` must remain populated so the caller
     /// can hand it to a follow-up parse without losing incrementality.
@@ -11751,6 +12863,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cursor_style_follows_the_focused_mode() {
+        let mut h = Stoat::test();
+        h.stoat
+            .settings
+            .cursor_shapes
+            .insert("insert".to_string(), stoat_config::CursorShape::Bar);
+        h.stoat.settings.editor_cursor_blink = Some(0);
+        assert_eq!(h.stoat.cursor_style(), crate::ui::CursorStyle::default());
+
+        h.stoat.settings.editor_cursor_blink = Some(500);
+        h.type_keys("i");
+        assert_eq!(
+            h.stoat.cursor_style(),
+            crate::ui::CursorStyle {
+                shape: Some(stoat_config::CursorShape::Bar),
+                blink: Some(std::time::Duration::from_millis(500)),
+            }
+        );
+    }
+
+    #[test]
+    fn cursor_animation_is_sent_once_per_change() {
+        use stoatty_protocol::command::{Command, CursorAnimation};
+
+        let mut h = Stoat::test();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Vec<u8>>();
+        h.stoat.set_apc_tx(tx);
+
+        h.stoat.emit_cursor_animation();
+        assert!(drain_apc(&mut rx).is_empty(), "unset sends nothing");
+
+        h.stoat.settings.editor_cursor_animation = Some(stoat_config::CursorAnimation::Smear);
+        h.stoat.emit_cursor_animation();
+        h.stoat.emit_cursor_animation();
+        assert_eq!(
+            drain_apc(&mut rx),
+            vec![Command::CursorAnimation(CursorAnimation::Warp)]
+        );
+
+        h.stoat.emit_config_reload();
+        h.stoat.emit_cursor_animation();
+        assert_eq!(
+            drain_apc(&mut rx),
+            vec![
+                Command::ConfigReload,
+                Command::CursorAnimation(CursorAnimation::Warp)
+            ],
+            "a terminal config reload resets the motion, so it is sent again"
+        );
+    }
+
+    #[test]
+    fn accessibility_is_announced_once_per_change() {
+        use stoatty_protocol::command::Command;
+
+        let mut h = Stoat::test();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Vec<u8>>();
+        h.stoat.set_apc_tx(tx);
+        h.type_keys("i");
+        h.type_text("ab");
+        h.type_keys("enter");
+        h.type_text("cdé");
+
+        h.stoat.emit_accessibility();
+        let [Command::Accessibility(state)] = drain_apc(&mut rx).as_slice() else {
+            panic!("expected one announcement");
+        };
+        assert_eq!((state.line, state.column), (1, 3));
+        assert_eq!(state.line_text, "cdé");
+        assert_eq!(state.mode, "insert");
+        assert_eq!(state.modal, None);
+
+        h.stoat.emit_accessibility();
+        assert!(
+            drain_apc(&mut rx).is_empty(),
+            "an unchanged state is not resent"
+        );
+
+        h.type_keys("escape");
+        h.stoat.emit_accessibility();
+        let [Command::Accessibility(state)] = drain_apc(&mut rx).as_slice() else {
+            panic!("expected the mode change announced");
+        };
+        assert_eq!(state.mode, "normal");
+    }
+
     #[test]
     fn minimap_emits_declare_and_line_summaries() {
         use stoatty_protocol::command::Command;
@@ -15807,7 +17006,7 @@ mod tests {
     }
 
     #[test]
-    fn lsp_spawn_failure_surfaces_in_message_row() {
+    fn lsp_spawn_failure_raises_an_error_toast() {
         let scheduler = Arc::new(stoat_scheduler::TestScheduler::new());
         let mut stoat = Stoat::new(scheduler.executor(), Settings::default(), PathBuf::new());
         stoat
@@ -15822,9 +17021,16 @@ mod tests {
 
         stoat.install_pending_lsp_host();
 
+        let toast = stoat.toasts.visible().next().expect("a toast is raised");
+        assert_eq!(toast.level, ToastLevel::Error);
+        assert_eq!(toast.message, "lsp: rust-analyzer: NotFound");
         assert_eq!(
-            stoat.pending_message.as_deref(),
-            Some("lsp: rust-analyzer: NotFound")
+            toast
+                .action
+                .as_ref()
+                .map(|action| action.action.name.as_str()),
+            Some("OpenLogs"),
+            "the toast offers the log",
         );
         assert!(
             stoat.lsp_host().is_noop(),
@@ -16168,6 +17374,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn toast_paints_until_its_ttl_elapses() {
+        let mut h = crate::test_harness::TestHarness::with_size(60, 12);
+        h.stoat
+            .notify(ToastLevel::Warning, "format failed: timed out");
+
+        h.snapshot();
+        assert!(
+            h.rendered_text().contains("format failed: timed out"),
+            "the toast paints before its ttl elapses",
+        );
+
+        h.advance_clock(ToastLevel::Warning.ttl());
+        h.snapshot();
+        assert!(
+            !h.rendered_text().contains("format failed"),
+            "the toast retires once its ttl elapses",
+        );
+        assert_eq!(
+            h.stoat.toasts.history().next().map(Toast::line).as_deref(),
+            Some("warning: format failed: timed out"),
+            "the history keeps the retired toast",
+        );
+    }
+
+    #[test]
+    fn run_toast_action_dispatches_the_offered_action() {
+        let mut h = crate::test_harness::TestHarness::with_size(60, 12);
+        h.stoat.notify_with_action(
+            ToastLevel::Error,
+            "lsp: gone",
+            ToastAction::new("version", "ShowVersion"),
+        );
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::RunToastAction);
+
+        assert!(h.stoat.toasts.is_empty(), "running the action dismisses it");
+        assert!(
+            h.stoat
+                .badges
+                .find_by_source(crate::badge::BadgeSource::Version)
+                .is_some(),
+            "the offered action ran",
+        );
+    }
+
     #[test]
     fn diagnostics_notification_updates_store() {
         use crate::host::LspNotification;
@@ -17354,6 +18606,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn clicking_a_breadcrumb_selects_its_item() {
+        let mut h = Stoat::test();
+        let source = "mod outer {\n    fn inner() {\n        let x = 1;\n    }\n}\n";
+        let path = PathBuf::from("/crumbs/a.rs");
+        h.fake_fs().insert_file(&path, source.as_bytes());
+        h.stoat.settings.editor_breadcrumbs = Some(true);
+        action_handlers::dispatch(&mut h.stoat, &OpenFile { path });
+        for _ in 0..2 {
+            h.stoat.drive_background();
+            let _ = h.stoat.render();
+            h.settle();
+        }
+        action_handlers::movement::jump_to_offset(&mut h.stoat, source.find("let x").unwrap());
+
+        let buf = h.render_composited();
+        let y = buf.area.height - 1;
+        let bar: String = (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect();
+        assert!(
+            bar.contains(" mod outer > fn inner "),
+            "the bar lists the enclosing items:\n{bar}"
+        );
+        assert_eq!(h.stoat.breadcrumb_rects.len(), 2);
+
+        let (rect, _) = h.stoat.breadcrumb_rects[1].clone();
+        h.stoat.update(mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            rect.x + 1,
+            rect.y,
+        ));
+        let start = source.find("fn inner").unwrap();
+        let end = source.find("    }\n}").unwrap() + 5;
+        assert_eq!(h.selection_spans(), vec![(start, end, false)]);
+    }
+
     #[test]
     fn minimap_drag_scrolls_monotonically() {
         let mut h = Stoat::test();
@@ -17382,6 +18669,30 @@ mod tests {
         assert_eq!(h.stoat.minimap_drag, None, "releasing clears the scrub");
     }
 
+    #[test]
+    fn minimap_visible_region_drags_from_where_it_was_grabbed() {
+        let mut h = Stoat::test();
+        let editor_id = open_with_minimap_strip(&mut h);
+
+        // Cell row 1 points at line 12, inside the 20-row viewport at the top.
+        h.stoat
+            .update(mouse_event(MouseEventKind::Down(MouseButton::Left), 74, 1));
+        let editor = &h.stoat.active_workspace().editors[editor_id];
+        assert_eq!(editor.scroll_row, 0, "grabbing the region does not jump");
+        assert_eq!(h.stoat.minimap_grab, 12);
+
+        // Cell row 2 points at line 20, so the grabbed row 12 follows it there.
+        h.stoat
+            .update(mouse_event(MouseEventKind::Drag(MouseButton::Left), 74, 2));
+        let editor = &h.stoat.active_workspace().editors[editor_id];
+        assert_eq!(editor.scroll_row, 8);
+        assert_eq!(
+            editor.scroll_glide,
+            ScrollGlide::Wheel,
+            "drag reports glide like wheel notches"
+        );
+    }
+
     #[test]
     fn single_band_click_scrubs_the_focused_editor_not_the_pane_under_it() {
         use stoat_config::MinimapMode;
@@ -18265,6 +19576,25 @@ mod tests {
         assert_eq!(h.head_offsets(), before, "undo restores both selections");
     }
 
+    #[test]
+    fn redo_restores_the_selections_after_the_edit() {
+        let mut h = Stoat::test();
+        let path = open_scratch_file(&mut h, "ab\nab\n");
+        h.type_keys("l");
+        h.type_keys("C");
+        h.type_keys("d");
+        let after = h.head_offsets();
+        h.type_keys("u");
+        h.type_keys("h");
+        h.type_keys("U");
+        assert_eq!(buffer_text(&h, &path), "a\na\n");
+        assert_eq!(
+            h.head_offsets(),
+            after,
+            "redo puts both cursors back where the edit left them"
+        );
+    }
+
     #[test]
     fn ctrl_s_splits_the_insert_session_into_two_undo_steps() {
         let mut h = Stoat::test();
@@ -18525,6 +19855,26 @@ mod tests {
         assert_eq!(focused_buffer_string(&h), "fn a() {\n\tx\n}\n");
     }
 
+    #[test]
+    fn move_lines_down_into_block_indents_line() {
+        let mut h = Stoat::test();
+        open_indent_buffer(&mut h, "a.rs", b"let y = 1;\nfn a() {\n\tx();\n}\n");
+        h.type_keys("alt-j");
+        assert_eq!(
+            focused_buffer_string(&h),
+            "fn a() {\n\tlet y = 1;\n\tx();\n}\n"
+        );
+    }
+
+    #[test]
+    fn move_lines_down_out_of_block_dedents_line() {
+        let mut h = Stoat::test();
+        open_indent_buffer(&mut h, "a.rs", b"fn a() {\n\tx();\n}\nlet y = 1;\n");
+        h.type_keys("j");
+        h.type_keys("alt-j");
+        assert_eq!(focused_buffer_string(&h), "fn a() {\n}\nx();\nlet y = 1;\n");
+    }
+
     #[test]
     fn open_above_inserts_blank_line_before_current_row() {
         let mut h = Stoat::test();
@@ -19036,6 +20386,76 @@ mod tests {
         assert!(h.stoat.editor_drag.is_some(), "drag state armed");
     }
 
+    #[test]
+    fn gutter_number_click_selects_the_whole_line() {
+        let mut h = Stoat::test();
+        let _ = open_scratch_file(&mut h, "abcdef\nghi\njk");
+        h.stoat.render();
+        let area = focused_editor_pane_area(&h);
+        assert!(focused_gutter_width(&h) > 1, "line numbers paint a gutter");
+
+        h.stoat.update(mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            area.x + 1,
+            area.y + 1,
+        ));
+        assert_eq!(focused_primary_offsets(&mut h), (7, 11));
+        assert!(h.stoat.editor_drag.is_none(), "a gutter click arms no drag");
+
+        h.stoat.update(mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            area.x + 1,
+            area.y + 2,
+        ));
+        assert_eq!(
+            focused_primary_offsets(&mut h),
+            (11, 13),
+            "the last line runs to the end of the buffer"
+        );
+    }
+
+    #[test]
+    fn gutter_fold_click_folds_then_unfolds_the_block() {
+        let mut h = Stoat::test();
+        let root = PathBuf::from("/gutter-fold");
+        let path = root.join("lib.rs");
+        h.fake_fs()
+            .insert_file(&path, b"fn one() {\n    1;\n    2;\n}\n");
+        h.stoat.active_workspace_mut().git_root = root;
+        action_handlers::dispatch(&mut h.stoat, &OpenFile { path });
+        h.stoat.drive_background();
+        h.stoat.render();
+        h.settle();
+        h.stoat.drive_background();
+        h.stoat.render();
+
+        let area = focused_editor_pane_area(&h);
+        let fold_col = area.x + focused_gutter_width(&h) - 1;
+        let line_count = |h: &mut crate::test_harness::TestHarness| {
+            action_handlers::focused_editor_mut(&mut h.stoat)
+                .expect("focused editor")
+                .display_map
+                .snapshot()
+                .line_count()
+        };
+        let unfolded = line_count(&mut h);
+
+        h.stoat.update(mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            fold_col,
+            area.y,
+        ));
+        assert!(line_count(&mut h) < unfolded, "the body folds");
+
+        h.stoat.render();
+        h.stoat.update(mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            fold_col,
+            area.y,
+        ));
+        assert_eq!(line_count(&mut h), unfolded, "a second click unfolds");
+    }
+
     /// Open a location picker over `count` candidates in a seeded file, each
     /// row's text naming its 1-based position, and return the file's path.
     fn open_location_picker(h: &mut crate::test_harness::TestHarness, count: usize) -> PathBuf {
@@ -1,17 +1,27 @@
-use crate::diff_map::DiffMap;
+use crate::{
+    buffer_search::Matches,
+    diff_map::DiffMap,
+    undo_tree::{UndoGroup, UndoTree},
+};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, ops::Range, sync::Arc};
+use std::{
+    collections::HashMap,
+    ops::Range,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use stoat_language::structural_diff::text_edits;
 pub use stoat_text::BufferId;
 use stoat_text::{
     patch::{Edit, Patch},
     Anchor, Bias, Dimensions, Fragment, IndentStyle, InsertionFragment, InsertionFragmentKey,
-    Locator, Point, Rope, Selection, SumTree, UndoMap, UndoOperation,
+    LineEnding, Locator, Point, Rope, Selection, SumTree, UndoMap, UndoOperation,
 };
 
 pub struct TextBuffer {
     pub snapshot: TextBufferSnapshot,
     pub dirty: bool,
-    /// Edit-frontier timestamp (the `edit_history` top) captured at the last
+    /// Edit-frontier timestamp (the newest applied edit) captured at the last
     /// clean point, whether a save or the seeded/pristine baseline. `None` is
     /// the pristine empty state. Reaching this frontier again via undo/redo
     /// reads as clean; [`Self::saved_text`] adds the content-based path that
@@ -27,32 +37,39 @@ pub struct TextBuffer {
     pub diff_map: Option<DiffMap>,
     next_timestamp: u64,
     buffer_id: BufferId,
-    /// Stack of edit groups eligible to be the target of the next `undo()`.
-    /// One group is one logical undo step -- a whole dispatched action or a
-    /// whole insert-mode session. Extended by `edit()`, popped by `undo()`.
-    /// Independent of [`Self::ops`], which records every edit and undo for replay.
-    edit_history: Vec<UndoGroup>,
-    /// Stack of edit groups undone and eligible for the next `redo()`. Pushed on
-    /// `undo()`, popped on `redo()`, cleared on any new `edit()`.
-    redo_history: Vec<UndoGroup>,
-    /// Count of leading [`Self::edit_history`] groups that seeded the buffer's
-    /// initial content rather than being user edits. [`Self::undo`] refuses to
-    /// pop below this floor, so undoing a freshly loaded file is a no-op instead
-    /// of reverting the whole load. Zero for a buffer created empty via
-    /// [`Self::new`], since it has no seed to protect.
+    /// Every edit group the buffer has been through, as a branching tree. One
+    /// group is one logical undo step -- a whole dispatched action or a whole
+    /// insert-mode session. `edit()` adds a child of the current node, `undo()`
+    /// and `redo()` walk to the parent and back, and an edit after an undo
+    /// starts a new branch rather than dropping the undone one. Independent of
+    /// [`Self::ops`], which records every edit and undo for replay.
+    undo_tree: UndoTree,
+    /// Count of leading [`Self::undo_tree`] nodes that seeded the buffer's
+    /// initial content rather than being user edits. [`Self::undo`] and
+    /// [`Self::restore`] refuse to go above the last of them, so undoing a
+    /// freshly loaded file is a no-op instead of reverting the whole load. Zero
+    /// for a buffer created empty via [`Self::new`], since it has no seed to
+    /// protect.
     undo_floor: usize,
     /// Whether [`Self::begin_group`] opened a group. While open, edits collapse
     /// into one logical undo step. The group is materialized lazily on its first
-    /// edit, so a group that never edits leaves `edit_history` untouched -- which
+    /// edit, so a group that never edits leaves `undo_tree` untouched -- which
     /// keeps a wrapped-but-non-editing action (including `undo`/`redo` itself)
     /// from stacking an empty step.
     open_group: bool,
-    /// Whether the open group has taken at least one edit and been pushed onto
-    /// `edit_history`, distinguishing appending to it from starting it.
+    /// Whether the open group has taken at least one edit and been added to
+    /// `undo_tree`, distinguishing appending to it from starting it.
     open_group_started: bool,
     /// Editor selections captured at [`Self::begin_group`], moved into the group
     /// when it materializes and restored when the group is undone.
     open_group_before: Vec<Selection<Anchor>>,
+    /// Window within which [`Self::edit_at`] folds an edit into the group of
+    /// the previous one, so a burst of edits from outside a dispatched action
+    /// undoes as one step. `None` makes every such edit its own step.
+    group_interval: Option<Duration>,
+    /// Group and time of the last [`Self::edit_at`], the burst a following
+    /// edit may join.
+    last_timed_edit: Option<(usize, Instant)>,
     /// Chronological log of user-driven mutations. Replaying this on a fresh
     /// [`TextBuffer`] reconstructs an identical fragment tree, anchors, and
     /// undo map, which is how workspace save/restore preserves selections and
@@ -66,32 +83,34 @@ pub struct TextBuffer {
     /// falling back to [`IndentStyle::default`] when the content carries no
     /// evidence. Cached rather than re-detected per edit.
     indent_style: IndentStyle,
+    /// Line terminator the buffer is written to disk with. Unless it is
+    /// [`LineEnding::Mixed`], the rope holds `\n` breaks only:
+    /// [`Self::owner_edit`] normalizes every inserted text, and the save path
+    /// re-expands line breaks through [`LineEnding::apply`]. A mixed buffer
+    /// holds its file's bytes as they are.
+    line_ending: LineEnding,
+    /// [`Self::line_ending`] at the last [`Self::mark_clean`]. A conversion
+    /// away from it marks the buffer modified even though the rope is
+    /// unchanged.
+    saved_line_ending: LineEnding,
+    /// Whether [`Self::edit`], [`Self::undo`] and [`Self::redo`] are refused.
+    /// Set on buffers whose content belongs to something other than the user,
+    /// such as command output, whose owner writes through [`Self::owner_edit`].
+    read_only: bool,
 }
 
 /// A single replayable mutation on a [`TextBuffer`]. Edits record the `(range,
-/// text)` inputs; undos target the top of the edit history the same way
-/// interactive `u` does; redos target the top of the redo history.
+/// text)` inputs; undos step to the parent in the undo tree the same way
+/// interactive `u` does; redos step back down the branch last undone out of.
+/// A restore jumps to the undo-tree state whose newest applied edit is `edit`
+/// (`None` for the empty buffer), named by timestamp because deterministic
+/// replay reassigns the same ones.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum BufferOp {
     Edit { old: Range<usize>, text: String },
     Undo,
     Redo,
-}
-
-/// A single logical undo step covering the edits made by one dispatched action
-/// or a whole insert-mode session, plus the editor selections to restore when
-/// the group is undone or redone.
-///
-/// Grouping is an in-session overlay on the flat [`BufferOp`] log, which still
-/// records each edit and undo individually, so it is not persisted -- a
-/// restored buffer replays every edit as its own singleton group.
-struct UndoGroup {
-    /// Edit timestamps in application order. Undo toggles them in reverse.
-    edits: Vec<u64>,
-    /// Editor selections captured when the group opened, restored on undo.
-    selections_before: Vec<Selection<Anchor>>,
-    /// Editor selections captured when the group sealed, restored on redo.
-    selections_after: Vec<Selection<Anchor>>,
+    Restore { edit: Option<u64> },
 }
 
 /// Serializable buffer state for persistence. Holds the op log plus the
@@ -112,6 +131,14 @@ pub struct BufferHistory {
     /// file is next reopened via [`TextBuffer::with_text`].
     #[serde(default)]
     pub undo_floor: usize,
+    /// Persisted [`TextBuffer::line_ending`]. `#[serde(default)]` reads an
+    /// older state file as `Unix`.
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    /// Persisted [`TextBuffer::saved_line_ending`], so a restored buffer with
+    /// an unsaved conversion stays modified.
+    #[serde(default)]
+    pub saved_line_ending: LineEnding,
 }
 
 /// Stable identifier for a [`Checkpoint`] within a single [`TextBuffer`].
@@ -175,25 +202,33 @@ impl TextBuffer {
             diff_map: None,
             next_timestamp: 1,
             buffer_id,
-            edit_history: Vec::new(),
-            redo_history: Vec::new(),
+            undo_tree: UndoTree::default(),
             undo_floor: 0,
             open_group: false,
             open_group_started: false,
             open_group_before: Vec::new(),
+            group_interval: None,
+            last_timed_edit: None,
             ops: Vec::new(),
             next_checkpoint_id: 0,
             checkpoints: Vec::new(),
             indent_style: IndentStyle::default(),
+            line_ending: LineEnding::default(),
+            saved_line_ending: LineEnding::default(),
+            read_only: false,
         }
     }
 
+    /// Create a buffer seeded with `text`, detecting its line ending and
+    /// storing it normalized to `\n`.
     pub fn with_text(buffer_id: BufferId, text: &str) -> Self {
         let mut buf = Self::new(buffer_id);
+        buf.line_ending = LineEnding::detect(text);
+        buf.saved_line_ending = buf.line_ending;
         if !text.is_empty() {
             buf.edit(0..0, text);
             buf.mark_clean();
-            buf.undo_floor = buf.edit_history.len();
+            buf.undo_floor = buf.undo_tree.len();
         }
         buf.detect_indent_style();
         buf
@@ -210,8 +245,99 @@ impl TextBuffer {
         self.indent_style = stoat_text::detect_indent_style(self.rope()).unwrap_or_default();
     }
 
+    /// The line terminator this buffer is saved with.
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Convert the buffer to `line_ending` for its next save. Only the saved
+    /// bytes change, so the rope and undo history are untouched, but the
+    /// buffer reads as modified until saved or converted back. Returns `false`
+    /// when the buffer already uses `line_ending`.
+    ///
+    /// Converting a [`LineEnding::Mixed`] buffer also drops the `\r` of each of
+    /// its `\r\n` breaks from the rope, as one undo step, so every line is
+    /// saved with the new ending.
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) -> bool {
+        if self.line_ending == line_ending {
+            return false;
+        }
+        if self.line_ending == LineEnding::Mixed {
+            let text = self.snapshot.visible_text.to_string();
+            let returns: Vec<usize> = text.match_indices("\r\n").map(|(i, _)| i).collect();
+            let own_group = !self.open_group;
+            if own_group {
+                self.begin_group(Vec::new());
+            }
+            for offset in returns.into_iter().rev() {
+                self.owner_edit(offset..offset + 1, "");
+            }
+            if own_group {
+                self.seal_group(Vec::new());
+            }
+        }
+        self.line_ending = line_ending;
+        self.recompute_dirty();
+        true
+    }
+
+    /// Take `line_ending` as the one the file on disk already has, as on a
+    /// reload, without touching the rope or marking the buffer modified.
+    /// Text edited in afterwards is normalized for it.
+    pub(crate) fn adopt_line_ending(&mut self, line_ending: LineEnding) {
+        self.line_ending = line_ending;
+        self.saved_line_ending = line_ending;
+        self.recompute_dirty();
+    }
+
+    /// Whether user edits to this buffer are refused.
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub(crate) fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Replace `range` with `text`. A no-op on a [`Self::read_only`] buffer.
     pub fn edit(&mut self, range: Range<usize>, text: &str) {
-        self.redo_history.clear();
+        if self.read_only {
+            return;
+        }
+        self.owner_edit(range, text);
+    }
+
+    /// Rewrite the text to `new_text` through the smallest edits
+    /// [`TextBufferSnapshot::diff`] finds, so anchors in the unchanged bytes,
+    /// and the selections and highlights built on them, keep their place. The
+    /// edits undo as one step. Returns whether anything changed.
+    pub fn set_text(&mut self, new_text: &str) -> bool {
+        let new_text = self.line_ending.normalize(new_text);
+        let edits = self.snapshot.diff(&new_text);
+        if edits.is_empty() {
+            return false;
+        }
+        let own_group = !self.open_group;
+        if own_group {
+            self.begin_group(Vec::new());
+        }
+        for edit in edits.into_iter().rev() {
+            self.edit(edit.old, &new_text[edit.new]);
+        }
+        if own_group {
+            self.seal_group(Vec::new());
+        }
+        true
+    }
+
+    /// Replace `range` with `text` even when the buffer is read-only, for the
+    /// code that owns a read-only buffer's content. `text` is normalized for
+    /// the buffer's [`LineEnding`], so no edit path can put a `\r\n` break in
+    /// the rope of a buffer that is not [`LineEnding::Mixed`].
+    pub(crate) fn owner_edit(&mut self, range: Range<usize>, text: &str) {
+        let text = &*self.line_ending.normalize(text);
+        let row = self.snapshot.visible_text.offset_to_point(range.start).row;
+        let deleted = range.len();
         self.ops.push(BufferOp::Edit {
             old: range.clone(),
             text: text.to_owned(),
@@ -448,33 +574,37 @@ impl TextBuffer {
         self.snapshot.fragments = new_fragments;
         self.snapshot.insertions = all_insertions;
         self.snapshot.version = timestamp;
-        self.record_edit(timestamp);
+        self.record_edit(timestamp, row, text.len(), deleted);
         self.recompute_dirty();
     }
 
     /// Record `timestamp` in the open group, or as its own singleton group when
     /// no group is open (the from_history replay and any unwrapped edit).
-    fn record_edit(&mut self, timestamp: u64) {
-        if self.open_group {
-            if self.open_group_started
-                && let Some(group) = self.edit_history.last_mut()
-            {
-                group.edits.push(timestamp);
-                return;
-            }
-            self.edit_history.push(UndoGroup {
-                edits: vec![timestamp],
-                selections_before: std::mem::take(&mut self.open_group_before),
-                selections_after: Vec::new(),
-            });
+    /// `row`, `inserted` and `deleted` describe the edit for the undo tree view.
+    fn record_edit(&mut self, timestamp: u64, row: u32, inserted: usize, deleted: usize) {
+        if self.open_group
+            && self.open_group_started
+            && let Some(group) = self.undo_tree.current_group_mut()
+        {
+            group.edits.push(timestamp);
+            group.inserted += inserted;
+            group.deleted += deleted;
+            return;
+        }
+        let selections_before = if self.open_group {
             self.open_group_started = true;
+            std::mem::take(&mut self.open_group_before)
         } else {
-            self.edit_history.push(UndoGroup {
-                edits: vec![timestamp],
-                selections_before: Vec::new(),
-                selections_after: Vec::new(),
-            });
-        }
+            Vec::new()
+        };
+        self.undo_tree.push(UndoGroup {
+            edits: vec![timestamp],
+            selections_before,
+            selections_after: Vec::new(),
+            row,
+            inserted,
+            deleted,
+        });
     }
 
     /// Open an undo group so the following [`Self::edit`] calls collapse into one
@@ -503,19 +633,71 @@ impl TextBuffer {
         self.open_group_before = Vec::new();
         if self.open_group_started {
             self.open_group_started = false;
-            if let Some(group) = self.edit_history.last_mut() {
+            if let Some(group) = self.undo_tree.current_group_mut() {
                 group.selections_after = selections_after;
             }
         }
     }
 
+    /// Id of the undo group holding the newest applied edit, `None` on the
+    /// empty state. Pass it to [`Self::edit_in_group`] to fold a later related
+    /// edit into the same step.
+    pub fn last_group(&self) -> Option<usize> {
+        self.undo_tree.current()
+    }
+
+    /// Replace `range` with `text` as part of undo group `group`, so both undo
+    /// as one step. Joins only while `group` is still the newest state, no
+    /// group is open and `group` is not seeded content; otherwise the edit is
+    /// its own step. Returns whether it joined.
+    pub fn edit_in_group(&mut self, group: usize, range: Range<usize>, text: &str) -> bool {
+        let joins = !self.read_only
+            && !self.open_group
+            && self.undo_tree.current() == Some(group)
+            && self.above_floor(Some(group));
+        if joins {
+            self.open_group = true;
+            self.open_group_started = true;
+        }
+        self.edit(range, text);
+        if joins {
+            self.open_group = false;
+            self.open_group_started = false;
+        }
+        joins
+    }
+
+    /// Set the window [`Self::edit_at`] groups bursts of edits within.
+    pub fn set_group_interval(&mut self, interval: Option<Duration>) {
+        self.group_interval = interval;
+    }
+
+    /// Replace `range` with `text` at time `now`. With a group interval set,
+    /// an edit within it of the previous `edit_at` joins that edit's group
+    /// through [`Self::edit_in_group`], so typing that arrives outside a
+    /// dispatched action still undoes as one step.
+    pub fn edit_at(&mut self, range: Range<usize>, text: &str, now: Instant) {
+        let burst =
+            self.group_interval
+                .zip(self.last_timed_edit)
+                .and_then(|(interval, (group, at))| {
+                    (now.saturating_duration_since(at) <= interval).then_some(group)
+                });
+        match burst {
+            Some(group) => {
+                self.edit_in_group(group, range, text);
+            },
+            None => self.edit(range, text),
+        }
+        self.last_timed_edit = self.undo_tree.current().map(|group| (group, now));
+    }
+
     /// Timestamp of the most recent edit, skipping a transiently empty open
     /// group. `None` when nothing has been edited.
     fn frontier(&self) -> Option<u64> {
-        self.edit_history
-            .iter()
-            .rev()
-            .find_map(|group| group.edits.last())
+        self.undo_tree
+            .ancestors(self.undo_tree.current())
+            .find_map(|node| self.undo_tree.group(node)?.edits.last())
             .copied()
     }
 
@@ -526,6 +708,7 @@ impl TextBuffer {
     pub(crate) fn mark_clean(&mut self) {
         self.saved_marker = self.frontier();
         self.saved_text = Some(self.snapshot.visible_text.clone());
+        self.saved_line_ending = self.line_ending;
         self.dirty = false;
     }
 
@@ -533,8 +716,10 @@ impl TextBuffer {
     /// the visible text differs from the saved bytes. The content check clears
     /// dirty for a round-trip edit -- type a char then delete it, or undo to a
     /// diverged frontier -- that the frontier comparison alone reports modified.
+    /// A pending line-ending conversion is modified regardless of content.
     fn recompute_dirty(&mut self) {
-        self.dirty = self.frontier() != self.saved_marker && !self.matches_saved_text();
+        self.dirty = (self.frontier() != self.saved_marker && !self.matches_saved_text())
+            || self.line_ending != self.saved_line_ending;
     }
 
     /// Whether the visible text is byte-identical to the content captured at the
@@ -554,41 +739,107 @@ impl TextBuffer {
                 .eq(current.chunks().flat_map(str::bytes))
     }
 
-    /// Undo the top edit group, reverting all of its edits as one step. Returns
-    /// the editor selections captured when the group opened, to restore the
-    /// cursor to edit time, or `None` when there is nothing to undo.
+    /// Undo the current edit group, reverting all of its edits as one step and
+    /// moving to the state it was made on. Returns the editor selections
+    /// captured when the group opened, to restore the cursor to edit time, or
+    /// `None` when there is nothing to undo.
     ///
     /// The content a buffer was loaded or seeded with is not an undo target, so
     /// undoing a freshly opened file with no user edits returns `None` and
     /// leaves the file intact rather than emptying it.
     pub fn undo(&mut self) -> Option<Vec<Selection<Anchor>>> {
-        if self.edit_history.len() <= self.undo_floor {
+        if self.read_only || !self.above_floor(self.undo_tree.current()) {
             return None;
         }
-        let group = self.edit_history.pop()?;
-        for &edit_timestamp in group.edits.iter().rev() {
-            self.apply_undo_toggle(edit_timestamp, BufferOp::Undo);
+        let node = self.undo_tree.undo()?;
+        let group = self.undo_tree.group(node)?;
+        let (edits, selections) = (group.edits.clone(), group.selections_before.clone());
+        for &edit_timestamp in edits.iter().rev() {
+            self.ops.push(BufferOp::Undo);
+            self.apply_undo_toggle(edit_timestamp);
         }
-        let selections = group.selections_before.clone();
-        self.redo_history.push(group);
         self.recompute_dirty();
         Some(selections)
     }
 
-    /// Redo the top undone group, reapplying all of its edits as one step.
-    /// Returns the editor selections captured when the group sealed, or `None`
-    /// when there is nothing to redo.
+    /// Redo the group the last undo stepped out of (or the newest child of the
+    /// current state), reapplying all of its edits as one step. Returns the
+    /// editor selections captured when the group sealed, or `None` when there
+    /// is nothing to redo.
     pub fn redo(&mut self) -> Option<Vec<Selection<Anchor>>> {
-        let group = self.redo_history.pop()?;
-        for &edit_timestamp in &group.edits {
-            self.apply_undo_toggle(edit_timestamp, BufferOp::Redo);
+        if self.read_only {
+            return None;
+        }
+        let node = self.undo_tree.redo()?;
+        let group = self.undo_tree.group(node)?;
+        let (edits, selections) = (group.edits.clone(), group.selections_after.clone());
+        for &edit_timestamp in &edits {
+            self.ops.push(BufferOp::Redo);
+            self.apply_undo_toggle(edit_timestamp);
         }
-        let selections = group.selections_after.clone();
-        self.edit_history.push(group);
         self.recompute_dirty();
         Some(selections)
     }
 
+    /// Move to any state in the undo tree: `Some(node)` for the state right
+    /// after that group, `None` for the empty buffer. Undoes back to the
+    /// nearest common ancestor and redoes down the target's branch, so redo
+    /// afterwards continues along that branch. Returns the selections captured
+    /// when the target group sealed, or `None` when the target is the current
+    /// state, does not exist, or sits behind the seeded content.
+    pub fn restore(&mut self, target: Option<usize>) -> Option<Vec<Selection<Anchor>>> {
+        if self.read_only
+            || target == self.undo_tree.current()
+            || target.is_some_and(|node| node >= self.undo_tree.len())
+            || (self.undo_floor > 0 && target.is_none_or(|node| node + 1 < self.undo_floor))
+        {
+            return None;
+        }
+        let edit = match target {
+            Some(node) => Some(*self.undo_tree.group(node)?.edits.last()?),
+            None => None,
+        };
+        self.ops.push(BufferOp::Restore { edit });
+        let (up, down) = self.undo_tree.path_to(target);
+        for _ in up {
+            let node = self.undo_tree.undo()?;
+            let edits = self.undo_tree.group(node)?.edits.clone();
+            for &edit_timestamp in edits.iter().rev() {
+                self.apply_undo_toggle(edit_timestamp);
+            }
+        }
+        for node in down {
+            self.undo_tree.descend(node);
+            let edits = self.undo_tree.group(node)?.edits.clone();
+            for &edit_timestamp in &edits {
+                self.apply_undo_toggle(edit_timestamp);
+            }
+        }
+        self.recompute_dirty();
+        Some(
+            target
+                .and_then(|node| self.undo_tree.group(node))
+                .map(|group| group.selections_after.clone())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// The buffer's branching undo history.
+    pub(crate) fn undo_tree(&self) -> &UndoTree {
+        &self.undo_tree
+    }
+
+    /// Count of leading undo-tree nodes holding the seeded content.
+    pub(crate) fn undo_floor(&self) -> usize {
+        self.undo_floor
+    }
+
+    /// Whether `node` is a user edit rather than the seeded content (or the
+    /// empty state beneath it), so undoing it keeps the seed intact.
+    fn above_floor(&self, node: Option<usize>) -> bool {
+        node.is_some_and(|node| node >= self.undo_floor)
+    }
+
     /// Place a named marker at the current op-log position. The returned
     /// [`CheckpointId`] is the navigation target consumed by checkpoint
     /// navigation actions; pass [`None`] for `label` for unlabeled markers
@@ -608,8 +859,7 @@ impl TextBuffer {
         &self.checkpoints
     }
 
-    fn apply_undo_toggle(&mut self, edit_timestamp: u64, op: BufferOp) {
-        self.ops.push(op);
+    fn apply_undo_toggle(&mut self, edit_timestamp: u64) {
         let undo_timestamp = self.next_timestamp;
         self.next_timestamp += 1;
 
@@ -710,6 +960,8 @@ impl TextBuffer {
             ops: self.ops.clone(),
             saved_marker: self.saved_marker,
             undo_floor: self.undo_floor,
+            line_ending: self.line_ending,
+            saved_line_ending: self.saved_line_ending,
         }
     }
 
@@ -727,10 +979,22 @@ impl TextBuffer {
                 BufferOp::Redo => {
                     buf.redo();
                 },
+                BufferOp::Restore { edit } => {
+                    let target = match edit {
+                        Some(edit) => match buf.undo_tree.node_ending_with(*edit) {
+                            Some(node) => Some(node),
+                            None => continue,
+                        },
+                        None => None,
+                    };
+                    buf.restore(target);
+                },
             }
         }
         buf.saved_marker = history.saved_marker;
         buf.undo_floor = history.undo_floor;
+        buf.line_ending = history.line_ending;
+        buf.saved_line_ending = history.saved_line_ending;
         buf.recompute_dirty();
         buf.detect_indent_style();
         buf
@@ -833,6 +1097,11 @@ impl TextBufferSnapshot {
         results
     }
 
+    /// Byte offset of `anchor` in the visible text. Undo and redo only flip
+    /// fragment visibility through the undo map, never discarding fragments,
+    /// so an anchor keeps resolving across them: one inside text that is
+    /// currently undone or deleted collapses to where that text would start,
+    /// and returns to its own spot once the text is visible again.
     pub fn resolve_anchor(&self, anchor: &Anchor) -> usize {
         if anchor.is_min() {
             return 0;
@@ -897,6 +1166,20 @@ impl TextBufferSnapshot {
         anchors.iter().map(|a| self.resolve_anchor(a)).collect()
     }
 
+    /// Anchored ranges of the matches of `regex` starting at or after `from`,
+    /// in ascending order. The text is searched a window of lines at a time
+    /// through [`Matches`], never copied out whole. To continue a search later,
+    /// possibly on a newer snapshot, pass the resolved end of the last range
+    /// as `from`.
+    pub fn search<'a>(
+        &'a self,
+        regex: &'a regex::Regex,
+        from: usize,
+    ) -> impl Iterator<Item = Range<Anchor>> + 'a {
+        Matches::new(&self.visible_text, regex, from)
+            .map(|r| self.anchor_at(r.start, Bias::Right)..self.anchor_at(r.end, Bias::Left))
+    }
+
     pub fn point_for_anchor(&self, anchor: &Anchor) -> Point {
         self.visible_text
             .offset_to_point(self.resolve_anchor(anchor))
@@ -923,6 +1206,14 @@ impl TextBufferSnapshot {
         fragment.is_some_and(|f| f.visible)
     }
 
+    /// The byte edits turning this text into `new_text`, in ascending order:
+    /// each changed run of lines narrowed to the bytes that differ. The
+    /// counterpart of [`Self::edits_since`] for text that did not come from
+    /// this buffer's history, such as a formatter's output or the file on disk.
+    pub fn diff(&self, new_text: &str) -> Vec<Edit<usize>> {
+        text_edits(&self.visible_text.to_string(), new_text)
+    }
+
     pub fn edits_since(&self, since_version: u64) -> Patch<usize> {
         if since_version >= self.version {
             return Patch::empty();
@@ -1005,8 +1296,13 @@ pub type SharedBuffer = Arc<std::sync::RwLock<TextBuffer>>;
 #[cfg(test)]
 mod tests {
     use super::TextBuffer;
-    use std::ops::Range;
-    use stoat_text::{Bias, BufferId, IndentStyle, Point, Selection, SelectionGoal};
+    use std::{
+        ops::Range,
+        time::{Duration, Instant},
+    };
+    use stoat_text::{
+        Anchor, Bias, BufferId, IndentStyle, LineEnding, Point, Selection, SelectionGoal,
+    };
 
     fn buf(content: &str) -> TextBuffer {
         TextBuffer::with_text(BufferId::new(0), content)
@@ -1056,6 +1352,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn crlf_load_normalizes_the_rope() {
+        let mut b = buf("one\r\ntwo\r\n");
+        assert_eq!(b.line_ending(), LineEnding::Windows);
+        assert_eq!(b.rope().to_string(), "one\ntwo\n");
+        assert!(!b.dirty);
+
+        b.edit(3..3, "\r\nthree");
+        assert_eq!(b.rope().to_string(), "one\nthree\ntwo\n");
+    }
+
+    #[test]
+    fn mixed_load_keeps_bytes_until_converted() {
+        let mut b = buf("one\r\ntwo\nthree\r\n");
+        assert_eq!(b.line_ending(), LineEnding::Mixed);
+        assert_eq!(b.rope().to_string(), "one\r\ntwo\nthree\r\n");
+        assert!(!b.dirty);
+
+        assert!(b.set_line_ending(LineEnding::Unix));
+        assert_eq!(b.rope().to_string(), "one\ntwo\nthree\n");
+        assert!(b.dirty);
+        assert!(b.undo().is_some(), "the conversion is one undo step");
+        assert_eq!(b.rope().to_string(), "one\r\ntwo\nthree\r\n");
+    }
+
+    #[test]
+    fn line_ending_conversion_dirties_until_saved_or_reverted() {
+        let mut b = buf("one\r\n");
+        assert!(b.set_line_ending(LineEnding::Unix));
+        assert!(b.dirty);
+        assert!(!b.set_line_ending(LineEnding::Unix));
+        assert!(b.set_line_ending(LineEnding::Windows));
+        assert!(!b.dirty, "converting back restores the saved bytes");
+
+        b.set_line_ending(LineEnding::Unix);
+        let restored = TextBuffer::from_history(BufferId::new(0), &b.history());
+        assert_eq!(restored.line_ending(), LineEnding::Unix);
+        assert!(restored.dirty, "the unsaved conversion survives a restore");
+    }
+
     #[test]
     fn anchor_insert_before() {
         let mut b = buf("hello");
@@ -1430,6 +1766,61 @@ mod tests {
         assert_eq!(b.resolve_anchor(&a), 8);
     }
 
+    #[test]
+    fn anchors_survive_edit_undo_redo() {
+        let mut b = buf("hello world");
+        let after = b.anchor_at(6, Bias::Left);
+        b.edit(5..5, ", big");
+        let inside = b.anchor_at(8, Bias::Right);
+        assert_eq!(b.resolve_anchor(&after), 11);
+
+        b.undo();
+        assert_eq!(b.snapshot.visible_text.to_string(), "hello world");
+        assert_eq!(b.resolve_anchor(&after), 6);
+        assert!(!b.snapshot.is_anchor_valid(&inside));
+        assert_eq!(
+            b.resolve_anchor(&inside),
+            5,
+            "an anchor in undone text sits at its insertion point"
+        );
+
+        b.redo();
+        assert_eq!(b.resolve_anchor(&after), 11);
+        assert!(b.snapshot.is_anchor_valid(&inside));
+        assert_eq!(b.resolve_anchor(&inside), 8);
+    }
+
+    #[test]
+    fn anchor_in_deleted_text_follows_undo_and_redo() {
+        let mut b = buf("hello world");
+        let a = b.anchor_at(8, Bias::Right);
+        b.edit(5..11, "");
+        assert_eq!(b.resolve_anchor(&a), 5);
+        b.undo();
+        assert_eq!(b.resolve_anchor(&a), 8);
+        b.redo();
+        assert!(!b.snapshot.is_anchor_valid(&a));
+        assert_eq!(b.resolve_anchor(&a), 5);
+        b.undo();
+        assert_eq!(b.resolve_anchor(&a), 8);
+    }
+
+    #[test]
+    fn anchors_survive_grouped_undo_redo() {
+        let mut b = buf("abc");
+        let c = b.anchor_at(2, Bias::Right);
+        b.begin_group(Vec::new());
+        b.edit(0..0, "xx");
+        b.edit(3..4, "");
+        b.seal_group(Vec::new());
+        assert_eq!(b.snapshot.visible_text.to_string(), "xxac");
+        assert_eq!(b.resolve_anchor(&c), 3);
+        b.undo();
+        assert_eq!(b.resolve_anchor(&c), 2);
+        b.redo();
+        assert_eq!(b.resolve_anchor(&c), 3);
+    }
+
     #[test]
     fn redo_after_undo_restores_edit() {
         let mut b = buf("hello");
@@ -1479,6 +1870,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn edits_within_the_group_interval_undo_together() {
+        let mut b = buf("");
+        b.set_group_interval(Some(Duration::from_millis(300)));
+        let t0 = Instant::now();
+        b.edit_at(0..0, "a", t0);
+        b.edit_at(1..1, "b", t0 + Duration::from_millis(200));
+        b.edit_at(2..2, "c", t0 + Duration::from_millis(400));
+        b.edit_at(3..3, "d", t0 + Duration::from_secs(1));
+        assert_eq!(b.snapshot.visible_text.to_string(), "abcd");
+        assert!(b.undo().is_some());
+        assert_eq!(
+            b.snapshot.visible_text.to_string(),
+            "abc",
+            "a pause ends the burst"
+        );
+        assert!(b.undo().is_some());
+        assert_eq!(
+            b.snapshot.visible_text.to_string(),
+            "",
+            "each edit chains off the previous one's time"
+        );
+    }
+
+    #[test]
+    fn edit_in_group_joins_only_the_newest_group() {
+        let mut b = buf("");
+        b.edit(0..0, "a");
+        let group = b.last_group().expect("a group");
+        assert!(b.edit_in_group(group, 1..1, "b"));
+        assert_eq!(b.last_group(), Some(group));
+        b.edit(2..2, "c");
+        assert!(
+            !b.edit_in_group(group, 3..3, "d"),
+            "another step came after it"
+        );
+        assert_eq!(b.snapshot.visible_text.to_string(), "abcd");
+        assert!(b.undo().is_some());
+        assert!(b.undo().is_some());
+        assert_eq!(b.snapshot.visible_text.to_string(), "ab");
+        assert!(b.undo().is_some());
+        assert_eq!(b.snapshot.visible_text.to_string(), "");
+    }
+
     #[test]
     fn empty_group_leaves_no_undo_step() {
         let mut b = buf("hi");
@@ -1543,6 +1978,58 @@ mod tests {
         assert_eq!(b.snapshot.visible_text.to_string(), "aX");
     }
 
+    #[test]
+    fn edit_after_undo_keeps_the_undone_branch_restorable() {
+        let mut b = buf("a");
+        b.edit(1..1, "b");
+        b.undo();
+        b.edit(1..1, "X");
+        assert_eq!(b.undo_tree().len(), 3);
+
+        assert!(b.restore(Some(1)).is_some());
+        assert_eq!(b.snapshot.visible_text.to_string(), "ab");
+        assert!(b.undo().is_some());
+        assert_eq!(b.snapshot.visible_text.to_string(), "a");
+        assert!(b.redo().is_some());
+        assert_eq!(
+            b.snapshot.visible_text.to_string(),
+            "ab",
+            "redo follows the restored branch"
+        );
+
+        assert!(b.restore(Some(2)).is_some());
+        assert_eq!(b.snapshot.visible_text.to_string(), "aX");
+        assert!(b.restore(Some(2)).is_none(), "already there");
+    }
+
+    #[test]
+    fn restore_refuses_to_revert_the_seed() {
+        let mut b = buf("hello");
+        b.edit(5..5, "!");
+        assert!(b.restore(None).is_none());
+        assert!(b.restore(Some(0)).is_some());
+        assert_eq!(b.snapshot.visible_text.to_string(), "hello");
+        assert!(!b.dirty);
+    }
+
+    #[test]
+    fn restore_survives_a_history_round_trip() {
+        let mut b = buf("a");
+        b.begin_group(Vec::new());
+        b.edit(1..1, "b");
+        b.edit(2..2, "c");
+        b.seal_group(Vec::new());
+        b.undo();
+        b.edit(1..1, "X");
+        b.restore(Some(1));
+        assert_eq!(b.snapshot.visible_text.to_string(), "abc");
+
+        let mut restored = TextBuffer::from_history(BufferId::new(0), &b.history());
+        assert_eq!(restored.snapshot.visible_text.to_string(), "abc");
+        assert!(restored.undo().is_some());
+        assert_eq!(restored.snapshot.visible_text.to_string(), "ab");
+    }
+
     #[test]
     fn undo_back_to_saved_clears_dirty() {
         let mut b = buf("hello");
@@ -1691,4 +2178,56 @@ mod tests {
         assert_eq!(cps[0].label.as_deref(), Some("before refactor"));
         assert_eq!(cps[1].label, None);
     }
+
+    #[test]
+    fn search_matches_follow_later_edits() {
+        let mut b = buf("let x = 1;
+let y = x;
+");
+        let regex = regex::Regex::new(r"\bx\b").expect("valid");
+        let snap = b.snapshot.clone();
+        let found: Vec<Range<Anchor>> = snap.search(&regex, 0).collect();
+        let resolved = |b: &TextBuffer, r: &Range<Anchor>| {
+            b.resolve_anchor(&r.start)..b.resolve_anchor(&r.end)
+        };
+        assert_eq!(
+            found.iter().map(|r| resolved(&b, r)).collect::<Vec<_>>(),
+            vec![4..5, 19..20]
+        );
+        assert_eq!(
+            snap.search(&regex, 5).count(),
+            1,
+            "search continues from an offset"
+        );
+
+        b.edit(0..0, "// header\n");
+        assert_eq!(resolved(&b, &found[0]), 14..15);
+        assert_eq!(resolved(&b, &found[1]), 29..30);
+    }
+
+    #[test]
+    fn set_text_edits_only_the_changed_bytes() {
+        let mut b = buf("fn a() {\n    one();\n}\n\nfn b() {}\n");
+        let before = b.anchor_at(b.rope().to_string().find("fn b").unwrap(), Bias::Left);
+        let group = b.last_group();
+
+        assert!(b.set_text("fn a() {\n    three();\n}\n\nfn b() {}\n"));
+        assert_eq!(
+            b.rope().to_string(),
+            "fn a() {\n    three();\n}\n\nfn b() {}\n"
+        );
+        assert_eq!(
+            b.resolve_anchor(&before),
+            25,
+            "text past the edit keeps its anchor"
+        );
+        assert!(!b.set_text("fn a() {\n    three();\n}\n\nfn b() {}\n"));
+
+        b.undo();
+        assert_eq!(
+            b.rope().to_string(),
+            "fn a() {\n    one();\n}\n\nfn b() {}\n"
+        );
+        assert_eq!(b.last_group(), group, "the rewrite undoes as one step");
+    }
 }
@@ -1,18 +1,20 @@
 use crate::{
     buffer::{BufferHistory, BufferId, SharedBuffer, TextBuffer},
+    completion::word::BufferWords,
     display_map::{HighlightStyleInterner, SemanticTokenHighlight},
     lsp::LspSymbolKind,
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     ops::Range,
     path::{Path, PathBuf},
     sync::{Arc, RwLock},
     time::SystemTime,
 };
 use stoat_language::{
-    drop_syntax_in_background, structural_diff::DiffResult, Language, SyntaxMap, SyntaxState,
+    drop_syntax_in_background, structural_diff::DiffResult, Language, SyntaxMap, SyntaxSnapshot,
+    SyntaxState,
 };
 use stoat_text::Anchor;
 
@@ -68,6 +70,10 @@ struct BufferEntry {
     /// callers migrate to capture merging. The `parse_buffer_step`
     /// pipeline writes to both fields on every reparse.
     syntax_map: Option<SyntaxMap>,
+    /// The layer set of the last installed [`Self::syntax_map`]. It stays
+    /// while the map itself is taken out for a background reparse, so readers
+    /// keep the previous parse instead of seeing none until the job lands.
+    syntax_snapshot: Option<SyntaxSnapshot>,
     /// Tree-sitter highlight tokens retained across editor lifetimes. The parse
     /// pipeline stores the same `(tokens, interner)` it installs onto editors,
     /// so a fresh editor built for an already-parsed buffer can be seeded and
@@ -86,6 +92,11 @@ struct BufferEntry {
     /// separate from [`Self::lsp_tokens`] so cursor-aware features can query the
     /// kind under an offset without the highlight styling. Start-anchor sorted.
     lsp_symbol_kinds: Option<LspSymbolKindIndex>,
+    /// Identifier index built by the parse pipeline for word completion.
+    /// Unlike the highlight state it survives
+    /// [`BufferRegistry::evict_hidden_highlights`], so a hidden buffer's
+    /// words stay offered until it is reparsed or closed.
+    words: Option<Arc<BufferWords>>,
     diff: Option<CachedDiff>,
     /// Marks this buffer as a transient preview surface (e.g. the
     /// file finder's preview pane). The parse pipeline pulls these
@@ -109,6 +120,9 @@ struct BufferEntry {
     /// [`BufferRegistry::mark_shown`]. It orders eviction of hidden buffers'
     /// highlight state, dropping the lowest values first.
     last_shown: u64,
+    /// Pinned buffers sort first in the buffer finder and are kept open by
+    /// `CloseOtherBuffers` and group switches.
+    pinned: bool,
 }
 
 pub(crate) struct BufferRegistry {
@@ -118,6 +132,9 @@ pub(crate) struct BufferRegistry {
     /// Monotonic counter stamped onto [`BufferEntry::last_shown`] by
     /// [`Self::mark_shown`] to order highlight eviction by recency.
     shown_counter: u64,
+    /// Named buffer groups, each an ordered list of file paths. A group
+    /// outlives its buffers, so switching to it reopens closed members.
+    groups: BTreeMap<String, Vec<PathBuf>>,
 }
 
 impl BufferRegistry {
@@ -127,6 +144,7 @@ impl BufferRegistry {
             path_to_id: HashMap::new(),
             next_id: 1,
             shown_counter: 0,
+            groups: BTreeMap::new(),
         }
     }
 
@@ -214,14 +232,17 @@ impl BufferRegistry {
                 language: None,
                 syntax: None,
                 syntax_map: None,
+                syntax_snapshot: None,
                 tokens: None,
                 lsp_tokens: None,
                 lsp_symbol_kinds: None,
+                words: None,
                 diff: None,
                 preview,
                 disk_mtime: None,
                 auto_reload: AutoReloadMode::Off,
                 last_shown: 0,
+                pinned: false,
             },
         );
         (id, buffer)
@@ -247,14 +268,17 @@ impl BufferRegistry {
                 language: None,
                 syntax: None,
                 syntax_map: None,
+                syntax_snapshot: None,
                 tokens: None,
                 lsp_tokens: None,
                 lsp_symbol_kinds: None,
+                words: None,
                 diff: None,
                 preview: false,
                 disk_mtime: None,
                 auto_reload: AutoReloadMode::Off,
                 last_shown: 0,
+                pinned: false,
             },
         );
         (id, buffer)
@@ -344,13 +368,79 @@ impl BufferRegistry {
             .collect()
     }
 
-    /// Returns paths of currently-open path-bound buffers in lexicographic
-    /// order. Scratch buffers (with no path) are skipped. The deterministic
-    /// ordering matches what the file finder shows for the All scope.
+    /// Returns paths of currently-open path-bound buffers, pinned buffers
+    /// first and each part in lexicographic order. Scratch buffers (with no
+    /// path) are skipped. The deterministic ordering matches what the file
+    /// finder shows for the All scope.
     pub(crate) fn open_paths(&self) -> Vec<PathBuf> {
-        let mut paths: Vec<PathBuf> = self.path_to_id.keys().cloned().collect();
+        let mut paths: Vec<(bool, &PathBuf)> = self
+            .path_to_id
+            .iter()
+            .map(|(path, id)| (!self.is_pinned(*id), path))
+            .collect();
         paths.sort();
-        paths
+        paths.into_iter().map(|(_, path)| path.clone()).collect()
+    }
+
+    /// Number of pinned path-bound buffers, which lead [`Self::open_paths`].
+    pub(crate) fn pinned_count(&self) -> usize {
+        self.path_to_id
+            .values()
+            .filter(|id| self.is_pinned(**id))
+            .count()
+    }
+
+    pub(crate) fn is_pinned(&self, id: BufferId) -> bool {
+        self.buffers.get(&id).is_some_and(|e| e.pinned)
+    }
+
+    /// Flip the pin on `id`, returning whether it is now pinned. Unknown ids
+    /// stay unpinned.
+    pub(crate) fn toggle_pinned(&mut self, id: BufferId) -> bool {
+        let Some(entry) = self.buffers.get_mut(&id) else {
+            return false;
+        };
+        entry.pinned = !entry.pinned;
+        entry.pinned
+    }
+
+    /// Path-bound, non-preview, unpinned buffers other than `keep`, sorted by
+    /// path. These are what `CloseOtherBuffers` tries to close.
+    pub(crate) fn unpinned_except(&self, keep: &[BufferId]) -> Vec<BufferId> {
+        let mut paths: Vec<(&PathBuf, BufferId)> = self
+            .path_to_id
+            .iter()
+            .filter(|(_, id)| !keep.contains(id))
+            .filter(|(_, id)| {
+                self.buffers
+                    .get(id)
+                    .is_some_and(|e| !e.pinned && !e.preview)
+            })
+            .map(|(path, id)| (path, *id))
+            .collect();
+        paths.sort();
+        paths.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Append `path` to the group `name`, creating the group. Returns `false`
+    /// when the path was already a member.
+    pub(crate) fn add_to_group(&mut self, name: &str, path: &Path) -> bool {
+        let members = self.groups.entry(name.to_string()).or_default();
+        if members.iter().any(|p| p == path) {
+            return false;
+        }
+        members.push(path.to_path_buf());
+        true
+    }
+
+    /// The member paths of group `name` in the order they were added.
+    pub(crate) fn group(&self, name: &str) -> Option<&[PathBuf]> {
+        self.groups.get(name).map(Vec::as_slice)
+    }
+
+    /// Every group name, sorted, for the `:buffer-group ` picker.
+    pub(crate) fn group_names(&self) -> Vec<String> {
+        self.groups.keys().cloned().collect()
     }
 
     /// Every buffer whose `dirty` flag is set: path-bound first sorted by
@@ -384,9 +474,11 @@ impl BufferRegistry {
             entry.language = Some(lang);
             entry.syntax = None;
             entry.syntax_map = None;
+            entry.syntax_snapshot = None;
             entry.tokens = None;
             entry.lsp_tokens = None;
             entry.lsp_symbol_kinds = None;
+            entry.words = None;
         }
     }
 
@@ -427,9 +519,11 @@ impl BufferRegistry {
                 drop_syntax_in_background(state);
             }
             entry.syntax_map = None;
+            entry.syntax_snapshot = None;
             entry.tokens = None;
             entry.lsp_tokens = None;
             entry.lsp_symbol_kinds = None;
+            entry.words = None;
         }
     }
 
@@ -467,6 +561,29 @@ impl BufferRegistry {
         }
     }
 
+    /// Retain the identifier index the parse pipeline built for `id`.
+    pub(crate) fn store_words(&mut self, id: BufferId, words: Arc<BufferWords>) {
+        if let Some(entry) = self.buffers.get_mut(&id) {
+            entry.words = Some(words);
+        }
+    }
+
+    /// The retained identifier index for `id`, if the parse pipeline has
+    /// indexed it.
+    pub(crate) fn words_for(&self, id: BufferId) -> Option<Arc<BufferWords>> {
+        self.buffers.get(&id)?.words.clone()
+    }
+
+    /// Every retained identifier index other than `id`'s, for completing
+    /// words from the rest of the open buffers.
+    pub(crate) fn words_except(&self, id: BufferId) -> Vec<Arc<BufferWords>> {
+        self.buffers
+            .iter()
+            .filter(|(other, _)| **other != id)
+            .filter_map(|(_, entry)| entry.words.clone())
+            .collect()
+    }
+
     /// The retained `(tokens, interner)` pair for `id`, if the parse pipeline
     /// has produced tree-sitter tokens for it.
     pub(crate) fn tokens_for(
@@ -599,6 +716,7 @@ impl BufferRegistry {
                     drop_syntax_in_background(state);
                 }
                 entry.syntax_map = None;
+                entry.syntax_snapshot = None;
                 entry.tokens = None;
                 entry.lsp_tokens = None;
                 entry.lsp_symbol_kinds = None;
@@ -627,17 +745,26 @@ impl BufferRegistry {
     /// capture-merging consumers always see the latest layer set.
     pub(crate) fn store_syntax_map(&mut self, id: BufferId, map: SyntaxMap) {
         if let Some(entry) = self.buffers.get_mut(&id) {
+            entry.syntax_snapshot = Some(map.snapshot().clone());
             entry.syntax_map = Some(map);
         }
     }
 
     /// Move the multi-layer [`SyntaxMap`] for `id` out of the
     /// registry, so the next reparse can interpolate it incrementally
-    /// before reinstalling.
+    /// before reinstalling. Its snapshot stays behind for
+    /// [`Self::syntax_snapshot`].
     pub(crate) fn take_syntax_map(&mut self, id: BufferId) -> Option<SyntaxMap> {
         self.buffers.get_mut(&id)?.syntax_map.take()
     }
 
+    /// The layer set of the last parse of `id`, available even while its
+    /// [`SyntaxMap`] is out for a background reparse. Its `parsed_version`
+    /// may trail the buffer, so callers resolving offsets compare it first.
+    pub(crate) fn syntax_snapshot(&self, id: BufferId) -> Option<&SyntaxSnapshot> {
+        self.buffers.get(&id)?.syntax_snapshot.as_ref()
+    }
+
     /// Return a cached [`DiffResult`] for `(buffer, base_text)` if one
     /// was stored against the current buffer version and base
     /// fingerprint; otherwise `None`. Callers recompute and cache via
@@ -698,6 +825,7 @@ impl BufferRegistry {
             .map(|(id, entry)| BufferEntrySnap {
                 id: *id,
                 path: entry.path.clone(),
+                pinned: entry.pinned,
                 history: {
                     let guard = entry.buffer.read().expect("buffer poisoned");
                     guard.history()
@@ -708,6 +836,7 @@ impl BufferRegistry {
         BufferRegistrySnapshot {
             entries,
             next_id: self.next_id,
+            groups: self.groups.clone(),
         }
     }
 
@@ -722,6 +851,7 @@ impl BufferRegistry {
         self.buffers.clear();
         self.path_to_id.clear();
         self.next_id = snap.next_id.max(1);
+        self.groups = snap.groups;
 
         for entry in snap.entries {
             let buffer = Arc::new(RwLock::new(TextBuffer::from_history(
@@ -739,14 +869,17 @@ impl BufferRegistry {
                     language: None,
                     syntax: None,
                     syntax_map: None,
+                    syntax_snapshot: None,
                     tokens: None,
                     lsp_tokens: None,
                     lsp_symbol_kinds: None,
+                    words: None,
                     diff: None,
                     preview: false,
                     disk_mtime: None,
                     auto_reload: AutoReloadMode::Off,
                     last_shown: 0,
+                    pinned: entry.pinned,
                 },
             );
         }
@@ -761,12 +894,16 @@ impl BufferRegistry {
 pub(crate) struct BufferRegistrySnapshot {
     pub entries: Vec<BufferEntrySnap>,
     pub next_id: u64,
+    #[serde(default)]
+    pub groups: BTreeMap<String, Vec<PathBuf>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct BufferEntrySnap {
     pub id: BufferId,
     pub path: Option<PathBuf>,
+    #[serde(default)]
+    pub pinned: bool,
     pub history: BufferHistory,
 }
 
@@ -821,6 +958,30 @@ mod tests {
         assert_eq!(reg.syntax_version(id), None);
     }
 
+    #[test]
+    fn syntax_snapshot_outlives_a_taken_map() {
+        use stoat_language::LanguageRegistry;
+
+        let mut reg = BufferRegistry::new();
+        let (id, _) = reg.open(Path::new("/a.rs"), "fn a() {}\n");
+        let lang = LanguageRegistry::standard()
+            .for_path(Path::new("/a.rs"))
+            .expect("rust language");
+        let mut map = SyntaxMap::new();
+        map.reparse(&stoat_text::Rope::from("fn a() {}\n"), lang, 1)
+            .expect("parsed");
+        reg.store_syntax_map(id, map);
+
+        let map = reg.take_syntax_map(id).expect("stored map");
+        assert!(reg.syntax_map(id).is_none());
+        let snapshot = reg.syntax_snapshot(id).expect("snapshot stays behind");
+        assert_eq!(snapshot.parsed_version, 1);
+        assert_eq!(snapshot.layer_count(), map.snapshot().layer_count());
+
+        reg.clear_syntax(id);
+        assert!(reg.syntax_snapshot(id).is_none());
+    }
+
     #[test]
     fn clear_syntax_and_set_language_drop_retained_tokens() {
         use stoat_language::LanguageRegistry;
@@ -988,4 +1149,24 @@ mod tests {
         assert_ne!(fingerprint_bytes("a"), fingerprint_bytes("b"));
         assert_eq!(fingerprint_bytes("abc"), fingerprint_bytes("abc"));
     }
+
+    #[test]
+    fn pins_and_groups_survive_a_snapshot() {
+        let mut reg = BufferRegistry::new();
+        reg.open(Path::new("/a.rs"), "");
+        let (b, _) = reg.open(Path::new("/b.rs"), "");
+        assert!(reg.toggle_pinned(b));
+        assert!(reg.add_to_group("work", Path::new("/b.rs")));
+        assert!(!reg.add_to_group("work", Path::new("/b.rs")));
+
+        let mut restored = BufferRegistry::new();
+        restored.restore_from(reg.snapshot());
+        assert_eq!(
+            restored.open_paths(),
+            [PathBuf::from("/b.rs"), PathBuf::from("/a.rs")]
+        );
+        assert_eq!(restored.pinned_count(), 1);
+        assert_eq!(restored.unpinned_except(&[]).len(), 1);
+        assert_eq!(restored.group("work"), Some(&[PathBuf::from("/b.rs")][..]));
+    }
 }
@@ -0,0 +1,197 @@
+//! Regex search over a rope without copying out the whole text.
+//!
+//! [`Matches`] walks the rope in windows of whole lines, each at least
+//! [`WINDOW_BYTES`] long, so a search holds one window in memory rather than
+//! the file. Every window opens at a line start, so `^`, `$` and `\b` see the
+//! context they would over the full text. A match is found whole when it ends
+//! within [`WINDOW_BYTES`] of the start of its first line; a longer
+//! multi-line match is cut at the window's end.
+
+use regex::Regex;
+use std::ops::Range;
+use stoat_text::{Point, Rope};
+
+/// Least number of bytes a search window spans, short of the rope's end.
+pub const WINDOW_BYTES: usize = 64 * 1024;
+
+/// Byte ranges of the matches of a regex in a rope, in ascending order,
+/// starting at an offset.
+pub struct Matches<'a> {
+    rope: &'a Rope,
+    regex: &'a Regex,
+    /// Where the next match may start.
+    offset: usize,
+    window: String,
+    /// Byte range of the rope held in `window`, or `None` before the first
+    /// load and after the window was invalidated.
+    window_range: Option<Range<usize>>,
+}
+
+impl<'a> Matches<'a> {
+    /// Matches of `regex` in `rope` starting at or after `from`.
+    pub fn new(rope: &'a Rope, regex: &'a Regex, from: usize) -> Self {
+        Self {
+            rope,
+            regex,
+            offset: from,
+            window: String::new(),
+            window_range: None,
+        }
+    }
+
+    /// Where the next match may start. Passing it to [`Self::new`] on the same
+    /// text continues the search where this one stopped.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Fill the window with the lines from the one holding `self.offset` up to
+    /// the first line boundary [`WINDOW_BYTES`] past its start.
+    fn load(&mut self) -> Range<usize> {
+        let len = self.rope.len();
+        let start = line_start(self.rope, self.offset);
+        let target = start.saturating_add(WINDOW_BYTES);
+        let end = if target >= len {
+            len
+        } else {
+            let row = self.rope.offset_to_point(target).row;
+            if row >= self.rope.max_point().row {
+                len
+            } else {
+                self.rope.point_to_offset(Point::new(row + 1, 0))
+            }
+        };
+        self.window.clear();
+        for chunk in self.rope.chunks_in_range(start..end) {
+            self.window.push_str(chunk);
+        }
+        self.window_range = Some(start..end);
+        start..end
+    }
+}
+
+impl Iterator for Matches<'_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let len = self.rope.len();
+        while self.offset <= len {
+            let window = match &self.window_range {
+                Some(range) if range.start <= self.offset && self.offset <= range.end => {
+                    range.clone()
+                },
+                _ => self.load(),
+            };
+            let at_end = window.end == len;
+            let found = self
+                .regex
+                .find_at(&self.window, self.offset - window.start)
+                .map(|m| m.start() + window.start..m.end() + window.start);
+            match found {
+                Some(range) if range.end < window.end || at_end => {
+                    self.offset = if range.is_empty() {
+                        range.end + next_char_len(self.rope, range.end)
+                    } else {
+                        range.end
+                    };
+                    return Some(range);
+                },
+                Some(range) => {
+                    // The match runs into the window's end and may continue past
+                    // it. Reload from its own line unless the window already
+                    // opens there.
+                    if line_start(self.rope, range.start) == window.start {
+                        self.offset = range.end;
+                        return Some(range);
+                    }
+                    self.offset = range.start;
+                    self.window_range = None;
+                },
+                None if at_end => break,
+                None => {
+                    // A match may start on the last line and cross into the next
+                    // window, so resume there rather than at the window's end.
+                    let last_line = line_start(self.rope, window.end - 1);
+                    self.offset = if last_line > window.start {
+                        last_line.max(self.offset)
+                    } else {
+                        window.end
+                    };
+                    self.window_range = None;
+                },
+            }
+        }
+        self.offset = len + 1;
+        None
+    }
+}
+
+fn line_start(rope: &Rope, offset: usize) -> usize {
+    let row = rope.offset_to_point(offset).row;
+    rope.point_to_offset(Point::new(row, 0))
+}
+
+/// Byte length of the character at `offset`, or 1 at the rope's end so a
+/// zero-width match there still moves the search past it.
+fn next_char_len(rope: &Rope, offset: usize) -> usize {
+    rope.chunks_in_range(offset..rope.len())
+        .flat_map(str::chars)
+        .next()
+        .map_or(1, char::len_utf8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Matches, WINDOW_BYTES};
+    use regex::RegexBuilder;
+    use stoat_text::Rope;
+
+    fn ranges(text: &str, pattern: &str, from: usize) -> Vec<std::ops::Range<usize>> {
+        let rope = Rope::from(text);
+        let regex = RegexBuilder::new(pattern)
+            .multi_line(true)
+            .build()
+            .expect("valid");
+        Matches::new(&rope, &regex, from).collect()
+    }
+
+    fn expected(text: &str, pattern: &str) -> Vec<std::ops::Range<usize>> {
+        let regex = RegexBuilder::new(pattern)
+            .multi_line(true)
+            .build()
+            .expect("valid");
+        regex.find_iter(text).map(|m| m.range()).collect()
+    }
+
+    #[test]
+    fn agrees_with_a_whole_text_search_across_windows() {
+        let line = "alpha beta gamma\n";
+        let text = line.repeat(3 * WINDOW_BYTES / line.len());
+        for pattern in [r"beta", r"^alpha", r"gamma$", r"gamma\nalpha"] {
+            assert_eq!(
+                ranges(&text, pattern, 0),
+                expected(&text, pattern),
+                "{pattern}"
+            );
+        }
+    }
+
+    #[test]
+    fn continues_from_an_offset() {
+        let text = "ab ab ab";
+        assert_eq!(ranges(text, "ab", 1), vec![3..5, 6..8]);
+
+        let rope = Rope::from(text);
+        let regex = RegexBuilder::new("ab").build().expect("valid");
+        let mut matches = Matches::new(&rope, &regex, 0);
+        assert_eq!(matches.next(), Some(0..2));
+        let resumed: Vec<_> = Matches::new(&rope, &regex, matches.offset()).collect();
+        assert_eq!(resumed, vec![3..5, 6..8]);
+    }
+
+    #[test]
+    fn zero_width_matches_advance() {
+        assert_eq!(ranges("a\nbé\n", "$", 0), vec![1..1, 5..5, 6..6]);
+        assert_eq!(ranges("", "^", 0), vec![0..0]);
+    }
+}
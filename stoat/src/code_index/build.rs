@@ -4,7 +4,9 @@
 //! root, extracts a [`FileShard`] from each, and streams the shards to the
 //! event loop as [`IndexUpdate`] messages. The loop merges each shard into
 //! the workspace graph and, on [`IndexUpdate::Complete`], resolves
-//! cross-file references and writes the manifest.
+//! cross-file references and writes the manifest. The same walk records
+//! every worktree file, indexable or not, so the file finder can list the
+//! workspace from the persisted list on the next startup.
 //!
 //! All parsing and extraction runs on the blocking pool. Only the cheap
 //! merge happens on the main thread, off the paint path.
@@ -16,7 +18,7 @@ use codegraph::{
     build_shard, decode_shard, FileEntry, FileId, FileShard, Manifest, SCHEMA_VERSION,
 };
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     ops::ControlFlow,
     path::{Path, PathBuf},
     sync::Arc,
@@ -39,10 +41,12 @@ pub(crate) enum IndexUpdate {
         persist: bool,
     },
     /// The scan finished. Resolve cross-file references and persist the
-    /// manifest listing every covered file.
+    /// manifest listing every covered file, along with `files`, the
+    /// workspace-relative path of every file the walk found, indexable or not.
     Complete {
         workspace: WorkspaceId,
         manifest: Manifest,
+        files: BTreeSet<String>,
     },
     /// One file's freshly re-extracted shard. The drain evicts the file's
     /// prior symbols, inserts these, and re-resolves so callers of the
@@ -116,6 +120,7 @@ pub(crate) fn build_index(
 
         let mut entries = Vec::new();
         let mut seen = HashSet::new();
+        let mut files = BTreeSet::new();
         let mut cancelled = false;
         fs.walk_workspace_files_streaming(&git_root, &mut |batch| {
             for path in batch {
@@ -125,6 +130,9 @@ pub(crate) fn build_index(
                     cancelled = true;
                     return ControlFlow::Break(());
                 }
+                if let Some(rel_path) = relpath(&git_root, &path) {
+                    files.insert(rel_path);
+                }
                 let Some((rel_path, shard, source)) = load_or_extract(
                     fs.as_ref(),
                     &languages,
@@ -179,6 +187,7 @@ pub(crate) fn build_index(
         let _ = tx.send(IndexUpdate::Complete {
             workspace,
             manifest,
+            files,
         });
         redraw.notify_one();
         tracing::info!(
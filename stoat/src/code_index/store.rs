@@ -7,7 +7,7 @@
 use crate::{host::FsHost, workspace::anchor_state_dir};
 use codegraph::{decode_manifest, encode_manifest, FileEntry, Manifest, SCHEMA_VERSION};
 use std::{
-    collections::HashSet,
+    collections::{BTreeSet, HashSet},
     hash::{Hash, Hasher},
     io,
     path::{Path, PathBuf},
@@ -15,6 +15,7 @@ use std::{
 
 const MANIFEST_FILE: &str = "manifest";
 const SHARDS_DIR: &str = "shards";
+const FILES_FILE: &str = "files";
 
 /// Resolve the on-disk index directory for `git_root`.
 ///
@@ -131,6 +132,29 @@ pub(crate) fn remove_manifest_entry(
     write_manifest(index_dir, &manifest, fs)
 }
 
+/// Write the worktree file list under `index_dir`, one workspace-relative
+/// path per line, replacing any existing list.
+pub(crate) fn write_file_list(
+    index_dir: &Path,
+    files: &BTreeSet<String>,
+    fs: &dyn FsHost,
+) -> io::Result<()> {
+    let mut data = String::new();
+    for rel_path in files.iter().filter(|rel_path| !rel_path.contains('\n')) {
+        data.push_str(rel_path);
+        data.push('\n');
+    }
+    write_atomic(&index_dir.join(FILES_FILE), data.as_bytes(), fs)
+}
+
+/// Read the worktree file list written by [`write_file_list`].
+pub(crate) fn read_file_list(index_dir: &Path, fs: &dyn FsHost) -> io::Result<BTreeSet<String>> {
+    let bytes = read_bytes(&index_dir.join(FILES_FILE), fs)?;
+    let text =
+        String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(text.lines().map(str::to_string).collect())
+}
+
 /// Write a file's already-encoded shard bytes under `index_dir`.
 pub(crate) fn write_shard(
     index_dir: &Path,
@@ -180,7 +204,8 @@ fn read_bytes(path: &Path, fs: &dyn FsHost) -> io::Result<Vec<u8>> {
 #[cfg(test)]
 mod tests {
     use super::{
-        prune_shards, read_manifest, read_shard, update_manifest_entry, write_manifest, write_shard,
+        prune_shards, read_file_list, read_manifest, read_shard, update_manifest_entry,
+        write_file_list, write_manifest, write_shard,
     };
     use crate::{buffer_registry::fingerprint_bytes, host::FakeFs};
     use codegraph::{FileEntry, Manifest, SCHEMA_VERSION};
@@ -214,6 +239,17 @@ mod tests {
         assert_eq!(read_shard(dir, "src/a.rs", &fs).unwrap(), bytes);
     }
 
+    #[test]
+    fn file_list_round_trips_through_the_store() {
+        let fs = FakeFs::new();
+        let dir = Path::new("/idx");
+        let files: std::collections::BTreeSet<String> = ["README.md", "src/a.rs", "src/b c.rs"]
+            .map(String::from)
+            .into();
+        write_file_list(dir, &files, &fs).unwrap();
+        assert_eq!(read_file_list(dir, &fs).unwrap(), files);
+    }
+
     #[test]
     fn content_hash_mismatch_is_detectable() {
         let fs = FakeFs::new();
@@ -0,0 +1,281 @@
+//! Code lenses: short annotations drawn on a block line above a source line.
+//!
+//! A [`LensProvider`] reports the lenses for a buffer, each covering a byte
+//! range and optionally carrying an action. [`refresh`] collects every
+//! provider's lenses for each editor in the active workspace and installs
+//! them as display-map blocks above the line where each range starts. Lenses
+//! sharing a line are joined into one block line, separated by ` | `.
+//! [`click`] maps a mouse press on a lens label back to its lens, moves the
+//! cursor to the start of the lens range, and dispatches its action.
+//!
+//! The test runner in [`crate::test_lens`] is the first provider.
+
+use crate::{
+    app::Stoat,
+    buffer::BufferId,
+    display_map::{Block, BlockPlacement, BlockProperties, BlockRowKind, BlockStyle},
+    editor_state::EditorId,
+    keymap::ResolvedAction,
+};
+use ratatui::layout::Rect;
+use std::{collections::HashMap, ops::Range};
+use stoat_text::{Point, Rope};
+
+/// Separator between the labels of lenses sharing a line.
+const SEPARATOR: &str = " | ";
+
+/// One annotation reported by a [`LensProvider`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Lens {
+    /// Bytes the lens describes. The lens sits above the line holding
+    /// `range.start`, indented to its column.
+    pub(crate) range: Range<usize>,
+    pub(crate) label: String,
+    /// Dispatched on a click on the label, after the cursor moves to
+    /// `range.start`. Without one the lens is display only.
+    pub(crate) action: Option<ResolvedAction>,
+}
+
+/// A source of code lenses for open buffers.
+pub(crate) trait LensProvider: Send + Sync {
+    /// A version stamp for `buffer_id`'s lenses, typically the buffer or
+    /// syntax version paired with a provider generation. Lenses rebuild when
+    /// any provider's stamp changes. [`None`] means the provider has no
+    /// lenses for the buffer.
+    fn stamp(&self, stoat: &Stoat, buffer_id: BufferId) -> Option<(u64, u64)>;
+
+    /// The lenses for `buffer_id`, in any order. Only called while
+    /// [`Self::stamp`] returns [`Some`].
+    fn lenses(&self, stoat: &Stoat, buffer_id: BufferId) -> Vec<Lens>;
+}
+
+/// Code lens state held on [`Stoat`].
+pub(crate) struct CodeLensState {
+    providers: Vec<Box<dyn LensProvider>>,
+    /// Provider stamps each editor's lenses were built at.
+    built: HashMap<EditorId, Vec<Option<(u64, u64)>>>,
+    /// Installed lines per editor, for mapping clicks back to lenses.
+    installed: HashMap<EditorId, Vec<LensLine>>,
+}
+
+impl Default for CodeLensState {
+    fn default() -> Self {
+        Self {
+            providers: vec![Box::new(crate::test_lens::TestLenses)],
+            built: HashMap::new(),
+            installed: HashMap::new(),
+        }
+    }
+}
+
+/// The lenses installed above one buffer row.
+#[derive(Debug, Clone, PartialEq)]
+struct LensLine {
+    row: u32,
+    /// Column of the first label, the smallest lens column on the row.
+    column: u32,
+    lenses: Vec<Lens>,
+}
+
+impl LensLine {
+    fn text(&self) -> String {
+        let labels: Vec<&str> = self.lenses.iter().map(|l| l.label.as_str()).collect();
+        format!(
+            "{}{}",
+            " ".repeat(self.column as usize),
+            labels.join(SEPARATOR)
+        )
+    }
+
+    /// The lens whose label covers `column`, counted in cells from the
+    /// block line's start.
+    fn lens_at(&self, column: u32) -> Option<&Lens> {
+        let mut start = self.column;
+        for lens in &self.lenses {
+            let end = start + lens.label.chars().count() as u32;
+            if (start..end).contains(&column) {
+                return Some(lens);
+            }
+            start = end + SEPARATOR.len() as u32;
+        }
+        None
+    }
+}
+
+/// Group `lenses` into lines by the row their range starts on in `rope`,
+/// sorted by row and then column.
+fn lens_lines(lenses: Vec<Lens>, rope: &Rope) -> Vec<LensLine> {
+    let mut placed: Vec<(Point, Lens)> = lenses
+        .into_iter()
+        .map(|lens| (rope.offset_to_point(lens.range.start.min(rope.len())), lens))
+        .collect();
+    placed.sort_by_key(|(point, _)| (point.row, point.column));
+    let mut lines: Vec<LensLine> = Vec::new();
+    for (point, lens) in placed {
+        match lines.last_mut() {
+            Some(line) if line.row == point.row => line.lenses.push(lens),
+            _ => lines.push(LensLine {
+                row: point.row,
+                column: point.column,
+                lenses: vec![lens],
+            }),
+        }
+    }
+    lines
+}
+
+/// Rebuild the lens blocks of every editor in the active workspace whose
+/// provider stamps changed since its last build, clearing the blocks of
+/// editors no provider reports lenses for anymore.
+pub(crate) fn refresh(stoat: &mut Stoat) {
+    let mut updates: Vec<(EditorId, Vec<Lens>, Vec<Option<(u64, u64)>>)> = Vec::new();
+    {
+        let ws = stoat.active_workspace();
+        let state = &stoat.code_lenses;
+        for (editor_id, editor) in ws.editors.iter() {
+            let stamps: Vec<Option<(u64, u64)>> = state
+                .providers
+                .iter()
+                .map(|provider| provider.stamp(stoat, editor.buffer_id))
+                .collect();
+            let built = state.built.get(&editor_id);
+            if built == Some(&stamps) || (built.is_none() && stamps.iter().all(Option::is_none)) {
+                continue;
+            }
+            let lenses = state
+                .providers
+                .iter()
+                .zip(&stamps)
+                .filter(|(_, stamp)| stamp.is_some())
+                .flat_map(|(provider, _)| provider.lenses(stoat, editor.buffer_id))
+                .collect();
+            updates.push((editor_id, lenses, stamps));
+        }
+    }
+    let ws = stoat.active_workspace_mut();
+    let live: Vec<EditorId> = ws.editors.keys().collect();
+    let mut installs = Vec::with_capacity(updates.len());
+    for (editor_id, lenses, stamps) in updates {
+        let Some(editor) = ws.editors.get_mut(editor_id) else {
+            continue;
+        };
+        let snapshot = editor.display_map.snapshot();
+        let lines = lens_lines(lenses, snapshot.buffer_snapshot().rope());
+        let blocks = lines
+            .iter()
+            .map(|line| {
+                BlockProperties::from_text(
+                    BlockPlacement::Above(line.row),
+                    vec![line.text()],
+                    BlockStyle::Fixed,
+                )
+            })
+            .collect();
+        editor.display_map.set_lens_blocks(blocks);
+        installs.push((editor_id, lines, stamps));
+    }
+    let state = &mut stoat.code_lenses;
+    for (editor_id, lines, stamps) in installs {
+        if stamps.iter().all(Option::is_none) {
+            state.built.remove(&editor_id);
+            state.installed.remove(&editor_id);
+        } else {
+            state.built.insert(editor_id, stamps);
+            state.installed.insert(editor_id, lines);
+        }
+    }
+    state.built.retain(|editor_id, _| live.contains(editor_id));
+    state
+        .installed
+        .retain(|editor_id, _| live.contains(editor_id));
+}
+
+/// Run the lens under the pane cell `col`/`row` of `editor_id`, laid out in
+/// `area`. Returns whether the cell was on a lens block line, so the press
+/// is consumed even between labels.
+pub(crate) fn click(
+    stoat: &mut Stoat,
+    editor_id: EditorId,
+    area: Rect,
+    col: u16,
+    row: u16,
+) -> bool {
+    if col >= area.width || row >= area.height {
+        return false;
+    }
+    let Some(editor) = stoat.active_workspace_mut().editors.get_mut(editor_id) else {
+        return false;
+    };
+    let Some(text_col) = col.checked_sub(editor.gutter_width) else {
+        return false;
+    };
+    let snapshot = editor.display_map.snapshot();
+    let BlockRowKind::Block {
+        block: Block::Custom(block),
+        ..
+    } = snapshot.classify_row(editor.scroll_row + row as u32)
+    else {
+        return false;
+    };
+    let BlockPlacement::Above(lens_row) = block.placement else {
+        return false;
+    };
+    if !editor.display_map.is_lens_block(block.id) {
+        return false;
+    }
+    let lens = stoat
+        .code_lenses
+        .installed
+        .get(&editor_id)
+        .and_then(|lines| lines.iter().find(|line| line.row == lens_row))
+        .and_then(|line| line.lens_at(text_col as u32))
+        .cloned();
+    let Some(Lens {
+        range,
+        action: Some(action),
+        ..
+    }) = lens
+    else {
+        return true;
+    };
+    if let Some(editor) = stoat.active_workspace_mut().editors.get_mut(editor_id) {
+        let snapshot = editor.display_map.snapshot();
+        let buf_snap = snapshot.buffer_snapshot();
+        let offset = range.start.min(buf_snap.rope().len());
+        editor.selections.set_block_cursor(offset, buf_snap);
+    }
+    stoat.dispatch_bound_actions(&[action], None);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lens(start: usize, label: &str) -> Lens {
+        Lens {
+            range: start..start + 1,
+            label: label.into(),
+            action: None,
+        }
+    }
+
+    #[test]
+    fn lenses_on_one_row_share_a_line() {
+        let rope = Rope::from("fn a() {}\n    fn b() {}\n");
+        let lines = lens_lines(
+            vec![lens(18, "2 refs"), lens(14, "▶ run"), lens(0, "1 ref")],
+            &rope,
+        );
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].text(), "1 ref");
+        assert_eq!(lines[1].text(), "    ▶ run | 2 refs");
+        assert_eq!(lines[1].lens_at(4).map(|l| l.label.as_str()), Some("▶ run"));
+        assert_eq!(lines[1].lens_at(9), None, "the separator is no lens");
+        assert_eq!(
+            lines[1].lens_at(12).map(|l| l.label.as_str()),
+            Some("2 refs")
+        );
+    }
+}
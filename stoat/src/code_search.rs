@@ -75,12 +75,13 @@ impl CodeSearchFinder {
         ws: &mut Workspace,
         executor: Executor,
         target_lang: Option<Arc<Language>>,
+        seed: &str,
     ) -> Self {
         let input = InputView::create(
             ws,
             executor.clone(),
             SubmitTarget::CodeSearch,
-            "",
+            seed,
             "insert",
             1,
         );
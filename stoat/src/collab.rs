@@ -0,0 +1,583 @@
+//! Two-replica co-editing of a single buffer.
+//!
+//! Each side of a shared buffer holds a [`CollabSession`] that turns local
+//! edits into [`SyncMessage`]s and folds the peer's messages back in. Local
+//! edits are not intercepted at the call site. [`pump`] diffs the buffer
+//! against the version it last synced via
+//! [`crate::buffer::TextBufferSnapshot::edits_since`], so every editing path
+//! (actions, undo, LSP edits, completion) shares without knowing about it.
+//!
+//! Concurrency is resolved with the two-party Jupiter scheme. Each message
+//! records how many of the receiver's messages the sender had applied, the
+//! receiver drops the outgoing messages that count acknowledges, and the
+//! incoming operations are transformed past the rest before they are applied.
+//! Inserts landing on the same offset are ordered by their [`Lamport`] stamp,
+//! which both replicas compare identically, so the two buffers converge.
+//!
+//! Remote edits are applied through [`crate::buffer::TextBuffer::edit`], so
+//! anchors, diffs and parses follow them like any other edit. They also land on
+//! the local undo stack, which a later change can separate out.
+
+use crate::{app::Stoat, buffer::BufferId};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    io,
+    ops::Range,
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+};
+
+/// Identifies one participant in a shared buffer. The two replicas of a
+/// session must use distinct ids, since ties between concurrent inserts break
+/// on them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ReplicaId(pub u16);
+
+/// A Lamport timestamp tagged with the replica that issued it.
+///
+/// Orders by `value`, then by `replica`, giving a total order over every
+/// message either side ever sends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Lamport {
+    pub value: u64,
+    pub replica: ReplicaId,
+}
+
+/// One primitive buffer change in byte offsets. A replace travels as a
+/// [`Delete`](Self::Delete) followed by an [`Insert`](Self::Insert) at the same
+/// offset.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditOp {
+    Insert { at: usize, text: String },
+    Delete { range: Range<usize> },
+}
+
+/// One batch of operations sent to the peer, applied in order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncMessage {
+    pub id: Lamport,
+    /// Index of this message among the sender's messages.
+    pub seq: u64,
+    /// Count of the receiver's messages the sender had applied when it sent
+    /// this one.
+    pub seen: u64,
+    pub ops: Vec<EditOp>,
+}
+
+/// A batch recorded in a session's operation log, in the order it was applied
+/// to the local buffer. Remote batches hold their transformed operations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoggedOp {
+    pub id: Lamport,
+    pub remote: bool,
+    pub ops: Vec<EditOp>,
+}
+
+/// Carries [`SyncMessage`]s between the two replicas of a shared buffer.
+///
+/// Delivery must be reliable and in order. [`ChannelTransport`] connects two
+/// instances in one process; a socket transport implements the same surface.
+pub trait SyncTransport: Send {
+    fn send(&self, message: SyncMessage) -> io::Result<()>;
+
+    /// Returns the next delivered message, or `None` when nothing is waiting.
+    fn try_recv(&mut self) -> Option<SyncMessage>;
+}
+
+/// In-process [`SyncTransport`] over a pair of channels.
+pub struct ChannelTransport {
+    tx: Sender<SyncMessage>,
+    rx: Receiver<SyncMessage>,
+}
+
+impl ChannelTransport {
+    /// Two connected ends: what one sends, the other receives.
+    pub fn pair() -> (Self, Self) {
+        let (a_tx, b_rx) = mpsc::channel();
+        let (b_tx, a_rx) = mpsc::channel();
+        (Self { tx: a_tx, rx: a_rx }, Self { tx: b_tx, rx: b_rx })
+    }
+}
+
+impl SyncTransport for ChannelTransport {
+    fn send(&self, message: SyncMessage) -> io::Result<()> {
+        self.tx
+            .send(message)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "peer disconnected"))
+    }
+
+    fn try_recv(&mut self) -> Option<SyncMessage> {
+        match self.rx.try_recv() {
+            Ok(message) => Some(message),
+            Err(TryRecvError::Empty | TryRecvError::Disconnected) => None,
+        }
+    }
+}
+
+/// A sent batch the peer has not yet acknowledged, kept in the local buffer's
+/// current coordinates so an incoming batch can be transformed past it.
+struct Pending {
+    seq: u64,
+    id: Lamport,
+    ops: Vec<EditOp>,
+}
+
+/// One replica's side of a shared buffer.
+pub struct CollabSession {
+    replica: ReplicaId,
+    clock: u64,
+    sent: u64,
+    received: u64,
+    outgoing: VecDeque<Pending>,
+    /// Buffer version every local edit up to which has been sent.
+    synced_version: u64,
+    log: Vec<LoggedOp>,
+    transport: Box<dyn SyncTransport>,
+}
+
+impl CollabSession {
+    fn new(replica: ReplicaId, synced_version: u64, transport: Box<dyn SyncTransport>) -> Self {
+        Self {
+            replica,
+            clock: 0,
+            sent: 0,
+            received: 0,
+            outgoing: VecDeque::new(),
+            synced_version,
+            log: Vec::new(),
+            transport,
+        }
+    }
+
+    pub fn replica(&self) -> ReplicaId {
+        self.replica
+    }
+
+    /// Every batch applied to the local buffer, local and remote, in order.
+    pub fn log(&self) -> &[LoggedOp] {
+        &self.log
+    }
+
+    fn tick(&mut self) -> Lamport {
+        self.clock += 1;
+        Lamport {
+            value: self.clock,
+            replica: self.replica,
+        }
+    }
+
+    /// Stamp and send a batch of local operations, keeping it pending until
+    /// the peer acknowledges it.
+    fn send_local(&mut self, ops: Vec<EditOp>) {
+        let id = self.tick();
+        let message = SyncMessage {
+            id,
+            seq: self.sent,
+            seen: self.received,
+            ops: ops.clone(),
+        };
+        if let Err(err) = self.transport.send(message) {
+            tracing::warn!(target: "stoat::collab", error = %err, "sync send failed");
+        }
+        self.outgoing.push_back(Pending {
+            seq: self.sent,
+            id,
+            ops: ops.clone(),
+        });
+        self.sent += 1;
+        self.log.push(LoggedOp {
+            id,
+            remote: false,
+            ops,
+        });
+    }
+
+    /// Transform an incoming batch past every unacknowledged local batch,
+    /// returning the operations to apply to the local buffer.
+    fn integrate(&mut self, message: SyncMessage) -> Vec<EditOp> {
+        while self
+            .outgoing
+            .front()
+            .is_some_and(|pending| pending.seq < message.seen)
+        {
+            self.outgoing.pop_front();
+        }
+
+        let mut incoming = message.ops;
+        for pending in &mut self.outgoing {
+            let (theirs, mine) = transform_batches(
+                incoming,
+                message.id,
+                std::mem::take(&mut pending.ops),
+                pending.id,
+            );
+            incoming = theirs;
+            pending.ops = mine;
+        }
+
+        self.received += 1;
+        self.clock = self.clock.max(message.id.value);
+        self.log.push(LoggedOp {
+            id: message.id,
+            remote: true,
+            ops: incoming.clone(),
+        });
+        incoming
+    }
+}
+
+impl Stoat {
+    /// Start co-editing `buffer_id` with the replica on the far end of
+    /// `transport`. Both replicas must hold identical text when sharing
+    /// starts; edits made from then on are exchanged by [`pump`].
+    pub fn share_buffer(
+        &mut self,
+        buffer_id: BufferId,
+        replica: ReplicaId,
+        transport: Box<dyn SyncTransport>,
+    ) {
+        let ws = self.active_workspace_mut();
+        let Some(buffer) = ws.buffers.get(buffer_id) else {
+            return;
+        };
+        let version = buffer.read().expect("buffer poisoned").version();
+        ws.collab
+            .insert(buffer_id, CollabSession::new(replica, version, transport));
+    }
+
+    /// Stop co-editing `buffer_id`, dropping its session and transport.
+    pub fn unshare_buffer(&mut self, buffer_id: BufferId) {
+        self.active_workspace_mut().collab.remove(&buffer_id);
+    }
+}
+
+/// Send every shared buffer's local edits since the last pump, then apply
+/// whatever the peers sent. Runs from [`Stoat::drive_background`].
+pub(crate) fn pump(stoat: &mut Stoat) {
+    for ws in stoat.workspaces.values_mut() {
+        for (buffer_id, session) in &mut ws.collab {
+            let Some(buffer) = ws.buffers.get(*buffer_id) else {
+                continue;
+            };
+            let mut guard = buffer.write().expect("buffer poisoned");
+
+            if guard.version() != session.synced_version {
+                let ops = local_ops(&guard.snapshot, session.synced_version);
+                if !ops.is_empty() {
+                    session.send_local(ops);
+                }
+            }
+
+            while let Some(message) = session.transport.try_recv() {
+                for op in session.integrate(message) {
+                    let len = guard.rope().len();
+                    match op {
+                        EditOp::Insert { at, text } => guard.edit(at.min(len)..at.min(len), &text),
+                        EditOp::Delete { range } => {
+                            guard.edit(range.start.min(len)..range.end.min(len), "")
+                        },
+                    }
+                }
+            }
+
+            session.synced_version = guard.version();
+        }
+    }
+}
+
+/// The edits made to `snapshot` since `since_version`, as operations applied
+/// in order. Each patch edit starts at its new-coordinate offset, since every
+/// edit before it has already been applied when it runs.
+fn local_ops(snapshot: &crate::buffer::TextBufferSnapshot, since_version: u64) -> Vec<EditOp> {
+    let patch = snapshot.edits_since(since_version);
+    let mut ops = Vec::new();
+    for edit in patch.edits() {
+        let at = edit.new.start;
+        if !edit.old.is_empty() {
+            ops.push(EditOp::Delete {
+                range: at..at + edit.old.len(),
+            });
+        }
+        if !edit.new.is_empty() {
+            ops.push(EditOp::Insert {
+                at,
+                text: snapshot.visible_text.slice(edit.new.clone()).to_string(),
+            });
+        }
+    }
+    ops
+}
+
+/// Transform two concurrent batches issued from the same state against each
+/// other. Returns `a` rewritten to apply after `b`, and `b` rewritten to apply
+/// after `a`, so both application orders reach the same text.
+fn transform_batches(
+    a: Vec<EditOp>,
+    a_id: Lamport,
+    b: Vec<EditOp>,
+    b_id: Lamport,
+) -> (Vec<EditOp>, Vec<EditOp>) {
+    if a.is_empty() || b.is_empty() {
+        return (a, b);
+    }
+    if a.len() == 1 && b.len() == 1 {
+        return (
+            transform(&a[0], a_id, &b[0], b_id),
+            transform(&b[0], b_id, &a[0], a_id),
+        );
+    }
+    if a.len() == 1 {
+        let mut head = b;
+        let tail = head.split_off(1);
+        let (a, head) = transform_batches(a, a_id, head, b_id);
+        let (a, tail) = transform_batches(a, a_id, tail, b_id);
+        return (a, [head, tail].concat());
+    }
+    let mut head = a;
+    let tail = head.split_off(1);
+    let (head, b) = transform_batches(head, a_id, b, b_id);
+    let (tail, b) = transform_batches(tail, a_id, b, b_id);
+    ([head, tail].concat(), b)
+}
+
+/// Rewrite `op` to apply after the concurrent `against`.
+///
+/// A delete spanning a concurrent insert splits around it, so the inserted text
+/// survives on both replicas. Inserts at the same offset order by Lamport
+/// stamp, the earlier stamp landing first.
+fn transform(op: &EditOp, op_id: Lamport, against: &EditOp, against_id: Lamport) -> Vec<EditOp> {
+    match (op, against) {
+        (
+            EditOp::Insert { at, text },
+            EditOp::Insert {
+                at: other,
+                text: other_text,
+            },
+        ) => {
+            let shifted = *other < *at || (*other == *at && against_id < op_id);
+            vec![EditOp::Insert {
+                at: if shifted { at + other_text.len() } else { *at },
+                text: text.clone(),
+            }]
+        },
+        (EditOp::Insert { at, text }, EditOp::Delete { range }) => vec![EditOp::Insert {
+            at: map_past_delete(*at, range),
+            text: text.clone(),
+        }],
+        (EditOp::Delete { range }, EditOp::Insert { at, text }) => {
+            let len = text.len();
+            if *at <= range.start {
+                vec![EditOp::Delete {
+                    range: range.start + len..range.end + len,
+                }]
+            } else if *at >= range.end {
+                vec![EditOp::Delete {
+                    range: range.clone(),
+                }]
+            } else {
+                // The later piece goes first so the earlier one's offsets hold.
+                vec![
+                    EditOp::Delete {
+                        range: at + len..range.end + len,
+                    },
+                    EditOp::Delete {
+                        range: range.start..*at,
+                    },
+                ]
+            }
+        },
+        (EditOp::Delete { range }, EditOp::Delete { range: other }) => {
+            let start = map_past_delete(range.start, other);
+            let end = map_past_delete(range.end, other);
+            if start < end {
+                vec![EditOp::Delete { range: start..end }]
+            } else {
+                Vec::new()
+            }
+        },
+    }
+}
+
+/// Where `offset` lands once `deleted` is removed. Offsets inside the deleted
+/// range collapse to its start.
+fn map_past_delete(offset: usize, deleted: &Range<usize>) -> usize {
+    if offset <= deleted.start {
+        offset
+    } else if offset >= deleted.end {
+        offset - deleted.len()
+    } else {
+        deleted.start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::TestHarness;
+    use std::path::{Path, PathBuf};
+
+    fn stamp(value: u64, replica: u16) -> Lamport {
+        Lamport {
+            value,
+            replica: ReplicaId(replica),
+        }
+    }
+
+    fn apply(text: &str, ops: &[EditOp]) -> String {
+        let mut text = text.to_string();
+        for op in ops {
+            match op {
+                EditOp::Insert { at, text: inserted } => text.insert_str(*at, inserted),
+                EditOp::Delete { range } => text.replace_range(range.clone(), ""),
+            }
+        }
+        text
+    }
+
+    fn assert_converges(base: &str, a: Vec<EditOp>, b: Vec<EditOp>) -> String {
+        let (a_id, b_id) = (stamp(1, 1), stamp(1, 2));
+        let (a_after_b, b_after_a) = transform_batches(a.clone(), a_id, b.clone(), b_id);
+        let one = apply(&apply(base, &a), &b_after_a);
+        let two = apply(&apply(base, &b), &a_after_b);
+        assert_eq!(one, two, "both application orders must agree");
+        one
+    }
+
+    #[test]
+    fn concurrent_inserts_at_one_offset_order_by_stamp() {
+        let merged = assert_converges(
+            "ac",
+            vec![EditOp::Insert {
+                at: 1,
+                text: "X".into(),
+            }],
+            vec![EditOp::Insert {
+                at: 1,
+                text: "Y".into(),
+            }],
+        );
+        assert_eq!(merged, "aXYc");
+    }
+
+    #[test]
+    fn delete_spanning_a_concurrent_insert_keeps_the_insert() {
+        let merged = assert_converges(
+            "abcdefgh",
+            vec![EditOp::Delete { range: 2..6 }],
+            vec![EditOp::Insert {
+                at: 4,
+                text: "zz".into(),
+            }],
+        );
+        assert_eq!(merged, "abzzgh");
+    }
+
+    #[test]
+    fn overlapping_deletes_remove_the_union_once() {
+        let merged = assert_converges(
+            "abcdefgh",
+            vec![EditOp::Delete { range: 2..6 }],
+            vec![EditOp::Delete { range: 4..8 }],
+        );
+        assert_eq!(merged, "ab");
+    }
+
+    #[test]
+    fn multi_op_batches_converge() {
+        assert_converges(
+            "hello world",
+            vec![
+                EditOp::Delete { range: 0..5 },
+                EditOp::Insert {
+                    at: 0,
+                    text: "howdy".into(),
+                },
+            ],
+            vec![
+                EditOp::Insert {
+                    at: 11,
+                    text: "!".into(),
+                },
+                EditOp::Delete { range: 3..8 },
+            ],
+        );
+    }
+
+    fn open_shared(h: &mut TestHarness, text: &str) -> BufferId {
+        let path = PathBuf::from("/collab/doc.txt");
+        h.fake_fs().insert_file(&path, text);
+        h.open_file(&path);
+        h.stoat
+            .active_workspace()
+            .buffers
+            .id_for_path(Path::new("/collab/doc.txt"))
+            .expect("buffer open")
+    }
+
+    fn text_of(h: &TestHarness, id: BufferId) -> String {
+        let buffer = h.stoat.active_workspace().buffers.get(id).expect("buffer");
+        let guard = buffer.read().expect("buffer poisoned");
+        guard.rope().to_string()
+    }
+
+    fn edit(h: &mut TestHarness, id: BufferId, range: Range<usize>, text: &str) {
+        let buffer = h.stoat.active_workspace().buffers.get(id).expect("buffer");
+        buffer.write().expect("buffer poisoned").edit(range, text);
+    }
+
+    fn pair() -> (TestHarness, BufferId, TestHarness, BufferId) {
+        let mut a = TestHarness::with_size(40, 10);
+        let mut b = TestHarness::with_size(40, 10);
+        let a_id = open_shared(&mut a, "one\ntwo\n");
+        let b_id = open_shared(&mut b, "one\ntwo\n");
+        let (a_end, b_end) = ChannelTransport::pair();
+        a.stoat.share_buffer(a_id, ReplicaId(1), Box::new(a_end));
+        b.stoat.share_buffer(b_id, ReplicaId(2), Box::new(b_end));
+        (a, a_id, b, b_id)
+    }
+
+    #[test]
+    fn a_local_edit_reaches_the_peer() {
+        let (mut a, a_id, mut b, b_id) = pair();
+
+        edit(&mut a, a_id, 0..3, "ONE");
+        pump(&mut a.stoat);
+        pump(&mut b.stoat);
+
+        assert_eq!(text_of(&b, b_id), "ONE\ntwo\n");
+        assert_eq!(
+            b.stoat.active_workspace().collab[&b_id].log().len(),
+            1,
+            "the peer logs the remote batch"
+        );
+    }
+
+    #[test]
+    fn concurrent_edits_on_two_instances_converge() {
+        let (mut a, a_id, mut b, b_id) = pair();
+
+        edit(&mut a, a_id, 3..3, " 1");
+        edit(&mut b, b_id, 4..7, "TWO");
+        edit(&mut b, b_id, 0..0, "> ");
+        pump(&mut a.stoat);
+        pump(&mut b.stoat);
+        pump(&mut a.stoat);
+
+        assert_eq!(text_of(&a, a_id), "> one 1\nTWO\n");
+        assert_eq!(text_of(&a, a_id), text_of(&b, b_id));
+    }
+
+    #[test]
+    fn applied_remote_edits_are_not_echoed_back() {
+        let (mut a, a_id, mut b, b_id) = pair();
+
+        edit(&mut a, a_id, 0..0, "x");
+        pump(&mut a.stoat);
+        pump(&mut b.stoat);
+        pump(&mut b.stoat);
+        pump(&mut a.stoat);
+
+        assert_eq!(text_of(&a, a_id), "xone\ntwo\n");
+        assert_eq!(text_of(&b, b_id), "xone\ntwo\n");
+    }
+}
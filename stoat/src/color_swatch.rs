@@ -0,0 +1,384 @@
+//! Color swatches for color literals.
+//!
+//! Hex (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`) and `rgb()` / `rgba()`
+//! literals inside the comment and string tokens of a parsed buffer get a
+//! [`SWATCH`] inlay painted in their color, on by default via
+//! `editor.color_swatches`. [`open_picker`] edits the literal under the
+//! cursor through a small prompt that previews the typed color.
+
+use crate::{
+    app::{Stoat, UpdateEffect},
+    display_map::InlayKind,
+    editor_state::EditorId,
+    input_view::{InputView, SubmitTarget},
+};
+use std::ops::Range;
+use stoat_language::Tree;
+use stoat_text::{Anchor, Bias, Rope};
+
+/// Inlay text drawn before each color literal.
+pub(crate) const SWATCH: &str = "■ ";
+
+/// A color literal found in a buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ColorLiteral {
+    /// Bytes of the literal, `#` or `rgb(` through the last digit or `)`.
+    pub(crate) range: Range<usize>,
+    pub(crate) rgb: [u8; 3],
+}
+
+/// The open color picker prompt.
+pub(crate) struct ColorInputState {
+    pub(crate) input: InputView,
+    /// Editor holding the literal being edited.
+    editor_id: EditorId,
+    /// The literal, anchored so edits elsewhere while the prompt is open
+    /// still replace the right bytes.
+    range: Range<Anchor>,
+    /// Offset of the literal's start, where the popup is placed.
+    pub(crate) anchor_offset: usize,
+}
+
+/// Every color literal inside the comment and string tokens of `tree`,
+/// parsed from `rope`, in source order.
+pub(crate) fn color_literals(tree: &Tree, rope: &Rope) -> Vec<ColorLiteral> {
+    let mut out = Vec::new();
+    for range in crate::spell::checked_ranges(tree) {
+        let text: String = rope.chunks_in_range(range.clone()).collect();
+        out.extend(scan(&text).into_iter().map(|lit| ColorLiteral {
+            range: range.start + lit.range.start..range.start + lit.range.end,
+            rgb: lit.rgb,
+        }));
+    }
+    out
+}
+
+/// Color literals in `text`. A literal must not be glued to a word on either
+/// side, so `a#fff`, `#fffz` and `argb(..)` are skipped.
+fn scan(text: &str) -> Vec<ColorLiteral> {
+    let bytes = text.as_bytes();
+    let is_word = |i: usize| {
+        bytes
+            .get(i)
+            .is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'_')
+    };
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if i > 0 && is_word(i - 1) {
+            i += 1;
+            continue;
+        }
+        let end = if bytes[i] == b'#' {
+            let digits = bytes[i + 1..]
+                .iter()
+                .take_while(|b| b.is_ascii_hexdigit())
+                .count();
+            Some(i + 1 + digits)
+        } else if bytes[i] == b'r' && text[i..].starts_with("rgb") {
+            text[i..].find(')').map(|close| i + close + 1)
+        } else {
+            None
+        };
+        let found = end
+            .filter(|&end| !is_word(end))
+            .and_then(|end| parse_color(&text[i..end]).map(|rgb| (end, rgb)));
+        match found {
+            Some((end, rgb)) => {
+                out.push(ColorLiteral { range: i..end, rgb });
+                i = end;
+            },
+            None => i += 1,
+        }
+    }
+    out
+}
+
+/// The color `text` spells, when all of it is one hex or `rgb()` literal.
+/// Alpha is accepted and dropped, since a terminal cell is opaque.
+pub(crate) fn parse_color(text: &str) -> Option<[u8; 3]> {
+    if let Some(hex) = text.strip_prefix('#') {
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok();
+        let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return match hex.len() {
+            3 | 4 => Some([digit(0)? * 17, digit(1)? * 17, digit(2)? * 17]),
+            6 | 8 => Some([pair(0)?, pair(2)?, pair(4)?]),
+            _ => None,
+        };
+    }
+    let (args, arity) = if let Some(rest) = text.strip_prefix("rgba(") {
+        (rest, 4)
+    } else {
+        (text.strip_prefix("rgb(")?, 3)
+    };
+    let parts: Vec<&str> = args.strip_suffix(')')?.split(',').map(str::trim).collect();
+    if parts.len() != arity {
+        return None;
+    }
+    if arity == 4 && parts[3].parse::<f32>().is_err() {
+        return None;
+    }
+    let channel = |part: &str| match part.strip_suffix('%') {
+        Some(pct) => pct
+            .parse::<f32>()
+            .ok()
+            .filter(|p| (0.0..=100.0).contains(p))
+            .map(|p| (p * 2.55).round() as u8),
+        None => part.parse::<u8>().ok(),
+    };
+    Some([channel(parts[0])?, channel(parts[1])?, channel(parts[2])?])
+}
+
+/// Reconcile the swatch inlays of every editor in the active workspace whose
+/// buffer reparsed since its last scan. With swatches disabled, removes any a
+/// prior enabled run spliced. An editor whose buffer moved past its syntax
+/// tree waits for the reparse, since the tree's offsets would be stale.
+pub(crate) fn refresh(stoat: &mut Stoat) {
+    let enabled = stoat.settings.color_swatches != Some(false);
+    let ws = stoat.active_workspace_mut();
+    for editor in ws.editors.values_mut() {
+        let syntax = ws
+            .buffers
+            .syntax(editor.buffer_id)
+            .filter(|_| enabled && editor.review_view.is_none());
+        let Some(syntax) = syntax else {
+            let (_, prev) = std::mem::take(&mut editor.swatch_inlays);
+            if !prev.is_empty() {
+                editor.display_map.splice_inlays(prev, Vec::new());
+            }
+            continue;
+        };
+        if editor.swatch_inlays.0 == Some(syntax.version) {
+            continue;
+        }
+        let inserts: Vec<(Anchor, String, InlayKind)> = {
+            let snapshot = editor.display_map.snapshot();
+            let buf_snap = snapshot.buffer_snapshot();
+            if !buf_snap.is_singleton() || buf_snap.version() != syntax.version {
+                continue;
+            }
+            color_literals(&syntax.tree, &syntax.rope_snapshot)
+                .into_iter()
+                .map(|lit| {
+                    (
+                        buf_snap.anchor_at(lit.range.start, Bias::Right),
+                        SWATCH.to_string(),
+                        InlayKind::Swatch(lit.rgb),
+                    )
+                })
+                .collect()
+        };
+        let prev = std::mem::take(&mut editor.swatch_inlays.1);
+        let ids = editor.display_map.reconcile_inlays(prev, inserts);
+        editor.swatch_inlays = (Some(syntax.version), ids);
+    }
+}
+
+/// Open the color picker on the color literal under the focused cursor.
+pub(crate) fn open_picker(stoat: &mut Stoat) -> UpdateEffect {
+    if stoat.color_input.is_some() {
+        return UpdateEffect::None;
+    }
+    let Some((editor_id, buffer_id)) = stoat.focused_editor_ids() else {
+        return UpdateEffect::None;
+    };
+    let executor = stoat.executor.clone();
+    let ws = stoat.active_workspace_mut();
+    let Some(syntax) = ws.buffers.syntax(buffer_id) else {
+        stoat.set_status("no color under the cursor");
+        return UpdateEffect::Redraw;
+    };
+    let Some(editor) = ws.editors.get_mut(editor_id) else {
+        return UpdateEffect::None;
+    };
+    let snapshot = editor.display_map.snapshot();
+    let buf_snap = snapshot.buffer_snapshot();
+    let cursor = buf_snap.resolve_anchor(&editor.selections.newest_anchor().head());
+    let literal = (buf_snap.version() == syntax.version)
+        .then(|| color_literals(&syntax.tree, &syntax.rope_snapshot))
+        .into_iter()
+        .flatten()
+        .find(|lit| lit.range.start <= cursor && cursor < lit.range.end);
+    let Some(literal) = literal else {
+        stoat.set_status("no color under the cursor");
+        return UpdateEffect::Redraw;
+    };
+    let seed: String = syntax
+        .rope_snapshot
+        .chunks_in_range(literal.range.clone())
+        .collect();
+    let range = buf_snap.anchor_at(literal.range.start, Bias::Left)
+        ..buf_snap.anchor_at(literal.range.end, Bias::Right);
+    let input = InputView::create(ws, executor, SubmitTarget::ColorPicker, &seed, "insert", 1);
+    stoat.color_input = Some(ColorInputState {
+        input,
+        editor_id,
+        range,
+        anchor_offset: literal.range.start,
+    });
+    UpdateEffect::Redraw
+}
+
+/// Replace the edited literal with the typed color. An entry that is not a
+/// color keeps the picker open and reports. Returns `true` when the picker
+/// was open.
+pub(crate) fn submit(stoat: &mut Stoat) -> bool {
+    let Some(state) = stoat.color_input.take() else {
+        return false;
+    };
+    let typed = state.input.text(stoat.active_workspace());
+    let typed = typed.trim();
+    if parse_color(typed).is_none() {
+        stoat.set_status(format!("not a color: {typed}"));
+        stoat.color_input = Some(state);
+        return true;
+    }
+    let ws = stoat.active_workspace_mut();
+    state.input.dispose(ws);
+    let Some(editor) = ws.editors.get_mut(state.editor_id) else {
+        return true;
+    };
+    let range = {
+        let snapshot = editor.display_map.snapshot();
+        let buf_snap = snapshot.buffer_snapshot();
+        buf_snap.resolve_anchor(&state.range.start)..buf_snap.resolve_anchor(&state.range.end)
+    };
+    if let Some(buffer) = ws.buffers.get(editor.buffer_id) {
+        buffer.write().expect("buffer poisoned").edit(range, typed);
+    }
+    true
+}
+
+/// Close the color picker without editing. Returns `true` when it was open.
+pub(crate) fn cancel(stoat: &mut Stoat) -> bool {
+    let Some(state) = stoat.color_input.take() else {
+        return false;
+    };
+    state.input.dispose(stoat.active_workspace_mut());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        action_handlers::dispatch,
+        test_harness::{keys, TestHarness},
+    };
+    use crossterm::event::{Event, KeyCode};
+
+    fn scanned(text: &str) -> Vec<(&str, [u8; 3])> {
+        scan(text)
+            .into_iter()
+            .map(|lit| (&text[lit.range], lit.rgb))
+            .collect()
+    }
+
+    fn open_rust(h: &mut TestHarness, source: &str) {
+        let path = h.write_file("colors.rs", source);
+        h.open_file(&path);
+        h.settle();
+        h.stoat.drive_background();
+    }
+
+    #[test]
+    fn scan_finds_hex_and_rgb_literals() {
+        assert_eq!(
+            scanned("fg #f80, bg #00ff0080 and rgb(255, 0, 10%) or rgba(0,0,255,0.5)"),
+            vec![
+                ("#f80", [0xff, 0x88, 0x00]),
+                ("#00ff0080", [0x00, 0xff, 0x00]),
+                ("rgb(255, 0, 10%)", [255, 0, 26]),
+                ("rgba(0,0,255,0.5)", [0, 0, 255]),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_skips_glued_and_malformed_literals() {
+        assert!(
+            scanned("issue#123 a#fff #fffz #12345 rgb(1,2) rgb(300,0,0) argb(1,2,3)").is_empty()
+        );
+    }
+
+    #[test]
+    fn swatches_render_before_string_literals_only() {
+        let mut h = TestHarness::with_size(60, 10);
+        open_rust(
+            &mut h,
+            "// #ff0000\nconst A: &str = \"#00ff00\";\nconst B: u32 = 0;\n",
+        );
+        let frame = h.snapshot();
+        assert!(frame.content.contains("// ■ #ff0000"), "{}", frame.content);
+        assert!(frame.content.contains("\"■ #00ff00\""), "{}", frame.content);
+
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        assert_eq!(editor.swatch_inlays.1.len(), 2);
+    }
+
+    #[test]
+    fn disabling_swatches_removes_them() {
+        let mut h = TestHarness::with_size(60, 10);
+        open_rust(&mut h, "const A: &str = \"#00ff00\";\n");
+        h.stoat.settings.color_swatches = Some(false);
+        h.stoat.drive_background();
+        let frame = h.snapshot();
+        assert!(!frame.content.contains('■'), "{}", frame.content);
+    }
+
+    #[test]
+    fn picker_replaces_the_literal_under_the_cursor() {
+        let mut h = TestHarness::with_size(60, 10);
+        open_rust(&mut h, "const A: &str = \"#00ff00\";\n");
+        h.stoat
+            .collapse_focused_cursor_to("const A: &str = \"#0".len());
+        dispatch(&mut h.stoat, &stoat_action::EditColorAtCursor);
+        let state = h.stoat.color_input.as_ref().expect("picker open");
+        assert_eq!(state.input.text(h.stoat.active_workspace()), "#00ff00");
+
+        for _ in 0.."#00ff00".len() {
+            h.stoat.update(Event::Key(keys::key(KeyCode::Backspace)));
+        }
+        h.type_text("rgb(1, 2, 3)");
+        h.stoat.update(Event::Key(keys::key(KeyCode::Enter)));
+        assert!(h.stoat.color_input.is_none());
+
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        let snapshot = editor.display_map.snapshot();
+        assert_eq!(
+            snapshot.buffer_snapshot().rope().to_string(),
+            "const A: &str = \"rgb(1, 2, 3)\";\n"
+        );
+    }
+
+    #[test]
+    fn picker_keeps_open_on_invalid_color() {
+        let mut h = TestHarness::with_size(60, 10);
+        open_rust(&mut h, "const A: &str = \"#00ff00\";\n");
+        h.stoat
+            .collapse_focused_cursor_to("const A: &str = \"".len());
+        dispatch(&mut h.stoat, &stoat_action::EditColorAtCursor);
+        h.type_text("z");
+        h.stoat.update(Event::Key(keys::key(KeyCode::Enter)));
+        assert!(h.stoat.color_input.is_some());
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("not a color: #00ff00z")
+        );
+    }
+
+    #[test]
+    fn picker_off_a_color_reports() {
+        let mut h = TestHarness::with_size(60, 10);
+        open_rust(&mut h, "const B: u32 = 0;\n");
+        dispatch(&mut h.stoat, &stoat_action::EditColorAtCursor);
+        assert!(h.stoat.color_input.is_none());
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("no color under the cursor")
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Commands run from the palette, logged per workspace.
+//!
+//! Each run records the action's registry name, its arguments as the palette
+//! parsed them, and when it ran. The log persists with the workspace.
+//! [`repeat_last`] runs the newest entry again, and `:history ` lists every
+//! logged line newest first so one can be picked and re-run.
+
+use crate::app::{Stoat, UpdateEffect};
+use serde::{Deserialize, Serialize};
+use std::time::UNIX_EPOCH;
+use stoat_action::{registry, ActionDef, ActionKind, ParamValue};
+
+/// Entries kept per workspace, evicting the oldest past this.
+const MAX_RUNS: usize = 200;
+
+/// One command run from the palette.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandRun {
+    /// Registry name of the action, such as `SetCwd`.
+    pub name: String,
+    /// Each argument written the way [`ParamValue::parse`] reads it back.
+    pub args: Vec<String>,
+    /// The line the palette recorded, such as `cd ~/work`, for listing.
+    pub line: String,
+    /// Seconds since the Unix epoch when the command ran.
+    pub at: u64,
+}
+
+/// Log a run of `def` with `params` in the active workspace. The history
+/// actions themselves are not logged, so repeating never repeats a repeat.
+pub(crate) fn record(stoat: &mut Stoat, def: &dyn ActionDef, params: &[ParamValue], line: String) {
+    if matches!(
+        def.kind(),
+        ActionKind::RepeatLastCommand | ActionKind::RunFromHistory
+    ) {
+        return;
+    }
+    let at = stoat
+        .executor
+        .system_now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let log = &mut stoat.active_workspace_mut().command_log;
+    log.push(CommandRun {
+        name: def.name().to_string(),
+        args: params.iter().map(ToString::to_string).collect(),
+        line,
+        at,
+    });
+    if log.len() > MAX_RUNS {
+        log.remove(0);
+    }
+}
+
+/// The distinct logged lines, newest first, for the `:history ` picker.
+pub(crate) fn lines(stoat: &Stoat) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for run in stoat.active_workspace().command_log.iter().rev() {
+        if !lines.contains(&run.line) {
+            lines.push(run.line.clone());
+        }
+    }
+    lines
+}
+
+/// Run the newest logged command again.
+pub(crate) fn repeat_last(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(run) = stoat.active_workspace().command_log.last().cloned() else {
+        stoat.set_status("no command to repeat");
+        return UpdateEffect::Redraw;
+    };
+    rerun(stoat, run)
+}
+
+/// Run the newest logged command whose line is `line` again.
+pub(crate) fn rerun_line(stoat: &mut Stoat, line: &str) -> UpdateEffect {
+    let log = &stoat.active_workspace().command_log;
+    let Some(run) = log.iter().rev().find(|run| run.line == line).cloned() else {
+        stoat.set_status(format!("not in command history: {line}"));
+        return UpdateEffect::Redraw;
+    };
+    rerun(stoat, run)
+}
+
+fn rerun(stoat: &mut Stoat, run: CommandRun) -> UpdateEffect {
+    let Some(entry) = registry::lookup(&run.name) else {
+        stoat.set_status(format!("unknown action: {}", run.name));
+        return UpdateEffect::Redraw;
+    };
+    let params = entry
+        .def
+        .params()
+        .iter()
+        .zip(&run.args)
+        .map(|(param, arg)| ParamValue::parse(param.kind, arg))
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|params| Ok(((entry.create)(&params)?, params)));
+    let (action, params) = match params {
+        Ok(created) => created,
+        Err(e) => {
+            stoat.set_status(format!("{}: {e}", run.line));
+            return UpdateEffect::Redraw;
+        },
+    };
+    record(stoat, entry.def, &params, run.line);
+    crate::action_handlers::dispatch(stoat, &*action)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{action_handlers::dispatch, app::Stoat};
+    use std::path::PathBuf;
+
+    #[test]
+    fn palette_runs_are_logged_and_repeat() {
+        let mut h = Stoat::test();
+        let root = PathBuf::from("/log-test");
+        let sub = root.join("sub");
+        h.fake_fs().insert_dir(&sub);
+        h.stoat.active_workspace_mut().git_root = root.clone();
+
+        h.type_text(&format!(":cd {}", sub.display()));
+        h.type_keys("enter");
+        let log = &h.stoat.active_workspace().command_log;
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].name, "SetCwd");
+        assert_eq!(log[0].args, [sub.display().to_string()]);
+
+        h.stoat.active_workspace_mut().git_root = root.clone();
+        dispatch(&mut h.stoat, &stoat_action::RepeatLastCommand);
+        assert_eq!(h.stoat.active_workspace().git_root, sub);
+        assert_eq!(h.stoat.active_workspace().command_log.len(), 2);
+
+        h.stoat.active_workspace_mut().git_root = root;
+        let line = h.stoat.active_workspace().command_log[0].line.clone();
+        dispatch(&mut h.stoat, &stoat_action::RunFromHistory { line });
+        assert_eq!(h.stoat.active_workspace().git_root, sub);
+        assert_eq!(super::lines(&h.stoat).len(), 1, "lines are listed once");
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     fuzzy,
     host::FsHost,
     input_view::{InputView, SubmitTarget},
+    keymap::{Keymap, KeymapState},
     pane::{FocusTarget, View},
     paths,
     picker::{PathPicker, PreviewPolicy},
@@ -11,7 +12,7 @@ use crate::{
     workspace::Workspace,
 };
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
 };
@@ -29,9 +30,13 @@ pub struct CommandPalette {
     /// Empty while [`Self::command`] is set, since arg mode replaces the
     /// action list with the argument picker.
     pub(crate) filtered: Vec<&'static registry::RegistryEntry>,
-    /// Per-row matched character offsets into each entry's name, parallel to
+    /// Per-row matched character offsets into each row's label, parallel to
     /// [`Self::filtered`], used by the renderer to highlight matched cells.
     pub(crate) match_indices: Vec<Vec<u32>>,
+    /// Per-row display label, parallel to [`Self::filtered`]. `Some` names the
+    /// configured [`PaletteAlias`] the row matched through, which the row shows
+    /// in place of the action's command name. `None` shows the command name.
+    pub(crate) labels: Vec<Option<String>>,
     pub(crate) selected: usize,
     /// `Some` once the input parses as a known command followed by a space:
     /// the palette is collecting that command's trailing argument inline. The
@@ -40,6 +45,17 @@ pub struct CommandPalette {
     /// Which subset of actions the palette currently lists. Captured at
     /// open time and toggled by `PaletteScopeToggle` (Shift-Tab).
     pub(crate) scope: PaletteScope,
+    /// Configured aliases, resolved when the palette opened. Matched and
+    /// submitted like command names, but always run the aliased action.
+    pub(crate) aliases: Vec<PaletteAlias>,
+    /// The key that runs each action in the mode the palette opened over, keyed
+    /// by action name and built by [`key_hints`]. The renderer shows it at the
+    /// right edge of the action's row.
+    pub(crate) key_hints: HashMap<String, String>,
+    /// Action names the palette has run, most recent first, resolved from the
+    /// workspace's palette history when the palette opened. An empty filter
+    /// lists these ahead of the priority order.
+    recent: Vec<&'static str>,
     /// Snapshot of contextual state derived from [`Stoat`] when the palette
     /// opened. Reused across every [`CommandPalette::refilter_from_input`]
     /// call because the workspace cannot mutate while the palette is modal.
@@ -258,9 +274,14 @@ impl ArgPicker {
     ) {
         let policy = match self.source {
             ValueSource::Buffers => PreviewPolicy::LiveBufferThenFile,
-            ValueSource::Directories | ValueSource::Themes | ValueSource::Values(_) => {
-                PreviewPolicy::NoPreview
-            },
+            ValueSource::Directories
+            | ValueSource::Themes
+            | ValueSource::Tasks
+            | ValueSource::Commands
+            | ValueSource::CommandHistory
+            | ValueSource::BufferGroups
+            | ValueSource::Notifications
+            | ValueSource::Values(_) => PreviewPolicy::NoPreview,
             _ => PreviewPolicy::File,
         };
         self.active_core()
@@ -294,6 +315,39 @@ pub enum PaletteScope {
     All,
 }
 
+/// A user-defined palette name for an action, from an `alias "name" -> Action;`
+/// config statement.
+///
+/// The name is one more spelling the palette matches, so a localized or
+/// personal name finds the action, while submitting it still runs the
+/// canonical action.
+#[derive(Clone)]
+pub(crate) struct PaletteAlias {
+    pub(crate) name: String,
+    pub(crate) entry: &'static registry::RegistryEntry,
+}
+
+impl PaletteAlias {
+    /// Resolve the configured `alias -> action` table against the registry.
+    ///
+    /// The action side resolves like a typed command, so it may be an action
+    /// name, a command name, or a builtin alias. Entries naming no registered
+    /// action are dropped here, since the config language server already warns
+    /// about them.
+    pub(crate) fn resolve_all(table: &BTreeMap<String, String>) -> Vec<Self> {
+        table
+            .iter()
+            .filter_map(|(name, action)| {
+                let entry = registry::lookup_alias(action)?;
+                Some(Self {
+                    name: name.clone(),
+                    entry,
+                })
+            })
+            .collect()
+    }
+}
+
 /// Snapshot of stoat state relevant to per-action availability. Booleans are
 /// derived once at palette-open via [`Availability::from_stoat`] so the scope
 /// filter is a cheap lookup on every keystroke.
@@ -417,27 +471,42 @@ pub(crate) enum PaletteOutcome {
 }
 
 impl CommandPalette {
-    pub fn new(ws: &mut Workspace, executor: Executor, availability: Availability) -> Self {
+    pub fn new(
+        ws: &mut Workspace,
+        executor: Executor,
+        availability: Availability,
+        aliases: Vec<PaletteAlias>,
+        key_hints: HashMap<String, String>,
+    ) -> Self {
+        let recent = recent_commands(ws.palette_history.entries(), &aliases);
         let input = InputView::create(ws, executor, SubmitTarget::PaletteFilter, "", "insert", 1);
         let scope = PaletteScope::Active;
         let mut filtered = Vec::new();
         let mut match_indices = Vec::new();
+        let mut labels = Vec::new();
         let mut selected = 0;
         refilter(
             "",
             scope,
             &availability,
+            &aliases,
+            &recent,
             &mut filtered,
             &mut match_indices,
+            &mut labels,
             &mut selected,
         );
         Self {
             input,
             filtered,
             match_indices,
+            labels,
             selected,
             command: None,
             scope,
+            aliases,
+            key_hints,
+            recent,
             availability,
             viewport_rows: None,
             arg_picker: None,
@@ -580,18 +649,22 @@ impl CommandPalette {
         self.last_filter_key = key;
         self.generation = crate::picker::next_generation();
 
-        self.command = parse_command(&text).map(|(entry, _)| entry);
+        self.command = parse_command(&text, &self.aliases).map(|(entry, _)| entry);
         if self.command.is_some() {
             self.filtered.clear();
             self.match_indices.clear();
+            self.labels.clear();
             self.selected = 0;
         } else {
             refilter(
                 &text,
                 self.scope,
                 &self.availability,
+                &self.aliases,
+                &self.recent,
                 &mut self.filtered,
                 &mut self.match_indices,
+                &mut self.labels,
                 &mut self.selected,
             );
         }
@@ -607,7 +680,7 @@ impl CommandPalette {
     /// `SubmitPromptInput` action handler while the palette is open.
     pub(crate) fn handle_submit(&mut self, ws: &mut Workspace) -> PaletteOutcome {
         let text = self.input.text(ws);
-        if let Some((entry, arg)) = parse_command(&text) {
+        if let Some((entry, arg)) = parse_command(&text, &self.aliases) {
             let param = &entry.def.params()[0];
             // An explicit `/` or `~` path browses the real filesystem, so a
             // highlighted browse directory wins and Enter descends into it.
@@ -651,7 +724,7 @@ impl CommandPalette {
         // alias like `w!` stays reachable. Arrowing to a candidate takes that
         // highlighted entry instead.
         if self.selected == 0
-            && let Some(entry) = registry::lookup_alias(text.trim())
+            && let Some((entry, _)) = lookup_token(&self.aliases, text.trim())
         {
             if dispatches_bare(entry) {
                 self.input.dispose(ws);
@@ -681,18 +754,66 @@ impl CommandPalette {
     }
 }
 
+/// Resolve a whole typed `token` to an action, trying the registry's names and
+/// aliases (via [`registry::lookup_alias`]) before the configured `aliases`.
+///
+/// A configured match also returns the alias name, which the palette shows as
+/// the row's label.
+fn lookup_token<'a>(
+    aliases: &'a [PaletteAlias],
+    token: &str,
+) -> Option<(&'static registry::RegistryEntry, Option<&'a str>)> {
+    if let Some(entry) = registry::lookup_alias(token) {
+        return Some((entry, None));
+    }
+    aliases
+        .iter()
+        .find(|alias| alias.name.eq_ignore_ascii_case(token))
+        .map(|alias| (alias.entry, Some(alias.name.as_str())))
+}
+
 /// Split palette input into a resolved command and its trailing argument text.
 ///
 /// Returns `Some((entry, arg))` only when the text is a command token followed
 /// by a space. The token is a command name or alias (resolved by
-/// [`registry::lookup_alias`]) and the command must take at least one
-/// parameter. `arg` is everything after the first space, so a path argument may
-/// itself contain spaces. Returns `None` for plain filter text, an unknown
-/// head, or a zero-argument command, keeping the palette in command-filter
-/// mode.
-fn parse_command(text: &str) -> Option<(&'static registry::RegistryEntry, &str)> {
+/// [`registry::lookup_alias`]) or a configured [`PaletteAlias`], and the
+/// command must take at least one parameter. `arg` is everything after the
+/// token's space, so a path argument may itself contain spaces. Returns `None`
+/// for plain filter text, an unknown head, or a zero-argument command, keeping
+/// the palette in command-filter mode.
+///
+/// A configured alias may contain spaces. Text that still spells the start of
+/// one stays in filter mode, so `open project` is not cut short at `open ` by
+/// the `open` command, and a multi-word alias head outranks a one-word command.
+fn parse_command<'a>(
+    text: &'a str,
+    aliases: &[PaletteAlias],
+) -> Option<(&'static registry::RegistryEntry, &'a str)> {
     let (head, arg) = text.split_once(' ')?;
-    let entry = registry::lookup_alias(head)?;
+    let spelling_alias = aliases.iter().any(|alias| {
+        alias
+            .name
+            .get(..text.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(text))
+    });
+    if spelling_alias {
+        return None;
+    }
+    let alias_head = aliases
+        .iter()
+        .filter(|alias| {
+            text.get(..alias.name.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&alias.name))
+                && text[alias.name.len()..].starts_with(' ')
+        })
+        .max_by_key(|alias| alias.name.len());
+    let (entry, arg) = match alias_head {
+        Some(alias) if alias.name.contains(' ') => (alias.entry, &text[alias.name.len() + 1..]),
+        _ => match registry::lookup_alias(head) {
+            Some(entry) => (entry, arg),
+            None => (alias_head?.entry, arg),
+        },
+    };
     (!entry.def.params().is_empty()).then_some((entry, arg))
 }
 
@@ -726,13 +847,61 @@ fn history_head(entry: &registry::RegistryEntry) -> &'static str {
         .unwrap_or_else(|| entry.def.name())
 }
 
+/// Map each action to the key that runs it in `state`, the context the palette
+/// opens over, from `keymap`'s [`Keymap::winning_bindings`].
+///
+/// Only a binding whose whole sequence is that one action without arguments
+/// counts, so a key bound to `AutoReload(follow)` is not offered as the key for
+/// a bare `AutoReload`. When several keys qualify the first in keymap order
+/// wins.
+pub(crate) fn key_hints(keymap: &Keymap, state: &dyn KeymapState) -> HashMap<String, String> {
+    let mut hints = HashMap::new();
+    for (label, actions) in keymap.winning_bindings(state) {
+        if let [action] = actions
+            && action.args.is_empty()
+        {
+            hints.entry(action.name.clone()).or_insert(label);
+        }
+    }
+    hints
+}
+
+/// Resolve palette `history` lines, stored oldest-first, to the action names
+/// they ran, most recent first and without repeats.
+///
+/// A line resolves as a whole token first, so a bare `w` or a multi-word alias
+/// maps to its action, then as `<command> <arg>` through [`parse_command`].
+/// Lines naming an action or alias that no longer exists are skipped.
+fn recent_commands(history: &[String], aliases: &[PaletteAlias]) -> Vec<&'static str> {
+    let mut recent: Vec<&'static str> = Vec::new();
+    for line in history.iter().rev() {
+        let entry = lookup_token(aliases, line.trim())
+            .map(|(entry, _)| entry)
+            .or_else(|| parse_command(line, aliases).map(|(entry, _)| entry));
+        if let Some(entry) = entry
+            && !recent.contains(&entry.def.name())
+        {
+            recent.push(entry.def.name());
+        }
+    }
+    recent
+}
+
+/// Rebuild the palette's action list for `input`.
+///
+/// An empty `input` lists every passing action with the `recent` ones first, in
+/// most-recent-first order, then the rest by priority and name. Otherwise rows
+/// are ranked by fuzzy score.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn refilter(
     input: &str,
     scope: PaletteScope,
     availability: &Availability,
+    aliases: &[PaletteAlias],
+    recent: &[&'static str],
     filtered: &mut Vec<&'static registry::RegistryEntry>,
     match_indices: &mut Vec<Vec<u32>>,
+    labels: &mut Vec<Option<String>>,
     selected: &mut usize,
 ) {
     let passes = |entry: &registry::RegistryEntry| {
@@ -746,17 +915,34 @@ pub(crate) fn refilter(
 
     filtered.clear();
     match_indices.clear();
+    labels.clear();
 
     let items = visible
         .iter()
         .copied()
-        .map(|entry| (entry, entry.command_name.clone()));
-    let Some(mut matches) = fuzzy::match_and_rank(input, items) else {
+        .map(|entry| (entry, entry.command_name.clone()))
+        .chain(
+            aliases
+                .iter()
+                .filter(|alias| passes(alias.entry))
+                .map(|alias| (alias.entry, alias.name.clone())),
+        );
+    let Some(all_matches) = fuzzy::match_and_rank(input, items) else {
         let mut all = visible;
-        all.sort_by_key(|e| (e.def.priority().ord(), e.command_name.as_str()));
+        all.sort_by_key(|e| {
+            (
+                recent
+                    .iter()
+                    .position(|name| *name == e.def.name())
+                    .unwrap_or(usize::MAX),
+                e.def.priority().ord(),
+                e.command_name.as_str(),
+            )
+        });
         for entry in all {
             filtered.push(entry);
             match_indices.push(Vec::new());
+            labels.push(None);
         }
         if *selected >= filtered.len() {
             *selected = filtered.len().saturating_sub(1);
@@ -764,6 +950,21 @@ pub(crate) fn refilter(
         return;
     };
 
+    // An action matched by its name and by aliases lists once, under whichever
+    // spelling scored best.
+    let mut matches: Vec<fuzzy::RankedMatch<&'static registry::RegistryEntry>> = Vec::new();
+    let mut row_of: HashMap<&'static str, usize> = HashMap::new();
+    for m in all_matches {
+        match row_of.get(m.item.def.name()) {
+            Some(&row) if matches[row].score >= m.score => {},
+            Some(&row) => matches[row] = m,
+            None => {
+                row_of.insert(m.item.def.name(), matches.len());
+                matches.push(m);
+            },
+        }
+    }
+
     matches.sort_by(|a, b| {
         b.score.cmp(&a.score).then_with(|| {
             a.item
@@ -775,8 +976,10 @@ pub(crate) fn refilter(
         })
     });
     for m in matches {
+        let label = (m.haystack != m.item.command_name).then_some(m.haystack);
         filtered.push(m.item);
         match_indices.push(m.matched_indices);
+        labels.push(label);
     }
 
     // Pin an exact name or alias match to the top so the displayed list agrees
@@ -784,24 +987,31 @@ pub(crate) fn refilter(
     // alias, which the name-only fuzzy rank never surfaces first -- or at all,
     // when the name lacks the typed characters.
     let needle = input.trim();
-    if let Some(pinned) = registry::lookup_alias(needle)
+    if let Some((pinned, alias_name)) = lookup_token(aliases, needle)
         && passes(pinned)
     {
-        let indices = match filtered
+        let existing = filtered
             .iter()
             .position(|e| e.def.name() == pinned.def.name())
-        {
-            Some(pos) => {
+            .map(|pos| {
                 filtered.remove(pos);
-                match_indices.remove(pos)
-            },
-            None if pinned.command_name.eq_ignore_ascii_case(needle) => {
-                (0..pinned.command_name.chars().count() as u32).collect()
-            },
-            None => Vec::new(),
+                (match_indices.remove(pos), labels.remove(pos))
+            });
+        let (indices, label) = match (alias_name, existing) {
+            (Some(name), _) => (
+                (0..name.chars().count() as u32).collect(),
+                Some(name.to_string()),
+            ),
+            (None, Some(row)) => row,
+            (None, None) if pinned.command_name.eq_ignore_ascii_case(needle) => (
+                (0..pinned.command_name.chars().count() as u32).collect(),
+                None,
+            ),
+            (None, None) => (Vec::new(), None),
         };
         filtered.insert(0, pinned);
         match_indices.insert(0, indices);
+        labels.insert(0, label);
     }
 
     if *selected >= filtered.len() {
@@ -813,7 +1023,8 @@ pub(crate) fn refilter(
 mod tests {
     use super::*;
     use crate::{
-        buffer_registry::AutoReloadMode, input_history::InputHistory, test_harness::TestHarness,
+        buffer_registry::AutoReloadMode, input_draft::DraftKey, input_history::InputHistory,
+        test_harness::TestHarness,
     };
 
     /// Seed `files` into the harness' fake fs under a fixed virtual root and
@@ -884,13 +1095,17 @@ mod tests {
     ) -> Vec<&'static registry::RegistryEntry> {
         let mut filtered = Vec::new();
         let mut match_indices = Vec::new();
+        let mut labels = Vec::new();
         let mut selected = 0;
         refilter(
             text,
             scope,
             availability,
+            &[],
+            &[],
             &mut filtered,
             &mut match_indices,
+            &mut labels,
             &mut selected,
         );
         filtered
@@ -1044,13 +1259,17 @@ mod tests {
     fn refilter_clamps_selected_when_results_shrink() {
         let mut filtered = Vec::new();
         let mut match_indices = Vec::new();
+        let mut labels = Vec::new();
         let mut selected = 7;
         refilter(
             "quit",
             PaletteScope::All,
             &Availability::default(),
+            &[],
+            &[],
             &mut filtered,
             &mut match_indices,
+            &mut labels,
             &mut selected,
         );
         assert_eq!(filtered.len(), 3);
@@ -1309,6 +1528,39 @@ mod tests {
         assert_eq!(frame.mode, "normal");
     }
 
+    #[test]
+    fn command_palette_escape_keeps_query_as_draft() {
+        let mut h = Stoat::test();
+        h.type_text(":focus");
+        h.type_keys("escape");
+        assert!(h.stoat.command_palette.is_none());
+
+        h.type_text(":");
+        assert_eq!(
+            palette_text(&h),
+            "focus",
+            "reopen restores the dismissed query"
+        );
+        h.type_keys("escape");
+        h.type_text(":");
+        assert_eq!(
+            palette_text(&h),
+            "focus",
+            "the draft survives a second dismiss"
+        );
+    }
+
+    #[test]
+    fn command_palette_submit_leaves_no_draft() {
+        let mut h = Stoat::test();
+        h.type_text(":focus");
+        h.type_keys("down enter");
+        assert!(h.stoat.command_palette.is_none());
+
+        h.type_text(":");
+        assert_eq!(palette_text(&h), "", "a submitted query is not restored");
+    }
+
     #[test]
     fn command_palette_filter_narrows_on_typing() {
         let mut h = Stoat::test();
@@ -1391,6 +1643,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn empty_filter_lists_recent_commands_first() {
+        let mut h = Stoat::test();
+        h.stoat.active_workspace_mut().palette_history = InputHistory::from_entries(
+            ["w", "cd /tmp", "no-such-command", "q", "w"]
+                .map(String::from)
+                .to_vec(),
+        );
+
+        h.type_text(":");
+        let listed: Vec<_> = h
+            .stoat
+            .command_palette
+            .as_ref()
+            .expect("open")
+            .filtered
+            .iter()
+            .map(|e| e.def.name())
+            .collect();
+        assert_eq!(
+            listed[..3],
+            ["SaveBuffer", "Quit", "SetCwd"],
+            "newest first, each once, and an unknown line is skipped"
+        );
+
+        h.type_text("quit");
+        let _ = h.snapshot();
+        let names: Vec<_> = h
+            .stoat
+            .command_palette
+            .as_ref()
+            .expect("open")
+            .filtered
+            .iter()
+            .map(|e| e.command_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            ["quit", "quit-all", "write-quit"],
+            "a typed query ranks by score alone"
+        );
+    }
+
+    #[test]
+    fn palette_hints_the_key_bound_in_the_underlying_mode() {
+        let mut h = Stoat::test();
+        h.type_text(":");
+        let hints = &h.stoat.command_palette.as_ref().expect("open").key_hints;
+        assert_eq!(
+            hints.get("FormatSelections").map(String::as_str),
+            Some("="),
+            "normal mode binds = while the palette itself is in insert"
+        );
+        assert!(
+            !hints.contains_key("SetMode"),
+            "a key that passes arguments is not offered as the bare action's key"
+        );
+    }
+
     #[test]
     fn palette_ctrl_keys_move_the_list_not_history() {
         let mut h = Stoat::test();
@@ -1845,6 +2156,9 @@ mod tests {
         );
 
         h.type_keys("escape");
+        // Escape stashed `set-cwd ` as a draft; drop it so the reopen starts
+        // from an empty query.
+        h.stoat.input_drafts.take(&DraftKey::Palette);
         h.type_text(":quit-al");
         let _ = h.snapshot();
         h.type_keys("tab");
@@ -2272,6 +2586,75 @@ mod tests {
         );
     }
 
+    fn with_aliases(h: &mut TestHarness, aliases: &[(&str, &str)]) {
+        h.stoat.settings.palette_aliases = aliases
+            .iter()
+            .map(|(name, action)| (name.to_string(), action.to_string()))
+            .collect();
+    }
+
+    #[test]
+    fn configured_alias_dispatches_the_aliased_action() {
+        let mut h = Stoat::test();
+        with_aliases(&mut h, &[("speichern", "SaveBuffer")]);
+        assert_eq!(
+            palette_dispatch_name(&mut h, ":Speichern"),
+            Some("SaveBuffer")
+        );
+    }
+
+    #[test]
+    fn configured_alias_fuzzy_matches_and_labels_its_row() {
+        let mut h = Stoat::test();
+        with_aliases(&mut h, &[("dokument speichern", "SaveBuffer")]);
+        h.type_text(":dokspei");
+        let _ = h.snapshot();
+        let palette = h.stoat.command_palette.as_ref().expect("palette open");
+        assert_eq!(palette.filtered[0].def.name(), "SaveBuffer");
+        assert_eq!(
+            palette.labels[0].as_deref(),
+            Some("dokument speichern"),
+            "a row matched through an alias shows the alias"
+        );
+        assert_eq!(
+            palette
+                .filtered
+                .iter()
+                .filter(|e| e.def.name() == "SaveBuffer")
+                .count(),
+            1,
+            "the aliased action lists once"
+        );
+    }
+
+    #[test]
+    fn configured_alias_to_unknown_action_is_dropped() {
+        let aliases = PaletteAlias::resolve_all(&BTreeMap::from([
+            ("kaputt".to_string(), "NoSuchAction".to_string()),
+            ("öffnen".to_string(), "edit".to_string()),
+        ]));
+        assert_eq!(aliases.len(), 1);
+        assert_eq!(aliases[0].name, "öffnen");
+        assert_eq!(aliases[0].entry.def.name(), "OpenFile");
+    }
+
+    #[test]
+    fn multi_word_alias_heads_an_argument() {
+        let aliases = PaletteAlias::resolve_all(&BTreeMap::from([(
+            "o projekt".to_string(),
+            "SetCwd".to_string(),
+        )]));
+        let parsed = |text| parse_command(text, &aliases).map(|(e, arg)| (e.def.name(), arg));
+
+        assert_eq!(
+            parsed("o "),
+            None,
+            "text still spelling the alias stays in filter mode"
+        );
+        assert_eq!(parsed("o src"), Some(("OpenFile", "src")));
+        assert_eq!(parsed("O Projekt /tmp"), Some(("SetCwd", "/tmp")));
+    }
+
     #[test]
     fn palette_optional_param_command_dispatches_bare() {
         let mut h = Stoat::test();
@@ -2663,7 +3046,7 @@ mod tests {
         let mut h = Stoat::test();
         h.type_text(":config ");
         h.snapshot();
-        assert_eq!(arg_rows(&h), ["stoat", "stoatty"]);
+        assert_eq!(arg_rows(&h), ["project", "stoat", "stoatty"]);
     }
 
     #[test]
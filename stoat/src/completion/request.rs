@@ -29,7 +29,8 @@ use crate::{
     app::Stoat,
     buffer::BufferId,
     completion::{
-        applicable_sources, CompletionContext, CompletionItem, CompletionPopup, CompletionSource,
+        applicable_sources, word::BufferWords, CompletionContext, CompletionItem, CompletionPopup,
+        CompletionSource,
     },
     host::{FsHost, LanguageServerFeature, LspHost, OffsetEncoding},
     keymap_state,
@@ -230,6 +231,18 @@ pub(crate) fn trigger(stoat: &mut Stoat) {
         None
     };
 
+    // The focused buffer's retained index is only reused while it matches the
+    // buffer; a keystroke ahead of the reparse rescans the rope instead.
+    let (focused_words, other_words) = if sources.contains(&CompletionSource::Word) {
+        let buffers = &stoat.active_workspace().buffers;
+        let focused = (buffers.syntax_version(snapshot.buffer_id) == Some(snapshot.buffer_version))
+            .then(|| buffers.words_for(snapshot.buffer_id))
+            .flatten();
+        (focused, buffers.words_except(snapshot.buffer_id))
+    } else {
+        (None, Vec::new())
+    };
+
     let task = stoat.spawn_woken(run_request(
         executor,
         owned,
@@ -237,6 +250,8 @@ pub(crate) fn trigger(stoat: &mut Stoat) {
         completion_hosts,
         fs_host,
         snapshot.rope,
+        focused_words,
+        other_words,
         encoding,
         base_dir,
         home_dir,
@@ -391,6 +406,8 @@ async fn run_request(
     completion_hosts: Vec<(String, Arc<dyn LspHost>)>,
     fs_host: Arc<dyn FsHost>,
     rope: Rope,
+    focused_words: Option<Arc<BufferWords>>,
+    other_words: Vec<Arc<BufferWords>>,
     encoding: OffsetEncoding,
     base_dir: PathBuf,
     home_dir: Option<PathBuf>,
@@ -435,9 +452,13 @@ async fn run_request(
                     .spawn_blocking({
                         let owned = owned.clone();
                         let rope = rope.clone();
+                        let focused_words = focused_words.clone();
+                        let other_words = other_words.clone();
                         move || {
                             let ctx = owned.as_borrowed();
-                            crate::completion::word::fetch(&ctx, &rope)
+                            let focused = focused_words
+                                .unwrap_or_else(|| Arc::new(BufferWords::from_rope(&rope)));
+                            crate::completion::word::fetch_ranked(&ctx, &focused, &other_words)
                         }
                     })
                     .await;
@@ -1,16 +1,104 @@
-//! Buffer-word completion source. Walks the focused buffer's
-//! rope for word-shaped tokens that start with the typed prefix
-//! and returns each unique match. Acts as a fallback when neither
-//! the LSP source nor the path source fires (plain-text buffers,
-//! files without an LSP).
+//! Buffer-word completion source. Offers the identifiers of every
+//! open buffer that start with the typed prefix, ranked by how close
+//! their nearest occurrence in the focused buffer sits to the cursor,
+//! then by how often they occur. Acts as a fallback when neither the
+//! LSP source nor the path source fires (plain-text buffers, files
+//! without an LSP).
+//!
+//! Each parsed buffer's [`BufferWords`] is rebuilt off-thread from
+//! its syntax tree by the parse pipeline and retained on the buffer
+//! registry, so a keystroke only ranks the stored indexes instead of
+//! rescanning every buffer. A buffer without a grammar is scanned
+//! from its rope when it is the focused one.
 
 use crate::completion::{CompletionContext, CompletionItem, CompletionSource};
-use std::collections::BTreeSet;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use stoat_language::Tree;
 use stoat_text::Rope;
 
+/// Occurrences of each word in one buffer.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BufferWords {
+    /// Start byte offsets of each word's occurrences, ascending.
+    occurrences: HashMap<String, Vec<usize>>,
+}
+
+impl BufferWords {
+    /// Index the identifier tokens of `tree`, parsed from `rope`: leaf
+    /// nodes whose kind names an identifier or a bare key. A grammar
+    /// without such tokens (markdown, JSON) falls back to
+    /// [`Self::from_rope`].
+    pub fn index(tree: &Tree, rope: &Rope) -> Self {
+        let mut words = Self::default();
+        let mut cursor = tree.walk();
+        'walk: loop {
+            let node = cursor.node();
+            let kind = node.kind();
+            if node.child_count() == 0 && (kind.contains("identifier") || kind == "bare_key") {
+                let text: String = rope.chunks_in_range(node.byte_range()).collect();
+                if is_word(&text) {
+                    words.push(text, node.start_byte());
+                }
+            }
+            if cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    break 'walk;
+                }
+            }
+        }
+        if words.occurrences.is_empty() {
+            return Self::from_rope(rope);
+        }
+        words
+    }
+
+    /// Index every word-shaped run of `rope`.
+    pub fn from_rope(rope: &Rope) -> Self {
+        let mut words = Self::default();
+        let mut current = String::new();
+        let mut start = 0;
+        let mut offset = 0;
+        for ch in rope.chars() {
+            if is_word_char(ch) {
+                if current.is_empty() {
+                    start = offset;
+                }
+                current.push(ch);
+            } else if !current.is_empty() {
+                words.push(std::mem::take(&mut current), start);
+            }
+            offset += ch.len_utf8();
+        }
+        if !current.is_empty() {
+            words.push(current, start);
+        }
+        words
+    }
+
+    fn push(&mut self, word: String, offset: usize) {
+        self.occurrences.entry(word).or_default().push(offset);
+    }
+
+    /// Words starting with `prefix`, other than `prefix` itself, with
+    /// their occurrence offsets.
+    fn matching<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a str, &'a [usize])> {
+        self.occurrences
+            .iter()
+            .filter(move |(word, _)| word.starts_with(prefix) && word.as_str() != prefix)
+            .map(|(word, offsets)| (word.as_str(), offsets.as_slice()))
+    }
+}
+
 /// Collect every word-shaped token in `rope` whose label starts
-/// with `ctx.prefix`. Skips the prefix itself (no point suggesting
-/// what is already typed) and dedupes repeats.
+/// with `ctx.prefix`, ranked as [`fetch_ranked`] does with `rope`
+/// as the focused buffer.
 ///
 /// Returns empty when `ctx.prefix` is empty -- the fallback source
 /// only fires once the user has typed at least one identifier
@@ -19,29 +107,49 @@ pub fn fetch(ctx: &CompletionContext<'_>, rope: &Rope) -> Vec<CompletionItem> {
     if ctx.prefix.is_empty() {
         return Vec::new();
     }
+    fetch_ranked(ctx, &BufferWords::from_rope(rope), &[])
+}
 
-    let mut seen: BTreeSet<String> = BTreeSet::new();
-    let mut current: String = String::new();
-
-    for ch in rope.chars() {
-        if is_word_char(ch) {
-            current.push(ch);
-        } else if !current.is_empty() {
-            collect(&mut current, ctx.prefix, &mut seen);
-        }
+/// Offer the words of `focused` and `others` that start with
+/// `ctx.prefix`, skipping the prefix itself and deduping repeats.
+///
+/// Words in the focused buffer come first, nearest first, where
+/// distances within the same power of two count as equally near so
+/// a frequent word can beat a rare one a few bytes closer. Ties, and
+/// words found only in other buffers, go by total occurrences across
+/// every buffer, then alphabetically.
+pub fn fetch_ranked(
+    ctx: &CompletionContext<'_>,
+    focused: &BufferWords,
+    others: &[Arc<BufferWords>],
+) -> Vec<CompletionItem> {
+    if ctx.prefix.is_empty() {
+        return Vec::new();
+    }
+    // Per word: (distance bucket in the focused buffer, total occurrences).
+    let mut ranked: BTreeMap<&str, (Option<u32>, usize)> = BTreeMap::new();
+    for (word, offsets) in focused.matching(ctx.prefix) {
+        let entry = ranked.entry(word).or_default();
+        entry.0 = Some(distance_bucket(offsets, ctx.cursor_offset));
+        entry.1 += offsets.len();
     }
-    if !current.is_empty() {
-        collect(&mut current, ctx.prefix, &mut seen);
+    for words in others {
+        for (word, offsets) in words.matching(ctx.prefix) {
+            ranked.entry(word).or_default().1 += offsets.len();
+        }
     }
 
-    seen.into_iter()
-        .map(|label| CompletionItem {
-            label: label.clone(),
+    let mut ranked: Vec<(&str, (Option<u32>, usize))> = ranked.into_iter().collect();
+    ranked.sort_by_key(|&(word, (bucket, count))| (bucket.is_none(), bucket, Reverse(count), word));
+    ranked
+        .into_iter()
+        .map(|(label, _)| CompletionItem {
+            label: label.to_string(),
             source: CompletionSource::Word,
             kind: None,
             detail: None,
             replace_range: ctx.prefix_range.clone(),
-            insert_text: label,
+            insert_text: label.to_string(),
             is_snippet: false,
             documentation: None,
             lsp_item: None,
@@ -50,11 +158,23 @@ pub fn fetch(ctx: &CompletionContext<'_>, rope: &Rope) -> Vec<CompletionItem> {
         .collect()
 }
 
-fn collect(current: &mut String, prefix: &str, seen: &mut BTreeSet<String>) {
-    if current.starts_with(prefix) && current != prefix {
-        seen.insert(current.clone());
-    }
-    current.clear();
+/// Bit length of the byte distance from `cursor` to the nearest of
+/// the ascending `offsets`.
+fn distance_bucket(offsets: &[usize], cursor: usize) -> u32 {
+    let after = offsets.partition_point(|&o| o < cursor);
+    let nearest = [after.checked_sub(1), Some(after)]
+        .into_iter()
+        .flatten()
+        .filter_map(|i| offsets.get(i))
+        .map(|&o| o.abs_diff(cursor))
+        .min()
+        .unwrap_or(usize::MAX);
+    usize::BITS - nearest.leading_zeros()
+}
+
+fn is_word(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_') && chars.all(is_word_char)
 }
 
 fn is_word_char(ch: char) -> bool {
@@ -161,4 +281,50 @@ mod tests {
         let items = fetch(&ctx("Fo"), &rope);
         assert_eq!(labels(&items), vec!["Foo"]);
     }
+
+    #[test]
+    fn nearer_words_rank_first() {
+        let text = "n nested x x x x x x x x x x x x near";
+        let rope = Rope::from(text);
+        assert_eq!(labels(&fetch(&ctx("n"), &rope)), vec!["nested", "near"]);
+        let mut c = ctx("n");
+        c.cursor_offset = text.len();
+        assert_eq!(labels(&fetch(&c, &rope)), vec!["near", "nested"]);
+    }
+
+    #[test]
+    fn frequent_words_break_distance_ties() {
+        let rope = Rope::from("f        fab fac fac");
+        let items = fetch(&ctx("f"), &rope);
+        assert_eq!(labels(&items), vec!["fac", "fab"]);
+    }
+
+    #[test]
+    fn other_buffer_words_follow_focused_words() {
+        let focused = BufferWords::from_rope(&Rope::from("ma zmatch"));
+        let others = [
+            Arc::new(BufferWords::from_rope(&Rope::from("main main"))),
+            Arc::new(BufferWords::from_rope(&Rope::from("map main"))),
+        ];
+        let items = fetch_ranked(&ctx("ma"), &focused, &others);
+        assert_eq!(labels(&items), vec!["main", "map"]);
+
+        let focused = BufferWords::from_rope(&Rope::from("ma x x x x x x x x x x x x x x x mat"));
+        let items = fetch_ranked(&ctx("ma"), &focused, &others);
+        assert_eq!(labels(&items), vec!["mat", "main", "map"]);
+    }
+
+    #[test]
+    fn tree_index_skips_comments_and_strings() {
+        let source = "// comment_word\nfn parse_args(input: &str) -> Config {\n    let s = \"string_word\";\n    Config { input }\n}\n";
+        let lang = stoat_language::LanguageRegistry::standard()
+            .for_path(std::path::Path::new("lib.rs"))
+            .expect("rust language");
+        let tree = stoat_language::parse(&lang, source, None).expect("parse");
+        let words = BufferWords::index(&tree, &Rope::from(source));
+        let mut indexed: Vec<&str> = words.occurrences.keys().map(String::as_str).collect();
+        indexed.sort_unstable();
+        assert_eq!(indexed, vec!["Config", "input", "parse_args", "s"]);
+        assert_eq!(words.occurrences["Config"].len(), 2);
+    }
 }
@@ -1,9 +1,11 @@
+pub mod bidi;
 mod block_map;
 mod crease_map;
 mod fold_map;
 pub mod highlights;
 pub mod inlay_map;
 pub mod invisibles;
+pub mod measure;
 pub mod syntax_theme;
 pub mod tab_map;
 mod wrap_map;
@@ -14,10 +16,11 @@ use crate::{
     host::DiffStatus,
     multi_buffer::{ExcerptId, MultiBuffer, MultiBufferSnapshot},
 };
+pub use bidi::VisualOrder;
 pub use block_map::{
     balancing_block, Block, BlockContext, BlockId, BlockMap, BlockPlacement, BlockPoint,
     BlockProperties, BlockRow, BlockRowKind, BlockSnapshot, BlockStyle, CompanionView, CustomBlock,
-    CustomBlockId, RenderBlock,
+    CustomBlockId, PinnedBlock, PinnedBlockProperties, PinnedPlacement, RenderBlock, ViewportEdge,
 };
 pub use crease_map::{
     Crease, CreaseId, CreaseMap, CreaseMetadata, CreaseSnapshot, RenderToggleFn, RenderTrailerFn,
@@ -30,6 +33,7 @@ pub use highlights::{
     SemanticTokenHighlight, SemanticTokensHighlights, TextHighlights,
 };
 pub use inlay_map::{InlayId, InlayKind, InlayMap, InlayOffset, InlayPoint, InlaySnapshot};
+pub use measure::{MeasureRef, Monospace, TextMeasure};
 use std::{
     collections::{BTreeMap, HashMap},
     sync::{
@@ -41,7 +45,7 @@ use stoat_scheduler::Executor;
 use stoat_text::{patch::Patch, Anchor, Bias, CharsAt, Point, ReversedCharsAt, Rope};
 pub use tab_map::{TabMap, TabPoint, TabRow, TabSnapshot};
 use unicode_width::UnicodeWidthChar;
-pub use wrap_map::{WrapMap, WrapPoint, WrapSnapshot};
+pub use wrap_map::{WrapMap, WrapPoint, WrapRules, WrapSnapshot};
 
 /// Shared empty text-highlight map, used as the `unwrap_or` fallback when an
 /// endpoint build carries no text highlights. Every live caller passes its own
@@ -236,6 +240,9 @@ pub struct DisplayMap {
     /// chunk whose center shrank below its taller side, tracked so each refresh
     /// replaces the previous set rather than stacking duplicates.
     conflict_padding_block_ids: Vec<CustomBlockId>,
+    /// Ids of the code-lens blocks, replaced as a set whenever the lenses are
+    /// recomputed.
+    lens_block_ids: Vec<CustomBlockId>,
     last_diff_version: usize,
     /// When false, `Deleted`/`Modified` diff hunks do not splice inline
     /// deleted-line block rows into the display. A plain editor with a populated
@@ -251,6 +258,14 @@ pub struct DisplayMap {
     /// [`DisplayMap::snapshot_with_companion`] so a single rebuild
     /// covers any number of highlight setters fired in the same frame.
     highlights_dirty: bool,
+    /// Bumped by each snapshot rebuild caused by anything besides buffer
+    /// edits: highlights, folds, inlays, blocks, wrapping, the diff, or a
+    /// display setting. A painter caching rows repaints all of them when it
+    /// changes.
+    layout_generation: u64,
+    /// Wrap-row edits synced since the last [`Self::take_row_damage`], the
+    /// rows buffer edits touched.
+    row_damage: Patch<u32>,
 }
 
 impl DisplayMap {
@@ -287,14 +302,28 @@ impl DisplayMap {
             last_crease_sync_version: version,
             inserted_diff_block_ids: Vec::new(),
             conflict_padding_block_ids: Vec::new(),
+            lens_block_ids: Vec::new(),
             last_diff_version: 0,
             show_deleted_blocks: false,
             last_show_deleted_blocks: false,
             cached_snapshot: None,
             highlights_dirty: false,
+            layout_generation: 0,
+            row_damage: Patch::empty(),
         }
     }
 
+    /// See [`Self::layout_generation`](#structfield.layout_generation).
+    pub(crate) fn layout_generation(&self) -> u64 {
+        self.layout_generation
+    }
+
+    /// Take the wrap-row edits accumulated since the last call. Rows outside
+    /// them are unchanged unless [`Self::layout_generation`] moved too.
+    pub(crate) fn take_row_damage(&mut self) -> Patch<u32> {
+        std::mem::replace(&mut self.row_damage, Patch::empty())
+    }
+
     pub fn id(&self) -> DisplayMapId {
         self.id
     }
@@ -369,6 +398,24 @@ impl DisplayMap {
         self.cached_snapshot = None;
     }
 
+    /// Insert blocks pinned to a viewport edge, returning their ids for
+    /// [`Self::remove_blocks`]. Like [`Self::insert_blocks`] this touches no
+    /// version the snapshot cache keys on, so the cache is dropped here.
+    pub fn insert_pinned_blocks(
+        &mut self,
+        blocks: Vec<PinnedBlockProperties>,
+    ) -> Vec<CustomBlockId> {
+        let ids = self.block_map.insert_pinned(blocks);
+        self.cached_snapshot = None;
+        ids
+    }
+
+    /// Remove the blocks with `ids`, pinned or placed at a row.
+    pub fn remove_blocks(&mut self, ids: &std::collections::HashSet<CustomBlockId>) {
+        self.block_map.remove(ids);
+        self.cached_snapshot = None;
+    }
+
     /// Replace the conflict view's padding spacer blocks with `blocks`.
     ///
     /// Removes the spacers installed by the previous call before inserting the
@@ -382,6 +429,21 @@ impl DisplayMap {
         self.cached_snapshot = None;
     }
 
+    /// Replace the code-lens blocks with `blocks`. Pass an empty vector to
+    /// clear them.
+    pub fn set_lens_blocks(&mut self, blocks: Vec<BlockProperties>) {
+        let stale: std::collections::HashSet<CustomBlockId> =
+            self.lens_block_ids.drain(..).collect();
+        self.block_map.remove(&stale);
+        self.lens_block_ids = self.block_map.insert(blocks);
+        self.cached_snapshot = None;
+    }
+
+    /// Whether `id` is one of the blocks installed by [`Self::set_lens_blocks`].
+    pub fn is_lens_block(&self, id: CustomBlockId) -> bool {
+        self.lens_block_ids.contains(&id)
+    }
+
     pub fn fold(&mut self, ranges: Vec<std::ops::Range<Point>>) {
         let buffer_snapshot = self.multi_buffer.snapshot();
         let anchor_ranges = ranges
@@ -410,6 +472,31 @@ impl DisplayMap {
         self.fold_map.unfold(offset_ranges, &buffer_snapshot);
     }
 
+    /// Fold each of `ranges`, buffer byte ranges of syntax nodes such as
+    /// function bodies or import runs, behind a placeholder counting the lines
+    /// it hides. Nested ranges merge into the outermost one.
+    pub fn fold_syntax_ranges(&mut self, ranges: Vec<std::ops::Range<usize>>) {
+        let buffer_snapshot = self.multi_buffer.snapshot();
+        let folds = ranges
+            .into_iter()
+            .map(|r| {
+                let placeholder = FoldPlaceholder::line_count(buffer_snapshot.rope(), r.clone());
+                let range = buffer_snapshot.anchor_at(r.start, Bias::Right)
+                    ..buffer_snapshot.anchor_at(r.end, Bias::Left);
+                (range, placeholder)
+            })
+            .collect();
+        self.fold_map
+            .fold_with_placeholders(folds, &buffer_snapshot);
+    }
+
+    /// Remove every fold.
+    pub fn unfold_all(&mut self) {
+        let buffer_snapshot = self.multi_buffer.snapshot();
+        let len = buffer_snapshot.rope().len();
+        self.fold_map.unfold(vec![0..len], &buffer_snapshot);
+    }
+
     pub fn toggle_fold(&mut self, ranges: Vec<std::ops::Range<Point>>) {
         let buffer_snapshot = self.multi_buffer.snapshot();
         let any_folded = ranges.iter().any(|r| {
@@ -437,12 +524,58 @@ impl DisplayMap {
         self.wrap_map.set_wrap_width(width);
     }
 
+    /// Set the rules soft wrap breaks lines by. Like a width change, a rule
+    /// change drops the cached snapshot so the next one rewraps.
+    pub fn set_wrap_rules(&mut self, rules: WrapRules) {
+        if self
+            .cached_snapshot
+            .as_ref()
+            .is_some_and(|snapshot| snapshot.wrap_rules() != rules)
+        {
+            self.cached_snapshot = None;
+        }
+        self.wrap_map.set_rules(rules);
+    }
+
+    /// Measure rows by the glyph advances of the font a host draws with,
+    /// rather than one cell per column. A different measure drops the cached
+    /// snapshot so the next one rewraps.
+    pub fn set_text_measure(&mut self, measure: MeasureRef) {
+        if *self.wrap_map.measure() != measure {
+            self.wrap_map.set_measure(measure);
+            self.cached_snapshot = None;
+        }
+    }
+
+    pub fn text_measure(&self) -> &MeasureRef {
+        self.wrap_map.measure()
+    }
+
+    /// Align tab-separated columns across adjacent lines instead of stopping
+    /// tabs every tab-size columns. A change drops the cached snapshot so the
+    /// next one re-expands every tab.
+    pub fn set_elastic_tabstops(&mut self, elastic: bool) {
+        if self.tab_map.elastic_tabstops() != elastic {
+            self.tab_map.set_elastic_tabstops(elastic);
+            self.cached_snapshot = None;
+        }
+    }
+
+    pub fn elastic_tabstops(&self) -> bool {
+        self.tab_map.elastic_tabstops()
+    }
+
     /// The wrap width most recently stamped by [`Self::set_wrap_width`], before
     /// the next snapshot applies it. `None` disables wrapping.
     pub fn wrap_width(&self) -> Option<u32> {
         self.wrap_map.wrap_width()
     }
 
+    /// Register `ranges` styled with `style` as the highlight set for `key`,
+    /// replacing any set already under it. Chunk iteration merges every set
+    /// into [`HighlightedChunk`] styles, layered by the key's
+    /// [`HighlightLayer`], so a transient decoration such as search matches or symbol occurrences
+    /// needs no painting of its own.
     pub fn highlight_text(
         &mut self,
         key: HighlightKey,
@@ -460,10 +593,14 @@ impl DisplayMap {
         self.highlights_dirty = true;
     }
 
+    /// Drop the highlight set under `key`, returning whether one was there.
+    /// Clearing an absent key leaves the highlights, and so the memoized
+    /// endpoints keyed on their identity, untouched.
     pub fn clear_highlights(&mut self, key: HighlightKey) -> bool {
-        let mut cleared = Arc::make_mut(&mut self.text_highlights)
-            .remove(&key)
-            .is_some();
+        let mut cleared = self.text_highlights.contains_key(&key)
+            && Arc::make_mut(&mut self.text_highlights)
+                .remove(&key)
+                .is_some();
         cleared |= self.inlay_highlights.remove(&key).is_some();
         if cleared {
             self.highlights_dirty = true;
@@ -537,6 +674,18 @@ impl DisplayMap {
         self.inlay_map.splice(remove, insert)
     }
 
+    /// Replace the inlays named by `current` with a provider's complete new
+    /// set, keeping those already shown unchanged. Returns the id for each
+    /// entry of `desired` in order. See [`InlayMap::reconcile`].
+    pub fn reconcile_inlays(
+        &mut self,
+        current: Vec<InlayId>,
+        desired: Vec<(Anchor, String, InlayKind)>,
+    ) -> Vec<InlayId> {
+        let buffer = self.multi_buffer.snapshot();
+        self.inlay_map.reconcile(&buffer, current, desired)
+    }
+
     pub fn insert_creases(
         &mut self,
         creases: impl IntoIterator<Item = Crease<Anchor>>,
@@ -587,7 +736,9 @@ impl DisplayMap {
         let (inlay_snapshot, inlay_edits) = self.inlay_map.sync(buffer_snapshot, &buffer_edits);
         let (fold_snapshot, fold_edits) = self.fold_map.sync(inlay_snapshot, &inlay_edits);
         let (tab_snapshot, tab_edits) = self.tab_map.sync(fold_snapshot, fold_edits);
-        self.wrap_map.sync(tab_snapshot, &tab_edits)
+        let (wrap_snapshot, wrap_edits) = self.wrap_map.sync(tab_snapshot, &tab_edits);
+        self.row_damage = self.row_damage.compose(wrap_edits.edits().iter().cloned());
+        (wrap_snapshot, wrap_edits)
     }
 
     pub fn snapshot(&mut self) -> DisplaySnapshot {
@@ -604,15 +755,19 @@ impl DisplayMap {
         }
         let buffer_version = self.multi_buffer.buffer_version();
         let diff_version_now = self.multi_buffer.diff_version();
-        if buffer_version == self.last_buffer_version
-            && diff_version_now == self.last_diff_version
+        let layout_unchanged = diff_version_now == self.last_diff_version
             && self.fold_map.version_unchanged()
             && self.inlay_map.version_unchanged()
-            && companion_wrap_data.is_none()
+            && companion_wrap_data.is_none();
+        if buffer_version == self.last_buffer_version
+            && layout_unchanged
             && let Some(ref cached) = self.cached_snapshot
         {
             return cached.clone();
         }
+        if !layout_unchanged || self.cached_snapshot.is_none() {
+            self.layout_generation += 1;
+        }
 
         let (wrap_snapshot, wrap_edits) = self.sync_through_wrap();
         let diff_map = self.multi_buffer.snapshot().diff_map.clone();
@@ -842,6 +997,18 @@ impl DisplaySnapshot {
         display_rows: std::ops::Range<u32>,
         cache: &mut Option<CachedHighlightEndpoints>,
     ) -> block_map::BlockChunks<'_> {
+        let endpoints = self.highlighted_endpoints_cached(display_rows.clone(), cache);
+        self.block_snapshot.chunks(display_rows, endpoints)
+    }
+
+    /// Like [`Self::highlighted_endpoints`] but memoized in `cache` the way
+    /// [`Self::highlighted_chunks_cached`] is, for a caller chunking only some
+    /// rows of a viewport.
+    pub fn highlighted_endpoints_cached(
+        &self,
+        display_rows: std::ops::Range<u32>,
+        cache: &mut Option<CachedHighlightEndpoints>,
+    ) -> Arc<[highlights::HighlightEndpoint]> {
         let highlights = Highlights {
             text_highlights: Some(&self.text_highlights),
             inlay_highlights: Some(&self.inlay_highlights),
@@ -850,9 +1017,8 @@ impl DisplaySnapshot {
         };
         let byte_range = self
             .block_snapshot
-            .row_range_to_buffer_byte_range(display_rows.clone());
-        let endpoints = self.build_endpoints_cached(highlights, byte_range, cache);
-        self.block_snapshot.chunks(display_rows, endpoints)
+            .row_range_to_buffer_byte_range(display_rows);
+        self.build_endpoints_cached(highlights, byte_range, cache)
     }
 
     fn build_endpoints(
@@ -912,6 +1078,10 @@ impl DisplaySnapshot {
         DisplayPoint::new(block.row, block.column)
     }
 
+    pub fn wrap_row_to_display_row(&self, wrap_row: u32) -> u32 {
+        self.block_snapshot.wrap_row_to_block_row(wrap_row)
+    }
+
     pub fn display_to_buffer(&self, point: DisplayPoint) -> Option<Point> {
         self.block_snapshot
             .block_to_buffer(BlockPoint::new(point.row, point.column))
@@ -965,6 +1135,11 @@ impl DisplaySnapshot {
         self.block_snapshot.total_lines()
     }
 
+    /// See [`BlockSnapshot::pinned_blocks`].
+    pub fn pinned_blocks(&self, scroll_row: u32, viewport_rows: u32) -> Vec<PinnedPlacement> {
+        self.block_snapshot.pinned_blocks(scroll_row, viewport_rows)
+    }
+
     pub fn buffer_line_count(&self) -> u32 {
         self.block_snapshot.buffer_line_count()
     }
@@ -1006,6 +1181,35 @@ impl DisplaySnapshot {
         range.map(move |row| self.display_line(row))
     }
 
+    /// Visual order of `display_row`, or `None` when it holds no
+    /// right-to-left text and reads in logical order.
+    pub fn visual_order(&self, display_row: u32) -> Option<VisualOrder> {
+        VisualOrder::new(self.display_line(display_row).chars())
+    }
+
+    /// Cells from the row's start to where the font draws `point`, which
+    /// is its column on a monospace grid and the sum of the glyph advances
+    /// before it otherwise, rounded to the nearest cell.
+    pub fn measured_x(&self, point: DisplayPoint) -> u32 {
+        let measure = self.wrap_snapshot().measure();
+        if measure.is_monospace() {
+            return point.column;
+        }
+        let mut column = 0;
+        let mut x = 0.0f32;
+        let mut prev = None;
+        for ch in self.display_line(point.row).chars() {
+            if column >= point.column {
+                break;
+            }
+            let width = display_width(ch);
+            x += measure.advance(prev, ch, width);
+            column += width;
+            prev = Some(ch);
+        }
+        (x + (point.column.saturating_sub(column)) as f32).round() as u32
+    }
+
     pub fn is_wrap_continuation(&self, display_row: u32) -> bool {
         self.block_snapshot.is_wrap_continuation(display_row)
     }
@@ -1018,6 +1222,10 @@ impl DisplaySnapshot {
         self.block_snapshot.wrap_width()
     }
 
+    pub fn wrap_rules(&self) -> WrapRules {
+        self.block_snapshot.wrap_snapshot().wrap_rules()
+    }
+
     pub fn has_deletion_after(&self, buffer_line: u32) -> bool {
         self.diff_map
             .as_ref()
@@ -1119,7 +1327,8 @@ impl Iterator for ReversedBufferCharsAt<'_> {
 mod tests {
     use super::{
         BlockPlacement, BlockProperties, BlockRowKind, BlockStyle, DisplayMap, DisplayPoint,
-        DisplayRow, InlayKind, InlayPoint,
+        DisplayRow, InlayKind, InlayPoint, MeasureRef, PinnedBlockProperties, TextMeasure,
+        ViewportEdge, WrapRules,
     };
     use crate::{
         buffer::{BufferId, TextBuffer},
@@ -1296,6 +1505,41 @@ mod tests {
         assert_eq!(display_map.snapshot().line_count(), 4);
     }
 
+    #[test]
+    fn pinned_scope_measures_folded_rows() {
+        let content = (0..12)
+            .map(|i| format!("line{i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut display_map = create_display_map(&content);
+        let ids = display_map.insert_pinned_blocks(vec![PinnedBlockProperties::from_text(
+            ViewportEdge::Top,
+            Some(2..8),
+            vec!["header".to_string()],
+        )]);
+        let shows = |display_map: &mut DisplayMap, scroll_row| {
+            !display_map
+                .snapshot()
+                .pinned_blocks(scroll_row, 3)
+                .is_empty()
+        };
+        assert!(
+            !shows(&mut display_map, 2),
+            "the header row is still on screen"
+        );
+        assert!(shows(&mut display_map, 7));
+        assert!(!shows(&mut display_map, 8), "scrolled past the scope");
+
+        // Folding rows 3..=5 into row 2 pulls the scope's last row up to
+        // display row 4, so it ends three rows sooner.
+        display_map.fold(vec![Point::new(2, 5)..Point::new(5, 5)]);
+        assert!(shows(&mut display_map, 4));
+        assert!(!shows(&mut display_map, 5));
+
+        display_map.remove_blocks(&ids.into_iter().collect());
+        assert!(!shows(&mut display_map, 4));
+    }
+
     #[test]
     fn max_point() {
         let mut display_map = create_display_map("short\nlonger line\nx");
@@ -1422,6 +1666,24 @@ mod tests {
         assert_eq!(snapshot.line_count(), 3);
     }
 
+    #[test]
+    fn syntax_folds_show_a_line_count() {
+        let text = "use a;\nuse b;\nfn main() {\n    body;\n}";
+        let mut display_map = create_display_map(text);
+        let body = text.find('{').unwrap()..text.len();
+        display_map.fold_syntax_ranges(vec![0..13, body]);
+        let snapshot = display_map.snapshot();
+        let total = snapshot.line_count();
+        let shown: String = snapshot
+            .highlighted_chunks(0..total)
+            .map(|c| c.text.into_owned())
+            .collect();
+        assert_eq!(shown, "… 2 lines\nfn main() { … 3 lines }");
+
+        display_map.unfold_all();
+        assert_eq!(display_map.snapshot().line_count(), 5);
+    }
+
     #[test]
     fn wrap_width_none_by_default() {
         let mut display_map = create_display_map("hello");
@@ -1437,6 +1699,74 @@ mod tests {
         assert_eq!(snapshot.wrap_width(), Some(40));
     }
 
+    #[test]
+    fn wrap_rules_change_rewraps_a_cached_snapshot() {
+        let mut display_map = create_display_map("hello world foo");
+        display_map.set_wrap_width(Some(8));
+        assert_eq!(display_map.snapshot().line_count(), 3);
+
+        display_map.set_wrap_rules(WrapRules {
+            word_boundaries: false,
+            ..WrapRules::default()
+        });
+        let snapshot = display_map.snapshot();
+        assert_eq!(snapshot.line_count(), 2);
+        assert!(!snapshot.wrap_rules().word_boundaries);
+    }
+
+    #[test]
+    fn text_measure_rewraps_and_places_columns_by_advance() {
+        /// Every glyph but a space draws half a cell wide.
+        struct Narrow;
+        impl TextMeasure for Narrow {
+            fn advance(&self, _prev: Option<char>, ch: char) -> f32 {
+                if ch == ' ' {
+                    1.0
+                } else {
+                    0.5
+                }
+            }
+        }
+
+        let mut display_map = create_display_map("hello world foo");
+        display_map.set_wrap_width(Some(9));
+        assert_eq!(display_map.snapshot().line_count(), 2);
+
+        display_map.set_text_measure(MeasureRef::new(Narrow));
+        let snapshot = display_map.snapshot();
+        assert_eq!(snapshot.line_count(), 1);
+        assert_eq!(snapshot.measured_x(DisplayPoint::new(0, 6)), 4);
+        assert_eq!(snapshot.measured_x(DisplayPoint::new(0, 15)), 9);
+    }
+
+    #[test]
+    fn elastic_tabstops_realign_after_an_edit() {
+        let buffer = TextBuffer::with_text(BufferId::new(0), "a\tb\nlonger\tc\n\nx\ty\n");
+        let shared = Arc::new(RwLock::new(buffer));
+        let multi_buffer = MultiBuffer::singleton(BufferId::new(0), shared.clone());
+        let mut display_map = DisplayMap::new(multi_buffer, test_executor());
+        display_map.set_elastic_tabstops(true);
+        let lines = |display_map: &mut DisplayMap| {
+            let snapshot = display_map.snapshot();
+            snapshot
+                .display_lines(0..snapshot.line_count())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lines(&mut display_map)[..4],
+            ["a      b", "longer c", "", "x   y"]
+        );
+
+        shared.write().unwrap().edit(0..1, "wider than all");
+        let edited = lines(&mut display_map);
+        assert_eq!(edited[1], format!("longer{}c", " ".repeat(9)));
+        assert_eq!(edited[3], "x   y");
+
+        let mut fresh = create_display_map(&shared.read().unwrap().rope().to_string());
+        fresh.set_elastic_tabstops(true);
+        assert_eq!(edited, lines(&mut fresh));
+    }
+
     #[test]
     fn longest_row_no_blocks() {
         let mut display_map = create_display_map("short\nlonger line\nx");
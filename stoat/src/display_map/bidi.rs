@@ -0,0 +1,310 @@
+//! Visual order of a display row holding right-to-left text.
+//!
+//! The display map lays text out in logical order: a display column counts
+//! the widths of the characters before it in the buffer. Hebrew and Arabic
+//! read right to left, so a row mixing them with left-to-right text shows
+//! its characters in a different order than it stores them. [`VisualOrder`]
+//! resolves that order for one row with a reduced form of the Unicode
+//! bidirectional algorithm, without explicit embeddings or isolates:
+//!
+//! - the row's base direction comes from its first strong character;
+//! - digits after right-to-left text keep their left-to-right order inside it;
+//! - neutrals between runs of one direction take that direction, others and trailing whitespace
+//!   take the base direction;
+//! - runs at each odd level are then reversed, highest level first.
+//!
+//! The renderer paints a row logically and moves each character's cells to
+//! its visual column. Cursors and selections stay logical and are carried
+//! along with the characters they cover, and a click maps its visual column
+//! back through [`VisualOrder::to_logical`].
+
+/// Whether `ch` belongs to a right-to-left script.
+pub fn is_rtl(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF | 0x10800..=0x10FFF | 0x1E800..=0x1EFFF
+    )
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Left,
+    Right,
+    Digit,
+    Neutral,
+}
+
+fn classify(ch: char) -> Class {
+    if is_rtl(ch) {
+        if ch.is_numeric() {
+            Class::Digit
+        } else {
+            Class::Right
+        }
+    } else if ch.is_ascii_digit() {
+        Class::Digit
+    } else if ch.is_alphabetic() {
+        Class::Left
+    } else {
+        Class::Neutral
+    }
+}
+
+/// One character of a row, at its logical and visual display columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Placed {
+    ch: char,
+    logical: u32,
+    visual: u32,
+    width: u32,
+}
+
+/// Where each character of a display row lands once right-to-left runs are
+/// reversed. Columns are display columns of the row in both directions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VisualOrder {
+    /// In logical order.
+    chars: Vec<Placed>,
+    width: u32,
+}
+
+impl VisualOrder {
+    /// Resolve the visual order of a row's characters. `None` when the row
+    /// holds no right-to-left character, so its visual order is its logical
+    /// one.
+    pub fn new(chars: impl IntoIterator<Item = char>) -> Option<Self> {
+        let chars: Vec<char> = chars.into_iter().collect();
+        if !chars.iter().copied().any(is_rtl) {
+            return None;
+        }
+        let levels = resolve_levels(&chars);
+        let order = reorder(&levels);
+
+        let mut placed: Vec<Placed> = Vec::with_capacity(chars.len());
+        let mut logical = 0;
+        for &ch in &chars {
+            let width = super::display_width(ch);
+            placed.push(Placed {
+                ch,
+                logical,
+                visual: 0,
+                width,
+            });
+            logical += width;
+        }
+        let mut visual = 0;
+        for ix in order {
+            placed[ix].visual = visual;
+            visual += placed[ix].width;
+        }
+        Some(Self {
+            chars: placed,
+            width: logical,
+        })
+    }
+
+    /// Visual column of the cell at logical `column`. Columns past the row's
+    /// end stay where they are.
+    pub fn to_visual(&self, column: u32) -> u32 {
+        if column >= self.width {
+            return column;
+        }
+        let ix = self
+            .chars
+            .partition_point(|c| c.logical + c.width <= column);
+        self.chars
+            .get(ix)
+            .map_or(column, |c| c.visual + (column - c.logical))
+    }
+
+    /// Logical column of the cell at visual `column`, the inverse of
+    /// [`Self::to_visual`].
+    pub fn to_logical(&self, column: u32) -> u32 {
+        if column >= self.width {
+            return column;
+        }
+        self.chars
+            .iter()
+            .find(|c| c.width > 0 && (c.visual..c.visual + c.width).contains(&column))
+            .map_or(column, |c| c.logical + (column - c.visual))
+    }
+
+    /// Each character's logical column, visual column and width, in logical
+    /// order.
+    pub fn moves(&self) -> impl Iterator<Item = (char, u32, u32, u32)> + '_ {
+        self.chars
+            .iter()
+            .map(|c| (c.ch, c.logical, c.visual, c.width))
+    }
+
+    /// Display width of the row.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+}
+
+/// Embedding level of each character: even reads left to right, odd right to
+/// left.
+fn resolve_levels(chars: &[char]) -> Vec<u8> {
+    let classes: Vec<Class> = chars.iter().map(|&ch| classify(ch)).collect();
+    let base_rtl = classes
+        .iter()
+        .find(|c| matches!(c, Class::Left | Class::Right))
+        .is_some_and(|c| *c == Class::Right);
+    let base: u8 = if base_rtl { 1 } else { 0 };
+
+    // Digits read left to right, but after right-to-left text they sit inside
+    // its run instead of splitting it. Resolve each class to a strong
+    // direction for the neutral rules, remembering which digits trail RTL.
+    let mut last_strong_rtl = base_rtl;
+    let mut resolved: Vec<Option<bool>> = Vec::with_capacity(classes.len());
+    let mut digit_in_rtl = vec![false; classes.len()];
+    for (ix, class) in classes.iter().enumerate() {
+        resolved.push(match class {
+            Class::Left => {
+                last_strong_rtl = false;
+                Some(false)
+            },
+            Class::Right => {
+                last_strong_rtl = true;
+                Some(true)
+            },
+            Class::Digit => {
+                digit_in_rtl[ix] = last_strong_rtl;
+                Some(last_strong_rtl)
+            },
+            Class::Neutral => None,
+        });
+    }
+
+    // A run of neutrals takes the direction of the strong text on both sides
+    // when they agree, and the base direction otherwise. The row's edges
+    // count as the base direction.
+    let mut ix = 0;
+    while ix < resolved.len() {
+        if resolved[ix].is_some() {
+            ix += 1;
+            continue;
+        }
+        let start = ix;
+        while ix < resolved.len() && resolved[ix].is_none() {
+            ix += 1;
+        }
+        let before = start
+            .checked_sub(1)
+            .and_then(|i| resolved[i])
+            .unwrap_or(base_rtl);
+        let after = resolved.get(ix).copied().flatten().unwrap_or(base_rtl);
+        let rtl = if before == after { before } else { base_rtl };
+        for slot in &mut resolved[start..ix] {
+            *slot = Some(rtl);
+        }
+    }
+
+    // Left-to-right text inside a right-to-left row, and digits inside
+    // right-to-left text, nest one level above the run around them.
+    let mut levels: Vec<u8> = resolved
+        .iter()
+        .zip(&classes)
+        .enumerate()
+        .map(|(ix, (rtl, class))| {
+            let rtl = rtl.expect("every neutral resolved above");
+            if *class == Class::Digit && (digit_in_rtl[ix] || base_rtl) {
+                2
+            } else if rtl {
+                1
+            } else {
+                base * 2
+            }
+        })
+        .collect();
+
+    // Trailing whitespace returns to the base level, so it stays at the end
+    // of the row rather than moving into the run before it.
+    for (level, ch) in levels.iter_mut().zip(chars).rev() {
+        if !ch.is_whitespace() {
+            break;
+        }
+        *level = base;
+    }
+    levels
+}
+
+/// Logical indices in visual order: from the highest level down to the
+/// lowest odd one, every maximal run at or above the level is reversed.
+fn reorder(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let Some(&highest) = levels.iter().max() else {
+        return order;
+    };
+    let lowest_odd = levels
+        .iter()
+        .copied()
+        .filter(|l| l % 2 == 1)
+        .min()
+        .unwrap_or(highest + 1);
+    let mut level = highest;
+    while level >= lowest_odd {
+        let mut ix = 0;
+        while ix < order.len() {
+            if levels[order[ix]] < level {
+                ix += 1;
+                continue;
+            }
+            let start = ix;
+            while ix < order.len() && levels[order[ix]] >= level {
+                ix += 1;
+            }
+            order[start..ix].reverse();
+        }
+        level -= 1;
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VisualOrder;
+
+    fn visual(text: &str) -> String {
+        let order = VisualOrder::new(text.chars()).expect("holds rtl text");
+        let mut cells = vec![' '; order.width() as usize];
+        for (ch, _, visual, _) in order.moves() {
+            cells[visual as usize] = ch;
+        }
+        cells.into_iter().collect()
+    }
+
+    #[test]
+    fn left_to_right_rows_need_no_reordering() {
+        assert_eq!(VisualOrder::new("let x = 1;".chars()), None);
+    }
+
+    #[test]
+    fn reverses_rtl_runs_inside_ltr_text() {
+        assert_eq!(visual("a אבג b"), "a גבא b");
+        assert_eq!(visual("x = \"שלום עולם\";"), "x = \"םלוע םולש\";");
+    }
+
+    #[test]
+    fn digits_keep_their_order_inside_rtl_text() {
+        assert_eq!(visual("a אב 123 ג"), "a ג 123 בא");
+    }
+
+    #[test]
+    fn rtl_base_direction_puts_ltr_runs_right_to_left() {
+        assert_eq!(visual("אב cd ג"), "ג cd בא");
+    }
+
+    #[test]
+    fn maps_columns_both_ways() {
+        let order = VisualOrder::new("a אבג b".chars()).unwrap();
+        assert_eq!(order.to_visual(0), 0);
+        assert_eq!(order.to_visual(2), 4);
+        assert_eq!(order.to_visual(4), 2);
+        assert_eq!(order.to_visual(7), 7);
+        for column in 0..order.width() {
+            assert_eq!(order.to_logical(order.to_visual(column)), column);
+        }
+    }
+}
@@ -14,7 +14,7 @@ use ratatui::text::Line;
 use std::{
     cmp::Ordering,
     collections::HashSet,
-    ops::Deref,
+    ops::{Deref, Range},
     sync::{
         atomic::{AtomicUsize, Ordering::SeqCst},
         Arc, OnceLock,
@@ -204,6 +204,78 @@ impl BlockProperties {
     }
 }
 
+/// Viewport edge a pinned block sticks to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ViewportEdge {
+    Top,
+    Bottom,
+}
+
+/// A block drawn against an edge of the viewport rather than at a buffer row,
+/// such as a sticky scope header or an inline panel.
+///
+/// Pinned blocks take no display rows, so inserting one leaves the transforms
+/// and every row mapping untouched. The renderer paints them over the rows
+/// [`BlockSnapshot::pinned_blocks`] assigns. With a `scope`, a block shows only
+/// while that half-open range of buffer rows runs past its edge: a top block
+/// once the scope's first row has scrolled above the viewport but its last has
+/// not, a bottom block the mirror of that. The check runs on display rows, so
+/// folds and soft wraps move a scope along with its text.
+#[derive(Clone)]
+pub struct PinnedBlockProperties {
+    pub edge: ViewportEdge,
+    pub scope: Option<Range<u32>>,
+    pub height: u32,
+    pub render: RenderBlock,
+    /// Higher priorities sit nearer their edge and win the space when the
+    /// viewport cannot fit every pinned block.
+    pub priority: usize,
+}
+
+impl PinnedBlockProperties {
+    pub fn from_text(edge: ViewportEdge, scope: Option<Range<u32>>, lines: Vec<String>) -> Self {
+        let height = lines.len().max(1) as u32;
+        let lines = Arc::new(lines);
+        Self {
+            edge,
+            scope,
+            height,
+            render: Arc::new(move |_ctx| lines.iter().map(|l| Line::raw(l.clone())).collect()),
+            priority: 0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PinnedBlock {
+    pub id: CustomBlockId,
+    pub edge: ViewportEdge,
+    pub scope: Option<Range<u32>>,
+    pub height: u32,
+    pub render: RenderBlock,
+    pub priority: usize,
+}
+
+impl std::fmt::Debug for PinnedBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PinnedBlock")
+            .field("id", &self.id)
+            .field("edge", &self.edge)
+            .field("scope", &self.scope)
+            .field("height", &self.height)
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
+/// A pinned block showing in a viewport, and the viewport row its first line
+/// paints at.
+#[derive(Clone, Debug)]
+pub struct PinnedPlacement {
+    pub block: Arc<PinnedBlock>,
+    pub viewport_row: u32,
+}
+
 #[derive(Clone)]
 pub struct CustomBlock {
     pub id: CustomBlockId,
@@ -534,6 +606,7 @@ pub struct BlockMap {
     next_spacer_id: AtomicUsize,
     custom_blocks: Vec<Arc<CustomBlock>>,
     custom_blocks_by_id: TreeMap<CustomBlockId, Arc<CustomBlock>>,
+    pinned_blocks: Arc<[Arc<PinnedBlock>]>,
     transforms: Option<SumTree<Transform>>,
     total_rows: u32,
     blocks_dirty: bool,
@@ -557,6 +630,7 @@ impl BlockMap {
             next_spacer_id: AtomicUsize::new(0),
             custom_blocks: Vec::new(),
             custom_blocks_by_id: TreeMap::default(),
+            pinned_blocks: Arc::new([]),
             transforms: None,
             total_rows: 0,
             blocks_dirty: true,
@@ -597,10 +671,39 @@ impl BlockMap {
         ids
     }
 
+    /// Insert blocks pinned to a viewport edge. Their ids share the custom
+    /// block space, so [`Self::remove`] drops them too.
+    pub fn insert_pinned(&mut self, blocks: Vec<PinnedBlockProperties>) -> Vec<CustomBlockId> {
+        let mut pinned = self.pinned_blocks.to_vec();
+        let mut ids = Vec::with_capacity(blocks.len());
+        for props in blocks {
+            let id = CustomBlockId(self.next_block_id.fetch_add(1, SeqCst));
+            pinned.push(Arc::new(PinnedBlock {
+                id,
+                edge: props.edge,
+                scope: props.scope,
+                height: props.height,
+                render: props.render,
+                priority: props.priority,
+            }));
+            ids.push(id);
+        }
+        self.pinned_blocks = pinned.into();
+        ids
+    }
+
     pub fn remove(&mut self, ids: &HashSet<CustomBlockId>) {
         if ids.is_empty() {
             return;
         }
+        if self.pinned_blocks.iter().any(|b| ids.contains(&b.id)) {
+            self.pinned_blocks = self
+                .pinned_blocks
+                .iter()
+                .filter(|b| !ids.contains(&b.id))
+                .cloned()
+                .collect();
+        }
         self.custom_blocks.retain(|b| !ids.contains(&b.id));
         for id in ids {
             self.custom_blocks_by_id.remove(id);
@@ -688,6 +791,7 @@ impl BlockMap {
                 wrap_snapshot,
                 transforms: transforms.clone(),
                 total_rows: self.total_rows,
+                pinned_blocks: self.pinned_blocks.clone(),
             };
         }
 
@@ -742,6 +846,7 @@ impl BlockMap {
             wrap_snapshot,
             transforms,
             total_rows: total_rows.0,
+            pinned_blocks: self.pinned_blocks.clone(),
         }
     }
 
@@ -890,6 +995,7 @@ pub struct BlockSnapshot {
     wrap_snapshot: Arc<WrapSnapshot>,
     transforms: SumTree<Transform>,
     total_rows: u32,
+    pinned_blocks: Arc<[Arc<PinnedBlock>]>,
 }
 
 impl Deref for BlockSnapshot {
@@ -914,8 +1020,16 @@ impl BlockSnapshot {
             .to_fold_point(inlay_point, Bias::Right);
         let tab_point = self.wrap_snapshot.tab_snapshot().to_tab_point(fold_point);
         let wrap_point = self.wrap_snapshot.to_wrap_point(tab_point);
-        let wrap_row = wrap_point.row();
 
+        BlockPoint {
+            row: self.wrap_row_to_block_row(wrap_point.row()),
+            column: wrap_point.column(),
+        }
+    }
+
+    /// Block row of the text line at `wrap_row`, past any blocks placed
+    /// above it.
+    pub fn wrap_row_to_block_row(&self, wrap_row: u32) -> u32 {
         let target = InputRow(wrap_row + 1);
         let mut cursor = self
             .transforms
@@ -923,13 +1037,7 @@ impl BlockSnapshot {
         cursor.seek(&target, Bias::Left);
 
         let Dimensions(input, output, _) = cursor.start();
-        let rows_into_transform = wrap_row.saturating_sub(input.0);
-        let block_row = output.0 + rows_into_transform;
-
-        BlockPoint {
-            row: block_row,
-            column: wrap_point.column(),
-        }
+        output.0 + wrap_row.saturating_sub(input.0)
     }
 
     pub fn block_to_buffer(&self, point: BlockPoint) -> Option<Point> {
@@ -1099,6 +1207,71 @@ impl BlockSnapshot {
         self.total_rows
     }
 
+    /// The pinned blocks showing in a viewport of `viewport_rows` display rows
+    /// scrolled to `scroll_row`, ordered by the viewport row each paints at.
+    ///
+    /// Top blocks stack down from the first row and bottom blocks up from the
+    /// last, in descending priority. A block that no longer fits in the rows
+    /// left between the two stacks is left out.
+    pub fn pinned_blocks(&self, scroll_row: u32, viewport_rows: u32) -> Vec<PinnedPlacement> {
+        let mut showing: Vec<&Arc<PinnedBlock>> = self
+            .pinned_blocks
+            .iter()
+            .filter(|block| self.pinned_scope_shows(block, scroll_row, viewport_rows))
+            .collect();
+        showing.sort_by_key(|block| (std::cmp::Reverse(block.priority), block.id));
+
+        let (mut top, mut bottom) = (0, viewport_rows);
+        let mut placements = Vec::with_capacity(showing.len());
+        for block in showing {
+            if block.height > bottom - top {
+                continue;
+            }
+            let viewport_row = match block.edge {
+                ViewportEdge::Top => {
+                    top += block.height;
+                    top - block.height
+                },
+                ViewportEdge::Bottom => {
+                    bottom -= block.height;
+                    bottom
+                },
+            };
+            placements.push(PinnedPlacement {
+                block: block.clone(),
+                viewport_row,
+            });
+        }
+        placements.sort_by_key(|placement| placement.viewport_row);
+        placements
+    }
+
+    /// Whether `block`'s scope runs past its edge of the viewport. The scope's
+    /// first and last buffer rows map to display rows through the fold and wrap
+    /// layers, the last to its final wrapped segment.
+    fn pinned_scope_shows(&self, block: &PinnedBlock, scroll_row: u32, viewport_rows: u32) -> bool {
+        let Some(scope) = &block.scope else {
+            return true;
+        };
+        let line_count = self.buffer_line_count();
+        if scope.is_empty() || scope.start >= line_count {
+            return false;
+        }
+        let last = (scope.end - 1).min(line_count - 1);
+        let rope = self.buffer_snapshot().rope();
+        let first_row = self.buffer_to_block(Point::new(scope.start, 0)).row;
+        let last_row = self
+            .buffer_to_block(Point::new(last, rope.line_len(last)))
+            .row;
+        match block.edge {
+            ViewportEdge::Top => first_row < scroll_row && last_row >= scroll_row,
+            ViewportEdge::Bottom => {
+                let end = scroll_row + viewport_rows;
+                first_row < end && last_row >= end
+            },
+        }
+    }
+
     pub fn buffer_line_count(&self) -> u32 {
         self.wrap_snapshot
             .fold_snapshot()
@@ -1796,7 +1969,10 @@ fn push_isomorphic(
 
 #[cfg(test)]
 mod tests {
-    use super::{BlockMap, BlockPlacement, BlockPoint, BlockProperties, BlockRowKind, BlockStyle};
+    use super::{
+        BlockMap, BlockPlacement, BlockPoint, BlockProperties, BlockRowKind, BlockStyle,
+        PinnedBlockProperties, ViewportEdge,
+    };
     use crate::{
         buffer::{BufferId, TextBuffer},
         display_map::{fold_map::FoldMap, inlay_map::InlayMap, tab_map::TabMap, wrap_map::WrapMap},
@@ -1833,6 +2009,81 @@ mod tests {
         )
     }
 
+    fn create_pinned_snapshot(
+        content: &str,
+        wrap_width: Option<u32>,
+        pinned: Vec<PinnedBlockProperties>,
+    ) -> super::BlockSnapshot {
+        let buffer = TextBuffer::with_text(BufferId::new(0), content);
+        let shared = Arc::new(RwLock::new(buffer));
+        let multi_buffer = MultiBuffer::singleton(BufferId::new(0), shared);
+        let (_, inlay_snapshot) = InlayMap::new(multi_buffer.snapshot());
+        let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
+        let mut tab_map = TabMap::new(std::num::NonZeroU32::new(4).unwrap());
+        let (tab_snapshot, _) = tab_map.sync(fold_snapshot, Patch::empty());
+        let (_, wrap_snapshot) = WrapMap::new(tab_snapshot, wrap_width, test_executor());
+        let mut block_map = BlockMap::new();
+        block_map.insert_pinned(pinned);
+        block_map.sync(wrap_snapshot, &Patch::empty(), None)
+    }
+
+    fn pinned_rows(snapshot: &super::BlockSnapshot, scroll_row: u32, rows: u32) -> Vec<(u32, u32)> {
+        snapshot
+            .pinned_blocks(scroll_row, rows)
+            .iter()
+            .map(|p| (p.viewport_row, p.block.height))
+            .collect()
+    }
+
+    #[test]
+    fn pinned_blocks_take_no_rows_and_stack_from_their_edges() {
+        let lines = |n: usize| vec![String::new(); n];
+        let mut low = PinnedBlockProperties::from_text(ViewportEdge::Top, None, lines(1));
+        low.priority = 0;
+        let mut high = PinnedBlockProperties::from_text(ViewportEdge::Top, None, lines(2));
+        high.priority = 1;
+        let mut bottom = PinnedBlockProperties::from_text(ViewportEdge::Bottom, None, lines(2));
+        bottom.priority = 1;
+        let snapshot = create_pinned_snapshot("a\nb\nc\nd\ne\nf", None, vec![low, high, bottom]);
+
+        assert_eq!(snapshot.total_lines(), 6, "pinned blocks add no rows");
+        assert_eq!(pinned_rows(&snapshot, 0, 6), vec![(0, 2), (2, 1), (4, 2)]);
+        assert_eq!(
+            pinned_rows(&snapshot, 0, 4),
+            vec![(0, 2), (2, 2)],
+            "the lowest priority block no longer fits"
+        );
+    }
+
+    #[test]
+    fn pinned_scope_measures_wrapped_rows() {
+        // Row 1 wraps into three display rows at width 4, so the scope 0..2
+        // covers display rows 0..=3.
+        let scoped = |edge| PinnedBlockProperties::from_text(edge, Some(0..2), vec!["h".into()]);
+        let snapshot = create_pinned_snapshot(
+            "ab\nabcdefghij\nc\nd\ne\nf",
+            Some(4),
+            vec![scoped(ViewportEdge::Top)],
+        );
+        assert!(
+            pinned_rows(&snapshot, 0, 2).is_empty(),
+            "scope start visible"
+        );
+        assert_eq!(pinned_rows(&snapshot, 3, 2), vec![(0, 1)]);
+        assert!(
+            pinned_rows(&snapshot, 4, 2).is_empty(),
+            "scope scrolled past"
+        );
+
+        let snapshot = create_pinned_snapshot(
+            "ab\nabcdefghij\nc\nd\ne\nf",
+            Some(4),
+            vec![scoped(ViewportEdge::Bottom)],
+        );
+        assert_eq!(pinned_rows(&snapshot, 0, 3), vec![(2, 1)]);
+        assert!(pinned_rows(&snapshot, 0, 4).is_empty(), "scope end visible");
+    }
+
     #[test]
     fn custom_block_render_memoized() {
         use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
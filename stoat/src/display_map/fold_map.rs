@@ -81,6 +81,31 @@ impl FoldPlaceholder {
     fn display_text(&self) -> &str {
         self.collapsed_text.as_deref().unwrap_or(self.text.as_ref())
     }
+
+    /// Placeholder naming how many lines a fold over `range` of `rope` hides,
+    /// such as `{ … 42 lines }`. The brackets are kept when the range opens
+    /// and closes with a matching pair, so a folded body still reads as one.
+    /// Counted folds never merge with their neighbours, whose counts differ.
+    pub fn line_count(rope: &Rope, range: Range<usize>) -> Self {
+        let lines = rope.offset_to_point(range.end).row - rope.offset_to_point(range.start).row + 1;
+        let open = rope.chars_at(range.start).next();
+        let close = rope.reversed_chars_at(range.end).next();
+        let text = match (open, close) {
+            (Some(open @ '{'), Some(close @ '}'))
+            | (Some(open @ '('), Some(close @ ')'))
+            | (Some(open @ '['), Some(close @ ']'))
+                if range.end - range.start > 1 =>
+            {
+                format!("{open} … {lines} lines {close}")
+            },
+            _ => format!("… {lines} lines"),
+        };
+        Self {
+            text: Arc::from(text),
+            merge_adjacent: false,
+            ..Self::default()
+        }
+    }
 }
 
 impl Default for FoldPlaceholder {
@@ -419,11 +444,25 @@ impl FoldMap {
         ranges: Vec<Range<Anchor>>,
         placeholder: FoldPlaceholder,
         buffer_snapshot: &MultiBufferSnapshot,
+    ) -> Vec<FoldId> {
+        let folds = ranges
+            .into_iter()
+            .map(|range| (range, placeholder.clone()))
+            .collect();
+        self.fold_with_placeholders(folds, buffer_snapshot)
+    }
+
+    /// Like [`Self::fold`], but each range brings its own placeholder, as
+    /// syntax folds labelled by [`FoldPlaceholder::line_count`] do.
+    pub fn fold_with_placeholders(
+        &mut self,
+        folds: Vec<(Range<Anchor>, FoldPlaceholder)>,
+        buffer_snapshot: &MultiBufferSnapshot,
     ) -> Vec<FoldId> {
         let resolve = |a: &Anchor| buffer_snapshot.resolve_anchor(a);
-        let mut new_folds: Vec<AnchoredFold> = ranges
+        let mut new_folds: Vec<AnchoredFold> = folds
             .into_iter()
-            .map(|range| {
+            .map(|(range, placeholder)| {
                 let resolved_start = resolve(&range.start);
                 let resolved_end = resolve(&range.end);
                 let id = FoldId(self.next_id);
@@ -431,7 +470,7 @@ impl FoldMap {
                 AnchoredFold {
                     id,
                     range,
-                    placeholder: placeholder.clone(),
+                    placeholder,
                     resolved_start,
                     resolved_end,
                 }
@@ -84,6 +84,7 @@ pub enum HighlightLayer {
     LspSemanticToken,
     SearchHighlight,
     DiffHighlight,
+    SymbolOccurrence,
     DocumentHighlightRead,
     DocumentHighlightWrite,
     EditPredictionHighlight,
@@ -121,6 +122,13 @@ pub type TextHighlights = Arc<HashMap<HighlightKey, Arc<(HighlightStyle, Vec<Ran
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct HighlightStyleId(u32);
 
+impl HighlightStyleId {
+    /// Position of the style in its [`HighlightStyleInterner`].
+    pub(crate) fn index(self) -> u32 {
+        self.0
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct HighlightStyleInterner {
     styles: Vec<HighlightStyle>,
@@ -137,6 +145,21 @@ impl HighlightStyleInterner {
         self.styles.push(style);
         HighlightStyleId(id)
     }
+
+    /// The id of the `index`th interned style, or `None` past the last one.
+    pub(crate) fn id_at(&self, index: u32) -> Option<HighlightStyleId> {
+        ((index as usize) < self.styles.len()).then_some(HighlightStyleId(index))
+    }
+
+    /// Hash of the interned styles in order. Two interners agree on what every
+    /// [`HighlightStyleId`] means exactly when their fingerprints match, barring
+    /// collisions.
+    pub(crate) fn fingerprint(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.styles.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
 impl std::ops::Index<HighlightStyleId> for HighlightStyleInterner {
@@ -225,6 +248,29 @@ impl BufferSemanticTokens {
         }
         left..hi
     }
+
+    /// The non-empty tokens overlapping rows `rows` of `rope`, with their
+    /// resolved byte ranges, in start order.
+    ///
+    /// The rows map to bytes through [`Rope::rows_byte_range`], a seek on the
+    /// rope's line summaries, and [`Self::overlap_bounds`] then limits the walk
+    /// to the tokens that can reach them.
+    pub fn tokens_in_lines<'a>(
+        &'a self,
+        rope: &Rope,
+        rows: Range<u32>,
+        resolve: impl Fn(&Anchor) -> usize + 'a,
+    ) -> impl Iterator<Item = (&'a SemanticTokenHighlight, Range<usize>)> + 'a {
+        let byte_range = rope.rows_byte_range(rows);
+        let bounds = self.overlap_bounds(&byte_range, &resolve);
+        self.tokens[bounds].iter().filter_map(move |token| {
+            let range = resolve(&token.range.start)..resolve(&token.range.end);
+            (range.start < range.end
+                && range.start < byte_range.end
+                && range.end > byte_range.start)
+                .then_some((token, range))
+        })
+    }
 }
 
 pub type SemanticTokensHighlights = Arc<HashMap<BufferId, BufferSemanticTokens>>;
@@ -1014,6 +1060,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tokens_in_lines_keeps_tokens_reaching_the_rows() {
+        use super::{BufferSemanticTokens, HighlightStyleInterner, SemanticTokenHighlight};
+        use stoat_text::Rope;
+
+        let rope = Rope::from("aaaa\nbbbb\ncccc\n");
+        let mut interner = HighlightStyleInterner::default();
+        let id = interner.intern(HighlightStyle::default());
+        let tokens: Arc<[SemanticTokenHighlight]> = [(0, 4), (2, 12), (5, 9), (9, 9), (10, 14)]
+            .iter()
+            .map(|&(s, e)| SemanticTokenHighlight {
+                range: anchor(s)..anchor(e),
+                style: id,
+            })
+            .collect();
+        let resolve = |a: &Anchor| a.offset as usize;
+        let channel = BufferSemanticTokens::new(tokens, Arc::new(interner), resolve);
+
+        let ranges = |rows| -> Vec<Range<usize>> {
+            channel
+                .tokens_in_lines(&rope, rows, resolve)
+                .map(|(_, range)| range)
+                .collect()
+        };
+        assert_eq!(ranges(1..2), vec![2..12, 5..9], "empty tokens are skipped");
+        assert_eq!(ranges(2..9), vec![2..12, 10..14]);
+        assert!(ranges(3..9).is_empty());
+    }
+
     #[test]
     fn bottom_viewport_emits_only_overlapping_endpoints() {
         use super::{
@@ -10,8 +10,9 @@ use std::{
     sync::{Arc, OnceLock},
 };
 use stoat_text::{
-    patch::Patch, Anchor, Bias, ContextLessSummary, Cursor, Dimension, Dimensions, Item, Point,
-    Rope, SeekTarget, SumTree, TextSummary,
+    patch::{Edit, Patch},
+    Anchor, Bias, ContextLessSummary, Cursor, Dimension, Dimensions, Item, Point, Rope, SeekTarget,
+    SumTree, TextSummary,
 };
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -44,6 +45,9 @@ impl AddAssign for InlayOffset {
 pub enum InlayKind {
     Hint,
     EditPrediction,
+    /// A color swatch, painted in the carried RGB color instead of the
+    /// virtual-text style.
+    Swatch([u8; 3]),
     Other,
 }
 
@@ -158,6 +162,11 @@ pub struct InlayMap {
     last_self_version: usize,
     inlays_sorted: bool,
     cached_offsets: Vec<usize>,
+    /// Positions of the inlays spliced in or out since the last sync. Only
+    /// their rows change when the buffer did not, so the sync reports just
+    /// those rows. `None` once a spliced inlay spans lines and shifts every
+    /// row after it.
+    spliced_positions: Option<Vec<Anchor>>,
 }
 
 pub struct InlaySnapshot {
@@ -195,6 +204,7 @@ impl InlayMap {
             last_self_version: 0,
             inlays_sorted: true,
             cached_offsets: Vec::new(),
+            spliced_positions: Some(Vec::new()),
         };
         (map, snapshot)
     }
@@ -213,6 +223,7 @@ impl InlayMap {
 
         let inlay_count = self.inlays.len();
         let inlays_changed = self.version != self.last_self_version;
+        let spliced = self.spliced_positions.replace(Vec::new());
         let can_incremental = !buffer_edits.is_empty()
             && !inlays_changed
             && self.cached_snapshot.is_some()
@@ -250,10 +261,19 @@ impl InlayMap {
             } else {
                 transforms.summary().output.lines.row + 1
             };
-            let edits = Patch::new(vec![stoat_text::patch::Edit {
-                old: 0..old_line_count,
-                new: 0..new_line_count,
-            }]);
+            let splice_only = inlays_changed
+                && buffer_edits.is_empty()
+                && buffer_snapshot.version() == self.last_buffer_version
+                && old_line_count == new_line_count;
+            let edits = match spliced {
+                Some(positions) if splice_only && !positions.is_empty() => {
+                    spliced_row_edits(&buffer_snapshot, &positions)
+                },
+                _ => Patch::new(vec![Edit {
+                    old: 0..old_line_count,
+                    new: 0..new_line_count,
+                }]),
+            };
             (transforms, edits)
         };
 
@@ -373,20 +393,35 @@ impl InlayMap {
         self.version == self.last_self_version
     }
 
+    /// Remove the inlays named by `remove` and add `insert`, returning the ids
+    /// of the added inlays. A splice that changes nothing leaves the version
+    /// alone.
     pub fn splice(
         &mut self,
         remove: Vec<InlayId>,
         insert: Vec<(Anchor, String, InlayKind)>,
     ) -> Vec<InlayId> {
+        if remove.is_empty() && insert.is_empty() {
+            return Vec::new();
+        }
+
         if !remove.is_empty() {
             let remove_set: HashSet<InlayId> = remove.into_iter().collect();
-            self.inlays.retain(|inlay| !remove_set.contains(&inlay.id));
+            let spliced = &mut self.spliced_positions;
+            self.inlays.retain(|inlay| {
+                let removed = remove_set.contains(&inlay.id);
+                if removed {
+                    note_spliced(spliced, inlay.position, &inlay.text);
+                }
+                !removed
+            });
         }
 
         let mut new_ids = Vec::with_capacity(insert.len());
         for (position, text, kind) in insert {
             let id = InlayId(self.next_id);
             self.next_id += 1;
+            note_spliced(&mut self.spliced_positions, position, &text);
             self.inlays.push(AnchoredInlay {
                 id,
                 position,
@@ -400,6 +435,103 @@ impl InlayMap {
         self.version += 1;
         new_ids
     }
+
+    /// Bring the inlays named by `current` in line with `desired`, a
+    /// provider's complete new set, returning the id standing for each entry
+    /// of `desired` in order.
+    ///
+    /// An inlay of `current` already showing the same text of the same kind at
+    /// the same offset of `buffer` keeps its id and stays put. Only the rest of
+    /// `current` is removed and only the unmatched entries of `desired` are
+    /// inserted, in a single [`Self::splice`], so a refresh that reports an
+    /// unchanged set rebuilds nothing and a partial change only touches the
+    /// rows it moves.
+    pub fn reconcile(
+        &mut self,
+        buffer: &MultiBufferSnapshot,
+        current: Vec<InlayId>,
+        desired: Vec<(Anchor, String, InlayKind)>,
+    ) -> Vec<InlayId> {
+        let current: HashSet<InlayId> = current.into_iter().collect();
+        let existing: Vec<&AnchoredInlay> = self
+            .inlays
+            .iter()
+            .filter(|inlay| current.contains(&inlay.id))
+            .collect();
+        let anchors: Vec<Anchor> = existing.iter().map(|inlay| inlay.position).collect();
+        let mut unmatched: HashMap<(usize, InlayKind), Vec<(Arc<str>, InlayId)>> = HashMap::new();
+        for (inlay, offset) in existing.iter().zip(buffer.resolve_anchors_batch(&anchors)) {
+            unmatched
+                .entry((offset, inlay.kind))
+                .or_default()
+                .push((Arc::clone(&inlay.text), inlay.id));
+        }
+
+        let anchors: Vec<Anchor> = desired.iter().map(|(anchor, _, _)| *anchor).collect();
+        let mut ids = Vec::with_capacity(desired.len());
+        let mut insert = Vec::new();
+        for ((anchor, text, kind), offset) in desired
+            .into_iter()
+            .zip(buffer.resolve_anchors_batch(&anchors))
+        {
+            let kept = unmatched.get_mut(&(offset, kind)).and_then(|candidates| {
+                let ix = candidates.iter().position(|(shown, _)| **shown == *text)?;
+                Some(candidates.swap_remove(ix).1)
+            });
+            if kept.is_none() {
+                insert.push((anchor, text, kind));
+            }
+            ids.push(kept);
+        }
+
+        let remove = unmatched
+            .into_values()
+            .flatten()
+            .map(|(_, id)| id)
+            .collect();
+        let mut inserted = self.splice(remove, insert).into_iter();
+        ids.into_iter()
+            .map(|kept| kept.unwrap_or_else(|| inserted.next().expect("an id per insert")))
+            .collect()
+    }
+}
+
+/// Record a spliced inlay's position, or give up on row-local edits when its
+/// text spans lines.
+fn note_spliced(spliced: &mut Option<Vec<Anchor>>, position: Anchor, text: &str) {
+    if text.contains('\n') {
+        *spliced = None;
+    } else if let Some(positions) = spliced {
+        positions.push(position);
+    }
+}
+
+/// Row edits covering each row that holds a spliced inlay. Single-line inlays
+/// leave the row count alone, so old and new rows coincide.
+fn spliced_row_edits(buffer: &MultiBufferSnapshot, positions: &[Anchor]) -> Patch<u32> {
+    let rope = buffer.rope();
+    let mut rows: Vec<u32> = buffer
+        .resolve_anchors_batch(positions)
+        .into_iter()
+        .map(|offset| rope.offset_to_point(offset.min(rope.len())).row)
+        .collect();
+    rows.sort_unstable();
+    rows.dedup();
+
+    let mut edits: Vec<Edit<u32>> = Vec::new();
+    for row in rows {
+        match edits.last_mut() {
+            Some(last) if last.new.end == row => {
+                last.old.end += 1;
+                last.new.end += 1;
+            },
+            _ => edits.push(Edit {
+                old: row..row + 1,
+                new: row..row + 1,
+            }),
+        }
+    }
+    Patch::new(edits)
 }
 
 fn build_transforms(rope: &Rope, text: &str, inlays: &[Inlay]) -> SumTree<Transform> {
@@ -1191,4 +1323,47 @@ mod tests {
         let text: String = chunks.iter().map(|c| c.text.as_ref()).collect();
         assert_eq!(text, "de!!fg");
     }
+
+    #[test]
+    fn reconcile_keeps_unchanged_inlays_and_edits_only_their_rows() {
+        let buffer = TextBuffer::with_text(BufferId::new(0), "ab\ncd\nef");
+        let shared = Arc::new(RwLock::new(buffer));
+        let multi_buffer = MultiBuffer::singleton(BufferId::new(0), shared);
+        let buffer_snapshot = multi_buffer.snapshot();
+        let (mut map, _) = InlayMap::new(buffer_snapshot.clone());
+        let hints = |texts: [&str; 2]| {
+            [Point::new(0, 1), Point::new(1, 1)]
+                .into_iter()
+                .zip(texts)
+                .map(|(point, text)| {
+                    let offset = buffer_snapshot.rope().point_to_offset(point);
+                    (
+                        buffer_snapshot.anchor_at(offset, stoat_text::Bias::Right),
+                        text.to_string(),
+                        super::InlayKind::Hint,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let ids = map.reconcile(&buffer_snapshot, Vec::new(), hints(["x", "y"]));
+        map.sync(buffer_snapshot.clone(), &Patch::empty());
+
+        let same = map.reconcile(&buffer_snapshot, ids.clone(), hints(["x", "y"]));
+        assert_eq!(same, ids);
+        assert!(map.version_unchanged());
+
+        let changed = map.reconcile(&buffer_snapshot, same, hints(["x", "z"]));
+        assert_eq!(changed[0], ids[0]);
+        assert_ne!(changed[1], ids[1]);
+        let (snap, edits) = map.sync(buffer_snapshot, &Patch::empty());
+        assert_eq!(
+            edits.edits(),
+            [stoat_text::patch::Edit {
+                old: 1..2,
+                new: 1..2,
+            }]
+        );
+        assert_eq!(snap.line_len(1), 3);
+    }
 }
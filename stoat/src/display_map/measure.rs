@@ -0,0 +1,91 @@
+//! How wide text is once a font draws it.
+//!
+//! Display columns assume a monospace grid: every character advances the
+//! pen by its [`super::display_width`] in cells. A host that draws with a
+//! proportional font, ligatures or fallback faces advances by the glyphs it
+//! actually shapes, so a row of `width` columns may hold more or fewer
+//! characters than the grid suggests. A [`TextMeasure`] reports those
+//! advances in cell units. The wrap map fills rows by it, and
+//! [`super::DisplaySnapshot::measured_x`] places the cursor by it, while
+//! display columns keep counting cells so every other coordinate is
+//! unchanged.
+
+use std::{fmt, sync::Arc};
+
+/// Advance widths of the glyphs a host draws, in cells of the editor grid.
+pub trait TextMeasure: Send + Sync {
+    /// How far `ch` moves the pen when it follows `prev` on the same row.
+    /// `prev` lets a shaper account for ligatures and kerning: a two-glyph
+    /// ligature can report the pair's combined width on its second character
+    /// less what it reported for the first.
+    fn advance(&self, prev: Option<char>, ch: char) -> f32;
+
+    /// Whether every advance equals the character's display width, letting
+    /// callers skip measuring and use columns directly.
+    fn is_monospace(&self) -> bool {
+        false
+    }
+}
+
+/// The terminal grid: each character advances by its display width.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Monospace;
+
+impl TextMeasure for Monospace {
+    fn advance(&self, _prev: Option<char>, ch: char) -> f32 {
+        super::display_width(ch) as f32
+    }
+
+    fn is_monospace(&self) -> bool {
+        true
+    }
+}
+
+/// A shared [`TextMeasure`], compared by identity so swapping in a new one
+/// is a change even when it measures the same.
+#[derive(Clone)]
+pub struct MeasureRef(Arc<dyn TextMeasure>);
+
+impl MeasureRef {
+    pub fn new(measure: impl TextMeasure + 'static) -> Self {
+        Self(Arc::new(measure))
+    }
+
+    pub fn is_monospace(&self) -> bool {
+        self.0.is_monospace()
+    }
+
+    /// Advance of `ch` after `prev`, in cells. A tab's advance is `tab_width`
+    /// whatever the font, since tab stops sit on the grid.
+    pub fn advance(&self, prev: Option<char>, ch: char, tab_width: u32) -> f32 {
+        if ch == '\t' {
+            tab_width as f32
+        } else {
+            self.0.advance(prev, ch)
+        }
+    }
+}
+
+impl Default for MeasureRef {
+    fn default() -> Self {
+        Self::new(Monospace)
+    }
+}
+
+impl PartialEq for MeasureRef {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for MeasureRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MeasureRef")
+            .field(&if self.is_monospace() {
+                "monospace"
+            } else {
+                "measured"
+            })
+            .finish()
+    }
+}
@@ -8,10 +8,39 @@ use std::{
     ops::{Deref, Range},
     sync::Arc,
 };
-use stoat_text::{patch::Patch, Bias};
+use stoat_text::{
+    patch::{Edit, Patch},
+    Bias, ContextLessSummary, Item, SumTree,
+};
 
 const MAX_EXPANSION_COLUMN: u32 = 256;
 
+/// Per-row tab widths in elastic mode, one item per fold row.
+type ElasticWidths = SumTree<ElasticRow>;
+
+/// Widths of the tabs on one fold row, or `None` for a row without a tab.
+#[derive(Clone, Debug)]
+struct ElasticRow(Option<Arc<[u32]>>);
+
+/// Number of fold rows covered, both the summary and the seek dimension of
+/// [`ElasticWidths`].
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct RowCount(usize);
+
+impl ContextLessSummary for RowCount {
+    fn add_summary(&mut self, other: &Self) {
+        self.0 += other.0;
+    }
+}
+
+impl Item for ElasticRow {
+    type Summary = RowCount;
+
+    fn summary(&self, _cx: ()) -> RowCount {
+        RowCount(1)
+    }
+}
+
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TabPoint(pub FoldPoint);
 
@@ -42,9 +71,19 @@ impl From<FoldPoint> for TabPoint {
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TabRow(pub u32);
 
+/// Expands tabs to display columns.
+///
+/// By default a tab advances to the next multiple of the tab size. In elastic
+/// mode the tabs instead align columns across adjacent lines: a line splits
+/// into cells at its tabs, and each run of consecutive lines that all have an
+/// `n`th cell pads that cell to the widest one in the run. The widths are kept
+/// per row and only the paragraphs an edit touches are recomputed.
 pub struct TabMap {
     tab_size: NonZeroU32,
     version: usize,
+    elastic: bool,
+    elastic_widths: Option<ElasticWidths>,
+    layout_version: usize,
 }
 
 impl TabMap {
@@ -52,11 +91,33 @@ impl TabMap {
         Self {
             tab_size,
             version: 0,
+            elastic: false,
+            elastic_widths: None,
+            layout_version: 0,
         }
     }
 
     pub fn set_tab_size(&mut self, size: NonZeroU32) {
-        self.tab_size = size;
+        if self.tab_size != size {
+            self.tab_size = size;
+            self.elastic_widths = None;
+            self.layout_version += 1;
+        }
+    }
+
+    /// Switch elastic tabstops on or off. Every tab may change width, so the
+    /// next sync recomputes all rows and [`TabSnapshot::layout_version`]
+    /// advances for the maps above to rebuild.
+    pub fn set_elastic_tabstops(&mut self, elastic: bool) {
+        if self.elastic != elastic {
+            self.elastic = elastic;
+            self.elastic_widths = None;
+            self.layout_version += 1;
+        }
+    }
+
+    pub fn elastic_tabstops(&self) -> bool {
+        self.elastic
     }
 
     pub fn sync(
@@ -66,7 +127,9 @@ impl TabMap {
     ) -> (TabSnapshot, Patch<u32>) {
         let tab_size = self.tab_size.get();
 
-        let expanded_edits = if fold_edits.is_empty() {
+        let expanded_edits = if self.elastic {
+            self.sync_elastic(&fold_snapshot, fold_edits)
+        } else if fold_edits.is_empty() {
             fold_edits
         } else {
             let mut expanded = Vec::new();
@@ -116,9 +179,169 @@ impl TabMap {
             tab_size,
             max_expansion_column: MAX_EXPANSION_COLUMN,
             version: self.version,
+            elastic_widths: self.elastic_widths.clone(),
+            layout_version: self.layout_version,
         };
         (snapshot, expanded_edits)
     }
+
+    /// Bring the elastic widths up to date with `fold_edits` and return the
+    /// tab edits covering every row whose widths may have changed.
+    ///
+    /// A tab width depends only on the paragraph of consecutive tab-bearing
+    /// rows around it, so each edit widens to the paragraphs it touches and
+    /// just those rows are recomputed. Without widths from a previous sync
+    /// every row is computed.
+    fn sync_elastic(&mut self, fold_snapshot: &FoldSnapshot, fold_edits: Patch<u32>) -> Patch<u32> {
+        let tab_size = self.tab_size.get();
+        let line_count = fold_snapshot.line_count();
+        let Some(old_widths) = self.elastic_widths.take() else {
+            self.elastic_widths = Some(SumTree::from_iter(
+                elastic_widths(fold_snapshot, 0..line_count, tab_size),
+                (),
+            ));
+            return fold_edits;
+        };
+
+        let has_tab = |row: u32| fold_snapshot.fold_line_chars(row).any(|ch| ch == '\t');
+        let mut widened: Vec<Edit<u32>> = Vec::new();
+        for edit in fold_edits.into_iter() {
+            let mut new = edit.new.clone();
+            while new.start > 0 && has_tab(new.start - 1) {
+                new.start -= 1;
+            }
+            while new.end < line_count && has_tab(new.end) {
+                new.end += 1;
+            }
+            // Rows outside the edit are unchanged, so the old side grows by
+            // the same number of rows.
+            let old = edit.old.start.saturating_sub(edit.new.start - new.start)
+                ..edit.old.end + (new.end - edit.new.end);
+            match widened.last_mut() {
+                Some(last) if old.start <= last.old.end => {
+                    last.old.end = last.old.end.max(old.end);
+                    last.new.end = last.new.end.max(new.end);
+                },
+                _ => widened.push(Edit { old, new }),
+            }
+        }
+
+        let mut cursor = old_widths.cursor::<RowCount>(());
+        let mut new_widths = SumTree::new(());
+        for edit in &widened {
+            new_widths.append(
+                cursor.slice(&RowCount(edit.old.start as usize), Bias::Right),
+                (),
+            );
+            new_widths.extend(
+                elastic_widths(fold_snapshot, edit.new.clone(), tab_size),
+                (),
+            );
+            cursor.seek_forward(&RowCount(edit.old.end as usize), Bias::Right);
+        }
+        new_widths.append(cursor.suffix(), ());
+        drop(cursor);
+        debug_assert_eq!(new_widths.summary().0, line_count as usize);
+        self.elastic_widths = Some(new_widths);
+        Patch::new(widened)
+    }
+}
+
+/// Elastic widths of each tab on each row in `rows`, which must cover whole
+/// paragraphs of tab-bearing rows.
+///
+/// A row's `n`th cell is its text before its `n`th tab. Consecutive rows that
+/// all have an `n`th cell form a column block, and every cell in the block
+/// pads to one past the widest, but never to less than `tab_size`.
+fn elastic_widths(
+    fold_snapshot: &FoldSnapshot,
+    rows: Range<u32>,
+    tab_size: u32,
+) -> Vec<ElasticRow> {
+    let cells: Vec<Vec<u32>> = rows
+        .map(|row| cell_widths(fold_snapshot.fold_line_chars(row)))
+        .collect();
+    let mut widths: Vec<Vec<u32>> = cells.iter().map(|row| vec![0; row.len()]).collect();
+    let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+    for column in 0..columns {
+        let mut row = 0;
+        while row < cells.len() {
+            if cells[row].len() <= column {
+                row += 1;
+                continue;
+            }
+            let block_start = row;
+            while row < cells.len() && cells[row].len() > column {
+                row += 1;
+            }
+            let widest = cells[block_start..row]
+                .iter()
+                .map(|cells| cells[column])
+                .max()
+                .unwrap_or(0);
+            let stop = (widest + 1).max(tab_size);
+            for (widths, cells) in widths[block_start..row]
+                .iter_mut()
+                .zip(&cells[block_start..row])
+            {
+                widths[column] = stop - cells[column];
+            }
+        }
+    }
+    widths
+        .into_iter()
+        .map(|row| ElasticRow((!row.is_empty()).then(|| Arc::from(row))))
+        .collect()
+}
+
+/// Display width of the text before each tab on a row.
+fn cell_widths(chars: impl Iterator<Item = char>) -> Vec<u32> {
+    let mut cells = Vec::new();
+    let mut width = 0;
+    for ch in chars {
+        if ch == '\t' {
+            cells.push(width);
+            width = 0;
+        } else {
+            width += super::display_width(ch);
+        }
+    }
+    cells
+}
+
+/// How the tabs on one row expand: to fixed stops every `tab_size` columns,
+/// or to the widths elastic mode computed for the row. Past
+/// `max_expansion_column` a tab counts as a single column either way.
+#[derive(Clone, Copy, Debug)]
+pub struct RowTabs<'a> {
+    tab_size: u32,
+    max_expansion_column: u32,
+    elastic: Option<&'a [u32]>,
+}
+
+impl RowTabs<'_> {
+    /// Display width of the row's `index`th tab, starting at `column`.
+    pub fn width(&self, column: u32, index: usize) -> u32 {
+        if column >= self.max_expansion_column {
+            return 1;
+        }
+        match self.elastic.and_then(|widths| widths.get(index)) {
+            Some(&width) => width,
+            None => self.tab_size - (column % self.tab_size),
+        }
+    }
+
+    /// Display width of `ch` at `column`, counting the tabs seen so far on the
+    /// row in `tab_index`.
+    pub fn char_width(&self, ch: char, column: u32, tab_index: &mut usize) -> u32 {
+        if ch == '\t' {
+            let width = self.width(column, *tab_index);
+            *tab_index += 1;
+            width
+        } else {
+            super::display_width(ch)
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -127,6 +350,8 @@ pub struct TabSnapshot {
     tab_size: u32,
     max_expansion_column: u32,
     version: usize,
+    elastic_widths: Option<ElasticWidths>,
+    layout_version: usize,
 }
 
 impl Deref for TabSnapshot {
@@ -153,14 +378,36 @@ impl TabSnapshot {
         self.version
     }
 
+    /// Whether tabs align columns across lines instead of stopping every
+    /// [`Self::tab_size`] columns.
+    pub fn elastic_tabstops(&self) -> bool {
+        self.elastic_widths.is_some()
+    }
+
+    /// Bumped whenever every tab may have changed width at once, such as on a
+    /// tab size change or toggling elastic tabstops.
+    pub fn layout_version(&self) -> usize {
+        self.layout_version
+    }
+
+    /// How the tabs on `fold_row` expand.
+    pub fn row_tabs(&self, fold_row: u32) -> RowTabs<'_> {
+        let elastic = self.elastic_widths.as_ref().and_then(|widths| {
+            let (_, _, item) =
+                widths.find::<RowCount, _>((), &RowCount(fold_row as usize), Bias::Right);
+            item.and_then(|row| row.0.as_deref())
+        });
+        RowTabs {
+            tab_size: self.tab_size,
+            max_expansion_column: self.max_expansion_column,
+            elastic,
+        }
+    }
+
     pub fn to_tab_point(&self, fold_point: FoldPoint) -> TabPoint {
         let chars = self.fold_snapshot.fold_line_chars(fold_point.row());
-        let expanded_column = expand_column(
-            chars,
-            fold_point.column(),
-            self.tab_size,
-            self.max_expansion_column,
-        );
+        let expanded_column =
+            expand_column(chars, fold_point.column(), self.row_tabs(fold_point.row()));
         TabPoint::new(fold_point.row(), expanded_column)
     }
 
@@ -169,9 +416,8 @@ impl TabSnapshot {
         let fold_column = collapse_column(
             chars,
             tab_point.column(),
-            self.tab_size,
+            self.row_tabs(tab_point.row()),
             bias,
-            self.max_expansion_column,
         );
         FoldPoint::new(tab_point.row(), fold_column)
     }
@@ -185,9 +431,8 @@ impl TabSnapshot {
         let (fold_column, expanded_char_column, to_next_stop) = collapse_column_detailed(
             chars,
             tab_point.column(),
-            self.tab_size,
+            self.row_tabs(tab_point.row()),
             bias,
-            self.max_expansion_column,
         );
         (
             FoldPoint::new(tab_point.row(), fold_column),
@@ -201,8 +446,7 @@ impl TabSnapshot {
         expand_column(
             self.fold_snapshot.fold_line_chars(fold_row),
             fold_line_len,
-            self.tab_size,
-            self.max_expansion_column,
+            self.row_tabs(fold_row),
         )
     }
 
@@ -215,22 +459,19 @@ impl TabSnapshot {
     }
 
     pub fn write_expand_line(&self, buf: &mut String, fold_row: u32) {
+        let tabs = self.row_tabs(fold_row);
         let mut column = 0u32;
+        let mut tab_index = 0;
         for ch in self.fold_snapshot.fold_line_chars(fold_row) {
+            let width = tabs.char_width(ch, column, &mut tab_index);
             if ch == '\t' {
-                let width = if column >= self.max_expansion_column {
-                    1
-                } else {
-                    self.tab_size - (column % self.tab_size)
-                };
                 for _ in 0..width {
                     buf.push(' ');
                 }
-                column += width;
             } else {
                 buf.push(ch);
-                column += super::display_width(ch);
             }
+            column += width;
         }
     }
 
@@ -247,17 +488,11 @@ impl TabSnapshot {
         start_col: u32,
         end_col: Option<u32>,
     ) {
+        let tabs = self.row_tabs(fold_row);
         let mut column = 0u32;
+        let mut tab_index = 0;
         for ch in self.fold_snapshot.fold_line_chars(fold_row) {
-            let width = if ch == '\t' {
-                if column >= self.max_expansion_column {
-                    1
-                } else {
-                    self.tab_size - (column % self.tab_size)
-                }
-            } else {
-                super::display_width(ch)
-            };
+            let width = tabs.char_width(ch, column, &mut tab_index);
 
             let next_column = column + width;
 
@@ -296,28 +531,29 @@ impl TabSnapshot {
 
     /// Stream [`Chunk`]s covering a fold-offset range with tabs expanded.
     ///
-    /// `start_column` is the display column at `range.start`; pass 0 when
-    /// starting at a row boundary (typical editor use). Tabs encountered in
+    /// `range.start` must be the start of `start_row`. Tabs encountered in
     /// the chunk stream are emitted as separate unstyled chunks tagged with
-    /// [`Chunk::is_tab`], sized to advance the running display column to the
-    /// next multiple of [`TabSnapshot::tab_size`] (clamped to
-    /// [`TabSnapshot::max_expansion_column`]).
+    /// [`Chunk::is_tab`], sized by the row's [`RowTabs`]: to the next multiple
+    /// of [`TabSnapshot::tab_size`] (clamped to
+    /// [`TabSnapshot::max_expansion_column`]), or to the row's elastic widths.
     ///
-    /// Newlines reset the display column to 0. The caller is responsible for
-    /// ensuring the starting column is accurate.
+    /// Newlines reset the display column to 0 and move on to the next row's
+    /// tab widths.
     pub fn chunks<'a>(
         &'a self,
         range: Range<FoldOffset>,
-        start_column: u32,
+        start_row: u32,
         endpoints: Arc<[HighlightEndpoint]>,
     ) -> TabChunks<'a> {
         TabChunks {
+            snapshot: self,
             fold_chunks: self.fold_snapshot.chunks(range, endpoints),
             pending: None,
             pending_offset: 0,
-            display_column: start_column,
-            tab_size: self.tab_size,
-            max_expansion_column: self.max_expansion_column,
+            row: start_row,
+            tabs: self.row_tabs(start_row),
+            tab_index: 0,
+            display_column: 0,
         }
     }
 }
@@ -326,15 +562,18 @@ impl TabSnapshot {
 /// tab characters and emits tab-expansion chunks interleaved with the
 /// preserved-style runs.
 pub struct TabChunks<'a> {
+    snapshot: &'a TabSnapshot,
     fold_chunks: FoldChunks<'a>,
     pending: Option<Chunk<'a>>,
     /// Byte cursor into [`Self::pending`]'s text for a borrowed chunk being
     /// split across successive tabs, so each split emits a subslice rather than
     /// reallocating the remainder. Always `0` for owned (block-row) pending.
     pending_offset: usize,
+    row: u32,
+    tabs: RowTabs<'a>,
+    /// Tabs already emitted on [`Self::row`].
+    tab_index: usize,
     display_column: u32,
-    tab_size: u32,
-    max_expansion_column: u32,
 }
 
 impl<'a> Iterator for TabChunks<'a> {
@@ -373,8 +612,7 @@ impl<'a> TabChunks<'a> {
         let remaining = &text[self.pending_offset..];
         match remaining.find('\t') {
             Some(0) => {
-                let spaces = self.tab_width();
-                self.display_column += spaces;
+                let spaces = self.take_tab();
                 self.pending_offset += 1;
                 if self.pending_offset >= text.len() {
                     self.pending = None;
@@ -391,7 +629,7 @@ impl<'a> TabChunks<'a> {
                 let metadata =
                     clone_chunk_metadata(self.pending.as_ref().expect("borrowed pending"));
                 let prefix = &remaining[..idx];
-                advance_display_column(prefix, &mut self.display_column);
+                self.advance(prefix);
                 self.pending_offset += idx;
                 Some(Chunk {
                     text: Cow::Borrowed(prefix),
@@ -401,7 +639,7 @@ impl<'a> TabChunks<'a> {
             None => {
                 let metadata =
                     clone_chunk_metadata(self.pending.as_ref().expect("borrowed pending"));
-                advance_display_column(remaining, &mut self.display_column);
+                self.advance(remaining);
                 self.pending = None;
                 self.pending_offset = 0;
                 Some(Chunk {
@@ -422,12 +660,11 @@ impl<'a> TabChunks<'a> {
         let text: &str = pending.text.as_ref();
         match text.find('\t') {
             None => {
-                advance_display_column(&pending.text, &mut self.display_column);
+                self.advance(&pending.text);
                 Some(pending)
             },
             Some(0) => {
-                let spaces = self.tab_width();
-                self.display_column += spaces;
+                let spaces = self.take_tab();
                 let rest = text[1..].to_string();
                 let metadata = clone_chunk_metadata(&pending);
                 if !rest.is_empty() {
@@ -456,7 +693,7 @@ impl<'a> TabChunks<'a> {
                     diagnostic_severity: metadata.diagnostic_severity,
                     renderer: metadata.renderer.clone(),
                 });
-                advance_display_column(&prefix, &mut self.display_column);
+                self.advance(&prefix);
                 Some(Chunk {
                     text: Cow::Owned(prefix),
                     ..metadata
@@ -479,21 +716,26 @@ fn clone_chunk_metadata<'a>(chunk: &Chunk<'a>) -> Chunk<'a> {
 }
 
 impl TabChunks<'_> {
-    fn tab_width(&self) -> u32 {
-        if self.display_column >= self.max_expansion_column {
-            1
-        } else {
-            self.tab_size - (self.display_column % self.tab_size)
-        }
+    /// Width of the tab under the cursor, advancing past it.
+    fn take_tab(&mut self) -> u32 {
+        let width = self.tabs.width(self.display_column, self.tab_index);
+        self.tab_index += 1;
+        self.display_column += width;
+        width
     }
-}
 
-fn advance_display_column(text: &str, column: &mut u32) {
-    for ch in text.chars() {
-        if ch == '\n' {
-            *column = 0;
-        } else {
-            *column += super::display_width(ch);
+    /// Advance past tab-free `text`, moving to the next row's tabs at each
+    /// newline.
+    fn advance(&mut self, text: &str) {
+        for ch in text.chars() {
+            if ch == '\n' {
+                self.row += 1;
+                self.tabs = self.snapshot.row_tabs(self.row);
+                self.tab_index = 0;
+                self.display_column = 0;
+            } else {
+                self.display_column += super::display_width(ch);
+            }
         }
     }
 }
@@ -508,42 +750,36 @@ fn tab_spaces_slice(width: u32) -> &'static str {
     &TAB_SPACES[..len]
 }
 
-/// Advance an expanded (tab-expanded, display-width) column past `ch`.
+/// Advance an expanded (tab-expanded, display-width) column past `ch` on a row
+/// with fixed tab stops.
 ///
 /// A tab jumps to the next `tab_size` stop until `max_expansion_column`, past
 /// which it counts as a single column. Any other char adds its display width.
 /// Shared with the render painter so its fast path and the display map's column
-/// math cannot drift.
+/// math cannot drift. Elastic rows go through [`RowTabs`] instead.
 pub(crate) fn advance_column_for_char(
     expanded: &mut u32,
     ch: char,
     tab_size: u32,
     max_expansion_column: u32,
 ) {
-    if ch == '\t' {
-        if *expanded >= max_expansion_column {
-            *expanded += 1;
-        } else {
-            *expanded += tab_size - (*expanded % tab_size);
-        }
-    } else {
-        *expanded += super::display_width(ch);
-    }
+    let tabs = RowTabs {
+        tab_size,
+        max_expansion_column,
+        elastic: None,
+    };
+    *expanded += tabs.char_width(ch, *expanded, &mut 0);
 }
 
-fn expand_column(
-    chars: impl Iterator<Item = char>,
-    fold_column: u32,
-    tab_size: u32,
-    max_expansion_column: u32,
-) -> u32 {
+fn expand_column(chars: impl Iterator<Item = char>, fold_column: u32, tabs: RowTabs<'_>) -> u32 {
     let mut expanded = 0u32;
     let mut byte_idx = 0u32;
+    let mut tab_index = 0;
     for ch in chars {
         if byte_idx >= fold_column {
             break;
         }
-        advance_column_for_char(&mut expanded, ch, tab_size, max_expansion_column);
+        expanded += tabs.char_width(ch, expanded, &mut tab_index);
         byte_idx += ch.len_utf8() as u32;
     }
     expanded
@@ -552,27 +788,18 @@ fn expand_column(
 fn collapse_column(
     chars: impl Iterator<Item = char>,
     tab_column: u32,
-    tab_size: u32,
+    tabs: RowTabs<'_>,
     bias: Bias,
-    max_expansion_column: u32,
 ) -> u32 {
     let mut expanded = 0u32;
     let mut fold_col = 0u32;
     let mut last_char_byte_len = 0u32;
+    let mut tab_index = 0;
     for ch in chars {
         if expanded >= tab_column {
             break;
         }
-        let char_width = if ch == '\t' {
-            if expanded >= max_expansion_column {
-                1
-            } else {
-                tab_size - (expanded % tab_size)
-            }
-        } else {
-            super::display_width(ch)
-        };
-        expanded += char_width;
+        expanded += tabs.char_width(ch, expanded, &mut tab_index);
         last_char_byte_len = ch.len_utf8() as u32;
         fold_col += last_char_byte_len;
     }
@@ -585,41 +812,34 @@ fn collapse_column(
 fn collapse_column_detailed(
     chars: impl Iterator<Item = char>,
     tab_column: u32,
-    tab_size: u32,
+    tabs: RowTabs<'_>,
     bias: Bias,
-    max_expansion_column: u32,
 ) -> (u32, u32, u32) {
     let mut expanded = 0u32;
     let mut fold_col = 0u32;
     let mut last_char_byte_len = 0u32;
     let mut last_char_width = 0u32;
+    let mut tab_index = 0;
+    let mut last_was_tab = false;
     for ch in chars {
         if expanded >= tab_column {
             break;
         }
-        let char_width = if ch == '\t' {
-            if expanded >= max_expansion_column {
-                1
-            } else {
-                tab_size - (expanded % tab_size)
-            }
-        } else {
-            super::display_width(ch)
-        };
+        let char_width = tabs.char_width(ch, expanded, &mut tab_index);
         expanded += char_width;
         last_char_byte_len = ch.len_utf8() as u32;
         last_char_width = char_width;
+        last_was_tab = ch == '\t';
         fold_col += last_char_byte_len;
     }
     if bias == Bias::Left && expanded > tab_column {
         fold_col = fold_col.saturating_sub(last_char_byte_len);
         expanded -= last_char_width;
+        if last_was_tab {
+            tab_index -= 1;
+        }
     }
-    let to_next_stop = if expanded >= max_expansion_column {
-        1
-    } else {
-        tab_size - (expanded % tab_size)
-    };
+    let to_next_stop = tabs.width(expanded, tab_index);
     (fold_col, expanded, to_next_stop)
 }
 
@@ -641,6 +861,10 @@ mod tests {
     use stoat_text::{patch::Patch, Bias};
 
     fn make_snapshot(content: &str) -> super::TabSnapshot {
+        make_snapshot_with(content, false)
+    }
+
+    fn make_snapshot_with(content: &str, elastic: bool) -> super::TabSnapshot {
         let buffer = TextBuffer::with_text(BufferId::new(0), content);
         let shared = Arc::new(RwLock::new(buffer));
         let multi_buffer = MultiBuffer::singleton(BufferId::new(0), shared);
@@ -648,6 +872,7 @@ mod tests {
         let (_, inlay_snapshot) = InlayMap::new(buffer_snapshot);
         let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
         let mut tab_map = TabMap::new(NonZeroU32::new(4).unwrap());
+        tab_map.set_elastic_tabstops(elastic);
         let (snapshot, _) = tab_map.sync(fold_snapshot, Patch::empty());
         snapshot
     }
@@ -865,4 +1090,33 @@ mod tests {
         assert_eq!(tab_chunks.len(), 1);
         assert_eq!(tab_chunks[0].text.as_ref(), "  ");
     }
+
+    #[test]
+    fn elastic_tabs_align_columns_within_a_paragraph() {
+        let snap = make_snapshot_with("a\tb\tc\nlonger\tb\n\nx\ty", true);
+        assert!(snap.elastic_tabstops());
+        assert_eq!(snap.expand_line(0), "a      b   c");
+        assert_eq!(snap.expand_line(1), "longer b");
+        // A line without tabs ends the paragraph, so `x` keeps a plain stop.
+        assert_eq!(snap.expand_line(3), "x   y");
+        assert_eq!(snap.to_tab_point(FoldPoint::new(1, 7)), TabPoint::new(1, 7));
+        assert_eq!(snap.line_len(0), 12);
+    }
+
+    #[test]
+    fn elastic_columns_roundtrip_and_chunk() {
+        use crate::display_map::fold_map::FoldOffset;
+
+        let snap = make_snapshot_with("a\tb\nlonger\tc", true);
+        for col in 0..3u32 {
+            let tab = snap.to_tab_point(FoldPoint::new(0, col));
+            assert_eq!(snap.to_fold_point(tab, Bias::Left), FoldPoint::new(0, col));
+        }
+        let end = snap.fold_snapshot().len();
+        let text: String = snap
+            .chunks(FoldOffset(0)..end, 0, Arc::from(Vec::new()))
+            .map(|c| c.text.into_owned())
+            .collect();
+        assert_eq!(text, "a      b\nlonger c");
+    }
 }
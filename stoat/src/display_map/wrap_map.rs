@@ -1,7 +1,8 @@
 use super::{
     fold_map::FoldOffset,
     highlights::{Chunk, HighlightEndpoint},
-    tab_map::{TabChunks, TabPoint, TabSnapshot},
+    measure::MeasureRef,
+    tab_map::{RowTabs, TabChunks, TabPoint, TabSnapshot},
 };
 use std::{
     borrow::Cow,
@@ -91,8 +92,9 @@ struct Transform {
     summary: TransformSummary,
     wrap_columns: Vec<u32>,
     tab_line_len: u32,
-    /// Display columns each continuation row is indented by, matching the
-    /// line's leading whitespace capped at half the wrap width. Zero on a
+    /// Display columns each continuation row is indented by: the line's
+    /// leading whitespace plus [`WrapRules::continuation_indent`], capped at
+    /// half the wrap width. Zero on a
     /// non-wrapping or single-row transform. Baked into the display columns of
     /// every `sub_row > 0`, so the synthetic margin needs no special casing
     /// downstream.
@@ -162,12 +164,47 @@ impl<'a> Dimension<'a, TransformSummary> for LongestInRange {
 
 const WRAP_SYNC_THRESHOLD: u32 = 100;
 
+/// How [`WrapMap`] chooses the columns a long line breaks at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrapRules {
+    /// Break between words, splitting a token only when it alone overflows a
+    /// row. Wide (CJK) characters allow a break on either side unless
+    /// punctuation forbids it. When false every row fills to the wrap width.
+    pub word_boundaries: bool,
+    /// Columns continuation rows are indented past the line's own leading
+    /// whitespace, which they always keep.
+    pub continuation_indent: u32,
+}
+
+impl Default for WrapRules {
+    fn default() -> Self {
+        Self {
+            word_boundaries: true,
+            continuation_indent: 0,
+        }
+    }
+}
+
+/// Characters that may not open a row, so a break never lands before them.
+const NO_BREAK_BEFORE: &[char] = &[
+    ')', ']', '}', ',', '.', ':', ';', '!', '?', '、', '。', '，', '．', '：', '；', '？', '！',
+    '）', '］', '｝', '」', '』', '〉', '》', '】', '〕', 'ー', '…', '・', 'ぁ', 'ぃ', 'ぅ', 'ぇ',
+    'ぉ', 'っ', 'ゃ', 'ゅ', 'ょ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ッ', 'ャ', 'ュ', 'ョ',
+];
+
+/// Characters that may not close a row, so a break never lands after them.
+const NO_BREAK_AFTER: &[char] = &[
+    '(', '[', '{', '（', '［', '｛', '「', '『', '〈', '《', '【', '〔',
+];
+
 pub struct WrapMap {
     snapshot: WrapSnapshot,
     pending_edits: VecDeque<(TabSnapshot, Patch<u32>)>,
     interpolated_edits: Patch<u32>,
     edits_since_sync: Patch<u32>,
     wrap_width: Option<u32>,
+    rules: WrapRules,
+    measure: MeasureRef,
     background_task: Option<Task<(WrapSnapshot, Patch<u32>)>>,
     executor: Executor,
 }
@@ -177,6 +214,8 @@ pub struct WrapSnapshot {
     tab_snapshot: TabSnapshot,
     transforms: SumTree<Transform>,
     wrap_width: Option<u32>,
+    rules: WrapRules,
+    measure: MeasureRef,
     total_rows: u32,
     longest_row: u32,
     longest_row_chars: u32,
@@ -196,7 +235,9 @@ impl WrapMap {
         wrap_width: Option<u32>,
         executor: Executor,
     ) -> (Self, Arc<WrapSnapshot>) {
-        let snapshot = build_snapshot(tab_snapshot, wrap_width);
+        let rules = WrapRules::default();
+        let measure = MeasureRef::default();
+        let snapshot = build_snapshot(tab_snapshot, wrap_width, rules, &measure);
         let snapshot_arc = Arc::new(snapshot.clone());
         let map = WrapMap {
             snapshot,
@@ -204,6 +245,8 @@ impl WrapMap {
             interpolated_edits: Patch::empty(),
             edits_since_sync: Patch::empty(),
             wrap_width,
+            rules,
+            measure,
             background_task: None,
             executor,
         };
@@ -215,7 +258,10 @@ impl WrapMap {
         tab_snapshot: TabSnapshot,
         tab_edits: &Patch<u32>,
     ) -> (Arc<WrapSnapshot>, Patch<u32>) {
-        let wrap_width_changed = self.wrap_width != self.snapshot.wrap_width;
+        let wrap_width_changed = self.wrap_width != self.snapshot.wrap_width
+            || self.rules != self.snapshot.rules
+            || self.measure != self.snapshot.measure
+            || tab_snapshot.layout_version() != self.snapshot.tab_snapshot.layout_version();
         let new_fold_ver = tab_snapshot.fold_snapshot().version();
         let new_buf_ver = tab_snapshot.fold_snapshot().inlay_snapshot().version();
         let new_inlay_ver = tab_snapshot.fold_snapshot().inlay_snapshot().inlay_version;
@@ -240,7 +286,8 @@ impl WrapMap {
 
         if needs_full_rebuild {
             let old_line_count = self.snapshot.line_count();
-            self.snapshot = build_snapshot(tab_snapshot, self.wrap_width);
+            self.snapshot =
+                build_snapshot(tab_snapshot, self.wrap_width, self.rules, &self.measure);
             let new_line_count = self.snapshot.line_count();
             self.edits_since_sync = self.edits_since_sync.compose([Edit {
                 old: 0..old_line_count,
@@ -309,6 +356,8 @@ impl WrapMap {
                         tab_snapshot,
                         &tab_edits,
                         Some(wrap_width),
+                        self.rules,
+                        &self.measure,
                     );
                     self.snapshot = wrap_edits.0;
                     self.edits_since_sync = self
@@ -319,11 +368,19 @@ impl WrapMap {
 
                 let mut snapshot = self.snapshot.clone();
                 let pending = self.pending_edits.clone();
+                let rules = self.rules;
+                let measure = self.measure.clone();
                 self.background_task = Some(self.executor.spawn(async move {
                     let mut edits = Patch::empty();
                     for (tab_snapshot, tab_edits) in pending {
-                        let (new_snap, wrap_edits) =
-                            sync_incremental(&snapshot, tab_snapshot, &tab_edits, Some(wrap_width));
+                        let (new_snap, wrap_edits) = sync_incremental(
+                            &snapshot,
+                            tab_snapshot,
+                            &tab_edits,
+                            Some(wrap_width),
+                            rules,
+                            &measure,
+                        );
                         snapshot = new_snap;
                         edits = edits.compose(wrap_edits.edits().iter().cloned());
                         yield_now().await;
@@ -382,6 +439,25 @@ impl WrapMap {
     pub fn wrap_width(&self) -> Option<u32> {
         self.wrap_width
     }
+
+    /// Rules the next sync wraps by. A change rewraps every line.
+    pub fn set_rules(&mut self, rules: WrapRules) {
+        self.rules = rules;
+    }
+
+    pub fn rules(&self) -> WrapRules {
+        self.rules
+    }
+
+    /// Glyph advances the next sync fills rows by. A change rewraps every
+    /// line.
+    pub fn set_measure(&mut self, measure: MeasureRef) {
+        self.measure = measure;
+    }
+
+    pub fn measure(&self) -> &MeasureRef {
+        &self.measure
+    }
 }
 
 /// The `(fold, buffer, inlay)` version tuple a tab snapshot carries.
@@ -399,7 +475,12 @@ fn tab_versions(tab: &TabSnapshot) -> (usize, u64, usize) {
     )
 }
 
-fn build_snapshot(tab_snapshot: TabSnapshot, wrap_width: Option<u32>) -> WrapSnapshot {
+fn build_snapshot(
+    tab_snapshot: TabSnapshot,
+    wrap_width: Option<u32>,
+    rules: WrapRules,
+    measure: &MeasureRef,
+) -> WrapSnapshot {
     let tab_line_count = tab_snapshot.line_count();
     let mut transforms = SumTree::new(());
 
@@ -414,8 +495,9 @@ fn build_snapshot(tab_snapshot: TabSnapshot, wrap_width: Option<u32>) -> WrapSna
                     chars,
                     tab_line_len,
                     width,
-                    tab_snapshot.tab_size(),
-                    tab_snapshot.max_expansion_column(),
+                    tab_snapshot.row_tabs(tab_row),
+                    rules,
+                    measure,
                 )
             },
         };
@@ -449,6 +531,8 @@ fn build_snapshot(tab_snapshot: TabSnapshot, wrap_width: Option<u32>) -> WrapSna
         tab_snapshot,
         transforms,
         wrap_width,
+        rules,
+        measure: measure.clone(),
         total_rows,
         longest_row,
         longest_row_chars,
@@ -461,6 +545,8 @@ fn sync_incremental(
     tab_snapshot: TabSnapshot,
     tab_edits: &Patch<u32>,
     wrap_width: Option<u32>,
+    rules: WrapRules,
+    measure: &MeasureRef,
 ) -> (WrapSnapshot, Patch<u32>) {
     let mut new_transforms = SumTree::new(());
     let mut cursor = old.transforms.cursor::<Dimensions<InputRow, OutputRow>>(());
@@ -488,8 +574,9 @@ fn sync_incremental(
                         chars,
                         tab_line_len,
                         width,
-                        tab_snapshot.tab_size(),
-                        tab_snapshot.max_expansion_column(),
+                        tab_snapshot.row_tabs(tab_row),
+                        rules,
+                        measure,
                     )
                 },
             };
@@ -531,6 +618,8 @@ fn sync_incremental(
         tab_snapshot,
         transforms: new_transforms,
         wrap_width,
+        rules,
+        measure: measure.clone(),
         total_rows,
         longest_row,
         longest_row_chars,
@@ -567,36 +656,48 @@ fn compute_transform_longest(wrap_columns: &[u32], tab_line_len: u32, indent: u3
 ///
 /// The returned columns always start with `0`. Each later entry is the tab
 /// column a continuation row begins at. The indent is the line's leading-
-/// whitespace display width capped at `width / 2`, and continuation rows wrap at
-/// `width - indent` so the indent plus text fills the pane. Both are zero when
-/// the line fits or wrapping is off.
+/// whitespace display width plus the rules' continuation indent, capped at
+/// `width / 2`, and continuation rows wrap at `width - indent` so the indent
+/// plus text fills the pane. Both are zero when the line fits or wrapping is
+/// off.
+///
+/// Under word-boundary rules a row ends at the last break opportunity before
+/// the character that overflows it: after whitespace or a hyphen, or on
+/// either side of a wide character unless [`NO_BREAK_BEFORE`] or
+/// [`NO_BREAK_AFTER`] forbids it. Whitespace that overflows hangs past the
+/// row's end rather than opening the next row. A row with no opportunity is
+/// cut at the overflowing character.
+///
+/// A row overflows once `measure`'s advances across it exceed the width, so
+/// under a proportional font a row of narrow glyphs holds more columns than
+/// `width`, and one of wide glyphs fewer.
 fn compute_wrap_columns(
     chars: impl Iterator<Item = char>,
     tab_line_len: u32,
     width: u32,
-    tab_size: u32,
-    max_expansion_column: u32,
+    tabs: RowTabs<'_>,
+    rules: WrapRules,
+    measure: &MeasureRef,
 ) -> (Vec<u32>, u32) {
-    if width == 0 || tab_line_len <= width {
+    if width == 0 || (measure.is_monospace() && tab_line_len <= width) {
         return (vec![0], 0);
     }
 
+    // Breaks land on tab columns, but whether a row overflows is decided by
+    // how far the font advances across it, tracked alongside as `x`.
     let mut breaks = vec![0u32];
+    let mut segment_x = 0.0f32;
     let mut expanded_col = 0u32;
-    let mut last_break_candidate: Option<u32> = None;
+    let mut x = 0.0f32;
+    let mut last_break_candidate: Option<(u32, f32)> = None;
     let mut leading_ws = 0u32;
     let mut in_leading = true;
+    let mut prev: Option<(char, u32)> = None;
+    let mut tab_index = 0;
 
     for ch in chars {
-        let char_width = if ch == '\t' {
-            if expanded_col >= max_expansion_column {
-                1
-            } else {
-                tab_size - (expanded_col % tab_size)
-            }
-        } else {
-            super::display_width(ch)
-        };
+        let char_width = tabs.char_width(ch, expanded_col, &mut tab_index);
+        let advance = measure.advance(prev.map(|(ch, _)| ch), ch, char_width);
 
         if in_leading {
             if ch.is_whitespace() {
@@ -606,42 +707,60 @@ fn compute_wrap_columns(
             }
         }
 
-        if ch == ' ' || ch == '\t' || ch == '-' {
-            last_break_candidate = Some(expanded_col + char_width);
-        } else if char_width >= 2 {
-            // CJK and other wide characters can break at any boundary.
-            last_break_candidate = Some(expanded_col);
+        if rules.word_boundaries
+            && let Some((prev_ch, prev_width)) = prev
+            && breaks_between(prev_ch, prev_width, ch, char_width)
+        {
+            last_break_candidate = Some((expanded_col, x));
         }
 
-        expanded_col += char_width;
-
         // The indent is finalized well before the first break, since a break
         // needs `width` columns and the indent caps at half of it.
-        let indent = leading_ws.min(width / 2);
-        let budget = if breaks.len() == 1 {
-            width
-        } else {
-            width - indent
-        };
-        let segment_start = *breaks.last().expect("breaks starts with [0]");
-        if expanded_col - segment_start >= budget {
-            let break_at = match last_break_candidate {
-                Some(b) if b > segment_start => b,
-                _ => expanded_col,
+        let indent = (leading_ws + rules.continuation_indent).min(width / 2);
+        loop {
+            let segment_start = *breaks.last().expect("breaks starts with [0]");
+            let budget = if breaks.len() == 1 {
+                width
+            } else {
+                width - indent
+            };
+            let overflows = x + advance - segment_x > budget as f32;
+            let hangs = rules.word_boundaries && ch.is_whitespace();
+            if !overflows || hangs || expanded_col == segment_start {
+                break;
+            }
+            let (break_at, break_x) = match last_break_candidate {
+                Some((b, b_x)) if b > segment_start => (b, b_x),
+                _ => (expanded_col, x),
             };
             breaks.push(break_at);
+            segment_x = break_x;
             last_break_candidate = None;
         }
-    }
 
-    if breaks.len() > 1 && *breaks.last().expect("breaks starts with [0]") >= tab_line_len {
-        breaks.pop();
+        expanded_col += char_width;
+        x += advance;
+        prev = Some((ch, char_width));
     }
 
-    let indent = leading_ws.min(width / 2);
+    let indent = (leading_ws + rules.continuation_indent).min(width / 2);
     (breaks, indent)
 }
 
+/// Whether a word-boundary wrap may break between `prev` and `next`, given
+/// their display widths.
+fn breaks_between(prev: char, prev_width: u32, next: char, next_width: u32) -> bool {
+    if next.is_whitespace() {
+        return false;
+    }
+    if prev.is_whitespace() || prev == '-' {
+        return true;
+    }
+    (prev_width >= 2 || next_width >= 2)
+        && !NO_BREAK_BEFORE.contains(&next)
+        && !NO_BREAK_AFTER.contains(&prev)
+}
+
 impl WrapSnapshot {
     /// Cheap approximation: replaces edited regions with 1:1 identity transforms
     /// (no wrapping). Fast but inaccurate -- sets `interpolated = true`.
@@ -711,6 +830,14 @@ impl WrapSnapshot {
         self.wrap_width
     }
 
+    pub fn wrap_rules(&self) -> WrapRules {
+        self.rules
+    }
+
+    pub fn measure(&self) -> &MeasureRef {
+        &self.measure
+    }
+
     pub fn to_tab_point(&self, wrap_point: WrapPoint) -> TabPoint {
         if self.wrap_width.is_none() {
             return TabPoint::new(wrap_point.row(), wrap_point.column());
@@ -920,7 +1047,7 @@ impl WrapSnapshot {
                 (start, end)
             };
             return WrapChunks::Passthrough {
-                tab_chunks: Box::new(self.tab_snapshot.chunks(start..end, 0, endpoints)),
+                tab_chunks: Box::new(self.tab_snapshot.chunks(start..end, rows.start, endpoints)),
             };
         }
 
@@ -1158,7 +1285,7 @@ impl<'a> WrappedChunksInner<'a> {
         let tab_chunks =
             self.snapshot
                 .tab_snapshot
-                .chunks(row_start..row_end, 0, self.endpoints.clone());
+                .chunks(row_start..row_end, tab_row, self.endpoints.clone());
 
         Some(RowChunksState {
             tab_chunks,
@@ -1275,13 +1402,14 @@ fn transform_sub_row_len(transform: &Transform, sub_idx: usize) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use super::{WrapMap, WrapPoint, WrapRowKind};
+    use super::{WrapMap, WrapPoint, WrapRowKind, WrapRules, WrapSnapshot};
     use crate::{
         buffer::{BufferId, TextBuffer},
         display_map::{
             fold_map::FoldMap,
             inlay_map::InlayMap,
-            tab_map::{TabMap, TabPoint},
+            measure::{MeasureRef, TextMeasure},
+            tab_map::{TabMap, TabPoint, TabSnapshot},
         },
         multi_buffer::MultiBuffer,
     };
@@ -1293,7 +1421,7 @@ mod tests {
         Executor::new(Arc::new(TestScheduler::new()))
     }
 
-    fn make_snapshot(content: &str, wrap_width: Option<u32>) -> Arc<super::WrapSnapshot> {
+    fn make_tab_snapshot(content: &str) -> TabSnapshot {
         let buffer = TextBuffer::with_text(BufferId::new(0), content);
         let shared = Arc::new(RwLock::new(buffer));
         let multi_buffer = MultiBuffer::singleton(BufferId::new(0), shared);
@@ -1302,10 +1430,24 @@ mod tests {
         let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
         let mut tab_map = TabMap::new(std::num::NonZeroU32::new(4).unwrap());
         let (tab_snapshot, _) = tab_map.sync(fold_snapshot, Patch::empty());
-        let (_, wrap_snapshot) = WrapMap::new(tab_snapshot, wrap_width, test_executor());
+        tab_snapshot
+    }
+
+    fn make_snapshot(content: &str, wrap_width: Option<u32>) -> Arc<super::WrapSnapshot> {
+        let (_, wrap_snapshot) =
+            WrapMap::new(make_tab_snapshot(content), wrap_width, test_executor());
         wrap_snapshot
     }
 
+    fn make_snapshot_with_rules(content: &str, width: u32, rules: WrapRules) -> WrapSnapshot {
+        super::build_snapshot(
+            make_tab_snapshot(content),
+            Some(width),
+            rules,
+            &MeasureRef::default(),
+        )
+    }
+
     #[test]
     fn no_wrap_passthrough() {
         let snap = make_snapshot("hello\nworld", None);
@@ -1444,6 +1586,109 @@ mod tests {
         assert_eq!(snap.line_len(1), 7);
     }
 
+    #[test]
+    fn word_wrap_keeps_a_row_that_fits() {
+        // "aaaa bbbb" fills the width exactly, so only the overflowing "cc"
+        // moves down and the space between them hangs off the first row.
+        let snap = make_snapshot("aaaa bbbb cc", Some(9));
+        assert_eq!(snap.line_count(), 2);
+        assert_eq!(
+            snap.to_wrap_point(TabPoint::new(0, 10)),
+            WrapPoint::new(1, 0)
+        );
+    }
+
+    #[test]
+    fn anywhere_rules_fill_every_row() {
+        let rules = WrapRules {
+            word_boundaries: false,
+            ..WrapRules::default()
+        };
+        let snap = make_snapshot_with_rules("hello world foo", 8, rules);
+        assert_eq!(snap.line_count(), 2);
+        assert_eq!(snap.line_len(0), 8);
+        assert_eq!(snap.line_len(1), 7);
+    }
+
+    #[test]
+    fn continuation_indent_adds_to_leading_whitespace() {
+        let rules = WrapRules {
+            continuation_indent: 2,
+            ..WrapRules::default()
+        };
+        let snap = make_snapshot_with_rules("  alpha beta gamma delta", 12, rules);
+        assert!(snap.line_count() > 1);
+        assert_eq!(snap.soft_wrap_indent(1), 4);
+        assert!(snap.display_line(1).starts_with("    "));
+
+        let capped = WrapRules {
+            continuation_indent: 20,
+            ..WrapRules::default()
+        };
+        let snap = make_snapshot_with_rules("  alpha beta gamma delta", 12, capped);
+        assert_eq!(snap.soft_wrap_indent(1), 6, "capped at half the width");
+    }
+
+    /// Narrow `i`, wide `m`, and an `->` ligature drawn in one cell.
+    struct Proportional;
+
+    impl TextMeasure for Proportional {
+        fn advance(&self, prev: Option<char>, ch: char) -> f32 {
+            match (prev, ch) {
+                (Some('-'), '>') => 0.0,
+                (_, 'i') => 0.5,
+                (_, 'm') => 1.5,
+                _ => 1.0,
+            }
+        }
+    }
+
+    #[test]
+    fn measured_advances_decide_where_rows_fill() {
+        let measured = |content: &str, width: u32| {
+            super::build_snapshot(
+                make_tab_snapshot(content),
+                Some(width),
+                WrapRules::default(),
+                &MeasureRef::new(Proportional),
+            )
+        };
+
+        // Twelve narrow glyphs and a space advance 7 cells, so the 13-column
+        // word fits a row of 8 and only the wide pair moves down.
+        let snap = measured("iiiiiiiiiiii mm", 8);
+        assert_eq!(snap.line_count(), 2);
+        assert_eq!(snap.display_line(1), "mm");
+        assert_eq!(
+            make_snapshot("iiiiiiiiiiii mm", Some(8)).display_line(1),
+            "iiii mm"
+        );
+
+        // Each ligature draws in one cell, so ten columns fit in seven.
+        assert_eq!(measured("a->b->c->d", 7).line_count(), 1);
+        assert_eq!(make_snapshot("a->b->c->d", Some(7)).line_count(), 2);
+    }
+
+    #[test]
+    fn cjk_breaks_between_characters_but_not_before_closing_punctuation() {
+        // Each ideograph is two columns wide. The full stop cannot open a row,
+        // so the break moves back one character to keep "字。" together.
+        let snap = make_snapshot("漢字漢字。漢字", Some(8));
+        assert_eq!(snap.line_count(), 2);
+        assert_eq!(
+            snap.to_wrap_point(TabPoint::new(0, 6)),
+            WrapPoint::new(1, 0)
+        );
+        assert_eq!(snap.display_line(0), "漢字漢");
+
+        // A Latin word after CJK text may start the next row.
+        let snap = make_snapshot("漢字漢字abc", Some(9));
+        assert_eq!(
+            snap.to_wrap_point(TabPoint::new(0, 8)),
+            WrapPoint::new(1, 0)
+        );
+    }
+
     #[test]
     fn soft_wrap_indent_primary() {
         let snap = make_snapshot("    hello world foo", Some(8));
@@ -1596,7 +1841,12 @@ mod tests {
         let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
         let mut tab_map = TabMap::new(std::num::NonZeroU32::new(4).unwrap());
         let (tab_snapshot, _) = tab_map.sync(fold_snapshot, Patch::empty());
-        let full = super::build_snapshot(tab_snapshot, Some(5));
+        let full = super::build_snapshot(
+            tab_snapshot,
+            Some(5),
+            WrapRules::default(),
+            &MeasureRef::default(),
+        );
 
         assert_eq!(incremental.line_count(), full.line_count());
         assert_eq!(incremental.longest_row, full.longest_row);
@@ -1628,7 +1878,12 @@ mod tests {
         let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
         let mut tab_map = TabMap::new(std::num::NonZeroU32::new(4).unwrap());
         let (tab_snapshot, _) = tab_map.sync(fold_snapshot, Patch::empty());
-        let full = super::build_snapshot(tab_snapshot, Some(5));
+        let full = super::build_snapshot(
+            tab_snapshot,
+            Some(5),
+            WrapRules::default(),
+            &MeasureRef::default(),
+        );
 
         assert_eq!(result.line_count(), full.line_count());
         for row in 0..full.line_count() {
@@ -1669,7 +1924,12 @@ mod tests {
         let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
         let mut tab_map = TabMap::new(std::num::NonZeroU32::new(4).unwrap());
         let (tab_snapshot, _) = tab_map.sync(fold_snapshot, Patch::empty());
-        let full = super::build_snapshot(tab_snapshot, Some(6));
+        let full = super::build_snapshot(
+            tab_snapshot,
+            Some(6),
+            WrapRules::default(),
+            &MeasureRef::default(),
+        );
 
         assert_eq!(incremental.line_count(), full.line_count());
         for row in 0..full.line_count() {
@@ -1691,7 +1951,12 @@ mod tests {
         let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
         let mut tab_map = TabMap::new(std::num::NonZeroU32::new(4).unwrap());
         let (tab_snapshot, _) = tab_map.sync(fold_snapshot, Patch::empty());
-        let full = super::build_snapshot(tab_snapshot, Some(new_width));
+        let full = super::build_snapshot(
+            tab_snapshot,
+            Some(new_width),
+            WrapRules::default(),
+            &MeasureRef::default(),
+        );
 
         assert_eq!(incremental.line_count(), full.line_count());
         assert_eq!(incremental.longest_row, full.longest_row);
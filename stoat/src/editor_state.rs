@@ -141,6 +141,15 @@ pub(crate) struct EditorState {
     /// display map. Kept so a refresh can remove the prior hints before adding
     /// the new set. Transient render state, not persisted.
     pub(crate) hint_inlay_ids: Vec<crate::display_map::InlayId>,
+    /// Ids of the color-swatch inlays spliced into this editor's display map
+    /// and the syntax version they were computed at, so
+    /// [`crate::color_swatch::refresh`] only rescans after a reparse.
+    /// Transient render state, not persisted.
+    pub(crate) swatch_inlays: (Option<u64>, Vec<crate::display_map::InlayId>),
+    /// Buffer version, cursor offset and scroll row the symbol-occurrence
+    /// highlights were computed at, so [`crate::occurrence_highlight::refresh`]
+    /// only recomputes when one moves. Transient render state, not persisted.
+    pub(crate) occurrence_key: Option<(u64, usize, u32)>,
     /// Cached diagnostic gutter severity map, keyed by the diagnostic-set
     /// version. Transient render state, not persisted.
     pub(crate) gutter_severity_cache: Option<crate::render::editor::GutterSeverityCache>,
@@ -149,6 +158,9 @@ pub(crate) struct EditorState {
     /// an unchanged repaint reuse the collections instead of rebuilding them
     /// every frame. Transient render state, not persisted.
     pub(crate) gutter_geometry_cache: Option<crate::render::editor::GutterGeometryCache>,
+    /// The text layer's cells from the last paint, reused for rows no edit or
+    /// scroll touched since. Transient render state, not persisted.
+    pub(crate) text_layer: Option<crate::render::editor::TextLayerCache>,
     /// Diagnostic spans resolved to byte offsets, keyed by the diagnostic-set
     /// and buffer versions, so the per-frame render paths reuse one resolution.
     /// Transient render state, not persisted.
@@ -216,8 +228,11 @@ impl EditorState {
             search_match_cache: None,
             highlight_endpoint_cache: None,
             hint_inlay_ids: Vec::new(),
+            swatch_inlays: (None, Vec::new()),
+            occurrence_key: None,
             gutter_severity_cache: None,
             gutter_geometry_cache: None,
+            text_layer: None,
             diagnostic_span_cache: None,
             gutter_width: 0,
             minimap_rect: None,
@@ -253,8 +268,11 @@ impl EditorState {
             search_match_cache: None,
             highlight_endpoint_cache: None,
             hint_inlay_ids: Vec::new(),
+            swatch_inlays: (None, Vec::new()),
+            occurrence_key: None,
             gutter_severity_cache: None,
             gutter_geometry_cache: None,
+            text_layer: None,
             diagnostic_span_cache: None,
             gutter_width: 0,
             minimap_rect: None,
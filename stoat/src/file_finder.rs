@@ -7,11 +7,15 @@ use crate::{
 };
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap, HashSet},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 use stoat_scheduler::{Executor, Task};
-use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::{
+    mpsc::UnboundedReceiver,
+    oneshot::{self, error::TryRecvError},
+};
 
 /// Upper bound on the paths a directory-browse walk collects. A bare `/` walk
 /// could traverse the whole filesystem, so draining stops here and the walk is
@@ -95,6 +99,41 @@ pub enum OpenIntent {
     VSplit,
 }
 
+/// A file's working-tree state, shown as a badge beside its finder row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GitFileStatus {
+    /// A tracked file changed in the working tree or the index.
+    Modified,
+    /// A file staged with no blob at HEAD.
+    Added,
+    /// A file git does not track yet.
+    Untracked,
+}
+
+impl GitFileStatus {
+    /// The badge letter, matching `git status --short`.
+    pub(crate) fn badge(self) -> char {
+        match self {
+            GitFileStatus::Modified => 'M',
+            GitFileStatus::Added => 'A',
+            GitFileStatus::Untracked => '?',
+        }
+    }
+
+    /// The theme scope the badge is colored with.
+    pub(crate) fn scope(self) -> &'static str {
+        use crate::theme::scope as s;
+        match self {
+            GitFileStatus::Modified => s::DIFF_MODIFIED,
+            GitFileStatus::Added => s::DIFF_ADDED,
+            GitFileStatus::Untracked => s::VCS_UNTRACKED,
+        }
+    }
+}
+
+/// Each changed file's [`GitFileStatus`], keyed by absolute path.
+pub(crate) type GitStatusMap = HashMap<PathBuf, GitFileStatus>;
+
 /// The finder's directory-browse mode, live while the query starts with `/`
 /// or `~/`.
 ///
@@ -119,6 +158,9 @@ pub struct FileFinder {
     /// Absolute paths of currently-open buffers. Captured once at open time;
     /// not re-queried on scope toggle.
     pub(crate) buffer_paths: Vec<PathBuf>,
+    /// How many of [`Self::buffer_paths`], from the front, are pinned. They
+    /// list ahead of the rest in [`FinderScope::Buffers`].
+    pub(crate) pinned_buffers: usize,
     /// The shared walk / fuzzy-list / preview core. Its `all_paths` is the
     /// [`FinderScope::All`] base; Modified/Buffers feed their own vecs through
     /// [`PathPicker::refilter_with_base`]. A scope toggle
@@ -134,6 +176,12 @@ pub struct FileFinder {
     /// keyed by scope name. Rebuilt when the walk grows or the scope changes,
     /// so a stable Named query does not re-run the globset over `all_paths`.
     pub(crate) named_cache: Option<(String, Vec<PathBuf>)>,
+    /// Each changed file's status, badged beside its row. Seeded from the
+    /// workspace's cached map at open and replaced when
+    /// [`Self::git_status_refresh`] lands.
+    pub(crate) git_status: Arc<GitStatusMap>,
+    /// The status query spawned at open, until its result arrives.
+    pub(crate) git_status_refresh: Option<(oneshot::Receiver<GitStatusMap>, Task<()>)>,
 }
 
 impl FileFinder {
@@ -148,6 +196,7 @@ impl FileFinder {
         walk_task: Task<()>,
         modified_paths: Vec<PathBuf>,
         buffer_paths: Vec<PathBuf>,
+        pinned_buffers: usize,
         finder_scopes: &BTreeMap<String, Vec<String>>,
     ) -> Self {
         let input = InputView::create(
@@ -166,10 +215,13 @@ impl FileFinder {
             scope: initial_scope,
             modified_paths,
             buffer_paths,
+            pinned_buffers,
             core,
             browse: None,
             named_scopes: compile_named_scopes(finder_scopes),
             named_cache: None,
+            git_status: Arc::default(),
+            git_status_refresh: None,
         };
         // Uniformly seed the initial (empty-query) list for whatever scope
         // opened, including a named scope's glob filter.
@@ -256,6 +308,7 @@ impl FileFinder {
     /// sync hook. Drains any pending walk result first so freshly arrived
     /// paths participate in the same render tick.
     pub(crate) fn refilter_from_input(&mut self, ws: &Workspace) {
+        self.pump_git_status();
         if let Some(browse) = &mut self.browse {
             browse.picker.pump_walk();
             if browse.picker.all_paths.len() >= BROWSE_PATH_CAP {
@@ -267,6 +320,10 @@ impl FileFinder {
         }
         let pumped = self.core.pump_walk();
         let text = self.input.text(ws);
+        self.core.picklist.leading = match self.scope {
+            FinderScope::Buffers => self.pinned_buffers,
+            _ => 0,
+        };
         match self.scope.clone() {
             FinderScope::All | FinderScope::AllWorkspaces => self.core.refilter(&text),
             FinderScope::Modified => self.core.refilter_with_base(&text, &self.modified_paths),
@@ -334,6 +391,22 @@ impl FileFinder {
     /// so each file finder lifetime returns the registry to its
     /// pre-open size; without this the preview entry would accumulate
     /// across opens.
+    /// Adopt the status refresh once it lands, leaving the seeded map in
+    /// place if the query was dropped.
+    fn pump_git_status(&mut self) {
+        let Some((rx, _)) = self.git_status_refresh.as_mut() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(status) => {
+                self.git_status = Arc::new(status);
+                self.git_status_refresh = None;
+            },
+            Err(TryRecvError::Empty) => {},
+            Err(TryRecvError::Closed) => self.git_status_refresh = None,
+        }
+    }
+
     pub(crate) fn dispose(&self, ws: &mut Workspace) {
         self.input.dispose(ws);
         self.core.dispose(ws);
@@ -426,6 +499,40 @@ pub(crate) fn query_modified(git_host: &dyn GitHost, git_root: &Path) -> Vec<Pat
     paths
 }
 
+/// Query git for each changed file's [`GitFileStatus`], keyed by absolute
+/// path. A staged file HEAD has no content for is [`GitFileStatus::Added`].
+/// Empty when no repo.
+pub(crate) fn query_git_status(git_host: &dyn GitHost, git_root: &Path) -> GitStatusMap {
+    let Some(repo) = git_host.discover(git_root) else {
+        return GitStatusMap::new();
+    };
+    let changed = repo.changed_files();
+    let staged: Vec<&Path> = changed
+        .iter()
+        .filter(|file| file.staged)
+        .map(|file| file.path.as_path())
+        .collect();
+    let added: HashSet<&Path> = staged
+        .iter()
+        .zip(repo.head_contents(&staged))
+        .filter(|(_, head)| head.is_none())
+        .map(|(path, _)| *path)
+        .collect();
+    changed
+        .iter()
+        .map(|file| {
+            let status = if file.untracked {
+                GitFileStatus::Untracked
+            } else if added.contains(file.path.as_path()) {
+                GitFileStatus::Added
+            } else {
+                GitFileStatus::Modified
+            };
+            (file.path.clone(), status)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -602,6 +709,27 @@ mod tests {
         assert_eq!(h.snapshot().mode, "normal");
     }
 
+    #[test]
+    fn reopen_restores_query_dismissed_with_escape_or_ctrl_c() {
+        let mut h = crate::Stoat::test();
+        seed_finder_workspace(&mut h, &[("a.rs", ""), ("b.rs", "")]);
+        let query = |h: &crate::test_harness::TestHarness| {
+            let finder = h.stoat.file_finder.as_ref().expect("finder open");
+            finder.input.text(h.stoat.active_workspace())
+        };
+
+        h.type_keys("space p");
+        h.type_text("b.r");
+        h.type_keys("escape");
+        h.type_keys("space p");
+        assert_eq!(query(&h), "b.r", "escape keeps the query as a draft");
+
+        h.type_text("s");
+        h.type_keys("Ctrl-c");
+        h.type_keys("space p");
+        assert_eq!(query(&h), "b.rs", "ctrl-c keeps the edited query");
+    }
+
     #[test]
     fn second_open_is_noop() {
         let mut h = crate::Stoat::test();
@@ -984,6 +1112,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn finder_lists_the_indexed_worktree_files_without_walking() {
+        let mut h = crate::Stoat::test();
+        let root = PathBuf::from("/ws-indexed");
+        h.fake_fs().insert_files([
+            (root.join("indexed.rs"), b"a".as_slice()),
+            (root.join("unindexed.rs"), b"b".as_slice()),
+        ]);
+        h.stoat.active_workspace_mut().git_root = root;
+        h.stoat.active_workspace_mut().worktree_files = Some(["indexed.rs".to_string()].into());
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::OpenFileFinder);
+        h.settle();
+
+        assert_eq!(
+            finder_rows(&h),
+            vec!["indexed.rs".to_string()],
+            "a known file list is served as-is instead of walking the root"
+        );
+    }
+
+    #[test]
+    fn finder_rows_carry_git_status_badges() {
+        let mut h = crate::Stoat::test();
+        let root = seed_finder_workspace(
+            &mut h,
+            &[
+                ("clean.rs", ""),
+                ("edited.rs", ""),
+                ("new.rs", ""),
+                ("scratch.rs", ""),
+            ],
+        );
+        {
+            let mut builder = h.fake_git().add_repo(&root);
+            builder.head_file("clean.rs", "");
+            builder.modified("edited.rs", "old", "new");
+            builder.staged_file("new.rs", "");
+            builder.untracked("scratch.rs");
+        }
+
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::OpenFileFinder);
+        h.settle();
+        let _ = h.snapshot();
+        let text = h.rendered_text();
+        let badge = |name: &str| {
+            let line = text.lines().find(|line| line.contains(name)).unwrap();
+            let rest = &line[line.find(name).unwrap() + name.len()..];
+            rest.trim_start().chars().next()
+        };
+
+        assert_eq!(badge("edited.rs"), Some('M'));
+        assert_eq!(badge("new.rs"), Some('A'));
+        assert_eq!(badge("scratch.rs"), Some('?'));
+        assert!(
+            !matches!(badge("clean.rs"), Some('M' | 'A' | '?')),
+            "a clean file carries no badge"
+        );
+    }
+
     #[test]
     fn workspace_leader_binding_opens_the_cross_workspace_finder() {
         let mut h = crate::Stoat::test();
@@ -11,10 +11,17 @@
 //! shas, and lets a `--fixture <name>` run reproduce the same working-tree
 //! state every time.
 //!
+//! Besides the named fixtures, a [`Scenario`] describes a repository as a RON
+//! list of steps and replays it through the same deterministic builder.
+//! [`assert_status`] and [`diff_snapshot`] check the result declaratively.
+//!
 //! Gated behind the non-default `fixture` feature so production builds carry no
 //! test-scaffolding code.
 
-use git2::{build::CheckoutBuilder, Commit, Repository, RepositoryInitOptions, Signature, Time};
+use git2::{
+    build::CheckoutBuilder, BranchType, Commit, ObjectType, Repository, RepositoryInitOptions,
+    ResetType, Signature, Time,
+};
 use snafu::{ResultExt, Snafu};
 use std::{
     io,
@@ -22,8 +29,12 @@ use std::{
 };
 
 pub mod harness;
+pub mod scenario;
+pub mod status;
 
 pub use harness::LiveHarness;
+pub use scenario::{Scenario, Step};
+pub use status::{assert_status, diff_snapshot, porcelain_status};
 
 /// Unix epoch seconds for the first commit's author/committer clock. Each
 /// subsequent commit advances by one second, keeping the timeline monotonic
@@ -325,6 +336,20 @@ fn emphasize(text: &str) -> String {
 
 const RUST_DIFF_GITIGNORE: &str = "/target\n";
 
+const NESTED_README: &str = "superproject with a submodule and a nested repo\n";
+
+const NESTED_LIB_HEAD: &str = "library at its pinned commit\n";
+
+const NESTED_LIB_WORK: &str = "library edited inside the submodule\n";
+
+const NESTED_NOTES: &str = "notes in an unregistered nested repo\n";
+
+const UPSTREAM_BASE: &str = "shared history\n";
+
+const UPSTREAM_REMOTE: &str = "pushed, then dropped locally\n";
+
+const UPSTREAM_LOCAL: &str = "committed locally, never pushed\n";
+
 /// Failure materializing a fixture repository.
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
@@ -350,6 +375,20 @@ pub enum FixtureError {
         #[snafu(implicit)]
         location: snafu::Location,
     },
+
+    #[snafu(display("invalid fixture scenario: {source}"))]
+    ParseScenario {
+        source: ron::error::SpannedError,
+        #[snafu(implicit)]
+        location: snafu::Location,
+    },
+
+    #[snafu(display("merging {branch} conflicts"))]
+    MergeConflict {
+        branch: String,
+        #[snafu(implicit)]
+        location: snafu::Location,
+    },
 }
 
 /// Build the named fixture as a git repository rooted at `dest`.
@@ -387,6 +426,13 @@ pub enum FixtureError {
 ///   change to `src/util.rs` and an unstaged change to `src/main.rs`, with every layer (HEAD,
 ///   index, worktree) compiling so `cargo build` stays green and rust-analyzer runs against a dirty
 ///   tree.
+/// - `nested-repos`: a superproject holding a committed submodule at `vendor/lib`, whose own
+///   working tree carries an unstaged edit, and an unregistered nested repository at `scratch/`
+///   with a commit of its own. Status reports each as one entry for the directory and never lists
+///   the files inside, which is what worktree and status consumers must skip or handle.
+/// - `upstream`: `main` tracking `origin/main` in a local bare remote, diverged one commit each way
+///   -- a pushed commit was dropped locally and a new one committed -- with an annotated `v0.1` and
+///   a lightweight `base` tag on the shared root, for ahead/behind and fetch/push paths offline.
 ///
 /// Fails with [`FixtureError::UnknownFixture`] for an unrecognized `name`, or
 /// [`FixtureError::Git`] / [`FixtureError::Io`] if the repository cannot be
@@ -401,6 +447,8 @@ pub fn materialize(name: &str, dest: &Path) -> Result<(), FixtureError> {
         "rebase" => materialize_rebase(dest),
         "rust-lsp" => materialize_rust_lsp(dest),
         "rust-diff" => materialize_rust_diff(dest),
+        "nested-repos" => materialize_nested_repos(dest),
+        "upstream" => materialize_upstream(dest),
         _ => UnknownFixtureSnafu {
             name: name.to_string(),
         }
@@ -525,6 +573,34 @@ fn materialize_rust_diff(dest: &Path) -> Result<(), FixtureError> {
     Ok(())
 }
 
+fn materialize_nested_repos(dest: &Path) -> Result<(), FixtureError> {
+    let mut repo = FixtureRepo::init(dest)?;
+    repo.commit("initial commit", &[("README.md", NESTED_README)])?;
+    repo.submodule("vendor/lib", "add lib submodule", |lib| {
+        lib.commit("initial lib", &[("lib.txt", NESTED_LIB_HEAD)])?;
+        Ok(())
+    })?;
+    repo.unstaged_file("vendor/lib/lib.txt", NESTED_LIB_WORK)?;
+    repo.nested_repo("scratch", |scratch| {
+        scratch.commit("initial notes", &[("notes.txt", NESTED_NOTES)])?;
+        Ok(())
+    })?;
+    Ok(())
+}
+
+fn materialize_upstream(dest: &Path) -> Result<(), FixtureError> {
+    let mut repo = FixtureRepo::init(dest)?;
+    repo.commit("shared base", &[("base.txt", UPSTREAM_BASE)])?;
+    repo.tag("v0.1", Some("first release"))?;
+    repo.tag("base", None)?;
+    repo.remote("origin")?;
+    repo.commit("remote only", &[("remote.txt", UPSTREAM_REMOTE)])?;
+    repo.push("origin")?;
+    repo.reset_hard("HEAD~1")?;
+    repo.commit("local only", &[("local.txt", UPSTREAM_LOCAL)])?;
+    Ok(())
+}
+
 /// Builder over a real git2 repository, modeled on the `TestRepo` helper used
 /// by the integration tests but authoring every commit with a deterministic
 /// signature so SHAs reproduce. Method vocabulary mirrors the in-memory
@@ -558,9 +634,7 @@ impl FixtureRepo {
             self.stage(name)?;
         }
 
-        let time = Time::new(FIXTURE_EPOCH + self.commit_count, 0);
-        self.commit_count += 1;
-        let sig = Signature::new(FIXTURE_AUTHOR, FIXTURE_EMAIL, &time).context(GitSnafu)?;
+        let sig = self.next_signature()?;
 
         // Scope the tree and parent borrows of `self.repo` so they drop before
         // the `&mut self` return. `git2::Tree` holds an immutable borrow of the
@@ -589,6 +663,183 @@ impl FixtureRepo {
         Ok(self)
     }
 
+    /// The pinned signature for the next commit or annotated tag, one second
+    /// after the previous one.
+    fn next_signature(&mut self) -> Result<Signature<'static>, FixtureError> {
+        let time = Time::new(FIXTURE_EPOCH + self.commit_count, 0);
+        self.commit_count += 1;
+        Signature::new(FIXTURE_AUTHOR, FIXTURE_EMAIL, &time).context(GitSnafu)
+    }
+
+    /// Merge branch `name` into HEAD with a two-parent commit and reset the
+    /// index and working tree to it. Fails with
+    /// [`FixtureError::MergeConflict`] when the trees conflict, since a
+    /// fixture never leaves a merge half-done.
+    fn merge(&mut self, name: &str, message: &str) -> Result<&mut Self, FixtureError> {
+        let sig = self.next_signature()?;
+        {
+            let ours = self
+                .repo
+                .head()
+                .context(GitSnafu)?
+                .peel_to_commit()
+                .context(GitSnafu)?;
+            let theirs = self
+                .repo
+                .revparse_single(&format!("refs/heads/{name}"))
+                .context(GitSnafu)?
+                .peel_to_commit()
+                .context(GitSnafu)?;
+            let mut index = self
+                .repo
+                .merge_commits(&ours, &theirs, None)
+                .context(GitSnafu)?;
+            if index.has_conflicts() {
+                return MergeConflictSnafu { branch: name }.fail();
+            }
+            let tree_id = index.write_tree_to(&self.repo).context(GitSnafu)?;
+            let tree = self.repo.find_tree(tree_id).context(GitSnafu)?;
+            self.repo
+                .commit(Some("HEAD"), &sig, &sig, message, &tree, &[&ours, &theirs])
+                .context(GitSnafu)?;
+            self.repo
+                .checkout_head(Some(CheckoutBuilder::new().force()))
+                .context(GitSnafu)?;
+        }
+        Ok(self)
+    }
+
+    /// Tag the HEAD commit as `name`. With a `message` the tag is annotated
+    /// and signed with the pinned signature, otherwise it is lightweight.
+    fn tag(&mut self, name: &str, message: Option<&str>) -> Result<&mut Self, FixtureError> {
+        let sig = message.map(|_| self.next_signature()).transpose()?;
+        {
+            let head = self
+                .repo
+                .head()
+                .context(GitSnafu)?
+                .peel(ObjectType::Commit)
+                .context(GitSnafu)?;
+            match (message, &sig) {
+                (Some(message), Some(sig)) => self.repo.tag(name, &head, sig, message, false),
+                _ => self.repo.tag_lightweight(name, &head, false),
+            }
+            .context(GitSnafu)?;
+        }
+        Ok(self)
+    }
+
+    /// Initialize a separate repository at `name` inside the working tree and
+    /// fill it with `build`. The superproject does not register it, so it
+    /// shows as an untracked directory.
+    fn nested_repo(
+        &mut self,
+        name: &str,
+        build: impl FnOnce(&mut FixtureRepo) -> Result<(), FixtureError>,
+    ) -> Result<&mut Self, FixtureError> {
+        let path = self.workdir().join(name);
+        std::fs::create_dir_all(&path).context(IoSnafu { path: path.clone() })?;
+        build(&mut FixtureRepo::init(&path)?)?;
+        Ok(self)
+    }
+
+    /// Build an upstream repository with `build`, then add it as a submodule
+    /// checked out at `name` and commit the registration with `message`.
+    ///
+    /// The upstream lives under `.git/fixture-sources/` and `.gitmodules`
+    /// records it by a workdir-relative URL, so the committed tree, and with
+    /// it every SHA, does not depend on where the fixture was materialized.
+    fn submodule(
+        &mut self,
+        name: &str,
+        message: &str,
+        build: impl FnOnce(&mut FixtureRepo) -> Result<(), FixtureError>,
+    ) -> Result<&mut Self, FixtureError> {
+        let source = self.repo.path().join("fixture-sources").join(name);
+        std::fs::create_dir_all(&source).context(IoSnafu {
+            path: source.clone(),
+        })?;
+        build(&mut FixtureRepo::init(&source)?)?;
+
+        let url = format!("./.git/fixture-sources/{name}");
+        {
+            let mut sub = self
+                .repo
+                .submodule(&url, Path::new(name), true)
+                .context(GitSnafu)?;
+            sub.clone(None).context(GitSnafu)?;
+            sub.add_finalize().context(GitSnafu)?;
+        }
+        self.commit(message, &[])
+    }
+
+    /// Create a bare repository under `.git/fixture-remotes/` and register it
+    /// as remote `name`, then push the current branch and set it to track
+    /// `<name>/<branch>`.
+    ///
+    /// The remote URL lands only in `.git/config`, never in a commit, so SHAs
+    /// stay independent of where the fixture was materialized.
+    fn remote(&mut self, name: &str) -> Result<&mut Self, FixtureError> {
+        let path = self
+            .repo
+            .path()
+            .join("fixture-remotes")
+            .join(format!("{name}.git"));
+        std::fs::create_dir_all(&path).context(IoSnafu { path: path.clone() })?;
+        Repository::init_bare(&path).context(GitSnafu)?;
+        let url = path.to_str().expect("fixture paths are utf-8");
+        self.repo.remote(name, url).context(GitSnafu)?;
+        self.push(name)?;
+
+        let branch = self.current_branch()?;
+        self.repo
+            .find_branch(&branch, BranchType::Local)
+            .context(GitSnafu)?
+            .set_upstream(Some(&format!("{name}/{branch}")))
+            .context(GitSnafu)?;
+        Ok(self)
+    }
+
+    /// Push the current branch to remote `name` and move its remote-tracking
+    /// ref to match, as `git push` does.
+    fn push(&mut self, name: &str) -> Result<&mut Self, FixtureError> {
+        let branch = self.current_branch()?;
+        {
+            let mut remote = self.repo.find_remote(name).context(GitSnafu)?;
+            let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+            remote.push(&[refspec.as_str()], None).context(GitSnafu)?;
+            let head = self.repo.head().context(GitSnafu)?;
+            let target = head.target().expect("HEAD resolves to a commit");
+            self.repo
+                .reference(
+                    &format!("refs/remotes/{name}/{branch}"),
+                    target,
+                    true,
+                    "fixture push",
+                )
+                .context(GitSnafu)?;
+        }
+        Ok(self)
+    }
+
+    /// Move the current branch, index, and working tree to `rev`, as
+    /// `git reset --hard`. Rewinding past pushed commits leaves the branch
+    /// behind its upstream.
+    fn reset_hard(&mut self, rev: &str) -> Result<&mut Self, FixtureError> {
+        {
+            let target = self.repo.revparse_single(rev).context(GitSnafu)?;
+            self.repo
+                .reset(&target, ResetType::Hard, None)
+                .context(GitSnafu)?;
+        }
+        Ok(self)
+    }
+
+    fn current_branch(&self) -> Result<String, FixtureError> {
+        let head = self.repo.head().context(GitSnafu)?;
+        Ok(head.shorthand().unwrap_or("main").to_string())
+    }
+
     /// Write `content` to `name` and stage it, leaving a staged modification
     /// against HEAD.
     fn staged_file(&mut self, name: &str, content: &str) -> Result<&mut Self, FixtureError> {
@@ -887,6 +1138,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nested_repos_surface_as_single_directory_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        materialize("nested-repos", dir.path()).unwrap();
+
+        let git = Repository::open(dir.path()).unwrap();
+        let submodules = git.submodules().unwrap();
+        assert_eq!(submodules.len(), 1);
+        assert_eq!(submodules[0].path(), Path::new("vendor/lib"));
+        assert!(Repository::open(dir.path().join("scratch")).is_ok());
+
+        let repo = LocalGit::new().discover(dir.path()).unwrap();
+        let workdir = repo.workdir().unwrap();
+        let rel: Vec<PathBuf> = repo
+            .changed_files()
+            .iter()
+            .map(|f| f.path.strip_prefix(&workdir).unwrap().to_path_buf())
+            .collect();
+        assert!(
+            !rel.iter()
+                .any(|p| p.ends_with("lib.txt") || p.ends_with("notes.txt")),
+            "status never lists files inside a nested repo: {rel:?}",
+        );
+    }
+
+    #[test]
+    fn upstream_diverges_from_its_remote_by_one_each_way() {
+        let dir = tempfile::tempdir().unwrap();
+        materialize("upstream", dir.path()).unwrap();
+
+        let git = Repository::open(dir.path()).unwrap();
+        let main = git.find_branch("main", git2::BranchType::Local).unwrap();
+        let upstream = main.upstream().unwrap();
+        assert_eq!(upstream.name().unwrap(), Some("origin/main"));
+        let local = main.get().target().unwrap();
+        let remote = upstream.get().target().unwrap();
+        assert_eq!(git.graph_ahead_behind(local, remote).unwrap(), (1, 1));
+
+        let pushed =
+            Repository::open_bare(dir.path().join(".git/fixture-remotes/origin.git")).unwrap();
+        assert_eq!(
+            pushed.refname_to_id("refs/heads/main").unwrap(),
+            remote,
+            "the bare remote holds the pushed tip"
+        );
+
+        let v01 = git.revparse_single("refs/tags/v0.1").unwrap();
+        assert!(v01.as_tag().is_some());
+        let base = git.revparse_single("refs/tags/base").unwrap();
+        assert_eq!(v01.peel_to_commit().unwrap().id(), base.id());
+    }
+
     #[test]
     fn rebase_replays_feature_onto_main_cleanly() {
         let dir = tempfile::tempdir().unwrap();
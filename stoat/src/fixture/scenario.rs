@@ -0,0 +1,226 @@
+//! Fixtures described as data instead of code.
+//!
+//! A scenario is a RON list of steps replayed in order against a fresh
+//! repository, through the same deterministic builder the named fixtures use.
+//! It covers what those fixtures hand-roll: commits, branches, checkouts,
+//! merges, tags, submodules, nested repositories, local remotes with an
+//! upstream, and staged or working-tree changes left on top.
+//!
+//! ```ron
+//! Scenario(steps: [
+//!     Commit(message: "base", files: {"file.txt": "one\n"}),
+//!     Branch("feature"),
+//!     Checkout("feature"),
+//!     Commit(message: "feature work", files: {"feature.txt": "two\n"}),
+//!     Checkout("main"),
+//!     Merge(branch: "feature", message: "merge feature"),
+//!     Tag(name: "v1", message: Some("first release")),
+//!     Stage(path: "file.txt", content: "one staged\n"),
+//!     Write(path: "scratch.txt", content: "untracked\n"),
+//! ])
+//! ```
+
+use super::{FixtureError, FixtureRepo, IoSnafu, ParseScenarioSnafu};
+use serde::Deserialize;
+use snafu::ResultExt;
+use std::{collections::BTreeMap, path::Path};
+
+/// A fixture as an ordered list of [`Step`]s.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub steps: Vec<Step>,
+}
+
+/// One operation replayed by [`Scenario::build`].
+#[derive(Debug, Clone, Deserialize)]
+pub enum Step {
+    /// Write and stage each file, then commit on the current branch.
+    Commit {
+        message: String,
+        files: BTreeMap<String, String>,
+    },
+    /// Create a branch at HEAD without switching to it.
+    Branch(String),
+    /// Switch HEAD, the index, and the working tree to a branch.
+    Checkout(String),
+    /// Merge a branch into the current one with a two-parent commit. Fails
+    /// when the branches conflict.
+    Merge { branch: String, message: String },
+    /// Tag HEAD. A `message` makes the tag annotated, otherwise lightweight.
+    Tag {
+        name: String,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    /// Write a file and stage it, left uncommitted.
+    Stage { path: String, content: String },
+    /// Write a file to the working tree only.
+    Write { path: String, content: String },
+    /// Remove a file from the working tree and the index.
+    StageDelete(String),
+    /// Remove a file from the working tree only.
+    Delete(String),
+    /// Rename a file in the working tree and the index.
+    StageRename { from: String, to: String },
+    /// Build a repository from `steps`, add it as a submodule at `path`, and
+    /// commit the registration with `message`.
+    Submodule {
+        path: String,
+        message: String,
+        steps: Vec<Step>,
+    },
+    /// Build an unregistered repository from `steps` at `path` inside the
+    /// working tree.
+    NestedRepo { path: String, steps: Vec<Step> },
+    /// Create a local bare remote with this name, push the current branch to
+    /// it, and make the branch track it.
+    Remote(String),
+    /// Push the current branch to the named remote.
+    Push(String),
+    /// Hard-reset the current branch to a revision such as `HEAD~1`.
+    Reset(String),
+}
+
+impl Scenario {
+    /// Parse a scenario from RON source.
+    pub fn parse(source: &str) -> Result<Self, FixtureError> {
+        ron::from_str(source).context(ParseScenarioSnafu)
+    }
+
+    /// Read and parse a `scenario.ron` file.
+    pub fn load(path: &Path) -> Result<Self, FixtureError> {
+        let source = std::fs::read_to_string(path).context(IoSnafu { path })?;
+        Self::parse(&source)
+    }
+
+    /// Replay every step into a new repository at `dest`, which must be an
+    /// existing, empty directory. Commits are authored with the pinned
+    /// fixture signature, so equal scenarios produce equal SHAs.
+    pub fn build(&self, dest: &Path) -> Result<(), FixtureError> {
+        replay(&mut FixtureRepo::init(dest)?, &self.steps)
+    }
+}
+
+fn replay(repo: &mut FixtureRepo, steps: &[Step]) -> Result<(), FixtureError> {
+    for step in steps {
+        match step {
+            Step::Commit { message, files } => {
+                let files: Vec<(&str, &str)> = files
+                    .iter()
+                    .map(|(path, content)| (path.as_str(), content.as_str()))
+                    .collect();
+                repo.commit(message, &files)?;
+            },
+            Step::Branch(name) => {
+                repo.branch(name)?;
+            },
+            Step::Checkout(name) => {
+                repo.checkout(name)?;
+            },
+            Step::Merge { branch, message } => {
+                repo.merge(branch, message)?;
+            },
+            Step::Tag { name, message } => {
+                repo.tag(name, message.as_deref())?;
+            },
+            Step::Stage { path, content } => {
+                repo.staged_file(path, content)?;
+            },
+            Step::Write { path, content } => {
+                repo.unstaged_file(path, content)?;
+            },
+            Step::StageDelete(path) => {
+                repo.staged_delete(path)?;
+            },
+            Step::Delete(path) => {
+                repo.unstaged_delete(path)?;
+            },
+            Step::StageRename { from, to } => {
+                repo.staged_rename(from, to)?;
+            },
+            Step::Submodule {
+                path,
+                message,
+                steps,
+            } => {
+                repo.submodule(path, message, |sub| replay(sub, steps))?;
+            },
+            Step::NestedRepo { path, steps } => {
+                repo.nested_repo(path, |nested| replay(nested, steps))?;
+            },
+            Step::Remote(name) => {
+                repo.remote(name)?;
+            },
+            Step::Push(name) => {
+                repo.push(name)?;
+            },
+            Step::Reset(rev) => {
+                repo.reset_hard(rev)?;
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::assert_status;
+    use git2::Repository;
+
+    const MERGE_AND_TAG: &str = r#"Scenario(steps: [
+        Commit(message: "base", files: {"base.txt": "base\n"}),
+        Branch("feature"),
+        Checkout("feature"),
+        Commit(message: "feature work", files: {"feature.txt": "feature\n"}),
+        Tag(name: "light"),
+        Checkout("main"),
+        Commit(message: "main work", files: {"main.txt": "main\n"}),
+        Merge(branch: "feature", message: "merge feature"),
+        Tag(name: "v1", message: Some("first release")),
+        Stage(path: "base.txt", content: "base staged\n"),
+        Write(path: "scratch.txt", content: "untracked\n"),
+    ])"#;
+
+    #[test]
+    fn scenario_builds_merges_tags_and_leaves_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        Scenario::parse(MERGE_AND_TAG)
+            .unwrap()
+            .build(dir.path())
+            .unwrap();
+
+        let git = Repository::open(dir.path()).unwrap();
+        let head = git.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_count(), 2, "HEAD is the merge commit");
+        assert!(head.tree().unwrap().get_name("feature.txt").is_some());
+
+        let v1 = git.revparse_single("refs/tags/v1").unwrap();
+        assert!(v1.as_tag().is_some(), "a message makes an annotated tag");
+        assert_eq!(v1.peel_to_commit().unwrap().id(), head.id());
+        let light = git.revparse_single("refs/tags/light").unwrap();
+        assert!(light.as_commit().is_some(), "no message is lightweight");
+
+        assert_status(dir.path(), &["M  base.txt", "?? scratch.txt"]);
+    }
+
+    #[test]
+    fn equal_scenarios_produce_equal_shas() {
+        let head = || {
+            let dir = tempfile::tempdir().unwrap();
+            Scenario::parse(MERGE_AND_TAG)
+                .unwrap()
+                .build(dir.path())
+                .unwrap();
+            let git = Repository::open(dir.path()).unwrap();
+            git.head().unwrap().target().unwrap()
+        };
+        assert_eq!(head(), head());
+    }
+
+    #[test]
+    fn malformed_scenario_errors() {
+        let err = Scenario::parse("Scenario(steps: [Rebase])").unwrap_err();
+        assert!(matches!(err, FixtureError::ParseScenario { .. }));
+    }
+}
@@ -0,0 +1,186 @@
+//! Declarative checks of a fixture repository's state.
+//!
+//! Git tests otherwise collect status entries by hand and compare ad-hoc
+//! tuples. [`assert_status`] compares the repository against `git status
+//! --porcelain` style lines instead, and [`diff_snapshot`] renders the staged
+//! and unstaged diffs as stable text for a single `assert_eq` or snapshot.
+
+use super::{FixtureError, GitSnafu};
+use git2::{DiffFormat, DiffOptions, Repository, Status, StatusOptions};
+use snafu::ResultExt;
+use std::path::Path;
+
+/// The status of the repository at `root` as porcelain v1 lines, `XY path`,
+/// sorted by path. `X` is the index column and `Y` the working tree, with
+/// `??` for untracked entries. Renames are not detected, matching
+/// [`crate::host::GitRepo::changed_files`], so a staged rename reads as a
+/// `D ` and an `A ` line.
+pub fn porcelain_status(root: &Path) -> Result<Vec<String>, FixtureError> {
+    let repo = Repository::open(root).context(GitSnafu)?;
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+    let statuses = repo.statuses(Some(&mut opts)).context(GitSnafu)?;
+
+    let mut lines: Vec<String> = statuses
+        .iter()
+        .filter_map(|entry| {
+            let path = entry.path()?;
+            Some(format!("{} {path}", status_code(entry.status())))
+        })
+        .collect();
+    lines.sort_by(|a, b| a[3..].cmp(&b[3..]));
+    Ok(lines)
+}
+
+/// Assert the repository at `root` has exactly the `expected` porcelain
+/// lines, in any order. See [`porcelain_status`] for the format.
+#[track_caller]
+pub fn assert_status(root: &Path, expected: &[&str]) {
+    let got = porcelain_status(root).expect("read fixture status");
+    let mut expected: Vec<&str> = expected.to_vec();
+    expected.sort_by(|a, b| a.get(3..).cmp(&b.get(3..)));
+    assert_eq!(got, expected, "porcelain status of {}", root.display());
+}
+
+/// The staged diff (HEAD to index) then the unstaged diff (index to working
+/// tree, untracked files included) of the repository at `root`.
+///
+/// Each file opens with a `staged PATH` or `unstaged PATH` line, followed by
+/// its hunk headers and `+`/`-`/` ` prefixed lines. Git's file headers are
+/// left out since they carry blob ids, so the text only changes when the
+/// content does.
+pub fn diff_snapshot(root: &Path) -> Result<String, FixtureError> {
+    let repo = Repository::open(root).context(GitSnafu)?;
+    let head = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let mut out = String::new();
+
+    let mut opts = DiffOptions::new();
+    let staged = repo
+        .diff_tree_to_index(head.as_ref(), None, Some(&mut opts))
+        .context(GitSnafu)?;
+    render_diff(&staged, "staged", &mut out)?;
+
+    let mut opts = DiffOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let unstaged = repo
+        .diff_index_to_workdir(None, Some(&mut opts))
+        .context(GitSnafu)?;
+    render_diff(&unstaged, "unstaged", &mut out)?;
+    Ok(out)
+}
+
+fn render_diff(diff: &git2::Diff<'_>, label: &str, out: &mut String) -> Result<(), FixtureError> {
+    diff.print(DiffFormat::Patch, |delta, _hunk, line| {
+        let content = String::from_utf8_lossy(line.content());
+        match line.origin() {
+            'F' => {
+                let path = delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default();
+                out.push_str(&format!("{label} {path}\n"));
+            },
+            'H' => out.push_str(&content),
+            origin @ ('+' | '-' | ' ') => {
+                out.push(origin);
+                out.push_str(&content);
+            },
+            _ => out.push_str(&content),
+        }
+        true
+    })
+    .context(GitSnafu)
+}
+
+fn status_code(status: Status) -> String {
+    if status.is_wt_new() {
+        return "??".to_string();
+    }
+    if status.is_conflicted() {
+        return "UU".to_string();
+    }
+    let index = if status.is_index_new() {
+        'A'
+    } else if status.is_index_modified() {
+        'M'
+    } else if status.is_index_deleted() {
+        'D'
+    } else if status.is_index_renamed() {
+        'R'
+    } else if status.is_index_typechange() {
+        'T'
+    } else {
+        ' '
+    };
+    let worktree = if status.is_wt_modified() {
+        'M'
+    } else if status.is_wt_deleted() {
+        'D'
+    } else if status.is_wt_renamed() {
+        'R'
+    } else if status.is_wt_typechange() {
+        'T'
+    } else {
+        ' '
+    };
+    format!("{index}{worktree}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::materialize;
+
+    #[test]
+    fn diff_kinds_status_matches_porcelain() {
+        let dir = tempfile::tempdir().unwrap();
+        materialize("diff-kinds", dir.path()).unwrap();
+        assert_status(
+            dir.path(),
+            &[
+                "A  added-staged.txt",
+                "D  deleted-staged.txt",
+                " D deleted-unstaged.txt",
+                " M hunks.txt",
+                "M  modified-staged.txt",
+                " M modified-unstaged.txt",
+                "D  renamed-from.txt",
+                "A  renamed-to.txt",
+                "?? untracked.txt",
+            ],
+        );
+    }
+
+    #[test]
+    fn basic_diff_snapshot_is_stable_text() {
+        let dir = tempfile::tempdir().unwrap();
+        materialize("basic-diff", dir.path()).unwrap();
+        assert_eq!(
+            diff_snapshot(dir.path()).unwrap(),
+            "\
+staged staged.txt
+@@ -1,6 +1,6 @@
+ 1 alpha
+ 2 bravo
+ 3 charlie
+-4 delta
++4 delta changed
+ 5 echo
+ 6 foxtrot
+unstaged unstaged.txt
+@@ -1,6 +1,6 @@
+ 1 one
+ 2 two
+ 3 three
+-4 four
++4 four changed
+ 5 five
+ 6 six
+"
+        );
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     keymap::{evaluate, Keymap, KeymapState, ResolvedAction, ResolvedArg, StateValue},
     workspace::Workspace,
 };
+use serde::Serialize;
 use std::{
     collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
@@ -149,11 +150,10 @@ pub fn build_help_bindings(
     context: &SnapshotState,
 ) -> HashMap<String, Vec<HelpBinding>> {
     let mut by_action: HashMap<String, Vec<HelpBinding>> = HashMap::new();
-    for (key, predicates, actions) in keymap.bindings() {
+    for (label, predicates, actions) in keymap.bindings() {
         let active = predicates
             .iter()
             .all(|predicate| evaluate(predicate, context));
-        let label = key.display_label();
         for action in actions {
             by_action
                 .entry(action.name.clone())
@@ -465,19 +465,22 @@ impl Help {
     }
 }
 
+/// The def help describes `name` with, covering the keymap-only `SetMode`.
+fn help_def(name: &str) -> Option<&'static dyn ActionDef> {
+    if name == "SetMode" {
+        return Some(&SET_MODE_DEF);
+    }
+    registry::lookup(name).map(|reg| reg.def)
+}
+
 fn build_active_entries(active: &[(String, Vec<ResolvedAction>)]) -> Vec<HelpEntry> {
     let mut entries = Vec::new();
     for (label, actions) in active {
         let Some(first) = actions.first() else {
             continue;
         };
-        let def: &'static dyn ActionDef = if first.name == "SetMode" {
-            &SET_MODE_DEF
-        } else {
-            match registry::lookup(&first.name) {
-                Some(reg) => reg.def,
-                None => continue,
-            }
+        let Some(def) = help_def(&first.name) else {
+            continue;
         };
         entries.push(HelpEntry {
             def,
@@ -528,6 +531,138 @@ pub fn format_arg(arg: &ResolvedArg) -> Option<String> {
     }
 }
 
+/// Render one action of a binding's sequence as `name(arg, ...)`, or bare `name`
+/// when it takes no arguments, so `AutoReload(follow)` reads distinctly from
+/// `AutoReload(on)`.
+pub fn format_action(action: &ResolvedAction) -> String {
+    let args: Vec<String> = action.args.iter().filter_map(format_arg).collect();
+    if args.is_empty() {
+        action.name.clone()
+    } else {
+        format!("{}({})", action.name, args.join(", "))
+    }
+}
+
+/// One binding in a [`keymap_reference`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReferenceBinding {
+    pub keys: String,
+    /// The binding's action sequence, each as [`format_action`] renders it.
+    pub actions: Vec<String>,
+    /// The binding's conditions other than its mode, as config source.
+    pub when: Vec<String>,
+    /// The first action's short description, absent for an unknown action.
+    pub description: Option<String>,
+}
+
+/// The bindings of one mode in a [`keymap_reference`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReferenceGroup {
+    /// The mode the bindings apply in, or `None` for bindings with no `mode ==`
+    /// condition, which hold in every mode.
+    pub mode: Option<String>,
+    pub bindings: Vec<ReferenceBinding>,
+}
+
+/// Every binding in `keymap`, grouped by the mode its `mode == name`
+/// condition selects.
+///
+/// Groups are ordered by mode name with `normal` first and the mode-less group
+/// last, and bindings keep source order within a group. `stoat keymap dump`
+/// prints this, and the help modal shares [`format_action`] for its action
+/// text so both read the same.
+pub fn keymap_reference(keymap: &Keymap) -> Vec<ReferenceGroup> {
+    let mut groups: Vec<ReferenceGroup> = Vec::new();
+    for (keys, predicates, actions) in keymap.bindings() {
+        let mut conditions = Vec::new();
+        for predicate in predicates {
+            conjuncts(predicate, &mut conditions);
+        }
+        let mode = conditions.iter().find_map(|p| mode_condition(p));
+        let when = conditions
+            .iter()
+            .filter(|predicate| mode_condition(predicate).is_none())
+            .map(ToString::to_string)
+            .collect();
+        let description = actions
+            .first()
+            .and_then(|first| help_def(&first.name))
+            .map(|def| def.short_desc().to_string());
+        let binding = ReferenceBinding {
+            keys,
+            actions: actions.iter().map(format_action).collect(),
+            when,
+            description,
+        };
+        match groups.iter_mut().find(|group| group.mode == mode) {
+            Some(group) => group.bindings.push(binding),
+            None => groups.push(ReferenceGroup {
+                mode,
+                bindings: vec![binding],
+            }),
+        }
+    }
+    groups.sort_by_key(|group| match group.mode.as_deref() {
+        Some("normal") => (0, String::new()),
+        Some(mode) => (1, mode.to_string()),
+        None => (2, String::new()),
+    });
+    groups
+}
+
+/// Split `predicate` at its top-level `&&`s, so a `modal == help && mode ==
+/// normal` block still groups under `normal`.
+fn conjuncts<'a>(predicate: &'a Predicate, out: &mut Vec<&'a Predicate>) {
+    match predicate {
+        Predicate::And(left, right) => {
+            conjuncts(&left.node, out);
+            conjuncts(&right.node, out);
+        },
+        other => out.push(other),
+    }
+}
+
+/// The mode a `mode == name` condition selects.
+fn mode_condition(predicate: &Predicate) -> Option<String> {
+    let Predicate::Eq(field, value) = predicate else {
+        return None;
+    };
+    if field.node != "mode" {
+        return None;
+    }
+    match &value.node {
+        Value::Ident(mode) | Value::String(mode) => Some(mode.clone()),
+        _ => None,
+    }
+}
+
+/// Render `groups` as a Markdown reference, one `##` section and table per
+/// mode.
+pub fn reference_markdown(groups: &[ReferenceGroup]) -> String {
+    let mut out = String::from("# Keybindings\n");
+    for group in groups {
+        let title = group.mode.as_deref().unwrap_or("any mode");
+        out.push_str(&format!("\n## {title}\n\n"));
+        out.push_str("| Keys | Action | When | Description |\n");
+        out.push_str("| --- | --- | --- | --- |\n");
+        for binding in &group.bindings {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                markdown_cell(&format!("`{}`", binding.keys)),
+                markdown_cell(&binding.actions.join(", ")),
+                markdown_cell(&binding.when.join(" && ")),
+                markdown_cell(binding.description.as_deref().unwrap_or("")),
+            ));
+        }
+    }
+    out
+}
+
+/// Escape the pipes that would otherwise split a Markdown table cell.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -577,6 +712,7 @@ mod tests {
     /// it.
     fn open_help_with(h: &mut TestHarness, active: Vec<(String, Vec<ResolvedAction>)>) {
         let executor = h.stoat.executor.clone();
+        crate::overlay::push(&mut h.stoat, crate::overlay::Overlay::Help);
         let active_idx = h.stoat.active_workspace;
         let ws = &mut h.stoat.workspaces[active_idx];
         h.stoat.help = Some(Help::new(
@@ -621,6 +757,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn keymap_reference_groups_by_mode() {
+        let (config, errors) = stoat_config::parse(
+            "on key {
+                Ctrl-q -> Quit();
+                mode == space && view == diff { F -> AutoReload(follow); }
+                mode == normal { j -> MoveDown(); }
+            }",
+        );
+        assert!(errors.is_empty(), "parse errors: {errors:?}");
+        let groups = keymap_reference(&Keymap::compile(&config.expect("config")));
+
+        let modes: Vec<Option<&str>> = groups.iter().map(|g| g.mode.as_deref()).collect();
+        assert_eq!(modes, [Some("normal"), Some("space"), None]);
+        let follow = &groups[1].bindings[0];
+        assert_eq!(follow.actions, ["AutoReload(follow)"]);
+        assert_eq!(follow.when, ["view == diff"]);
+        assert!(follow.description.is_some());
+
+        let markdown = reference_markdown(&groups);
+        assert!(markdown.contains("## space"));
+        assert!(markdown.contains("| `F` | AutoReload(follow) | view == diff |"));
+    }
+
     /// Dispatch a key through the test harness's top-level key handler so
     /// typing flows through `dispatch_help_key` with workspace access.
     fn send_key(h: &mut TestHarness, key: KeyEvent) {
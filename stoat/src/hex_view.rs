@@ -0,0 +1,153 @@
+//! Read-only hex dump of a binary file.
+//!
+//! A file that [`is_binary`] flags opens as a [`HexView`] rather than a text
+//! buffer, which would garble it. The view keeps the raw bytes and formats a
+//! row only when it is painted, so a large file costs its bytes and nothing
+//! per row. `OpenAsText` swaps the view for a lossily decoded text buffer when
+//! the detection is wrong or the text is wanted anyway.
+
+use slotmap::new_key_type;
+use std::{
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+new_key_type! {
+    pub struct HexId;
+}
+
+/// Bytes shown on one row of the dump.
+pub const BYTES_PER_ROW: usize = 16;
+
+/// Leading bytes [`is_binary`] scans for a NUL, the same window git uses.
+const SNIFF_LEN: usize = 8000;
+
+/// Whether `bytes` should open as a hex dump: a NUL in the first
+/// [`SNIFF_LEN`] bytes, or content that is not valid UTF-8.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(SNIFF_LEN)].contains(&0) || std::str::from_utf8(bytes).is_err()
+}
+
+pub struct HexView {
+    path: PathBuf,
+    bytes: Arc<[u8]>,
+    /// First row painted at the top of the pane.
+    pub scroll_row: usize,
+}
+
+impl HexView {
+    pub fn new(path: PathBuf, bytes: Arc<[u8]>) -> Self {
+        Self {
+            path,
+            bytes,
+            scroll_row: 0,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn bytes(&self) -> &Arc<[u8]> {
+        &self.bytes
+    }
+
+    /// Rows in the dump, at least one so an empty file still shows its offset.
+    pub fn row_count(&self) -> usize {
+        self.bytes.len().div_ceil(BYTES_PER_ROW).max(1)
+    }
+
+    /// Move [`Self::scroll_row`] by `delta` rows, keeping a `page`-row viewport
+    /// filled where the file allows. Returns whether it moved.
+    pub fn scroll_by(&mut self, delta: isize, page: usize) -> bool {
+        let max = self.row_count().saturating_sub(page.max(1));
+        let next = self.scroll_row.saturating_add_signed(delta).min(max);
+        let moved = next != self.scroll_row;
+        self.scroll_row = next;
+        moved
+    }
+
+    /// Format row `row` as `offset  hex bytes  |ascii|`, with the hex split
+    /// into two groups of eight and non-printable bytes shown as `.` in the
+    /// ASCII column. A short final row pads its hex so the ASCII column lines
+    /// up. `None` past the last row.
+    pub fn row(&self, row: usize) -> Option<String> {
+        if row >= self.row_count() {
+            return None;
+        }
+        let start = row * BYTES_PER_ROW;
+        let len = self.bytes.len();
+        let chunk = &self.bytes[start.min(len)..(start + BYTES_PER_ROW).min(len)];
+
+        let mut line = format!("{start:08x} ");
+        for idx in 0..BYTES_PER_ROW {
+            if idx % 8 == 0 {
+                line.push(' ');
+            }
+            match chunk.get(idx) {
+                Some(byte) => {
+                    let _ = write!(line, "{byte:02x} ");
+                },
+                None => line.push_str("   "),
+            }
+        }
+        line.push('|');
+        line.extend(chunk.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        }));
+        line.push('|');
+        Some(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(bytes: &[u8]) -> HexView {
+        HexView::new(PathBuf::from("/a.bin"), Arc::from(bytes))
+    }
+
+    #[test]
+    fn binary_detection() {
+        assert!(is_binary(b"\x7fELF\x00\x01"));
+        assert!(is_binary(&[0xff, 0xfe, b'a']));
+        assert!(!is_binary("plain text, ünïcode\n".as_bytes()));
+        assert!(!is_binary(b""));
+    }
+
+    #[test]
+    fn rows_format_hex_and_ascii() {
+        let bytes: Vec<u8> = (0x41..0x41 + 18).collect();
+        let v = view(&bytes);
+        assert_eq!(v.row_count(), 2);
+        assert_eq!(
+            v.row(0).unwrap(),
+            "00000000  41 42 43 44 45 46 47 48  49 4a 4b 4c 4d 4e 4f 50 |ABCDEFGHIJKLMNOP|"
+        );
+        assert_eq!(
+            v.row(1).unwrap(),
+            format!("00000010  51 52{}|QR|", " ".repeat(44))
+        );
+        assert_eq!(v.row(2), None);
+        assert_eq!(
+            view(&[0, b'\n']).row(0).unwrap().rsplit(' ').next(),
+            Some("|..|")
+        );
+    }
+
+    #[test]
+    fn scroll_clamps_to_the_last_page() {
+        let mut v = view(&[0; BYTES_PER_ROW * 10]);
+        assert!(v.scroll_by(100, 4));
+        assert_eq!(v.scroll_row, 6);
+        assert!(!v.scroll_by(1, 4));
+        assert!(v.scroll_by(-100, 4));
+        assert_eq!(v.scroll_row, 0);
+    }
+}
@@ -39,6 +39,7 @@ pub use lsp::{LanguageServerFeature, LspHost, LspNotification, NoopLsp, OffsetEn
 pub use stoat_host::FakeShell;
 pub use stoat_host::{
     EnvHost, FsDirEntry, FsEventKind, FsHost, FsMetadata, FsWatchEvent, FsWatchHost, LocalEnv,
-    LocalFs, LocalFsWatcher, LocalShell, NoopFsWatcher, ShellHost, ShellOutput, WatchToken,
+    LocalFs, LocalFsWatcher, LocalShell, NoopFsWatcher, ShellHost, ShellOutput, ShellStream,
+    WatchToken,
 };
 pub use terminal::{SpawnArgs, TerminalHost, TerminalSession};
@@ -0,0 +1,106 @@
+use crate::symbol_finder::SymbolFinderScope;
+
+/// The modal a dismissed draft belongs to.
+///
+/// A reword draft carries the commit it was rewording, so a half-written
+/// message only returns when the same commit is reworded again rather than
+/// leaking into an unrelated one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DraftKey {
+    Palette,
+    FileFinder,
+    SymbolFinder(SymbolFinderScope),
+    Reword(String),
+}
+
+/// Session-only stack of input text from modals that were dismissed rather
+/// than submitted.
+///
+/// Cancelling a picker or the reword modal stashes its text here, and the next
+/// open of the same modal takes it back, so an accidental Escape does not lose
+/// a typed query or a half-written commit message. Opening takes the draft, so a submitted
+/// input leaves nothing behind. Each key holds at most one draft, the newest, and the stack keeps
+/// only the [`MAX_DRAFTS`] most recent. Nothing is persisted across restarts.
+#[derive(Debug, Default)]
+pub(crate) struct DraftStack {
+    /// Drafts oldest-first, at most one per key.
+    entries: Vec<(DraftKey, String)>,
+}
+
+/// Cap on retained drafts, evicting the oldest past this.
+const MAX_DRAFTS: usize = 8;
+
+impl DraftStack {
+    /// Record `text` as the newest draft for `key`, replacing any older one.
+    ///
+    /// Blank text clears the key instead, so dismissing an emptied input does
+    /// not resurrect an older draft on the next open.
+    pub(crate) fn stash(&mut self, key: DraftKey, text: String) {
+        self.entries.retain(|(k, _)| *k != key);
+        if text.trim().is_empty() {
+            return;
+        }
+        self.entries.push((key, text));
+        if self.entries.len() > MAX_DRAFTS {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Remove and return the draft for `key`, if one was stashed.
+    pub(crate) fn take(&mut self, key: &DraftKey) -> Option<String> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        Some(self.entries.remove(index).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn take_returns_the_stashed_text_once() {
+        let mut drafts = DraftStack::default();
+        drafts.stash(DraftKey::FileFinder, "src/ma".into());
+        assert_eq!(
+            drafts.take(&DraftKey::FileFinder).as_deref(),
+            Some("src/ma")
+        );
+        assert_eq!(drafts.take(&DraftKey::FileFinder), None);
+    }
+
+    #[test]
+    fn keys_do_not_share_drafts() {
+        let mut drafts = DraftStack::default();
+        drafts.stash(DraftKey::Palette, "open".into());
+        drafts.stash(DraftKey::Reword("abc".into()), "fix: typo".into());
+        assert_eq!(drafts.take(&DraftKey::FileFinder), None);
+        assert_eq!(drafts.take(&DraftKey::Reword("def".into())), None);
+        assert_eq!(
+            drafts.take(&DraftKey::Reword("abc".into())).as_deref(),
+            Some("fix: typo")
+        );
+    }
+
+    #[test]
+    fn restash_replaces_and_blank_clears() {
+        let mut drafts = DraftStack::default();
+        drafts.stash(DraftKey::Palette, "old".into());
+        drafts.stash(DraftKey::Palette, "new".into());
+        assert_eq!(drafts.take(&DraftKey::Palette).as_deref(), Some("new"));
+
+        drafts.stash(DraftKey::Palette, "kept".into());
+        drafts.stash(DraftKey::Palette, "  ".into());
+        assert_eq!(drafts.take(&DraftKey::Palette), None);
+    }
+
+    #[test]
+    fn oldest_draft_is_evicted_past_the_cap() {
+        let mut drafts = DraftStack::default();
+        drafts.stash(DraftKey::Reword("0".into()), "first".into());
+        for i in 1..=MAX_DRAFTS {
+            drafts.stash(DraftKey::Reword(i.to_string()), "later".into());
+        }
+        assert_eq!(drafts.take(&DraftKey::Reword("0".into())), None);
+        assert!(drafts.take(&DraftKey::Reword("1".into())).is_some());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     buffer::BufferId,
+    buffer_registry::BufferRegistry,
     editor_state::{EditorId, EditorState},
     render::{editor::render_editor, pane::mode_segment, text::write_str},
     theme::{scope, Theme},
@@ -50,7 +51,11 @@ pub(crate) enum SubmitTarget {
     CodeSearch,
     SplitSelection,
     KeepRemoveSelections,
+    SurroundTag,
     Shell,
+    RegexPlayground,
+    ColorPicker,
+    LocalRename,
 }
 
 impl InputView {
@@ -104,7 +109,13 @@ impl InputView {
     }
 
     pub(crate) fn text(&self, ws: &Workspace) -> String {
-        let Some(buffer) = ws.buffers.get(self.buffer_id) else {
+        self.text_in(&ws.buffers)
+    }
+
+    /// [`Self::text`] for a caller holding only the workspace's buffers, like a
+    /// pane render that borrows the editors alongside them.
+    pub(crate) fn text_in(&self, buffers: &BufferRegistry) -> String {
+        let Some(buffer) = buffers.get(self.buffer_id) else {
             return String::new();
         };
         let guard = buffer.read().expect("buffer poisoned");
@@ -115,11 +126,9 @@ impl InputView {
     /// [`crate::app::Stoat::editor_insert`] but parameterized by workspace +
     /// the view's own editor / buffer ids so consumers that ever want to
     /// drive input mutations outside the focused-editor short-circuit
-    /// (`handle_insert_key`) can do so directly. Currently unused because
-    /// every live consumer routes typing through the global keymap, but
-    /// kept as a building block for future short-circuited flows (eg. a
-    /// paste action that bypasses insert mode).
-    #[allow(dead_code)]
+    /// (`handle_insert_key`) can do so directly. The regex playground uses
+    /// it to break a line in its sample pane, where Enter would otherwise
+    /// submit.
     pub(crate) fn insert_at_cursor(&self, ws: &mut Workspace, text: &str) {
         let Some(editor) = ws.editors.get_mut(self.editor_id) else {
             return;
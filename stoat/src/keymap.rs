@@ -5,7 +5,8 @@ use std::{
     sync::Arc,
 };
 use stoat_config::{
-    ActionExpr, Binding, Config, EventType, Key, KeyPart, Predicate, Statement, Value,
+    ActionExpr, Binding, Config, EventType, FnDecl, Key, KeyPart, Predicate, Statement, Unbind,
+    Value,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -343,8 +344,130 @@ pub struct ResolvedArg {
 #[derive(Debug, Clone)]
 struct CompiledBinding {
     key: CompiledKey,
+    /// Chords that must follow `key`, for a sequence binding like `g g`.
+    then: Vec<CompiledKey>,
     predicates: Vec<Predicate>,
     actions: Arc<[ResolvedAction]>,
+    /// Index of the `on key` block the binding came from.
+    block: usize,
+}
+
+impl CompiledBinding {
+    fn chords(&self) -> impl Iterator<Item = &CompiledKey> {
+        std::iter::once(&self.key).chain(&self.then)
+    }
+
+    fn last_chord(&self) -> &CompiledKey {
+        self.then.last().unwrap_or(&self.key)
+    }
+
+    /// Standing against other bindings matching the same presses: predicate
+    /// specificity, then an exact key over a `num` placeholder, so `3 -> ...`
+    /// overrides `num -> ...`, then a later `on key` block over an earlier
+    /// one. Lookups keep the earliest binding on a full tie, so within one
+    /// block source order decides.
+    fn rank(&self) -> (usize, bool, usize) {
+        let score = self.predicates.iter().map(predicate_atoms).sum();
+        (score, !self.last_chord().any_digit, self.block)
+    }
+
+    /// The binding's chords as a hint label, space-separated for a sequence.
+    fn label(&self) -> String {
+        self.chords()
+            .map(CompiledKey::display_label)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn summary(&self) -> BindingSummary {
+        BindingSummary {
+            label: self.label(),
+            scope: self
+                .predicates
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" && "),
+            actions: self.actions.iter().map(|a| a.name.clone()).collect(),
+            block: self.block,
+        }
+    }
+}
+
+/// A binding considered for a press, from [`Keymap::explain`].
+#[derive(Debug)]
+pub struct Candidate<'a> {
+    pub label: String,
+    pub predicates: &'a [Predicate],
+    pub actions: &'a [ResolvedAction],
+    /// Whether every predicate holds.
+    pub active: bool,
+    /// Predicate atoms, the first thing competing bindings compare.
+    pub specificity: usize,
+    /// Index of the `on key` block the binding came from.
+    pub block: usize,
+    /// Whether a lookup runs this binding.
+    pub won: bool,
+}
+
+/// The highest-ranked of `bindings`, keeping the earliest on a full tie.
+fn best_binding<'a>(
+    bindings: impl IntoIterator<Item = &'a CompiledBinding>,
+) -> Option<&'a CompiledBinding> {
+    let mut best: Option<&CompiledBinding> = None;
+    for binding in bindings {
+        if best.is_none_or(|best| binding.rank() > best.rank()) {
+            best = Some(binding);
+        }
+    }
+    best
+}
+
+/// How a run of chord presses resolves, from [`Keymap::lookup_sequence`].
+#[derive(Debug)]
+pub enum SequenceMatch {
+    /// No active binding starts with the presses.
+    Unbound,
+    /// A longer binding starts with the presses, so the next chord decides.
+    /// `exact` is the binding the presses already complete, which runs if the
+    /// sequence times out instead.
+    Pending {
+        exact: Option<(Arc<[ResolvedAction]>, Option<f64>)>,
+    },
+    /// The presses complete a binding and no longer one continues them.
+    Matched(Arc<[ResolvedAction]>, Option<f64>),
+}
+
+/// A binding as named in a [`KeymapIssue`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingSummary {
+    pub label: String,
+    /// The binding's predicates joined with `&&`, empty when it is global.
+    pub scope: String,
+    /// Names of the actions the binding runs, in order.
+    pub actions: Vec<String>,
+    /// Index of the `on key` block the binding came from.
+    pub block: usize,
+}
+
+/// A problem found by [`Keymap::check`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeymapIssue {
+    /// `dropped` has the same chords as `kept` and an equivalent scope, so
+    /// `kept` wins every lookup both match.
+    Duplicate {
+        kept: BindingSummary,
+        dropped: BindingSummary,
+    },
+    /// `by` has the same chords, holds wherever `binding` holds, and outranks
+    /// it, so `binding` never runs.
+    Shadowed {
+        binding: BindingSummary,
+        by: BindingSummary,
+    },
+    /// A mode some binding block selects on that no chain of `SetMode`
+    /// actions reaches from `normal` or `insert`.
+    UnreachableMode(String),
 }
 
 pub struct Keymap {
@@ -353,36 +476,41 @@ pub struct Keymap {
 
 impl Keymap {
     pub fn compile(config: &Config) -> Self {
-        let mut functions: HashMap<String, Vec<stoat_config::Spanned<Statement>>> = HashMap::new();
+        let mut functions: HashMap<String, FnDecl> = HashMap::new();
         let mut bindings = Vec::new();
 
-        for block in &config.blocks {
+        for (ix, block) in config.blocks.iter().enumerate() {
             if block.node.event != EventType::Key {
                 continue;
             }
             collect_functions(&block.node.statements, &mut functions);
-            compile_statements(&block.node.statements, &[], &functions, &mut bindings);
+            compile_statements(&block.node.statements, &[], &functions, ix, &mut bindings);
         }
 
         Self { bindings }
     }
 
-    /// Every compiled binding as `(key, predicates, actions)`, in source order.
+    /// Every compiled binding as `(label, predicates, actions)`, in source
+    /// order.
     ///
     /// Help walks this to index which keys reach each action and under what
     /// conditions, rather than resolving one key against a single state.
-    pub fn bindings(
-        &self,
-    ) -> impl Iterator<Item = (&CompiledKey, &[Predicate], &[ResolvedAction])> {
+    pub fn bindings(&self) -> impl Iterator<Item = (String, &[Predicate], &[ResolvedAction])> {
         self.bindings.iter().map(|binding| {
             (
-                &binding.key,
+                binding.label(),
                 binding.predicates.as_slice(),
                 &*binding.actions,
             )
         })
     }
 
+    /// Whether any binding is a multi-chord sequence, letting key dispatch
+    /// skip sequence tracking for a keymap without one.
+    pub fn has_sequences(&self) -> bool {
+        self.bindings.iter().any(|binding| !binding.then.is_empty())
+    }
+
     /// Compile `config` and collect warnings for `SetMode` targets no binding
     /// block selects on.
     ///
@@ -420,6 +548,109 @@ impl Keymap {
         (keymap, warnings)
     }
 
+    /// Find bindings no lookup can run and modes no binding can enter.
+    ///
+    /// A binding is dead when another binding on the same chords holds in
+    /// every state it does and outranks it. Equivalent scopes are reported as
+    /// [`KeymapIssue::Duplicate`], a broader winning scope as
+    /// [`KeymapIssue::Shadowed`]. Scope implication is decided structurally
+    /// and conservatively, so a reported binding is dead, though not every
+    /// dead binding is found.
+    ///
+    /// A mode is reachable from `normal` and `insert` through the `SetMode`
+    /// actions of bindings active in an already reachable mode. Issues come
+    /// back in binding source order, followed by the unreachable modes sorted
+    /// by name.
+    pub fn check(&self) -> Vec<KeymapIssue> {
+        let mut by_label: HashMap<String, Vec<usize>> = HashMap::new();
+        for (ix, binding) in self.bindings.iter().enumerate() {
+            by_label.entry(binding.label()).or_default().push(ix);
+        }
+
+        let mut issues = Vec::new();
+        for (ix, binding) in self.bindings.iter().enumerate() {
+            let beats = |other: usize| {
+                let (a, b) = (self.bindings[other].rank(), binding.rank());
+                a > b || (a == b && other < ix)
+            };
+            let mut winner: Option<usize> = None;
+            for &other in &by_label[&binding.label()] {
+                let candidate = &self.bindings[other];
+                if other == ix
+                    || !candidate.chords().eq(binding.chords())
+                    || !beats(other)
+                    || !scope_implies(&binding.predicates, &candidate.predicates)
+                {
+                    continue;
+                }
+                let better = winner.is_none_or(|best| {
+                    let (a, b) = (candidate.rank(), self.bindings[best].rank());
+                    a > b || (a == b && other < best)
+                });
+                if better {
+                    winner = Some(other);
+                }
+            }
+            let Some(winner) = winner else {
+                continue;
+            };
+            let by = &self.bindings[winner];
+            issues.push(if scope_implies(&by.predicates, &binding.predicates) {
+                KeymapIssue::Duplicate {
+                    kept: by.summary(),
+                    dropped: binding.summary(),
+                }
+            } else {
+                KeymapIssue::Shadowed {
+                    binding: binding.summary(),
+                    by: by.summary(),
+                }
+            });
+        }
+
+        let mut selected: HashSet<String> = HashSet::new();
+        for binding in &self.bindings {
+            for predicate in &binding.predicates {
+                collect_mode_targets(predicate, &mut selected);
+            }
+        }
+        let scopes: Vec<Option<HashSet<String>>> = self
+            .bindings
+            .iter()
+            .map(|binding| scope_modes(&binding.predicates))
+            .collect();
+        let mut reachable: HashSet<String> = ["normal", "insert"].map(String::from).into();
+        loop {
+            let before = reachable.len();
+            for (binding, modes) in self.bindings.iter().zip(&scopes) {
+                let active = modes
+                    .as_ref()
+                    .is_none_or(|modes| modes.iter().any(|mode| reachable.contains(mode)));
+                if !active {
+                    continue;
+                }
+                for action in binding.actions.iter() {
+                    if action.name == "SetMode"
+                        && let Some(target) = action.args.first().and_then(|a| value_str(&a.value))
+                    {
+                        reachable.insert(target.to_string());
+                    }
+                }
+            }
+            if reachable.len() == before {
+                break;
+            }
+        }
+        let mut unreachable: Vec<String> = selected
+            .into_iter()
+            .filter(|mode| !reachable.contains(mode))
+            .collect();
+        unreachable.sort();
+        issues.extend(unreachable.into_iter().map(KeymapIssue::UnreachableMode));
+
+        issues
+    }
+
     pub fn lookup(
         &self,
         state: &dyn KeymapState,
@@ -432,39 +663,141 @@ impl Keymap {
     /// Resolve `event` against `state` to the winning binding's actions and the
     /// digit it captured, if the winning key is a `num` placeholder.
     ///
-    /// Bindings rank by predicate specificity first. On a tie an exact key beats
-    /// a placeholder, so `3 -> ...` overrides `num -> ...` for the `3` press,
-    /// and equally-ranked matches keep source order. The captured digit is
-    /// `Some` only when the winning key is the placeholder, so a `$num` argument
-    /// binds a value exactly when a placeholder matched.
+    /// The winner is the matching binding of highest rank, which
+    /// [`Keymap::explain`] reports. The captured digit is `Some` only when the
+    /// winning key is the placeholder, so a `$num` argument binds a value
+    /// exactly when a placeholder matched.
+    ///
+    /// Only single-chord bindings take part. Sequences resolve through
+    /// [`Self::lookup_sequence`].
     pub fn lookup_with_capture(
         &self,
         state: &dyn KeymapState,
         event: &KeyEvent,
     ) -> Option<(Arc<[ResolvedAction]>, Option<f64>)> {
-        let mut best: Option<((usize, bool), &CompiledBinding)> = None;
+        let best = best_binding(self.bindings.iter().filter(|binding| {
+            binding.then.is_empty()
+                && binding.key.matches(event)
+                && binding.predicates.iter().all(|p| evaluate(p, state))
+        }));
+        best.map(|binding| (binding.actions.clone(), binding.key.captured_digit(event)))
+    }
+
+    /// Resolve the chords pressed so far, oldest first, against `state`.
+    ///
+    /// A press that only starts a sequence binding is [`SequenceMatch::Pending`].
+    /// A complete sequence ranks like [`Self::lookup_with_capture`], with the
+    /// digit captured by its last chord.
+    pub fn lookup_sequence(&self, state: &dyn KeymapState, presses: &[KeyEvent]) -> SequenceMatch {
+        let Some(last) = presses.last() else {
+            return SequenceMatch::Unbound;
+        };
+        let mut continued = false;
+        let mut complete = Vec::new();
         for binding in &self.bindings {
-            if !binding.key.matches(event) {
+            let len = binding.then.len() + 1;
+            if len < presses.len()
+                || !binding
+                    .chords()
+                    .zip(presses)
+                    .all(|(chord, press)| chord.matches(press))
+            {
                 continue;
             }
             if !binding.predicates.iter().all(|p| evaluate(p, state)) {
                 continue;
             }
-            let score: usize = binding.predicates.iter().map(predicate_atoms).sum();
-            // Rank exact keys above placeholders at equal specificity. Strict `>`
-            // then keeps the earliest binding on a full tie, so equally specific
-            // matches still resolve in source order.
-            let rank = (score, !binding.key.any_digit);
-            if best.is_none_or(|(best_rank, _)| rank > best_rank) {
-                best = Some((rank, binding));
+            if len > presses.len() {
+                continued = true;
+            } else {
+                complete.push(binding);
             }
         }
-        best.map(|(_, binding)| (binding.actions.clone(), binding.key.captured_digit(event)))
+        let exact = best_binding(complete).map(|binding| {
+            (
+                binding.actions.clone(),
+                binding.last_chord().captured_digit(last),
+            )
+        });
+        if continued {
+            return SequenceMatch::Pending { exact };
+        }
+        match exact {
+            Some((actions, captured)) => SequenceMatch::Matched(actions, captured),
+            None => SequenceMatch::Unbound,
+        }
     }
 
+    /// Every binding on exactly `presses`, in source order, with whether its
+    /// predicates hold in `state` and whether it is the one a lookup runs.
+    ///
+    /// Bindings an `unbind` dropped are gone from the keymap and not listed.
+    pub fn explain(&self, state: &dyn KeymapState, presses: &[KeyEvent]) -> Vec<Candidate<'_>> {
+        let on_presses: Vec<&CompiledBinding> = self
+            .bindings
+            .iter()
+            .filter(|binding| {
+                binding.then.len() + 1 == presses.len()
+                    && binding
+                        .chords()
+                        .zip(presses)
+                        .all(|(chord, press)| chord.matches(press))
+            })
+            .collect();
+        let active =
+            |binding: &CompiledBinding| binding.predicates.iter().all(|p| evaluate(p, state));
+        let winner = best_binding(on_presses.iter().copied().filter(|b| active(b)));
+        on_presses
+            .into_iter()
+            .map(|binding| Candidate {
+                label: binding.label(),
+                predicates: &binding.predicates,
+                actions: &binding.actions,
+                active: active(binding),
+                specificity: binding.rank().0,
+                block: binding.block,
+                won: winner.is_some_and(|winner| std::ptr::eq(winner, binding)),
+            })
+            .collect()
+    }
+
+    /// The active bindings that continue `presses`, as `(remaining chords,
+    /// actions)`. The which-key box lists these while a sequence is pending.
+    pub fn sequence_continuations(
+        &self,
+        state: &dyn KeymapState,
+        presses: &[KeyEvent],
+    ) -> Vec<(String, &[ResolvedAction])> {
+        let mut results = Vec::new();
+        for binding in &self.bindings {
+            if binding.then.len() < presses.len()
+                || !binding
+                    .chords()
+                    .zip(presses)
+                    .all(|(chord, press)| chord.matches(press))
+                || !binding.predicates.iter().all(|p| evaluate(p, state))
+            {
+                continue;
+            }
+            let rest = binding
+                .chords()
+                .skip(presses.len())
+                .map(CompiledKey::display_label)
+                .collect::<Vec<_>>()
+                .join(" ");
+            results.push((rest, binding.actions.as_ref()));
+        }
+        results
+    }
+
+    /// Single-chord bindings whose predicates hold in `state`. Sequence
+    /// bindings are left out, since one key cannot stand for them.
     pub fn active_keys(&self, state: &dyn KeymapState) -> Vec<(&CompiledKey, &[ResolvedAction])> {
         let mut results = Vec::new();
         for binding in &self.bindings {
+            if !binding.then.is_empty() {
+                continue;
+            }
             let matches = binding.predicates.iter().all(|p| evaluate(p, state));
             if matches {
                 results.push((&binding.key, binding.actions.as_ref()));
@@ -480,12 +813,40 @@ impl Keymap {
         for binding in &self.bindings {
             let matches = binding.predicates.iter().all(|p| evaluate(p, state));
             if matches {
-                results.push((binding.key.display_label(), binding.actions.as_ref()));
+                results.push((binding.label(), binding.actions.as_ref()));
             }
         }
         results
     }
 
+    /// The active bindings a lookup would run, as `(label, actions)` in source
+    /// order.
+    ///
+    /// Like [`Self::active_bindings`], but a binding outranked by another active
+    /// binding on the same chords is left out, so every listed key runs its
+    /// listed actions.
+    pub fn winning_bindings(&self, state: &dyn KeymapState) -> Vec<(String, &[ResolvedAction])> {
+        let active: Vec<&CompiledBinding> = self
+            .bindings
+            .iter()
+            .filter(|binding| binding.predicates.iter().all(|p| evaluate(p, state)))
+            .collect();
+        active
+            .iter()
+            .copied()
+            .filter(|binding| {
+                let winner = best_binding(
+                    active
+                        .iter()
+                        .copied()
+                        .filter(|other| other.chords().eq(binding.chords())),
+                );
+                winner.is_some_and(|winner| std::ptr::eq(winner, *binding))
+            })
+            .map(|binding| (binding.label(), binding.actions.as_ref()))
+            .collect()
+    }
+
     /// Returns the active bindings scoped to `scope_field == scope_value`. A
     /// binding qualifies when at least one of its predicates compares
     /// `scope_field` equal to `scope_value`.
@@ -512,7 +873,7 @@ impl Keymap {
                 .iter()
                 .any(|p| predicate_eq_matches(p, scope_field, scope_value));
             if in_scope {
-                results.push((binding.key.display_label(), binding.actions.as_ref()));
+                results.push((binding.label(), binding.actions.as_ref()));
             }
         }
         results
@@ -562,6 +923,84 @@ fn collect_mode_targets(predicate: &Predicate, out: &mut HashSet<String>) {
     }
 }
 
+/// Whether every state satisfying all of `scope` satisfies all of `other`,
+/// decided per predicate by [`predicate_implies`].
+fn scope_implies(scope: &[Predicate], other: &[Predicate]) -> bool {
+    other
+        .iter()
+        .all(|want| scope.iter().any(|have| predicate_implies(have, want)))
+}
+
+/// Whether `have` holding guarantees `want` holds. Structural only: equal
+/// predicates, `And`/`Or` on either side, and `field == a` implying
+/// `field != b` for another value `b`.
+fn predicate_implies(have: &Predicate, want: &Predicate) -> bool {
+    // Predicates compare by their printed form, since parsed ones carry the
+    // source spans they were read from.
+    if have.to_string() == want.to_string() {
+        return true;
+    }
+    if let Predicate::And(l, r) = want {
+        return predicate_implies(have, &l.node) && predicate_implies(have, &r.node);
+    }
+    if let Predicate::Or(l, r) = have {
+        return predicate_implies(&l.node, want) && predicate_implies(&r.node, want);
+    }
+    if let Predicate::Or(l, r) = want
+        && (predicate_implies(have, &l.node) || predicate_implies(have, &r.node))
+    {
+        return true;
+    }
+    match (have, want) {
+        (Predicate::And(l, r), _) => {
+            predicate_implies(&l.node, want) || predicate_implies(&r.node, want)
+        },
+        (Predicate::Eq(field, value), Predicate::NotEq(other_field, other_value)) => {
+            let (value, other_value) = (value_str(&value.node), value_str(&other_value.node));
+            field.node == other_field.node
+                && value.is_some()
+                && other_value.is_some()
+                && value != other_value
+        },
+        _ => false,
+    }
+}
+
+/// The modes a binding with `predicates` is confined to by `mode ==`
+/// predicates, or [`None`] when it may hold in any mode.
+fn scope_modes(predicates: &[Predicate]) -> Option<HashSet<String>> {
+    predicates
+        .iter()
+        .map(predicate_modes)
+        .fold(None, intersect_modes)
+}
+
+fn predicate_modes(predicate: &Predicate) -> Option<HashSet<String>> {
+    match predicate {
+        Predicate::Eq(field, value) if field.node == "mode" => {
+            value_str(&value.node).map(|mode| HashSet::from([mode.to_string()]))
+        },
+        Predicate::And(l, r) => intersect_modes(predicate_modes(&l.node), predicate_modes(&r.node)),
+        Predicate::Or(l, r) => {
+            let mut modes = predicate_modes(&l.node)?;
+            modes.extend(predicate_modes(&r.node)?);
+            Some(modes)
+        },
+        _ => None,
+    }
+}
+
+fn intersect_modes(
+    a: Option<HashSet<String>>,
+    b: Option<HashSet<String>>,
+) -> Option<HashSet<String>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.intersection(&b).cloned().collect()),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
 /// Collect every state field name `predicate` references, walking through
 /// `And`/`Or`/`Not`.
 ///
@@ -587,11 +1026,11 @@ pub(crate) fn collect_predicate_fields(predicate: &Predicate, out: &mut Vec<Stri
 
 fn collect_functions(
     stmts: &[stoat_config::Spanned<Statement>],
-    functions: &mut HashMap<String, Vec<stoat_config::Spanned<Statement>>>,
+    functions: &mut HashMap<String, FnDecl>,
 ) {
     for stmt in stmts {
         if let Statement::FnDecl(decl) = &stmt.node {
-            functions.insert(decl.name.node.clone(), decl.body.clone());
+            functions.insert(decl.name.node.clone(), decl.clone());
         }
     }
 }
@@ -599,33 +1038,145 @@ fn collect_functions(
 fn compile_statements(
     stmts: &[stoat_config::Spanned<Statement>],
     parent_predicates: &[Predicate],
-    functions: &HashMap<String, Vec<stoat_config::Spanned<Statement>>>,
+    functions: &HashMap<String, FnDecl>,
+    block: usize,
     out: &mut Vec<CompiledBinding>,
 ) {
     for stmt in stmts {
         match &stmt.node {
             Statement::Binding(binding) => {
-                compile_binding(binding, parent_predicates, out);
+                compile_binding(binding, parent_predicates, block, out);
             },
-            Statement::PredicateBlock(block) => {
+            Statement::Unbind(unbind) => unbind_chords(unbind, parent_predicates, out),
+            Statement::PredicateBlock(predicate_block) => {
                 let mut preds = parent_predicates.to_vec();
-                preds.push(block.predicate.node.clone());
-                compile_statements(&block.body, &preds, functions, out);
+                preds.push(predicate_block.predicate.node.clone());
+                compile_statements(&predicate_block.body, &preds, functions, block, out);
             },
-            Statement::FnCall(name) => {
-                if let Some(body) = functions.get(&name.node) {
-                    compile_statements(body, parent_predicates, functions, out);
+            Statement::FnCall(call) => {
+                let Some(decl) = functions.get(&call.name.node) else {
+                    continue;
+                };
+                if decl.params.len() != call.args.len() {
+                    continue;
                 }
+                if decl.params.is_empty() {
+                    compile_statements(&decl.body, parent_predicates, functions, block, out);
+                    continue;
+                }
+                let substitutions: HashMap<&str, &[Key]> = decl
+                    .params
+                    .iter()
+                    .zip(&call.args)
+                    .map(|(param, arg)| (param.node.as_str(), arg.node.keys.as_slice()))
+                    .collect();
+                let mut body = decl.body.clone();
+                substitute_keys(&mut body, &substitutions);
+                compile_statements(&body, parent_predicates, functions, block, out);
             },
-            Statement::FnDecl(_) | Statement::Setting(_) | Statement::Let(_) => {},
+            Statement::FnDecl(_)
+            | Statement::Setting(_)
+            | Statement::Let(_)
+            | Statement::Alias(_)
+            | Statement::Command(_) => {},
         }
     }
 }
 
-fn compile_binding(binding: &Binding, predicates: &[Predicate], out: &mut Vec<CompiledBinding>) {
+/// Drop the bindings compiled so far on `unbind`'s chords that sit inside
+/// every predicate enclosing it. A top-level `unbind h;` clears `h`
+/// everywhere, and `mode == normal { unbind h; }` clears only the `h`
+/// bindings scoped to normal mode. Bindings after the `unbind` are kept.
+fn unbind_chords(unbind: &Unbind, predicates: &[Predicate], out: &mut Vec<CompiledBinding>) {
+    let Some(chords) = std::iter::once(&unbind.key)
+        .chain(&unbind.then)
+        .map(|chord| CompiledKey::from_key_part(&chord.node))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return;
+    };
+    // Predicates compare by their printed form, since parsed ones carry the
+    // source spans they were read from.
+    let scope: Vec<String> = predicates.iter().map(ToString::to_string).collect();
+    out.retain(|binding| {
+        let in_scope = scope.iter().all(|outer| {
+            binding
+                .predicates
+                .iter()
+                .any(|predicate| predicate.to_string() == *outer)
+        });
+        !(in_scope && binding.chords().eq(&chords))
+    });
+}
+
+/// Replace every parameter key in `stmts` with the chord passed for it, so
+/// `mod-h` in `fn motions(mod)` becomes `Ctrl-h` for `motions(Ctrl)`.
+fn substitute_keys(
+    stmts: &mut [stoat_config::Spanned<Statement>],
+    substitutions: &HashMap<&str, &[Key]>,
+) {
+    let substitute = |part: &mut KeyPart| {
+        part.keys = part
+            .keys
+            .iter()
+            .flat_map(|key| {
+                let name = match key {
+                    Key::Char(ch) => ch.to_string(),
+                    Key::Named(name) => name.clone(),
+                };
+                match substitutions.get(name.as_str()) {
+                    Some(keys) => keys.to_vec(),
+                    None => vec![key.clone()],
+                }
+            })
+            .collect();
+    };
+    for stmt in stmts {
+        match &mut stmt.node {
+            Statement::Binding(binding) => {
+                substitute(&mut binding.key.node);
+                for chord in &mut binding.then {
+                    substitute(&mut chord.node);
+                }
+            },
+            Statement::Unbind(unbind) => {
+                substitute(&mut unbind.key.node);
+                for chord in &mut unbind.then {
+                    substitute(&mut chord.node);
+                }
+            },
+            Statement::FnCall(call) => {
+                for arg in &mut call.args {
+                    substitute(&mut arg.node);
+                }
+            },
+            Statement::PredicateBlock(block) => substitute_keys(&mut block.body, substitutions),
+            Statement::FnDecl(_)
+            | Statement::Setting(_)
+            | Statement::Let(_)
+            | Statement::Alias(_)
+            | Statement::Command(_) => {},
+        }
+    }
+}
+
+fn compile_binding(
+    binding: &Binding,
+    predicates: &[Predicate],
+    block: usize,
+    out: &mut Vec<CompiledBinding>,
+) {
     let Some(key) = CompiledKey::from_key_part(&binding.key.node) else {
         return;
     };
+    let Some(then) = binding
+        .then
+        .iter()
+        .map(|chord| CompiledKey::from_key_part(&chord.node))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return;
+    };
 
     let actions = match &binding.action.node {
         ActionExpr::Single(action) => vec![resolve_config_action(action)],
@@ -637,8 +1188,10 @@ fn compile_binding(binding: &Binding, predicates: &[Predicate], out: &mut Vec<Co
 
     out.push(CompiledBinding {
         key,
+        then,
         predicates: predicates.to_vec(),
         actions: actions.into(),
+        block,
     });
 }
 
@@ -668,7 +1221,9 @@ pub(crate) fn resolve_config_action(action: &stoat_config::Action) -> ResolvedAc
 mod tests {
     use super::*;
     use std::collections::BTreeMap;
-    use stoat_config::{LineNumbers, MouseCapturePolicy, Settings, TabBarMode, WrapMode};
+    use stoat_config::{
+        LineNumbers, MouseCapturePolicy, Settings, StateFormat, TabBarMode, WrapBreak, WrapMode,
+    };
 
     struct TestState {
         values: HashMap<String, StateValue>,
@@ -991,6 +1546,36 @@ mod tests {
         assert_eq!(keymap.bindings[0].predicates.len(), 1);
     }
 
+    #[test]
+    fn compile_fn_substitutes_parameter_keys() {
+        let config = parse_config(
+            r#"on key {
+                fn motions(mod) {
+                    mod-h -> MoveLeft();
+                }
+                fn both(mod) {
+                    motions(mod);
+                }
+                both(Ctrl);
+                motions(Ctrl-Alt);
+            }"#,
+        );
+        let keymap = Keymap::compile(&config);
+        let state = TestState::new();
+
+        assert_eq!(keymap.bindings.len(), 2);
+        for modifiers in [
+            KeyModifiers::CONTROL,
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        ] {
+            let event = key_event(KeyCode::Char('h'), modifiers);
+            let actions = keymap.lookup(&state, &event).expect("should match");
+            assert_eq!(actions[0].name, "MoveLeft");
+        }
+        let event = key_event(KeyCode::Char('h'), KeyModifiers::NONE);
+        assert!(keymap.lookup(&state, &event).is_none());
+    }
+
     #[test]
     fn lookup_prefers_more_specific() {
         let config = parse_config(
@@ -1010,6 +1595,53 @@ mod tests {
         assert_eq!(actions[0].name, "MoveRight");
     }
 
+    #[test]
+    fn lookup_sequence_waits_for_the_next_chord() {
+        let config = parse_config(
+            r#"on key {
+                g -> MoveLeft();
+                g g -> MoveToFileStart();
+                Space f f -> OpenFileFinder();
+            }"#,
+        );
+        let keymap = Keymap::compile(&config);
+        let state = TestState::new();
+        let g = key_event(KeyCode::Char('g'), KeyModifiers::NONE);
+        let f = key_event(KeyCode::Char('f'), KeyModifiers::NONE);
+        let space = key_event(KeyCode::Char(' '), KeyModifiers::NONE);
+
+        let SequenceMatch::Pending {
+            exact: Some((actions, _)),
+        } = keymap.lookup_sequence(&state, &[g])
+        else {
+            panic!("`g` should wait with its own binding as the fallback");
+        };
+        assert_eq!(actions[0].name, "MoveLeft");
+        let SequenceMatch::Matched(actions, _) = keymap.lookup_sequence(&state, &[g, g]) else {
+            panic!("`g g` should complete");
+        };
+        assert_eq!(actions[0].name, "MoveToFileStart");
+
+        assert!(matches!(
+            keymap.lookup_sequence(&state, &[space, f]),
+            SequenceMatch::Pending { exact: None }
+        ));
+        let continuations = keymap.sequence_continuations(&state, &[space]);
+        assert_eq!(continuations.len(), 1);
+        assert_eq!(continuations[0].0, "f f");
+        assert!(matches!(
+            keymap.lookup_sequence(&state, &[space, g]),
+            SequenceMatch::Unbound
+        ));
+
+        // Single-key lookup ignores sequences.
+        assert_eq!(
+            keymap.lookup(&state, &g).expect("match")[0].name,
+            "MoveLeft"
+        );
+        assert!(keymap.lookup(&state, &space).is_none());
+    }
+
     /// Resolve the digit press against `keymap` and return the resulting
     /// [`stoat_action::FocusPane`]'s index, or `None` if nothing bound or the
     /// action dropped.
@@ -1212,6 +1844,70 @@ mod tests {
         assert_eq!(actions[0].name, "MoveLeft");
     }
 
+    #[test]
+    fn lookup_ties_prefer_a_later_block() {
+        let config = parse_config(
+            r#"on key {
+                mode == "normal" { q -> MoveLeft(); }
+            }
+            on key {
+                mode != "insert" { q -> MoveRight(); }
+                q -> Quit();
+            }"#,
+        );
+        let keymap = Keymap::compile(&config);
+        let state = TestState::new().set("mode", StateValue::String("normal".into()));
+        let event = key_event(KeyCode::Char('q'), KeyModifiers::NONE);
+
+        // Equal specificity, so the second block shadows the first. The
+        // unscoped `q` stays less specific than both.
+        let actions = keymap.lookup(&state, &event).expect("should match");
+        assert_eq!(actions[0].name, "MoveRight");
+        let explained = keymap.explain(&state, &[event]);
+        let won: Vec<_> = explained
+            .iter()
+            .map(|c| (c.actions[0].name.as_str(), c.won))
+            .collect();
+        assert_eq!(
+            won,
+            [("MoveLeft", false), ("MoveRight", true), ("Quit", false)]
+        );
+    }
+
+    #[test]
+    fn unbind_drops_earlier_bindings_in_scope() {
+        let config = parse_config(
+            r#"on key {
+                q -> Quit();
+                mode == "normal" { q -> MoveLeft(); }
+                mode == "insert" { q -> MoveRight(); }
+                g g -> MoveToFileStart();
+            }
+            on key {
+                mode == "normal" { unbind q; }
+                unbind g g;
+                g g -> MoveToFileEnd();
+            }"#,
+        );
+        let keymap = Keymap::compile(&config);
+        let normal = TestState::new().set("mode", StateValue::String("normal".into()));
+        let insert = TestState::new().set("mode", StateValue::String("insert".into()));
+        let q = key_event(KeyCode::Char('q'), KeyModifiers::NONE);
+        let g = key_event(KeyCode::Char('g'), KeyModifiers::NONE);
+
+        // Only the normal-mode `q` is gone, so normal falls back to the
+        // unscoped one and insert keeps its own.
+        assert_eq!(keymap.lookup(&normal, &q).expect("match")[0].name, "Quit");
+        assert_eq!(
+            keymap.lookup(&insert, &q).expect("match")[0].name,
+            "MoveRight"
+        );
+        let SequenceMatch::Matched(actions, _) = keymap.lookup_sequence(&normal, &[g, g]) else {
+            panic!("`g g` should be rebound");
+        };
+        assert_eq!(actions[0].name, "MoveToFileEnd");
+    }
+
     #[test]
     fn lookup_no_match() {
         let config = parse_config(r#"on key { mode == "normal" { q -> Quit(); } }"#);
@@ -1254,6 +1950,58 @@ mod tests {
         assert!(warnings.is_empty(), "shipped config warnings: {warnings:?}");
     }
 
+    #[test]
+    fn check_reports_duplicate_and_shadowed_bindings() {
+        let config = parse_config(
+            r#"on key {
+                mode == "normal" { q -> MoveLeft(); q -> MoveRight(); }
+                mode == "normal" && visible { w -> MoveLeft(); }
+                mode == "insert" { w -> MoveRight(); }
+            }
+            on key {
+                mode == "normal" { e -> MoveLeft(); }
+            }
+            on key {
+                mode == "normal" || mode == "select" { w -> Quit(); }
+                mode != "insert" { e -> Quit(); }
+            }"#,
+        );
+        let issues = Keymap::compile(&config).check();
+
+        let [KeymapIssue::Duplicate { kept, dropped }, KeymapIssue::Shadowed { binding, by }] =
+            issues.as_slice()
+        else {
+            panic!("issues: {issues:?}");
+        };
+        assert_eq!(kept.actions, ["MoveLeft"]);
+        assert_eq!(dropped.actions, ["MoveRight"]);
+        assert_eq!(binding.label, "e");
+        assert_eq!(binding.scope, "mode == \"normal\"");
+        assert_eq!(by.actions, ["Quit"]);
+        assert_eq!(by.block, 2);
+    }
+
+    #[test]
+    fn check_reports_modes_no_set_mode_chain_reaches() {
+        let config = parse_config(
+            r#"on key {
+                mode == "normal" { g -> SetMode(goto); }
+                mode == "goto" { z -> SetMode(zoom); }
+                mode == "zoom" { Escape -> SetMode(normal); }
+                mode == "orphan" { o -> SetMode(orphan_child); }
+                mode == "orphan_child" { Escape -> SetMode(normal); }
+            }"#,
+        );
+        let issues = Keymap::compile(&config).check();
+        assert_eq!(
+            issues,
+            [
+                KeymapIssue::UnreachableMode("orphan".into()),
+                KeymapIssue::UnreachableMode("orphan_child".into()),
+            ]
+        );
+    }
+
     #[test]
     fn question_mark_rebinds_across_modes() {
         let config = parse_config(crate::app::DEFAULT_KEYMAP);
@@ -1637,25 +2385,44 @@ mod tests {
                 mouse_capture: Some(MouseCapturePolicy::Auto),
                 scrolloff: Some(3),
                 editor_line_numbers: Some(LineNumbers::Relative),
+                editor_cursor_line: None,
                 editor_minimap: None,
                 editor_wrap: Some(WrapMode::EditorWidth),
                 editor_wrap_column: None,
+                editor_wrap_break: Some(WrapBreak::Word),
+                editor_wrap_indent: None,
                 ui_tab_bar: Some(TabBarMode::Auto),
                 ui_inactive_dim: None,
+                ui_which_key_delay: None,
+                editor_cursor_blink: None,
+                editor_cursor_animation: None,
                 highlight_retention: Some(64),
                 terminal_shell: None,
                 terminal_args: None,
                 direnv_load: Some(true),
                 direnv_reload_on_cd: Some(true),
                 direnv_unset_on_exit: Some(false),
+                spell_enabled: Some(false),
+                spell_dictionary: None,
+                clipboard_mirror: Some(false),
+                state_format: Some(StateFormat::Ron),
+                test_lenses: Some(false),
+                color_swatches: Some(true),
+                editor_breadcrumbs: Some(false),
+                editor_highlight_occurrences: Some(false),
                 mode_badges: BTreeMap::new(),
+                cursor_shapes: BTreeMap::new(),
                 lsp_servers: BTreeMap::new(),
                 lsp_server_lists: BTreeMap::new(),
                 lsp_commands: BTreeMap::new(),
                 lsp_only: BTreeMap::new(),
                 lsp_except: BTreeMap::new(),
                 finder_scopes: BTreeMap::new(),
+                task_commands: BTreeMap::new(),
+                task_matchers: BTreeMap::new(),
                 finder_default_scope: Some("all".to_string()),
+                palette_aliases: BTreeMap::new(),
+                commands: BTreeMap::new(),
             },
         );
     }
@@ -1900,6 +2667,34 @@ mod tests {
         assert_eq!(keymap.active_bindings(&with).len(), 2);
     }
 
+    #[test]
+    fn winning_bindings_drops_outranked_keys() {
+        let config = parse_config(
+            r#"on key {
+                q -> Quit();
+                g g -> MoveLeft();
+                mode == "normal" { q -> MoveRight(); }
+            }"#,
+        );
+        let keymap = Keymap::compile(&config);
+        let state = TestState::new().set("mode", StateValue::String("normal".into()));
+
+        let names: Vec<_> = keymap
+            .winning_bindings(&state)
+            .into_iter()
+            .map(|(label, actions)| (label, actions[0].name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("g g".to_string(), "MoveLeft"),
+                ("q".to_string(), "MoveRight")
+            ],
+            "the scoped q outranks the bare one"
+        );
+        assert_eq!(keymap.active_bindings(&state).len(), 3);
+    }
+
     #[test]
     fn scoped_bindings_keeps_only_scoped() {
         let config = parse_config(
@@ -295,6 +295,8 @@ fn pane_predicate(ws: &Workspace) -> Option<&'static str> {
         View::Run(_) => "run",
         View::Agent(_) => "agent",
         View::Terminal(_) => "terminal",
+        View::Hex(_) => "hex",
+        View::RegexPlayground(_) => "regex_playground",
     })
 }
 
@@ -362,24 +364,24 @@ pub(crate) fn modal_predicate(stoat: &Stoat) -> Option<&'static str> {
         Some("run")
     } else if stoat.quit_all_confirm.is_some() {
         Some("quit_confirm")
+    } else if !stoat.agent_permissions.is_empty() {
+        Some("agent_permission")
     } else if stoat.workspace_picker.is_some() {
         Some("workspace_picker")
     } else if stoat.jumplist_picker.is_some() {
         Some("jumplist")
+    } else if stoat.undo_tree_picker.is_some() {
+        Some("undo_tree")
     } else if stoat.diagnostics_picker.is_some() {
         Some("diagnostics")
     } else if stoat.location_picker.is_some() {
         Some("location")
-    } else if stoat.file_finder.is_some() {
-        Some("finder")
+    } else if let Some(overlay) = crate::overlay::top(stoat) {
+        Some(overlay.predicate())
     } else if stoat.symbol_finder.is_some() {
         Some("symbols")
     } else if stoat.code_search.is_some() {
         Some("code_search")
-    } else if stoat.command_palette.is_some() {
-        Some("palette")
-    } else if stoat.help.is_some() {
-        Some("help")
     } else if stoat.rename_input.is_some() {
         Some("rename")
     } else if stoat.search_input.is_some() {
@@ -388,8 +390,14 @@ pub(crate) fn modal_predicate(stoat: &Stoat) -> Option<&'static str> {
         Some("split_selection")
     } else if stoat.filter_selections_input.is_some() {
         Some("filter_selections")
+    } else if stoat.surround_tag_input.is_some() {
+        Some("surround_tag")
     } else if stoat.shell_input.is_some() {
         Some("shell")
+    } else if stoat.color_input.is_some() {
+        Some("color")
+    } else if stoat.local_rename.is_some() {
+        Some("rename")
     } else {
         None
     }
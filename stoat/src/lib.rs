@@ -1,11 +1,18 @@
+pub(crate) mod accessibility;
 pub mod action_handlers;
 pub mod agent_ipc;
+pub(crate) mod agent_permission;
 pub mod agent_status;
 pub mod app;
 pub mod badge;
 pub mod buffer;
 mod buffer_registry;
+pub mod buffer_search;
 mod code_index;
+pub(crate) mod code_lens;
+pub mod collab;
+pub(crate) mod color_swatch;
+pub(crate) mod command_log;
 pub mod command_palette;
 mod commit_list;
 pub mod completion;
@@ -29,7 +36,9 @@ pub mod fixture;
 pub mod fuzzy;
 pub(crate) mod goto_word;
 pub mod help;
+pub mod hex_view;
 pub mod host;
+mod input_draft;
 mod input_history;
 pub mod input_parse;
 mod input_view;
@@ -37,6 +46,7 @@ mod jumplist;
 pub(crate) mod jumplist_picker;
 pub mod keymap;
 mod keymap_state;
+pub(crate) mod local_rename;
 pub(crate) mod location_picker;
 pub mod lsp;
 pub(crate) mod markdown;
@@ -44,14 +54,18 @@ pub(crate) mod merge_view;
 pub mod minimap;
 pub mod multi_buffer;
 mod nav_list;
+pub(crate) mod occurrence_highlight;
+mod overlay;
 pub mod pane;
 mod paths;
 #[cfg(feature = "perf")]
 pub mod perf;
 pub(crate) mod picker;
+pub(crate) mod project_config;
 pub(crate) mod project_env;
 pub(crate) mod quit_all_confirm;
 mod rebase;
+pub(crate) mod regex_playground;
 mod register;
 pub(crate) mod render;
 mod review;
@@ -60,12 +74,20 @@ mod review_session;
 pub mod run;
 mod selection;
 mod smooth_scroll;
+pub(crate) mod spell;
 pub(crate) mod symbol_finder;
+pub(crate) mod task;
 pub mod term_screen;
 pub mod term_session;
+pub(crate) mod test_lens;
 pub mod theme;
 mod theme_vscode;
+pub(crate) mod toast;
+mod token_cache;
 pub mod ui;
+mod undo_tree;
+pub(crate) mod undo_tree_picker;
+pub(crate) mod user_command;
 pub mod workspace;
 pub mod workspace_picker;
 
@@ -0,0 +1,230 @@
+//! Rename of a local binding without a language server.
+//!
+//! [`open`] resolves the name under the cursor through its language's locals
+//! query, as [`stoat_language::local_references_at`] does, and opens the
+//! rename prompt seeded with it. [`submit`] rewrites every reference of that
+//! binding as one undo step. A same-named binding in another scope resolves
+//! elsewhere, so it is left alone.
+
+use crate::{
+    app::{Stoat, UpdateEffect},
+    buffer::BufferId,
+    editor_state::EditorId,
+    input_view::{InputView, SubmitTarget},
+    workspace::Workspace,
+};
+use std::{cmp::Reverse, ops::Range};
+use stoat_text::{Anchor, Bias};
+
+/// The open local rename prompt.
+pub(crate) struct LocalRenameState {
+    pub(crate) input: InputView,
+    /// Editor holding the binding being renamed.
+    editor_id: EditorId,
+    /// Every reference of the binding, the definition included, anchored so
+    /// the rewrite lands on the right bytes.
+    ranges: Vec<Range<Anchor>>,
+    /// Offset of the reference under the cursor, where the prompt is placed.
+    pub(crate) anchor_offset: usize,
+}
+
+/// Open the rename prompt on the local binding under the focused cursor.
+pub(crate) fn open(stoat: &mut Stoat) -> UpdateEffect {
+    if stoat.local_rename.is_some() {
+        return UpdateEffect::None;
+    }
+    let Some((editor_id, buffer_id)) = stoat.focused_editor_ids() else {
+        return UpdateEffect::None;
+    };
+    let executor = stoat.executor.clone();
+    let ws = stoat.active_workspace_mut();
+    let Some((seed, ranges, anchor_offset)) = binding_at(ws, editor_id, buffer_id) else {
+        stoat.set_status("no local binding under the cursor");
+        return UpdateEffect::Redraw;
+    };
+    let input = InputView::create(ws, executor, SubmitTarget::LocalRename, &seed, "insert", 1);
+    stoat.local_rename = Some(LocalRenameState {
+        input,
+        editor_id,
+        ranges,
+        anchor_offset,
+    });
+    UpdateEffect::Redraw
+}
+
+/// The name under `editor_id`'s cursor, the anchored ranges of every
+/// reference to its binding, and the offset of the one under the cursor.
+/// `None` when the buffer's tree is stale or the name does not resolve.
+fn binding_at(
+    ws: &mut Workspace,
+    editor_id: EditorId,
+    buffer_id: BufferId,
+) -> Option<(String, Vec<Range<Anchor>>, usize)> {
+    let editor = ws.editors.get_mut(editor_id)?;
+    if editor.review_view.is_some() {
+        return None;
+    }
+    let snapshot = editor.display_map.snapshot();
+    let buf_snap = snapshot.buffer_snapshot();
+    let rope = buf_snap.rope();
+    let sel = editor.selections.newest_anchor();
+    let cursor = stoat_text::cursor_offset(
+        rope,
+        buf_snap.resolve_anchor(&sel.tail()),
+        buf_snap.resolve_anchor(&sel.head()),
+    );
+    let syntax = ws.buffers.syntax_snapshot(buffer_id)?;
+    if syntax.parsed_version != buf_snap.version() {
+        return None;
+    }
+    let layer = syntax.deepest_layer_at(cursor)?;
+    let query = layer.language.locals_query.as_ref()?;
+    let references =
+        stoat_language::local_references_at(query, layer.tree.root_node(), rope, cursor);
+    let under = references
+        .iter()
+        .find(|r| r.contains(&cursor))
+        .or(references.first())?;
+    let seed: String = rope.chunks_in_range(under.clone()).collect();
+    let ranges = references
+        .iter()
+        .map(|r| buf_snap.anchor_at(r.start, Bias::Left)..buf_snap.anchor_at(r.end, Bias::Right))
+        .collect();
+    Some((seed, ranges, under.start))
+}
+
+/// Rewrite every reference with the typed name in one undo group. An empty
+/// entry closes the prompt without editing; one that is not a name keeps it
+/// open and reports. Returns `true` when the prompt was open.
+pub(crate) fn submit(stoat: &mut Stoat) -> bool {
+    let Some(state) = stoat.local_rename.take() else {
+        return false;
+    };
+    let typed = state.input.text(stoat.active_workspace());
+    let name = typed.trim();
+    if !name.is_empty() && !is_name(name) {
+        stoat.set_status(format!("not a name: {name}"));
+        stoat.local_rename = Some(state);
+        return true;
+    }
+    let ws = stoat.active_workspace_mut();
+    state.input.dispose(ws);
+    if name.is_empty() {
+        return true;
+    }
+    let Some(editor) = ws.editors.get_mut(state.editor_id) else {
+        return true;
+    };
+    let selections = editor.selections.all_anchors().to_vec();
+    let mut ranges: Vec<Range<usize>> = {
+        let snapshot = editor.display_map.snapshot();
+        let buf_snap = snapshot.buffer_snapshot();
+        state
+            .ranges
+            .iter()
+            .map(|r| buf_snap.resolve_anchor(&r.start)..buf_snap.resolve_anchor(&r.end))
+            .collect()
+    };
+    // Right to left, so each edit leaves the offsets before it intact.
+    ranges.sort_by_key(|r| Reverse(r.start));
+    if let Some(buffer) = ws.buffers.get(editor.buffer_id) {
+        let mut guard = buffer.write().expect("buffer poisoned");
+        guard.begin_group(selections.clone());
+        for range in ranges {
+            guard.edit(range, name);
+        }
+        guard.seal_group(selections);
+    }
+    true
+}
+
+/// Close the rename prompt without editing. Returns `true` when it was open.
+pub(crate) fn cancel(stoat: &mut Stoat) -> bool {
+    let Some(state) = stoat.local_rename.take() else {
+        return false;
+    };
+    state.input.dispose(stoat.active_workspace_mut());
+    true
+}
+
+/// Whether `text` is a plain identifier: word characters, not led by a
+/// digit.
+fn is_name(text: &str) -> bool {
+    text.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !text.starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        action_handlers::dispatch,
+        test_harness::{keys, TestHarness},
+    };
+    use crossterm::event::{Event, KeyCode};
+
+    const SOURCE: &str = "fn f() {\n    let y = 1;\n    g(y);\n    let y = y;\n    g(y);\n}\n";
+
+    fn open_rust(h: &mut TestHarness) {
+        let path = h.write_file("rename.rs", SOURCE);
+        h.open_file(&path);
+        for _ in 0..2 {
+            h.stoat.drive_background();
+            let _ = h.stoat.render();
+            h.settle();
+        }
+    }
+
+    fn text(h: &mut TestHarness) -> String {
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        let snapshot = editor.display_map.snapshot();
+        snapshot.buffer_snapshot().rope().to_string()
+    }
+
+    #[test]
+    fn renames_one_binding_in_one_undo_step() {
+        let mut h = TestHarness::with_size(60, 10);
+        open_rust(&mut h);
+        h.stoat
+            .collapse_focused_cursor_to(SOURCE.find("y);").unwrap());
+        dispatch(&mut h.stoat, &stoat_action::RenameLocal);
+        let state = h.stoat.local_rename.as_ref().expect("prompt open");
+        assert_eq!(state.input.text(h.stoat.active_workspace()), "y");
+
+        h.stoat.update(Event::Key(keys::key(KeyCode::Backspace)));
+        h.type_text("first");
+        h.stoat.update(Event::Key(keys::key(KeyCode::Enter)));
+        assert!(h.stoat.local_rename.is_none());
+        assert_eq!(
+            text(&mut h),
+            "fn f() {\n    let first = 1;\n    g(first);\n    let y = first;\n    g(y);\n}\n"
+        );
+
+        dispatch(&mut h.stoat, &stoat_action::Undo);
+        assert_eq!(text(&mut h), SOURCE);
+    }
+
+    #[test]
+    fn rejects_a_non_name_and_reports_unbound() {
+        let mut h = TestHarness::with_size(60, 10);
+        open_rust(&mut h);
+        h.stoat
+            .collapse_focused_cursor_to(SOURCE.find("y = 1").unwrap());
+        dispatch(&mut h.stoat, &stoat_action::RenameLocal);
+        h.type_text(" z");
+        h.stoat.update(Event::Key(keys::key(KeyCode::Enter)));
+        assert!(h.stoat.local_rename.is_some());
+        assert_eq!(h.stoat.pending_message.as_deref(), Some("not a name: y z"));
+        h.stoat.update(Event::Key(keys::key(KeyCode::Esc)));
+        assert!(h.stoat.local_rename.is_none());
+        assert_eq!(text(&mut h), SOURCE);
+
+        h.stoat
+            .collapse_focused_cursor_to(SOURCE.find("g(").unwrap());
+        dispatch(&mut h.stoat, &stoat_action::RenameLocal);
+        assert!(h.stoat.local_rename.is_none());
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("no local binding under the cursor")
+        );
+    }
+}
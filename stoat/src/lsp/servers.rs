@@ -85,13 +85,11 @@ fn stcfg_host() -> Arc<dyn LspHost> {
 /// The LSP language name for a file `extension` that has no tree-sitter grammar.
 ///
 /// A grammar-backed buffer resolves its language through the language registry.
-/// A `.stcfg` file has no grammar, so it has no registry language, yet still
-/// needs an LSP identity to route to the in-process stcfg server.
+/// A `.stcfg` or python file has no bundled grammar, so it has no registry
+/// language, yet still needs an LSP identity to route to its server. Both
+/// come from the shared [`stoat_language::LANGUAGE_EXTENSIONS`] table.
 pub(crate) fn lsp_language_for_extension(extension: &str) -> Option<&'static str> {
-    match extension {
-        "stcfg" => Some("stcfg"),
-        _ => None,
-    }
+    stoat_language::language_name_for_extension(extension)
 }
 
 /// Resolve the ordered language servers for `language`.
@@ -307,7 +305,8 @@ mod tests {
     #[test]
     fn stcfg_extension_resolves_to_the_stcfg_language() {
         assert_eq!(lsp_language_for_extension("stcfg"), Some("stcfg"));
-        assert_eq!(lsp_language_for_extension("rs"), None);
+        assert_eq!(lsp_language_for_extension("py"), Some("python"));
+        assert_eq!(lsp_language_for_extension("txt"), None);
     }
 
     #[test]
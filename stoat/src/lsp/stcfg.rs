@@ -6,6 +6,11 @@
 //! PATH resolution, and candidates that cannot drift from the running binary's
 //! settings table.
 //!
+//! Diagnostics are pushed, not only pulled: every open, edit and save
+//! re-parses and re-checks the document and queues a
+//! [`LspNotification::Diagnostics`], which the editor drains into its
+//! squiggles and problems list like any server's `publishDiagnostics`.
+//!
 //! Every recognized setting comes from [`stoat_config::settings_schema`], the
 //! single source of truth that mirrors `Settings::apply`. The server advertises
 //! UTF-8 position encoding so line/column offsets are plain byte counts.
@@ -43,7 +48,11 @@ use std::{
     io,
     sync::{Arc, LazyLock, Mutex},
 };
-use stoat_config::{EventType, PathSeg, SettingDef, Span, Spanned, Statement, ValueShape};
+use stoat_config::{PathSeg, SettingDef, Span, ValueShape};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    Mutex as TokioMutex,
+};
 
 /// In-process settings language server backing `.stcfg` buffers.
 ///
@@ -53,14 +62,28 @@ use stoat_config::{EventType, PathSeg, SettingDef, Span, Spanned, Statement, Val
 /// on `did_close`.
 pub struct StcfgLsp {
     docs: Mutex<HashMap<Uri, String>>,
+    notif_tx: UnboundedSender<LspNotification>,
+    notif_rx: TokioMutex<UnboundedReceiver<LspNotification>>,
 }
 
 impl StcfgLsp {
     pub fn new() -> Self {
+        let (notif_tx, notif_rx) = unbounded_channel();
         Self {
             docs: Mutex::new(HashMap::new()),
+            notif_tx,
+            notif_rx: TokioMutex::new(notif_rx),
         }
     }
+
+    /// Queue the diagnostics of `text` as the current set for `uri`.
+    fn publish(&self, uri: Uri, text: &str, version: Option<i32>) {
+        let _ = self.notif_tx.send(LspNotification::Diagnostics {
+            uri,
+            diagnostics: diagnose(text),
+            version,
+        });
+    }
 }
 
 impl Default for StcfgLsp {
@@ -104,6 +127,7 @@ impl LspHost for StcfgLsp {
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) -> io::Result<()> {
         let doc = params.text_document;
+        self.publish(doc.uri.clone(), &doc.text, Some(doc.version));
         self.docs
             .lock()
             .expect("stcfg docs poisoned")
@@ -112,25 +136,43 @@ impl LspHost for StcfgLsp {
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) -> io::Result<()> {
-        let uri = params.text_document.uri;
+        let doc = params.text_document;
         if let Some(change) = params.content_changes.into_iter().next_back() {
+            self.publish(doc.uri.clone(), &change.text, Some(doc.version));
             self.docs
                 .lock()
                 .expect("stcfg docs poisoned")
-                .insert(uri, change.text);
+                .insert(doc.uri, change.text);
         }
         Ok(())
     }
 
-    async fn did_save(&self, _params: DidSaveTextDocumentParams) -> io::Result<()> {
+    async fn did_save(&self, params: DidSaveTextDocumentParams) -> io::Result<()> {
+        let uri = params.text_document.uri;
+        let text = match params.text {
+            Some(text) => Some(text),
+            None => self
+                .docs
+                .lock()
+                .expect("stcfg docs poisoned")
+                .get(&uri)
+                .cloned(),
+        };
+        if let Some(text) = text {
+            self.publish(uri, &text, None);
+        }
         Ok(())
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) -> io::Result<()> {
-        self.docs
-            .lock()
-            .expect("stcfg docs poisoned")
-            .remove(&params.text_document.uri);
+        let uri = params.text_document.uri;
+        self.docs.lock().expect("stcfg docs poisoned").remove(&uri);
+        // A closed document's problems go with it.
+        let _ = self.notif_tx.send(LspNotification::Diagnostics {
+            uri,
+            diagnostics: Vec::new(),
+            version: None,
+        });
         Ok(())
     }
 
@@ -430,11 +472,11 @@ impl LspHost for StcfgLsp {
     }
 
     async fn recv_notification(&self) -> Option<LspNotification> {
-        None
+        self.notif_rx.lock().await.recv().await
     }
 
     async fn try_recv_notification(&self) -> Option<LspNotification> {
-        None
+        self.notif_rx.try_lock().ok()?.try_recv().ok()
     }
 
     async fn recv_incoming_request(&self) -> Option<IncomingRequest> {
@@ -476,11 +518,12 @@ fn complete(text: &str, offset: usize) -> Vec<CompletionItem> {
     }
 }
 
-/// Diagnose parse errors and unrecognized settings across the whole document.
+/// Diagnose parse errors and semantic problems across the whole document.
 ///
-/// Every [`stoat_config::ParseError`] becomes an error diagnostic. Each `on
-/// init` setting whose path matches no schema entry becomes an "unknown
-/// setting" warning spanning its path.
+/// Every [`stoat_config::ParseError`] becomes an error diagnostic. A document
+/// that parses is then run through [`stoat_config::check`], whose findings --
+/// unknown settings and actions, mistyped arguments, values that do not fit
+/// their setting -- become warnings, since the config still loads with them.
 fn diagnose(text: &str) -> Vec<Diagnostic> {
     let (config, errors) = stoat_config::parse(text);
 
@@ -499,24 +542,13 @@ fn diagnose(text: &str) -> Vec<Diagnostic> {
         return diagnostics;
     };
 
-    for block in &config.blocks {
-        if block.node.event != EventType::Init {
-            continue;
-        }
-        for statement in &block.node.statements {
-            let Statement::Setting(setting) = &statement.node else {
-                continue;
-            };
-            let segments: Vec<&str> = setting.path.iter().map(|seg| seg.node.as_str()).collect();
-            if def_for_path(&segments).is_none() {
-                diagnostics.push(diagnostic(
-                    range_from_span(text, path_span(&setting.path)),
-                    DiagnosticSeverity::WARNING,
-                    format!("unknown setting `{}`", segments.join(".")),
-                ));
-            }
-        }
-    }
+    diagnostics.extend(stoat_config::check(&config).into_iter().map(|warning| {
+        diagnostic(
+            range_from_span(text, warning.span),
+            DiagnosticSeverity::WARNING,
+            warning.message,
+        )
+    }));
 
     diagnostics
 }
@@ -640,13 +672,6 @@ fn prefix_segments_match(segments: &[&str], def: &SettingDef) -> bool {
         })
 }
 
-/// Byte span covering a setting's whole dotted path.
-fn path_span(path: &[Spanned<String>]) -> Span {
-    let start = path.first().map(|seg| seg.span.start).unwrap_or(0);
-    let end = path.last().map(|seg| seg.span.end).unwrap_or(0);
-    start..end
-}
-
 /// Whether the innermost block enclosing `offset` is an `on init` block.
 ///
 /// This is a best-effort lexical scan. It tracks brace nesting and the header
@@ -808,6 +833,36 @@ mod tests {
         assert_eq!(warning.range.end, offset_to_position(text, path_end));
     }
 
+    #[test]
+    fn alias_to_unknown_action_warns_at_action_span() {
+        let text = r#"on init { alias "format document" -> Formatt; alias "fmt" -> Format; }"#;
+        let diagnostics = diagnose(text);
+        assert_eq!(diagnostics.len(), 1, "the alias to a real action is clean");
+
+        let warning = &diagnostics[0];
+        assert_eq!(warning.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(warning.message, "unknown action `Formatt`");
+        let start = text.find("Formatt").expect("action present");
+        assert_eq!(warning.range.start, offset_to_position(text, start));
+        assert_eq!(
+            warning.range.end,
+            offset_to_position(text, start + "Formatt".len())
+        );
+    }
+
+    #[test]
+    fn mistyped_binding_argument_warns_at_its_value() {
+        let text = r#"on key { g -> GotoTab("two"); }"#;
+        let diagnostics = diagnose(text);
+        assert_eq!(diagnostics.len(), 1);
+
+        let warning = &diagnostics[0];
+        assert_eq!(warning.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(warning.message, "argument `index` expects a number");
+        let start = text.find("\"two\"").expect("argument present");
+        assert_eq!(warning.range.start, offset_to_position(text, start));
+    }
+
     #[test]
     fn known_settings_produce_no_diagnostics() {
         let text = "on init {\n  format_on_save = true;\n  editor.line_numbers = relative;\n}";
@@ -925,6 +980,41 @@ mod tests {
         });
     }
 
+    #[test]
+    fn open_and_close_push_diagnostics() {
+        TestScheduler::new().block_on(async {
+            let server = StcfgLsp::new();
+            server
+                .did_open(open_params(uri(), "on key { q -> Quitt(); }"))
+                .await
+                .expect("did_open");
+            let Some(LspNotification::Diagnostics {
+                diagnostics,
+                version,
+                ..
+            }) = server.try_recv_notification().await
+            else {
+                panic!("expected diagnostics after open");
+            };
+            assert_eq!(version, Some(1));
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].message, "unknown action `Quitt`");
+
+            server
+                .did_close(DidCloseTextDocumentParams {
+                    text_document: TextDocumentIdentifier { uri: uri() },
+                })
+                .await
+                .expect("did_close");
+            let Some(LspNotification::Diagnostics { diagnostics, .. }) =
+                server.try_recv_notification().await
+            else {
+                panic!("expected diagnostics after close");
+            };
+            assert!(diagnostics.is_empty());
+        });
+    }
+
     #[test]
     fn completion_absent_for_unopened_document() {
         TestScheduler::new().block_on(async {
@@ -0,0 +1,248 @@
+//! Highlights of the identifier under the cursor, without a language server.
+//!
+//! With `editor.highlight_occurrences` on, [`refresh`] paints every visible
+//! occurrence of the identifier under the focused normal-mode cursor on the
+//! [`HighlightLayer::SymbolOccurrence`] layer. Where the language ships a
+//! locals query, occurrences are the names resolving to the same binding, so a
+//! shadowed `x` is not lit by another; otherwise they are identifiers of the
+//! same text. A buffer whose server answers document highlights is left to
+//! that machinery instead.
+
+use crate::{
+    app::Stoat,
+    display_map::{syntax_theme, HighlightKey, HighlightLayer},
+    host::LanguageServerFeature,
+    theme::scope,
+};
+use std::ops::Range;
+use stoat_language::{Node, SyntaxSnapshot};
+use stoat_text::{Bias, Rope};
+
+const KEY: HighlightKey = HighlightKey::layer(HighlightLayer::SymbolOccurrence);
+
+/// Largest buffer resolved through its locals query. The query runs over the
+/// whole file on each cursor move, so bigger files fall back to text matches.
+const LOCALS_MAX_BYTES: usize = 256 * 1024;
+
+/// Recompute the occurrence highlights of the focused editor when its buffer,
+/// cursor or scroll moved, and clear them from every other editor.
+pub(crate) fn refresh(stoat: &mut Stoat) {
+    let target = (stoat.settings.editor_highlight_occurrences == Some(true)
+        && stoat.focused_mode() == "normal")
+        .then(|| stoat.focused_editor_ids())
+        .flatten()
+        .filter(|&(_, buffer_id)| {
+            stoat
+                .feature_hosts(buffer_id, LanguageServerFeature::DocumentHighlight)
+                .is_empty()
+        })
+        .map(|(editor_id, _)| editor_id);
+    let style = syntax_theme::style_to_highlight_style(&stoat.theme.get(scope::UI_HIGHLIGHT_READ));
+
+    let ws = stoat.active_workspace_mut();
+    for (editor_id, editor) in ws.editors.iter_mut() {
+        let syntax = ws
+            .buffers
+            .syntax_snapshot(editor.buffer_id)
+            .filter(|_| Some(editor_id) == target && editor.review_view.is_none());
+        let Some(syntax) = syntax else {
+            if editor.occurrence_key.take().is_some() {
+                editor.display_map.clear_highlights(KEY);
+            }
+            continue;
+        };
+        let (key, anchors) = {
+            let snapshot = editor.display_map.snapshot();
+            let buf_snap = snapshot.buffer_snapshot();
+            if !buf_snap.is_singleton() || buf_snap.version() != syntax.parsed_version {
+                continue;
+            }
+            let rope = buf_snap.rope();
+            let sel = editor.selections.newest_anchor();
+            let cursor = stoat_text::cursor_offset(
+                rope,
+                buf_snap.resolve_anchor(&sel.tail()),
+                buf_snap.resolve_anchor(&sel.head()),
+            );
+            let key = (buf_snap.version(), cursor, editor.scroll_row);
+            if editor.occurrence_key == Some(key) {
+                continue;
+            }
+            let end_row = editor.scroll_row.saturating_add(
+                editor
+                    .viewport_rows
+                    .unwrap_or(crate::action_handlers::movement::DEFAULT_VIEWPORT_ROWS),
+            );
+            let visible = crate::render::editor::visible_byte_range(
+                &snapshot,
+                rope,
+                editor.scroll_row,
+                end_row,
+            );
+            let anchors: Vec<_> = occurrences(syntax, rope, cursor, visible)
+                .into_iter()
+                .map(|r| {
+                    buf_snap.anchor_at(r.start, Bias::Right)..buf_snap.anchor_at(r.end, Bias::Left)
+                })
+                .collect();
+            (key, anchors)
+        };
+        editor.occurrence_key = Some(key);
+        if anchors.is_empty() {
+            editor.display_map.clear_highlights(KEY);
+        } else {
+            editor.display_map.highlight_text(KEY, anchors, style);
+        }
+    }
+}
+
+/// Byte ranges of the occurrences within `visible` of the identifier at
+/// `cursor`, in source order. Empty when the cursor is not on an identifier.
+fn occurrences(
+    syntax: &SyntaxSnapshot,
+    rope: &Rope,
+    cursor: usize,
+    visible: Range<usize>,
+) -> Vec<Range<usize>> {
+    let Some(layer) = syntax.deepest_layer_at(cursor) else {
+        return Vec::new();
+    };
+    let root = layer.tree.root_node();
+    let Some(node) = root
+        .descendant_for_byte_range(cursor, cursor)
+        .filter(is_identifier)
+    else {
+        return Vec::new();
+    };
+    let overlaps = |r: &Range<usize>| r.start < visible.end && r.end > visible.start;
+
+    if let Some(query) = layer.language.locals_query.as_ref() {
+        if rope.len() <= LOCALS_MAX_BYTES {
+            let scoped = stoat_language::local_references_at(query, root, rope, cursor);
+            if !scoped.is_empty() {
+                return scoped.into_iter().filter(overlaps).collect();
+            }
+        }
+    }
+
+    let name: String = rope.chunks_in_range(node.byte_range()).collect();
+    let mut out = Vec::new();
+    collect_named(root, &name, rope, &visible, &mut out);
+    out
+}
+
+/// Push the range of every identifier under `node` overlapping `visible`
+/// whose text is `name`.
+fn collect_named(
+    node: Node<'_>,
+    name: &str,
+    rope: &Rope,
+    visible: &Range<usize>,
+    out: &mut Vec<Range<usize>>,
+) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.end_byte() <= visible.start || child.start_byte() >= visible.end {
+            continue;
+        }
+        if child.child_count() > 0 {
+            collect_named(child, name, rope, visible, out);
+        } else if is_identifier(&child)
+            && child.byte_range().len() == name.len()
+            && rope.chunks_in_range(child.byte_range()).collect::<String>() == name
+        {
+            out.push(child.byte_range());
+        }
+    }
+}
+
+/// Whether `node` is a name token, e.g. `identifier` or
+/// `field_identifier`.
+fn is_identifier(node: &Node<'_>) -> bool {
+    node.kind().ends_with("identifier")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KEY;
+    use crate::test_harness::TestHarness;
+
+    fn highlighted(h: &mut TestHarness) -> Vec<String> {
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        let snapshot = editor.display_map.snapshot();
+        let buf_snap = snapshot.buffer_snapshot();
+        let rope = buf_snap.rope();
+        snapshot
+            .text_highlights()
+            .get(&KEY)
+            .map(|hl| {
+                hl.1.iter()
+                    .map(|r| {
+                        let range =
+                            buf_snap.resolve_anchor(&r.start)..buf_snap.resolve_anchor(&r.end);
+                        format!(
+                            "{}:{}",
+                            range.start,
+                            rope.chunks_in_range(range.clone()).collect::<String>()
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn open_rust(h: &mut TestHarness, source: &str) {
+        h.stoat.settings.editor_highlight_occurrences = Some(true);
+        let path = h.write_file("occ.rs", source);
+        h.open_file(&path);
+        for _ in 0..2 {
+            h.stoat.drive_background();
+            let _ = h.stoat.render();
+            h.settle();
+        }
+    }
+
+    #[test]
+    fn highlights_the_binding_under_the_cursor() {
+        let source = "fn f() {\n    let y = 1;\n    g(y);\n    let y = y;\n    g(y);\n}\n";
+        let mut h = TestHarness::with_size(40, 10);
+        open_rust(&mut h, source);
+
+        h.stoat
+            .collapse_focused_cursor_to(source.rfind("y);").unwrap());
+        h.stoat.drive_background();
+        let second = source.find("y = y").unwrap();
+        let last_use = source.rfind("y);").unwrap();
+        assert_eq!(
+            highlighted(&mut h),
+            vec![format!("{second}:y"), format!("{last_use}:y")],
+            "the shadowed y is not lit"
+        );
+
+        h.stoat
+            .collapse_focused_cursor_to(source.find("g(").unwrap());
+        h.stoat.drive_background();
+        assert_eq!(
+            highlighted(&mut h).len(),
+            2,
+            "an unbound name falls back to text matches"
+        );
+
+        h.stoat
+            .collapse_focused_cursor_to(source.find("1;").unwrap());
+        h.stoat.drive_background();
+        assert!(highlighted(&mut h).is_empty(), "a literal is no identifier");
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let source = "fn f(a: u32) -> u32 { a }\n";
+        let mut h = TestHarness::with_size(40, 10);
+        open_rust(&mut h, source);
+        h.stoat.settings.editor_highlight_occurrences = None;
+        h.stoat
+            .collapse_focused_cursor_to(source.find("a }").unwrap());
+        h.stoat.drive_background();
+        assert!(highlighted(&mut h).is_empty());
+    }
+}
@@ -0,0 +1,222 @@
+//! Stack of the modal overlays that take focus from the panes.
+//!
+//! The file finder, the command palette, and the help modal each own an
+//! [`crate::input_view::InputView`] that takes keyboard focus while open. They
+//! share one lifecycle here instead of each tracking what it opened over:
+//!
+//! - [`push`] records an overlay as it opens, along with the mode of the focus target beneath it,
+//!   and [`pop`] hands that mode back as it closes.
+//! - [`top`] is the most recently opened overlay still open. It owns keyboard focus, the `modal`
+//!   predicate, and the Escape and Ctrl-C keys.
+//! - [`cancel_top`] and [`dismiss_top`] close the top overlay the way Escape and Ctrl-C do, leaving
+//!   the ones beneath it open.
+
+use crate::{
+    app::{Stoat, UpdateEffect},
+    input_view::InputView,
+};
+
+/// A modal overlay managed by the [`OverlayStack`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Overlay {
+    FileFinder,
+    CommandPalette,
+    Help,
+}
+
+impl Overlay {
+    /// The value the `modal` keymap predicate reports while this overlay is on
+    /// top.
+    pub(crate) fn predicate(self) -> &'static str {
+        match self {
+            Self::FileFinder => "finder",
+            Self::CommandPalette => "palette",
+            Self::Help => "help",
+        }
+    }
+
+    fn is_open(self, stoat: &Stoat) -> bool {
+        match self {
+            Self::FileFinder => stoat.file_finder.is_some(),
+            Self::CommandPalette => stoat.command_palette.is_some(),
+            Self::Help => stoat.help.is_some(),
+        }
+    }
+
+    /// The input that takes keyboard focus while this overlay is on top. A
+    /// palette between inputs has none, and focus falls through to the pane.
+    pub(crate) fn input(self, stoat: &Stoat) -> Option<&InputView> {
+        match self {
+            Self::FileFinder => stoat.file_finder.as_ref().map(|finder| &finder.input),
+            Self::CommandPalette => stoat
+                .command_palette
+                .as_ref()
+                .and_then(|palette| palette.focused_input()),
+            Self::Help => stoat.help.as_ref().map(|help| &help.input),
+        }
+    }
+
+    /// Close this overlay without acting on it. The finder and palette stash
+    /// their query as a draft the next open restores.
+    fn dismiss(self, stoat: &mut Stoat) {
+        match self {
+            Self::FileFinder => crate::action_handlers::dismiss_file_finder(stoat),
+            Self::CommandPalette => {
+                crate::action_handlers::dismiss_palette(stoat);
+            },
+            Self::Help => crate::action_handlers::close_help(stoat),
+        }
+    }
+}
+
+/// One open overlay and the mode to hand back to whatever it covered.
+#[derive(Debug)]
+struct Entry {
+    overlay: Overlay,
+    restore_mode: String,
+}
+
+/// Open overlays, oldest first.
+#[derive(Debug, Default)]
+pub(crate) struct OverlayStack {
+    entries: Vec<Entry>,
+}
+
+impl OverlayStack {
+    /// Put `overlay` on top, remembering `restore_mode`. An overlay already on
+    /// the stack moves to the top and keeps its first saved mode.
+    fn push(&mut self, overlay: Overlay, restore_mode: String) {
+        let restore_mode = match self.entries.iter().position(|e| e.overlay == overlay) {
+            Some(index) => self.entries.remove(index).restore_mode,
+            None => restore_mode,
+        };
+        self.entries.push(Entry {
+            overlay,
+            restore_mode,
+        });
+    }
+
+    /// Take `overlay` off the stack. Returns its saved mode when it was the
+    /// top. A buried overlay instead passes its saved mode to the entry above,
+    /// which had saved this overlay's mode, and returns `None`.
+    fn remove(&mut self, overlay: Overlay) -> Option<String> {
+        let index = self.entries.iter().position(|e| e.overlay == overlay)?;
+        let entry = self.entries.remove(index);
+        match self.entries.get_mut(index) {
+            Some(above) => {
+                above.restore_mode = entry.restore_mode;
+                None
+            },
+            None => Some(entry.restore_mode),
+        }
+    }
+}
+
+/// Record `overlay` as opening over the current focus. Call before setting the
+/// overlay's field, so the mode saved is the one of the target it covers.
+pub(crate) fn push(stoat: &mut Stoat, overlay: Overlay) {
+    let mode = stoat.focused_mode().to_string();
+    stoat.overlays.push(overlay, mode);
+}
+
+/// Record `overlay` as closed. Call after clearing its field. When it was the
+/// top, the target focus returns to gets back the mode it had when the overlay
+/// opened.
+pub(crate) fn pop(stoat: &mut Stoat, overlay: Overlay) {
+    if let Some(mode) = stoat.overlays.remove(overlay) {
+        stoat.set_focused_mode(mode);
+    }
+}
+
+/// The most recently opened overlay that is still open.
+pub(crate) fn top(stoat: &Stoat) -> Option<Overlay> {
+    stoat
+        .overlays
+        .entries
+        .iter()
+        .rev()
+        .map(|entry| entry.overlay)
+        .find(|overlay| overlay.is_open(stoat))
+}
+
+/// Handle Escape for the top overlay. The finder and palette close. Help
+/// closes in two steps: the first Escape leaves its search input for the list,
+/// and the `modal == help && mode == normal` binding closes it on the second.
+/// Returns `None` when no overlay is open.
+pub(crate) fn cancel_top(stoat: &mut Stoat) -> Option<UpdateEffect> {
+    let overlay = top(stoat)?;
+    if overlay == Overlay::Help && stoat.focused_mode() == "insert" {
+        stoat.set_focused_mode("normal".into());
+    } else {
+        overlay.dismiss(stoat);
+    }
+    Some(UpdateEffect::Redraw)
+}
+
+/// Close the top overlay for Ctrl-C, leaving any beneath it open. Returns
+/// whether one was open.
+pub(crate) fn dismiss_top(stoat: &mut Stoat) -> bool {
+    let Some(overlay) = top(stoat) else {
+        return false;
+    };
+    overlay.dismiss(stoat);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::TestHarness;
+    use stoat_action::{OpenCommandPalette, OpenHelp};
+
+    fn dispatch(h: &mut TestHarness, action: &dyn stoat_action::Action) {
+        crate::action_handlers::dispatch(&mut h.stoat, action);
+    }
+
+    #[test]
+    fn escape_closes_only_the_top_overlay() {
+        let mut h = TestHarness::with_size(80, 24);
+        dispatch(&mut h, &OpenCommandPalette);
+        dispatch(&mut h, &OpenHelp);
+        assert_eq!(top(&h.stoat), Some(Overlay::Help));
+
+        h.type_keys("escape escape");
+
+        assert!(h.stoat.help.is_none(), "two Escapes close help");
+        assert!(h.stoat.command_palette.is_some(), "the palette stays open");
+        assert_eq!(top(&h.stoat), Some(Overlay::CommandPalette));
+        assert_eq!(
+            h.stoat.focused_mode(),
+            "insert",
+            "focus is back in its input"
+        );
+
+        h.type_keys("escape");
+        assert_eq!(top(&h.stoat), None);
+        assert_eq!(h.stoat.focused_mode(), "normal");
+    }
+
+    #[test]
+    fn closing_an_overlay_restores_the_mode_it_opened_over() {
+        let mut h = TestHarness::with_size(80, 24);
+        h.type_keys("v");
+        assert_eq!(h.stoat.focused_mode(), "select");
+
+        dispatch(&mut h, &OpenCommandPalette);
+        assert_eq!(h.stoat.focused_mode(), "insert");
+        h.type_keys("ctrl-c");
+
+        assert!(h.stoat.command_palette.is_none());
+        assert_eq!(h.stoat.focused_mode(), "select");
+    }
+
+    #[test]
+    fn a_buried_overlay_hands_its_mode_to_the_one_above() {
+        let mut stack = OverlayStack::default();
+        stack.push(Overlay::CommandPalette, "select".into());
+        stack.push(Overlay::Help, "insert".into());
+
+        assert_eq!(stack.remove(Overlay::CommandPalette), None);
+        assert_eq!(stack.remove(Overlay::Help).as_deref(), Some("select"));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{editor_state::EditorId, jumplist::JumpList, run::RunId, term_session::TermId};
+use crate::{
+    editor_state::EditorId, hex_view::HexId, jumplist::JumpList, regex_playground::PlaygroundId,
+    run::RunId, term_session::TermId,
+};
 use ratatui::layout::Rect;
 use serde::{Deserialize, Serialize};
 use slotmap::{new_key_type, SlotMap};
@@ -57,6 +60,8 @@ pub enum View {
     Run(RunId),
     Agent(TermId),
     Terminal(TermId),
+    Hex(HexId),
+    RegexPlayground(PlaygroundId),
 }
 
 /// How a pane is presented on screen.
@@ -81,6 +81,13 @@ pub fn user_config_path() -> Option<PathBuf> {
         .map(|x| x.config_dir().join("stoat/config.stcfg"))
 }
 
+/// The project-local config under `root`, `.stoat/config`, layered over the
+/// user config while `root` is the active workspace's root. The path is not
+/// guaranteed to exist.
+pub(crate) fn project_config_path(root: &Path) -> PathBuf {
+    root.join(".stoat/config")
+}
+
 /// Absolute path to stoatty's config file, `config.toml` under the XDG config
 /// home (typically `~/.config/stoatty/config.toml`).
 ///
@@ -8,14 +8,22 @@
 //!
 //! Timing reads [`std::time::Instant`], i.e. real elapsed wall time, not the
 //! scheduler's virtual clock.
+//!
+//! A [`Capture`] started with [`PerfStats::start_capture`] additionally keeps
+//! every timed step as a span until it is finished, and serializes them in the
+//! Chrome trace event format that `chrome://tracing` and Perfetto load.
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Samples retained per metric. At a few hundred frames a second of activity
 /// this is several seconds of history, enough for a stable percentile
 /// readout.
 const RING: usize = 4096;
 
+/// Events a [`Capture`] keeps before it stops recording, so a capture left
+/// running for hours does not grow without bound.
+const CAPTURE_LIMIT: usize = 1 << 20;
+
 /// last/p50/p95/worst of one metric over its retained ring, in the metric's
 /// own unit (nanoseconds for durations, a raw count for `coalesced`).
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -29,27 +37,59 @@ pub struct MetricStats {
 /// Per-frame main-thread metrics, each a fixed ring of recent samples.
 ///
 /// Populated by [`crate::app::Stoat::run`] around the update, drain, and paint
-/// steps. The status-bar readout and the exit summary read the percentiles
-/// back through the `*_stats` accessors.
+/// steps, and by [`crate::render::frame`] around layout. The status-bar
+/// readout, the perf overlay, and the exit summary read the percentiles back
+/// through the `*_stats` accessors.
 #[derive(Default)]
 pub struct PerfStats {
     update: Ring,
+    frame: Ring,
+    layout: Ring,
     paint: Ring,
+    lines: Ring,
     input_to_publish: Ring,
     input_to_flush: Ring,
     coalesced: Ring,
     anim_tick: Ring,
+    capture: Option<Capture>,
 }
 
 impl PerfStats {
     /// Time spent applying one event in `update`.
     pub fn record_update(&mut self, elapsed: Duration) {
         self.update.record(elapsed.as_nanos() as u64);
+        self.trace_span("update", elapsed);
+    }
+
+    /// Time spent producing one redraw, from the background pump through
+    /// publishing the painted buffer.
+    pub fn record_frame(&mut self, elapsed: Duration) {
+        self.frame.record(elapsed.as_nanos() as u64);
+        self.trace_span("frame", elapsed);
+    }
+
+    /// Time spent laying out the active workspace's panes, part of the paint.
+    pub fn record_layout(&mut self, elapsed: Duration) {
+        self.layout.record(elapsed.as_nanos() as u64);
+        self.trace_span("layout", elapsed);
     }
 
     /// Time spent painting the frame buffer.
     pub fn record_paint(&mut self, elapsed: Duration) {
         self.paint.record(elapsed.as_nanos() as u64);
+        self.trace_span("paint", elapsed);
+    }
+
+    /// Editor text rows a paint drew across every visible pane.
+    pub fn record_lines(&mut self, count: usize) {
+        self.lines.record(count as u64);
+        if let Some(capture) = &mut self.capture {
+            capture.push(TraceEvent::Counter {
+                name: "lines",
+                at: Instant::now(),
+                value: count as u64,
+            });
+        }
     }
 
     /// Latency from the first event of a frame to publishing that frame.
@@ -78,10 +118,22 @@ impl PerfStats {
         self.update.stats()
     }
 
+    pub fn frame_stats(&self) -> Option<MetricStats> {
+        self.frame.stats()
+    }
+
+    pub fn layout_stats(&self) -> Option<MetricStats> {
+        self.layout.stats()
+    }
+
     pub fn paint_stats(&self) -> Option<MetricStats> {
         self.paint.stats()
     }
 
+    pub fn lines_stats(&self) -> Option<MetricStats> {
+        self.lines.stats()
+    }
+
     pub fn input_to_publish_stats(&self) -> Option<MetricStats> {
         self.input_to_publish.stats()
     }
@@ -97,6 +149,132 @@ impl PerfStats {
     pub fn anim_tick_stats(&self) -> Option<MetricStats> {
         self.anim_tick.stats()
     }
+
+    /// Start keeping every timed step as a trace span, discarding any capture
+    /// already running.
+    pub fn start_capture(&mut self) {
+        self.capture = Some(Capture {
+            started: Instant::now(),
+            events: Vec::new(),
+        });
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    /// Stop the running capture and hand it back, or `None` when none runs.
+    pub fn finish_capture(&mut self) -> Option<Capture> {
+        self.capture.take()
+    }
+
+    /// Record a span of `elapsed` ending now into the running capture.
+    fn trace_span(&mut self, name: &'static str, elapsed: Duration) {
+        if let Some(capture) = &mut self.capture {
+            let end = Instant::now();
+            capture.push(TraceEvent::Span {
+                name,
+                start: end.checked_sub(elapsed).unwrap_or(end),
+                duration: elapsed,
+            });
+        }
+    }
+}
+
+/// One event a [`Capture`] recorded.
+#[derive(Clone, Copy, Debug)]
+enum TraceEvent {
+    /// A timed step, such as one paint.
+    Span {
+        name: &'static str,
+        start: Instant,
+        duration: Duration,
+    },
+    /// A sampled count, such as the lines one paint drew.
+    Counter {
+        name: &'static str,
+        at: Instant,
+        value: u64,
+    },
+}
+
+/// The events recorded between [`PerfStats::start_capture`] and
+/// [`PerfStats::finish_capture`].
+#[derive(Debug)]
+pub struct Capture {
+    started: Instant,
+    events: Vec<TraceEvent>,
+}
+
+impl Capture {
+    fn push(&mut self, event: TraceEvent) {
+        if self.events.len() < CAPTURE_LIMIT {
+            self.events.push(event);
+        }
+    }
+
+    /// How long the capture ran, measured to its last event.
+    pub fn duration(&self) -> Duration {
+        self.events
+            .iter()
+            .map(|event| match *event {
+                TraceEvent::Span {
+                    start, duration, ..
+                } => start + duration,
+                TraceEvent::Counter { at, .. } => at,
+            })
+            .max()
+            .map_or(Duration::ZERO, |end| {
+                end.saturating_duration_since(self.started)
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Serialize the capture as a Chrome trace event JSON document, with
+    /// timestamps in microseconds from the capture's start. Spans become
+    /// complete (`X`) events and counts become counter (`C`) events, all on
+    /// the main thread's track.
+    pub fn to_chrome_trace(&self) -> String {
+        let micros = |elapsed: Duration| elapsed.as_nanos() as f64 / 1e3;
+        let events: Vec<serde_json::Value> = self
+            .events
+            .iter()
+            .map(|event| match *event {
+                TraceEvent::Span {
+                    name,
+                    start,
+                    duration,
+                } => serde_json::json!({
+                    "name": name,
+                    "ph": "X",
+                    "ts": micros(start.saturating_duration_since(self.started)),
+                    "dur": micros(duration),
+                    "pid": 1,
+                    "tid": 1,
+                }),
+                TraceEvent::Counter { name, at, value } => serde_json::json!({
+                    "name": name,
+                    "ph": "C",
+                    "ts": micros(at.saturating_duration_since(self.started)),
+                    "pid": 1,
+                    "tid": 1,
+                    "args": { (name): value },
+                }),
+            })
+            .collect();
+        serde_json::json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+        .to_string()
+    }
 }
 
 /// A fixed-capacity ring of `u64` samples with on-demand percentiles.
@@ -197,4 +375,30 @@ mod tests {
         assert_eq!(perf.coalesced_stats().expect("coalesced").last, 3);
         assert_eq!(perf.anim_tick_stats().expect("anim").last, 8_000_000);
     }
+
+    #[test]
+    fn capture_keeps_spans_only_while_running() {
+        let mut perf = PerfStats::default();
+        perf.record_paint(Duration::from_micros(10));
+        assert!(perf.finish_capture().is_none());
+
+        perf.start_capture();
+        perf.record_layout(Duration::from_micros(4));
+        perf.record_paint(Duration::from_micros(10));
+        perf.record_lines(42);
+        let capture = perf.finish_capture().expect("a capture ran");
+        perf.record_paint(Duration::from_micros(10));
+
+        assert_eq!(capture.len(), 3);
+        assert!(!perf.is_capturing());
+        let trace: serde_json::Value =
+            serde_json::from_str(&capture.to_chrome_trace()).expect("valid json");
+        let events = trace["traceEvents"].as_array().expect("event array");
+        assert_eq!(events[0]["name"], "layout");
+        assert_eq!(events[0]["ph"], "X");
+        assert_eq!(events[0]["dur"], 4.0);
+        assert_eq!(events[1]["name"], "paint");
+        assert_eq!(events[2]["ph"], "C");
+        assert_eq!(events[2]["args"]["lines"], 42);
+    }
 }
@@ -63,6 +63,11 @@ pub(crate) struct PickList {
     /// single `git_root`. Set for the cross-workspace scope. `None` leaves every
     /// existing finder git-root-relative.
     pub(crate) display_roots: Option<Vec<PathBuf>>,
+    /// Base entries before this index rank ahead of every other row under any
+    /// query. The buffer scope sets it to the pinned-buffer count, since
+    /// [`crate::buffer_registry::BufferRegistry::open_paths`] lists pinned
+    /// buffers first.
+    pub(crate) leading: usize,
 }
 
 impl Default for PickList {
@@ -75,6 +80,7 @@ impl Default for PickList {
             viewport_rows: None,
             filter_generation: next_generation(),
             display_roots: None,
+            leading: 0,
         }
     }
 }
@@ -113,7 +119,8 @@ impl PickList {
     /// Re-run the matcher over `base` for `query` via
     /// [`crate::fuzzy::match_and_rank`], ordering matches by score descending,
     /// ties alphabetical. Empty or whitespace-only input lists every candidate
-    /// alphabetically.
+    /// alphabetically. Either way the first [`Self::leading`] base entries sort
+    /// ahead of the rest.
     ///
     /// A leading `./` token anchors to the workspace root. Candidates are first
     /// restricted to those whose display path starts with the token's prefix,
@@ -150,7 +157,11 @@ impl PickList {
                 .map(|(idx, path)| (idx, row_display(path, git_root, display_roots, home)))
                 .filter(|(_, display)| anchor.is_none_or(|a| display.starts_with(a)))
                 .collect();
-            rows.sort_by(|a, b| a.1.cmp(&b.1));
+            rows.sort_by(|a, b| {
+                (a.0 >= self.leading)
+                    .cmp(&(b.0 >= self.leading))
+                    .then_with(|| a.1.cmp(&b.1))
+            });
             for (idx, _) in &rows {
                 self.filtered.push(*idx);
                 self.match_indices.push((0..anchor_len).collect());
@@ -160,8 +171,9 @@ impl PickList {
         };
 
         matches.sort_by(|a, b| {
-            b.score
-                .cmp(&a.score)
+            (a.item >= self.leading)
+                .cmp(&(b.item >= self.leading))
+                .then_with(|| b.score.cmp(&a.score))
                 .then_with(|| a.haystack.cmp(&b.haystack))
         });
         for m in matches {
@@ -692,6 +704,28 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn leading_entries_rank_first_under_any_query() {
+        let git_root = p("/r");
+        let mut list = PickList {
+            base: vec![p("/r/z.rs"), p("/r/a.rs"), p("/r/zz.rs")].into(),
+            leading: 1,
+            ..PickList::default()
+        };
+        let names = |list: &PickList| -> Vec<String> {
+            list.filtered
+                .iter()
+                .map(|i| paths::display_relative(&list.base[*i], &git_root))
+                .collect()
+        };
+        list.refilter("", &git_root);
+        assert_eq!(names(&list), ["z.rs", "a.rs", "zz.rs"]);
+        list.refilter("zz", &git_root);
+        assert_eq!(names(&list), ["zz.rs"], "a non-match still drops out");
+        list.refilter("rs", &git_root);
+        assert_eq!(names(&list)[0], "z.rs");
+    }
+
     #[test]
     fn empty_input_lists_all_base_paths_sorted() {
         let git_root = p("/r");
@@ -0,0 +1,237 @@
+//! Project-local configuration layered over the user config.
+//!
+//! A `.stoat/config` file at the active workspace's root holds settings and
+//! bindings for that project. Its blocks are appended after the user config's
+//! (or the embedded default's), so a project setting overrides the user's and
+//! a project binding wins a tie with a user binding of equal specificity. CLI
+//! overrides still outrank both.
+//!
+//! [`sync`] re-evaluates the layer whenever the active root changes, whether
+//! by switching workspaces or `cd`, and [`reload`] re-applies it when the file
+//! is saved. [`show_effective`] lists the merged settings with the layer each
+//! value came from.
+
+use crate::app::{Stoat, UpdateEffect};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use stoat_config::{Config, EventType, Statement};
+
+/// Seed written by `:config project` when the project has no config yet.
+pub(crate) const SEED: &str = "# Project config, layered over the user config.\n\
+# Settings and bindings here win over the user's while this root is active.\n";
+
+/// The project layer held on [`Stoat`].
+#[derive(Default)]
+pub(crate) struct ProjectConfig {
+    /// Root the layer was last evaluated for.
+    root: Option<PathBuf>,
+    layer: Option<Layer>,
+}
+
+/// A project config that parsed clean.
+struct Layer {
+    path: PathBuf,
+    source: String,
+    config: Config,
+}
+
+impl ProjectConfig {
+    /// `base` with the project's blocks and themes appended, so they apply
+    /// after the base's.
+    pub(crate) fn layer_over(&self, base: Option<Config>) -> Option<Config> {
+        let Some(layer) = &self.layer else {
+            return base;
+        };
+        let mut config = base.unwrap_or(Config {
+            blocks: Vec::new(),
+            themes: Vec::new(),
+        });
+        config.blocks.extend(layer.config.blocks.iter().cloned());
+        config.themes.extend(layer.config.themes.iter().cloned());
+        Some(config)
+    }
+}
+
+/// Re-evaluate the project layer when the active workspace's root differs
+/// from the one it was built for. Called from [`Stoat::drive_background`].
+pub(crate) fn sync(stoat: &mut Stoat) {
+    let root = stoat.active_workspace().git_root.clone();
+    if stoat.project_config.root.as_ref() == Some(&root) {
+        return;
+    }
+    let path = crate::paths::project_config_path(&root);
+    stoat.project_config.root = Some(root);
+    let mut bytes = Vec::new();
+    let source = stoat
+        .fs_host
+        .read(&path, &mut bytes)
+        .ok()
+        .map(|()| String::from_utf8_lossy(&bytes).into_owned());
+    match source {
+        Some(source) => reload(stoat, &path, &source),
+        None if stoat.project_config.layer.is_some() => {
+            stoat.project_config.layer = None;
+            stoat.apply_config_layers();
+            stoat.set_status("project config unloaded");
+        },
+        None => {},
+    }
+}
+
+/// Install `source` as the project layer read from `path` and rebuild the
+/// running config. A source that fails to parse drops the layer, so the user
+/// config applies alone, and reports the failure.
+pub(crate) fn reload(stoat: &mut Stoat, path: &Path, source: &str) {
+    let (config, errors) = stoat_config::parse(source);
+    let shown = crate::paths::display_relative(path, &stoat.active_workspace().git_root);
+    if !errors.is_empty() {
+        tracing::error!(
+            "project config {} parse failed: {}",
+            path.display(),
+            stoat_config::format_errors(source, &errors)
+        );
+        stoat.project_config.layer = None;
+        stoat.apply_config_layers();
+        stoat.set_status(format!("{shown} parse failed; using the user config"));
+        return;
+    }
+    stoat.project_config.layer = config.map(|config| Layer {
+        path: path.to_path_buf(),
+        source: source.to_string(),
+        config,
+    });
+    stoat.apply_config_layers();
+    stoat.set_status(format!("project config {shown} applied"));
+}
+
+/// Open a read-only buffer in a vertical split listing every setting the
+/// user and project configs assign, with its final value and the layer it
+/// came from, followed by the project's binding blocks.
+pub(crate) fn show_effective(stoat: &mut Stoat) -> UpdateEffect {
+    let text = effective_text(stoat);
+    crate::action_handlers::file::open_read_only_text(stoat, &text)
+}
+
+fn effective_text(stoat: &Stoat) -> String {
+    let (base_name, base_source) = match &stoat.user_config_source {
+        Some(source) => ("user", source.clone()),
+        None => ("default", crate::app::DEFAULT_KEYMAP.to_string()),
+    };
+    let mut settings: BTreeMap<String, (String, &str)> = BTreeMap::new();
+    if let (Some(base), _) = stoat_config::parse(&base_source) {
+        collect_settings(&base, &base_source, base_name, &mut settings);
+    }
+    let layer = stoat.project_config.layer.as_ref();
+    if let Some(layer) = layer {
+        collect_settings(&layer.config, &layer.source, "project", &mut settings);
+    }
+
+    let mut text = String::from("# Effective config, later layers winning\n");
+    text.push_str(&format!("# {base_name}: "));
+    text.push_str(
+        &match (&stoat.user_config_source, crate::paths::user_config_path()) {
+            (Some(_), Some(path)) => path.display().to_string(),
+            (Some(_), None) => "user config".to_string(),
+            (None, _) => "built-in default".to_string(),
+        },
+    );
+    text.push('\n');
+    match layer {
+        Some(layer) => text.push_str(&format!("# project: {}\n", layer.path.display())),
+        None => text.push_str("# project: none\n"),
+    }
+    text.push('\n');
+    for (path, (value, origin)) in &settings {
+        text.push_str(&format!("{path} = {value};  # {origin}\n"));
+    }
+    if let Some(layer) = layer {
+        let bindings: Vec<&str> = layer
+            .config
+            .blocks
+            .iter()
+            .filter(|block| block.node.event != EventType::Init)
+            .filter_map(|block| layer.source.get(block.span.clone()))
+            .collect();
+        if !bindings.is_empty() {
+            text.push_str("\n# project bindings\n");
+            for block in bindings {
+                text.push_str(block);
+                text.push('\n');
+            }
+        }
+    }
+    text
+}
+
+/// Record each top-level setting in `config`'s `init` blocks under its dotted
+/// path, as written in `source`, replacing any earlier layer's entry.
+fn collect_settings<'a>(
+    config: &Config,
+    source: &str,
+    origin: &'a str,
+    out: &mut BTreeMap<String, (String, &'a str)>,
+) {
+    for block in &config.blocks {
+        if block.node.event != EventType::Init {
+            continue;
+        }
+        for stmt in &block.node.statements {
+            let Statement::Setting(setting) = &stmt.node else {
+                continue;
+            };
+            let path: Vec<&str> = setting.path.iter().map(|seg| seg.node.as_str()).collect();
+            let value = source
+                .get(setting.value.span.clone())
+                .unwrap_or_default()
+                .to_string();
+            out.insert(path.join("."), (value, origin));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::TestHarness;
+
+    const PROJECT: &str = "init {\n    editor.cursor_line = true;\n}\n";
+
+    #[test]
+    fn project_config_layers_over_the_user_config_per_root() {
+        let mut h = TestHarness::with_size(80, 24);
+        let root = PathBuf::from("/proj");
+        h.fake_fs
+            .insert_file("/proj/.stoat/config", PROJECT.as_bytes());
+        h.stoat.active_workspace_mut().git_root = root;
+        assert_ne!(h.stoat.settings.editor_cursor_line, Some(true));
+
+        sync(&mut h.stoat);
+        assert_eq!(h.stoat.settings.editor_cursor_line, Some(true));
+
+        h.stoat.active_workspace_mut().git_root = PathBuf::from("/elsewhere");
+        sync(&mut h.stoat);
+        assert_ne!(
+            h.stoat.settings.editor_cursor_line,
+            Some(true),
+            "another root drops the layer"
+        );
+    }
+
+    #[test]
+    fn effective_config_names_the_layer_each_setting_came_from() {
+        let mut h = TestHarness::with_size(80, 24);
+        h.fake_fs
+            .insert_file("/proj/.stoat/config", PROJECT.as_bytes());
+        h.stoat.active_workspace_mut().git_root = PathBuf::from("/proj");
+        sync(&mut h.stoat);
+
+        let text = effective_text(&h.stoat);
+        assert!(
+            text.contains("editor.cursor_line = true;  # project"),
+            "{text}"
+        );
+        assert!(text.contains("# project: /proj/.stoat/config"), "{text}");
+    }
+}
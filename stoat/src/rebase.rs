@@ -137,6 +137,10 @@ pub(crate) enum RebasePause {
         /// Original commit message, kept for the modal's reference line
         /// (the editable copy lives in the buffer below).
         original_message: String,
+        /// The todo entry's pre-rebase sha, stable across rebase attempts, so
+        /// a message drafted before `RewordAbort` returns when the same commit
+        /// is reworded again.
+        source_commit: String,
         input: crate::input_view::InputView,
     },
     /// Waiting for the user to modify the picked commit (typically via
@@ -0,0 +1,256 @@
+use crate::{
+    input_view::{InputView, SubmitTarget},
+    workspace::Workspace,
+};
+use regex::Regex;
+use slotmap::new_key_type;
+use stoat_scheduler::Executor;
+
+new_key_type! {
+    pub struct PlaygroundId;
+}
+
+/// Matches the capture list describes before it stops, so a pattern matching
+/// every character of a long sample does not build thousands of rows.
+pub(crate) const CAPTURE_MATCH_CAP: usize = 200;
+
+/// Which of the playground's two inputs receives typing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlaygroundField {
+    Pattern,
+    Sample,
+}
+
+/// One match of the pattern against the sample text.
+///
+/// `groups` holds every capture group after the whole match, in pattern order,
+/// with its name when the group is named and `None` for a group that did not
+/// participate in the match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlaygroundMatch {
+    pub(crate) range: std::ops::Range<usize>,
+    pub(crate) text: String,
+    pub(crate) groups: Vec<(Option<String>, Option<String>)>,
+}
+
+/// Live regex tester, shown in its own split pane.
+///
+/// A single-line pattern input sits above a multi-line sample pane. The sample
+/// highlights every match of the pattern as it is typed, compiled through
+/// [`crate::action_handlers::search::compile_search_regex`] so what matches here
+/// matches under `/`. A side list breaks each match into its capture groups.
+/// Tab moves typing between the two inputs. Enter on the pattern hands it to
+/// workspace code search, Escape closes the pane.
+pub(crate) struct RegexPlayground {
+    pub(crate) pattern: InputView,
+    pub(crate) sample: InputView,
+    pub(crate) focus: PlaygroundField,
+    /// The last pattern compiled and its result, so a repaint with the pattern
+    /// unchanged skips recompiling it.
+    compiled: Option<(String, Result<Regex, regex::Error>)>,
+}
+
+impl RegexPlayground {
+    pub(crate) fn new(ws: &mut Workspace, executor: Executor, pattern: &str, sample: &str) -> Self {
+        let pattern = InputView::create(
+            ws,
+            executor.clone(),
+            SubmitTarget::RegexPlayground,
+            pattern,
+            "insert",
+            1,
+        );
+        let sample = InputView::create(
+            ws,
+            executor,
+            SubmitTarget::RegexPlayground,
+            sample,
+            "insert",
+            u16::MAX,
+        );
+        Self {
+            pattern,
+            sample,
+            focus: PlaygroundField::Pattern,
+            compiled: None,
+        }
+    }
+
+    /// `pattern` compiled, or `None` while it is empty. Reuses the previous
+    /// compile when the pattern has not changed since.
+    pub(crate) fn regex(&mut self, pattern: &str) -> Option<&Result<Regex, regex::Error>> {
+        if pattern.is_empty() {
+            return None;
+        }
+        if self.compiled.as_ref().is_none_or(|(p, _)| p != pattern) {
+            let regex = crate::action_handlers::search::compile_search_regex(pattern);
+            self.compiled = Some((pattern.to_string(), regex));
+        }
+        self.compiled.as_ref().map(|(_, regex)| regex)
+    }
+
+    /// The input typing currently goes to.
+    pub(crate) fn focused_input(&self) -> &InputView {
+        match self.focus {
+            PlaygroundField::Pattern => &self.pattern,
+            PlaygroundField::Sample => &self.sample,
+        }
+    }
+
+    pub(crate) fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            PlaygroundField::Pattern => PlaygroundField::Sample,
+            PlaygroundField::Sample => PlaygroundField::Pattern,
+        };
+    }
+
+    pub(crate) fn dispose(&self, ws: &mut Workspace) {
+        self.pattern.dispose(ws);
+        self.sample.dispose(ws);
+    }
+}
+
+/// Every non-empty match of `regex` in `text`, up to [`CAPTURE_MATCH_CAP`].
+///
+/// Empty matches are skipped like the editor's search highlight skips them,
+/// since a zero-width hit has nothing to paint or list.
+pub(crate) fn collect_matches(regex: &Regex, text: &str) -> Vec<PlaygroundMatch> {
+    let names: Vec<Option<&str>> = regex.capture_names().skip(1).collect();
+    regex
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let whole = caps.get(0)?;
+            if whole.is_empty() {
+                return None;
+            }
+            let groups = names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    (
+                        name.map(str::to_string),
+                        caps.get(i + 1).map(|m| m.as_str().to_string()),
+                    )
+                })
+                .collect();
+            Some(PlaygroundMatch {
+                range: whole.range(),
+                text: whole.as_str().to_string(),
+                groups,
+            })
+        })
+        .take(CAPTURE_MATCH_CAP)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_matches, PlaygroundField, PlaygroundId};
+    use crate::{
+        action_handlers::search::compile_search_regex, pane::View, test_harness::TestHarness,
+    };
+    use stoat_action::OpenRegexPlayground;
+
+    /// The playground shown in the focused pane, if any.
+    fn focused_playground(h: &TestHarness) -> Option<PlaygroundId> {
+        let ws = h.stoat.active_workspace();
+        match ws.panes.pane(ws.panes.focus()).view {
+            View::RegexPlayground(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn collect_matches_reports_numbered_and_named_groups() {
+        let regex = compile_search_regex(r"(\w+)=(?<value>\d+)?").expect("valid");
+        let matches = collect_matches(&regex, "a=1 b=");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].range, 0..3);
+        assert_eq!(
+            matches[0].groups,
+            vec![
+                (None, Some("a".to_string())),
+                (Some("value".to_string()), Some("1".to_string())),
+            ]
+        );
+        assert_eq!(
+            matches[1].groups[1],
+            (Some("value".to_string()), None),
+            "a group that did not participate is reported as absent"
+        );
+    }
+
+    #[test]
+    fn collect_matches_skips_empty_matches() {
+        let regex = compile_search_regex("x*").expect("valid");
+        let matches = collect_matches(&regex, "axxb");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "xx");
+    }
+
+    #[test]
+    fn regex_recompiles_only_when_the_pattern_changes() {
+        let mut h = crate::Stoat::test();
+        crate::action_handlers::dispatch(&mut h.stoat, &OpenRegexPlayground);
+        let id = focused_playground(&h).expect("playground open");
+        let playground = h
+            .stoat
+            .active_workspace_mut()
+            .regex_playgrounds
+            .get_mut(id)
+            .expect("state");
+        assert!(playground.regex("").is_none());
+        assert!(matches!(playground.regex("a+"), Some(Ok(_))));
+        assert_eq!(
+            playground.compiled.as_ref().map(|(p, _)| p.as_str()),
+            Some("a+")
+        );
+        assert!(matches!(playground.regex("a+"), Some(Ok(_))));
+        assert!(matches!(playground.regex("("), Some(Err(_))));
+        assert_eq!(
+            playground.compiled.as_ref().map(|(p, _)| p.as_str()),
+            Some("(")
+        );
+    }
+
+    #[test]
+    fn opens_beside_the_editor_and_types_into_either_field() {
+        let mut h = TestHarness::with_size(100, 30);
+        let path = h.write_file("s.txt", "foo = 12\n");
+        h.open_file(&path);
+        h.type_keys("x");
+        crate::action_handlers::dispatch(&mut h.stoat, &OpenRegexPlayground);
+        assert_eq!(h.stoat.active_workspace().panes.split_pane_ids().len(), 2);
+
+        h.type_text("\\d+");
+        h.type_keys("tab");
+        let id = focused_playground(&h).expect("playground focused");
+        let ws = h.stoat.active_workspace();
+        let playground = ws.regex_playgrounds.get(id).expect("state");
+        assert_eq!(playground.focus, PlaygroundField::Sample);
+        assert_eq!(playground.pattern.text(ws), "\\d+");
+        assert_eq!(playground.sample.text(ws), "foo = 12\n");
+
+        h.type_keys("escape");
+        let ws = h.stoat.active_workspace();
+        assert!(ws.regex_playgrounds.is_empty(), "escape closes");
+        assert_eq!(ws.panes.split_pane_ids().len(), 1);
+        assert!(matches!(
+            ws.panes.pane(ws.panes.focus()).view,
+            View::Editor(_)
+        ));
+    }
+
+    #[test]
+    fn enter_on_the_pattern_hands_it_to_code_search() {
+        let mut h = crate::Stoat::test();
+        crate::action_handlers::dispatch(&mut h.stoat, &OpenRegexPlayground);
+        h.type_text("fn \\w+");
+        h.type_keys("enter");
+        assert!(focused_playground(&h).is_none());
+        let ws = h.stoat.active_workspace();
+        assert!(ws.regex_playgrounds.is_empty());
+        let finder = h.stoat.code_search.as_ref().expect("code search open");
+        assert_eq!(finder.input.text(ws), "fn \\w+");
+    }
+}
@@ -1,7 +1,9 @@
+pub(crate) mod agent_permission;
 pub(crate) mod badges;
 pub(crate) mod chrome;
 pub(crate) mod code_action;
 pub(crate) mod code_search;
+pub(crate) mod color_picker;
 pub(crate) mod command_palette;
 pub(crate) mod commits;
 pub(crate) mod completion;
@@ -12,16 +14,20 @@ pub(crate) mod dock;
 pub(crate) mod editor;
 pub(crate) mod file_finder;
 pub(crate) mod help;
+pub(crate) mod hex_view;
 pub(crate) mod hints;
 pub(crate) mod hover;
 pub(crate) mod jumplist_picker;
 pub(crate) mod layout;
 pub(crate) mod location_picker;
 pub(crate) mod pane;
+#[cfg(feature = "perf")]
+pub(crate) mod perf_overlay;
 pub(crate) mod picker;
 pub(crate) mod popout;
 pub(crate) mod quit_all_confirm;
 pub(crate) mod rebase;
+pub(crate) mod regex_playground;
 pub(crate) mod rename_input;
 pub(crate) mod review;
 pub(crate) mod reword;
@@ -32,7 +38,9 @@ pub(crate) mod symbol_finder;
 pub(crate) mod symbol_picker;
 pub(crate) mod term_pane;
 pub(crate) mod text;
+pub(crate) mod toast;
 pub(crate) mod undercurl;
+pub(crate) mod undo_tree_picker;
 pub(crate) mod workspace_picker;
 
 use self::undercurl::UndercurlSpan;
@@ -40,13 +48,16 @@ use crate::{
     app::{self, Stoat},
     buffer::BufferId,
     buffer_registry::BufferRegistry,
+    display_map::WrapRules,
     editor_state::{EditorId, EditorState},
+    hex_view::{HexId, HexView},
     keymap_state::{
         binding_display_desc, cursor_token, focus_flags, Flags, FocusFlags, StoatKeymapState,
     },
     minimap::MinimapContent,
     pane::{DockVisibility, FocusTarget, View},
     rebase::RebasePause,
+    regex_playground::{PlaygroundId, RegexPlayground},
     run::{RunId, RunState},
     term_session::{TermId, TermSession},
     workspace::{Workspace, WorkspaceId},
@@ -58,7 +69,7 @@ use std::{
     hash::{Hash, Hasher},
     path::Path,
 };
-use stoat_config::{LineNumbers, MinimapMode, WrapMode};
+use stoat_config::{LineNumbers, MinimapMode, WrapBreak, WrapMode};
 use stoatty_widgets::{minimap::Minimap, popover::Popover, ApcScene};
 
 /// Full-cell text scale under stoatty, in 256ths of a cell, for grid-size modal
@@ -86,6 +97,8 @@ pub(crate) struct PaneCtx<'a> {
     pub(crate) buffers: &'a BufferRegistry,
     pub(crate) runs: &'a SlotMap<RunId, RunState>,
     pub(crate) terms: &'a SlotMap<TermId, TermSession>,
+    pub(crate) hex_views: &'a SlotMap<HexId, HexView>,
+    pub(crate) regex_playgrounds: &'a mut SlotMap<PlaygroundId, RegexPlayground>,
 }
 
 /// The lookup and colors a pane needs to declare its minimap strip.
@@ -180,9 +193,14 @@ pub(crate) struct FrameCtx<'a> {
     /// `ui.search.match` style so users see all hits at once.
     pub(crate) search_query: Option<&'a str>,
     /// How document editor panes number the gutter, resolved from
-    /// `editor.line_numbers` (default [`LineNumbers::Relative`]).
+    /// `editor.line_numbers` and the `ToggleRelativeNumbers` override (default
+    /// [`LineNumbers::Relative`]).
     /// [`LineNumbers::Off`] keeps the diagnostic-only gutter column.
     pub(crate) line_numbers: LineNumbers,
+    /// Whether the focused editor pane washes its cursor line with
+    /// `ui.cursorline`, resolved from `editor.cursor_line` and the
+    /// `ToggleCursorLine` override (default off).
+    pub(crate) cursor_line: bool,
     /// How document editor panes soft-wrap long lines, resolved from
     /// `editor.wrap` (default [`WrapMode::EditorWidth`]). Applied only to pane
     /// editors. Non-pane inputs and pickers never wrap.
@@ -191,6 +209,10 @@ pub(crate) struct FrameCtx<'a> {
     /// `editor.wrap_column` (default 80, at least 1). Ignored by the other wrap
     /// modes.
     pub(crate) wrap_column: u32,
+    /// Where soft wrap breaks lines and how far continuation rows indent,
+    /// resolved from `editor.wrap_break` (default word) and
+    /// `editor.wrap_indent` (default 0).
+    pub(crate) wrap_rules: WrapRules,
     /// Fraction an unfocused pane's colors blend toward the theme background,
     /// resolved from `ui.inactive_dim` (default 0.25, clamped to `0.0..=1.0`).
     /// `0.0` disables dimming. Applied by [`crate::render::pane::render_pane`]
@@ -212,6 +234,9 @@ pub(crate) struct FrameCtx<'a> {
     /// moved over a pane. The focused editor resolves the diagnostic under it
     /// to raise a hover popover.
     pub(crate) hover_cell: Option<(u16, u16)>,
+    /// Whether the focused editor's status bar lists the outline items around
+    /// its cursor, from `editor.breadcrumbs`.
+    pub(crate) breadcrumbs: bool,
     /// The user home directory, for `~`-abbreviating run-pane prompt cwds.
     /// Resolved through [`crate::host::EnvHost`] so tests control it instead of
     /// the paint reading the real environment. `None` when `$HOME` is unset.
@@ -276,13 +301,13 @@ pub(crate) fn hints_overlay_area(size: Rect) -> Rect {
 /// exclusive overlays of the frame's modal chain.
 fn modal_overlay_open(stoat: &Stoat) -> bool {
     stoat.modal_run.is_some()
-        || stoat.help.is_some()
-        || stoat.file_finder.is_some()
+        || crate::overlay::top(stoat).is_some()
         || stoat.symbol_finder.is_some()
-        || stoat.command_palette.is_some()
         || stoat.workspace_picker.is_some()
         || stoat.quit_all_confirm.is_some()
+        || !stoat.agent_permissions.is_empty()
         || stoat.jumplist_picker.is_some()
+        || stoat.undo_tree_picker.is_some()
         || stoat.diagnostics_picker.is_some()
         || stoat.location_picker.is_some()
         || stoat.code_search.is_some()
@@ -291,8 +316,9 @@ fn modal_overlay_open(stoat: &Stoat) -> bool {
 /// Paint one full frame of the TUI into `buf`. Called once per [`Stoat::render`]
 /// tick after the parse pipeline and commits pump have run.
 ///
-/// Retires an expired [`Stoat::pending_message`] up front, then hands the live
-/// one to the panes as a status-bar segment. The panes always keep full height.
+/// Retires an expired [`Stoat::pending_message`] and any expired toasts up
+/// front, then hands the live message to the panes as a status-bar segment. The
+/// panes always keep full height.
 pub(crate) fn frame(
     stoat: &mut Stoat,
     buf: &mut Buffer,
@@ -308,6 +334,7 @@ pub(crate) fn frame(
         stoat.pending_message_deadline = None;
         stoat.pending_message_expiry = None;
     }
+    stoat.toasts.retire_expired(stoat.executor.now());
 
     let mode = stoat.focused_mode().to_string();
     let minimap_mode = stoat.minimap_mode();
@@ -350,10 +377,15 @@ pub(crate) fn frame(
 
     let lsp_pending = stoat.lsp_pending_label();
     let diff_warm_busy = stoat.diff_warm_busy();
+    let which_key_due = stoat.which_key_due();
 
     let ws = &mut stoat.workspaces[stoat.active_workspace];
 
+    #[cfg(feature = "perf")]
+    let laid_out = std::time::Instant::now();
     ws.layout(size);
+    #[cfg(feature = "perf")]
+    stoat.perf.record_layout(laid_out.elapsed());
 
     let screen = crate::keymap_state::view_predicate(ws);
 
@@ -425,12 +457,14 @@ pub(crate) fn frame(
         diagnostics: &stoat.diagnostics,
         lsp_registry: &stoat.lsp_registry,
         search_query: stoat.last_search.as_ref().map(|s| s.query.as_str()),
-        line_numbers: stoat
-            .settings
-            .editor_line_numbers
-            .unwrap_or(LineNumbers::Relative),
+        line_numbers: stoat.line_numbers(),
+        cursor_line: stoat.cursor_line_enabled(),
         wrap_mode: stoat.settings.editor_wrap.unwrap_or(WrapMode::EditorWidth),
         wrap_column: stoat.settings.editor_wrap_column.unwrap_or(80).max(1),
+        wrap_rules: WrapRules {
+            word_boundaries: stoat.settings.editor_wrap_break != Some(WrapBreak::Anywhere),
+            continuation_indent: stoat.settings.editor_wrap_indent.unwrap_or(0),
+        },
         inactive_dim: stoat
             .settings
             .ui_inactive_dim
@@ -440,6 +474,7 @@ pub(crate) fn frame(
         minimap_chrome,
         minimap_band: single_minimap_rect,
         hover_cell: stoat.hover_cell,
+        breadcrumbs: stoat.settings.editor_breadcrumbs == Some(true),
         home: stoat.home.as_deref(),
         #[cfg(feature = "perf")]
         perf: PerfSegment::capture(&stoat.perf),
@@ -456,7 +491,7 @@ pub(crate) fn frame(
     }
 
     let split_focused = ws.panes.focus();
-    let mut lsp_badge_rect: Option<Rect> = None;
+    let mut status_hits = pane::StatusHits::default();
     for (id, pane) in ws.panes.split_panes() {
         let is_focused = matches!(ws.focus, FocusTarget::SplitPane) && id == split_focused;
         if Some(id) == overlay_pane {
@@ -473,16 +508,27 @@ pub(crate) fn frame(
                 buffers: &ws.buffers,
                 runs: &ws.runs,
                 terms: &ws.terms,
+                hex_views: &ws.hex_views,
+                regex_playgrounds: &mut ws.regex_playgrounds,
             },
             frame,
             buf,
             scene,
             undercurls,
-            &mut lsp_badge_rect,
+            &mut status_hits,
         );
     }
-    stoat.lsp_badge_rect = lsp_badge_rect;
-    if lsp_badge_rect.is_none() {
+    #[cfg(feature = "perf")]
+    stoat.perf.record_lines(
+        ws.panes
+            .split_panes()
+            .filter(|(id, pane)| Some(*id) != overlay_pane && matches!(pane.view, View::Editor(_)))
+            .map(|(_, pane)| usize::from(layout::split_pane_status(pane.area).0.height))
+            .sum(),
+    );
+    stoat.lsp_badge_rect = status_hits.lsp_badge;
+    stoat.breadcrumb_rects = status_hits.breadcrumbs;
+    if stoat.lsp_badge_rect.is_none() {
         stoat.lsp_badge_hovered = false;
     }
 
@@ -539,6 +585,7 @@ pub(crate) fn frame(
                         cherry_picked_commit,
                         original_message,
                         input,
+                        ..
                     } => Some((
                         cherry_picked_commit.clone(),
                         original_message.clone(),
@@ -583,6 +630,8 @@ pub(crate) fn frame(
                     buffers: &ws.buffers,
                     runs: &ws.runs,
                     terms: &ws.terms,
+                    hex_views: &ws.hex_views,
+                    regex_playgrounds: &mut ws.regex_playgrounds,
                 },
                 frame,
                 buf,
@@ -595,6 +644,7 @@ pub(crate) fn frame(
     completion::render_completion(stoat, buf, &mut *scene);
     code_action::render_code_action(stoat, buf, &mut *scene);
     rename_input::render_rename_input(stoat, buf, &mut *scene);
+    color_picker::render_color_picker(stoat, buf, &mut *scene);
     symbol_picker::render_symbol_picker(stoat, buf, &mut *scene);
     let ws = &mut stoat.workspaces[stoat.active_workspace];
     badges::sync_agent_badge(&mut ws.badges, ws.agent.as_ref());
@@ -606,6 +656,11 @@ pub(crate) fn frame(
         &stoat.theme,
         buf,
     );
+    toast::render_toasts(&stoat.toasts, &stoat.theme, size, buf, &mut *scene);
+    #[cfg(feature = "perf")]
+    if stoat.perf_overlay {
+        perf_overlay::render_perf_overlay(&stoat.perf, &stoat.theme, size, buf, &mut *scene);
+    }
     if let Some(run_id) = stoat.modal_run {
         if let Some(run_state) = ws.runs.get(run_id) {
             run_pane::render_modal_run(run_state, &stoat.theme, full, buf, &mut *scene);
@@ -716,6 +771,23 @@ pub(crate) fn frame(
             buf,
             &mut *scene,
         );
+    } else if let Some(request) = stoat.agent_permissions.front() {
+        agent_permission::render_agent_permission(request, &stoat.theme, full, buf, &mut *scene);
+        let bindings: Vec<(&'static str, String)> = vec![
+            ("y", "allow once".to_string()),
+            ("a", "always allow".to_string()),
+            ("n", "deny".to_string()),
+            ("Esc", "deny".to_string()),
+        ];
+        hints::render_hints(
+            "agent",
+            &bindings,
+            None,
+            &stoat.theme,
+            full,
+            buf,
+            &mut *scene,
+        );
     } else if let Some(picker) = &stoat.jumplist_picker {
         jumplist_picker::render_jumplist_picker(picker, &stoat.theme, full, buf, &mut *scene);
         let bindings = picker.hint_bindings();
@@ -728,6 +800,18 @@ pub(crate) fn frame(
             buf,
             &mut *scene,
         );
+    } else if let Some(picker) = &stoat.undo_tree_picker {
+        undo_tree_picker::render_undo_tree_picker(picker, &stoat.theme, full, buf, &mut *scene);
+        let bindings = picker.hint_bindings();
+        hints::render_hints(
+            "undo tree",
+            &bindings,
+            None,
+            &stoat.theme,
+            full,
+            buf,
+            &mut *scene,
+        );
     } else if let Some(picker) = &stoat.diagnostics_picker {
         diagnostics_picker::render_diagnostics_picker(
             picker,
@@ -766,8 +850,27 @@ pub(crate) fn frame(
             buf,
             &mut *scene,
         );
+    } else if let Some(pending) = &stoat.pending_keys {
+        // A started key sequence lists the chords that can finish it, titled
+        // by the chords pressed so far, once `ui.which_key_delay` has passed.
+        if which_key_due {
+            let bindings: Vec<(&str, String)> = pending
+                .hints
+                .iter()
+                .map(|(keys, desc)| (keys.as_str(), desc.clone()))
+                .collect();
+            hints::render_hints(
+                &pending.label(),
+                &bindings,
+                None,
+                &stoat.theme,
+                full,
+                buf,
+                &mut *scene,
+            );
+        }
     } else if mode != "space_pane_display"
-        && (!PRIMARY_MODES.contains(&mode.as_str())
+        && ((!PRIMARY_MODES.contains(&mode.as_str()) && which_key_due)
             || screen == Some("review")
             || screen == Some("conflict")
             || stoat.key_hints_visible)
@@ -850,13 +953,19 @@ pub(crate) fn frame(
         } else {
             None
         };
+        // A count typed ahead of a motion or operator shows under the bindings
+        // it will multiply, unless a screen footer already owns the row.
+        let count_footer = stoat.pending_count.map(|count| hints::HintsFooter {
+            text: format!("count {count}"),
+            style: stoat.theme.get(crate::theme::scope::UI_TEXT),
+        });
         let footer = if screen == Some("review") {
             stoat
                 .review_footer_cache
                 .as_ref()
                 .and_then(|c| c.1.as_ref())
         } else {
-            conflict_footer.as_ref()
+            conflict_footer.as_ref().or(count_footer.as_ref())
         };
 
         let hint_label = match screen {
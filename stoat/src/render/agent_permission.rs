@@ -0,0 +1,55 @@
+use crate::{agent_permission::AgentPermissionRequest, render::text::write_str};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Clear, Widget},
+};
+
+pub(crate) fn render_agent_permission(
+    request: &AgentPermissionRequest,
+    theme: &crate::theme::Theme,
+    area: Rect,
+    buf: &mut Buffer,
+    scene: &mut stoatty_widgets::ApcScene,
+) {
+    if area.width < 50 || area.height < 7 {
+        return;
+    }
+
+    let box_width = 70u16.min(area.width.saturating_sub(4));
+    if box_width < 50 {
+        return;
+    }
+    let box_height = if request.detail.is_some() { 5 } else { 3 };
+    let x = area.x + (area.width.saturating_sub(box_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(box_height)) / 2;
+    let modal_area = Rect::new(x, y, box_width, box_height);
+
+    let modal_style = theme.get(crate::theme::scope::UI_MODAL_PICKER);
+    Clear.render(modal_area, buf);
+    let inner = crate::render::chrome::modal_frame(
+        buf,
+        modal_area,
+        Some(" agent permission "),
+        modal_style,
+        theme,
+        scene,
+    );
+
+    let row_style = theme.get(crate::theme::scope::UI_TEXT);
+    let prompt_style = theme.get(crate::theme::scope::UI_PROMPT);
+    let max_display = inner.width as usize;
+
+    let prompt = format!("claude wants to run {}", request.tool);
+    let prompt: String = prompt.chars().take(max_display).collect();
+    write_str(buf, inner.x, inner.y, &prompt, prompt_style);
+
+    if let Some(detail) = &request.detail {
+        let first_line = detail.lines().next().unwrap_or_default();
+        let trimmed: String = first_line
+            .chars()
+            .take(max_display.saturating_sub(2))
+            .collect();
+        write_str(buf, inner.x + 2, inner.y + 2, &trimmed, row_style);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    app::Stoat,
+    color_swatch::parse_color,
+    pane::{FocusTarget, View},
+    render::{editor::render_editor, layout::split_pane_status, text::write_str},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Color,
+    widgets::{Clear, Widget},
+};
+
+/// Paint the color picker anchored to the edited literal in the focused
+/// editor: the embedded [`crate::input_view::InputView`] on the first row
+/// and a swatch of the typed color with its channels on the second.
+///
+/// No-op when the picker is closed or the focused pane is not an editor.
+pub(crate) fn render_color_picker(
+    stoat: &mut Stoat,
+    buf: &mut Buffer,
+    scene: &mut stoatty_widgets::ApcScene,
+) {
+    let Some((anchor_offset, editor_id)) = stoat
+        .color_input
+        .as_ref()
+        .map(|s| (s.anchor_offset, s.input.editor_id))
+    else {
+        return;
+    };
+    let typed = stoat
+        .color_input
+        .as_ref()
+        .map(|s| s.input.text(stoat.active_workspace()))
+        .unwrap_or_default();
+
+    let ws = stoat.active_workspace_mut();
+    let FocusTarget::SplitPane = ws.focus else {
+        return;
+    };
+    let pane = ws.panes.pane(ws.panes.focus());
+    let (content_area, _) = split_pane_status(pane.area);
+    let View::Editor(source_id) = pane.view else {
+        return;
+    };
+    let Some(cursor_screen) = ws.editors.get_mut(source_id).and_then(|editor| {
+        crate::render::hover::cursor_screen_position(editor, content_area, anchor_offset)
+    }) else {
+        return;
+    };
+
+    let modal_style = stoat.theme.get(crate::theme::scope::UI_MODAL_HINTS);
+    let popup_width = 28.min(content_area.width);
+    let popup_height: u16 = 4;
+    let popup_x = cursor_screen
+        .0
+        .min(content_area.x + content_area.width.saturating_sub(popup_width));
+    let popup_y = if cursor_screen.1 + 1 + popup_height <= content_area.y + content_area.height {
+        cursor_screen.1 + 1
+    } else if cursor_screen.1 >= content_area.y + popup_height {
+        cursor_screen.1 - popup_height
+    } else {
+        content_area.y
+    };
+    let popup_area = Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    Clear.render(popup_area, buf);
+    let inner = crate::render::chrome::modal_frame(
+        buf,
+        popup_area,
+        Some(" color "),
+        modal_style,
+        &stoat.theme,
+        scene,
+    );
+    if inner.height < 2 {
+        return;
+    }
+
+    let preview_y = inner.y + 1;
+    match parse_color(typed.trim()) {
+        Some([r, g, b]) => {
+            write_str(
+                buf,
+                inner.x,
+                preview_y,
+                "████",
+                modal_style.fg(Color::Rgb(r, g, b)),
+            );
+            let channels = format!(" {r:>3} {g:>3} {b:>3}");
+            write_str(buf, inner.x + 4, preview_y, &channels, modal_style);
+        },
+        None => {
+            let dim = modal_style.patch(stoat.theme.get(crate::theme::scope::UI_TEXT_DIM));
+            write_str(buf, inner.x, preview_y, "not a color", dim);
+        },
+    }
+
+    let theme = stoat.theme.clone();
+    let ws = stoat.active_workspace_mut();
+    if let Some(editor) = ws.editors.get_mut(editor_id) {
+        let input_area = Rect { height: 1, ..inner };
+        render_editor(editor, input_area, modal_style, &theme, buf, true);
+    }
+}
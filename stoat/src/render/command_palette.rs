@@ -9,6 +9,7 @@ use ratatui::{
     layout::Rect,
     widgets::{Block, Borders, Clear, Widget},
 };
+use std::collections::HashMap;
 use stoat_action::registry::RegistryEntry;
 
 const LIST_ROWS: u16 = 10;
@@ -141,6 +142,8 @@ pub(crate) fn render_command_palette(
         &palette.input,
         &palette.filtered,
         &palette.match_indices,
+        &palette.labels,
+        &palette.key_hints,
         palette.selected,
         scope,
         ws,
@@ -349,6 +352,8 @@ fn render_palette_filter(
     input: &InputView,
     filtered: &[&'static RegistryEntry],
     match_indices: &[Vec<u32>],
+    labels: &[Option<String>],
+    key_hints: &HashMap<String, String>,
     selected: usize,
     scope: PaletteScope,
     ws: &mut Workspace,
@@ -406,7 +411,17 @@ fn render_palette_filter(
 
     let list = layout.list;
     let scroll = selected.saturating_sub(list.height.saturating_sub(1) as usize);
-    paint_palette_rows(filtered, match_indices, selected, list, scroll, theme, buf);
+    paint_palette_rows(
+        filtered,
+        match_indices,
+        labels,
+        key_hints,
+        selected,
+        list,
+        scroll,
+        theme,
+        buf,
+    );
 
     let doc = layout.doc;
     if doc.height > 0 {
@@ -432,14 +447,20 @@ fn render_palette_filter(
 
 /// Paint command-palette result rows into `area` starting at `start_row`, one
 /// row per entry, with the selected row, fuzzy-match characters, and each
-/// entry's short description highlighted.
+/// entry's short description highlighted. A row with a `labels` entry shows
+/// that configured alias in place of the command name, and a row whose action
+/// has a `key_hints` entry shows that key right-aligned, clipping the
+/// description short of it.
 ///
 /// Shared by the live list, which derives `start_row` from the selection, and
 /// the smooth-scroll pool, which paints absolute pages, so both render
 /// identical rows.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn paint_palette_rows(
     filtered: &[&'static RegistryEntry],
     match_indices: &[Vec<u32>],
+    labels: &[Option<String>],
+    key_hints: &HashMap<String, String>,
     selected: usize,
     area: Rect,
     start_row: usize,
@@ -456,11 +477,18 @@ pub(crate) fn paint_palette_rows(
     let desc_style = theme.get(crate::theme::scope::UI_TEXT_MUTED);
     let match_style = theme.get(crate::theme::scope::UI_SEARCH_MATCH);
 
+    let label = |abs: usize, entry: &'static RegistryEntry| {
+        labels
+            .get(abs)
+            .and_then(Option::as_deref)
+            .unwrap_or(entry.command_name.as_str())
+    };
     let name_col_width: usize = filtered
         .iter()
+        .enumerate()
         .skip(start_row)
         .take(rows)
-        .map(|e| e.command_name.len())
+        .map(|(abs, e)| label(abs, *e).chars().count())
         .max()
         .unwrap_or(0);
 
@@ -481,7 +509,7 @@ pub(crate) fn paint_palette_rows(
             buf[(col, row)].set_char(' ').set_style(style);
         }
 
-        let name = entry.command_name.as_str();
+        let name = label(abs, *entry);
         let name_x = area.x + 1;
         write_str(buf, name_x, row, name, style);
         let indices = match_indices.get(abs).unwrap_or(&empty_indices);
@@ -494,10 +522,28 @@ pub(crate) fn paint_palette_rows(
                 buf[(col, row)].set_style(match_style);
             }
         }
+        let desc_style = if is_selected { style } else { desc_style };
         let desc_col = area.x + 1 + name_col_width as u16 + 2;
-        if desc_col < end_x {
-            let desc_style = if is_selected { style } else { desc_style };
-            write_str(buf, desc_col, row, entry.def.short_desc(), desc_style);
+        match key_hints.get(entry.def.name()) {
+            Some(key) => {
+                let key_x = end_x.saturating_sub(key.chars().count() as u16 + 1);
+                write_str_clipped(buf, key_x, row, key, desc_style, end_x);
+                let desc_end = key_x.saturating_sub(2);
+                if desc_col < desc_end {
+                    write_str_clipped(
+                        buf,
+                        desc_col,
+                        row,
+                        entry.def.short_desc(),
+                        desc_style,
+                        desc_end,
+                    );
+                }
+            },
+            None if desc_col < end_x => {
+                write_str(buf, desc_col, row, entry.def.short_desc(), desc_style);
+            },
+            None => {},
         }
     }
 }
@@ -1,7 +1,11 @@
 use super::TEXT_SCALE_COMPACT;
 use crate::{
     diff_map::DiffHunkStatus,
-    display_map::{tab_map, BlockRowKind, DisplayPoint, DisplaySnapshot},
+    display_map::{
+        bidi, syntax_theme::style_to_highlight_style, tab_map, BlockContext, BlockId, BlockRowKind,
+        DisplayMapId, DisplayPoint, DisplaySnapshot, HighlightKey, HighlightLayer, InlayKind,
+        VisualOrder, WrapRules,
+    },
     editor_state::{EditorState, SearchMatchCache},
     host::OffsetEncoding,
     lsp::registry::LspRegistry,
@@ -28,7 +32,7 @@ use std::{
     sync::Arc,
 };
 use stoat_config::{LineNumbers, WrapMode};
-use stoat_text::{cursor_offset, Bias, Rope};
+use stoat_text::{cursor_offset, Bias, Point, Rope};
 use stoatty_protocol::command::IconKind;
 use stoatty_widgets::{
     bar::Bar,
@@ -76,6 +80,7 @@ pub(crate) fn render_editor(
         false,
         LineNumbers::Off,
         false,
+        false,
         None,
         None,
         None,
@@ -85,6 +90,7 @@ pub(crate) fn render_editor(
         0.0,
         WrapMode::None,
         80,
+        WrapRules::default(),
     );
 }
 
@@ -98,6 +104,7 @@ pub(crate) fn render_editor_with_overlay(
     is_focused: bool,
     minimap_enabled: bool,
     line_numbers: LineNumbers,
+    cursor_line: bool,
     insert_mode: bool,
     hover_cell: Option<(u16, u16)>,
     goto_word_labels: Option<&BTreeMap<String, usize>>,
@@ -108,6 +115,7 @@ pub(crate) fn render_editor_with_overlay(
     dim: f32,
     wrap: WrapMode,
     wrap_column: u32,
+    wrap_rules: WrapRules,
 ) {
     editor.viewport_rows = Some(inner.height as u32);
     editor.cursor_screen_cell = None;
@@ -165,6 +173,7 @@ pub(crate) fn render_editor_with_overlay(
         WrapMode::Bounded => Some(u32::from(text_width).max(1).min(wrap_column)),
     };
     editor.display_map.set_wrap_width(wrap_width);
+    editor.display_map.set_wrap_rules(wrap_rules);
 
     let snapshot = editor.display_map.snapshot();
     let visible_rows = inner.height as u32;
@@ -299,6 +308,19 @@ pub(crate) fn render_editor_with_overlay(
         "painted gutter width matches the measured width the wrap used",
     );
 
+    if line_numbers != LineNumbers::Off {
+        paint_gutter_affordances(
+            &snapshot,
+            editor.scroll_row,
+            end_row,
+            inner,
+            gutter_w,
+            hover_cell,
+            theme,
+            buf,
+        );
+    }
+
     // Inset the text rect by the gutter, and record the width so click-to-offset
     // subtracts the same shift. Written after the `row_severity` borrow ends.
     let inner = Rect {
@@ -330,48 +352,35 @@ pub(crate) fn render_editor_with_overlay(
     let right = inner.x + inner.width;
     let bottom = inner.y + inner.height;
 
-    {
-        let mut x = inner.x;
-        let mut y = inner.y;
-        let inlay_style = fallback_style.patch(theme.get(crate::theme::scope::UI_VIRTUAL_INLAY));
-        'chunks: for chunk in snapshot.highlighted_chunks_cached(
-            editor.scroll_row..end_row,
-            &mut editor.highlight_endpoint_cache,
-        ) {
-            let style = if chunk.is_inlay {
-                inlay_style
-            } else {
-                chunk
-                    .highlight_style
-                    .as_ref()
-                    .map(|hs| hs.to_ratatui_style())
-                    .unwrap_or(fallback_style)
-            };
-            for ch in chunk.text.chars() {
-                if ch == '\n' {
-                    y += 1;
-                    x = inner.x;
-                    if y >= bottom {
-                        break 'chunks;
-                    }
-                    continue;
-                }
-                if x >= right {
-                    continue;
-                }
-                buf[(x, y)].set_char(ch).set_style(style);
-                x += 1;
-            }
-        }
-    }
-
-    let buffer_snapshot = snapshot.buffer_snapshot();
     let visible = visible_byte_range(
         &snapshot,
-        buffer_snapshot.rope(),
+        snapshot.buffer_snapshot().rope(),
         editor.scroll_row,
         end_row,
     );
+    let snapshot = if sync_search_highlights(editor, &snapshot, search_query, &visible, theme) {
+        editor.display_map.snapshot()
+    } else {
+        snapshot
+    };
+
+    let inlay_style = fallback_style.patch(theme.get(crate::theme::scope::UI_VIRTUAL_INLAY));
+    // Rows painted with right-to-left text, reordered visually once every
+    // logical paint of the row is down.
+    let rtl_rows = paint_text_layer(
+        editor,
+        &snapshot,
+        inner,
+        end_row,
+        fallback_style,
+        inlay_style,
+        buf,
+    );
+    let visual_orders = visual_orders(&snapshot, &rtl_rows, editor.scroll_row, inner);
+
+    paint_pinned_blocks(&snapshot, editor.scroll_row, inner, fallback_style, buf);
+
+    let buffer_snapshot = snapshot.buffer_snapshot();
 
     if let Some((path, set, registry)) = diagnostic_info {
         let rope = buffer_snapshot.rope();
@@ -399,78 +408,33 @@ pub(crate) fn render_editor_with_overlay(
         );
     }
 
-    if let Some(query) = search_query.filter(|q| !q.is_empty()) {
-        let version = buffer_snapshot.version();
-        let rope = buffer_snapshot.rope();
-        let stale = match &editor.search_match_cache {
-            Some(cache) => {
-                cache.version != version || cache.query != query || cache.visible != visible
-            },
-            None => true,
-        };
-        if stale {
-            // Reuse the compiled regex while the query text holds, so only a new
-            // query pays a fresh compile. A cached None from a failed compile is
-            // reused too, so an invalid query does not recompile every frame.
-            let (mut window, regex) = match editor.search_match_cache.take() {
-                Some(cache) if cache.query == query => (cache.window, cache.regex),
-                Some(cache) => (
-                    cache.window,
-                    crate::action_handlers::search::compile_search_regex(query).ok(),
-                ),
-                None => (
-                    String::new(),
-                    crate::action_handlers::search::compile_search_regex(query).ok(),
-                ),
-            };
-            window.clear();
-            for chunk in rope.chunks_in_range(visible.clone()) {
-                window.push_str(chunk);
-            }
-            let matches = match &regex {
-                Some(regex) => regex
-                    .find_iter(&window)
-                    .filter(|m| m.end() > m.start())
-                    .map(|m| (m.start() + visible.start, m.end() + visible.start))
-                    .collect(),
-                None => Vec::new(),
-            };
-            editor.search_match_cache = Some(SearchMatchCache {
-                version,
-                query: query.to_string(),
-                visible: visible.clone(),
-                matches,
-                window,
-                regex,
-            });
-        }
-
-        let match_style = theme.get(crate::theme::scope::UI_SEARCH_MATCH);
-        let cache = editor.search_match_cache.as_ref().expect("set above");
-        for &(match_start, match_end) in &cache.matches {
-            paint_offset_range(
-                rope,
-                &snapshot,
-                match_start..match_end,
-                None,
-                &mut |_, _, cell| {
-                    cell.set_style(match_style);
-                },
-                editor.scroll_row,
-                end_row,
-                inner,
-                right,
-                bottom,
-                buf,
-                None,
-            );
-        }
-    }
-
     if !is_focused {
+        reorder_bidi_cells(&visual_orders, editor.scroll_row, inner, buf);
         return;
     }
 
+    // Painted before the selections so a selection on the cursor line keeps
+    // its own background.
+    if cursor_line {
+        let sel = editor.selections.newest_anchor();
+        let cursor = cursor_offset(
+            buffer_snapshot.rope(),
+            buffer_snapshot.resolve_anchor(&sel.tail()),
+            buffer_snapshot.resolve_anchor(&sel.head()),
+        );
+        paint_cursor_line(
+            &snapshot,
+            cursor,
+            theme.get(crate::theme::scope::UI_CURSOR_LINE),
+            fallback_style,
+            editor.scroll_row,
+            end_row,
+            inner,
+            right,
+            buf,
+        );
+    }
+
     let selection_style = theme.get(crate::theme::scope::UI_SELECTION_EDITOR);
     let cursor_style = theme.cursor_style();
     let primary_id = editor.selections.newest_anchor().id;
@@ -534,7 +498,21 @@ pub(crate) fn render_editor_with_overlay(
         }
     }
 
-    editor.cursor_screen_cell = primary_cell;
+    // Selections and painted cursors were laid down logically and move with
+    // the characters they cover. The delegated cursor cell is mapped instead,
+    // and placed where the terminal's font draws it rather than on the grid.
+    reorder_bidi_cells(&visual_orders, editor.scroll_row, inner, buf);
+    editor.cursor_screen_cell = primary_cell.map(|(x, y)| {
+        let row = editor.scroll_row + u32::from(y - inner.y);
+        let column = u32::from(x - inner.x);
+        match visual_orders.get(&row) {
+            Some(order) => (inner.x + order.to_visual(column) as u16, y),
+            None => (
+                inner.x + snapshot.measured_x(DisplayPoint::new(row, column)) as u16,
+                y,
+            ),
+        }
+    });
 
     if let Some((path, set, registry)) = diagnostic_info {
         build_diagnostic_span_cache(editor, set, path, rope, registry, buffer_snapshot.version());
@@ -634,8 +612,11 @@ pub(crate) fn render_editor_with_overlay(
                 continue;
             }
             let y = inner.y + (display.row - editor.scroll_row) as u16;
+            let column = visual_orders
+                .get(&display.row)
+                .map_or(display.column, |order| order.to_visual(display.column));
             for (i, ch) in label.chars().enumerate() {
-                let x = inner.x + display.column as u16 + i as u16;
+                let x = inner.x + column as u16 + i as u16;
                 if x >= right || y >= bottom {
                     break;
                 }
@@ -645,6 +626,70 @@ pub(crate) fn render_editor_with_overlay(
     }
 }
 
+/// Visual orders of the viewport rows in `rtl_rows`, skipping rows a pinned
+/// block covers since their cells hold the block rather than the row's text.
+fn visual_orders(
+    snapshot: &DisplaySnapshot,
+    rtl_rows: &[u32],
+    scroll_row: u32,
+    inner: Rect,
+) -> BTreeMap<u32, VisualOrder> {
+    if rtl_rows.is_empty() {
+        return BTreeMap::new();
+    }
+    let pinned: Vec<Range<u32>> = snapshot
+        .pinned_blocks(scroll_row, inner.height as u32)
+        .into_iter()
+        .map(|placement| {
+            let start = scroll_row + placement.viewport_row;
+            start..start + placement.block.height
+        })
+        .collect();
+    rtl_rows
+        .iter()
+        .filter(|row| !pinned.iter().any(|rows| rows.contains(row)))
+        .filter_map(|&row| Some((row, snapshot.visual_order(row)?)))
+        .collect()
+}
+
+/// Move each character's cells on the rows of `orders` from its logical
+/// display column to its visual one, carrying the styles painted on them.
+/// A character whose logical cells fell past the pane's right edge was never
+/// painted, so only its glyph is written.
+fn reorder_bidi_cells(
+    orders: &BTreeMap<u32, VisualOrder>,
+    scroll_row: u32,
+    inner: Rect,
+    buf: &mut Buffer,
+) {
+    let right = inner.x + inner.width;
+    for (&row, order) in orders {
+        let y = inner.y + (row - scroll_row) as u16;
+        let logical: Vec<Option<Cell>> = (0..order.width())
+            .map(|column| {
+                let x = u32::from(inner.x) + column;
+                (x < u32::from(right)).then(|| buf[(x as u16, y)].clone())
+            })
+            .collect();
+        for (ch, from, to, width) in order.moves() {
+            for i in 0..width {
+                let x = u32::from(inner.x) + to + i;
+                if x >= u32::from(right) {
+                    continue;
+                }
+                let cell = &mut buf[(x as u16, y)];
+                match &logical[(from + i) as usize] {
+                    Some(painted) => *cell = painted.clone(),
+                    None if i == 0 => {
+                        cell.set_char(ch);
+                    },
+                    None => {},
+                }
+            }
+        }
+    }
+}
+
 /// Cached gutter severity map for one diagnostic-set version.
 ///
 /// `map` is the per-buffer-row worst severity. Recomputed only when the
@@ -671,6 +716,276 @@ pub(crate) struct GutterGeometryCache {
     lines: Vec<GutterLine>,
 }
 
+/// The text layer's cells from the last paint, one list per visible row, so a
+/// repaint rewalks the highlighted chunks only for rows that changed.
+///
+/// `key` holds the inputs that can restyle any row: the text rect, the display
+/// map and its layout generation, and the two base styles. A mismatch repaints
+/// every row. Otherwise only the rows the display map's row damage covers and
+/// the rows a scroll brings into view are walked again.
+pub(crate) struct TextLayerCache {
+    key: (Rect, DisplayMapId, u64, Style, Style),
+    scroll_row: u32,
+    rows: Vec<Vec<(u16, char, Style)>>,
+    rtl: Vec<bool>,
+}
+
+/// Paint the highlighted text of `scroll_row..end_row` into `inner`, reusing
+/// the undamaged rows of [`EditorState::text_layer`], and return the display
+/// rows holding right-to-left text.
+#[allow(clippy::too_many_arguments)]
+fn paint_text_layer(
+    editor: &mut EditorState,
+    snapshot: &DisplaySnapshot,
+    inner: Rect,
+    end_row: u32,
+    fallback_style: Style,
+    inlay_style: Style,
+    buf: &mut Buffer,
+) -> Vec<u32> {
+    let scroll_row = editor.scroll_row;
+    let height = end_row
+        .saturating_sub(scroll_row)
+        .min(u32::from(inner.height)) as usize;
+    let key = (
+        inner,
+        editor.display_map.id(),
+        editor.display_map.layout_generation(),
+        fallback_style,
+        inlay_style,
+    );
+    let damage = editor.display_map.take_row_damage();
+
+    let mut rows = vec![Vec::new(); height];
+    let mut rtl = vec![false; height];
+    let mut dirty = vec![true; height];
+    if let Some(cache) = editor.text_layer.take().filter(|cache| cache.key == key) {
+        for (i, (cells, is_rtl)) in cache.rows.into_iter().zip(cache.rtl).enumerate() {
+            let Some(slot) = (cache.scroll_row + i as u32)
+                .checked_sub(scroll_row)
+                .map(|slot| slot as usize)
+                .filter(|&slot| slot < height)
+            else {
+                continue;
+            };
+            rows[slot] = cells;
+            rtl[slot] = is_rtl;
+            dirty[slot] = false;
+        }
+        for edit in damage.edits() {
+            let start = snapshot.wrap_row_to_display_row(edit.new.start);
+            // A row-count change shifts every row below it.
+            let end = if edit.old_len() != edit.new_len() {
+                end_row
+            } else if edit.new.is_empty() {
+                continue;
+            } else {
+                snapshot.wrap_row_to_display_row(edit.new.end - 1) + 1
+            };
+            for row in start.max(scroll_row)..end.min(end_row) {
+                if let Some(flag) = dirty.get_mut((row - scroll_row) as usize) {
+                    *flag = true;
+                }
+            }
+        }
+    }
+
+    let right = inner.x + inner.width;
+    let mut endpoints = None;
+    let mut slot = 0;
+    while slot < height {
+        if !dirty[slot] {
+            slot += 1;
+            continue;
+        }
+        let run_end = (slot..height).find(|&s| !dirty[s]).unwrap_or(height);
+        let endpoints = endpoints
+            .get_or_insert_with(|| {
+                snapshot.highlighted_endpoints_cached(
+                    scroll_row..end_row,
+                    &mut editor.highlight_endpoint_cache,
+                )
+            })
+            .clone();
+        for cells in &mut rows[slot..run_end] {
+            cells.clear();
+        }
+        rtl[slot..run_end].fill(false);
+        let mut x = inner.x;
+        let mut row = slot;
+        'chunks: for chunk in snapshot.highlighted_chunks_with_endpoints(
+            scroll_row + slot as u32..scroll_row + run_end as u32,
+            endpoints,
+        ) {
+            let style = if let Some(InlayKind::Swatch([r, g, b])) = chunk.inlay_kind {
+                fallback_style.fg(Color::Rgb(r, g, b))
+            } else if chunk.is_inlay {
+                inlay_style
+            } else {
+                chunk
+                    .highlight_style
+                    .as_ref()
+                    .map(|hs| hs.to_ratatui_style())
+                    .unwrap_or(fallback_style)
+            };
+            for ch in chunk.text.chars() {
+                if ch == '\n' {
+                    row += 1;
+                    x = inner.x;
+                    if row >= run_end {
+                        break 'chunks;
+                    }
+                    continue;
+                }
+                if x >= right {
+                    continue;
+                }
+                if bidi::is_rtl(ch) {
+                    rtl[row] = true;
+                }
+                rows[row].push((x - inner.x, ch, style));
+                x += 1;
+            }
+        }
+        slot = run_end;
+    }
+
+    let mut rtl_rows = Vec::new();
+    for (slot, cells) in rows.iter().enumerate() {
+        let y = inner.y + slot as u16;
+        for &(col, ch, style) in cells {
+            buf[(inner.x + col, y)].set_char(ch).set_style(style);
+        }
+        if rtl[slot] {
+            rtl_rows.push(scroll_row + slot as u32);
+        }
+    }
+    editor.text_layer = Some(TextLayerCache {
+        key,
+        scroll_row,
+        rows,
+        rtl,
+    });
+    rtl_rows
+}
+
+/// Paint the display map's viewport-pinned blocks over the text rows they
+/// cover, blanking each row first so no text shows through past a short line.
+fn paint_pinned_blocks(
+    snapshot: &DisplaySnapshot,
+    scroll_row: u32,
+    inner: Rect,
+    fallback_style: Style,
+    buf: &mut Buffer,
+) {
+    for placement in snapshot.pinned_blocks(scroll_row, inner.height as u32) {
+        let block = &placement.block;
+        let lines = (block.render)(&BlockContext {
+            block_id: BlockId::Custom(block.id),
+            max_width: inner.width as u32,
+            height: block.height,
+            selected: false,
+            anchor_row: scroll_row,
+            diff_status: None,
+            buffer_snapshot: snapshot.buffer_snapshot(),
+        });
+        for (i, line) in lines.iter().take(block.height as usize).enumerate() {
+            let y = inner.y + placement.viewport_row as u16 + i as u16;
+            for x in inner.x..inner.x + inner.width {
+                buf[(x, y)].reset();
+                buf[(x, y)].set_style(fallback_style);
+            }
+            buf.set_line(inner.x, y, line, inner.width);
+        }
+    }
+}
+
+/// Key of the search-match ranges in an editor's display-map highlights.
+const SEARCH_MATCH_KEY: HighlightKey = HighlightKey::layer(HighlightLayer::SearchHighlight);
+
+/// Keep the search-match highlights in `editor`'s display map in step with
+/// `query` over the `visible` byte range, so chunk iteration styles matches
+/// along with every other highlight layer. Matches are rescanned only when the
+/// [`SearchMatchCache`] key moves. Returns `true` when the highlights changed
+/// and `snapshot` no longer reflects them.
+fn sync_search_highlights(
+    editor: &mut EditorState,
+    snapshot: &DisplaySnapshot,
+    query: Option<&str>,
+    visible: &Range<usize>,
+    theme: &crate::theme::Theme,
+) -> bool {
+    let Some(query) = query.filter(|q| !q.is_empty()) else {
+        editor.search_match_cache = None;
+        return editor.display_map.clear_highlights(SEARCH_MATCH_KEY);
+    };
+    let buffer_snapshot = snapshot.buffer_snapshot();
+    let version = buffer_snapshot.version();
+    let rope = buffer_snapshot.rope();
+    let stale = match &editor.search_match_cache {
+        Some(cache) => {
+            cache.version != version || cache.query != query || cache.visible != *visible
+        },
+        None => true,
+    };
+    if stale {
+        // Reuse the compiled regex while the query text holds, so only a new
+        // query pays a fresh compile. A cached None from a failed compile is
+        // reused too, so an invalid query does not recompile every frame.
+        let (mut window, regex) = match editor.search_match_cache.take() {
+            Some(cache) if cache.query == query => (cache.window, cache.regex),
+            Some(cache) => (
+                cache.window,
+                crate::action_handlers::search::compile_search_regex(query).ok(),
+            ),
+            None => (
+                String::new(),
+                crate::action_handlers::search::compile_search_regex(query).ok(),
+            ),
+        };
+        window.clear();
+        for chunk in rope.chunks_in_range(visible.clone()) {
+            window.push_str(chunk);
+        }
+        let matches = match &regex {
+            Some(regex) => regex
+                .find_iter(&window)
+                .filter(|m| m.end() > m.start())
+                .map(|m| (m.start() + visible.start, m.end() + visible.start))
+                .collect(),
+            None => Vec::new(),
+        };
+        editor.search_match_cache = Some(SearchMatchCache {
+            version,
+            query: query.to_string(),
+            visible: visible.clone(),
+            matches,
+            window,
+            regex,
+        });
+    }
+
+    // A theme switch restyles the matches without moving the cache key.
+    let style = style_to_highlight_style(&theme.get(crate::theme::scope::UI_SEARCH_MATCH));
+    let registered = snapshot.text_highlights().get(&SEARCH_MATCH_KEY);
+    if !stale && registered.is_some_and(|set| set.0 == style) {
+        return false;
+    }
+    let cache = editor.search_match_cache.as_ref().expect("set above");
+    let ranges = cache
+        .matches
+        .iter()
+        .map(|&(start, end)| {
+            buffer_snapshot.anchor_at(start, Bias::Right)
+                ..buffer_snapshot.anchor_at(end, Bias::Left)
+        })
+        .collect();
+    editor
+        .display_map
+        .highlight_text(SEARCH_MATCH_KEY, ranges, style);
+    true
+}
+
 /// Build a per-buffer-row map from `path`'s diagnostics, picking the
 /// worst severity (lowest LSP code) when multiple diagnostics overlap
 /// the same row.
@@ -1373,6 +1688,95 @@ fn draw_line_number_gutter(
     }
 }
 
+/// The clickable part of an editor gutter a pane-relative column falls on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GutterZone {
+    /// The line number and the marks beside it. A click selects the line.
+    Number,
+    /// The gutter's last column, where folded lines show their indicator. A
+    /// click folds or unfolds the syntax block starting on the line.
+    Fold,
+}
+
+/// The [`GutterZone`] under pane-relative column `col` for a gutter
+/// `gutter_width` cells wide, or `None` past the gutter. A one-column
+/// diagnostic-only gutter has no fold column.
+pub(crate) fn gutter_zone(gutter_width: u16, col: u16) -> Option<GutterZone> {
+    if col >= gutter_width {
+        None
+    } else if gutter_width > 1 && col == gutter_width - 1 {
+        Some(GutterZone::Fold)
+    } else {
+        Some(GutterZone::Number)
+    }
+}
+
+/// Paint the gutter's fold column and pointer affordances. A row whose buffer
+/// line is folded shows `▸` in the last gutter column. With the pointer over
+/// the gutter, the hovered row's number is underlined, or over the fold column
+/// its indicator brightens, with `▾` standing in on an unfolded line.
+#[allow(clippy::too_many_arguments)]
+fn paint_gutter_affordances(
+    snapshot: &DisplaySnapshot,
+    scroll_row: u32,
+    end_row: u32,
+    area: Rect,
+    gutter_w: u16,
+    hover_cell: Option<(u16, u16)>,
+    theme: &crate::theme::Theme,
+    buf: &mut Buffer,
+) {
+    use crate::theme::scope as s;
+    if gutter_w < 2 {
+        return;
+    }
+    let fold_x = area.x + gutter_w - 1;
+    let buffer_line = |display_row: u32| {
+        snapshot
+            .display_to_buffer(DisplayPoint::new(display_row, 0))
+            .map(|point| point.row)
+    };
+
+    if snapshot.fold_snapshot().fold_count() > 0 {
+        let style = theme.get(s::UI_TEXT_MUTED);
+        for display_row in scroll_row..end_row.min(scroll_row + area.height as u32) {
+            if buffer_line(display_row).is_some_and(|line| snapshot.is_line_folded(line)) {
+                let y = area.y + (display_row - scroll_row) as u16;
+                buf[(fold_x, y)].set_char('▸').set_style(style);
+            }
+        }
+    }
+
+    let Some((hx, hy)) = hover_cell else {
+        return;
+    };
+    if hy < area.y || hy >= area.y + area.height || hx < area.x {
+        return;
+    }
+    let display_row = scroll_row + (hy - area.y) as u32;
+    let Some(line) = buffer_line(display_row).filter(|_| display_row < end_row) else {
+        return;
+    };
+    match gutter_zone(gutter_w, hx - area.x) {
+        Some(GutterZone::Number) => {
+            for x in area.x..fold_x {
+                buf[(x, hy)].modifier.insert(Modifier::UNDERLINED);
+            }
+        },
+        Some(GutterZone::Fold) => {
+            let glyph = if snapshot.is_line_folded(line) {
+                '▸'
+            } else {
+                '▾'
+            };
+            buf[(fold_x, hy)]
+                .set_char(glyph)
+                .set_style(theme.get(s::UI_TEXT));
+        },
+        None => {},
+    }
+}
+
 /// Paint right-aligned cell line numbers, a one-column severity mark left of the
 /// number, and two diff glyph cells (change kind then staged state) right of it,
 /// for a terminal without the sub-cell components. Returns the reserved cell
@@ -1634,13 +2038,51 @@ fn paint_cursor_line_diagnostic(
     }
 }
 
+/// Wash the background of every display row of the buffer line holding
+/// `cursor`, wrapped continuations included, with `style`'s background. Only
+/// cells still on the pane's base background take it, so syntax, search, and
+/// diagnostic backgrounds painted earlier show through.
+#[allow(clippy::too_many_arguments)]
+fn paint_cursor_line(
+    snapshot: &DisplaySnapshot,
+    cursor: usize,
+    style: Style,
+    fallback_style: Style,
+    scroll_row: u32,
+    end_row: u32,
+    inner: Rect,
+    right: u16,
+    buf: &mut Buffer,
+) {
+    let Some(bg) = style.bg else {
+        return;
+    };
+    let rope = snapshot.buffer_snapshot().rope();
+    let line = rope.offset_to_point(cursor).row;
+    let first = snapshot.buffer_to_display(Point::new(line, 0)).row;
+    let last = snapshot
+        .buffer_to_display(Point::new(line, rope.line_len(line)))
+        .row;
+    let base = fallback_style.bg;
+    for row in first.max(scroll_row)..=last.min(end_row.saturating_sub(1)) {
+        let y = inner.y + (row - scroll_row) as u16;
+        for x in inner.x..right {
+            let cell = &mut buf[(x, y)];
+            if cell.bg == Color::Reset || Some(cell.bg) == base {
+                cell.set_bg(bg);
+            }
+        }
+    }
+}
+
 /// Byte offset of the buffer position under the pane-content cell `(col, row)`,
 /// or `None` when it maps to no buffer point.
 ///
 /// `col`/`row` are relative to the pane's content area. `gutter_width` is the
 /// column inset the gutter shifted the text by, subtracted so a cell over the
-/// glyph resolves to that glyph. This is the shared screen-to-offset math both
-/// mouse clicks and the diagnostic popover resolve through.
+/// glyph resolves to that glyph, and a row holding right-to-left text maps the
+/// visual column back to its logical one. This is the shared screen-to-offset
+/// math both mouse clicks and the diagnostic popover resolve through.
 pub(crate) fn display_cell_to_offset(
     snapshot: &DisplaySnapshot,
     scroll_row: u32,
@@ -1650,6 +2092,9 @@ pub(crate) fn display_cell_to_offset(
 ) -> Option<usize> {
     let display_row = scroll_row + row as u32;
     let display_col = (col as u32).saturating_sub(gutter_width as u32);
+    let display_col = snapshot
+        .visual_order(display_row)
+        .map_or(display_col, |order| order.to_logical(display_col));
     let clipped = snapshot.clip_point(DisplayPoint::new(display_row, display_col), Bias::Left);
     let buffer_pt = snapshot.display_to_buffer(clipped)?;
     Some(snapshot.buffer_snapshot().rope().point_to_offset(buffer_pt))
@@ -1884,7 +2329,8 @@ fn render_diagnostic_popover(
 /// [`tab_map::advance_column_for_char`] instead of re-resolving each character.
 /// Re-resolving walks the whole row prefix, so the per-character path is
 /// quadratic in the row length. It is kept only for rows carrying folds,
-/// inlays, or soft wrap, where the display column is not a simple accumulation.
+/// inlays, or soft wrap, and under elastic tabstops, where the display column
+/// is not a simple accumulation.
 #[allow(clippy::too_many_arguments)]
 fn paint_offset_range(
     rope: &Rope,
@@ -1903,7 +2349,10 @@ fn paint_offset_range(
     // Folds shift columns across the whole buffer, so they force the general
     // path everywhere. Inlays only shift the row they sit on, so the fast path
     // is checked per segment against that row rather than the whole buffer.
+    // Elastic tabs take their widths from neighbouring rows, which the
+    // per-character accumulation cannot see.
     let no_folds = snapshot.fold_snapshot().fold_count() == 0;
+    let fixed_tabs = !snapshot.tab_snapshot().elastic_tabstops();
     let inlay_snapshot = snapshot.inlay_snapshot();
     let any_inlays = inlay_snapshot.has_inlays();
     let tab_size = snapshot.tab_snapshot().tab_size();
@@ -1936,6 +2385,7 @@ fn paint_offset_range(
             && (display.row + 1 >= line_count || !snapshot.is_wrap_continuation(display.row + 1));
 
         let simple_row = no_folds
+            && fixed_tabs
             && single_display_row
             && !(any_inlays && inlay_snapshot.has_inlays_in_row_range(point.row..point.row + 1));
         if simple_row {
@@ -2014,7 +2464,7 @@ fn coalesce_runs(painted: &[(u16, u16)], out: &mut Vec<(u16, u16, u16)>) {
 ///
 /// Rows beyond the buffer resolve to the rope length, so the returned range is
 /// always valid to slice.
-fn visible_byte_range(
+pub(crate) fn visible_byte_range(
     snapshot: &DisplaySnapshot,
     rope: &Rope,
     scroll_row: u32,
@@ -2089,6 +2539,17 @@ mod tests {
     /// Render the focused editor with `query` active and return the cached match
     /// byte-ranges.
     fn render_search(stoat: &mut Stoat, area: Rect, query: &str) -> Vec<(usize, usize)> {
+        render_with_query(stoat, area, Some(query));
+        action_handlers::focused_editor_mut(stoat)
+            .expect("focused editor")
+            .search_match_cache
+            .as_ref()
+            .expect("a search render populates the cache")
+            .matches
+            .clone()
+    }
+
+    fn render_with_query(stoat: &mut Stoat, area: Rect, query: Option<&str>) {
         let theme = crate::theme::Theme::empty();
         let fallback = theme.get(crate::theme::scope::UI_TEXT);
         let editor = action_handlers::focused_editor_mut(stoat).expect("focused editor");
@@ -2103,22 +2564,18 @@ mod tests {
             false,
             LineNumbers::Off,
             false,
+            false,
             None,
             None,
-            Some(query),
+            query,
             None,
             None,
             None,
             0.0,
             WrapMode::None,
             80,
+            WrapRules::default(),
         );
-        editor
-            .search_match_cache
-            .as_ref()
-            .expect("a search render populates the cache")
-            .matches
-            .clone()
     }
 
     #[test]
@@ -2152,6 +2609,124 @@ mod tests {
         );
     }
 
+    #[test]
+    fn search_matches_register_as_a_display_map_highlight_set() {
+        let mut h = Stoat::test();
+        open_search_buffer(&mut h, "foo bar foo");
+        let area = Rect::new(0, 0, 20, 4);
+        let registered = |stoat: &mut Stoat| {
+            let editor = action_handlers::focused_editor_mut(stoat).expect("editor");
+            let snapshot = editor.display_map.snapshot();
+            let buffer = snapshot.buffer_snapshot();
+            snapshot
+                .text_highlights()
+                .get(&super::SEARCH_MATCH_KEY)
+                .map(|set| {
+                    set.1
+                        .iter()
+                        .map(|r| buffer.resolve_anchor(&r.start)..buffer.resolve_anchor(&r.end))
+                        .collect::<Vec<_>>()
+                })
+        };
+
+        render_with_query(&mut h.stoat, area, Some("foo"));
+        assert_eq!(registered(&mut h.stoat), Some(vec![0..3, 8..11]));
+
+        render_with_query(&mut h.stoat, area, None);
+        assert_eq!(registered(&mut h.stoat), None, "no query clears the set");
+    }
+
+    #[test]
+    fn pinned_blocks_paint_over_the_viewport_rows() {
+        use crate::display_map::{PinnedBlockProperties, ViewportEdge};
+
+        let mut h = Stoat::test();
+        open_search_buffer(&mut h, "one two three\nfour\nfive\n");
+        let editor = action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        editor
+            .display_map
+            .insert_pinned_blocks(vec![PinnedBlockProperties::from_text(
+                ViewportEdge::Top,
+                None,
+                vec!["pin".to_string()],
+            )]);
+
+        let area = Rect::new(0, 0, 20, 4);
+        let theme = crate::theme::Theme::empty();
+        let fallback = theme.get(crate::theme::scope::UI_TEXT);
+        let editor = action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        let mut buf = Buffer::empty(area);
+        super::render_editor_with_overlay(
+            editor,
+            area,
+            fallback,
+            &theme,
+            &mut buf,
+            false,
+            false,
+            LineNumbers::Off,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            0.0,
+            WrapMode::None,
+            80,
+            WrapRules::default(),
+        );
+        let row = |y: u16| (0..20).map(|x| buf[(x, y)].symbol()).collect::<String>();
+        assert_eq!(
+            row(0).trim_end(),
+            "pin",
+            "the pinned line hides the text under it"
+        );
+        assert_eq!(row(1).trim_end(), "four");
+    }
+
+    #[test]
+    fn rtl_runs_paint_in_visual_order_and_click_back_to_logical() {
+        let mut h = Stoat::test();
+        open_search_buffer(&mut h, "a אבג b\nplain\n");
+        let area = Rect::new(0, 0, 20, 4);
+        let theme = crate::theme::Theme::empty();
+        let fallback = theme.get(crate::theme::scope::UI_TEXT);
+        let editor = action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        let mut buf = Buffer::empty(area);
+        super::render_editor_with_overlay(
+            editor,
+            area,
+            fallback,
+            &theme,
+            &mut buf,
+            false,
+            false,
+            LineNumbers::Off,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            0.0,
+            WrapMode::None,
+            80,
+            WrapRules::default(),
+        );
+        let row = |y: u16| (0..20).map(|x| buf[(x, y)].symbol()).collect::<String>();
+        assert_eq!(row(0).trim_end(), "a גבא b");
+        assert_eq!(row(1).trim_end(), "plain");
+
+        let snapshot = editor.display_map.snapshot();
+        let offset = super::display_cell_to_offset(&snapshot, 0, 0, 2, 0);
+        assert_eq!(offset, Some("a אב".len()), "the leftmost RTL cell is ג");
+    }
+
     #[test]
     fn search_recompiles_on_a_new_query() {
         let mut h = Stoat::test();
@@ -2201,6 +2776,7 @@ mod tests {
             false,
             LineNumbers::Off,
             false,
+            false,
             None,
             None,
             None,
@@ -2210,6 +2786,7 @@ mod tests {
             0.0,
             WrapMode::None,
             80,
+            WrapRules::default(),
         );
 
         let italic: String = (0..area.width)
@@ -2273,6 +2850,7 @@ mod tests {
                 false,
                 LineNumbers::Off,
                 false,
+                false,
                 None,
                 None,
                 None,
@@ -2282,6 +2860,7 @@ mod tests {
                 0.0,
                 WrapMode::None,
                 80,
+                WrapRules::default(),
             );
             (0..area.width).map(|x| buf[(x, 1)].clone()).collect()
         }
@@ -2441,6 +3020,7 @@ mod tests {
             false,
             LineNumbers::Off,
             false,
+            false,
             None,
             None,
             None,
@@ -2450,6 +3030,7 @@ mod tests {
             0.0,
             wrap,
             wrap_column,
+            WrapRules::default(),
         );
         let snapshot = editor.display_map.snapshot();
         (
@@ -2574,6 +3155,7 @@ mod tests {
                 false,
                 LineNumbers::Off,
                 false,
+                false,
                 None,
                 None,
                 None,
@@ -2583,6 +3165,7 @@ mod tests {
                 0.0,
                 WrapMode::EditorWidth,
                 80,
+                WrapRules::default(),
             );
         }
 
@@ -2616,6 +3199,7 @@ mod tests {
             false,
             LineNumbers::Absolute,
             false,
+            false,
             None,
             None,
             None,
@@ -2625,6 +3209,7 @@ mod tests {
             0.0,
             WrapMode::None,
             80,
+            WrapRules::default(),
         );
         editor
             .gutter_geometry_cache
@@ -2686,6 +3271,107 @@ mod tests {
         );
     }
 
+    /// Paint the focused editor's text with no gutter and return each row's
+    /// characters, trailing blanks trimmed.
+    fn paint_text_rows(stoat: &mut Stoat, rows: u16) -> Vec<String> {
+        let theme = crate::theme::Theme::empty();
+        let fallback = theme.get(crate::theme::scope::UI_TEXT);
+        let editor = action_handlers::focused_editor_mut(stoat).expect("focused editor");
+        let area = Rect::new(0, 0, 12, rows);
+        let mut buf = Buffer::empty(area);
+        super::render_editor_with_overlay(
+            editor,
+            area,
+            fallback,
+            &theme,
+            &mut buf,
+            true,
+            false,
+            LineNumbers::Off,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            0.0,
+            WrapMode::None,
+            80,
+            WrapRules::default(),
+        );
+        (0..rows)
+            .map(|y| {
+                let row: String = (0..12).map(|x| buf[(x, y)].symbol()).collect();
+                row.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    /// Overwrite every cached text cell with `#`. A row that still reads `#`
+    /// after the next paint was replayed from the cache rather than rewalked.
+    fn scribble_text_layer(stoat: &mut Stoat) {
+        let editor = action_handlers::focused_editor_mut(stoat).unwrap();
+        for row in &mut editor.text_layer.as_mut().unwrap().rows {
+            for cell in row {
+                cell.1 = '#';
+            }
+        }
+    }
+
+    fn edit_focused_buffer(stoat: &mut Stoat, range: std::ops::Range<usize>, text: &str) {
+        let buffer_id = action_handlers::focused_editor_mut(stoat)
+            .unwrap()
+            .buffer_id;
+        let buffer = stoat.active_workspace().buffers.get(buffer_id).unwrap();
+        buffer.write().unwrap().edit(range, text);
+    }
+
+    #[test]
+    fn text_layer_repaints_only_damaged_and_exposed_rows() {
+        let mut h = Stoat::test();
+        let root = PathBuf::from("/text-layer");
+        let path = root.join("a.txt");
+        h.fake_fs().insert_file(&path, b"one\ntwo\nthree\nfour");
+        h.stoat.active_workspace_mut().git_root = root;
+        dispatch(&mut h.stoat, &OpenFile { path });
+        h.settle();
+
+        assert_eq!(paint_text_rows(&mut h.stoat, 3), ["one", "two", "three"]);
+        scribble_text_layer(&mut h.stoat);
+        assert_eq!(
+            paint_text_rows(&mut h.stoat, 3),
+            ["###", "###", "#####"],
+            "an unchanged repaint replays every row"
+        );
+
+        edit_focused_buffer(&mut h.stoat, 4..7, "TWO");
+        assert_eq!(
+            paint_text_rows(&mut h.stoat, 3),
+            ["###", "TWO", "#####"],
+            "an in-place edit repaints only its row"
+        );
+
+        scribble_text_layer(&mut h.stoat);
+        edit_focused_buffer(&mut h.stoat, 4..7, "2\n2");
+        assert_eq!(
+            paint_text_rows(&mut h.stoat, 3),
+            ["###", "2", "2"],
+            "a line-count change repaints every row below it"
+        );
+
+        scribble_text_layer(&mut h.stoat);
+        action_handlers::focused_editor_mut(&mut h.stoat)
+            .unwrap()
+            .scroll_row = 2;
+        assert_eq!(
+            paint_text_rows(&mut h.stoat, 3),
+            ["#", "three", "four"],
+            "a scroll replays the rows still in view and paints the exposed ones"
+        );
+    }
+
     /// Render the focused editor's gutter into cells and return the trimmed
     /// number string each visible row paints.
     ///
@@ -2712,6 +3398,7 @@ mod tests {
             is_focused,
             false,
             line_numbers,
+            false,
             insert_mode,
             None,
             None,
@@ -2722,6 +3409,7 @@ mod tests {
             0.0,
             WrapMode::None,
             80,
+            WrapRules::default(),
         );
         let gutter_w = editor.gutter_width;
         (0..rows)
@@ -2759,6 +3447,7 @@ mod tests {
             minimap_enabled,
             LineNumbers::Off,
             false,
+            false,
             None,
             None,
             None,
@@ -2768,6 +3457,7 @@ mod tests {
             0.0,
             WrapMode::None,
             80,
+            WrapRules::default(),
         );
         let rect = editor.minimap_rect;
         let strip = (0..rows)
@@ -2847,6 +3537,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cursor_line_washes_only_the_cursor_row() {
+        let mut h = Stoat::test();
+        let root = PathBuf::from("/cursorline");
+        let path = root.join("a.txt");
+        h.fake_fs().insert_file(&path, b"one\ntwo\nthree");
+        h.stoat.active_workspace_mut().git_root = root;
+        dispatch(&mut h.stoat, &OpenFile { path });
+        h.settle();
+        dispatch(&mut h.stoat, &MoveDown);
+
+        let theme = h.stoat.theme.clone();
+        let wash = theme
+            .get(crate::theme::scope::UI_CURSOR_LINE)
+            .bg
+            .expect("the default theme sets ui.cursorline");
+        let fallback = theme.get(crate::theme::scope::UI_TEXT);
+        let editor = action_handlers::focused_editor_mut(&mut h.stoat).expect("focused editor");
+        let area = Rect::new(0, 0, 12, 3);
+        let mut buf = Buffer::empty(area);
+        super::render_editor_with_overlay(
+            editor,
+            area,
+            fallback,
+            &theme,
+            &mut buf,
+            true,
+            false,
+            LineNumbers::Off,
+            true,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            0.0,
+            WrapMode::None,
+            80,
+            WrapRules::default(),
+        );
+
+        let washed = |y: u16| (0..12).filter(|&x| buf[(x, y)].bg == wash).count();
+        assert_eq!(washed(0), 0);
+        assert!(washed(1) >= 10, "the cursor row is washed to the pane edge");
+        assert_eq!(washed(2), 0);
+    }
+
     /// Render the focused editor's gutter in fallback mode and return each
     /// visible row's leftmost mark glyph paired with whether it is dimmed.
     /// The fallback gutter's per-row `(change glyph, staged glyph color)`, read
@@ -2868,6 +3607,7 @@ mod tests {
             false,
             LineNumbers::Absolute,
             false,
+            false,
             None,
             None,
             None,
@@ -2877,6 +3617,7 @@ mod tests {
             0.0,
             WrapMode::None,
             80,
+            WrapRules::default(),
         );
         let change_x = editor.gutter_width - 3;
         let staged_x = change_x + 1;
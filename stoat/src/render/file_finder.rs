@@ -155,10 +155,16 @@ fn render_list(finder: &FileFinder, area: Rect, theme: &crate::theme::Theme, buf
     paint_finder_rows(finder, area, start_row, theme, buf);
 }
 
+/// Columns reserved on the list's right edge for git status badges: a gap and
+/// the badge letter.
+const BADGE_COLS: u16 = 2;
+
 /// Paint finder result rows into `area` starting at `start_row`.
 ///
 /// A thin adapter over [`crate::render::picker::paint_path_rows`], kept because
-/// the smooth-scroll pool paints pages through a `&FileFinder`.
+/// the smooth-scroll pool paints pages through a `&FileFinder`. While any file
+/// has a git status, the rows give up [`BADGE_COLS`] on the right for a colored
+/// status letter.
 pub(crate) fn paint_finder_rows(
     finder: &FileFinder,
     area: Rect,
@@ -172,15 +178,54 @@ pub(crate) fn paint_finder_rows(
         .as_ref()
         .map(|browse| browse.typed_dir.as_str())
         .unwrap_or_default();
+    let badged = !finder.git_status.is_empty() && area.width > BADGE_COLS;
+    let rows_area = if badged {
+        Rect {
+            width: area.width - BADGE_COLS,
+            ..area
+        }
+    } else {
+        area
+    };
     crate::render::picker::paint_path_rows(
         &core.picklist,
         &core.git_root,
         prefix,
-        area,
+        rows_area,
         start_row,
         theme,
         buf,
     );
+    if !badged {
+        return;
+    }
+
+    let row_style = theme.get(crate::theme::scope::UI_TEXT);
+    let selected_style = theme.get(crate::theme::scope::UI_SELECTION);
+    let badge_x = area.x + area.width - 1;
+    let list = &core.picklist;
+    for (row_idx, &idx) in list
+        .filtered
+        .iter()
+        .skip(start_row)
+        .take(area.height as usize)
+        .enumerate()
+    {
+        let row = area.y + row_idx as u16;
+        let style = if start_row + row_idx == list.selected {
+            selected_style
+        } else {
+            row_style
+        };
+        for col in rows_area.x + rows_area.width..area.x + area.width {
+            buf[(col, row)].set_char(' ').set_style(style);
+        }
+        if let Some(status) = finder.git_status.get(&list.base[idx]) {
+            buf[(badge_x, row)]
+                .set_char(status.badge())
+                .set_style(style.patch(theme.get(status.scope())));
+        }
+    }
 }
 
 fn render_preview(
@@ -1,6 +1,6 @@
 use crate::{
-    help::{format_arg, Help, SnapshotState},
-    keymap::{collect_predicate_fields, evaluate, KeymapState, ResolvedAction, StateValue},
+    help::{format_action, format_arg, Help, SnapshotState},
+    keymap::{collect_predicate_fields, evaluate, KeymapState, StateValue},
     render::{
         pane::mode_segment,
         text::{wrap_text, write_str, write_str_clipped},
@@ -341,18 +341,6 @@ pub(crate) fn paint_help_detail_rows(
     }
 }
 
-/// Render one action of a binding's sequence as `name(arg, ...)`, or bare `name`
-/// when it takes no arguments, so `AutoReload(follow)` reads distinctly from
-/// `AutoReload(on)`.
-fn format_action(action: &ResolvedAction) -> String {
-    let args: Vec<String> = action.args.iter().filter_map(format_arg).collect();
-    if args.is_empty() {
-        action.name.clone()
-    } else {
-        format!("{}({})", action.name, args.join(", "))
-    }
-}
-
 /// Render one predicate of a binding's condition. A `[x]`/`[ ]` box marks whether
 /// it holds in `context`, followed by the predicate source and each field it
 /// tests with its current value (`unset` when the field is absent).
@@ -427,7 +415,7 @@ fn format_example(entry: &crate::help::HelpEntry) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::keymap::ResolvedArg;
+    use crate::keymap::{ResolvedAction, ResolvedArg};
     use stoat_config::{Spanned, Value};
 
     fn context(fields: &[(&str, &str)]) -> SnapshotState {
@@ -0,0 +1,37 @@
+use crate::{hex_view::HexView, render::text::write_str_clipped};
+use ratatui::{buffer::Buffer, layout::Rect};
+
+/// Width of the offset column [`HexView::row`] leads each row with.
+const OFFSET_WIDTH: u16 = 8;
+
+/// Paint the rows of `view` visible in `area`, formatting only those.
+///
+/// The scroll position is clamped here rather than trusted, so a pane that
+/// grew taller since the last scroll still fills from the top of the final
+/// page. The offset column draws dimmed against the bytes.
+pub(crate) fn render_hex_view(
+    view: &HexView,
+    theme: &crate::theme::Theme,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+
+    let text_style = theme.get(crate::theme::scope::UI_TEXT);
+    let offset_style = text_style.patch(theme.get(crate::theme::scope::UI_TEXT_DIM));
+    let end_x = area.x + area.width;
+
+    let page = area.height as usize;
+    let first = view.scroll_row.min(view.row_count().saturating_sub(page));
+    for (i, row) in (first..first + page).enumerate() {
+        let Some(line) = view.row(row) else {
+            break;
+        };
+        let y = area.y + i as u16;
+        let (offset, rest) = line.split_at(OFFSET_WIDTH as usize);
+        write_str_clipped(buf, area.x, y, offset, offset_style, end_x);
+        write_str_clipped(buf, area.x + OFFSET_WIDTH, y, rest, text_style, end_x);
+    }
+}
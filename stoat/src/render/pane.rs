@@ -6,11 +6,13 @@ use crate::{
     render::{
         chrome,
         editor::{editor_cursor_position, render_editor_with_overlay},
+        hex_view::render_hex_view,
         layout::split_pane_status,
         popout::{
             paint_popout_card, popout_area, popout_card_bg, popout_inset, scaled_char_capacity,
             wrap_popout_lines,
         },
+        regex_playground::render_regex_playground,
         review::{dim_rgb, style_rgb},
         run_pane::render_run_pane,
         term_pane::render_term_pane,
@@ -29,6 +31,7 @@ use ratatui::{
 };
 use slotmap::SlotMap;
 use std::path::Path;
+use stoat_text::LineEnding;
 use stoatty_widgets::{
     minimap::Minimap,
     status_bar::{StatusBar, StatusSegment},
@@ -50,7 +53,7 @@ pub(crate) fn render_pane(
     buf: &mut Buffer,
     scene: &mut ApcScene,
     undercurls: &mut Vec<UndercurlSpan>,
-    badge_rect: &mut Option<Rect>,
+    hits: &mut StatusHits,
 ) {
     let theme = frame.theme;
     let text_style = theme.get(crate::theme::scope::UI_TEXT);
@@ -72,6 +75,8 @@ pub(crate) fn render_pane(
         buffers,
         runs,
         terms,
+        hex_views,
+        regex_playgrounds,
     } = ctx;
 
     match &pane.view {
@@ -99,6 +104,7 @@ pub(crate) fn render_pane(
                     is_focused,
                     frame.minimap_enabled,
                     frame.line_numbers,
+                    frame.cursor_line,
                     frame.mode == "insert",
                     if is_focused { frame.hover_cell } else { None },
                     labels,
@@ -109,6 +115,7 @@ pub(crate) fn render_pane(
                     if is_focused { 0.0 } else { frame.inactive_dim },
                     frame.wrap_mode,
                     frame.wrap_column,
+                    frame.wrap_rules,
                 );
 
                 if let (Some(strip), Some(chrome)) = (editor.minimap_rect, frame.minimap_chrome)
@@ -163,6 +170,24 @@ pub(crate) fn render_pane(
                 render_term_pane(term, theme, content_area, is_focused, buf);
             }
         },
+        View::Hex(hex_id) => {
+            if let Some(hex) = hex_views.get(*hex_id) {
+                render_hex_view(hex, theme, content_area, buf);
+            }
+        },
+        View::RegexPlayground(id) => {
+            if let Some(playground) = regex_playgrounds.get_mut(*id) {
+                render_regex_playground(
+                    playground,
+                    editors,
+                    buffers,
+                    theme,
+                    content_area,
+                    buf,
+                    scene,
+                );
+            }
+        },
     }
 
     if !is_focused
@@ -183,7 +208,7 @@ pub(crate) fn render_pane(
         frame,
         editors,
         buffers,
-        badge_rect,
+        hits,
         buf,
         scene,
     );
@@ -432,7 +457,7 @@ fn render_pane_status(
     frame: FrameCtx<'_>,
     editors: &mut SlotMap<EditorId, EditorState>,
     buffers: &BufferRegistry,
-    badge_rect: &mut Option<Rect>,
+    hits: &mut StatusHits,
     buf: &mut Buffer,
     scene: &mut ApcScene,
 ) {
@@ -452,8 +477,7 @@ fn render_pane_status(
         buf[(x, y)].set_char(' ').set_style(base_style);
     }
 
-    let (left, right) =
-        status_segments(view, is_focused, area, frame, editors, buffers, badge_rect);
+    let (left, right) = status_segments(view, is_focused, area, frame, editors, buffers, hits);
     render_status_segments(area, base_style, frame, &left, &right, buf, scene);
 }
 
@@ -489,8 +513,15 @@ pub(crate) fn paint_pane_status_cells(
         buf[(x, y)].set_char(' ').set_style(base_style);
     }
 
-    let (mut left, right) =
-        status_segments(view, is_focused, area, frame, editors, buffers, &mut None);
+    let (mut left, right) = status_segments(
+        view,
+        is_focused,
+        area,
+        frame,
+        editors,
+        buffers,
+        &mut StatusHits::default(),
+    );
 
     // A detached pane cannot host a primary-scene digit popover, so its numeric
     // selection badge rides the status row instead.
@@ -582,6 +613,16 @@ pub(crate) fn render_tab_bar(
 /// One built status-bar segment pairing painted text with its cell style.
 type StatusSeg = (String, Style);
 
+/// Pointer targets the status bars stamp while painting, read back by the
+/// mouse handlers.
+#[derive(Default)]
+pub(crate) struct StatusHits {
+    /// The focused bar's LSP badge group, `Some` only when a badge painted.
+    pub(crate) lsp_badge: Option<Rect>,
+    /// Each painted breadcrumb's cells and the byte range of its item.
+    pub(crate) breadcrumbs: Vec<(Rect, std::ops::Range<usize>)>,
+}
+
 /// Resolve the style for the transient status message segment from the bar's
 /// base style plus the theme's `ui.message.error` override.
 ///
@@ -615,7 +656,7 @@ fn status_segments(
     frame: FrameCtx<'_>,
     editors: &mut SlotMap<EditorId, EditorState>,
     buffers: &BufferRegistry,
-    badge_rect: &mut Option<Rect>,
+    hits: &mut StatusHits,
 ) -> (Vec<StatusSeg>, Vec<StatusSeg>) {
     let theme = frame.theme;
     let base_style = if is_focused {
@@ -666,6 +707,17 @@ fn status_segments(
         };
         push_left(&mut left, &mut cursor, end_x, text, base_style);
     }
+    if is_focused && frame.breadcrumbs {
+        push_breadcrumbs(
+            &mut left,
+            &mut cursor,
+            area,
+            focused_breadcrumbs(view, editors, buffers),
+            base_style,
+            theme,
+            &mut hits.breadcrumbs,
+        );
+    }
 
     let mut right: Vec<(String, Style)> = Vec::new();
     let mut right_anchor = end_x;
@@ -678,6 +730,14 @@ fn status_segments(
             right_anchor = start;
         }
     }
+    if let Some(text) = line_ending_label(view, editors, buffers) {
+        let width = text.chars().count() as u16;
+        let start = right_anchor.saturating_sub(width);
+        if start >= cursor {
+            right.push((text, base_style));
+            right_anchor = start;
+        }
+    }
     if is_focused {
         let badge_right = right_anchor;
         for (short, busy) in frame.lsp_servers {
@@ -699,7 +759,7 @@ fn status_segments(
             // pad space, which carries no glyph) so hover matches the drawn glyphs.
             let chars = badge_right - right_anchor;
             let width = ((chars - 1) * TEXT_SCALE_COMPACT).div_ceil(TEXT_SCALE_FULL);
-            *badge_rect = Some(Rect::new(badge_right - width, area.y, width, area.height));
+            hits.lsp_badge = Some(Rect::new(badge_right - width, area.y, width, area.height));
         }
         if frame.diff_warm_busy {
             let text = format!(" {} diff ", SPINNER_FRAMES[frame.spinner_phase as usize]);
@@ -790,6 +850,75 @@ fn status_segments(
     (left, right)
 }
 
+/// Append one left segment per crumb, joined by dim `>` separators, and
+/// record each crumb's cells with its item range in `rects`.
+///
+/// A crumb that would not fit whole ends the chain, so the bar never shows a
+/// clipped crumb it cannot click. As with the LSP badge, the recorded cells
+/// follow the rich bar's TEXT_SCALE_COMPACT glyph extent.
+fn push_breadcrumbs(
+    left: &mut Vec<StatusSeg>,
+    cursor: &mut u16,
+    area: Rect,
+    crumbs: Vec<stoat_language::Breadcrumb>,
+    base_style: Style,
+    theme: &crate::theme::Theme,
+    rects: &mut Vec<(Rect, std::ops::Range<usize>)>,
+) {
+    let end_x = area.x + area.width;
+    let separator_style = base_style.patch(theme.get(crate::theme::scope::UI_TEXT_DIM));
+    let scaled = |cells: u16| {
+        (u32::from(cells) * u32::from(TEXT_SCALE_COMPACT)).div_ceil(u32::from(TEXT_SCALE_FULL))
+            as u16
+    };
+    for (idx, crumb) in crumbs.into_iter().enumerate() {
+        let separator = if idx == 0 { "" } else { ">" };
+        let text = format!(" {} ", crumb.label);
+        let width = (separator.len() + text.chars().count()) as u16;
+        if cursor.saturating_add(width) > end_x {
+            break;
+        }
+        if !separator.is_empty() {
+            push_left(left, cursor, end_x, separator.to_string(), separator_style);
+        }
+        let start = area.x + scaled(*cursor - area.x);
+        push_left(left, cursor, end_x, text, base_style);
+        let end = area.x + scaled(*cursor - area.x);
+        rects.push((
+            Rect::new(start, area.y, end - start, area.height),
+            crumb.range,
+        ));
+    }
+}
+
+/// The outline items around the cursor of an editor pane, outermost first.
+/// Empty for other views and for buffers with no parsed syntax.
+fn focused_breadcrumbs(
+    view: &View,
+    editors: &mut SlotMap<EditorId, EditorState>,
+    buffers: &BufferRegistry,
+) -> Vec<stoat_language::Breadcrumb> {
+    let View::Editor(editor_id) = view else {
+        return Vec::new();
+    };
+    let Some(editor) = editors.get_mut(*editor_id) else {
+        return Vec::new();
+    };
+    let Some(syntax_map) = buffers.syntax_map(editor.buffer_id) else {
+        return Vec::new();
+    };
+    let snapshot = editor.display_map.snapshot();
+    let buffer_snapshot = snapshot.buffer_snapshot();
+    let sel = editor.selections.newest_anchor();
+    let rope = buffer_snapshot.rope();
+    let cursor = stoat_text::cursor_offset(
+        rope,
+        buffer_snapshot.resolve_anchor(&sel.tail()),
+        buffer_snapshot.resolve_anchor(&sel.head()),
+    );
+    stoat_language::breadcrumbs_at(&syntax_map.snapshot(), rope, cursor)
+}
+
 /// Append a left-anchored segment and advance `cursor` as [`paint_segment`]
 /// would, clamping at `end_x`.
 fn push_left(left: &mut Vec<StatusSeg>, cursor: &mut u16, end_x: u16, text: String, style: Style) {
@@ -895,6 +1024,22 @@ fn focused_staged_label(
     Some(format!(" {staged} staged / {unstaged} unstaged "))
 }
 
+/// The status-bar label for an editor pane's line ending, or `None` for a
+/// non-editor pane or a buffer on the default `LF`, which stays unlabelled like
+/// a clean buffer stays unmarked.
+fn line_ending_label(
+    view: &View,
+    editors: &SlotMap<EditorId, EditorState>,
+    buffers: &BufferRegistry,
+) -> Option<String> {
+    let View::Editor(editor_id) = view else {
+        return None;
+    };
+    let editor = editors.get(*editor_id)?;
+    let line_ending = buffers.get(editor.buffer_id)?.read().ok()?.line_ending();
+    (line_ending != LineEnding::default()).then(|| format!(" {} ", line_ending.label()))
+}
+
 fn diagnostic_severity_scope(severity: DiagnosticSeverity) -> &'static str {
     use crate::theme::scope;
     match severity {
@@ -1061,6 +1206,8 @@ fn pane_status_info(
         View::Run(_) => (Some("[run]".to_string()), false, None),
         View::Agent(_) => (Some("[agent]".to_string()), false, None),
         View::Terminal(_) => (Some("[term]".to_string()), false, None),
+        View::Hex(_) => (Some("[hex]".to_string()), false, None),
+        View::RegexPlayground(_) => (Some("[regex]".to_string()), false, None),
         View::Label(label) => (Some(label.clone()), false, None),
     }
 }
@@ -1131,6 +1278,24 @@ mod tests {
         h.drain_lsp();
     }
 
+    #[test]
+    fn crlf_buffer_labels_its_line_ending() {
+        let mut h = Stoat::test();
+        let path = PathBuf::from("/crlf/a.txt");
+        h.fake_fs().insert_file(&path, b"a\r\nb\r\n");
+        dispatch(&mut h.stoat, &OpenFile { path });
+        h.settle();
+        let bar = bar_row(&h.render_composited());
+        assert!(bar.contains(" CRLF "), "CRLF is labelled:\n{bar}");
+
+        dispatch(&mut h.stoat, &stoat_action::SetLineEndingLf);
+        let bar = bar_row(&h.render_composited());
+        assert!(
+            !bar.contains("CRLF"),
+            "LF is the unlabelled default:\n{bar}"
+        );
+    }
+
     #[test]
     fn lsp_badge_hidden_when_idle() {
         let mut h = Stoat::test();
@@ -0,0 +1,124 @@
+use crate::{
+    perf::{MetricStats, PerfStats},
+    render::text::write_str,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Clear, Widget},
+};
+
+/// Box width, border included: a six-column label plus four seven-column
+/// value columns and padding.
+const OVERLAY_WIDTH: u16 = 40;
+
+/// Paint the perf overlay in `area`'s bottom-right corner, one row above the
+/// bottom so the status bar stays readable. Each row lists a metric's last,
+/// p50, p95, and worst sample: the whole redraw (`frame`), its `layout` and
+/// `paint` steps, event handling (`update`), and the editor text rows painted
+/// (`lines`). A running profile capture adds a footer row. Metrics with no
+/// samples yet show dashes.
+pub(crate) fn render_perf_overlay(
+    perf: &PerfStats,
+    theme: &crate::theme::Theme,
+    area: Rect,
+    buf: &mut Buffer,
+    scene: &mut stoatty_widgets::ApcScene,
+) {
+    let capturing = perf.is_capturing();
+    let height = 8 + u16::from(capturing);
+    if area.width < OVERLAY_WIDTH + 2 || area.height < height + 2 {
+        return;
+    }
+
+    let rows: [(&str, Option<MetricStats>, bool); 5] = [
+        ("frame", perf.frame_stats(), true),
+        ("layout", perf.layout_stats(), true),
+        ("paint", perf.paint_stats(), true),
+        ("update", perf.update_stats(), true),
+        ("lines", perf.lines_stats(), false),
+    ];
+
+    let rect = Rect::new(
+        area.x + area.width - OVERLAY_WIDTH - 1,
+        area.y + area.height - height - 1,
+        OVERLAY_WIDTH,
+        height,
+    );
+    Clear.render(rect, buf);
+    let border = theme.get(crate::theme::scope::UI_BORDER_FOCUSED);
+    let inner = crate::render::chrome::modal_frame(buf, rect, Some(" perf "), border, theme, scene);
+
+    let text_style = theme.get(crate::theme::scope::UI_TEXT);
+    let label_style = theme.get(crate::theme::scope::UI_KEY_LABEL);
+    write_str(
+        buf,
+        inner.x + 1,
+        inner.y,
+        &format!(
+            "{:6}{:>7}{:>7}{:>7}{:>7}",
+            "us", "last", "p50", "p95", "worst"
+        ),
+        label_style,
+    );
+    for (row, (label, stats, duration)) in rows.into_iter().enumerate() {
+        let y = inner.y + 1 + row as u16;
+        write_str(buf, inner.x + 1, y, &format!("{label:6}"), label_style);
+        write_str(
+            buf,
+            inner.x + 7,
+            y,
+            &metric_cells(stats, duration),
+            text_style,
+        );
+    }
+    if capturing {
+        write_str(
+            buf,
+            inner.x + 1,
+            inner.y + 1 + rows.len() as u16,
+            "capturing profile",
+            theme.get(crate::theme::scope::UI_DIAGNOSTIC_WARNING),
+        );
+    }
+}
+
+/// The four right-aligned value columns of one metric row. Durations are
+/// converted from nanoseconds to microseconds; counts print as recorded.
+fn metric_cells(stats: Option<MetricStats>, duration: bool) -> String {
+    let Some(stats) = stats else {
+        return format!("{:>7}{:>7}{:>7}{:>7}", "-", "-", "-", "-");
+    };
+    let scale = if duration { 1_000 } else { 1 };
+    format!(
+        "{:>7}{:>7}{:>7}{:>7}",
+        stats.last / scale,
+        stats.p50 / scale,
+        stats.p95 / scale,
+        stats.worst / scale
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metric_cells_scale_durations_but_not_counts() {
+        let stats = MetricStats {
+            last: 12_000,
+            p50: 10_000,
+            p95: 30_000,
+            worst: 45_000,
+        };
+        assert_eq!(
+            metric_cells(Some(stats), true),
+            "     12     10     30     45"
+        );
+        assert_eq!(
+            metric_cells(Some(stats), false),
+            "  12000  10000  30000  45000"
+        );
+        assert_eq!(metric_cells(None, true), "      -      -      -      -");
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    buffer_registry::BufferRegistry,
+    display_map::WrapRules,
+    editor_state::{EditorId, EditorState},
+    regex_playground::{collect_matches, PlaygroundField, PlaygroundMatch, RegexPlayground},
+    render::{
+        editor::render_editor_with_overlay,
+        text::{write_str, write_str_clipped},
+    },
+    theme::{scope, Theme},
+};
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+use slotmap::SlotMap;
+use stoat_config::{LineNumbers, WrapMode};
+
+/// Paint `playground` into its pane's content `area`: the pattern input on the
+/// first row, a separator carrying the match count, and the sample beside the
+/// capture list below.
+pub(crate) fn render_regex_playground(
+    playground: &mut RegexPlayground,
+    editors: &mut SlotMap<EditorId, EditorState>,
+    buffers: &BufferRegistry,
+    theme: &Theme,
+    area: Rect,
+    buf: &mut Buffer,
+    scene: &mut stoatty_widgets::ApcScene,
+) {
+    if area.width < 4 || area.height < 3 {
+        return;
+    }
+
+    let pattern = playground.pattern.text_in(buffers);
+    let sample = playground.sample.text_in(buffers);
+    let focus = playground.focus;
+    let (valid, matches) = match playground.regex(&pattern) {
+        Some(Ok(regex)) => (Some(true), collect_matches(regex, &sample)),
+        Some(Err(_)) => (Some(false), Vec::new()),
+        None => (None, Vec::new()),
+    };
+
+    let prompt_style = theme.get(scope::UI_PROMPT);
+    let separator_style = theme.get(scope::UI_BORDER_INACTIVE);
+
+    write_str(buf, area.x, area.y, "/", prompt_style);
+    let input_area = Rect::new(area.x + 2, area.y, area.width.saturating_sub(2), 1);
+    playground.pattern.render(
+        editors,
+        input_area,
+        focus == PlaygroundField::Pattern,
+        "prompt",
+        theme,
+        &std::collections::BTreeMap::new(),
+        buf,
+    );
+
+    crate::render::chrome::hline(
+        buf,
+        area.x,
+        area.y + 1,
+        area.width,
+        separator_style,
+        Some(&mut *scene),
+    );
+    let title = playground_title(valid, matches.len());
+    write_str_clipped(
+        buf,
+        area.x + 1,
+        area.y + 1,
+        &title,
+        theme.get(scope::UI_TEXT_MUTED),
+        area.x + area.width,
+    );
+
+    // The sample takes the wide side of the split and the capture list the
+    // narrow one. A narrow pane drops the list and gives the sample the body.
+    let (list, preview) = crate::render::picker::split_list_preview(
+        area.x,
+        area.y + 2,
+        area.width,
+        area.height - 2,
+        60,
+        20,
+    );
+    let (sample_rect, captures_rect) = match preview {
+        Some(preview) => (preview, Some(list)),
+        None => (list, None),
+    };
+
+    if let Some(editor) = editors.get_mut(playground.sample.editor_id) {
+        render_editor_with_overlay(
+            editor,
+            sample_rect,
+            theme.get(scope::UI_TEXT),
+            theme,
+            buf,
+            focus == PlaygroundField::Sample,
+            false,
+            LineNumbers::Off,
+            false,
+            true,
+            None,
+            None,
+            (valid == Some(true)).then_some(pattern.as_str()),
+            None,
+            None,
+            None,
+            0.0,
+            WrapMode::None,
+            80,
+            WrapRules::default(),
+        );
+    }
+
+    if let Some(captures_rect) = captures_rect {
+        crate::render::chrome::vline(
+            buf,
+            captures_rect.x + captures_rect.width,
+            captures_rect.y,
+            captures_rect.height,
+            separator_style,
+            scene,
+        );
+        paint_capture_rows(&matches, captures_rect, theme, buf);
+    }
+}
+
+/// Paint one row per match followed by an indented row per capture group. A
+/// numbered group is labelled `$n`, a named one by its name, and a group that
+/// did not participate shows a dim `-`.
+fn paint_capture_rows(matches: &[PlaygroundMatch], area: Rect, theme: &Theme, buf: &mut Buffer) {
+    let rows = area.height as usize;
+    if rows == 0 {
+        return;
+    }
+    let text_style = theme.get(scope::UI_TEXT);
+    let dim_style = theme.get(scope::UI_TEXT_MUTED);
+    let match_style = theme.get(scope::UI_SEARCH_MATCH);
+    let end_x = area.x + area.width;
+
+    let mut lines: Vec<(String, String, Style)> = Vec::new();
+    for (i, m) in matches.iter().enumerate() {
+        lines.push((format!("#{}", i + 1), escape_controls(&m.text), match_style));
+        for (g, (name, value)) in m.groups.iter().enumerate() {
+            let label = match name {
+                Some(name) => format!("  {name}"),
+                None => format!("  ${}", g + 1),
+            };
+            match value {
+                Some(value) => lines.push((label, escape_controls(value), text_style)),
+                None => lines.push((label, "-".to_string(), dim_style)),
+            }
+        }
+    }
+
+    for (row_idx, (label, value, style)) in lines.iter().take(rows).enumerate() {
+        let row = area.y + row_idx as u16;
+        let label_x = area.x + 1;
+        write_str_clipped(buf, label_x, row, label, dim_style, end_x);
+        let value_x = label_x + label.chars().count() as u16 + 1;
+        if value_x < end_x {
+            write_str_clipped(buf, value_x, row, value, *style, end_x);
+        }
+    }
+}
+
+/// Spell newlines and tabs out so a multi-line match stays on one list row.
+fn escape_controls(text: &str) -> String {
+    text.replace('\n', "\\n").replace('\t', "\\t")
+}
+
+/// Separator title carrying the match count, or why there is none. `valid` is
+/// `None` while the pattern is empty.
+fn playground_title(valid: Option<bool>, count: usize) -> String {
+    match valid {
+        None => " regex playground ".to_string(),
+        Some(false) => " regex playground: invalid pattern ".to_string(),
+        Some(true) if count == 1 => " regex playground: 1 match ".to_string(),
+        Some(true) => format!(" regex playground: {count} matches "),
+    }
+}
@@ -11,7 +11,8 @@ use ratatui::{
 
 /// Paint the rename input modal anchored to the focused editor's
 /// cursor. Renders the embedded [`crate::input_view::InputView`]
-/// inside a bordered popup titled "rename".
+/// inside a bordered popup titled "rename". Serves both the LSP rename
+/// and the locals-based [`crate::local_rename`] prompt.
 ///
 /// No-op when there is no rename input open or the focused pane is
 /// not an editor.
@@ -20,14 +21,19 @@ pub(crate) fn render_rename_input(
     buf: &mut Buffer,
     scene: &mut stoatty_widgets::ApcScene,
 ) {
-    if stoat.rename_input.is_none() {
-        return;
-    }
-    let anchor_offset = stoat
+    let open = stoat
         .rename_input
         .as_ref()
-        .map(|s| s.anchor_offset)
-        .expect("checked above");
+        .map(|s| (s.anchor_offset, s.input.editor_id))
+        .or_else(|| {
+            stoat
+                .local_rename
+                .as_ref()
+                .map(|s| (s.anchor_offset, s.input.editor_id))
+        });
+    let Some((anchor_offset, editor_id)) = open else {
+        return;
+    };
 
     let (content_area, focus_pane_id) = match stoat.active_workspace().focus {
         FocusTarget::SplitPane => {
@@ -78,11 +84,6 @@ pub(crate) fn render_rename_input(
         scene,
     );
 
-    let editor_id = stoat
-        .rename_input
-        .as_ref()
-        .map(|s| s.input.editor_id)
-        .expect("checked above");
     let theme = stoat.theme.clone();
     let ws = stoat.active_workspace_mut();
     if let Some(editor) = ws.editors.get_mut(editor_id) {
@@ -0,0 +1,78 @@
+use crate::{
+    render::text::{write_str, write_str_clipped},
+    toast::Toasts,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Clear, Widget},
+};
+
+/// Widest a toast grows, border included. Longer messages are cut to fit.
+const MAX_TOAST_WIDTH: u16 = 60;
+
+/// Paint the visible toasts as a stack in `area`'s top-right corner, newest on
+/// top. Each is a one-line box whose border and title take the style of its
+/// [`crate::toast::ToastLevel`], with its action label, if any, after the
+/// message. Toasts that would run past `area`'s bottom are skipped.
+pub(crate) fn render_toasts(
+    toasts: &Toasts,
+    theme: &crate::theme::Theme,
+    area: Rect,
+    buf: &mut Buffer,
+    scene: &mut stoatty_widgets::ApcScene,
+) {
+    if toasts.is_empty() || area.width < 20 || area.height < 4 {
+        return;
+    }
+
+    let text_style = theme.get(crate::theme::scope::UI_TEXT);
+    let label_style = theme.get(crate::theme::scope::UI_KEY_LABEL);
+    let max_width = MAX_TOAST_WIDTH.min(area.width.saturating_sub(2));
+    let right = area.x + area.width - 1;
+    let mut y = area.y + 1;
+
+    for toast in toasts.visible() {
+        if y + 3 > area.y + area.height {
+            break;
+        }
+        let label = toast
+            .action
+            .as_ref()
+            .map(|action| format!("[{}]", action.label));
+        let content_width = toast.message.chars().count()
+            + label.as_ref().map_or(0, |label| label.chars().count() + 2);
+        let title = format!(" {} ", toast.level.label());
+        let width = (content_width.max(title.chars().count() + 2) as u16 + 4).min(max_width);
+        let rect = Rect::new(right.saturating_sub(width), y, width, 3);
+
+        Clear.render(rect, buf);
+        let style = theme.get(toast.level.theme_scope());
+        let inner =
+            crate::render::chrome::modal_frame(buf, rect, Some(&title), style, theme, scene);
+
+        let end_x = inner.x + inner.width.saturating_sub(1);
+        let label_x = match &label {
+            Some(label) => end_x.saturating_sub(label.chars().count() as u16),
+            None => end_x,
+        };
+        let message_end = if label.is_some() {
+            label_x.saturating_sub(2)
+        } else {
+            end_x
+        };
+        write_str_clipped(
+            buf,
+            inner.x + 1,
+            inner.y,
+            &toast.message,
+            text_style,
+            message_end,
+        );
+        if let Some(label) = &label {
+            write_str(buf, label_x, inner.y, label, label_style);
+        }
+
+        y += 3;
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{render::text::write_str, undo_tree_picker::UndoTreePicker};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Clear, Widget},
+};
+
+/// Paint the undo tree modal centered in `area`. Each row is indented two
+/// columns per branch level and marked `●` on the buffer's current state and
+/// `○` elsewhere. A history taller than the modal scrolls to keep the selection
+/// in view.
+pub(crate) fn render_undo_tree_picker(
+    picker: &UndoTreePicker,
+    theme: &crate::theme::Theme,
+    area: Rect,
+    buf: &mut Buffer,
+    scene: &mut stoatty_widgets::ApcScene,
+) {
+    if area.width < 30 || area.height < 6 {
+        return;
+    }
+
+    let rows = picker.rows();
+    if rows.is_empty() {
+        return;
+    }
+    let max_rows = 16u16.min(area.height.saturating_sub(2));
+    let visible_rows = (rows.len() as u16).min(max_rows);
+
+    let box_width = 60u16.min(area.width.saturating_sub(4));
+    let box_height = 2 + visible_rows;
+    let x = area.x + (area.width.saturating_sub(box_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(box_height)) / 2;
+    let modal_area = Rect::new(x, y, box_width, box_height);
+
+    let modal_style = theme.get(crate::theme::scope::UI_MODAL_PICKER);
+    Clear.render(modal_area, buf);
+    let inner = crate::render::chrome::modal_frame(
+        buf,
+        modal_area,
+        Some(" undo tree "),
+        modal_style,
+        theme,
+        scene,
+    );
+
+    let row_style = theme.get(crate::theme::scope::UI_TEXT);
+    let selected_style = theme.get(crate::theme::scope::UI_SELECTION);
+    let prompt_style = theme.get(crate::theme::scope::UI_PROMPT);
+
+    let selected = picker.selected();
+    let first = (selected + 1).saturating_sub(visible_rows as usize);
+
+    for (i, entry) in rows
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible_rows as usize)
+    {
+        let row = inner.y + (i - first) as u16;
+        let base_style = if i == selected {
+            selected_style
+        } else if entry.current {
+            prompt_style
+        } else {
+            row_style
+        };
+
+        for col in inner.x..inner.x + inner.width {
+            buf[(col, row)].set_char(' ').set_style(base_style);
+        }
+
+        let marker = if entry.current { '●' } else { '○' };
+        let line = format!("{}{marker} {}", "  ".repeat(entry.depth), entry.label);
+        let line: String = line.chars().take(inner.width as usize).collect();
+        write_str(buf, inner.x, row, &line, base_style);
+    }
+}
@@ -80,9 +80,9 @@ impl ReviewSource {
     }
 }
 
-/// Test-only / future-facing carrier for agent-proposed edits. Kept as a
-/// concrete type rather than an opaque placeholder so the variant signature
-/// does not churn when the real agent bridge lands.
+/// One file of an agent-edit review: the text the agent proposed against the
+/// base it read. Filled from a `propose-edits` request over the agent socket,
+/// and written to disk only for the chunks the user accepts.
 #[derive(Clone, Debug)]
 pub(crate) struct AgentEditProposal {
     pub path: PathBuf,
@@ -2,6 +2,11 @@ use crate::multi_buffer::MultiBufferSnapshot;
 use serde::{Deserialize, Serialize};
 use stoat_text::{Anchor, Bias, Selection, SelectionGoal};
 
+/// An editor's selections, sorted by start and never overlapping. Endpoints
+/// are buffer [`Anchor`]s rather than offsets or points, so a selection stays
+/// on its text when the buffer is edited from elsewhere (another editor on
+/// the same buffer, a collab peer, a formatter) and across undo and redo.
+/// Resolve them against a fresh [`MultiBufferSnapshot`] to get offsets.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct SelectionsCollection {
     next_selection_id: usize,
@@ -319,6 +324,42 @@ mod tests {
         assert!(sel.start.is_min());
     }
 
+    #[test]
+    fn selection_follows_edits_made_outside_the_editor() {
+        let id = BufferId::new(0);
+        let buffer = Arc::new(RwLock::new(TextBuffer::with_text(id, "hello world")));
+        let multi = MultiBuffer::singleton(id, buffer.clone());
+        let snapshot = multi.snapshot();
+        let mut collection = SelectionsCollection::new();
+        collection.set_single_range(
+            snapshot.anchor_at(6, Bias::Right),
+            snapshot.anchor_at(11, Bias::Left),
+            SelectionGoal::None,
+        );
+
+        {
+            let mut guard = buffer.write().unwrap();
+            guard.edit(0..0, "oh, ");
+            guard.edit(11..12, "");
+        }
+        let snapshot = multi.snapshot();
+        assert_eq!(snapshot.rope().to_string(), "oh, hello wrld");
+        let sel = collection.newest_anchor();
+        assert_eq!(
+            snapshot.resolve_anchor(&sel.start)..snapshot.resolve_anchor(&sel.end),
+            10..14
+        );
+
+        buffer.write().unwrap().undo();
+        let snapshot = multi.snapshot();
+        let sel = collection.newest_anchor();
+        assert_eq!(
+            snapshot.resolve_anchor(&sel.start)..snapshot.resolve_anchor(&sel.end),
+            10..15,
+            "undoing the deletion widens the selection back over it"
+        );
+    }
+
     #[test]
     fn insert_cursor_assigns_monotonic_id() {
         let multi = singleton("abcdef");
@@ -2595,6 +2636,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn duplicate_and_comment_original_keeps_cursor_on_the_copy() {
+        let mut h = crate::test_harness::TestHarness::with_size(40, 5);
+        let path = h.write_file("s.rs", "let x = 42;\n");
+        h.open_file(&path);
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::DuplicateAndCommentOriginal);
+        assert_eq!(focused_buffer_text(&mut h), "// let x = 42;\nlet x = 42;\n");
+        assert_eq!(
+            h.primary_head_offset(),
+            "// let x = 42;\n".len(),
+            "cursor follows the live copy below the commented original"
+        );
+    }
+
+    #[test]
+    fn duplicate_and_comment_original_is_one_undo_step() {
+        let mut h = crate::test_harness::TestHarness::with_size(40, 8);
+        let path = h.write_file("s.rs", "abc\n\nxyz\n");
+        h.open_file(&path);
+        h.type_keys("%");
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::DuplicateAndCommentOriginal);
+        assert_eq!(
+            focused_buffer_text(&mut h),
+            "// abc\n\n// xyz\nabc\n\nxyz\n",
+            "blank line is copied uncommented"
+        );
+        h.type_keys("u");
+        assert_eq!(focused_buffer_text(&mut h), "abc\n\nxyz\n");
+    }
+
+    #[test]
+    fn duplicate_selection_copies_cursor_line_and_stays_on_copy() {
+        let mut h = crate::test_harness::TestHarness::with_size(40, 5);
+        let path = h.write_file("s.txt", "abc\nxyz\n");
+        h.open_file(&path);
+        h.type_keys("l");
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::DuplicateSelection);
+        assert_eq!(focused_buffer_text(&mut h), "abc\nabc\nxyz\n");
+        assert_eq!(h.primary_head_offset(), "abc\na".len());
+    }
+
+    #[test]
+    fn duplicate_selection_copies_partial_selection_text() {
+        let mut h = crate::test_harness::TestHarness::with_size(40, 5);
+        let path = h.write_file("s.txt", "foo bar\n");
+        h.open_file(&path);
+        h.type_keys("w");
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::DuplicateSelection);
+        assert_eq!(focused_buffer_text(&mut h), "foo foo bar\n");
+        assert_eq!(h.selection_spans(), vec![(4, 8, false)]);
+    }
+
+    #[test]
+    fn duplicate_selection_copies_final_line_without_newline() {
+        let mut h = crate::test_harness::TestHarness::with_size(40, 5);
+        let path = h.write_file("s.txt", "abc");
+        h.open_file(&path);
+        crate::action_handlers::dispatch(&mut h.stoat, &stoat_action::DuplicateSelection);
+        assert_eq!(focused_buffer_text(&mut h), "abc\nabc");
+    }
+
+    #[test]
+    fn move_lines_down_carries_cursor_with_line() {
+        let mut h = crate::test_harness::TestHarness::with_size(40, 5);
+        let path = h.write_file("s.txt", "one\ntwo\nthree\n");
+        h.open_file(&path);
+        h.type_keys("l");
+        h.type_keys("alt-j");
+        assert_eq!(focused_buffer_text(&mut h), "two\none\nthree\n");
+        assert_eq!(h.primary_head_offset(), "two\no".len());
+        h.type_keys("alt-j");
+        assert_eq!(focused_buffer_text(&mut h), "two\nthree\none\n");
+        h.type_keys("alt-j");
+        assert_eq!(
+            focused_buffer_text(&mut h),
+            "two\nthree\none\n",
+            "last line stays put"
+        );
+        h.type_keys("u");
+        assert_eq!(focused_buffer_text(&mut h), "two\none\nthree\n");
+    }
+
+    #[test]
+    fn move_lines_up_moves_selected_block_by_count() {
+        let mut h = crate::test_harness::TestHarness::with_size(40, 8);
+        let path = h.write_file("s.txt", "a\nb\nc\nd\ne");
+        h.open_file(&path);
+        h.type_keys("j j j x x");
+        h.type_keys("2 alt-k");
+        assert_eq!(focused_buffer_text(&mut h), "a\nd\ne\nb\nc");
+        assert_eq!(h.selection_spans(), vec![(2, 5, false)]);
+    }
+
+    #[test]
+    fn move_lines_down_huge_count_stops_at_buffer_end() {
+        let mut h = crate::test_harness::TestHarness::with_size(40, 5);
+        let path = h.write_file("s.txt", "one\ntwo\nthree\n");
+        h.open_file(&path);
+        h.type_keys("9 9 9 9 9 9 9 9 alt-j");
+        assert_eq!(focused_buffer_text(&mut h), "two\nthree\none\n");
+    }
+
     #[test]
     fn indent_selection_inserts_tab_at_cursor_line() {
         let mut h = crate::test_harness::TestHarness::with_size(20, 5);
@@ -3815,6 +3958,19 @@ mod tests {
         assert_eq!(positions, vec![(4, 0)]);
     }
 
+    #[test]
+    fn open_file_at_places_and_scrolls_to_line_and_column() {
+        let mut h = crate::test_harness::TestHarness::with_size(20, 10);
+        let text: String = (1..=40).map(|n| format!("line {n}\n")).collect();
+        let path = h.write_file("s.txt", &text);
+        h.stoat.open_file_at(&path, 30, 3);
+        assert_eq!(h.cursor_display_positions(), vec![(29, 2)]);
+        assert!(
+            h.editor_scroll_rows().iter().any(|&row| row > 0),
+            "view scrolled toward line 30"
+        );
+    }
+
     #[test]
     fn goto_line_number_clamps_at_last_line() {
         let mut h = crate::test_harness::TestHarness::with_size(20, 10);
@@ -749,9 +749,12 @@ pub(crate) fn render_finder_page(
 ///
 /// Mirrors [`render_finder_page`] but paints palette result rows; the page
 /// index alone selects the rows, and the list is read-only here.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn render_palette_page(
     filtered: &[&'static RegistryEntry],
     match_indices: &[Vec<u32>],
+    labels: &[Option<String>],
+    key_hints: &HashMap<String, String>,
     selected: usize,
     page: u64,
     theme: &crate::theme::Theme,
@@ -767,6 +770,8 @@ pub(crate) fn render_palette_page(
     paint_palette_rows(
         filtered,
         match_indices,
+        labels,
+        key_hints,
         selected,
         area,
         start_row,
@@ -965,6 +970,7 @@ mod tests {
         use super::{render_page_from_snapshot, serialize_buffer, Buffer, PageGutter, Rect};
         use crate::{
             action_handlers::{self, dispatch},
+            display_map::WrapRules,
             render::editor::render_editor_with_overlay,
             theme::{scope, Theme},
             LineNumbers, Stoat,
@@ -1013,6 +1019,7 @@ mod tests {
                 false,
                 LineNumbers::Absolute,
                 false,
+                false,
                 None,
                 None,
                 None,
@@ -1022,6 +1029,7 @@ mod tests {
                 0.0,
                 WrapMode::None,
                 80,
+                WrapRules::default(),
             );
             editor.scroll_row = saved;
             let expected = serialize_buffer(&expected);
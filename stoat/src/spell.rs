@@ -0,0 +1,795 @@
+//! Spell checking for comment and string tokens.
+//!
+//! Only text the tree-sitter grammar classifies as a comment or a string is
+//! checked, so identifiers and keywords never light up. Each unknown word is
+//! published into [`crate::app::Stoat::diagnostics`] as a hint under the
+//! [`SPELL_SERVER`] name, which gives it the usual underline and lets
+//! [`crate::action_handlers::lsp::code_action`] offer replacements for it.
+//!
+//! Words are accepted by the system word list named by `spell.dictionary`
+//! plus the workspace's own [`WORKSPACE_DICTIONARY`], which the
+//! add-to-dictionary code action appends to. Checking is opt-in via
+//! `spell.enabled`.
+
+use crate::{app::Stoat, buffer::BufferId, workspace::WorkspaceId};
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    io,
+    ops::Range,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use stoat_language::{Node, Tree};
+use stoat_scheduler::Task;
+use stoat_text::Rope;
+
+/// Server name spelling diagnostics are published under, keeping them apart
+/// from every language server's slice of [`crate::diagnostics::DiagnosticSet`].
+pub(crate) const SPELL_SERVER: &str = "spell";
+
+/// Word list used when `spell.dictionary` is unset.
+const DEFAULT_DICTIONARY: &str = "/usr/share/dict/words";
+
+/// Per-workspace word list, relative to the workspace root.
+const WORKSPACE_DICTIONARY: &str = ".stoat/dictionary.txt";
+
+/// Words shorter than this are skipped; they are mostly abbreviations.
+const MIN_WORD_LEN: usize = 3;
+
+/// Most replacements offered for one misspelling.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Longest word that falls back to two-edit suggestions when no single edit
+/// lands on a known word. The candidate set grows quadratically with length.
+const MAX_SECOND_EDIT_LEN: usize = 10;
+
+/// A set of accepted words, stored lowercase.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Dictionary {
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    /// Parse a word list with one word per line. Blank lines and lines
+    /// starting with `#` are ignored.
+    pub(crate) fn parse(text: &str) -> Self {
+        let words = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect();
+        Self { words }
+    }
+
+    fn load(fs: &dyn crate::host::FsHost, path: &Path) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        fs.read(path, &mut bytes)?;
+        Ok(Self::parse(&String::from_utf8_lossy(&bytes)))
+    }
+
+    fn contains(&self, lower: &str) -> bool {
+        self.words.contains(lower)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Spell checker state held on [`Stoat`].
+#[derive(Default)]
+pub(crate) struct SpellState {
+    /// The system word list and the path it was read from, so a changed
+    /// `spell.dictionary` setting triggers a reload.
+    base: Option<(String, Arc<Dictionary>)>,
+    /// Workspace word lists keyed by workspace root, read on first use and
+    /// kept current by [`add_to_dictionary`].
+    workspace: HashMap<PathBuf, Arc<Dictionary>>,
+    /// Syntax version each path was last checked at, keyed by the workspace
+    /// holding the buffer. Doubles as the set of paths holding published
+    /// spelling diagnostics.
+    checked: HashMap<(WorkspaceId, PathBuf), u64>,
+    /// Checks running on the background pool, keyed like `checked`.
+    jobs: HashMap<(WorkspaceId, PathBuf), SpellJob>,
+}
+
+impl SpellState {
+    /// Forget every check so each open buffer is checked again, dropping any
+    /// in flight against the old word lists.
+    fn invalidate(&mut self) {
+        self.checked.clear();
+        self.jobs.clear();
+    }
+}
+
+/// A background check of one buffer at one syntax version.
+struct SpellJob {
+    version: u64,
+    task: Task<Vec<Diagnostic>>,
+}
+
+/// The word lists consulted for one workspace.
+pub(crate) struct Lexicon<'a> {
+    base: &'a Dictionary,
+    workspace: Option<&'a Dictionary>,
+}
+
+impl Lexicon<'_> {
+    /// Whether `word` is accepted, ignoring case and a trailing possessive.
+    pub(crate) fn knows(&self, word: &str) -> bool {
+        let lower = word.to_lowercase();
+        self.knows_lower(&lower)
+            || lower
+                .strip_suffix("'s")
+                .is_some_and(|w| self.knows_lower(w))
+    }
+
+    fn knows_lower(&self, lower: &str) -> bool {
+        self.base.contains(lower) || self.workspace.is_some_and(|d| d.contains(lower))
+    }
+
+    /// Known words within two edits of `word`, closest first, cased like
+    /// `word`. Transpositions rank ahead of substitutions, which rank ahead
+    /// of insertions and deletions; candidates sharing the first letter win
+    /// ties. Two-edit candidates are only tried when no single edit is known.
+    pub(crate) fn suggest(&self, word: &str) -> Vec<String> {
+        let lower = word.to_ascii_lowercase();
+        let first = lower.chars().next();
+        let mut ranked: Vec<(u8, bool, String)> = edits(&lower)
+            .into_iter()
+            .filter(|(_, c)| self.knows_lower(c))
+            .map(|(rank, c)| (rank, c.chars().next() != first, c))
+            .collect();
+        if ranked.is_empty() && lower.len() <= MAX_SECOND_EDIT_LEN {
+            for (_, once) in edits(&lower) {
+                ranked.extend(
+                    edits(&once)
+                        .into_iter()
+                        .filter(|(_, c)| c != &lower && self.knows_lower(c))
+                        .map(|(rank, c)| (3 + rank, c.chars().next() != first, c)),
+                );
+            }
+        }
+        ranked.sort();
+        let mut seen = HashSet::new();
+        ranked
+            .into_iter()
+            .filter(|(_, _, c)| seen.insert(c.clone()))
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, _, c)| match_case(word, &c))
+            .collect()
+    }
+}
+
+/// Every string one edit away from the ASCII `word`, tagged with a rank:
+/// 0 for a transposition, 1 for a substitution, 2 for an insertion or
+/// deletion.
+fn edits(word: &str) -> Vec<(u8, String)> {
+    let bytes = word.as_bytes();
+    let mut out = Vec::new();
+    for i in 0..bytes.len().saturating_sub(1) {
+        let mut b = bytes.to_vec();
+        b.swap(i, i + 1);
+        out.push((0, b));
+    }
+    for (i, &orig) in bytes.iter().enumerate() {
+        for c in b'a'..=b'z' {
+            if orig != c {
+                let mut b = bytes.to_vec();
+                b[i] = c;
+                out.push((1, b));
+            }
+        }
+    }
+    for i in 0..bytes.len() {
+        let mut b = bytes.to_vec();
+        b.remove(i);
+        out.push((2, b));
+    }
+    for i in 0..=bytes.len() {
+        for c in b'a'..=b'z' {
+            let mut b = bytes.to_vec();
+            b.insert(i, c);
+            out.push((2, b));
+        }
+    }
+    out.into_iter()
+        .filter_map(|(rank, b)| String::from_utf8(b).ok().map(|s| (rank, s)))
+        .collect()
+}
+
+/// Recase the lowercase `suggestion` after `word`: all caps stays all caps,
+/// a leading capital stays capitalized.
+fn match_case(word: &str, suggestion: &str) -> String {
+    if word.len() > 1 && word.chars().all(|c| !c.is_lowercase()) {
+        return suggestion.to_uppercase();
+    }
+    if word.chars().next().is_some_and(char::is_uppercase) {
+        let mut chars = suggestion.chars();
+        return match chars.next() {
+            Some(c) => c.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        };
+    }
+    suggestion.to_string()
+}
+
+/// Byte ranges of `tree` holding comment or string text.
+///
+/// A node whose kind names a comment is taken whole. A string node is taken
+/// minus its named children that are not themselves string content, which
+/// drops escape sequences and interpolated code. Neither is descended into
+/// further.
+pub(crate) fn checked_ranges(tree: &Tree) -> Vec<Range<usize>> {
+    let mut out = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        let kind = node.kind();
+        if kind.contains("comment") {
+            out.push(node.start_byte()..node.end_byte());
+        } else if kind.contains("string") {
+            let mut start = node.start_byte();
+            for child in named_children(node) {
+                let ck = child.kind();
+                if ck.contains("string") || ck.contains("content") || ck.contains("fragment") {
+                    continue;
+                }
+                out.push(start..child.start_byte());
+                start = child.end_byte();
+            }
+            out.push(start..node.end_byte());
+        } else {
+            let mut children = named_children(node);
+            children.reverse();
+            stack.extend(children);
+        }
+    }
+    out.retain(|r| r.start < r.end);
+    out.sort_by_key(|r| r.start);
+    out
+}
+
+fn named_children(node: Node<'_>) -> Vec<Node<'_>> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+/// Byte ranges within `text` of the words worth checking.
+///
+/// A word is a run of letters, digits, underscores, and inner apostrophes.
+/// Runs glued by `.`, `/`, `:`, or `@` to more alphanumerics (paths, URLs,
+/// addresses, qualified names) are skipped, as are runs right after a
+/// backslash escape. Of the rest, only all-letter ASCII words of at least
+/// [`MIN_WORD_LEN`] letters with no capital past the first are checked, so
+/// identifiers and acronyms quoted in prose are left alone.
+pub(crate) fn words(text: &str) -> Vec<Range<usize>> {
+    let mut out = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if !(c.is_alphanumeric() || c == '_') {
+            prev = Some(c);
+            continue;
+        }
+        let escaped = prev == Some('\\');
+        let mut compound = false;
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, c)) = chars.peek() {
+            let rest = text[i..].trim_start_matches(is_joiner);
+            let glued = is_joiner(c) && rest.chars().next().is_some_and(char::is_alphanumeric);
+            if c.is_alphanumeric() || c == '_' || c == '\'' {
+                end = i + c.len_utf8();
+            } else if glued {
+                compound = true;
+                end = i + c.len_utf8();
+            } else {
+                break;
+            }
+            chars.next();
+        }
+        prev = text[..end].chars().next_back();
+        let word = text[start..end].trim_end_matches('\'');
+        if !escaped && !compound && is_checkable(word) {
+            out.push(start..start + word.len());
+        }
+    }
+    out
+}
+
+fn is_joiner(c: char) -> bool {
+    matches!(c, '.' | '/' | ':' | '@')
+}
+
+fn is_checkable(word: &str) -> bool {
+    word.len() >= MIN_WORD_LEN
+        && word.chars().all(|c| c.is_ascii_alphabetic() || c == '\'')
+        && !word.chars().skip(1).any(|c| c.is_ascii_uppercase())
+}
+
+/// Byte ranges of every unknown word in the comments and strings of `tree`,
+/// which was parsed from `rope`.
+pub(crate) fn misspellings(tree: &Tree, rope: &Rope, lexicon: &Lexicon<'_>) -> Vec<Range<usize>> {
+    let mut out = Vec::new();
+    for range in checked_ranges(tree) {
+        let text: String = rope.chunks_in_range(range.clone()).collect();
+        for word in words(&text) {
+            if !lexicon.knows(&text[word.clone()]) {
+                out.push(range.start + word.start..range.start + word.end);
+            }
+        }
+    }
+    out
+}
+
+/// Check every open buffer in the active workspace whose syntax tree has
+/// changed since its last check, replacing its spelling diagnostics.
+///
+/// Checks run on the background pool, at most one per buffer at a time, and
+/// publish when a later call finds them finished. With spell checking
+/// disabled, clears any diagnostics a prior enabled run published. The system
+/// word list is read on first use; when it cannot be read, nothing is
+/// checked, since every word would otherwise be flagged.
+pub(crate) fn refresh(stoat: &mut Stoat) {
+    if stoat.settings.spell_enabled != Some(true) {
+        clear(stoat);
+        return;
+    }
+    let dict_path = stoat
+        .settings
+        .spell_dictionary
+        .clone()
+        .unwrap_or_else(|| DEFAULT_DICTIONARY.to_string());
+    if stoat.spell.base.as_ref().map(|(p, _)| p) != Some(&dict_path) {
+        let dict = match Dictionary::load(stoat.fs_host.as_ref(), Path::new(&dict_path)) {
+            Ok(dict) => dict,
+            Err(err) => {
+                tracing::warn!(target: "stoat::spell", %err, path = %dict_path, "dictionary unreadable");
+                stoat.set_status(format!("spell: cannot read {dict_path}: {err}"));
+                Dictionary::default()
+            },
+        };
+        stoat.spell.base = Some((dict_path, Arc::new(dict)));
+        stoat.spell.invalidate();
+    }
+    let root = stoat.active_workspace().git_root.clone();
+    if !stoat.spell.workspace.contains_key(&root) {
+        // A workspace without a word list yet is the common case.
+        let dict = Dictionary::load(stoat.fs_host.as_ref(), &root.join(WORKSPACE_DICTIONARY))
+            .unwrap_or_default();
+        stoat.spell.workspace.insert(root.clone(), Arc::new(dict));
+    }
+    let Some((_, base)) = stoat.spell.base.clone() else {
+        return;
+    };
+    if base.is_empty() {
+        return;
+    }
+    let words = stoat.spell.workspace.get(&root).cloned();
+
+    let ws_id = stoat.active_workspace;
+    let mut stale = Vec::new();
+    let open: Vec<PathBuf>;
+    {
+        let buffers = &stoat.active_workspace().buffers;
+        open = buffers.open_paths();
+        for path in &open {
+            let Some(syntax) = buffers.id_for_path(path).and_then(|id| buffers.syntax(id)) else {
+                continue;
+            };
+            let key = (ws_id, path.clone());
+            if stoat.spell.checked.get(&key) == Some(&syntax.version)
+                || stoat.spell.jobs.contains_key(&key)
+            {
+                continue;
+            }
+            stale.push((
+                key,
+                syntax.version,
+                syntax.tree.clone(),
+                syntax.rope_snapshot.clone(),
+            ));
+        }
+    }
+    for (key, version, tree, rope) in stale {
+        let (base, words) = (base.clone(), words.clone());
+        let redraw = stoat.redraw_notify.clone();
+        let task = stoat.executor.spawn_blocking(move || {
+            let lexicon = Lexicon {
+                base: &base,
+                workspace: words.as_deref(),
+            };
+            let diagnostics = misspellings(&tree, &rope, &lexicon)
+                .into_iter()
+                .map(|range| diagnostic(&rope, range))
+                .collect();
+            redraw.notify_one();
+            diagnostics
+        });
+        stoat.spell.jobs.insert(key, SpellJob { version, task });
+    }
+
+    // A buffer is gone once its workspace is, or once the active workspace
+    // no longer holds it. Other workspaces' buffers are left for their turn.
+    let workspaces = &stoat.workspaces;
+    let gone = |(ws, path): &(WorkspaceId, PathBuf)| {
+        !workspaces.contains_key(*ws) || (*ws == ws_id && !open.contains(path))
+    };
+    stoat.spell.jobs.retain(|key, _| !gone(key));
+    let closed: Vec<(WorkspaceId, PathBuf)> = stoat
+        .spell
+        .checked
+        .keys()
+        .filter(|k| gone(k))
+        .cloned()
+        .collect();
+    for key in closed {
+        stoat.spell.checked.remove(&key);
+        // Another workspace may still show the same file.
+        if !stoat.spell.checked.keys().any(|(_, p)| *p == key.1) {
+            stoat
+                .diagnostics
+                .replace_from_server(key.1, SPELL_SERVER.to_string(), Vec::new());
+        }
+    }
+
+    let waker = futures::task::noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut finished = Vec::new();
+    stoat
+        .spell
+        .jobs
+        .retain(|key, job| match Pin::new(&mut job.task).poll(&mut cx) {
+            Poll::Ready(diagnostics) => {
+                finished.push((key.clone(), job.version, diagnostics));
+                false
+            },
+            Poll::Pending => true,
+        });
+    for (key, version, diagnostics) in finished {
+        let path = key.1.clone();
+        stoat.spell.checked.insert(key, version);
+        stoat
+            .diagnostics
+            .replace_from_server(path, SPELL_SERVER.to_string(), diagnostics);
+    }
+}
+
+/// The word lists for the workspace rooted at `root`, or `None` before the
+/// system list is loaded or when it came up empty.
+fn lexicon<'a>(stoat: &'a Stoat, root: &Path) -> Option<Lexicon<'a>> {
+    let (_, base) = stoat.spell.base.as_ref()?;
+    if base.is_empty() {
+        return None;
+    }
+    Some(Lexicon {
+        base,
+        workspace: stoat.spell.workspace.get(root).map(|d| &**d),
+    })
+}
+
+fn diagnostic(rope: &Rope, range: Range<usize>) -> Diagnostic {
+    let word: String = rope.chunks_in_range(range.clone()).collect();
+    Diagnostic {
+        range: crate::lsp::util::byte_range_to_lsp_range(
+            rope,
+            range,
+            crate::host::OffsetEncoding::Utf16,
+        ),
+        severity: Some(DiagnosticSeverity::HINT),
+        source: Some(SPELL_SERVER.to_string()),
+        message: format!("unknown word: {word}"),
+        ..Diagnostic::default()
+    }
+}
+
+/// Drop every published spelling diagnostic and forget the check history.
+fn clear(stoat: &mut Stoat) {
+    stoat.spell.jobs.clear();
+    if stoat.spell.checked.is_empty() {
+        return;
+    }
+    for ((_, path), _) in std::mem::take(&mut stoat.spell.checked) {
+        stoat
+            .diagnostics
+            .replace_from_server(path, SPELL_SERVER.to_string(), Vec::new());
+    }
+}
+
+/// A misspelling under the cursor: its byte range in the live buffer, the
+/// word itself, and the replacements to offer.
+pub(crate) struct Misspelling {
+    pub(crate) range: Range<usize>,
+    pub(crate) word: String,
+    pub(crate) suggestions: Vec<String>,
+}
+
+/// The spelling diagnostic on `buffer_id` covering byte `offset`, resolved
+/// against the live `rope`, with suggestions for it.
+pub(crate) fn misspelling_at(
+    stoat: &Stoat,
+    buffer_id: BufferId,
+    rope: &Rope,
+    offset: usize,
+) -> Option<Misspelling> {
+    let ws = stoat.active_workspace();
+    let path = ws.buffers.path_for(buffer_id)?;
+    let range = stoat
+        .diagnostics
+        .attributed(path)
+        .filter(|(server, _)| *server == SPELL_SERVER)
+        .map(|(_, diag)| {
+            crate::lsp::util::lsp_range_to_byte_range(
+                rope,
+                diag.range,
+                crate::host::OffsetEncoding::Utf16,
+            )
+        })
+        .find(|r| r.start <= offset && offset <= r.end)?;
+    let word: String = rope.chunks_in_range(range.clone()).collect();
+    if !is_checkable(&word) {
+        return None;
+    }
+    let lexicon = lexicon(stoat, &ws.git_root)?;
+    Some(Misspelling {
+        suggestions: lexicon.suggest(&word),
+        range,
+        word,
+    })
+}
+
+/// Replace `range` of `buffer_id` with `replacement`.
+pub(crate) fn replace_word(
+    stoat: &mut Stoat,
+    buffer_id: BufferId,
+    range: Range<usize>,
+    replacement: &str,
+) {
+    let Some(buffer) = stoat.active_workspace().buffers.get(buffer_id) else {
+        return;
+    };
+    let mut guard = buffer.write().expect("buffer poisoned");
+    if range.end <= guard.rope().len() {
+        guard.edit(range, replacement);
+    }
+}
+
+/// Append `word` to the active workspace's word list, creating the file if
+/// needed, and recheck every buffer against the grown list.
+pub(crate) fn add_to_dictionary(stoat: &mut Stoat, word: &str) {
+    let root = stoat.active_workspace().git_root.clone();
+    let path = root.join(WORKSPACE_DICTIONARY);
+    let mut bytes = Vec::new();
+    // A missing list starts empty.
+    let _ = stoat.fs_host.read(&path, &mut bytes);
+    if !bytes.is_empty() && !bytes.ends_with(b"\n") {
+        bytes.push(b'\n');
+    }
+    bytes.extend_from_slice(word.as_bytes());
+    bytes.push(b'\n');
+    let written = match path.parent() {
+        Some(dir) => stoat.fs_host.create_dir_all(dir),
+        None => Ok(()),
+    }
+    .and_then(|()| stoat.fs_host.write(&path, &bytes));
+    if let Err(err) = written {
+        stoat.set_status(format!("spell: cannot write {}: {err}", path.display()));
+        return;
+    }
+    let dict = stoat.spell.workspace.entry(root).or_default();
+    Arc::make_mut(dict).words.insert(word.to_lowercase());
+    stoat.spell.invalidate();
+    stoat.set_status(format!(
+        "spell: added \"{word}\" to the workspace dictionary"
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::TestHarness;
+
+    /// Open `/spell/a.rs` holding `contents` with spell checking on against a
+    /// small word list, and drive the parse and check that follow.
+    fn open_checked(h: &mut TestHarness, contents: &str) -> PathBuf {
+        let root = PathBuf::from("/spell");
+        let path = root.join("a.rs");
+        h.fake_fs()
+            .insert_file(root.join("words"), "the\nquick\nbrown\nfox\n");
+        h.fake_fs().insert_file(&path, contents);
+        h.stoat.settings.spell_enabled = Some(true);
+        h.stoat.settings.spell_dictionary = Some("/spell/words".to_string());
+        h.stoat.active_workspace_mut().git_root = root;
+        crate::action_handlers::dispatch(
+            &mut h.stoat,
+            &stoat_action::OpenFile { path: path.clone() },
+        );
+        h.settle();
+        check(h);
+        path
+    }
+
+    /// Spawn the checks of stale buffers, run them, and publish the results.
+    fn check(h: &mut TestHarness) {
+        h.stoat.drive_background();
+        h.settle();
+        h.stoat.drive_background();
+    }
+
+    fn flagged(h: &TestHarness, path: &Path) -> Vec<String> {
+        h.stoat
+            .diagnostics
+            .attributed(path)
+            .filter(|(server, _)| *server == SPELL_SERVER)
+            .map(|(_, diag)| diag.message.clone())
+            .collect()
+    }
+
+    fn buffer_string(h: &TestHarness, path: &Path) -> String {
+        let buffers = &h.stoat.active_workspace().buffers;
+        let id = buffers.id_for_path(path).expect("open");
+        let buffer = buffers.get(id).expect("buffer");
+        let text = buffer.read().expect("poisoned").rope().to_string();
+        text
+    }
+
+    fn lexicon_of(base: &Dictionary) -> Lexicon<'_> {
+        Lexicon {
+            base,
+            workspace: None,
+        }
+    }
+
+    fn word_list(text: &str) -> Vec<&str> {
+        words(text).into_iter().map(|r| &text[r]).collect()
+    }
+
+    #[test]
+    fn words_skip_identifiers_paths_and_short_runs() {
+        assert_eq!(
+            word_list("// Thsi isn't a camelCase or snake_case URL: https://example.com."),
+            vec!["Thsi", "isn't"]
+        );
+        assert_eq!(word_list("see src/main.rs and NASA"), vec!["see", "and"]);
+        assert_eq!(word_list("line\\nbreak word"), vec!["line", "word"]);
+    }
+
+    #[test]
+    fn knows_ignores_case_and_possessive() {
+        let base = Dictionary::parse("# comment\nthe\nDog\n");
+        let lexicon = lexicon_of(&base);
+        assert!(lexicon.knows("The"));
+        assert!(lexicon.knows("dog's"));
+        assert!(!lexicon.knows("cat"));
+    }
+
+    #[test]
+    fn suggest_ranks_transpositions_first_and_keeps_case() {
+        let base = Dictionary::parse("the\ntea\nten\n");
+        let lexicon = lexicon_of(&base);
+        assert_eq!(lexicon.suggest("teh"), vec!["the", "tea", "ten"]);
+        assert_eq!(lexicon.suggest("Teh"), vec!["The", "Tea", "Ten"]);
+        assert_eq!(lexicon.suggest("TEH"), vec!["THE", "TEA", "TEN"]);
+    }
+
+    #[test]
+    fn suggest_falls_back_to_two_edits() {
+        let base = Dictionary::parse("receive\n");
+        assert_eq!(lexicon_of(&base).suggest("recieev"), vec!["receive"]);
+        assert!(lexicon_of(&base).suggest("zzzzzzzzzzzzzz").is_empty());
+    }
+
+    #[test]
+    fn workspace_dictionary_extends_the_base() {
+        let base = Dictionary::parse("hello\n");
+        let workspace = Dictionary::parse("stoat\n");
+        let lexicon = Lexicon {
+            base: &base,
+            workspace: Some(&workspace),
+        };
+        assert!(lexicon.knows("Stoat"));
+        assert!(lexicon.knows("hello"));
+    }
+
+    #[test]
+    fn flags_comment_and_string_words_only() {
+        let mut h = TestHarness::with_size(80, 24);
+        let path = open_checked(
+            &mut h,
+            "// the qiuck fox\nfn main() { let misspeled = \"brwon fox\"; }\n",
+        );
+        assert_eq!(
+            flagged(&h, &path),
+            vec!["unknown word: qiuck", "unknown word: brwon"]
+        );
+    }
+
+    #[test]
+    fn disabling_clears_published_diagnostics() {
+        let mut h = TestHarness::with_size(80, 24);
+        let path = open_checked(&mut h, "// qiuck\n");
+        assert_eq!(flagged(&h, &path).len(), 1);
+        h.stoat.settings.spell_enabled = Some(false);
+        h.stoat.drive_background();
+        assert!(flagged(&h, &path).is_empty());
+    }
+
+    #[test]
+    fn code_action_replaces_the_word_under_the_cursor() {
+        let mut h = TestHarness::with_size(80, 24);
+        let path = open_checked(&mut h, "// the qiuck fox\n");
+        for _ in 0..8 {
+            h.type_keys("l");
+        }
+        h.type_keys("space l a");
+        h.settle();
+        let picker = h
+            .stoat
+            .pending_code_action_picker
+            .as_ref()
+            .expect("picker open");
+        let titles: Vec<&str> = picker.entries.iter().map(|e| e.title()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Change to \"quick\"",
+                "Add \"qiuck\" to workspace dictionary"
+            ]
+        );
+        h.type_keys("1");
+        h.settle();
+        assert!(h.stoat.pending_code_action_picker.is_none());
+        assert_eq!(buffer_string(&h, &path), "// the quick fox\n");
+    }
+
+    #[test]
+    fn add_to_dictionary_writes_the_workspace_list_and_rechecks() {
+        use crate::host::FsHost;
+        let mut h = TestHarness::with_size(80, 24);
+        let path = open_checked(&mut h, "// stoat qiuck\n");
+        assert_eq!(flagged(&h, &path).len(), 2);
+        add_to_dictionary(&mut h.stoat, "stoat");
+        check(&mut h);
+        assert_eq!(flagged(&h, &path), vec!["unknown word: qiuck"]);
+
+        let mut bytes = Vec::new();
+        h.fake_fs()
+            .read(Path::new("/spell/.stoat/dictionary.txt"), &mut bytes)
+            .unwrap();
+        assert_eq!(bytes, b"stoat\n");
+    }
+
+    #[test]
+    fn checks_publish_once_the_background_job_finishes() {
+        let mut h = TestHarness::with_size(80, 24);
+        let path = open_checked(&mut h, "// qiuck\n");
+        {
+            let buffers = &h.stoat.active_workspace().buffers;
+            let id = buffers.id_for_path(&path).expect("open");
+            let buffer = buffers.get(id).expect("buffer");
+            buffer.write().expect("poisoned").edit(9..9, "// brwon\n");
+        }
+        h.stoat.drive_background();
+        assert_eq!(flagged(&h, &path), vec!["unknown word: qiuck"]);
+        h.settle();
+        h.stoat.drive_background();
+        assert_eq!(
+            flagged(&h, &path),
+            vec!["unknown word: qiuck", "unknown word: brwon"]
+        );
+    }
+
+    #[test]
+    fn switching_workspaces_keeps_diagnostics_of_the_one_left() {
+        let mut h = TestHarness::with_size(80, 24);
+        let path = open_checked(&mut h, "// qiuck\n");
+        let other = h.create_workspace();
+        h.set_active_workspace(other);
+        check(&mut h);
+        assert_eq!(flagged(&h, &path), vec!["unknown word: qiuck"]);
+    }
+}
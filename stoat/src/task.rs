@@ -0,0 +1,631 @@
+//! Configured build and test tasks.
+//!
+//! A task is a named shell command from `task.command.<name>`, run from the
+//! workspace root on the blocking pool. When it exits, its combined output is
+//! fed through the task's [`ProblemMatcher`] and every problem found is
+//! published into [`crate::app::Stoat::diagnostics`] under [`TASK_SERVER`],
+//! replacing the previous run's problems. The problems are also kept in output
+//! order so [`goto_next_problem`] can walk them across files.
+
+use crate::{
+    action_handlers,
+    app::{Stoat, UpdateEffect},
+    host::{OffsetEncoding, ShellOutput},
+};
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use stoat_scheduler::Task;
+
+/// Server name task problems are published under, keeping them apart from
+/// every language server's slice of [`crate::diagnostics::DiagnosticSet`].
+pub(crate) const TASK_SERVER: &str = "task";
+
+/// Snippet lines scanned past a `-->` location for the `^^^` marker that
+/// gives a problem's width.
+const CARET_LOOKAHEAD: usize = 8;
+
+/// How a task's output is turned into problems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProblemMatcher {
+    /// rustc diagnostics as cargo prints them, in the default human format or
+    /// `--message-format=short`, plus `cargo test` panic locations.
+    Cargo,
+    /// No parsing; the task only reports its exit status.
+    Off,
+}
+
+impl ProblemMatcher {
+    /// The matcher named by a `task.matcher.<name>` value, `cargo` when unset.
+    fn from_setting(name: Option<&str>) -> Option<Self> {
+        match name {
+            None | Some("cargo") | Some("rustc") => Some(Self::Cargo),
+            Some("none") => Some(Self::Off),
+            Some(_) => None,
+        }
+    }
+
+    pub(crate) fn parse(self, output: &str) -> Vec<Problem> {
+        match self {
+            Self::Cargo => parse_cargo(output),
+            Self::Off => Vec::new(),
+        }
+    }
+}
+
+/// One problem a matcher found, positioned as the tool printed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Problem {
+    /// Path as printed, relative to the directory the task ran in unless
+    /// absolute.
+    pub(crate) path: PathBuf,
+    /// Zero-based line.
+    pub(crate) line: u32,
+    /// Zero-based column.
+    pub(crate) column: u32,
+    /// Columns the problem spans, at least one.
+    pub(crate) width: u32,
+    pub(crate) severity: DiagnosticSeverity,
+    pub(crate) code: Option<String>,
+    pub(crate) message: String,
+}
+
+/// Task state held on [`Stoat`].
+#[derive(Default)]
+pub(crate) struct TaskState {
+    /// Name of the most recently started task, for `RerunTask`.
+    last: Option<String>,
+    running: Option<RunningTask>,
+    /// Problems of the last finished run in output order, as absolute path
+    /// plus position and message.
+    problems: Vec<(PathBuf, Position, String)>,
+    /// Index into `problems` of the last one jumped to.
+    cursor: Option<usize>,
+    /// Paths holding published task diagnostics, cleared on the next run.
+    published: Vec<PathBuf>,
+}
+
+/// A task in flight. The job stores its output in `result` and wakes the
+/// event loop; [`pump`] installs it.
+struct RunningTask {
+    name: String,
+    root: PathBuf,
+    matcher: ProblemMatcher,
+    _task: Task<()>,
+    result: Arc<Mutex<Option<io::Result<ShellOutput>>>>,
+}
+
+/// Start the task `name` from the active workspace's root. Reports instead
+/// when the name is not configured, its matcher is unknown, or a task is
+/// already running.
+pub(crate) fn run_task(stoat: &mut Stoat, name: &str) -> UpdateEffect {
+    if let Some(running) = &stoat.tasks.running {
+        let message = format!("{}: still running", running.name);
+        stoat.set_status(message);
+        return UpdateEffect::Redraw;
+    }
+    let Some(command) = stoat.settings.task_commands.get(name).cloned() else {
+        stoat.set_status(format!("unknown task: {name}"));
+        return UpdateEffect::Redraw;
+    };
+    let matcher_name = stoat.settings.task_matchers.get(name).map(String::as_str);
+    let Some(matcher) = ProblemMatcher::from_setting(matcher_name) else {
+        let message = format!(
+            "{name}: unknown problem matcher {}",
+            matcher_name.unwrap_or("")
+        );
+        stoat.set_status(message);
+        return UpdateEffect::Redraw;
+    };
+    let ws = stoat.active_workspace();
+    let root = ws.git_root.clone();
+    let env = ws.env.diff.clone();
+    let shell_host = stoat.shell_host.clone();
+    let redraw = stoat.redraw_notify.clone();
+    let result = Arc::new(Mutex::new(None));
+    let slot = result.clone();
+    let cwd = root.clone();
+    let task = stoat.executor.spawn_blocking(move || {
+        let out = shell_host.run(&command, b"", Some(&cwd), &env);
+        *slot.lock().expect("task result poisoned") = Some(out);
+        redraw.notify_one();
+    });
+    stoat.tasks.last = Some(name.to_string());
+    stoat.tasks.running = Some(RunningTask {
+        name: name.to_string(),
+        root,
+        matcher,
+        _task: task,
+        result,
+    });
+    stoat.set_status(format!("{name}: running"));
+    UpdateEffect::Redraw
+}
+
+/// Run the most recently started task again.
+pub(crate) fn rerun_task(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(name) = stoat.tasks.last.clone() else {
+        stoat.set_status("no task has run");
+        return UpdateEffect::Redraw;
+    };
+    run_task(stoat, &name)
+}
+
+/// Install a finished task run: publish its problems, replacing the previous
+/// run's, and report a summary. Called from [`Stoat::drive_background`].
+pub(crate) fn pump(stoat: &mut Stoat) {
+    let Some(running) = &stoat.tasks.running else {
+        return;
+    };
+    let Some(result) = running.result.lock().expect("task result poisoned").take() else {
+        return;
+    };
+    let running = stoat.tasks.running.take().expect("checked above");
+    let output = match result {
+        Ok(output) => output,
+        Err(err) => {
+            stoat.set_status(format!("{}: {err}", running.name));
+            return;
+        },
+    };
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    let problems = running.matcher.parse(&text);
+    let summary = summarize(&running.name, &problems, output.exit_code);
+    publish(stoat, &running.root, &running.name, problems);
+    stoat.set_status(summary);
+}
+
+/// Replace the published task diagnostics with `problems`, resolving their
+/// paths against `root`.
+fn publish(stoat: &mut Stoat, root: &Path, name: &str, problems: Vec<Problem>) {
+    for path in std::mem::take(&mut stoat.tasks.published) {
+        stoat
+            .diagnostics
+            .replace_from_server(path, TASK_SERVER.to_string(), Vec::new());
+    }
+    let mut by_path: BTreeMap<PathBuf, Vec<Diagnostic>> = BTreeMap::new();
+    let mut ordered = Vec::with_capacity(problems.len());
+    for problem in problems {
+        let path = root.join(&problem.path);
+        let start = Position::new(problem.line, problem.column);
+        ordered.push((path.clone(), start, problem.message.clone()));
+        by_path.entry(path).or_default().push(Diagnostic {
+            range: Range::new(
+                start,
+                Position::new(problem.line, problem.column + problem.width),
+            ),
+            severity: Some(problem.severity),
+            code: problem.code.map(NumberOrString::String),
+            source: Some(name.to_string()),
+            message: problem.message,
+            ..Diagnostic::default()
+        });
+    }
+    for (path, diagnostics) in by_path {
+        stoat.tasks.published.push(path.clone());
+        stoat
+            .diagnostics
+            .replace_from_server(path, TASK_SERVER.to_string(), diagnostics);
+    }
+    stoat.tasks.problems = ordered;
+    stoat.tasks.cursor = None;
+}
+
+fn summarize(name: &str, problems: &[Problem], exit_code: i32) -> String {
+    let errors = problems
+        .iter()
+        .filter(|p| p.severity == DiagnosticSeverity::ERROR)
+        .count();
+    let warnings = problems.len() - errors;
+    let mut parts = Vec::new();
+    if errors > 0 {
+        parts.push(plural(errors, "error"));
+    }
+    if warnings > 0 {
+        parts.push(plural(warnings, "warning"));
+    }
+    match (parts.is_empty(), exit_code) {
+        (true, 0) => format!("{name}: ok"),
+        (true, code) => format!("{name}: failed with exit {code}"),
+        (false, _) => format!("{name}: {}", parts.join(", ")),
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Jump to the next problem of the last task run, wrapping past the last.
+pub(crate) fn goto_next_problem(stoat: &mut Stoat) -> UpdateEffect {
+    goto_problem(stoat, true)
+}
+
+/// Jump to the previous problem of the last task run, wrapping before the
+/// first.
+pub(crate) fn goto_prev_problem(stoat: &mut Stoat) -> UpdateEffect {
+    goto_problem(stoat, false)
+}
+
+fn goto_problem(stoat: &mut Stoat, forward: bool) -> UpdateEffect {
+    let len = stoat.tasks.problems.len();
+    if len == 0 {
+        stoat.set_status("no task problems");
+        return UpdateEffect::Redraw;
+    }
+    let index = match (stoat.tasks.cursor, forward) {
+        (None, true) => 0,
+        (None, false) => len - 1,
+        (Some(i), true) => (i + 1) % len,
+        (Some(i), false) => (i + len - 1) % len,
+    };
+    stoat.tasks.cursor = Some(index);
+    let (path, position, message) = stoat.tasks.problems[index].clone();
+    action_handlers::jump::push_jump(stoat);
+    if action_handlers::file::open_file(stoat, &path).is_none() {
+        stoat.set_status(format!("cannot open {}", path.display()));
+        return UpdateEffect::Redraw;
+    }
+    let offset = stoat
+        .offset_for_focused_point(position.line, position.character, OffsetEncoding::Utf16)
+        .unwrap_or(0);
+    stoat.collapse_focused_cursor_to(offset);
+    stoat.set_status(format!("[{}/{len}] {message}", index + 1));
+    UpdateEffect::Redraw
+}
+
+/// Parse rustc diagnostics and test panics out of cargo output.
+///
+/// A human-format `error[E0425]: message` header takes its location from the
+/// `-->` line that follows and its width from the first `^^^` marker of the
+/// snippet. A header with no location (`error: could not compile`) is
+/// dropped. Short-format lines carry header and location on one line.
+fn parse_cargo(output: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut pending: Option<(DiagnosticSeverity, Option<String>, String)> = None;
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        if let Some(problem) = parse_short(line) {
+            problems.push(problem);
+            pending = None;
+        } else if let Some(header) = parse_header(line) {
+            pending = Some(header);
+        } else if let Some(location) = line.trim_start().strip_prefix("--> ") {
+            if let Some((severity, code, message)) = pending.take()
+                && let Some((path, line, column)) = parse_location(location)
+            {
+                problems.push(Problem {
+                    path,
+                    line,
+                    column,
+                    width: caret_width(lines.clone()),
+                    severity,
+                    code,
+                    message,
+                });
+            }
+        } else if let Some(location) = panic_location(line) {
+            pending = None;
+            if let Some((path, line, column)) = parse_location(location) {
+                let detail = lines.clone().next().unwrap_or("").trim();
+                problems.push(Problem {
+                    path,
+                    line,
+                    column,
+                    width: 1,
+                    severity: DiagnosticSeverity::ERROR,
+                    code: None,
+                    message: format!("panicked: {detail}"),
+                });
+            }
+        }
+    }
+    problems
+}
+
+/// `error[E0425]: message` or `warning: message` at the start of a line.
+fn parse_header(line: &str) -> Option<(DiagnosticSeverity, Option<String>, String)> {
+    let (severity, rest) = if let Some(rest) = line.strip_prefix("error") {
+        (DiagnosticSeverity::ERROR, rest)
+    } else if let Some(rest) = line.strip_prefix("warning") {
+        (DiagnosticSeverity::WARNING, rest)
+    } else {
+        return None;
+    };
+    let (code, rest) = match rest.strip_prefix('[') {
+        Some(rest) => {
+            let (code, rest) = rest.split_once(']')?;
+            (Some(code.to_string()), rest)
+        },
+        None => (None, rest),
+    };
+    let message = rest.strip_prefix(": ")?;
+    Some((severity, code, message.to_string()))
+}
+
+/// `src/main.rs:2:5: error[E0425]: message`, the short message format.
+fn parse_short(line: &str) -> Option<Problem> {
+    let (location, rest) = line.split_once(": ")?;
+    let (path, line, column) = parse_location(location)?;
+    let (severity, code, message) = parse_header(rest)?;
+    Some(Problem {
+        path,
+        line,
+        column,
+        width: 1,
+        severity,
+        code,
+        message,
+    })
+}
+
+/// `path:line:column` with one-based line and column, returned zero-based.
+fn parse_location(location: &str) -> Option<(PathBuf, u32, u32)> {
+    let mut parts = location.trim().rsplitn(3, ':');
+    let column: u32 = parts.next()?.parse().ok()?;
+    let line: u32 = parts.next()?.parse().ok()?;
+    let path = parts.next().filter(|p| !p.is_empty())?;
+    Some((
+        PathBuf::from(path),
+        line.checked_sub(1)?,
+        column.checked_sub(1)?,
+    ))
+}
+
+/// The location of `thread 'name' panicked at src/lib.rs:10:5:`.
+fn panic_location(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("thread '")?;
+    let (_, location) = rest.split_once("' panicked at ")?;
+    location.strip_suffix(':')
+}
+
+/// Length of the first `^` run in the snippet lines ahead, stopping at the
+/// next unindented line. One when the snippet has no marker.
+fn caret_width<'a>(lines: impl Iterator<Item = &'a str>) -> u32 {
+    for line in lines.take(CARET_LOOKAHEAD) {
+        if !line.starts_with(' ') && !line.starts_with(|c: char| c.is_ascii_digit()) {
+            break;
+        }
+        let Some((_, snippet)) = line.split_once('|') else {
+            continue;
+        };
+        if let Some(start) = snippet.find('^') {
+            let width = snippet[start..].chars().take_while(|&c| c == '^').count();
+            return width as u32;
+        }
+    }
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{action_handlers::dispatch, host::FakeShell, test_harness::TestHarness};
+
+    const BUILD_OUTPUT: &str = "   Compiling demo v0.1.0 (/task)
+error[E0425]: cannot find value `x` in this scope
+ --> src/main.rs:2:5
+  |
+2 |     xyz;
+  |     ^^^ not found in this scope
+
+warning: unused variable: `y`
+ --> src/lib.rs:3:9
+  |
+3 |     let y = 1;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_y`
+  |
+  = note: `#[warn(unused_variables)]` on by default
+
+error: could not compile `demo` (bin \"demo\") due to 1 previous error
+";
+
+    fn problem(
+        path: &str,
+        line: u32,
+        column: u32,
+        width: u32,
+        severity: DiagnosticSeverity,
+        code: Option<&str>,
+        message: &str,
+    ) -> Problem {
+        Problem {
+            path: PathBuf::from(path),
+            line,
+            column,
+            width,
+            severity,
+            code: code.map(str::to_string),
+            message: message.to_string(),
+        }
+    }
+
+    /// Point the workspace at `/task` holding `src/main.rs`, configure a
+    /// `build` task, and answer it with `stderr` from a fake shell.
+    fn setup(h: &mut TestHarness, stderr: &str) -> Arc<FakeShell> {
+        let root = PathBuf::from("/task");
+        h.fake_fs()
+            .insert_file(root.join("src/main.rs"), "fn main() {\n    xyz;\n}\n");
+        h.stoat.active_workspace_mut().git_root = root;
+        h.stoat
+            .settings
+            .task_commands
+            .insert("build".to_string(), "cargo build".to_string());
+        let fake = Arc::new(FakeShell::new());
+        fake.set_response(
+            "cargo build",
+            ShellOutput {
+                stdout: Vec::new(),
+                stderr: stderr.as_bytes().to_vec(),
+                exit_code: 101,
+            },
+        );
+        h.stoat.set_shell_host(fake.clone());
+        fake
+    }
+
+    fn run(h: &mut TestHarness, name: &str) {
+        dispatch(
+            &mut h.stoat,
+            &stoat_action::RunTask {
+                name: name.to_string(),
+            },
+        );
+        h.settle();
+        h.stoat.drive_background();
+    }
+
+    fn task_messages(h: &TestHarness, path: &Path) -> Vec<String> {
+        h.stoat
+            .diagnostics
+            .attributed(path)
+            .filter(|(server, _)| *server == TASK_SERVER)
+            .map(|(_, diag)| diag.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn cargo_matcher_parses_human_format() {
+        assert_eq!(
+            parse_cargo(BUILD_OUTPUT),
+            vec![
+                problem(
+                    "src/main.rs",
+                    1,
+                    4,
+                    3,
+                    DiagnosticSeverity::ERROR,
+                    Some("E0425"),
+                    "cannot find value `x` in this scope",
+                ),
+                problem(
+                    "src/lib.rs",
+                    2,
+                    8,
+                    1,
+                    DiagnosticSeverity::WARNING,
+                    None,
+                    "unused variable: `y`",
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn cargo_matcher_parses_short_format_and_panics() {
+        let output = "src/main.rs:2:5: error[E0425]: cannot find value `x` in this scope
+thread 'tests::adds' panicked at src/lib.rs:10:9:
+assertion `left == right` failed
+";
+        assert_eq!(
+            parse_cargo(output),
+            vec![
+                problem(
+                    "src/main.rs",
+                    1,
+                    4,
+                    1,
+                    DiagnosticSeverity::ERROR,
+                    Some("E0425"),
+                    "cannot find value `x` in this scope",
+                ),
+                problem(
+                    "src/lib.rs",
+                    9,
+                    8,
+                    1,
+                    DiagnosticSeverity::ERROR,
+                    None,
+                    "panicked: assertion `left == right` failed",
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn run_task_publishes_problems_and_summarizes() {
+        let mut h = TestHarness::with_size(80, 24);
+        let fake = setup(&mut h, BUILD_OUTPUT);
+        run(&mut h, "build");
+
+        let invocation = &fake.invocations()[0];
+        assert_eq!(invocation.cmd, "cargo build");
+        assert_eq!(invocation.cwd.as_deref(), Some(Path::new("/task")));
+        assert_eq!(
+            task_messages(&h, Path::new("/task/src/main.rs")),
+            vec!["cannot find value `x` in this scope"]
+        );
+        assert_eq!(
+            task_messages(&h, Path::new("/task/src/lib.rs")),
+            vec!["unused variable: `y`"]
+        );
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("build: 1 error, 1 warning")
+        );
+    }
+
+    #[test]
+    fn rerun_replaces_previous_problems() {
+        let mut h = TestHarness::with_size(80, 24);
+        let fake = setup(&mut h, BUILD_OUTPUT);
+        run(&mut h, "build");
+        fake.set_response(
+            "cargo build",
+            ShellOutput {
+                stdout: Vec::new(),
+                stderr: b"    Finished `dev` profile\n".to_vec(),
+                exit_code: 0,
+            },
+        );
+        dispatch(&mut h.stoat, &stoat_action::RerunTask);
+        h.settle();
+        h.stoat.drive_background();
+
+        assert_eq!(fake.invocations().len(), 2);
+        assert!(task_messages(&h, Path::new("/task/src/main.rs")).is_empty());
+        assert!(task_messages(&h, Path::new("/task/src/lib.rs")).is_empty());
+        assert_eq!(h.stoat.pending_message.as_deref(), Some("build: ok"));
+    }
+
+    #[test]
+    fn goto_next_problem_opens_file_at_problem() {
+        let mut h = TestHarness::with_size(80, 24);
+        setup(&mut h, BUILD_OUTPUT);
+        run(&mut h, "build");
+        dispatch(&mut h.stoat, &stoat_action::GotoNextTaskProblem);
+
+        let (editor_id, buffer_id) = h.stoat.focused_editor_ids().expect("editor");
+        let ws = h.stoat.active_workspace();
+        assert_eq!(
+            ws.buffers.path_for(buffer_id),
+            Some(Path::new("/task/src/main.rs"))
+        );
+        let editor = ws.editors.get(editor_id).expect("editor");
+        let snapshot = editor.display_map.snapshot();
+        let head = snapshot
+            .buffer_snapshot()
+            .resolve_anchor(&editor.selections.newest_anchor().head());
+        assert_eq!(head, "fn main() {\n    ".len());
+    }
+
+    #[test]
+    fn unknown_task_reports() {
+        let mut h = TestHarness::with_size(80, 24);
+        let fake = setup(&mut h, "");
+        run(&mut h, "deploy");
+        assert!(fake.invocations().is_empty());
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("unknown task: deploy")
+        );
+    }
+}
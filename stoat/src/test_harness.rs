@@ -86,6 +86,7 @@ impl TestHarness {
             Arc::new(crate::host::FakeTerminalHost::new(fake_terminal.clone()));
         let mut stoat = Stoat::new(executor, settings, std::path::PathBuf::new());
         stoat.persistence_disabled = true;
+        stoat.token_cache_dir = None;
         stoat.active_workspace_mut().name = String::new();
         stoat.set_fs_host(fake_fs.clone());
         stoat.set_fs_watch_host(fake_fs_watcher.clone());
@@ -0,0 +1,552 @@
+//! Run lenses for Rust test functions.
+//!
+//! With `test.lenses` on, every test function in an open Rust buffer gets a
+//! code lens above its attributes showing its last result, through the
+//! [`TestLenses`] provider of [`crate::code_lens`]. A test function
+//! is a `function_item` carrying `#[test]` or a `*::test` attribute such as
+//! `#[tokio::test]`. [`run_test_at_cursor`] runs the test under the cursor as
+//! `cargo test <module path>::<name>` from the workspace root on the blocking
+//! pool, and a failure shows its output in a read-only output buffer.
+
+use crate::{
+    action_handlers::shell,
+    app::{Stoat, UpdateEffect},
+    buffer::BufferId,
+    code_lens::{Lens, LensProvider},
+    editor_state::EditorId,
+    host::ShellOutput,
+    keymap::ResolvedAction,
+};
+use std::{
+    collections::HashMap,
+    io,
+    ops::Range,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use stoat_language::{Node, Tree};
+use stoat_scheduler::Task;
+use stoat_text::Rope;
+
+/// A test function found in a syntax tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TestFn {
+    /// Name qualified by the inline `mod` blocks around it, e.g.
+    /// `tests::adds`. The file's own module path is unknown here; cargo's
+    /// substring filter matches without it.
+    pub(crate) name: String,
+    /// Bytes from the first attribute to the end of the function. The lens
+    /// sits above the first attribute, lined up with it.
+    pub(crate) range: Range<usize>,
+}
+
+/// Result of a test's last run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TestStatus {
+    Running,
+    Passed,
+    Failed,
+}
+
+/// Test runner state held on [`Stoat`].
+#[derive(Default)]
+pub(crate) struct TestLensState {
+    /// Last status per `(file, qualified test name)`.
+    results: HashMap<(PathBuf, String), TestStatus>,
+    /// Bumped on every change to `results`, so lenses rebuild.
+    generation: u64,
+    running: Vec<RunningTest>,
+    /// Output buffer of the last failure, reused by the next one while open.
+    output: Option<BufferId>,
+}
+
+/// A test run in flight. The job stores its output in `result` and wakes the
+/// event loop; [`pump`] installs it.
+struct RunningTest {
+    path: PathBuf,
+    name: String,
+    command: String,
+    root: PathBuf,
+    source_editor: EditorId,
+    _task: Task<()>,
+    result: Arc<Mutex<Option<io::Result<ShellOutput>>>>,
+}
+
+/// Every test function in `tree`, parsed from `rope`, in source order.
+pub(crate) fn test_functions(tree: &Tree, rope: &Rope) -> Vec<TestFn> {
+    let mut out = Vec::new();
+    collect(tree.root_node(), "", rope, &mut out);
+    out
+}
+
+fn collect(container: Node<'_>, prefix: &str, rope: &Rope, out: &mut Vec<TestFn>) {
+    let mut cursor = container.walk();
+    let children: Vec<Node<'_>> = container.named_children(&mut cursor).collect();
+    for (i, child) in children.iter().enumerate() {
+        match child.kind() {
+            "mod_item" => {
+                let (Some(name), Some(body)) = (
+                    child.child_by_field_name("name"),
+                    child.child_by_field_name("body"),
+                ) else {
+                    continue;
+                };
+                let prefix = format!("{prefix}{}::", node_text(name, rope));
+                collect(body, &prefix, rope, out);
+            },
+            "function_item" => {
+                let mut first = None;
+                let mut is_test = false;
+                for prev in children[..i].iter().rev() {
+                    if prev.kind() == "attribute_item" {
+                        is_test |= is_test_attribute(&node_text(*prev, rope));
+                        first = Some(*prev);
+                    } else if !prev.kind().contains("comment") {
+                        break;
+                    }
+                }
+                let (Some(first), true) = (first, is_test) else {
+                    continue;
+                };
+                let Some(name) = child.child_by_field_name("name") else {
+                    continue;
+                };
+                out.push(TestFn {
+                    name: format!("{prefix}{}", node_text(name, rope)),
+                    range: first.start_byte()..child.end_byte(),
+                });
+            },
+            _ => {},
+        }
+    }
+}
+
+fn node_text(node: Node<'_>, rope: &Rope) -> String {
+    rope.chunks_in_range(node.start_byte()..node.end_byte())
+        .collect()
+}
+
+/// Whether the attribute item `#[...]` marks a test: `test` itself or any
+/// path ending in `::test`, with or without arguments.
+fn is_test_attribute(text: &str) -> bool {
+    let Some(inner) = text
+        .trim()
+        .strip_prefix("#[")
+        .and_then(|t| t.strip_suffix(']'))
+    else {
+        return false;
+    };
+    let path = inner.split('(').next().unwrap_or("").trim();
+    path == "test" || path.ends_with("::test")
+}
+
+/// The [`LensProvider`] for test functions. With `test.lenses` on, each test
+/// in a Rust buffer gets a lens showing its last result; a click on it runs
+/// the test.
+pub(crate) struct TestLenses;
+
+impl LensProvider for TestLenses {
+    fn stamp(&self, stoat: &Stoat, buffer_id: BufferId) -> Option<(u64, u64)> {
+        if stoat.settings.test_lenses != Some(true) {
+            return None;
+        }
+        let ws = stoat.active_workspace();
+        let path = ws.buffers.path_for(buffer_id)?;
+        if !stoat
+            .language_registry
+            .for_path(path)
+            .is_some_and(|l| l.name == "rust")
+        {
+            return None;
+        }
+        let syntax = ws.buffers.syntax(buffer_id)?;
+        Some((syntax.version, stoat.test_lenses.generation))
+    }
+
+    fn lenses(&self, stoat: &Stoat, buffer_id: BufferId) -> Vec<Lens> {
+        let ws = stoat.active_workspace();
+        let (Some(path), Some(syntax)) =
+            (ws.buffers.path_for(buffer_id), ws.buffers.syntax(buffer_id))
+        else {
+            return Vec::new();
+        };
+        test_functions(&syntax.tree, &syntax.rope_snapshot)
+            .into_iter()
+            .map(|test| {
+                let status = stoat
+                    .test_lenses
+                    .results
+                    .get(&(path.to_path_buf(), test.name.clone()));
+                Lens {
+                    label: lens_label(status.copied()).to_owned(),
+                    range: test.range,
+                    action: Some(ResolvedAction {
+                        name: "RunTestAtCursor".to_owned(),
+                        args: Vec::new(),
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+fn lens_label(status: Option<TestStatus>) -> &'static str {
+    match status {
+        None => "▶ run test",
+        Some(TestStatus::Running) => "… running",
+        Some(TestStatus::Passed) => "✓ passed",
+        Some(TestStatus::Failed) => "✗ failed",
+    }
+}
+
+/// Run the test function under the focused cursor.
+pub(crate) fn run_test_at_cursor(stoat: &mut Stoat) -> UpdateEffect {
+    let Some((path, test, source_editor)) = test_at_cursor(stoat) else {
+        stoat.set_status("no test under the cursor");
+        return UpdateEffect::Redraw;
+    };
+    if stoat
+        .test_lenses
+        .running
+        .iter()
+        .any(|r| r.path == path && r.name == test.name)
+    {
+        stoat.set_status(format!("{}: still running", test.name));
+        return UpdateEffect::Redraw;
+    }
+    let command = format!("cargo test {}", test.name);
+    let ws = stoat.active_workspace();
+    let root = ws.git_root.clone();
+    let env = ws.env.diff.clone();
+    let shell_host = stoat.shell_host.clone();
+    let redraw = stoat.redraw_notify.clone();
+    let result = Arc::new(Mutex::new(None));
+    let slot = result.clone();
+    let (run, cwd) = (command.clone(), root.clone());
+    let task = stoat.executor.spawn_blocking(move || {
+        let out = shell_host.run(&run, b"", Some(&cwd), &env);
+        *slot.lock().expect("test result poisoned") = Some(out);
+        redraw.notify_one();
+    });
+    stoat.set_status(format!("{}: running", test.name));
+    let state = &mut stoat.test_lenses;
+    state
+        .results
+        .insert((path.clone(), test.name.clone()), TestStatus::Running);
+    state.generation += 1;
+    state.running.push(RunningTest {
+        path,
+        name: test.name,
+        command,
+        root,
+        source_editor,
+        _task: task,
+        result,
+    });
+    UpdateEffect::Redraw
+}
+
+/// The focused buffer's path, the test function containing the cursor, and
+/// the focused editor.
+fn test_at_cursor(stoat: &mut Stoat) -> Option<(PathBuf, TestFn, EditorId)> {
+    let (editor_id, buffer_id) = stoat.focused_editor_ids()?;
+    let ws = stoat.active_workspace_mut();
+    let editor = ws.editors.get_mut(editor_id)?;
+    let offset = editor
+        .display_map
+        .snapshot()
+        .buffer_snapshot()
+        .resolve_anchor(&editor.selections.newest_anchor().head());
+    let path = ws.buffers.path_for(buffer_id)?.to_path_buf();
+    let syntax = ws.buffers.syntax(buffer_id)?;
+    let test = test_functions(&syntax.tree, &syntax.rope_snapshot)
+        .into_iter()
+        .find(|test| test.range.start <= offset && offset <= test.range.end)?;
+    Some((path, test, editor_id))
+}
+
+/// Install finished test runs: record their status and show a failure's
+/// output. Called from [`Stoat::drive_background`].
+pub(crate) fn pump(stoat: &mut Stoat) {
+    let mut finished = Vec::new();
+    stoat.test_lenses.running.retain(|run| {
+        let result = run.result.lock().expect("test result poisoned").take();
+        match result {
+            Some(result) => {
+                finished.push((
+                    run.path.clone(),
+                    run.name.clone(),
+                    run.command.clone(),
+                    run.root.clone(),
+                    run.source_editor,
+                    result,
+                ));
+                false
+            },
+            None => true,
+        }
+    });
+    for (path, name, command, root, source_editor, result) in finished {
+        let status = match &result {
+            Ok(output) => {
+                let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+                outcome(&text, &name, output.exit_code)
+            },
+            Err(_) => Some(TestStatus::Failed),
+        };
+        let state = &mut stoat.test_lenses;
+        state.generation += 1;
+        let key = (path, name.clone());
+        match status {
+            Some(status) => state.results.insert(key, status),
+            None => state.results.remove(&key),
+        };
+        match status {
+            Some(TestStatus::Failed) => {
+                show_failure(stoat, &command, root, source_editor, result);
+                stoat.set_status(format!("{name}: failed"));
+            },
+            Some(_) => stoat.set_status(format!("{name}: passed")),
+            None => stoat.set_status(format!("{name}: no test ran")),
+        }
+    }
+}
+
+/// Whether the run of `name` passed, from cargo's `test <path> ... ok` lines.
+/// Without such a line, a nonzero exit (a build failure) counts as failed
+/// and a zero exit means the filter matched no test.
+fn outcome(output: &str, name: &str, exit_code: i32) -> Option<TestStatus> {
+    let suffix = format!("::{name}");
+    for line in output.lines() {
+        let Some((test, result)) = line
+            .strip_prefix("test ")
+            .and_then(|rest| rest.split_once(" ... "))
+        else {
+            continue;
+        };
+        if test != name && !test.ends_with(&suffix) {
+            continue;
+        }
+        match result.trim() {
+            "ok" => return Some(TestStatus::Passed),
+            "FAILED" => return Some(TestStatus::Failed),
+            _ => {},
+        }
+    }
+    (exit_code != 0).then_some(TestStatus::Failed)
+}
+
+/// Show a failed run's output, reusing the last failure's output buffer while
+/// it is still open.
+fn show_failure(
+    stoat: &mut Stoat,
+    command: &str,
+    root: PathBuf,
+    source_editor: EditorId,
+    result: io::Result<ShellOutput>,
+) {
+    let reused = stoat
+        .test_lenses
+        .output
+        .filter(|&id| shell::retarget_output(stoat.active_workspace_mut(), id, command));
+    let buffer_id = reused.unwrap_or_else(|| {
+        shell::open_output_buffer(stoat, command, Vec::new(), Some(root), source_editor)
+    });
+    stoat.test_lenses.output = Some(buffer_id);
+    shell::show_output(stoat.active_workspace_mut(), buffer_id, result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{action_handlers::dispatch, host::FakeShell, test_harness::TestHarness};
+    use std::path::Path;
+
+    const SOURCE: &str = "fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds() {
+        assert_eq!(add(1, 2), 3);
+    }
+
+    // Runs on the async runtime.
+    #[tokio::test(flavor = \"multi_thread\")]
+    async fn adds_async() {}
+
+    fn helper() {}
+}
+";
+
+    fn parse(source: &str) -> (Tree, Rope) {
+        let lang = stoat_language::LanguageRegistry::standard()
+            .for_path(Path::new("lib.rs"))
+            .expect("rust language");
+        let tree = stoat_language::parse(&lang, source, None).expect("parse");
+        (tree, Rope::from(source))
+    }
+
+    /// Open `/test/src/lib.rs` holding [`SOURCE`] with lenses on, park the
+    /// cursor inside `adds`, and answer its `cargo test` with `output`.
+    fn setup(h: &mut TestHarness, output: &str, exit_code: i32) -> Arc<FakeShell> {
+        let fake = Arc::new(FakeShell::new());
+        fake.set_response(
+            "cargo test tests::adds",
+            ShellOutput {
+                stdout: output.as_bytes().to_vec(),
+                stderr: Vec::new(),
+                exit_code,
+            },
+        );
+        h.stoat.set_shell_host(fake.clone());
+        h.stoat.settings.test_lenses = Some(true);
+        h.stoat.active_workspace_mut().git_root = PathBuf::from("/test");
+        let path = h.write_file("src/lib.rs", SOURCE);
+        h.open_file(&path);
+        h.settle();
+        h.stoat.drive_background();
+        let offset = SOURCE.find("assert_eq").expect("body");
+        h.stoat.collapse_focused_cursor_to(offset);
+        fake
+    }
+
+    fn run(h: &mut TestHarness) {
+        dispatch(&mut h.stoat, &stoat_action::RunTestAtCursor);
+        h.settle();
+        h.stoat.drive_background();
+    }
+
+    #[test]
+    fn finds_test_functions_in_nested_modules() {
+        let (tree, rope) = parse(SOURCE);
+        let tests = test_functions(&tree, &rope);
+        let names: Vec<(&str, u32, u32)> = tests
+            .iter()
+            .map(|t| {
+                let start = rope.offset_to_point(t.range.start);
+                (t.name.as_str(), start.row, start.column)
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![("tests::adds", 8, 4), ("tests::adds_async", 14, 4)]
+        );
+        assert_eq!(&SOURCE[tests[0].range.clone()][..7], "#[test]");
+    }
+
+    #[test]
+    fn outcome_reads_the_matching_result_line() {
+        let output = "running 2 tests
+test tests::adds_more ... FAILED
+test tests::adds ... ok
+";
+        assert_eq!(
+            outcome(output, "tests::adds", 101),
+            Some(TestStatus::Passed)
+        );
+        assert_eq!(
+            outcome("test math::tests::adds ... FAILED\n", "tests::adds", 101),
+            Some(TestStatus::Failed)
+        );
+        assert_eq!(outcome("running 0 tests\n", "tests::adds", 0), None);
+        assert_eq!(
+            outcome("error[E0425]: cannot find value\n", "tests::adds", 101),
+            Some(TestStatus::Failed)
+        );
+    }
+
+    #[test]
+    fn lenses_render_above_tests() {
+        let mut h = TestHarness::with_size(60, 30);
+        setup(&mut h, "", 0);
+        let frame = h.snapshot();
+        assert_eq!(frame.content.matches("▶ run test").count(), 2);
+    }
+
+    #[test]
+    fn passing_run_updates_the_lens() {
+        let mut h = TestHarness::with_size(60, 30);
+        let fake = setup(&mut h, "test tests::adds ... ok\n", 0);
+        run(&mut h);
+
+        let invocation = &fake.invocations()[0];
+        assert_eq!(invocation.cmd, "cargo test tests::adds");
+        assert_eq!(invocation.cwd.as_deref(), Some(Path::new("/test")));
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("tests::adds: passed")
+        );
+        let frame = h.snapshot();
+        assert!(frame.content.contains("✓ passed"), "{}", frame.content);
+        assert!(frame.content.contains("▶ run test"), "{}", frame.content);
+    }
+
+    #[test]
+    fn clicking_a_lens_runs_its_test() {
+        use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+        let mut h = TestHarness::with_size(60, 30);
+        let fake = setup(&mut h, "test tests::adds ... ok\n", 0);
+        h.stoat.collapse_focused_cursor_to(0);
+        let frame = h.snapshot();
+        let (row, line) = frame
+            .content
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.contains("▶ run test"))
+            .expect("lens line");
+        let column = line.chars().take_while(|&c| c != '▶').count();
+
+        h.stoat.update(Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: column as u16,
+            row: row as u16,
+            modifiers: KeyModifiers::NONE,
+        }));
+        h.settle();
+        h.stoat.drive_background();
+
+        assert_eq!(fake.invocations()[0].cmd, "cargo test tests::adds");
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("tests::adds: passed")
+        );
+    }
+
+    #[test]
+    fn failing_run_opens_output_buffer() {
+        let mut h = TestHarness::with_size(60, 30);
+        setup(&mut h, "test tests::adds ... FAILED\n", 101);
+        run(&mut h);
+
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("tests::adds: failed")
+        );
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        let snapshot = editor.display_map.snapshot();
+        assert_eq!(
+            snapshot.buffer_snapshot().rope().to_string(),
+            "$ cargo test tests::adds\ntest tests::adds ... FAILED\n[exit 101]\n"
+        );
+    }
+
+    #[test]
+    fn run_outside_a_test_reports() {
+        let mut h = TestHarness::with_size(60, 30);
+        let fake = setup(&mut h, "", 0);
+        h.stoat.collapse_focused_cursor_to(0);
+        run(&mut h);
+        assert!(fake.invocations().is_empty());
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("no test under the cursor")
+        );
+    }
+}
@@ -190,7 +190,10 @@ fn apply_setting(
     mods: &mut HashMap<String, Modifier>,
 ) -> Result<(), ThemeError> {
     let path: Vec<&str> = setting.path.iter().map(|p| p.node.as_str()).collect();
-    if let Value::Map(entries) = &setting.value.node {
+    let Expr::Value(value) = &setting.value.node else {
+        return UnsupportedExprSnafu.fail();
+    };
+    if let Value::Map(entries) = value {
         let scope = path.join(".");
         for (key, value) in entries {
             apply_field(
@@ -219,7 +222,7 @@ fn apply_setting(
         .fail();
     }
     let scope = scope_parts.join(".");
-    apply_field(&scope, field, &setting.value.node, palette, fg, bg, mods)
+    apply_field(&scope, field, value, palette, fg, bg, mods)
 }
 
 fn apply_field(
@@ -259,7 +262,7 @@ fn resolve_color_from_expr(
     match expr {
         Expr::Value(v) => resolve_color_from_value(v, palette),
         Expr::Variable(name) => lookup_named_or_palette(name, palette),
-        Expr::If { .. } => UnsupportedExprSnafu.fail(),
+        Expr::If { .. } | Expr::Binary { .. } | Expr::Match { .. } => UnsupportedExprSnafu.fail(),
     }
 }
 
@@ -453,7 +456,9 @@ pub enum ThemeError {
         #[snafu(implicit)]
         location: snafu::Location,
     },
-    #[snafu(display("if-expressions are not supported inside theme blocks"))]
+    #[snafu(display(
+        "conditional and arithmetic expressions are not supported inside theme blocks"
+    ))]
     UnsupportedExpr {
         #[snafu(implicit)]
         location: snafu::Location,
@@ -477,6 +482,10 @@ pub mod scope {
 
     pub const UI_CURSOR: &str = "ui.cursor";
     pub const UI_CURSOR_INPUT: &str = "ui.cursor.input";
+    /// Background washed across the focused editor's cursor line when
+    /// `editor.cursor_line` is on. Kept out of `ui.cursor` so the line does not
+    /// inherit the cursor's reversed style.
+    pub const UI_CURSOR_LINE: &str = "ui.cursorline";
 
     pub const UI_SELECTION: &str = "ui.selection";
     pub const UI_SELECTION_EDITOR: &str = "ui.selection.editor";
@@ -547,6 +556,7 @@ pub mod scope {
     pub const VCS_COMMIT_SHA: &str = "vcs.commit.sha";
     pub const VCS_COMMIT_SUMMARY: &str = "vcs.commit.summary";
     pub const VCS_COMMIT_METADATA: &str = "vcs.commit.metadata";
+    pub const VCS_UNTRACKED: &str = "vcs.untracked";
 
     pub const VCS_REBASE_PICK: &str = "vcs.rebase.pick";
     pub const VCS_REBASE_SQUASH: &str = "vcs.rebase.squash";
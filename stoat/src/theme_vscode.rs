@@ -89,6 +89,10 @@ fn ui_settings(theme: &VsCodeTheme, background: (u8, u8, u8), out: &mut Vec<Span
             "editor.selectionBackground",
             &["ui", "selection", "editor", "bg"],
         ),
+        (
+            "editor.lineHighlightBackground",
+            &["ui", "cursorline", "bg"],
+        ),
         ("focusBorder", &["ui", "border", "focused", "fg"]),
         (
             "statusBar.background",
@@ -254,20 +258,20 @@ fn let_ref(name: &str, target: &str) -> Spanned<Statement> {
 fn scalar_setting(path: &[&str], hex: String) -> Spanned<Statement> {
     spanned(Statement::Setting(Setting {
         path: path.iter().map(|s| spanned(s.to_string())).collect(),
-        value: spanned(Value::String(hex)),
+        value: spanned(Expr::Value(Value::String(hex))),
     }))
 }
 
 fn map_setting(scope: &[&str], hex: String, modifiers: Vec<Spanned<Value>>) -> Spanned<Statement> {
     spanned(Statement::Setting(Setting {
         path: scope.iter().map(|s| spanned(s.to_string())).collect(),
-        value: spanned(Value::Map(vec![
+        value: spanned(Expr::Value(Value::Map(vec![
             (spanned("fg".to_string()), spanned(Value::String(hex))),
             (
                 spanned("modifiers".to_string()),
                 spanned(Value::Array(modifiers)),
             ),
-        ])),
+        ]))),
     }))
 }
 
@@ -0,0 +1,269 @@
+//! Toast notifications: transient, severity-styled messages stacked in the
+//! editor's top-right corner.
+//!
+//! [`crate::app::Stoat::notify`] raises one. Each toast carries its own
+//! deadline and a timer that wakes the run loop when it elapses, so an idle
+//! screen retires it the way [`crate::app::Stoat::set_status`] retires the
+//! message row. A toast may offer one action, run by `RunToastAction`, and every
+//! raised toast is kept in a bounded history the `:notifications` picker lists.
+
+use crate::{
+    app::{Stoat, UpdateEffect},
+    keymap::ResolvedAction,
+};
+use std::{collections::VecDeque, time::Instant};
+use stoat_scheduler::Task;
+
+/// Toasts painted at once. Raising one past this retires the oldest early, and
+/// it stays reachable through the history.
+pub(crate) const MAX_VISIBLE: usize = 4;
+
+/// Raised toasts the history keeps, oldest dropped first.
+pub(crate) const HISTORY_LIMIT: usize = 100;
+
+/// How serious a toast is. Selects its border style and how long it stays up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    /// How long a toast of this level stays visible. Problems linger longer
+    /// than confirmations so they are not missed.
+    pub(crate) fn ttl(self) -> std::time::Duration {
+        std::time::Duration::from_secs(match self {
+            Self::Info | Self::Success => 4,
+            Self::Warning => 8,
+            Self::Error => 12,
+        })
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Success => "ok",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    pub(crate) fn theme_scope(self) -> &'static str {
+        use crate::theme::scope;
+        match self {
+            Self::Info => scope::UI_DIAGNOSTIC_INFO,
+            Self::Success => scope::UI_BADGE_COMPLETE,
+            Self::Warning => scope::UI_DIAGNOSTIC_WARNING,
+            Self::Error => scope::UI_DIAGNOSTIC_ERROR,
+        }
+    }
+}
+
+/// The button a toast offers: a short label painted after the message and the
+/// action `RunToastAction` dispatches for it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ToastAction {
+    pub(crate) label: String,
+    pub(crate) action: ResolvedAction,
+}
+
+impl ToastAction {
+    /// A button running the zero-argument action `name`.
+    pub(crate) fn new(label: impl Into<String>, name: &str) -> Self {
+        Self {
+            label: label.into(),
+            action: ResolvedAction {
+                name: name.to_string(),
+                args: Vec::new(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Toast {
+    pub(crate) level: ToastLevel,
+    pub(crate) message: String,
+    pub(crate) action: Option<ToastAction>,
+}
+
+impl Toast {
+    /// The one-line form the history picker lists, such as
+    /// `error: lsp: rust-analyzer: NotFound`.
+    pub(crate) fn line(&self) -> String {
+        format!("{}: {}", self.level.label(), self.message)
+    }
+}
+
+/// A toast on screen, with the deadline it retires at and the timer that wakes
+/// the run loop then. Dropping the entry cancels the timer.
+struct Shown {
+    toast: Toast,
+    deadline: Instant,
+    _expiry: Task<()>,
+}
+
+/// The visible toast stack plus the history of every toast raised.
+#[derive(Default)]
+pub(crate) struct Toasts {
+    shown: Vec<Shown>,
+    history: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Show `toast` until `deadline`, keeping `expiry` alive until then, and
+    /// record it in the history.
+    pub(crate) fn push(&mut self, toast: Toast, deadline: Instant, expiry: Task<()>) {
+        if self.history.len() == HISTORY_LIMIT {
+            self.history.pop_front();
+        }
+        self.history.push_back(toast.clone());
+        if self.shown.len() == MAX_VISIBLE {
+            self.shown.remove(0);
+        }
+        self.shown.push(Shown {
+            toast,
+            deadline,
+            _expiry: expiry,
+        });
+    }
+
+    /// Drop every toast whose deadline is at or before `now`. Returns whether
+    /// any retired.
+    pub(crate) fn retire_expired(&mut self, now: Instant) -> bool {
+        let before = self.shown.len();
+        self.shown.retain(|shown| shown.deadline > now);
+        self.shown.len() != before
+    }
+
+    /// The visible toasts, newest first.
+    pub(crate) fn visible(&self) -> impl Iterator<Item = &Toast> {
+        self.shown.iter().rev().map(|shown| &shown.toast)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.shown.is_empty()
+    }
+
+    /// Dismiss every visible toast. The history keeps them.
+    pub(crate) fn dismiss_all(&mut self) {
+        self.shown.clear();
+    }
+
+    /// Dismiss the newest visible toast offering an action and return that
+    /// action, or `None` when no visible toast has one.
+    pub(crate) fn take_action(&mut self) -> Option<ResolvedAction> {
+        let index = self
+            .shown
+            .iter()
+            .rposition(|shown| shown.toast.action.is_some())?;
+        let shown = self.shown.remove(index);
+        shown.toast.action.map(|action| action.action)
+    }
+
+    /// Every raised toast, newest first.
+    pub(crate) fn history(&self) -> impl Iterator<Item = &Toast> {
+        self.history.iter().rev()
+    }
+}
+
+/// Run the action of the newest visible toast offering one, dismissing that
+/// toast, as the binding for it would.
+pub(crate) fn run_action(stoat: &mut Stoat) -> UpdateEffect {
+    let Some(action) = stoat.toasts.take_action() else {
+        stoat.set_status("no notification action to run");
+        return UpdateEffect::Redraw;
+    };
+    match stoat.dispatch_bound_actions(&[action], None) {
+        UpdateEffect::None => UpdateEffect::Redraw,
+        effect => effect,
+    }
+}
+
+/// Show the newest history entry whose [`Toast::line`] is `line` again, with
+/// its action.
+pub(crate) fn reshow(stoat: &mut Stoat, line: &str) -> UpdateEffect {
+    let Some(toast) = stoat.toasts.history().find(|toast| toast.line() == line) else {
+        stoat.set_status(format!("not in notification history: {line}"));
+        return UpdateEffect::Redraw;
+    };
+    let toast = toast.clone();
+    stoat.show_toast(toast);
+    UpdateEffect::Redraw
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, time::Duration};
+    use stoat_scheduler::TestScheduler;
+
+    fn toast(level: ToastLevel, message: &str) -> Toast {
+        Toast {
+            level,
+            message: message.to_string(),
+            action: None,
+        }
+    }
+
+    fn push(toasts: &mut Toasts, toast: Toast, deadline: Instant) {
+        let scheduler = Arc::new(TestScheduler::new());
+        toasts.push(toast, deadline, scheduler.executor().spawn(async {}));
+    }
+
+    #[test]
+    fn expired_toasts_retire_but_stay_in_history() {
+        let now = Instant::now();
+        let mut toasts = Toasts::default();
+        push(&mut toasts, toast(ToastLevel::Info, "saved"), now);
+        push(
+            &mut toasts,
+            toast(ToastLevel::Error, "lsp: gone"),
+            now + Duration::from_secs(5),
+        );
+
+        assert!(toasts.retire_expired(now));
+        let visible: Vec<_> = toasts.visible().map(|t| t.message.as_str()).collect();
+        assert_eq!(visible, ["lsp: gone"]);
+        let history: Vec<_> = toasts.history().map(Toast::line).collect();
+        assert_eq!(history, ["error: lsp: gone", "info: saved"]);
+    }
+
+    #[test]
+    fn stack_and_history_are_bounded() {
+        let later = Instant::now() + Duration::from_secs(60);
+        let mut toasts = Toasts::default();
+        for n in 0..=HISTORY_LIMIT {
+            push(&mut toasts, toast(ToastLevel::Info, &n.to_string()), later);
+        }
+
+        assert_eq!(toasts.visible().count(), MAX_VISIBLE);
+        assert_eq!(
+            toasts.visible().next().map(|t| t.message.as_str()),
+            Some("100")
+        );
+        assert_eq!(toasts.history().count(), HISTORY_LIMIT);
+        assert_eq!(
+            toasts.history().last().map(|t| t.message.as_str()),
+            Some("1")
+        );
+    }
+
+    #[test]
+    fn take_action_dismisses_the_newest_toast_with_one() {
+        let later = Instant::now() + Duration::from_secs(60);
+        let mut toasts = Toasts::default();
+        let mut failed = toast(ToastLevel::Error, "lsp: gone");
+        failed.action = Some(ToastAction::new("logs", "OpenLogs"));
+        push(&mut toasts, failed, later);
+        push(&mut toasts, toast(ToastLevel::Info, "saved"), later);
+
+        let action = toasts.take_action().expect("a toast offers an action");
+        assert_eq!(action.name, "OpenLogs");
+        let visible: Vec<_> = toasts.visible().map(|t| t.message.as_str()).collect();
+        assert_eq!(visible, ["saved"]);
+        assert_eq!(toasts.take_action(), None);
+    }
+}
@@ -0,0 +1,163 @@
+//! Highlight tokens kept on disk across a file's close and reopen.
+//!
+//! Closing a clean buffer writes its tree-sitter tokens as byte ranges, along
+//! with a hash of its text and a fingerprint of the theme's interned styles.
+//! Reopening the file with the same text under the same theme reads them back,
+//! so the editor shows highlighting from its first frame instead of waiting on
+//! the initial parse. The parse still runs to rebuild the syntax tree that
+//! structural features need, and its tokens replace the cached ones when it
+//! lands.
+//!
+//! Each file gets one cache file, named by a hash of its canonical path the
+//! way [`anchor_state_dir`] names workspace state. A cache that fails to read,
+//! decode or match is ignored, so a stale or corrupt entry only costs the
+//! instant highlighting.
+
+use crate::{
+    display_map::highlights::{HighlightStyleId, HighlightStyleInterner},
+    host::FsHost,
+    workspace::anchor_state_dir,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use stoat_text::Rope;
+
+/// Bumped when [`CachedTokens`] changes shape, so old entries miss.
+const FORMAT: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct CachedTokens {
+    format: u32,
+    content_hash: [u8; 32],
+    styles: u64,
+    /// `(start, end, style index)` per token, in buffer order.
+    tokens: Vec<(usize, usize, u32)>,
+}
+
+/// Where the tokens for `file` are cached under `cache_dir`.
+pub(crate) fn cache_path(cache_dir: &Path, file: &Path, fs: &dyn FsHost) -> PathBuf {
+    anchor_state_dir(cache_dir, file, fs).with_extension("json")
+}
+
+/// Blake3 hash of `rope`'s text, fed chunk by chunk so the text is never
+/// copied out whole.
+pub(crate) fn content_hash(rope: &Rope) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    for chunk in rope.chunks_in_range(0..rope.len()) {
+        hasher.update(chunk.as_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// Write `tokens` as the cache for `file` holding `text`, replacing any
+/// earlier entry.
+pub(crate) fn write_tokens(
+    cache_dir: &Path,
+    file: &Path,
+    text: &Rope,
+    tokens: &[(Range<usize>, HighlightStyleId)],
+    interner: &HighlightStyleInterner,
+    fs: &dyn FsHost,
+) -> io::Result<()> {
+    let cached = CachedTokens {
+        format: FORMAT,
+        content_hash: content_hash(text),
+        styles: interner.fingerprint(),
+        tokens: tokens
+            .iter()
+            .map(|(range, style)| (range.start, range.end, style.index()))
+            .collect(),
+    };
+    let bytes = serde_json::to_vec(&cached)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    let path = cache_path(cache_dir, file, fs);
+    if let Some(parent) = path.parent() {
+        fs.create_dir_all(parent)?;
+    }
+    fs.write_atomic(&path, &bytes)
+}
+
+/// The cached tokens for `file`, if an entry exists for exactly `text` under
+/// styles interned as in `interner`.
+pub(crate) fn read_tokens(
+    cache_dir: &Path,
+    file: &Path,
+    text: &Rope,
+    interner: &HighlightStyleInterner,
+    fs: &dyn FsHost,
+) -> Option<Vec<(Range<usize>, HighlightStyleId)>> {
+    let mut bytes = Vec::new();
+    fs.read(&cache_path(cache_dir, file, fs), &mut bytes).ok()?;
+    let cached: CachedTokens = serde_json::from_slice(&bytes).ok()?;
+    if cached.format != FORMAT
+        || cached.styles != interner.fingerprint()
+        || cached.content_hash != content_hash(text)
+    {
+        return None;
+    }
+    cached
+        .tokens
+        .into_iter()
+        .map(|(start, end, style)| {
+            let in_bounds = start <= end && end <= text.len();
+            Some((start..end, interner.id_at(style).filter(|_| in_bounds)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_tokens, write_tokens};
+    use crate::{
+        display_map::highlights::{HighlightStyle, HighlightStyleInterner},
+        host::FakeFs,
+    };
+    use ratatui::style::Color;
+    use std::path::Path;
+    use stoat_text::Rope;
+
+    fn interner(color: Color) -> HighlightStyleInterner {
+        let mut interner = HighlightStyleInterner::default();
+        interner.intern(HighlightStyle {
+            foreground: Some(color),
+            ..Default::default()
+        });
+        interner
+    }
+
+    #[test]
+    fn round_trips_for_the_same_text_and_styles() {
+        let fs = FakeFs::new();
+        let dir = Path::new("/state/tokens");
+        let file = Path::new("/repo/main.rs");
+        let text = Rope::from("fn main() {}\n");
+        let styles = interner(Color::Red);
+        let id = styles.id_at(0).expect("interned");
+        let tokens = vec![(0..2, id), (3..7, id)];
+
+        write_tokens(dir, file, &text, &tokens, &styles, &fs).expect("write");
+        assert_eq!(read_tokens(dir, file, &text, &styles, &fs), Some(tokens));
+    }
+
+    #[test]
+    fn misses_on_changed_text_styles_or_path() {
+        let fs = FakeFs::new();
+        let dir = Path::new("/state/tokens");
+        let file = Path::new("/repo/main.rs");
+        let text = Rope::from("fn main() {}\n");
+        let styles = interner(Color::Red);
+        let id = styles.id_at(0).expect("interned");
+        write_tokens(dir, file, &text, &[(0..2, id)], &styles, &fs).expect("write");
+
+        let edited = Rope::from("fn main() { }\n");
+        assert_eq!(read_tokens(dir, file, &edited, &styles, &fs), None);
+        let retheme = interner(Color::Blue);
+        assert_eq!(read_tokens(dir, file, &text, &retheme, &fs), None);
+        let other = Path::new("/repo/lib.rs");
+        assert_eq!(read_tokens(dir, other, &text, &styles, &fs), None);
+    }
+}
@@ -6,6 +6,7 @@
 //! guarantees that terminal IO latency is independent of main-thread workload.
 
 use crossterm::{
+    cursor::SetCursorStyle,
     event::{DisableMouseCapture, EnableMouseCapture, Event, EventStream},
     execute, queue,
     terminal::{BeginSynchronizedUpdate, EndSynchronizedUpdate},
@@ -13,13 +14,13 @@ use crossterm::{
 use futures::StreamExt;
 use ratatui::buffer::Buffer;
 use std::{
-    backtrace::Backtrace,
     io::{self, Write},
     panic,
     sync::{Arc, Once},
     thread,
     time::{Duration, Instant},
 };
+use stoat_config::CursorShape;
 use stoatty_protocol::{
     command::{self, HelloCommand, IdentReply},
     frame,
@@ -39,6 +40,9 @@ use tokio::sync::{
 pub struct RenderFrame {
     pub buffer: Arc<Buffer>,
     pub cursor: Option<(u16, u16)>,
+    /// How the terminal cursor at `cursor` looks. Ignored when `cursor` is
+    /// `None`.
+    pub cursor_style: CursorStyle,
     /// Raw VT that re-stamps diagnostic curly underlines over `buffer` after it
     /// is drawn, empty outside stoatty or when no diagnostic span is visible.
     /// Written to stdout right after the grid draw so it decorates the exact
@@ -55,13 +59,34 @@ pub struct RenderFrame {
     pub input_time: Option<Instant>,
 }
 
+/// The terminal cursor's look for one frame: its shape in the focused mode,
+/// from `editor.cursor_shape.<mode>`, and its blink interval, from
+/// `editor.cursor_blink`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CursorStyle {
+    /// `None` keeps the terminal's default shape.
+    pub shape: Option<CursorShape>,
+    /// How long the cursor stays lit, then dark. `None` keeps it steady.
+    pub blink: Option<Duration>,
+}
+
+/// The DECSCUSR sequence selecting `shape`. Shapes are sent steady because the
+/// UI thread times the blink itself, at the configured interval.
+fn cursor_shape_command(shape: Option<CursorShape>) -> SetCursorStyle {
+    match shape {
+        None => SetCursorStyle::DefaultUserShape,
+        Some(CursorShape::Block) => SetCursorStyle::SteadyBlock,
+        Some(CursorShape::Bar) => SetCursorStyle::SteadyBar,
+        Some(CursorShape::Underline) => SetCursorStyle::SteadyUnderScore,
+    }
+}
+
 /// Install a process-global panic hook that restores the terminal before the
 /// default hook runs, so a panic in either the main thread or the UI thread
 /// leaves cooked mode + the main screen + the panic message visible to the
-/// user. Logs `panic_message`, `location`, and a captured backtrace via
-/// [`tracing::error`] so the same information is preserved in
-/// `stoat-<pid>.log` after the terminal scrollback is gone. Idempotent across
-/// repeated calls.
+/// user. The prior hook is normally [`stoat_log::install_panic_hook`]'s, which
+/// logs the message and a backtrace before the default hook prints. Idempotent
+/// across repeated calls.
 pub fn install_panic_hook() {
     static INSTALLED: Once = Once::new();
     INSTALLED.call_once(|| {
@@ -69,20 +94,6 @@ pub fn install_panic_hook() {
         panic::set_hook(Box::new(move |info| {
             let _ = execute!(io::stdout(), DisableMouseCapture);
             ratatui::restore();
-
-            let panic_message = match info.payload().downcast_ref::<&'static str>() {
-                Some(message) => *message,
-                None => match info.payload().downcast_ref::<String>() {
-                    Some(message) => message.as_str(),
-                    None => "Box<Any>",
-                },
-            };
-            let location = info
-                .location()
-                .map(|loc| format!("{}:{}", loc.file(), loc.line()));
-            let backtrace = Backtrace::force_capture();
-            tracing::error!(panic = true, ?location, %panic_message, %backtrace, "stoat panic");
-
             prior(info);
         }));
     });
@@ -106,6 +117,7 @@ pub fn spawn(
                 execute!(io::stdout(), EnableMouseCapture)?;
             }
             let result = run(&event_tx, &mut render_rx, &mut apc_rx, &mut terminal).await;
+            let _ = execute!(io::stdout(), SetCursorStyle::DefaultUserShape);
             if mouse_captured {
                 let _ = execute!(io::stdout(), DisableMouseCapture);
             }
@@ -142,6 +154,14 @@ async fn run(
     #[cfg(feature = "perf")]
     let mut recorded_frames: usize = 0;
 
+    // The shape last sent to the terminal, `None` until the first frame with a
+    // cursor. A blinking cursor toggles at `blink_at`, and every drawn frame
+    // relights it so it stays visible while the user types.
+    let mut shape_sent: Option<Option<CursorShape>> = None;
+    let mut blink_interval: Option<Duration> = None;
+    let mut blink_at: Option<tokio::time::Instant> = None;
+    let mut cursor_lit = true;
+
     loop {
         // Biased: always drain input before flushing frames so keypresses
         // are never starved by a burst of render buffers
@@ -178,11 +198,24 @@ async fn run(
                         {
                             input_time = src.input_time;
                         }
-                        (src.buffer.clone(), src.cursor, src.undercurl.clone())
+                        (
+                            src.buffer.clone(),
+                            src.cursor,
+                            src.cursor_style,
+                            src.undercurl.clone(),
+                        )
                     })
                 };
                 let undercurl = match framed {
-                    Some((buffer, cursor, undercurl)) => {
+                    Some((buffer, cursor, cursor_style, undercurl)) => {
+                        if cursor.is_some() && shape_sent != Some(cursor_style.shape) {
+                            queue!(io::stdout(), cursor_shape_command(cursor_style.shape))?;
+                            shape_sent = Some(cursor_style.shape);
+                        }
+                        blink_interval = cursor_style.blink.filter(|_| cursor.is_some());
+                        blink_at = blink_interval
+                            .map(|interval| tokio::time::Instant::now() + interval);
+                        cursor_lit = true;
                         terminal.draw(|f| {
                             let dst = f.buffer_mut();
                             if dst.area == buffer.area {
@@ -227,6 +260,18 @@ async fn run(
                 }
             }
 
+            _ = tokio::time::sleep_until(blink_at.unwrap_or_else(tokio::time::Instant::now)),
+                if blink_at.is_some() =>
+            {
+                cursor_lit = !cursor_lit;
+                if cursor_lit {
+                    terminal.show_cursor()?;
+                } else {
+                    terminal.hide_cursor()?;
+                }
+                blink_at = blink_interval.map(|interval| tokio::time::Instant::now() + interval);
+            }
+
             batch = apc_rx.recv() => {
                 let Some(batch) = batch else { break };
                 let mut stdout = io::stdout();
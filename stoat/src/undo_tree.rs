@@ -0,0 +1,217 @@
+//! Branching undo history for a [`TextBuffer`](crate::buffer::TextBuffer).
+//!
+//! Every logical undo step is a node whose parent is the state it was made
+//! on. An edit after an undo starts a sibling branch instead of discarding the
+//! undone steps, so every state the buffer has been in stays reachable. Undo
+//! walks to the parent, redo descends into the child the walk last came out of
+//! (or the newest child), and [`UndoTree::path_to`] gives the steps between
+//! the current state and any other node for a direct restore.
+//!
+//! The tree only orders the groups. Reverting and reapplying their edits is
+//! the buffer's undo-map toggle, which works for any node because each edit is
+//! toggled by its own timestamp.
+
+use stoat_text::{Anchor, Selection};
+
+/// A single logical undo step covering the edits made by one dispatched action
+/// or a whole insert-mode session, plus the editor selections to restore when
+/// the group is undone or redone.
+///
+/// Grouping is an in-session overlay on the flat
+/// [`BufferOp`](crate::buffer::BufferOp) log, which still records each edit and
+/// undo individually, so it is not persisted -- a restored buffer replays every
+/// edit as its own singleton group.
+pub(crate) struct UndoGroup {
+    /// Edit timestamps in application order. Undo toggles them in reverse.
+    pub(crate) edits: Vec<u64>,
+    /// Editor selections captured when the group opened, restored on undo.
+    pub(crate) selections_before: Vec<Selection<Anchor>>,
+    /// Editor selections captured when the group sealed, restored on redo.
+    pub(crate) selections_after: Vec<Selection<Anchor>>,
+    /// Zero-based row of the group's first edit, for the undo tree view.
+    pub(crate) row: u32,
+    /// Bytes inserted across the group's edits.
+    pub(crate) inserted: usize,
+    /// Bytes deleted across the group's edits.
+    pub(crate) deleted: usize,
+}
+
+struct UndoNode {
+    group: UndoGroup,
+    /// The node this one was made on, `None` for a step on the empty buffer.
+    parent: Option<usize>,
+    /// Child a redo from this node descends into: the newest child, or the one
+    /// the last undo came out of.
+    redo_child: Option<usize>,
+}
+
+/// Undo groups as a tree, indexed by creation order.
+#[derive(Default)]
+pub(crate) struct UndoTree {
+    nodes: Vec<UndoNode>,
+    /// Node whose edits are the newest applied. `None` is the empty state
+    /// before any edit.
+    current: Option<usize>,
+    /// Redo target from the empty state.
+    root_redo: Option<usize>,
+}
+
+impl UndoTree {
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub(crate) fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub(crate) fn group(&self, node: usize) -> Option<&UndoGroup> {
+        self.nodes.get(node).map(|n| &n.group)
+    }
+
+    pub(crate) fn current_group_mut(&mut self) -> Option<&mut UndoGroup> {
+        let current = self.current?;
+        self.nodes.get_mut(current).map(|n| &mut n.group)
+    }
+
+    /// Add `group` as the newest child of the current node and make it
+    /// current. Earlier children stay in the tree as other branches.
+    pub(crate) fn push(&mut self, group: UndoGroup) -> usize {
+        let id = self.nodes.len();
+        self.nodes.push(UndoNode {
+            group,
+            parent: self.current,
+            redo_child: None,
+        });
+        self.descend(id);
+        id
+    }
+
+    /// Step to the current node's parent and return the node stepped out of,
+    /// which the next [`Self::redo`] descends back into.
+    pub(crate) fn undo(&mut self) -> Option<usize> {
+        let node = self.current?;
+        let parent = self.nodes[node].parent;
+        *self.redo_slot(parent) = Some(node);
+        self.current = parent;
+        Some(node)
+    }
+
+    /// Descend into the current node's redo child and return it.
+    pub(crate) fn redo(&mut self) -> Option<usize> {
+        let child = (*self.redo_slot(self.current))?;
+        self.current = Some(child);
+        Some(child)
+    }
+
+    /// Make `child`, a child of the current node, current and its parent's redo
+    /// target.
+    pub(crate) fn descend(&mut self, child: usize) {
+        *self.redo_slot(self.current) = Some(child);
+        self.current = Some(child);
+    }
+
+    /// The nodes to undo, deepest first, then the nodes to descend into, in
+    /// order, to move from the current node to `target`.
+    pub(crate) fn path_to(&self, target: Option<usize>) -> (Vec<usize>, Vec<usize>) {
+        let from: Vec<usize> = self.ancestors(self.current).collect();
+        let mut down: Vec<usize> = self.ancestors(target).collect();
+        let common = from.iter().copied().find(|node| down.contains(node));
+        let up: Vec<usize> = from
+            .into_iter()
+            .take_while(|&node| Some(node) != common)
+            .collect();
+        down.truncate(
+            down.iter()
+                .position(|&n| Some(n) == common)
+                .unwrap_or(down.len()),
+        );
+        down.reverse();
+        (up, down)
+    }
+
+    /// `node` and its ancestors, nearest first.
+    pub(crate) fn ancestors(&self, node: Option<usize>) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(node, |&n| self.nodes[n].parent)
+    }
+
+    /// The node whose group ends with the edit stamped `timestamp`.
+    pub(crate) fn node_ending_with(&self, timestamp: u64) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|n| n.group.edits.last() == Some(&timestamp))
+    }
+
+    /// Children of `node` (`None` for the empty state), oldest first.
+    pub(crate) fn children(&self, node: Option<usize>) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(move |(_, n)| n.parent == node)
+            .map(|(id, _)| id)
+    }
+
+    fn redo_slot(&mut self, node: Option<usize>) -> &mut Option<usize> {
+        match node {
+            Some(n) => &mut self.nodes[n].redo_child,
+            None => &mut self.root_redo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(edit: u64) -> UndoGroup {
+        UndoGroup {
+            edits: vec![edit],
+            selections_before: Vec::new(),
+            selections_after: Vec::new(),
+            row: 0,
+            inserted: 1,
+            deleted: 0,
+        }
+    }
+
+    /// `0 -> 1`, then undo to `0` and branch `0 -> 2`.
+    fn branched() -> UndoTree {
+        let mut tree = UndoTree::default();
+        tree.push(group(1));
+        tree.push(group(2));
+        tree.undo();
+        tree.push(group(3));
+        tree
+    }
+
+    #[test]
+    fn edit_after_undo_keeps_the_undone_branch() {
+        let tree = branched();
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.current(), Some(2));
+        assert_eq!(tree.children(Some(0)).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn redo_follows_the_branch_last_undone() {
+        let mut tree = branched();
+        assert_eq!(tree.undo(), Some(2));
+        assert_eq!(tree.redo(), Some(2));
+        assert_eq!(tree.redo(), None);
+
+        let (up, down) = tree.path_to(Some(1));
+        assert_eq!((up, down), (vec![2], vec![1]));
+        tree.undo();
+        tree.descend(1);
+        tree.undo();
+        assert_eq!(tree.redo(), Some(1));
+    }
+
+    #[test]
+    fn path_to_the_empty_state_undoes_everything() {
+        let tree = branched();
+        assert_eq!(tree.path_to(None), (vec![2, 0], vec![]));
+        assert_eq!(tree.path_to(Some(2)), (vec![], vec![]));
+        assert_eq!(tree.node_ending_with(3), Some(2));
+    }
+}
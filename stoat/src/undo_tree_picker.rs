@@ -0,0 +1,175 @@
+use crate::undo_tree::UndoTree;
+
+/// Modal drawing the focused buffer's [`UndoTree`] as an indented list.
+///
+/// Rows run in history order from the original content. A node's newest child
+/// continues on the node's own column, and each older branch is listed first,
+/// one column deeper, so the live line of edits reads straight down and an
+/// abandoned branch hangs off the step it split from.
+///
+/// Navigation and restore route through the `modal == undo_tree` keymap block.
+/// [`Self::select_next`] and [`Self::select_prev`] move the highlight, and
+/// [`Self::selected_node`] names the state to restore.
+pub struct UndoTreePicker {
+    rows: Vec<UndoTreeRow>,
+    selected: usize,
+}
+
+pub struct UndoTreeRow {
+    /// Undo-tree node the row restores, `None` for the empty buffer.
+    pub node: Option<usize>,
+    /// Branch nesting, the number of older branches taken to reach the row.
+    pub depth: usize,
+    /// Whether the buffer is currently at this state.
+    pub current: bool,
+    pub label: String,
+}
+
+impl UndoTreePicker {
+    /// Build a picker over `tree`. Nodes below `floor`, the buffer's seeded
+    /// content, collapse into a single `original` row, since they cannot be
+    /// restored individually. The highlight starts on the current state.
+    pub(crate) fn new(tree: &UndoTree, floor: usize) -> Self {
+        let start = floor.checked_sub(1);
+        let mut rows = Vec::new();
+        let mut stack = vec![(start, 0usize)];
+        while let Some((node, depth)) = stack.pop() {
+            let label = match node.filter(|&n| Some(n) != start) {
+                Some(n) => step_label(tree, n, floor),
+                None => "original".to_string(),
+            };
+            rows.push(UndoTreeRow {
+                node,
+                depth,
+                current: node == tree.current(),
+                label,
+            });
+            let children: Vec<usize> = tree.children(node).collect();
+            if let Some((&newest, older)) = children.split_last() {
+                stack.push((Some(newest), depth));
+                stack.extend(older.iter().rev().map(|&child| (Some(child), depth + 1)));
+            }
+        }
+        let selected = rows.iter().position(|row| row.current).unwrap_or(0);
+        Self { rows, selected }
+    }
+
+    pub fn rows(&self) -> &[UndoTreeRow] {
+        &self.rows
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// The undo-tree node under the highlight, `None` for the empty buffer.
+    pub fn selected_node(&self) -> Option<usize> {
+        self.rows.get(self.selected).and_then(|row| row.node)
+    }
+
+    pub fn select_next(&mut self) {
+        self.move_selection(1);
+    }
+
+    pub fn select_prev(&mut self) {
+        self.move_selection(-1);
+    }
+
+    pub fn hint_bindings(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Enter", "restore".to_string()),
+            ("Esc", "cancel".to_string()),
+            ("Ctrl-N", "next".to_string()),
+            ("Ctrl-P", "prev".to_string()),
+        ]
+    }
+
+    fn move_selection(&mut self, delta: i32) {
+        if self.rows.is_empty() {
+            self.selected = 0;
+            return;
+        }
+        let max = (self.rows.len() - 1) as i32;
+        self.selected = (self.selected as i32 + delta).clamp(0, max) as usize;
+    }
+}
+
+/// `#<step>  line <row>  +<inserted> -<deleted>`, numbering the steps after the
+/// seeded content in the order they were made.
+fn step_label(tree: &UndoTree, node: usize, floor: usize) -> String {
+    let Some(group) = tree.group(node) else {
+        return String::new();
+    };
+    format!(
+        "#{}  line {}  +{} -{}",
+        node + 1 - floor,
+        group.row + 1,
+        group.inserted,
+        group.deleted
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{action_handlers::dispatch, buffer::TextBuffer};
+    use stoat_text::BufferId;
+
+    fn labels(picker: &UndoTreePicker) -> Vec<(usize, bool, &str)> {
+        picker
+            .rows()
+            .iter()
+            .map(|row| (row.depth, row.current, row.label.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn older_branches_indent_under_their_parent() {
+        let mut buf = TextBuffer::with_text(BufferId::new(0), "a\n");
+        buf.edit(0..0, "b");
+        buf.edit(0..0, "c");
+        buf.undo();
+        buf.undo();
+        buf.edit(2..2, "d\ne\n");
+        let picker = UndoTreePicker::new(buf.undo_tree(), 1);
+        assert_eq!(
+            labels(&picker),
+            vec![
+                (0, false, "original"),
+                (1, false, "#1  line 1  +1 -0"),
+                (1, false, "#2  line 1  +1 -0"),
+                (0, true, "#3  line 2  +4 -0"),
+            ]
+        );
+        assert_eq!(picker.selected(), 3);
+        assert_eq!(picker.selected_node(), Some(3));
+    }
+
+    #[test]
+    fn select_restores_the_chosen_branch() {
+        let mut h = crate::Stoat::test();
+        h.seed_focused_buffer("one\n");
+        h.type_keys("i");
+        h.type_text("two ");
+        h.type_keys("esc");
+        h.type_keys("u");
+        h.type_keys("i");
+        h.type_text("three ");
+        h.type_keys("esc");
+        assert_eq!(focused_text(&mut h), "three one\n");
+
+        dispatch(&mut h.stoat, &stoat_action::OpenUndoTree);
+        let picker = h.stoat.undo_tree_picker.as_ref().expect("modal open");
+        assert_eq!(picker.rows().len(), 4);
+        dispatch(&mut h.stoat, &stoat_action::defs::picker::UndoTreePrev);
+        dispatch(&mut h.stoat, &stoat_action::defs::picker::UndoTreeSelect);
+        assert!(h.stoat.undo_tree_picker.is_none());
+        assert_eq!(focused_text(&mut h), "two one\n");
+    }
+
+    fn focused_text(h: &mut crate::test_harness::TestHarness) -> String {
+        let editor = crate::action_handlers::focused_editor_mut(&mut h.stoat).expect("editor");
+        let snapshot = editor.display_map.snapshot();
+        snapshot.buffer_snapshot().rope().to_string()
+    }
+}
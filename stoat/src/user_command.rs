@@ -0,0 +1,115 @@
+//! Commands defined in the config with `command NAME(params) -> ...`.
+//!
+//! The palette runs one as `:command NAME ARGS`. The arguments bind to the
+//! command's parameters in order, and each `$param` in its actions reads the
+//! bound text before the actions dispatch the way a key binding's would.
+
+use crate::{
+    app::{Stoat, UpdateEffect},
+    keymap::resolve_config_action,
+};
+use std::collections::HashMap;
+use stoat_config::Value;
+
+/// Run the command named by the first word of `line`, binding the words
+/// after it to its parameters. The last parameter takes the rest of the
+/// line, so it may hold spaces.
+pub(crate) fn run_user_command(stoat: &mut Stoat, line: &str) -> UpdateEffect {
+    let line = line.trim();
+    let (name, mut rest) = line.split_once(' ').unwrap_or((line, ""));
+    let Some(command) = stoat.settings.commands.get(name) else {
+        stoat.set_status(format!("unknown command: {name}"));
+        return UpdateEffect::Redraw;
+    };
+
+    let mut bound = HashMap::new();
+    for (index, param) in command.params.iter().enumerate() {
+        rest = rest.trim_start();
+        let arg = if index + 1 == command.params.len() {
+            rest
+        } else {
+            let (arg, tail) = rest.split_once(' ').unwrap_or((rest, ""));
+            rest = tail;
+            arg
+        };
+        if arg.is_empty() {
+            let message = format!("{name}: missing argument `{param}`");
+            stoat.set_status(message);
+            return UpdateEffect::Redraw;
+        }
+        bound.insert(param.as_str(), arg.to_string());
+    }
+
+    let actions: Vec<_> = command
+        .actions
+        .iter()
+        .map(|action| {
+            let mut resolved = resolve_config_action(action);
+            for arg in &mut resolved.args {
+                if let Value::StateRef(param) = &arg.value
+                    && let Some(text) = bound.get(param.as_str())
+                {
+                    arg.value = Value::String(text.clone());
+                }
+            }
+            resolved
+        })
+        .collect();
+    stoat.dispatch_bound_actions(&actions, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{action_handlers::dispatch, keymap::StateValue, test_harness::TestHarness};
+    use stoat_config::{Action, Arg, Spanned, UserCommand, Value};
+
+    fn greet_command() -> UserCommand {
+        let arg = |value| Spanned::new(Arg::Positional(Spanned::new(value, 0..0)), 0..0);
+        UserCommand {
+            description: None,
+            params: vec!["who".to_string()],
+            actions: vec![Action {
+                name: "SetVar".to_string(),
+                args: vec![
+                    arg(Value::Ident("greeting".to_string())),
+                    arg(Value::StateRef("who".to_string())),
+                ],
+            }],
+        }
+    }
+
+    fn run(h: &mut TestHarness, line: &str) {
+        dispatch(
+            &mut h.stoat,
+            &stoat_action::RunUserCommand {
+                line: line.to_string(),
+            },
+        );
+    }
+
+    #[test]
+    fn arguments_replace_params_in_the_actions() {
+        let mut h = TestHarness::with_size(80, 24);
+        h.stoat
+            .settings
+            .commands
+            .insert("greet".to_string(), greet_command());
+
+        run(&mut h, "greet big world");
+        assert_eq!(
+            h.stoat.user_vars.get("greeting"),
+            Some(&StateValue::String("big world".into()))
+        );
+
+        run(&mut h, "greet");
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("greet: missing argument `who`")
+        );
+        run(&mut h, "wave");
+        assert_eq!(
+            h.stoat.pending_message.as_deref(),
+            Some("unknown command: wave")
+        );
+    }
+}
@@ -1,6 +1,7 @@
 mod name;
 pub(crate) mod persist;
 pub(crate) mod registry;
+pub(crate) mod store;
 
 use crate::{
     agent_status::AgentStatus,
@@ -12,6 +13,7 @@ use crate::{
         build::{file_id, reindex_buffer, IndexUpdate, ReindexTarget},
         nav::TrailState,
     },
+    command_log::CommandRun,
     commit_list::CommitListState,
     conflict_session::ConflictSession,
     diff,
@@ -19,10 +21,12 @@ use crate::{
     diff_map::{line_starts, BaseHighlights, DiffMap},
     display_map::syntax_theme::SyntaxStyles,
     editor_state::{EditorId, EditorState},
+    hex_view::{HexId, HexView},
     host::{FsHost, GitHost},
     input_history::InputHistory,
     pane::{DockId, DockPanel, DockSide, FocusTarget, PaneTree, View},
     rebase::{ActiveRebase, RebaseState},
+    regex_playground::{PlaygroundId, RegexPlayground},
     render::layout::split_pane_status,
     review::ReviewFileInput,
     review_session::ReviewSession,
@@ -36,20 +40,20 @@ use ratatui::layout::Rect;
 use serde::{Deserialize, Serialize};
 use slotmap::{new_key_type, SlotMap};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     future::Future,
     ops::Range,
     path::{Path, PathBuf},
     pin::Pin,
     sync::{Arc, Mutex},
     task::{Context, Poll},
-    time::UNIX_EPOCH,
+    time::{Duration, UNIX_EPOCH},
 };
 use stoat_language::{
     extract_highlights, parse, structural_diff, HighlightSpan, Language, LanguageRegistry,
 };
 use stoat_scheduler::{Executor, Task};
-use stoat_text::{Point, Rope};
+use stoat_text::{LineEnding, Point, Rope};
 use tokio::sync::{mpsc::UnboundedSender, oneshot, Notify};
 
 new_key_type! {
@@ -64,6 +68,10 @@ new_key_type! {
 /// buffer is parsed on the background pool instead of blocking a keystroke.
 const SYNC_PARSE_MAX_BYTES: usize = 256 * 1024;
 
+/// Quiet window after an edit before a background reparse starts. Edits that
+/// land inside it are folded into the same parse.
+const PARSE_DEBOUNCE: Duration = Duration::from_millis(30);
+
 /// Stable-across-restart workspace identifier. [`WorkspaceId`] is a SlotMap
 /// key whose generation is recycled each run, so it can't serve as an on-disk
 /// filename. [`WorkspaceUid`] is assigned once at construction time from the
@@ -152,6 +160,12 @@ pub struct Workspace {
     /// Fish-style recall history of executed command-palette lines, walked by
     /// bare Up/Down in the palette. Persisted per workspace.
     pub(crate) palette_history: InputHistory,
+    /// Recall history of submitted `/` and `?` queries, walked by Up/Down in
+    /// the search input. Persisted per workspace.
+    pub(crate) search_history: InputHistory,
+    /// Every command run from the palette, oldest first, for
+    /// `RepeatLastCommand` and the `:history ` picker. Persisted per workspace.
+    pub(crate) command_log: Vec<CommandRun>,
     /// The active tab's pane layout. Every render, focus, and navigation site
     /// reads this and never sees the parked tabs.
     pub panes: PaneTree,
@@ -169,6 +183,8 @@ pub struct Workspace {
     pub(crate) editors: SlotMap<EditorId, EditorState>,
     pub(crate) runs: SlotMap<RunId, RunState>,
     pub(crate) terms: SlotMap<TermId, TermSession>,
+    pub(crate) hex_views: SlotMap<HexId, HexView>,
+    pub(crate) regex_playgrounds: SlotMap<PlaygroundId, RegexPlayground>,
     /// In-RAM symbol-and-call graph for this workspace, merged from the
     /// per-file shards the cold build and incremental reindex produce.
     pub(crate) code_graph: CodeGraph,
@@ -179,6 +195,14 @@ pub struct Workspace {
     /// recover a symbol's file from its graph id. The graph keys files by a
     /// one-way hash, so this is the only way back to a path.
     pub(crate) file_paths: HashMap<FileId, PathBuf>,
+    /// Workspace-relative path of every non-ignored file in the worktree, or
+    /// `None` until the index build finishes or a persisted list loads. The
+    /// file finder lists from this instead of walking when it is set, and fs
+    /// events keep it current between builds.
+    pub(crate) worktree_files: Option<BTreeSet<String>>,
+    /// Each changed file's git status as of the last file finder, so the next
+    /// finder badges its rows before its own refresh lands.
+    pub(crate) git_status: Arc<crate::file_finder::GitStatusMap>,
     /// Byte ranges changed against HEAD for each file with a working-tree
     /// diff, in the working-tree text's byte space. Rebuilt by
     /// [`Self::refresh_changed_ranges`] so diff-filtered navigation can ask
@@ -230,6 +254,19 @@ pub struct Workspace {
     /// are not retried every frame, and drives re-population when a buffer is
     /// edited past the recorded version.
     diff_versions: HashMap<BufferId, u64>,
+    /// Buffers diffed against another open buffer instead of HEAD, keyed by
+    /// the diffed (right-hand) buffer. Installed by `DiffWithBuffer`, kept
+    /// current by [`Self::sync_buffer_diffs`], and skipped by
+    /// [`Self::drive_diff_jobs`] so a HEAD diff never overwrites them.
+    pub(crate) buffer_diff_bases: HashMap<BufferId, BufferDiffBase>,
+    /// Co-editing sessions for buffers shared with another replica, installed
+    /// by [`Stoat::share_buffer`](crate::Stoat::share_buffer) and pumped by
+    /// [`crate::collab::pump`].
+    pub(crate) collab: HashMap<BufferId, crate::collab::CollabSession>,
+    /// Read-only buffers opened by `RunCommand`, with the command that fills
+    /// them. Entries for closed buffers are dropped by
+    /// [`crate::action_handlers::shell::install_command_outputs`].
+    pub(crate) command_outputs: HashMap<BufferId, crate::action_handlers::shell::CommandOutput>,
     /// In-flight live-reindex jobs, one per buffer, held so the spawned
     /// extraction is not cancelled. Replaced when the buffer reparses.
     index_jobs: HashMap<BufferId, Task<()>>,
@@ -248,6 +285,10 @@ pub struct Workspace {
     /// close path fires it to unblock the waiting agent. It is not persisted,
     /// because a oneshot cannot outlive the process.
     pub(crate) editor_bridge_waiters: HashMap<BufferId, oneshot::Sender<()>>,
+    /// Tools this workspace's agent may run without a permission prompt,
+    /// added by [`stoat_action::defs::agent::AgentPermissionAlways`].
+    /// Persisted, so the choice outlives the session.
+    pub(crate) agent_allowed_tools: BTreeSet<String>,
 }
 
 struct ParseJob {
@@ -266,6 +307,16 @@ struct DiffJobOutput {
     diff_map: Option<DiffMap>,
 }
 
+/// The buffer another buffer is diffed against, see
+/// [`Workspace::buffer_diff_bases`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BufferDiffBase {
+    pub(crate) base: BufferId,
+    /// `(buffer, base)` edit versions the installed diff map was computed
+    /// for, `None` until the first sync.
+    computed: Option<(u64, u64)>,
+}
+
 /// A one-pane tree showing a fresh scratch buffer, the shape a workspace and
 /// every new tab start in.
 ///
@@ -302,6 +353,8 @@ impl Workspace {
             diff_warmed: false,
             last_finder_scope: None,
             palette_history: InputHistory::default(),
+            search_history: InputHistory::default(),
+            command_log: Vec::new(),
             panes,
             tabs: vec![Tab {
                 parked: None,
@@ -315,9 +368,13 @@ impl Workspace {
             editors,
             runs: SlotMap::with_key(),
             terms: SlotMap::with_key(),
+            hex_views: SlotMap::with_key(),
+            regex_playgrounds: SlotMap::with_key(),
             code_graph: CodeGraph::new(),
             index_generation: 0,
             file_paths: HashMap::new(),
+            worktree_files: None,
+            git_status: Arc::default(),
             changed_ranges: HashMap::new(),
             changed_ranges_memo: HashMap::new(),
             #[cfg(test)]
@@ -331,10 +388,14 @@ impl Workspace {
             parse_jobs: HashMap::new(),
             diff_jobs: HashMap::new(),
             diff_versions: HashMap::new(),
+            buffer_diff_bases: HashMap::new(),
+            collab: HashMap::new(),
+            command_outputs: HashMap::new(),
             index_jobs: HashMap::new(),
             badges: BadgeTray::new(),
             agent: None,
             editor_bridge_waiters: HashMap::new(),
+            agent_allowed_tools: BTreeSet::new(),
         }
     }
 
@@ -468,6 +529,13 @@ impl Workspace {
             View::Agent(_) => "agent".to_string(),
             View::Terminal(_) => "term".to_string(),
             View::Run(_) => "run".to_string(),
+            View::Hex(id) => self
+                .hex_views
+                .get(*id)
+                .and_then(|hex| hex.path().file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "hex".to_string()),
+            View::RegexPlayground(_) => "regex".to_string(),
             View::Label(_) => "pane".to_string(),
         }
     }
@@ -600,11 +668,12 @@ impl Workspace {
         let Some(shared) = self.buffers.get(id) else {
             return;
         };
-        let (version, text) = {
+        let (version, text, line_ending) = {
             let guard = shared.read().expect("buffer poisoned");
             (
                 guard.snapshot.version,
                 guard.snapshot.visible_text.to_string(),
+                guard.line_ending(),
             )
         };
 
@@ -614,6 +683,7 @@ impl Workspace {
             &self.git_root,
             &path,
             &text,
+            line_ending,
             language.as_ref(),
             syntax_styles,
             base_cache,
@@ -624,6 +694,94 @@ impl Workspace {
         self.diff_versions.insert(id, version);
     }
 
+    /// Diff `id` against the open buffer `base` instead of HEAD, installing the
+    /// map on the current turn. Drops any in-flight HEAD diff job for `id` so
+    /// its result cannot land over the pair's map.
+    pub(crate) fn set_buffer_diff_base(
+        &mut self,
+        id: BufferId,
+        base: BufferId,
+        language_registry: &Arc<LanguageRegistry>,
+        syntax_styles: &SyntaxStyles,
+        base_cache: &BaseHighlightCache,
+    ) {
+        self.diff_jobs.remove(&id);
+        self.buffer_diff_bases.insert(
+            id,
+            BufferDiffBase {
+                base,
+                computed: None,
+            },
+        );
+        self.sync_buffer_diffs(language_registry, syntax_styles, base_cache);
+    }
+
+    /// Stop diffing `id` against another buffer. Clears the pair's map and
+    /// stales the buffer, so the next [`Self::drive_diff_jobs`] pass restores
+    /// its HEAD diff. Returns whether `id` had a pair.
+    pub(crate) fn clear_buffer_diff_base(&mut self, id: BufferId) -> bool {
+        if self.buffer_diff_bases.remove(&id).is_none() {
+            return false;
+        }
+        if let Some(shared) = self.buffers.get(id) {
+            shared.write().expect("buffer poisoned").diff_map = None;
+        }
+        self.invalidate_diff(id);
+        true
+    }
+
+    /// Recompute every buffer-pair diff whose buffer or base was edited since
+    /// its last sync.
+    ///
+    /// Runs synchronously, like [`Self::install_diff_map_now`], since the pair
+    /// is on screen and the view reads its hunks on the same turn. A pair whose
+    /// buffer or base has closed is dropped.
+    pub(crate) fn sync_buffer_diffs(
+        &mut self,
+        language_registry: &Arc<LanguageRegistry>,
+        syntax_styles: &SyntaxStyles,
+        base_cache: &BaseHighlightCache,
+    ) {
+        let ids: Vec<BufferId> = self.buffer_diff_bases.keys().copied().collect();
+        for id in ids {
+            let pair = self.buffer_diff_bases[&id];
+            let (Some(shared), Some(base_shared)) =
+                (self.buffers.get(id), self.buffers.get(pair.base))
+            else {
+                self.clear_buffer_diff_base(id);
+                continue;
+            };
+            let (version, buffer_rope) = {
+                let guard = shared.read().expect("buffer poisoned");
+                (guard.snapshot.version, guard.snapshot.visible_text.clone())
+            };
+            let (base_version, base_rope) = {
+                let guard = base_shared.read().expect("buffer poisoned");
+                (guard.snapshot.version, guard.snapshot.visible_text.clone())
+            };
+            if pair.computed == Some((version, base_version)) {
+                continue;
+            }
+            let language = self
+                .buffers
+                .path_for(pair.base)
+                .or_else(|| self.buffers.path_for(id))
+                .and_then(|path| language_registry.for_path(path));
+            let diff_map = compute_buffer_diff_map(
+                &base_rope.to_string(),
+                &buffer_rope.to_string(),
+                language.as_ref(),
+                syntax_styles,
+                base_cache,
+            );
+            shared.write().expect("buffer poisoned").diff_map = Some(diff_map);
+            self.diff_versions.insert(id, version);
+            if let Some(pair) = self.buffer_diff_bases.get_mut(&id) {
+                pair.computed = Some((version, base_version));
+            }
+        }
+    }
+
     /// Install `diff_map` on `id` and record it as computed for the buffer's
     /// current version, so it reads as current to [`Self::diff_map_current`].
     ///
@@ -675,6 +833,29 @@ impl Workspace {
         editor
     }
 
+    /// Swap a finished parse into the registry and every editor showing its
+    /// buffer. Syntax tree, syntax map, words and tokens are replaced together
+    /// within one frame, so a render never pairs the new tree with old tokens.
+    fn install_parse_output(&mut self, out: ParseJobOutput, syntax_styles: &SyntaxStyles) {
+        self.buffers.store_syntax(out.buffer_id, out.syntax);
+        self.buffers.store_syntax_map(out.buffer_id, out.syntax_map);
+        self.buffers.store_words(out.buffer_id, out.words);
+        self.buffers.store_tokens(
+            out.buffer_id,
+            out.tokens.clone(),
+            syntax_styles.interner.clone(),
+        );
+        for editor in self.editors.values_mut() {
+            if editor.buffer_id == out.buffer_id {
+                editor.display_map.set_semantic_token_highlights(
+                    out.buffer_id,
+                    out.tokens.clone(),
+                    syntax_styles.interner.clone(),
+                );
+            }
+        }
+    }
+
     /// Drive background parse jobs: poll any in-flight tasks for completion,
     /// install their results, then spawn new jobs for visible buffers whose
     /// stored syntax version is stale.
@@ -684,6 +865,9 @@ impl Workspace {
     /// after the old one completes. Anchors in the result are computed using
     /// the parsed snapshot, so they remain valid even if the buffer has been
     /// edited further while the parse was running.
+    ///
+    /// A background reparse of an already-highlighted buffer is debounced by
+    /// [`PARSE_DEBOUNCE`], so typing in a large file never waits on a parse.
     pub(crate) fn drive_parse_jobs(
         &mut self,
         executor: &Executor,
@@ -706,23 +890,9 @@ impl Workspace {
             }
         });
         for out in completed {
-            self.buffers.store_syntax(out.buffer_id, out.syntax);
-            self.buffers.store_syntax_map(out.buffer_id, out.syntax_map);
-            self.buffers.store_tokens(
-                out.buffer_id,
-                out.tokens.clone(),
-                syntax_styles.interner.clone(),
-            );
-            for editor in self.editors.values_mut() {
-                if editor.buffer_id == out.buffer_id {
-                    editor.display_map.set_semantic_token_highlights(
-                        out.buffer_id,
-                        out.tokens.clone(),
-                        syntax_styles.interner.clone(),
-                    );
-                }
-            }
-            let text = self.buffers.get(out.buffer_id).map(|shared| {
+            let buffer_id = out.buffer_id;
+            self.install_parse_output(out, syntax_styles);
+            let text = self.buffers.get(buffer_id).map(|shared| {
                 shared
                     .read()
                     .expect("buffer poisoned")
@@ -731,13 +901,7 @@ impl Workspace {
                     .clone()
             });
             if let Some(text) = text {
-                self.enqueue_reindex(
-                    executor,
-                    index_update_tx,
-                    redraw_notify,
-                    out.buffer_id,
-                    text,
-                );
+                self.enqueue_reindex(executor, index_update_tx, redraw_notify, buffer_id, text);
             }
         }
 
@@ -775,7 +939,7 @@ impl Workspace {
             // background pool instead of blocking the keystroke.
             let sync_out = (snapshot.len() <= SYNC_PARSE_MAX_BYTES)
                 .then(|| {
-                    let deadline = executor.now() + std::time::Duration::from_millis(1);
+                    let deadline = executor.now() + Duration::from_millis(1);
                     parse_buffer_step(
                         buffer_id,
                         snapshot.clone(),
@@ -788,22 +952,7 @@ impl Workspace {
                 })
                 .flatten();
             if let Some(out) = sync_out {
-                self.buffers.store_syntax(out.buffer_id, out.syntax);
-                self.buffers.store_syntax_map(out.buffer_id, out.syntax_map);
-                self.buffers.store_tokens(
-                    out.buffer_id,
-                    out.tokens.clone(),
-                    syntax_styles.interner.clone(),
-                );
-                for editor in self.editors.values_mut() {
-                    if editor.buffer_id == out.buffer_id {
-                        editor.display_map.set_semantic_token_highlights(
-                            out.buffer_id,
-                            out.tokens.clone(),
-                            syntax_styles.interner.clone(),
-                        );
-                    }
-                }
+                self.install_parse_output(out, syntax_styles);
                 self.enqueue_reindex(
                     executor,
                     index_update_tx,
@@ -814,11 +963,23 @@ impl Workspace {
                 continue;
             }
 
+            // A reparse after an edit waits out PARSE_DEBOUNCE and then parses
+            // whatever text the buffer holds by then, so a burst of keystrokes
+            // costs one parse of the latest text rather than one per key. The
+            // old tokens stay on screen meanwhile. A first parse has nothing to
+            // show yet, so it starts at once.
+            let debounce = prior.is_some().then(|| (executor.clone(), shared.clone()));
             let styles = syntax_styles.clone();
-            let task = executor.spawn_with_redraw(
-                redraw_notify.clone(),
-                parse_buffer_async(buffer_id, snapshot, lang, prior, prior_map, styles),
-            );
+            let task = executor.spawn_with_redraw(redraw_notify.clone(), async move {
+                let snapshot = match debounce {
+                    Some((executor, shared)) => {
+                        executor.timer(PARSE_DEBOUNCE).await;
+                        shared.read().expect("buffer poisoned").snapshot.clone()
+                    },
+                    None => snapshot,
+                };
+                parse_buffer_async(buffer_id, snapshot, lang, prior, prior_map, styles).await
+            });
             self.parse_jobs.insert(
                 buffer_id,
                 ParseJob {
@@ -857,7 +1018,12 @@ impl Workspace {
                         visible.push(editor.buffer_id);
                     }
                 },
-                View::Label(_) | View::Run(_) | View::Agent(_) | View::Terminal(_) => {},
+                View::Label(_)
+                | View::Run(_)
+                | View::Agent(_)
+                | View::Terminal(_)
+                | View::Hex(_)
+                | View::RegexPlayground(_) => {},
             }
         }
         for id in self.buffers.preview_buffer_ids() {
@@ -907,15 +1073,22 @@ impl Workspace {
 
         let git_root = self.git_root.clone();
         for buffer_id in self.visible_buffer_ids() {
+            if self.buffer_diff_bases.contains_key(&buffer_id) {
+                continue;
+            }
             let Some(path) = self.buffers.path_for(buffer_id).map(Path::to_path_buf) else {
                 continue;
             };
             let Some(shared) = self.buffers.get(buffer_id) else {
                 continue;
             };
-            let (cur_version, buffer_rope) = {
+            let (cur_version, buffer_rope, line_ending) = {
                 let guard = shared.read().expect("buffer poisoned");
-                (guard.snapshot.version, guard.snapshot.visible_text.clone())
+                (
+                    guard.snapshot.version,
+                    guard.snapshot.visible_text.clone(),
+                    guard.line_ending(),
+                )
             };
 
             if self.diff_versions.get(&buffer_id) == Some(&cur_version) {
@@ -945,6 +1118,7 @@ impl Workspace {
                         &git_root,
                         &path,
                         &buffer_text,
+                        line_ending,
                         language.as_ref(),
                         &syntax_styles,
                         &base_cache,
@@ -968,7 +1142,8 @@ impl Workspace {
     }
 
     /// Detect and assign a language to every path-bearing buffer that
-    /// lacks one, resolving the path's extension through `registry`.
+    /// lacks one, resolving the path's extension, or the buffer's shebang or
+    /// modeline, through `registry`.
     ///
     /// Session restore (via [`Self::restore_state`]) rebuilds buffers
     /// with no language, and the parse pipeline only highlights buffers
@@ -978,7 +1153,11 @@ impl Workspace {
     /// during the session are unaffected.
     pub(crate) fn assign_languages_from_paths(&mut self, registry: &LanguageRegistry) {
         for (id, path) in self.buffers.buffers_needing_language() {
-            if let Some(lang) = registry.for_path(&path) {
+            let Some(shared) = self.buffers.get(id) else {
+                continue;
+            };
+            let lang = registry.detect(&path, shared.read().expect("buffer poisoned").rope());
+            if let Some(lang) = lang {
                 self.buffers.set_language(id, lang);
             }
         }
@@ -1136,20 +1315,28 @@ impl Workspace {
 pub(crate) type BaseHighlightCache =
     Arc<Mutex<HashMap<(ContentHash, String), Arc<Vec<HighlightSpan>>>>>;
 
+#[allow(clippy::too_many_arguments)]
 fn compute_diff_map(
     git: &dyn GitHost,
     git_root: &Path,
     path: &Path,
     buffer_text: &str,
+    line_ending: LineEnding,
     language: Option<&Arc<Language>>,
     syntax_styles: &SyntaxStyles,
     base_cache: &BaseHighlightCache,
 ) -> Option<DiffMap> {
     let repo = git.discover(git_root)?;
-    let base_text = repo.head_content(path)?;
+    // The buffer holds its text normalized for its line ending, so HEAD and
+    // the index are brought to the same form before diffing. Otherwise every
+    // line of a CRLF file would read modified.
+    let base_text = line_ending
+        .normalize(&repo.head_content(path)?)
+        .into_owned();
 
     let index_text = repo
         .index_content(path)
+        .map(|text| line_ending.normalize(&text).into_owned())
         .unwrap_or_else(|| base_text.clone());
     let index_changed: Vec<Range<u32>> = {
         let index_result = structural_diff::diff(&index_text, buffer_text);
@@ -1175,6 +1362,29 @@ fn compute_diff_map(
     Some(diff_map)
 }
 
+/// Diff `buffer_text` against another buffer's `base_text`, for a pair in
+/// [`Workspace::buffer_diff_bases`]. No git index is involved, so no hunk
+/// reads as staged.
+fn compute_buffer_diff_map(
+    base_text: &str,
+    buffer_text: &str,
+    language: Option<&Arc<Language>>,
+    syntax_styles: &SyntaxStyles,
+    base_cache: &BaseHighlightCache,
+) -> DiffMap {
+    let result = structural_diff::diff(base_text, buffer_text);
+    let mut diff_map = DiffMap::from_structural_changes(result, base_text, buffer_text);
+    if let Some(language) = language {
+        diff_map.set_base_highlights(compute_base_highlights(
+            base_text,
+            language,
+            syntax_styles,
+            base_cache,
+        ));
+    }
+    diff_map
+}
+
 /// Highlight `base_text` for the diff view's left column, memoizing the parse in
 /// `cache`. Styles resolve against the current `syntax_styles` on every call so
 /// a theme change still takes effect on the next build.
@@ -1274,7 +1484,7 @@ fn changed_byte_ranges(input: &ReviewFileInput) -> Vec<Range<usize>> {
 
 #[cfg(test)]
 mod tests {
-    use super::{changed_byte_ranges, ParseJob, Workspace, SYNC_PARSE_MAX_BYTES};
+    use super::{changed_byte_ranges, ParseJob, Workspace, PARSE_DEBOUNCE, SYNC_PARSE_MAX_BYTES};
     use crate::{
         buffer::BufferId, host::DiffStatus, pane::View, review::ReviewFileInput,
         test_harness::TestHarness,
@@ -1502,6 +1712,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn diff_job_compares_a_crlf_file_line_for_line() {
+        let mut h = TestHarness::with_size(80, 24);
+        h.stage_review_scenario("/repo", &[("a.txt", "a\r\nb\r\n", "a\r\nc\r\n")]);
+        h.stoat.set_diff_warm_auto(true);
+        h.open_file(Path::new("/repo/a.txt"));
+        h.settle_diff_jobs();
+
+        let buffer_id = h.stoat.focused_editor_ids().expect("focused editor").1;
+        let buffer = h
+            .stoat
+            .active_workspace()
+            .buffers
+            .get(buffer_id)
+            .expect("buffer");
+        let guard = buffer.read().expect("poisoned");
+        let dm = guard.diff_map.as_ref().expect("diff map populated");
+        assert_eq!(
+            dm.status_for_line(0),
+            DiffStatus::Unchanged,
+            "HEAD's CRLF breaks match the buffer's normalized ones"
+        );
+        assert_eq!(dm.status_for_line(1), DiffStatus::Modified);
+    }
+
     #[test]
     fn drive_diff_jobs_skips_an_already_current_buffer() {
         let mut h = TestHarness::with_size(80, 24);
@@ -1773,6 +2008,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn background_reparse_debounces_edits_into_one_parse() {
+        use crate::action_handlers::dispatch;
+        use stoat_action::OpenFile;
+
+        let mut h = TestHarness::with_size(24, 4);
+        let root = PathBuf::from("/big");
+        let path = root.join("big.rs");
+        let big: String = "fn f() {}\n".repeat(SYNC_PARSE_MAX_BYTES / 10 + 100);
+        h.fake_fs().insert_file(path.clone(), big.as_bytes());
+        h.stoat.active_workspace_mut().git_root = root.clone();
+
+        dispatch(&mut h.stoat, &OpenFile { path: path.clone() });
+        h.stoat.drive_background();
+        h.settle();
+        h.stoat.drive_background();
+
+        let id = h
+            .stoat
+            .active_workspace()
+            .buffers
+            .id_for_path(&path)
+            .expect("the big buffer opened");
+        let shared = h.stoat.active_workspace().buffers.get(id).unwrap();
+        let version = || shared.read().unwrap().snapshot.version;
+        assert_eq!(
+            h.stoat.active_workspace().buffers.syntax_version(id),
+            Some(version()),
+            "the first parse is not debounced"
+        );
+
+        shared.write().unwrap().edit(0..0, "// a\n");
+        h.stoat.drive_background();
+        shared.write().unwrap().edit(0..0, "// b\n");
+        h.settle();
+        h.stoat.drive_background();
+        let ws = h.stoat.active_workspace();
+        assert!(
+            ws.parse_jobs.contains_key(&id),
+            "the reparse waits out the debounce"
+        );
+        assert!(ws.buffers.tokens_for(id).is_some(), "old tokens stay shown");
+
+        h.advance_clock(PARSE_DEBOUNCE);
+        h.stoat.drive_background();
+        let ws = h.stoat.active_workspace();
+        assert!(!ws.parse_jobs.contains_key(&id));
+        assert_eq!(
+            ws.buffers.syntax_version(id),
+            Some(version()),
+            "one parse covers both edits"
+        );
+    }
+
     #[test]
     fn highlight_retention_evicts_least_recently_shown() {
         use crate::action_handlers::dispatch;
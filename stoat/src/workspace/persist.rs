@@ -1,7 +1,9 @@
 //! Per-workspace session state persistence.
 //!
 //! Each workspace serializes to
-//! `<stoat_log::workspace_state_dir()>/<git_root_hash>/<uid>.ron`. When the
+//! `<stoat_log::workspace_state_dir()>/<git_root_hash>/<uid>.<ext>`, where the
+//! extension names the `workspace.state_format` it was saved in (see
+//! [`super::store`]). When the
 //! user passes `--continue`, the binary scans this directory and rehydrates
 //! the most-recently-modified file before the first frame renders; a bare
 //! launch skips the load so each new session begins in a fresh workspace.
@@ -18,13 +20,17 @@
 
 use crate::{
     buffer_registry::{BufferRegistry, BufferRegistrySnapshot},
+    command_log::CommandRun,
     dump::snapshot::ActiveRebaseSnap,
     editor_state::{EditorId, EditorState, EditorStateSnapshot},
     host::FsHost,
     input_history::InputHistory,
     pane::{DockId, DockPanel, FocusTarget, PaneId, PaneTree, View},
     rebase::RebaseState,
-    workspace::{Tab, Workspace, WorkspaceUid},
+    workspace::{
+        store::{self, StateFormat, StateStore, Versioned},
+        Tab, Workspace, WorkspaceUid,
+    },
 };
 use serde::{Deserialize, Serialize};
 use slotmap::SlotMap;
@@ -52,6 +58,11 @@ pub(crate) enum FocusTargetSnap {
 /// by this struct are regenerated from defaults on load.
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct WorkspaceStateV1 {
+    /// Schema version, read by [`StateStore::load_state`] to pick a
+    /// migration. Zero on files that predate the field, which hold this
+    /// same schema.
+    #[serde(default)]
+    pub version: u32,
     /// Stable workspace identifier preserved across saves; doubles as the
     /// on-disk filename. Defaults to zero for pre-field on-disk files; the
     /// loader will treat that as a legacy file (still readable, but its
@@ -82,6 +93,14 @@ pub(crate) struct WorkspaceStateV1 {
     /// survives a restart. Empty on legacy files that predate the field.
     #[serde(default)]
     pub palette_history: Vec<String>,
+    /// Submitted search queries, oldest first. Empty on legacy files that
+    /// predate the field.
+    #[serde(default)]
+    pub search_history: Vec<String>,
+    /// Commands run from the palette, oldest first. Empty on legacy files
+    /// that predate the field.
+    #[serde(default)]
+    pub command_log: Vec<CommandRun>,
     /// One entry per tab in display order, mirroring the in-memory shape: the
     /// entry at [`Self::active_tab`] is `None` because that tree is saved in
     /// [`Self::panes`]. Empty on legacy files, which restore as a single tab.
@@ -95,6 +114,14 @@ pub(crate) struct WorkspaceStateV1 {
     /// missing entries restore as unnamed tabs.
     #[serde(default)]
     pub tab_names: Vec<Option<String>>,
+    /// Tools the agent may run without a prompt, sorted. Empty on legacy files
+    /// that predate the field.
+    #[serde(default)]
+    pub agent_allowed_tools: Vec<String>,
+}
+
+impl Versioned for WorkspaceStateV1 {
+    const VERSION: u32 = 1;
 }
 
 /// Resolve the per-git-root directory that holds every workspace persisted
@@ -124,24 +151,27 @@ pub(crate) fn anchor_state_dir(state_dir: &Path, anchor: &Path, fs: &dyn FsHost)
     state_dir.join(name)
 }
 
-/// Resolve the on-disk state file path for a specific workspace.
+/// Resolve the on-disk state file path for a specific workspace saved in
+/// `format`.
 pub(crate) fn state_path_for(
     git_root: &Path,
     uid: WorkspaceUid,
+    format: StateFormat,
     fs: &dyn FsHost,
 ) -> io::Result<PathBuf> {
-    Ok(workspace_dir_for(git_root, fs)?.join(format!("{uid}.ron")))
+    let ext = store::extension(format);
+    Ok(workspace_dir_for(git_root, fs)?.join(format!("{uid}.{ext}")))
 }
 
 /// List every persisted workspace file for a git root, newest first by
 /// filesystem mtime. Returns an empty vec (not an error) if the directory
 /// does not exist.
 pub(crate) fn list_workspace_files(git_root: &Path, fs: &dyn FsHost) -> io::Result<Vec<PathBuf>> {
-    list_ron_files_by_mtime_desc(&workspace_dir_for(git_root, fs)?, fs)
+    list_state_files_by_mtime_desc(&workspace_dir_for(git_root, fs)?, fs)
 }
 
 /// Walk ancestors of `cwd` (cwd itself first) for any directory whose
-/// workspace state directory contains persisted `.ron` files, and return
+/// workspace state directory contains persisted state files, and return
 /// the ancestor whose newest file has the highest mtime across all
 /// candidates. Returns `None` when no ancestor has any persisted state.
 ///
@@ -170,7 +200,7 @@ fn find_resume_anchor_in(
         let mut newest: Option<std::time::SystemTime> = None;
         for entry in fs.list_dir(&dir)? {
             let path = dir.join(entry.name.as_str());
-            if path.extension().and_then(|s| s.to_str()) != Some("ron") {
+            if store::format_of(&path).is_none() {
                 continue;
             }
             let mtime = fs
@@ -195,14 +225,14 @@ fn find_resume_anchor_in(
 /// can exercise it against a tempdir without touching the real XDG path.
 /// Entries whose metadata cannot be read are treated as unix-epoch-old so
 /// they sort to the bottom rather than dropping out silently.
-fn list_ron_files_by_mtime_desc(dir: &Path, fs: &dyn FsHost) -> io::Result<Vec<PathBuf>> {
+fn list_state_files_by_mtime_desc(dir: &Path, fs: &dyn FsHost) -> io::Result<Vec<PathBuf>> {
     if !fs.exists(dir) {
         return Ok(Vec::new());
     }
     let mut entries: Vec<(PathBuf, std::time::SystemTime)> = Vec::new();
     for entry in fs.list_dir(dir)? {
         let path = dir.join(entry.name.as_str());
-        if path.extension().and_then(|s| s.to_str()) != Some("ron") {
+        if store::format_of(&path).is_none() {
             continue;
         }
         let mtime = fs
@@ -233,6 +263,7 @@ impl Workspace {
             .map(|active| ActiveRebaseSnap::from_active(active).snap);
 
         WorkspaceStateV1 {
+            version: WorkspaceStateV1::VERSION,
             uid: self.uid,
             git_root: self.git_root.clone(),
             panes: clone_pane_tree(&self.panes),
@@ -248,6 +279,8 @@ impl Workspace {
             name: self.name.clone(),
             last_finder_scope: self.last_finder_scope.clone(),
             palette_history: self.palette_history.entries().to_vec(),
+            search_history: self.search_history.entries().to_vec(),
+            command_log: self.command_log.clone(),
             tabs: self
                 .tabs
                 .iter()
@@ -255,21 +288,25 @@ impl Workspace {
                 .collect(),
             active_tab: self.active_tab,
             tab_names: self.tabs.iter().map(|tab| tab.name.clone()).collect(),
+            agent_allowed_tools: self.agent_allowed_tools.iter().cloned().collect(),
         }
     }
 
-    /// Serialize the current workspace state to RON and write it atomically
-    /// to `path`. Parent directory is created if missing.
+    /// Serialize the current workspace state in the format `path`'s
+    /// extension names and write it atomically to `path`. Parent directory is
+    /// created if missing.
+    ///
+    /// A copy saved under another format, left from before the user changed
+    /// `workspace.state_format`, is removed so a resume cannot pick the stale
+    /// one by its newer mtime.
     pub(crate) fn save_state(&self, path: &Path, fs: &dyn FsHost) -> io::Result<()> {
-        if let Some(parent) = path.parent() {
-            fs.create_dir_all(parent)?;
+        fs.save_state(path, &self.to_state())?;
+        for format in store::FORMATS {
+            let stale = path.with_extension(store::extension(format));
+            if stale != path && fs.exists(&stale) {
+                fs.remove_file(&stale)?;
+            }
         }
-        let state = self.to_state();
-        let body = ron::ser::to_string_pretty(&state, ron::ser::PrettyConfig::default())
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-        let tmp = path.with_extension("ron.tmp");
-        fs.write(&tmp, body.as_bytes())?;
-        fs.rename(&tmp, path)?;
 
         let meta = super::registry::WorkspaceMeta {
             uid: self.uid,
@@ -295,12 +332,7 @@ impl Workspace {
         fs: &dyn FsHost,
         executor: &Executor,
     ) -> io::Result<()> {
-        let mut buf = Vec::new();
-        fs.read(path, &mut buf)?;
-        let body = String::from_utf8(buf)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-        let state: WorkspaceStateV1 = ron::from_str(&body)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let state: WorkspaceStateV1 = fs.load_state(path)?;
         self.apply_state(state, executor);
         Ok(())
     }
@@ -382,6 +414,9 @@ impl Workspace {
         };
         self.last_finder_scope = state.last_finder_scope;
         self.palette_history = InputHistory::from_entries(state.palette_history);
+        self.search_history = InputHistory::from_entries(state.search_history);
+        self.command_log = state.command_log;
+        self.agent_allowed_tools = state.agent_allowed_tools.into_iter().collect();
 
         // Exactly the active slot may be empty, since that tree is in `panes`.
         let coherent = parked.len() > 1
@@ -442,12 +477,7 @@ pub(crate) fn read_restore_parts(
     path: &Path,
     fs: &dyn FsHost,
 ) -> io::Result<(BufferRegistry, WorkspaceStateV1)> {
-    let mut buf = Vec::new();
-    fs.read(path, &mut buf)?;
-    let body = String::from_utf8(buf)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-    let mut state: WorkspaceStateV1 = ron::from_str(&body)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    let mut state: WorkspaceStateV1 = fs.load_state(path)?;
     let mut registry = BufferRegistry::new();
     registry.restore_from(std::mem::take(&mut state.buffers));
     Ok((registry, state))
@@ -497,6 +527,8 @@ fn stale_replacement(view: &View) -> Option<View> {
     match view {
         View::Run(_) => Some(View::Label("Terminal (closed)".into())),
         View::Agent(_) => Some(View::Label("Agent (closed)".into())),
+        View::Hex(_) => Some(View::Label("Hex view (closed)".into())),
+        View::RegexPlayground(_) => Some(View::Label("Regex playground (closed)".into())),
         // Terminal panes survive the sweep with a dead id. The app respawns a
         // fresh shell for each after restore. See action_handlers::terminal.
         View::Terminal(_) | View::Label(_) | View::Editor(_) => None,
@@ -1112,7 +1144,7 @@ mod tests {
         fake.insert_file(&older, "old");
         fake.insert_file(&newer, "new");
 
-        let listed = list_ron_files_by_mtime_desc(&ws_dir, &fake).unwrap();
+        let listed = list_state_files_by_mtime_desc(&ws_dir, &fake).unwrap();
         assert_eq!(listed, vec![newer, older]);
     }
 
@@ -1124,7 +1156,7 @@ mod tests {
         fake.insert_file(ws_dir.join("skip.txt"), "");
         fake.insert_dir(ws_dir.join("subdir"));
 
-        let listed = list_ron_files_by_mtime_desc(&ws_dir, &fake).unwrap();
+        let listed = list_state_files_by_mtime_desc(&ws_dir, &fake).unwrap();
         assert_eq!(listed, vec![ws_dir.join("ok.ron")]);
     }
 
@@ -1133,7 +1165,7 @@ mod tests {
         let fake = FakeFs::new();
         let ws_dir = PathBuf::from("/test");
         let missing = ws_dir.join("nope");
-        assert!(list_ron_files_by_mtime_desc(&missing, &fake)
+        assert!(list_state_files_by_mtime_desc(&missing, &fake)
             .unwrap()
             .is_empty());
     }
@@ -1195,13 +1227,14 @@ mod tests {
     }
 
     #[test]
-    fn palette_history_round_trips_through_save_and_restore() {
+    fn palette_and_search_history_round_trip_through_save_and_restore() {
         let fake = FakeFs::new();
         let ws_dir = PathBuf::from("/test");
         let exec = executor();
 
         let mut ws = new_laid_out_workspace(ws_dir.clone(), &exec);
         ws.palette_history = InputHistory::from_entries(vec!["cd ~/work".into(), "w".into()]);
+        ws.search_history = InputHistory::from_entries(vec!["fn main".into()]);
         let state_path = ws_dir.join("state.ron");
         ws.save_state(&state_path, &fake).unwrap();
 
@@ -1212,6 +1245,7 @@ mod tests {
         );
         fresh.restore_state(&state_path, &fake, &exec).unwrap();
         assert_eq!(fresh.palette_history.entries().to_vec(), ["cd ~/work", "w"]);
+        assert_eq!(fresh.search_history.entries().to_vec(), ["fn main"]);
     }
 
     #[test]
@@ -1262,10 +1296,31 @@ mod tests {
         ws.save_state(&path, &fake).unwrap();
         ws.save_state(&path, &fake).unwrap();
 
-        let listed = list_ron_files_by_mtime_desc(&ws_dir, &fake).unwrap();
+        let listed = list_state_files_by_mtime_desc(&ws_dir, &fake).unwrap();
         assert_eq!(listed, vec![path]);
     }
 
+    #[test]
+    fn switching_format_replaces_the_old_file() {
+        let fake = FakeFs::new();
+        let ws_dir = PathBuf::from("/test");
+        let exec = executor();
+
+        let mut ws = new_laid_out_workspace(ws_dir.clone(), &exec);
+        ws.name = "formats".into();
+        let ron = ws_dir.join(format!("{}.ron", ws.uid));
+        let msgpack = ron.with_extension("msgpack");
+        ws.save_state(&ron, &fake).unwrap();
+        ws.save_state(&msgpack, &fake).unwrap();
+
+        let listed = list_state_files_by_mtime_desc(&ws_dir, &fake).unwrap();
+        assert_eq!(listed, vec![msgpack.clone()]);
+        let mut fresh = Workspace::new(ws_dir.clone(), &exec);
+        fresh.restore_state(&msgpack, &fake, &exec).unwrap();
+        assert_eq!(fresh.name, "formats");
+        assert_eq!(fresh.uid, ws.uid);
+    }
+
     #[test]
     fn different_uids_sit_side_by_side() {
         let fake = FakeFs::new();
@@ -1283,7 +1338,7 @@ mod tests {
         ws_a.save_state(&path_a, &fake).unwrap();
         ws_b.save_state(&path_b, &fake).unwrap();
 
-        let listed = list_ron_files_by_mtime_desc(&ws_dir, &fake).unwrap();
+        let listed = list_state_files_by_mtime_desc(&ws_dir, &fake).unwrap();
         assert_eq!(listed.len(), 2);
     }
 
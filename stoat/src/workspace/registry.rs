@@ -1,4 +1,4 @@
-use super::{persist::WorkspaceStateV1, WorkspaceUid};
+use super::{persist::WorkspaceStateV1, store, store::StateStore, WorkspaceUid};
 use crate::host::FsHost;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -7,14 +7,13 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-/// Lightweight per-workspace metadata written beside each `<uid>.ron` state file
-/// as a `<uid>.meta` sidecar.
+/// Lightweight per-workspace metadata written beside each `<uid>.<ext>` state
+/// file as a `<uid>.meta` sidecar.
 ///
 /// Sidecars let a workspace finder and cross-workspace search list every
-/// persisted workspace without parsing the heavy state RON, which carries full
-/// buffer op logs. The `.meta` extension keeps them invisible to the
-/// `extension == "ron"` state scans, so a resume never mistakes one for a state
-/// file.
+/// persisted workspace without parsing the heavy state file, which carries full
+/// buffer op logs. The `.meta` extension names no [`store::StateFormat`], so
+/// state scans skip them and a resume never mistakes one for a state file.
 ///
 /// One wart is the single-instance assumption. Workspace status (active,
 /// background, inactive) is derived in-process against the one running instance
@@ -40,7 +39,7 @@ pub(crate) struct RegistryEntry {
     pub mtime: SystemTime,
 }
 
-/// The sidecar path for a `<uid>.ron` state file, its path with a `.meta`
+/// The sidecar path for a `<uid>.<ext>` state file, its path with a `.meta`
 /// extension.
 pub(crate) fn meta_path_for(state_path: &Path) -> PathBuf {
     state_path.with_extension("meta")
@@ -64,7 +63,7 @@ pub(crate) fn write_meta(
 /// List every persisted workspace across all git roots, newest state file first.
 ///
 /// Reads each `<uid>.meta` sidecar under the workspace state directory. A legacy
-/// `<uid>.ron` with no sidecar is backfilled by parsing its state (metadata
+/// `<uid>.<ext>` state file with no sidecar is backfilled by parsing its state (metadata
 /// only, no op-log replay) and writing the sidecar.
 pub(crate) fn list_all(fs: &dyn FsHost) -> io::Result<Vec<RegistryEntry>> {
     list_all_in(&stoat_log::workspace_state_dir()?, fs)
@@ -85,7 +84,7 @@ fn list_all_in(workspaces_dir: &Path, fs: &dyn FsHost) -> io::Result<Vec<Registr
         let root_dir = workspaces_dir.join(root_entry.name.as_str());
         for entry in fs.list_dir(&root_dir)? {
             let state_path = root_dir.join(entry.name.as_str());
-            if state_path.extension().and_then(|s| s.to_str()) != Some("ron") {
+            if store::format_of(&state_path).is_none() {
                 continue;
             }
             let Some(meta) = read_or_backfill(&state_path, fs) else {
@@ -131,10 +130,7 @@ fn read_meta(path: &Path, fs: &dyn FsHost) -> Option<WorkspaceMeta> {
 }
 
 fn meta_from_state(state_path: &Path, fs: &dyn FsHost) -> Option<WorkspaceMeta> {
-    let mut buf = Vec::new();
-    fs.read(state_path, &mut buf).ok()?;
-    let body = String::from_utf8(buf).ok()?;
-    let state: WorkspaceStateV1 = ron::from_str(&body).ok()?;
+    let state: WorkspaceStateV1 = fs.load_state(state_path).ok()?;
     Some(WorkspaceMeta {
         uid: state.uid,
         name: state.name,
@@ -0,0 +1,215 @@
+//! Encoding persisted state to disk, in whichever [`StateFormat`] the user
+//! configured.
+//!
+//! A state file's extension names its format (`.ron`, `.json`, or
+//! `.msgpack`), so a file always decodes the way it was written even after
+//! `workspace.state_format` changes. [`StateStore`] writes through any
+//! [`FsHost`] with a tmp-file-plus-rename, so a crash mid-save leaves the
+//! previous file intact rather than a truncated one.
+//!
+//! Every [`Versioned`] type carries a `version` field. Loading reads that
+//! field first and hands a file from an older schema to
+//! [`Versioned::migrate`] instead of decoding it as the current shape. Files
+//! written before the field existed read as version 0, which each type maps
+//! onto whichever schema those files hold.
+
+use crate::host::FsHost;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{io, path::Path};
+pub(crate) use stoat_config::StateFormat;
+
+/// Every format a state file may be in, for scans that must find sessions
+/// saved under an earlier `workspace.state_format`.
+pub(crate) const FORMATS: [StateFormat; 3] = [
+    StateFormat::Ron,
+    StateFormat::Json,
+    StateFormat::MessagePack,
+];
+
+/// The file extension `format` writes under.
+pub(crate) fn extension(format: StateFormat) -> &'static str {
+    match format {
+        StateFormat::Ron => "ron",
+        StateFormat::Json => "json",
+        StateFormat::MessagePack => "msgpack",
+    }
+}
+
+/// The format a state file at `path` is in, by its extension. `None` for
+/// anything else in a state directory, such as `.meta` sidecars and `.tmp`
+/// leftovers.
+pub(crate) fn format_of(path: &Path) -> Option<StateFormat> {
+    let ext = path.extension()?.to_str()?;
+    FORMATS.into_iter().find(|&format| extension(format) == ext)
+}
+
+/// Serialize `value` as `format`. MessagePack writes structs as maps rather
+/// than its default positional arrays, so a `#[serde(default)]` field added
+/// later still reads from older files.
+pub(crate) fn encode<T: Serialize>(format: StateFormat, value: &T) -> io::Result<Vec<u8>> {
+    match format {
+        StateFormat::Ron => ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+            .map(String::into_bytes)
+            .map_err(invalid),
+        StateFormat::Json => serde_json::to_vec_pretty(value).map_err(invalid),
+        StateFormat::MessagePack => rmp_serde::to_vec_named(value).map_err(invalid),
+    }
+}
+
+/// Deserialize a `T` from `body`, which holds `format`.
+pub(crate) fn decode<T: DeserializeOwned>(format: StateFormat, body: &[u8]) -> io::Result<T> {
+    match format {
+        StateFormat::Ron => {
+            let text = std::str::from_utf8(body).map_err(invalid)?;
+            ron::from_str(text).map_err(invalid)
+        },
+        StateFormat::Json => serde_json::from_slice(body).map_err(invalid),
+        StateFormat::MessagePack => rmp_serde::from_slice(body).map_err(invalid),
+    }
+}
+
+fn invalid(err: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+/// A persisted type whose schema is versioned.
+pub(crate) trait Versioned: Serialize + DeserializeOwned {
+    /// The schema version this build writes.
+    const VERSION: u32;
+
+    /// Decode `body`, saved as `format` at the older schema `version`, into
+    /// the current shape. The default refuses, which is right until a type's
+    /// first breaking change; after it, decode the old shape here and convert.
+    fn migrate(version: u32, format: StateFormat, body: &[u8]) -> io::Result<Self> {
+        let _ = (format, body);
+        Err(invalid(format!(
+            "no migration from state version {version} to {}",
+            Self::VERSION
+        )))
+    }
+}
+
+/// The one field every [`Versioned`] file shares, read before the rest so
+/// the loader knows which shape to decode.
+#[derive(Deserialize)]
+struct Header {
+    #[serde(default)]
+    version: u32,
+}
+
+/// Saving and loading [`Versioned`] state through a filesystem.
+pub(crate) trait StateStore {
+    /// Write `value` to `path` in the format its extension names, creating
+    /// the parent directory if missing.
+    fn save_state<T: Versioned>(&self, path: &Path, value: &T) -> io::Result<()>;
+
+    /// Read the `T` at `path`, migrating it if an older schema wrote it.
+    fn load_state<T: Versioned>(&self, path: &Path) -> io::Result<T>;
+}
+
+impl<F: FsHost + ?Sized> StateStore for F {
+    fn save_state<T: Versioned>(&self, path: &Path, value: &T) -> io::Result<()> {
+        let format = format_of(path)
+            .ok_or_else(|| invalid(format!("{} is not a state file", path.display())))?;
+        if let Some(parent) = path.parent() {
+            self.create_dir_all(parent)?;
+        }
+        let body = encode(format, value)?;
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = Path::new(&tmp);
+        self.write(tmp, &body)?;
+        self.rename(tmp, path)
+    }
+
+    fn load_state<T: Versioned>(&self, path: &Path) -> io::Result<T> {
+        let format = format_of(path)
+            .ok_or_else(|| invalid(format!("{} is not a state file", path.display())))?;
+        let mut body = Vec::new();
+        self.read(path, &mut body)?;
+        let Header { version } = decode(format, &body)?;
+        match version {
+            0 => decode(format, &body),
+            v if v == T::VERSION => decode(format, &body),
+            v if v > T::VERSION => Err(invalid(format!(
+                "state version {v} is newer than this build reads ({})",
+                T::VERSION
+            ))),
+            v => T::migrate(v, format, &body),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::FakeFs;
+    use std::path::PathBuf;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Note {
+        #[serde(default)]
+        version: u32,
+        text: String,
+    }
+
+    /// [`Note`] before `body` was renamed to `text`.
+    #[derive(Serialize, Deserialize)]
+    struct NoteV1 {
+        version: u32,
+        body: String,
+    }
+
+    impl Versioned for Note {
+        const VERSION: u32 = 2;
+
+        fn migrate(version: u32, format: StateFormat, body: &[u8]) -> io::Result<Self> {
+            assert_eq!(version, 1);
+            let old: NoteV1 = decode(format, body)?;
+            Ok(Note {
+                version: 2,
+                text: old.body,
+            })
+        }
+    }
+
+    #[test]
+    fn every_format_round_trips_by_extension() {
+        let fake = FakeFs::new();
+        let note = Note {
+            version: 2,
+            text: "hello".into(),
+        };
+        for format in FORMATS {
+            let path = PathBuf::from(format!("/state/note.{}", extension(format)));
+            assert_eq!(format_of(&path), Some(format));
+            fake.save_state(&path, &note).unwrap();
+            assert_eq!(fake.load_state::<Note>(&path).unwrap(), note);
+            assert!(!fake.exists(&path.with_extension(format!("{}.tmp", extension(format)))));
+        }
+        assert_eq!(format_of(Path::new("/state/note.meta")), None);
+    }
+
+    #[test]
+    fn older_versions_migrate_and_newer_ones_are_refused() {
+        let fake = FakeFs::new();
+        for format in FORMATS {
+            let ext = extension(format);
+            let old = PathBuf::from(format!("/state/old.{ext}"));
+            let v1 = NoteV1 {
+                version: 1,
+                body: "kept".into(),
+            };
+            fake.write(&old, &encode(format, &v1).unwrap()).unwrap();
+            assert_eq!(fake.load_state::<Note>(&old).unwrap().text, "kept");
+
+            let new = PathBuf::from(format!("/state/new.{ext}"));
+            let v3 = Note {
+                version: 3,
+                text: "from the future".into(),
+            };
+            fake.write(&new, &encode(format, &v3).unwrap()).unwrap();
+            assert!(fake.load_state::<Note>(&new).is_err());
+        }
+    }
+}
@@ -2,9 +2,11 @@ mod anchor;
 mod buffer_id;
 mod fragment;
 mod indent;
+mod line_ending;
 mod locator;
 mod movement;
 mod offset_utf16;
+mod offset_utf32;
 pub mod patch;
 mod point;
 mod rope;
@@ -19,6 +21,7 @@ pub use fragment::{
     Fragment, FragmentSummary, FragmentTextSummary, InsertionFragment, InsertionFragmentKey,
 };
 pub use indent::{detect_indent_style, IndentStyle};
+pub use line_ending::{normalize_line_endings, LineEnding};
 pub use locator::Locator;
 pub use movement::{
     categorize_char, find_decimal_number_at, find_decimal_number_seeking, find_number_at,
@@ -28,6 +31,7 @@ pub use movement::{
     CharCategory, NumberKind, NumberMatch,
 };
 pub use offset_utf16::OffsetUtf16;
+pub use offset_utf32::OffsetUtf32;
 pub use point::{Point, PointUtf16};
 pub use rope::{
     BytesInRange, CharsAt, ChunksInLine, ChunksInRange, FindIter, Lines, ReversedCharsAt, Rope,
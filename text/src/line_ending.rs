@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// The line terminator a file is stored with.
+///
+/// Buffers of a `Unix` or `Windows` file hold `\n` internally. The line ending
+/// is detected from the loaded text, stripped by [`LineEnding::normalize`], and
+/// written back by [`LineEnding::apply`] on save, so editing code only ever
+/// sees `\n`. A `Mixed` file is held byte for byte instead, so saving an
+/// untouched one rewrites nothing. The [`Default`] is `Unix`, the choice for
+/// new files and files without a newline.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum LineEnding {
+    #[default]
+    Unix,
+    Windows,
+    /// Both `\n` and `\r\n` breaks occur, so no single terminator can be
+    /// applied on save without rewriting some of the file's lines.
+    Mixed,
+}
+
+impl LineEnding {
+    /// Detect the line ending of `text` from all of its line breaks, falling
+    /// back to the default when the text has none.
+    pub fn detect(text: &str) -> Self {
+        let mut crlf = false;
+        let mut lf = false;
+        for (idx, _) in text.match_indices('\n') {
+            if text[..idx].ends_with('\r') {
+                crlf = true;
+            } else {
+                lf = true;
+            }
+            if crlf && lf {
+                return LineEnding::Mixed;
+            }
+        }
+        if crlf {
+            LineEnding::Windows
+        } else {
+            LineEnding::default()
+        }
+    }
+
+    /// Short status-bar label, `LF`, `CRLF`, or `Mixed`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineEnding::Unix => "LF",
+            LineEnding::Windows => "CRLF",
+            LineEnding::Mixed => "Mixed",
+        }
+    }
+
+    /// Bring `text` to the form a buffer with this line ending holds: `\r\n`
+    /// collapsed to `\n`, or untouched for `Mixed`.
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self {
+            LineEnding::Mixed => Cow::Borrowed(text),
+            LineEnding::Unix | LineEnding::Windows => normalize_line_endings(text),
+        }
+    }
+
+    /// Rewrite buffer `text` with this line ending, for writing to disk.
+    /// Borrows when the line ending is `Unix` or `Mixed`.
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self {
+            LineEnding::Unix | LineEnding::Mixed => Cow::Borrowed(text),
+            LineEnding::Windows => Cow::Owned(text.replace('\n', "\r\n")),
+        }
+    }
+}
+
+/// Replace every `\r\n` in `text` with `\n`, leaving a lone `\r` as the
+/// literal character it is. Borrows when the text has no `\r\n`, the common
+/// case.
+pub fn normalize_line_endings(text: &str) -> Cow<'_, str> {
+    if !text.contains("\r\n") {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.replace("\r\n", "\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_line_endings, LineEnding};
+
+    #[test]
+    fn detects_from_every_break() {
+        assert_eq!(LineEnding::detect("a\r\nb\r\n"), LineEnding::Windows);
+        assert_eq!(LineEnding::detect("a\nb\n"), LineEnding::Unix);
+        assert_eq!(LineEnding::detect("a\r\nb\n"), LineEnding::Mixed);
+        assert_eq!(LineEnding::detect("a\nb\r\n"), LineEnding::Mixed);
+        assert_eq!(LineEnding::detect("a\rb\n"), LineEnding::Unix);
+        assert_eq!(LineEnding::detect("no break"), LineEnding::Unix);
+    }
+
+    #[test]
+    fn normalize_collapses_only_crlf() {
+        assert_eq!(normalize_line_endings("a\r\nb\rc\n"), "a\nb\rc\n");
+        assert!(matches!(
+            normalize_line_endings("a\nb"),
+            std::borrow::Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn apply_round_trips() {
+        let disk = "one\r\ntwo\r\n";
+        let normalized = normalize_line_endings(disk);
+        assert_eq!(LineEnding::detect(disk).apply(&normalized), disk);
+        assert_eq!(LineEnding::Unix.apply("x\n"), "x\n");
+    }
+
+    #[test]
+    fn mixed_round_trips_untouched() {
+        let disk = "one\r\ntwo\nthree\rfour\r\n";
+        let line_ending = LineEnding::detect(disk);
+        assert_eq!(line_ending, LineEnding::Mixed);
+        let held = line_ending.normalize(disk);
+        assert_eq!(held, disk, "a mixed file is held byte for byte");
+        assert_eq!(line_ending.apply(&held), disk);
+    }
+
+    #[test]
+    fn literal_carriage_return_survives_a_round_trip() {
+        let disk = "progress\r50%\r\ndone\r\n";
+        let line_ending = LineEnding::detect(disk);
+        assert_eq!(line_ending, LineEnding::Windows);
+        let held = line_ending.normalize(disk);
+        assert_eq!(held, "progress\r50%\ndone\n");
+        assert_eq!(line_ending.apply(&held), disk);
+    }
+}
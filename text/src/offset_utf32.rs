@@ -0,0 +1,31 @@
+use crate::{rope::TextSummary, Dimension};
+use std::ops::{Add, AddAssign};
+
+/// An offset counted in Unicode code points, the unit of LSP's `utf-32`
+/// position encoding.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OffsetUtf32(pub usize);
+
+impl Add for OffsetUtf32 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        OffsetUtf32(self.0 + other.0)
+    }
+}
+
+impl AddAssign for OffsetUtf32 {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl<'a> Dimension<'a, TextSummary> for OffsetUtf32 {
+    fn zero(_cx: ()) -> Self {
+        Self::default()
+    }
+
+    fn add_summary(&mut self, summary: &'a TextSummary, _cx: ()) {
+        self.0 += summary.chars;
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     sum_tree::{self, ContextLessSummary, Dimension},
-    Bias, Dimensions, Item, OffsetUtf16, Point, PointUtf16, SumTree,
+    Bias, Dimensions, Item, OffsetUtf16, OffsetUtf32, Point, PointUtf16, SumTree,
 };
 use arrayvec::ArrayString;
 use std::{cmp, ops::Range};
@@ -786,14 +786,25 @@ impl Rope {
     }
 
     pub fn slice_rows(&self, range: Range<u32>) -> Rope {
-        let start = self.point_to_offset(Point::new(range.start, 0));
-        let end = if range.end > self.max_point().row {
-            self.len()
-        } else {
-            self.point_to_offset(Point::new(range.end, 0))
+        let range = self.rows_byte_range(range);
+        let mut cursor = self.cursor(range.start);
+        cursor.slice(range.end)
+    }
+
+    /// Byte span of the rows in `rows`, from the start of the first through
+    /// the newline ending the last. Rows past the end clamp to the rope's
+    /// length.
+    pub fn rows_byte_range(&self, rows: Range<u32>) -> Range<usize> {
+        let max_row = self.max_point().row;
+        let offset = |row: u32| {
+            if row > max_row {
+                self.len()
+            } else {
+                self.point_to_offset(Point::new(row, 0))
+            }
         };
-        let mut cursor = self.cursor(start);
-        cursor.slice(end)
+        let start = offset(rows.start);
+        start..offset(rows.end).max(start)
     }
 
     pub fn point_to_point_utf16(&self, target: Point) -> PointUtf16 {
@@ -914,6 +925,44 @@ impl Rope {
         chunk_start_offset + byte_offset
     }
 
+    pub fn offset_to_offset_utf32(&self, offset: usize) -> OffsetUtf32 {
+        let (start, _end, chunk_opt) =
+            self.chunks
+                .find::<Dimensions<usize, OffsetUtf32>, _>((), &offset, Bias::Right);
+        let Dimensions(chunk_start_offset, chunk_start_utf32, ()) = start;
+
+        let chunk = match chunk_opt {
+            Some(c) => c,
+            None => return OffsetUtf32(self.chunks.summary().chars),
+        };
+
+        let remaining = offset - chunk_start_offset;
+        OffsetUtf32(chunk_start_utf32.0 + chunk.text.as_str()[..remaining].chars().count())
+    }
+
+    pub fn offset_utf32_to_offset(&self, target: OffsetUtf32) -> usize {
+        let (start, _end, chunk_opt) =
+            self.chunks
+                .find::<Dimensions<OffsetUtf32, usize>, _>((), &target, Bias::Right);
+        let Dimensions(chunk_start_utf32, chunk_start_offset, ()) = start;
+
+        let chunk = match chunk_opt {
+            Some(c) => c,
+            None => return self.len(),
+        };
+
+        let remaining = target.0 - chunk_start_utf32.0;
+        let byte_offset: usize = chunk
+            .text
+            .as_str()
+            .chars()
+            .take(remaining)
+            .map(char::len_utf8)
+            .sum();
+
+        chunk_start_offset + byte_offset
+    }
+
     pub fn clip_point_utf16(&self, point: PointUtf16, bias: Bias) -> PointUtf16 {
         let p = self.point_utf16_to_point(point);
         let clipped = self.clip_point(p, bias);
@@ -1869,6 +1918,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rows_byte_range_spans_whole_lines() {
+        let rope = Rope::from("ab\ncd\nef");
+        assert_eq!(rope.rows_byte_range(0..1), 0..3);
+        assert_eq!(rope.rows_byte_range(1..3), 3..8);
+        assert_eq!(rope.rows_byte_range(2..9), 6..8);
+        assert_eq!(rope.rows_byte_range(5..9), 8..8);
+    }
+
     #[test]
     fn chars_at_start() {
         let rope = Rope::from("hello");
@@ -2137,6 +2195,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn offset_utf32_counts_code_points() {
+        let text = "a\u{10000}b\nc\u{00e9}";
+        let rope = Rope::from(text);
+        for (chars, (offset, _)) in text.char_indices().enumerate() {
+            assert_eq!(rope.offset_to_offset_utf32(offset), OffsetUtf32(chars));
+            assert_eq!(rope.offset_utf32_to_offset(OffsetUtf32(chars)), offset);
+        }
+        let total = text.chars().count();
+        assert_eq!(rope.offset_to_offset_utf32(rope.len()), OffsetUtf32(total));
+        assert_eq!(rope.offset_utf32_to_offset(OffsetUtf32(total)), rope.len());
+    }
+
     #[test]
     fn text_summary_lines_utf16_ascii() {
         let s = TextSummary::from_str("abc\ndef");
@@ -37,11 +37,14 @@ use std::{
 };
 use stoat_cli::CommonArgs;
 use stoatty_protocol::{
-    command::{PoolRegionCommand, WindowOpenCommand, NON_PANE_POOL_BASE},
+    command::{
+        CursorAnimation as ProtoCursorAnimation, PoolRegionCommand, WindowOpenCommand,
+        NON_PANE_POOL_BASE,
+    },
     window_ipc::{MouseButton as IpcMouseButton, MouseKind, WindowIpcEvent},
 };
 use stoatty_render::{
-    gpu::{FontConfig, FontLoad, Frame, GpuContext, PoolComposite, Scroll},
+    gpu::{FontConfig, FontFallback, FontLoad, Frame, GpuContext, PoolComposite, Scroll},
     render,
 };
 use stoatty_term::{
@@ -197,6 +200,7 @@ fn run_with_config(
         FontSettings {
             size: config.font_size,
             family: config.font_family,
+            fallback: config.font_fallback.to_render(),
             ligatures: config.ligatures,
         },
         config.cursor_animation,
@@ -250,6 +254,7 @@ enum PtyEvent {
 struct FontSettings {
     size: u32,
     family: Vec<String>,
+    fallback: FontFallback,
     ligatures: bool,
 }
 
@@ -279,6 +284,9 @@ struct App {
     /// Ordered font-family cascade from the config, resolved against the font db
     /// at renderer creation to pick the shaping primary. Read once in `resumed`.
     font_family: Vec<String>,
+    /// Fallback families for glyphs the primary lacks, read once in `resumed`
+    /// like [`Self::font_family`].
+    font_fallback: FontFallback,
     /// Whether the renderer shapes cell runs together so ligatures form. Read
     /// once in `resumed` into the renderer's [`FontConfig`].
     ligatures: bool,
@@ -318,6 +326,7 @@ impl App {
             theme_name,
             font_size: font.size,
             font_family: font.family,
+            font_fallback: font.fallback,
             ligatures: font.ligatures,
             cursor_animation,
             size,
@@ -378,6 +387,7 @@ struct AuxWindowConfig<'a> {
     proxy: &'a EventLoopProxy<PtyEvent>,
     theme: Theme,
     font_family: &'a [String],
+    font_fallback: &'a FontFallback,
     ligatures: bool,
 }
 
@@ -430,8 +440,9 @@ struct State {
     /// toward the terminal's actual cursor cell each frame. Drives the
     /// [`CursorAnimation::Block`] motion.
     cursor_anim: [f32; 2],
-    /// Cursor motion style, copied from [`App`] at construction. Selects which
-    /// animation [`Self::step_cursor`] advances each frame.
+    /// Cursor motion style, copied from [`App`] at construction and replaced by
+    /// a program's [`TermEvent::CursorAnimation`]. Selects which animation
+    /// [`Self::step_cursor`] advances each frame.
     cursor_animation: CursorAnimation,
     /// The warp cursor's four animated corners [TL, TR, BL, BR] in fractional
     /// cell coordinates, eased independently toward the target cell's block so
@@ -781,6 +792,7 @@ impl ApplicationHandler<PtyEvent> for App {
                 size: self.font_size,
                 scale_factor: scale_factor as f32,
                 family: &self.font_family,
+                fallback: &self.font_fallback,
                 ligatures: self.ligatures,
             },
             self.theme.background,
@@ -944,6 +956,7 @@ impl ApplicationHandler<PtyEvent> for App {
                         &mut self.theme,
                         &mut self.theme_name,
                         &mut self.font_family,
+                        &mut self.font_fallback,
                         &mut self.ligatures,
                         &mut self.cursor_animation,
                     );
@@ -955,6 +968,7 @@ impl ApplicationHandler<PtyEvent> for App {
                         proxy: &self.proxy,
                         theme: self.theme,
                         font_family: &self.font_family,
+                        font_fallback: &self.font_fallback,
                         ligatures: self.ligatures,
                     },
                     events,
@@ -1036,6 +1050,7 @@ impl ApplicationHandler<PtyEvent> for App {
                         proxy: &self.proxy,
                         theme: self.theme,
                         font_family: &self.font_family,
+                        font_fallback: &self.font_fallback,
                         ligatures: self.ligatures,
                     },
                     events,
@@ -2296,9 +2311,9 @@ fn sgr_motion_bytes(button: Option<u8>, col: usize, row: usize) -> Vec<u8> {
 /// Re-read the config file and apply what a running window can change.
 ///
 /// Theme, font size, and cursor animation take effect on the next frame. Font
-/// family and ligatures are baked into the primary window's text pass when it is
-/// built, so a change to either only reaches windows opened afterward and is
-/// logged rather than silently dropped.
+/// family, fallback, and ligatures are baked into the primary window's text pass
+/// when it is built, so a change to any of them only reaches windows opened
+/// afterward and is logged rather than silently dropped.
 ///
 /// A config that fails to load leaves everything running as it was. Startup
 /// falls back to the embedded default, which is right when there is nothing to
@@ -2309,6 +2324,7 @@ fn apply_config_reload(
     theme: &mut Theme,
     theme_name: &mut String,
     font_family: &mut Vec<String>,
+    font_fallback: &mut FontFallback,
     ligatures: &mut bool,
     cursor_animation: &mut CursorAnimation,
 ) {
@@ -2342,9 +2358,16 @@ fn apply_config_reload(
     state.cursor_animation = config.cursor_animation;
     *cursor_animation = config.cursor_animation;
 
-    if *font_family != config.font_family || *ligatures != config.ligatures {
-        tracing::info!("font family and ligature changes apply to this window on next launch");
+    let fallback = config.font_fallback.to_render();
+    if *font_family != config.font_family
+        || *font_fallback != fallback
+        || *ligatures != config.ligatures
+    {
+        tracing::info!(
+            "font family, fallback, and ligature changes apply to this window on next launch"
+        );
         font_family.clone_from(&config.font_family);
+        *font_fallback = fallback;
         *ligatures = config.ligatures;
     }
 
@@ -2383,6 +2406,17 @@ fn handle_term_events(
                 version = %hello.version,
                 "program hello"
             ),
+            // No platform accessibility bridge is linked yet, so the
+            // announcement is recorded where one would read it.
+            TermEvent::Accessibility(a11y) => tracing::debug!(
+                target: "stoatty::a11y",
+                line = a11y.line + 1,
+                column = a11y.column + 1,
+                mode = %a11y.mode,
+                modal = a11y.modal.as_deref().unwrap_or_default(),
+                "{}",
+                a11y.line_text
+            ),
             TermEvent::WindowOpen(cmd) => open_aux_window(state, event_loop, config, cmd),
             TermEvent::WindowClose(window) => state.aux.retain(|aux| aux.id != window),
             TermEvent::WindowFocus(window) => {
@@ -2395,6 +2429,14 @@ fn handle_term_events(
                     aux.window.request_redraw();
                 }
             },
+            // Only the primary window's cursor follows the program's choice,
+            // and only until the next config reload restores the configured one.
+            TermEvent::CursorAnimation(animation) => {
+                state.cursor_animation = match animation {
+                    ProtoCursorAnimation::Block => CursorAnimation::Block,
+                    ProtoCursorAnimation::Warp => CursorAnimation::Warp,
+                };
+            },
             // Filtered out before this fan-out, since re-applying a config
             // touches window state this function has no handle on.
             TermEvent::ConfigReload => {},
@@ -2458,6 +2500,7 @@ fn open_aux_window(
     let proxy = config.proxy.clone();
     let theme = config.theme;
     let font_family = config.font_family.to_vec();
+    let font_fallback = config.font_fallback.clone();
     let ligatures = config.ligatures;
     let spawn = std::thread::Builder::new()
         .name(format!("aux-gpu-{window_id}"))
@@ -2471,6 +2514,7 @@ fn open_aux_window(
                     size: font_size,
                     scale_factor,
                     family: &font_family,
+                    fallback: &font_fallback,
                     ligatures,
                 },
                 theme.background,
@@ -9,6 +9,7 @@ use etcetera::{base_strategy::Xdg, BaseStrategy};
 use serde::{de::Error as _, Deserialize, Deserializer};
 use snafu::{ResultExt, Snafu};
 use std::{collections::BTreeMap, io, path::PathBuf};
+use stoatty_render::gpu::FontFallback;
 use stoatty_term::{grid::Rgb, theme::Theme};
 use vscode_theme::{Rgba, VsCodeTheme};
 
@@ -59,6 +60,11 @@ pub struct Config {
     #[serde(default)]
     pub font_family: Vec<String>,
 
+    /// Families tried for a glyph the [`font_family`](Self::font_family)
+    /// primary lacks, such as CJK or emoji, before the system's own fallback.
+    #[serde(default)]
+    pub font_fallback: FontFallbackConfig,
+
     /// Name of the [`themes`](Self::themes) entry colors resolve against.
     #[serde(default)]
     pub theme: String,
@@ -227,6 +233,33 @@ fn default_cursor_animation() -> CursorAnimation {
     CursorAnimation::Block
 }
 
+/// The `[font_fallback]` table: ordered family lists for glyphs the primary
+/// font lacks.
+///
+/// A character's script list (`cjk`, `emoji`, or `symbols`) is tried before
+/// `families`, and families not installed are skipped. Every list defaults to
+/// empty, leaving the bundled symbols font and the system fallback.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct FontFallbackConfig {
+    pub families: Vec<String>,
+    pub cjk: Vec<String>,
+    pub emoji: Vec<String>,
+    pub symbols: Vec<String>,
+}
+
+impl FontFallbackConfig {
+    /// The renderer's form of the fallback chain.
+    pub fn to_render(&self) -> FontFallback {
+        FontFallback {
+            families: self.families.clone(),
+            cjk: self.cjk.clone(),
+            emoji: self.emoji.clone(),
+            symbols: self.symbols.clone(),
+        }
+    }
+}
+
 /// A program to launch over the PTY instead of the default shell, with its
 /// arguments.
 ///
@@ -496,7 +529,7 @@ fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
 mod tests {
     use super::{
         builtin_vscode_themes, embedded_default, merge_tables, parse_vscode_themes, settle, Config,
-        CursorAnimation, ShellConfig, DEFAULT_CONFIG,
+        CursorAnimation, FontFallbackConfig, ShellConfig, DEFAULT_CONFIG,
     };
     use stoatty_term::{grid::Rgb, theme::Theme};
 
@@ -546,6 +579,25 @@ mod tests {
         assert_eq!(settle(DEFAULT_CONFIG, None).unwrap().shell, None);
     }
 
+    #[test]
+    fn font_fallback_defaults_empty_and_user_sets_script_lists() {
+        assert_eq!(
+            settle(DEFAULT_CONFIG, None).unwrap().font_fallback,
+            FontFallbackConfig::default()
+        );
+        let config = settle(
+            DEFAULT_CONFIG,
+            Some(
+                "[font_fallback]\ncjk = [\"Noto Sans CJK JP\"]\nfamilies = [\"Noto Sans Mono\"]\n",
+            ),
+        )
+        .unwrap();
+        let fallback = config.font_fallback.to_render();
+        assert_eq!(fallback.cjk, ["Noto Sans CJK JP"]);
+        assert_eq!(fallback.families, ["Noto Sans Mono"]);
+        assert!(fallback.emoji.is_empty());
+    }
+
     #[test]
     fn ligatures_default_on_and_user_can_disable() {
         assert!(
@@ -114,12 +114,46 @@ pub enum Command {
     /// re-apply it. Carries no payload: the terminal reads the file itself, and
     /// the program that sends this is only reporting that it wrote it.
     ConfigReload,
+    /// Override how the terminal animates the cursor between cells, in place of
+    /// its config's `cursor_animation`, until the config is reloaded.
+    CursorAnimation(CursorAnimation),
     /// A handshake the program sends to identify itself to the terminal, so the
     /// terminal's log records which process drives it. The terminal replies with
     /// its own [`IdentReply`].
     Hello(HelloCommand),
+    /// The editor state assistive technology should announce: the cursor's
+    /// line and column, the line's text, the mode, and any open modal. Sent
+    /// when any of them changes, so each frame is one announcement.
+    Accessibility(AccessibilityCommand),
 }
 
+/// The payload of [`Command::CursorAnimation`]: the cursor motion to use.
+/// [`Self::Block`] slides a rigid block between cells. [`Self::Warp`]
+/// stretches it along the path, then snaps it back to a block.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CursorAnimation {
+    Block,
+    Warp,
+}
+
+/// The payload of [`Command::Accessibility`].
+///
+/// `line` and `column` are zero-based, the column counted in characters.
+/// `line_text` is capped by the sender at [`MAX_ACCESSIBLE_LINE_BYTES`], so a
+/// minified file's line cannot overrun the frame cap.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AccessibilityCommand {
+    pub line: u32,
+    pub column: u32,
+    pub mode: String,
+    /// The open modal's name, [`None`] when the editor has focus.
+    pub modal: Option<String>,
+    pub line_text: String,
+}
+
+/// Most bytes of line text an [`AccessibilityCommand`] carries.
+pub const MAX_ACCESSIBLE_LINE_BYTES: usize = 4096;
+
 /// The payload of [`Command::Hello`]: a program's self-identification.
 ///
 /// Sent by a program (the stoat editor) to the terminal (stoatty) so the
@@ -1227,6 +1261,26 @@ pub fn encode_config_reload_into(out: &mut Vec<u8>) {
     frame::end(out);
 }
 
+/// Encode a [`Command::CursorAnimation`] as a full `Gstoatty;cursor_animation`
+/// frame for an emitter.
+pub fn encode_cursor_animation(animation: CursorAnimation) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_cursor_animation_into(&mut out, animation);
+    out
+}
+
+/// Append a `Gstoatty;cursor_animation` frame to `out`, carrying the motion as
+/// one byte: 0 for block, 1 for warp.
+pub fn encode_cursor_animation_into(out: &mut Vec<u8>, animation: CursorAnimation) {
+    let byte = match animation {
+        CursorAnimation::Block => 0u8,
+        CursorAnimation::Warp => 1,
+    };
+    frame::begin(out, "cursor_animation");
+    frame::push_arg(out, |w| w.write_all(&[byte]));
+    frame::end(out);
+}
+
 /// Encode a [`HelloCommand`] as a full `Gstoatty;hello` frame for an emitter.
 pub fn encode_hello(command: &HelloCommand) -> Vec<u8> {
     let mut out = Vec::new();
@@ -1247,6 +1301,45 @@ pub fn encode_hello_into(out: &mut Vec<u8>, command: &HelloCommand) {
     frame::end(out);
 }
 
+/// Encode an [`AccessibilityCommand`] as a full `Gstoatty;accessibility`
+/// frame for an emitter.
+pub fn encode_accessibility(command: &AccessibilityCommand) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_accessibility_into(&mut out, command);
+    out
+}
+
+/// Append a `Gstoatty;accessibility` frame for `command` to `out`. An absent
+/// modal rides as an empty argument.
+pub fn encode_accessibility_into(out: &mut Vec<u8>, command: &AccessibilityCommand) {
+    frame::begin(out, "accessibility");
+    frame::push_arg(out, |w| {
+        w.write_all(&command.line.to_be_bytes())?;
+        w.write_all(&command.column.to_be_bytes())
+    });
+    frame::push_arg(out, |w| w.write_all(command.mode.as_bytes()));
+    frame::push_arg(out, |w| {
+        w.write_all(command.modal.as_deref().unwrap_or_default().as_bytes())
+    });
+    frame::push_arg(out, |w| w.write_all(command.line_text.as_bytes()));
+    frame::end(out);
+}
+
+fn decode_accessibility(args: &[Vec<u8>]) -> Option<AccessibilityCommand> {
+    let [head, mode, modal, line_text] = args else {
+        return None;
+    };
+    let head: &[u8; 8] = head.as_slice().try_into().ok()?;
+    let modal = String::from_utf8(modal.clone()).ok()?;
+    Some(AccessibilityCommand {
+        line: u32::from_be_bytes([head[0], head[1], head[2], head[3]]),
+        column: u32::from_be_bytes([head[4], head[5], head[6], head[7]]),
+        mode: String::from_utf8(mode.clone()).ok()?,
+        modal: (!modal.is_empty()).then_some(modal),
+        line_text: String::from_utf8(line_text.clone()).ok()?,
+    })
+}
+
 fn decode_hello(args: &[Vec<u8>]) -> Option<HelloCommand> {
     let [pid, log_id, hostname, version] = args else {
         return None;
@@ -1348,6 +1441,7 @@ pub fn encode_into(out: &mut Vec<u8>, command: &Command) {
         Command::WindowFocus(c) => encode_window_focus_into(out, c),
         Command::Reset => encode_reset_into(out),
         Command::ConfigReload => encode_config_reload_into(out),
+        Command::CursorAnimation(animation) => encode_cursor_animation_into(out, *animation),
         Command::Hello(c) => encode_hello_into(out, c),
     }
 }
@@ -1385,7 +1479,9 @@ fn dispatch(sub: &str, args: &[Vec<u8>]) -> Option<Command> {
         "window_focus" => decode_window_focus(args).map(Command::WindowFocus),
         "reset" => Some(Command::Reset),
         "config_reload" => Some(Command::ConfigReload),
+        "cursor_animation" => decode_cursor_animation(args).map(Command::CursorAnimation),
         "hello" => decode_hello(args).map(Command::Hello),
+        "accessibility" => decode_accessibility(args).map(Command::Accessibility),
         _ => None,
     }
 }
@@ -1634,6 +1730,14 @@ fn decode_reposition(args: &[Vec<u8>]) -> Option<RepositionCommand> {
     })
 }
 
+fn decode_cursor_animation(args: &[Vec<u8>]) -> Option<CursorAnimation> {
+    match args.first()?.as_slice() {
+        [0] => Some(CursorAnimation::Block),
+        [1] => Some(CursorAnimation::Warp),
+        _ => None,
+    }
+}
+
 fn decode_pool_drop(args: &[Vec<u8>]) -> Option<PoolDropCommand> {
     let arg: &[u8; 4] = args.first()?.as_slice().try_into().ok()?;
 
@@ -1789,18 +1893,18 @@ fn icon_kind_code(kind: IconKind) -> u8 {
 mod tests {
     use super::{
         decode, decode_ident_reply, decode_shadow, encode_bar, encode_border, encode_config_reload,
-        encode_fill, encode_fill_end, encode_hello, encode_icon, encode_ident_reply, encode_into,
-        encode_line_layout, encode_minimap, encode_minimap_drop, encode_minimap_lines,
-        encode_minimap_view, encode_panel, encode_pool_cursor, encode_pool_drop,
-        encode_pool_region, encode_popover, encode_popover_end, encode_reposition, encode_reset,
-        encode_scale, encode_scroll, encode_scroll_region, encode_text_run_end,
-        encode_window_close, encode_window_focus, encode_window_open, BarCommand, BorderCommand,
-        BorderStyle, Command, FillCommand, HelloCommand, IconCommand, IconKind, IdentReply,
-        LineLayoutCommand, MinimapCommand, MinimapDropCommand, MinimapLinesCommand, MinimapRun,
-        MinimapViewCommand, PanelCommand, PanelShadow, PoolCursorCommand, PoolDropCommand,
-        PoolRegionCommand, PopoverCommand, RepositionCommand, ScaleCommand, ScrollCommand,
-        ScrollRegionCommand, TextRunCommand, WindowCloseCommand, WindowFocusCommand,
-        WindowOpenCommand,
+        encode_cursor_animation, encode_fill, encode_fill_end, encode_hello, encode_icon,
+        encode_ident_reply, encode_into, encode_line_layout, encode_minimap, encode_minimap_drop,
+        encode_minimap_lines, encode_minimap_view, encode_panel, encode_pool_cursor,
+        encode_pool_drop, encode_pool_region, encode_popover, encode_popover_end,
+        encode_reposition, encode_reset, encode_scale, encode_scroll, encode_scroll_region,
+        encode_text_run_end, encode_window_close, encode_window_focus, encode_window_open,
+        BarCommand, BorderCommand, BorderStyle, Command, CursorAnimation, FillCommand,
+        HelloCommand, IconCommand, IconKind, IdentReply, LineLayoutCommand, MinimapCommand,
+        MinimapDropCommand, MinimapLinesCommand, MinimapRun, MinimapViewCommand, PanelCommand,
+        PanelShadow, PoolCursorCommand, PoolDropCommand, PoolRegionCommand, PopoverCommand,
+        RepositionCommand, ScaleCommand, ScrollCommand, ScrollRegionCommand, TextRunCommand,
+        WindowCloseCommand, WindowFocusCommand, WindowOpenCommand,
     };
 
     #[test]
@@ -1834,6 +1938,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accessibility_round_trips_with_and_without_a_modal() {
+        let mut command = AccessibilityCommand {
+            line: 41,
+            column: 7,
+            mode: "insert".to_string(),
+            modal: None,
+            line_text: "    let café = 1;".to_string(),
+        };
+        assert_eq!(
+            decode(&encode_accessibility(&command)),
+            Some(Command::Accessibility(command.clone()))
+        );
+        command.modal = Some("file finder".to_string());
+        assert_eq!(
+            decode(&encode_accessibility(&command)),
+            Some(Command::Accessibility(command))
+        );
+    }
+
     #[test]
     fn ident_reply_round_trips() {
         let reply = IdentReply {
@@ -2575,6 +2699,17 @@ mod tests {
         assert_eq!(decode(&encode_config_reload()), Some(Command::ConfigReload));
     }
 
+    #[test]
+    fn cursor_animation_round_trips() {
+        for animation in [CursorAnimation::Block, CursorAnimation::Warp] {
+            assert_eq!(
+                decode(&encode_cursor_animation(animation)),
+                Some(Command::CursorAnimation(animation))
+            );
+        }
+        assert!(decode(b"Gstoatty;cursor_animation;Ag==").is_none());
+    }
+
     #[test]
     fn ignores_unknown_subcommand() {
         assert!(decode(b"Gstoatty;nope").is_none());
@@ -2687,6 +2822,7 @@ mod tests {
             }),
             Command::MinimapDrop(MinimapDropCommand { content_id: 9 }),
             Command::Reset,
+            Command::CursorAnimation(CursorAnimation::Warp),
         ];
 
         for command in commands {
@@ -9,7 +9,10 @@
 //! draws into any texture view, so a frame can target an off-screen texture as
 //! well as the window surface that [`GpuContext`] wraps.
 
-pub use crate::render::{text::build_font_system, Frame, Scroll};
+pub use crate::render::{
+    text::{build_font_system, FontFallback},
+    Frame, Scroll,
+};
 use crate::{
     perf::FrameProfiler,
     render::{
@@ -215,13 +218,14 @@ fn device_descriptor(adapter: &Adapter) -> DeviceDescriptor<'static> {
 /// `size` is the logical font size in points; the physical rasterization size
 /// is `size * scale_factor`, so a given `size` keeps its apparent size across
 /// displays of different density. `family` is an ordered cascade whose first
-/// entry present in the font db becomes the shaping primary, cosmic-text
-/// falling back per glyph for codepoints it lacks.
+/// entry present in the font db becomes the shaping primary. A glyph it lacks
+/// walks `fallback`, then cosmic-text's own per-glyph fallback.
 #[derive(Clone, Copy)]
 pub struct FontConfig<'a> {
     pub size: u32,
     pub scale_factor: f32,
     pub family: &'a [String],
+    pub fallback: &'a FontFallback,
     /// Whether the text pass shapes contiguous same-style cell runs together so
     /// the font's ligatures form. When false, each cell is shaped on its own.
     pub ligatures: bool,
@@ -290,6 +294,7 @@ impl Renderer {
                 metrics,
                 font_system,
                 font.family,
+                font.fallback,
                 font.ligatures,
             ),
             overlay: OverlayPass::new(device, format, metrics),
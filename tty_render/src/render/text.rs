@@ -294,6 +294,9 @@ pub struct TextPass {
     /// through to the fallback font. Fixed for the pass's lifetime, as the
     /// family is.
     primary_font: Option<Arc<Font>>,
+    /// The configured fallback chain, resolved once at construction like
+    /// [`Self::primary_font`], for glyphs the primary lacks.
+    fallback: FallbackFonts,
     /// Whether adjacent same-style cells shape together so the font's ligatures
     /// form across cells. When false, every cell is shaped on its own.
     ligatures: bool,
@@ -368,20 +371,22 @@ impl TextPass {
     /// Build the pipeline targeting `format`, with an empty instance buffer.
     ///
     /// Takes a ready `font_system` (see [`build_font_system`]), resolves
-    /// `font_family` against it to pick the shaping primary, and creates the
-    /// glyph atlas. `format` must be the non-sRGB surface format the text pass
-    /// composites into; the shader does its own sRGB encoding.
+    /// `font_family` against it to pick the shaping primary, resolves the
+    /// `fallback` families, and creates the glyph atlas. `format` must be the non-sRGB surface
+    /// format the text pass composites into; the shader does its own sRGB encoding.
     pub(crate) fn new(
         device: &Device,
         format: TextureFormat,
         metrics: CellMetrics,
         mut font_system: FontSystem,
         font_family: &[String],
+        fallback: &FontFallback,
         ligatures: bool,
     ) -> TextPass {
         let family = resolve_primary_family(&font_system, font_family);
         let baseline = probe_baseline(&mut font_system, metrics, shape_family(&family));
         let primary_font = resolve_primary_font(&mut font_system, &family);
+        let fallback = FallbackFonts::resolve(&mut font_system, fallback);
         let swash_cache = SwashCache::new();
         let atlas = GlyphAtlas::new(device);
 
@@ -633,6 +638,7 @@ impl TextPass {
             font_system,
             family,
             primary_font,
+            fallback,
             ligatures,
             swash_cache,
             shape_cache: FxHashMap::default(),
@@ -2154,6 +2160,7 @@ impl TextPass {
             scale,
             self.metrics,
             shape_family(&self.family),
+            &self.fallback,
             weight,
         );
         self.shape_cache.insert(cache_key, key);
@@ -2485,16 +2492,18 @@ fn create_atlas_bind_group(
 /// size, so each scale of a character keys a distinct atlas entry.
 ///
 /// `primary` is the preferred family; glyphs it lacks are shaped with the
-/// bundled symbols font instead (see [`glyph_family`]).
+/// first `fallback` family carrying them, or the bundled symbols font (see
+/// [`glyph_family`]).
 fn shape_char(
     font_system: &mut FontSystem,
     ch: char,
     scale: f32,
     metrics: CellMetrics,
     primary: Family<'_>,
+    fallback: &FallbackFonts,
     weight: Weight,
 ) -> Option<CacheKey> {
-    let family = glyph_family(font_system, ch, primary);
+    let family = glyph_family(font_system, ch, primary, fallback);
     let size = scale;
     let mut buffer = CosmicBuffer::new(
         font_system,
@@ -2606,16 +2615,116 @@ fn run_text_and_columns_into(
 }
 
 /// The cosmic-text family to shape `ch` with: `primary` when it carries the
-/// glyph, otherwise the bundled symbols font so Private-Use-Area icons resolve
-/// to it ahead of cosmic-text's system fallback.
-fn glyph_family<'a>(font_system: &mut FontSystem, ch: char, primary: Family<'a>) -> Family<'a> {
+/// glyph, then the first configured fallback family that does, otherwise the
+/// bundled symbols font so Private-Use-Area icons resolve to it ahead of
+/// cosmic-text's system fallback.
+fn glyph_family<'a>(
+    font_system: &mut FontSystem,
+    ch: char,
+    primary: Family<'a>,
+    fallback: &'a FallbackFonts,
+) -> Family<'a> {
     if family_covers(font_system, primary, ch) {
         primary
+    } else if let Some(name) = fallback.family_for(ch) {
+        Family::Name(name)
     } else {
         Family::Name(SYMBOLS_FAMILY)
     }
 }
 
+/// The fallback font families tried, in order, for a glyph the primary family
+/// lacks, before the bundled symbols font and cosmic-text's system fallback.
+///
+/// A character's script list is tried ahead of [`Self::families`], so CJK text
+/// can prefer a CJK face while everything else uses a general one. Families
+/// not installed are skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FontFallback {
+    /// Families tried for any character after its script's own list.
+    pub families: Vec<String>,
+    /// Families tried first for Han, kana, Hangul, and fullwidth forms.
+    pub cjk: Vec<String>,
+    /// Families tried first for emoji and pictographs.
+    pub emoji: Vec<String>,
+    /// Families tried first for arrows, math, shapes, and other symbol blocks.
+    pub symbols: Vec<String>,
+}
+
+/// The script class a [`FontFallback`] picks a family list by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackScript {
+    Cjk,
+    Emoji,
+    Symbols,
+    Other,
+}
+
+/// The [`FallbackScript`] `ch` belongs to, by Unicode block.
+pub fn fallback_script(ch: char) -> FallbackScript {
+    match ch as u32 {
+        0x1100..=0x11FF
+        | 0x2E80..=0x2FDF
+        | 0x3000..=0x30FF
+        | 0x3130..=0x318F
+        | 0x31C0..=0x9FFF
+        | 0xA960..=0xA97F
+        | 0xAC00..=0xD7FF
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFFEF
+        | 0x20000..=0x3FFFF => FallbackScript::Cjk,
+        0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B50..=0x2B55 | 0xFE0F => FallbackScript::Emoji,
+        0x2100..=0x25FF | 0x2900..=0x2BFF | 0x1D400..=0x1D7FF => FallbackScript::Symbols,
+        _ => FallbackScript::Other,
+    }
+}
+
+/// A [`FontFallback`] with every installed family resolved to its face, so the
+/// per-glyph walk is charmap lookups rather than font-database queries.
+#[derive(Default)]
+struct FallbackFonts {
+    families: Vec<(String, Arc<Font>)>,
+    cjk: Vec<(String, Arc<Font>)>,
+    emoji: Vec<(String, Arc<Font>)>,
+    symbols: Vec<(String, Arc<Font>)>,
+}
+
+impl FallbackFonts {
+    fn resolve(font_system: &mut FontSystem, config: &FontFallback) -> Self {
+        let mut resolve = |names: &[String]| -> Vec<(String, Arc<Font>)> {
+            names
+                .iter()
+                .filter_map(|name| {
+                    let font = resolve_primary_font(font_system, &Some(name.clone()))?;
+                    Some((name.clone(), font))
+                })
+                .collect()
+        };
+        FallbackFonts {
+            families: resolve(&config.families),
+            cjk: resolve(&config.cjk),
+            emoji: resolve(&config.emoji),
+            symbols: resolve(&config.symbols),
+        }
+    }
+
+    /// The first family carrying `ch`, its script's list before the general one.
+    fn family_for(&self, ch: char) -> Option<&str> {
+        let script: &[(String, Arc<Font>)] = match fallback_script(ch) {
+            FallbackScript::Cjk => &self.cjk,
+            FallbackScript::Emoji => &self.emoji,
+            FallbackScript::Symbols => &self.symbols,
+            FallbackScript::Other => &[],
+        };
+        script
+            .iter()
+            .chain(&self.families)
+            .find(|(_, font)| font_covers(font, ch))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
 /// Whether the face that `family` resolves to in `font_system` has a glyph for
 /// `ch`.
 ///
@@ -2986,9 +3095,10 @@ fn underline_style_flag(style: UnderlineStyle) -> Option<u32> {
 mod tests {
     use super::{
         build_font_system, build_underline_row, cell_glyph_scale, cell_rect_scissor, cursor_cell,
-        fill_cell_box, glyph_family, glyph_origin, grid_build, is_cell_fill, load_bundled_fonts,
-        overlay_content_cells, resolve_primary_family, run_text_and_columns_into, shape_char,
-        shape_family, shape_run, shape_run_cached, text_run_origin, FxHashMap, GlyphSource,
+        fallback_script, fill_cell_box, glyph_family, glyph_origin, grid_build, is_cell_fill,
+        load_bundled_fonts, overlay_content_cells, resolve_primary_family,
+        run_text_and_columns_into, shape_char, shape_family, shape_run, shape_run_cached,
+        text_run_origin, FallbackFonts, FallbackScript, FontFallback, FxHashMap, GlyphSource,
         GridBuild, RectInstance, TextInstance, TextPass, STYLE_DOTTED, SYMBOLS_FAMILY,
     };
     use crate::{
@@ -3250,10 +3360,28 @@ mod tests {
         let metrics = CellMetrics::from_font_size(30, 1.0);
         let family = Family::Name("JetBrains Mono");
 
-        let normal = shape_char(&mut font_system, 'A', 1.0, metrics, family, Weight::NORMAL)
-            .expect("normal glyph shapes");
-        let bold = shape_char(&mut font_system, 'A', 1.0, metrics, family, Weight::BOLD)
-            .expect("bold glyph shapes");
+        let fallback = FallbackFonts::default();
+
+        let normal = shape_char(
+            &mut font_system,
+            'A',
+            1.0,
+            metrics,
+            family,
+            &fallback,
+            Weight::NORMAL,
+        )
+        .expect("normal glyph shapes");
+        let bold = shape_char(
+            &mut font_system,
+            'A',
+            1.0,
+            metrics,
+            family,
+            &fallback,
+            Weight::BOLD,
+        )
+        .expect("bold glyph shapes");
 
         assert_ne!(
             normal, bold,
@@ -3322,18 +3450,53 @@ mod tests {
         load_bundled_fonts(&mut font_system);
         let primary = Family::Name("JetBrains Mono");
 
+        let fallback = FallbackFonts::default();
+
         assert_eq!(
-            glyph_family(&mut font_system, 'A', primary),
+            glyph_family(&mut font_system, 'A', primary, &fallback),
             primary,
             "a glyph the primary family carries shapes with the primary"
         );
         assert_eq!(
-            glyph_family(&mut font_system, '\u{e0b6}', primary),
+            glyph_family(&mut font_system, '\u{e0b6}', primary, &fallback),
             Family::Name(SYMBOLS_FAMILY),
             "a Private-Use-Area powerline glyph the primary lacks routes to the symbols font"
         );
     }
 
+    #[test]
+    fn glyph_family_walks_the_configured_fallback_chain() {
+        let mut font_system = FontSystem::new_with_locale_and_db("en-US".into(), Database::new());
+        load_bundled_fonts(&mut font_system);
+        // The symbols face carries no Latin letters, so `A` must come from the
+        // fallback chain rather than the primary.
+        let primary = Family::Name(SYMBOLS_FAMILY);
+        let fallback = FallbackFonts::resolve(
+            &mut font_system,
+            &FontFallback {
+                families: vec!["Not Installed".to_owned(), "JetBrains Mono".to_owned()],
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(fallback.families.len(), 1, "a missing family is skipped");
+        assert_eq!(
+            glyph_family(&mut font_system, 'A', primary, &fallback),
+            Family::Name("JetBrains Mono"),
+            "a glyph the primary lacks takes the first fallback carrying it"
+        );
+    }
+
+    #[test]
+    fn fallback_script_classifies_by_block() {
+        assert_eq!(fallback_script('漢'), FallbackScript::Cjk);
+        assert_eq!(fallback_script('か'), FallbackScript::Cjk);
+        assert_eq!(fallback_script('한'), FallbackScript::Cjk);
+        assert_eq!(fallback_script('😀'), FallbackScript::Emoji);
+        assert_eq!(fallback_script('→'), FallbackScript::Symbols);
+        assert_eq!(fallback_script('a'), FallbackScript::Other);
+    }
+
     #[test]
     fn shape_run_forms_ligatures_and_maps_clusters() {
         let mut font_system = FontSystem::new_with_locale_and_db("en-US".into(), Database::new());
@@ -3478,6 +3641,7 @@ mod tests {
             CellMetrics::from_font_size(font_size, 1.0),
             build_font_system(),
             &["JetBrains Mono".to_owned()],
+            &FontFallback::default(),
             true,
         );
         Some((device, queue, pass))
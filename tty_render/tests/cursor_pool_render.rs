@@ -11,7 +11,7 @@
 //! no GPU adapter is present, so a GPU-less CI stays green.
 
 use stoatty_render::{
-    gpu::{build_font_system, headless_device, FontConfig, Frame, Renderer, Scroll},
+    gpu::{build_font_system, headless_device, FontConfig, FontFallback, Frame, Renderer, Scroll},
     render::cell_size,
 };
 use stoatty_term::{
@@ -67,6 +67,7 @@ fn cursor_draws_over_pool_and_obeys_its_scissor() {
             size: font_size,
             scale_factor: 1.0,
             family: &["JetBrains Mono".to_owned()],
+            fallback: &FontFallback::default(),
             ligatures: true,
         },
         black,
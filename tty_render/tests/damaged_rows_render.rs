@@ -11,7 +11,7 @@
 //! is present, so a GPU-less CI stays green.
 
 use stoatty_render::{
-    gpu::{build_font_system, headless_device, FontConfig, Frame, Renderer, Scroll},
+    gpu::{build_font_system, headless_device, FontConfig, FontFallback, Frame, Renderer, Scroll},
     render::cell_size,
 };
 use stoatty_term::{
@@ -65,6 +65,7 @@ fn patched_rows_match_a_full_rebuild() {
             size: font_size,
             scale_factor: 1.0,
             family: &["JetBrains Mono".to_owned()],
+            fallback: &FontFallback::default(),
             ligatures: true,
         },
         black,
@@ -11,7 +11,7 @@
 //! when no GPU adapter is present so a GPU-less CI stays green.
 
 use stoatty_render::{
-    gpu::{build_font_system, headless_device, FontConfig, Frame, Renderer, Scroll},
+    gpu::{build_font_system, headless_device, FontConfig, FontFallback, Frame, Renderer, Scroll},
     render::cell_size,
 };
 use stoatty_term::{
@@ -68,6 +68,7 @@ fn grid_glyph_blends_over_the_framebuffer_not_an_assumed_bg() {
             size: font_size,
             scale_factor: 1.0,
             family: &["JetBrains Mono".to_owned()],
+            fallback: &FontFallback::default(),
             ligatures: true,
         },
         surface,
@@ -10,7 +10,7 @@
 
 use std::time::Duration;
 use stoatty_render::gpu::{
-    build_font_system, headless_device, FontConfig, Frame, Renderer, Scroll,
+    build_font_system, headless_device, FontConfig, FontFallback, Frame, Renderer, Scroll,
 };
 use stoatty_term::{
     grid::{Grid, Rgb},
@@ -59,6 +59,7 @@ fn timestamp_queries_yield_a_nonzero_gpu_duration() {
             size: 30,
             scale_factor: 1.0,
             family: &["JetBrains Mono".to_owned()],
+            fallback: &FontFallback::default(),
             ligatures: true,
         },
         Rgb::new(0, 0, 0),
@@ -8,7 +8,7 @@
 //! skipping when no GPU adapter is present so GPU-less CI stays green.
 
 use stoatty_render::gpu::{
-    build_font_system, headless_device, FontConfig, Frame, Renderer, Scroll,
+    build_font_system, headless_device, FontConfig, FontFallback, Frame, Renderer, Scroll,
 };
 use stoatty_term::{
     grid::{
@@ -57,6 +57,7 @@ fn builds_passes_and_draws_a_frame_off_screen() {
             size: 30,
             scale_factor: 1.0,
             family: &["JetBrains Mono".to_owned()],
+            fallback: &FontFallback::default(),
             ligatures: true,
         },
         Rgb::new(0, 0, 0),
@@ -10,7 +10,7 @@
 
 use std::time::Duration;
 use stoatty_render::{
-    gpu::{build_font_system, headless_device, FontConfig, Frame, Renderer, Scroll},
+    gpu::{build_font_system, headless_device, FontConfig, FontFallback, Frame, Renderer, Scroll},
     perf::{FrameSample, FrameStats, Percentiles},
 };
 use stoatty_term::{
@@ -67,6 +67,7 @@ fn hud_composites_over_a_frame_off_screen() {
             size: 30,
             scale_factor: 1.0,
             family: &["JetBrains Mono".to_owned()],
+            fallback: &FontFallback::default(),
             ligatures: true,
         },
         Rgb::new(0, 0, 0),
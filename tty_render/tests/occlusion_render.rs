@@ -10,7 +10,7 @@
 //! GPU adapter is present, so a GPU-less CI stays green.
 
 use stoatty_render::{
-    gpu::{build_font_system, headless_device, FontConfig, Frame, Renderer, Scroll},
+    gpu::{build_font_system, headless_device, FontConfig, FontFallback, Frame, Renderer, Scroll},
     render::cell_size,
 };
 use stoatty_term::{
@@ -68,6 +68,7 @@ fn a_box_occludes_the_bars_runs_and_icons_beneath_it() {
             size: font_size,
             scale_factor: 1.0,
             family: &["JetBrains Mono".to_owned()],
+            fallback: &FontFallback::default(),
             ligatures: true,
         },
         Rgb::new(0, 0, 0),
@@ -231,6 +232,7 @@ fn a_box_occludes_the_pool_composite_beneath_it() {
             size: font_size,
             scale_factor: 1.0,
             family: &["JetBrains Mono".to_owned()],
+            fallback: &FontFallback::default(),
             ligatures: true,
         },
         Rgb::new(0, 0, 0),
@@ -8,7 +8,7 @@
 //! present so a GPU-less CI stays green.
 
 use stoatty_render::{
-    gpu::{build_font_system, headless_device, FontConfig, Frame, Renderer, Scroll},
+    gpu::{build_font_system, headless_device, FontConfig, FontFallback, Frame, Renderer, Scroll},
     render::cell_size,
 };
 use stoatty_term::{
@@ -80,6 +80,7 @@ fn render_panel(build: impl FnOnce(usize, usize) -> Panel) -> Option<Rendered> {
             size: font_size,
             scale_factor: 1.0,
             family: &["JetBrains Mono".to_owned()],
+            fallback: &FontFallback::default(),
             ligatures: true,
         },
         surface,
@@ -36,10 +36,10 @@ use std::{
 };
 use stoatty_protocol::{
     command::{
-        self, BarCommand, BorderCommand, Command, HelloCommand, IconCommand, IdentReply,
-        LineLayoutCommand, LineSummary, MinimapCommand, MinimapLinesCommand, PanelCommand,
-        PoolRegionCommand, PopoverCommand, ScaleCommand, ScrollRegionCommand, TextRunCommand,
-        WindowOpenCommand,
+        self, AccessibilityCommand, BarCommand, BorderCommand, Command, CursorAnimation,
+        HelloCommand, IconCommand, IdentReply, LineLayoutCommand, LineSummary, MinimapCommand,
+        MinimapLinesCommand, PanelCommand, PoolRegionCommand, PopoverCommand, ScaleCommand,
+        ScrollRegionCommand, TextRunCommand, WindowOpenCommand,
     },
     frame::FrameScratch,
 };
@@ -349,6 +349,9 @@ pub enum TermEvent {
     /// A program identified itself with a [`Command::Hello`]. The host logs it so
     /// a remote editor is attributable to this terminal's log.
     Hello(HelloCommand),
+    /// A program reported the state assistive technology should announce. The
+    /// host hands it to its accessibility path.
+    Accessibility(AccessibilityCommand),
     /// A program asked to open an aux OS window as a second render target.
     WindowOpen(WindowOpenCommand),
     /// A program asked to close the aux OS window with this id.
@@ -361,6 +364,9 @@ pub enum TermEvent {
     /// A program reported that the terminal's config file changed on disk, so
     /// the host should re-read and re-apply it.
     ConfigReload,
+    /// A program chose how the cursor animates between cells, overriding the
+    /// host's configured motion.
+    CursorAnimation(CursorAnimation),
 }
 
 /// A snapshot of one smooth-scroll pool, for the render loop's per-pool ease.
@@ -684,7 +690,9 @@ impl Terminal {
                         | Command::WindowClose(_)
                         | Command::WindowFocus(_)
                         | Command::Hello(_)
+                        | Command::Accessibility(_)
                         | Command::ConfigReload
+                        | Command::CursorAnimation(_)
                 );
                 if routed || (self.fill.is_none() && self.capture.is_none()) {
                     self.apply_command(command);
@@ -990,6 +998,11 @@ impl Terminal {
                 }
                 self.pending_events.push(TermEvent::Hello(hello));
             },
+            // Announcements describe the program's state, not the grid, so
+            // they surface immediately for the host like Hello.
+            Command::Accessibility(state) => {
+                self.pending_events.push(TermEvent::Accessibility(state))
+            },
             // Window lifecycle commands surface as events for the host's
             // windowing path, which owns the actual aux OS windows.
             Command::WindowOpen(open) => self.pending_events.push(TermEvent::WindowOpen(open)),
@@ -1003,6 +1016,9 @@ impl Terminal {
             // update. Rereading a config has nothing to do with the frame being
             // composed, and delaying it would only defer the user's edit.
             Command::ConfigReload => self.pending_events.push(TermEvent::ConfigReload),
+            Command::CursorAnimation(animation) => self
+                .pending_events
+                .push(TermEvent::CursorAnimation(animation)),
         }
     }
 
@@ -1128,7 +1144,9 @@ impl Terminal {
             | Command::WindowClose(_)
             | Command::WindowFocus(_)
             | Command::Hello(_)
-            | Command::ConfigReload => {},
+            | Command::Accessibility(_)
+            | Command::ConfigReload
+            | Command::CursorAnimation(_) => {},
         }
     }
 
@@ -2893,8 +2911,8 @@ fn cube_channel(level: u8) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::{
-        ApcScanner, Cursor, CursorShape, OscNotifyScanner, TermEvent, Terminal, XtVersionScanner,
-        MAX_OSC_NOTIFY_BYTES, XTVERSION_REPLY,
+        ApcScanner, Cursor, CursorAnimation, CursorShape, OscNotifyScanner, TermEvent, Terminal,
+        XtVersionScanner, MAX_OSC_NOTIFY_BYTES, XTVERSION_REPLY,
     };
     use crate::{
         grid::{
@@ -2905,18 +2923,18 @@ mod tests {
         theme::Theme,
     };
     use stoatty_protocol::command::{
-        encode_bar, encode_border, encode_config_reload, encode_fill, encode_fill_end,
-        encode_hello, encode_icon, encode_ident_reply, encode_line_layout, encode_minimap,
-        encode_minimap_drop, encode_minimap_lines, encode_minimap_view, encode_panel,
-        encode_pool_cursor, encode_pool_drop, encode_pool_region, encode_popover,
-        encode_reposition, encode_reset, encode_scale, encode_scroll, encode_scroll_region,
-        encode_text_run, encode_window_open, BarCommand, BorderCommand,
-        BorderStyle as ProtoBorderStyle, FillCommand, HelloCommand, IconCommand,
-        IconKind as ProtoIconKind, IdentReply, LineLayoutCommand, MinimapCommand,
-        MinimapDropCommand, MinimapLinesCommand, MinimapRun, MinimapViewCommand, PanelCommand,
-        PanelShadow as ProtoPanelShadow, PoolCursorCommand, PoolDropCommand, PoolRegionCommand,
-        PopoverCommand, RepositionCommand, ScaleCommand, ScrollCommand, ScrollRegionCommand,
-        TextRunCommand, WindowOpenCommand,
+        encode_accessibility, encode_bar, encode_border, encode_config_reload,
+        encode_cursor_animation, encode_fill, encode_fill_end, encode_hello, encode_icon,
+        encode_ident_reply, encode_line_layout, encode_minimap, encode_minimap_drop,
+        encode_minimap_lines, encode_minimap_view, encode_panel, encode_pool_cursor,
+        encode_pool_drop, encode_pool_region, encode_popover, encode_reposition, encode_reset,
+        encode_scale, encode_scroll, encode_scroll_region, encode_text_run, encode_window_open,
+        AccessibilityCommand, BarCommand, BorderCommand, BorderStyle as ProtoBorderStyle,
+        FillCommand, HelloCommand, IconCommand, IconKind as ProtoIconKind, IdentReply,
+        LineLayoutCommand, MinimapCommand, MinimapDropCommand, MinimapLinesCommand, MinimapRun,
+        MinimapViewCommand, PanelCommand, PanelShadow as ProtoPanelShadow, PoolCursorCommand,
+        PoolDropCommand, PoolRegionCommand, PopoverCommand, RepositionCommand, ScaleCommand,
+        ScrollCommand, ScrollRegionCommand, TextRunCommand, WindowOpenCommand,
     };
 
     fn project(rows: usize, cols: usize, bytes: &[u8]) -> (Grid, Cursor) {
@@ -3252,6 +3270,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accessibility_surfaces_as_an_event() {
+        let mut terminal = Terminal::new(4, 8, Theme::default());
+
+        let state = AccessibilityCommand {
+            line: 3,
+            column: 0,
+            mode: "normal".to_string(),
+            modal: None,
+            line_text: "fn main() {}".to_string(),
+        };
+        terminal.advance(&encode_accessibility(&state));
+
+        assert_eq!(
+            terminal.take_events(),
+            vec![TermEvent::Accessibility(state)]
+        );
+    }
+
     #[test]
     fn surfaces_title_event() {
         let mut terminal = Terminal::new(4, 8, Theme::default());
@@ -4677,6 +4714,18 @@ mod tests {
         assert_eq!(terminal.take_events(), vec![TermEvent::ConfigReload]);
     }
 
+    #[test]
+    fn cursor_animation_command_surfaces_as_a_term_event() {
+        let mut terminal = Terminal::new(4, 8, Theme::default());
+
+        terminal.advance(&encode_cursor_animation(CursorAnimation::Warp));
+
+        assert_eq!(
+            terminal.take_events(),
+            vec![TermEvent::CursorAnimation(CursorAnimation::Warp)]
+        );
+    }
+
     #[test]
     fn config_reload_survives_an_active_fill() {
         let mut terminal = Terminal::new(4, 8, Theme::default());